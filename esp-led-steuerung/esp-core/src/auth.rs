@@ -0,0 +1,31 @@
+//! Token-Prüfung für Client-Authentifizierung (WebSocket `auth`, HTTP
+//! `Authorization: Bearer <token>`)
+//!
+//! Der Vergleich läuft immer über die volle Länge des erwarteten Tokens,
+//! damit die Antwortzeit nicht verrät, wie viele Zeichen bereits stimmen.
+
+/// Prüft ein vom Client gesendetes Token gegen das konfigurierte
+pub fn token_matches(expected: &str, given: &str) -> bool {
+    let expected = expected.as_bytes();
+    let given = given.as_bytes();
+    let mut diff = u8::from(expected.len() != given.len());
+    for (i, &byte) in expected.iter().enumerate() {
+        // Bei abweichender Länge gegen 0 vergleichen (Laufzeit bleibt gleich)
+        diff |= byte ^ given.get(i).copied().unwrap_or(0);
+    }
+    diff == 0
+}
+
+/// Prüft einen HTTP-`Authorization`-Header der Form `Bearer <token>`
+///
+/// Das Schema ist unabhängig von Groß-/Kleinschreibung, das Token nicht.
+pub fn bearer_matches(expected: &str, header: &[u8]) -> bool {
+    const SCHEME: &[u8] = b"bearer ";
+    let Some((scheme, token)) = header.split_at_checked(SCHEME.len()) else {
+        return false;
+    };
+    let Ok(token) = core::str::from_utf8(token) else {
+        return false;
+    };
+    scheme.eq_ignore_ascii_case(SCHEME) && token_matches(expected, token.trim())
+}
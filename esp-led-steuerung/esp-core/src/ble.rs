@@ -0,0 +1,69 @@
+//! BLE Advertisement Payload
+//!
+//! Kodiert den LED-Zustand in BLE Manufacturer Specific Data,
+//! damit passive Scanner (Handy-Widget, anderer ESP32) den Zustand
+//! ohne Verbindungsaufbau mitlesen können.
+//!
+//! # Format (5 Bytes)
+//!
+//! | Byte | Inhalt                         |
+//! |------|--------------------------------|
+//! | 0    | Format-Version (`1`)           |
+//! | 1    | Flags (Bit 0: Auto-Modus)      |
+//! | 2    | Rot                            |
+//! | 3    | Grün                           |
+//! | 4    | Blau                           |
+
+use rgb::RGB8;
+
+use crate::types::LedColorMessage;
+
+/// Bluetooth SIG Company Identifier für die Manufacturer Data
+///
+/// 0xFFFF ist laut Bluetooth SIG für Tests/interne Nutzung reserviert.
+pub const BLE_COMPANY_ID: u16 = 0xFFFF;
+
+/// Version des Payload-Formats
+pub const BLE_PAYLOAD_VERSION: u8 = 1;
+
+/// Länge des Payloads in Bytes (ohne Company Identifier)
+pub const BLE_PAYLOAD_LEN: usize = 5;
+
+/// Flag-Bit: LED läuft im Auto-Modus
+const FLAG_AUTO_MODE: u8 = 0x01;
+
+/// Dekodierter LED-Zustand aus einem BLE Advertisement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BleLedState {
+    pub color: RGB8,
+    pub is_auto_mode: bool,
+}
+
+/// Kodiert eine LedColorMessage als Manufacturer Data Payload
+pub fn encode_ble_payload(msg: &LedColorMessage) -> [u8; BLE_PAYLOAD_LEN] {
+    let flags = if msg.is_auto_mode { FLAG_AUTO_MODE } else { 0 };
+    [
+        BLE_PAYLOAD_VERSION,
+        flags,
+        msg.color.r,
+        msg.color.g,
+        msg.color.b,
+    ]
+}
+
+/// Dekodiert einen Manufacturer Data Payload
+///
+/// Gibt `None` zurück bei falscher Länge oder unbekannter Format-Version.
+pub fn decode_ble_payload(payload: &[u8]) -> Option<BleLedState> {
+    match payload {
+        [BLE_PAYLOAD_VERSION, flags, r, g, b] => Some(BleLedState {
+            color: RGB8 {
+                r: *r,
+                g: *g,
+                b: *b,
+            },
+            is_auto_mode: flags & FLAG_AUTO_MODE != 0,
+        }),
+        _ => None,
+    }
+}
@@ -0,0 +1,89 @@
+//! Priorisierte MQTT-Broker-Liste mit Umschaltung bei Verbindungsfehlern
+//!
+//! Index 0 ist der primäre Broker (`MQTT_BROKER`), danach folgen die
+//! Fallbacks aus einer kommagetrennten Liste `host[:port],host[:port]`.
+//! [`BrokerRotation`] zählt aufeinanderfolgende Fehlversuche und wechselt
+//! nach einer festen Anzahl zum nächsten Broker; nach dem letzten folgt
+//! wieder der primäre. Ein erfolgreicher Connect setzt den Zähler zurück,
+//! der aktive Broker bleibt bis zum nächsten Wechsel bestehen.
+
+/// Ein Eintrag der Broker-Liste
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrokerAddress<'a> {
+    pub host: &'a str,
+    /// `None` = Standard-Port
+    pub port: Option<u16>,
+}
+
+impl<'a> BrokerAddress<'a> {
+    /// Parst `host` oder `host:port`, `None` bei leerem Host oder ungültigem Port
+    pub fn parse(entry: &'a str) -> Option<Self> {
+        let entry = entry.trim();
+        let (host, port) = match entry.rsplit_once(':') {
+            Some((host, port)) => (host.trim(), Some(port.trim().parse().ok()?)),
+            None => (entry, None),
+        };
+        if host.is_empty() || port == Some(0) {
+            return None;
+        }
+        Some(Self { host, port })
+    }
+}
+
+/// Gültige Einträge einer Fallback-Liste (leere und ungültige werden übersprungen)
+pub fn fallback_brokers(list: &str) -> impl Iterator<Item = BrokerAddress<'_>> {
+    list.split(',').filter_map(BrokerAddress::parse)
+}
+
+/// Auswahl des aktiven Brokers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrokerRotation {
+    index: usize,
+    count: usize,
+    failures: u8,
+    switch_after: u8,
+}
+
+impl BrokerRotation {
+    /// `count` Broker (mindestens 1), Wechsel nach `switch_after` Fehlversuchen
+    pub const fn new(count: usize, switch_after: u8) -> Self {
+        Self {
+            index: 0,
+            count: if count == 0 { 1 } else { count },
+            failures: 0,
+            switch_after: if switch_after == 0 { 1 } else { switch_after },
+        }
+    }
+
+    /// Index des aktiven Brokers
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Aufeinanderfolgende Fehlversuche beim aktiven Broker
+    pub fn failures(&self) -> u8 {
+        self.failures
+    }
+
+    /// Broker hat CONNECT bestätigt
+    pub fn connected(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Verbindungsversuch fehlgeschlagen → neuer Index bei Wechsel
+    ///
+    /// Mit nur einem Broker wird nie gewechselt.
+    pub fn failed(&mut self) -> Option<usize> {
+        self.failures = self.failures.saturating_add(1);
+        if self.count < 2 || self.failures < self.switch_after {
+            return None;
+        }
+        self.failures = 0;
+        self.index = (self.index + 1) % self.count;
+        Some(self.index)
+    }
+}
@@ -0,0 +1,115 @@
+//! Weißabgleich: Faktoren pro Farbkanal
+//!
+//! LEDs aus verschiedenen Chargen zeigen dieselbe Farbe unterschiedlich an.
+//! Die Kalibrierung skaliert jeden Kanal vor der Ausgabe (255 = unverändert),
+//! damit z.B. Weiß auf allen Geräten gleich aussieht. Das Testmuster
+//! ([`CalibrationPattern`]) hilft beim Einstellen.
+
+use rgb::RGB8;
+
+use crate::color::scale_brightness;
+
+/// Serialisierte Größe einer Kalibrierung in Bytes
+pub const CALIBRATION_LEN: usize = 3;
+
+/// Anzeigedauer einer Testfarbe im Kalibriermodus
+pub const CALIBRATION_STEP_MS: u32 = 3000;
+
+/// Testfarben des Kalibriermodus (volle Helligkeit, wird skaliert)
+///
+/// Weiß zuerst, da es am empfindlichsten auf Abweichungen reagiert.
+pub const CALIBRATION_COLORS: [RGB8; 5] = [
+    RGB8::new(255, 255, 255),
+    RGB8::new(255, 0, 0),
+    RGB8::new(0, 255, 0),
+    RGB8::new(0, 0, 255),
+    RGB8::new(128, 128, 128),
+];
+
+/// Faktoren pro Kanal (0-255, 255 = unverändert)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+impl Calibration {
+    /// Keine Korrektur
+    pub const NEUTRAL: Self = Self::new(255, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Wendet die Faktoren auf eine Farbe an
+    pub fn apply(&self, color: RGB8) -> RGB8 {
+        let scale = |c: u8, factor: u8| ((c as u16 * factor as u16 + 127) / 255) as u8;
+        RGB8::new(
+            scale(color.r, self.r),
+            scale(color.g, self.g),
+            scale(color.b, self.b),
+        )
+    }
+
+    /// Serialisiert die Kalibrierung für die Flash-Persistenz (R, G, B)
+    pub fn to_bytes(&self) -> [u8; CALIBRATION_LEN] {
+        [self.r, self.g, self.b]
+    }
+
+    /// Deserialisiert eine Kalibrierung, `None` bei falscher Länge
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            &[r, g, b] => Some(Self::new(r, g, b)),
+            _ => None,
+        }
+    }
+}
+
+/// Faktoren als Farbe, z.B. für JSON `{"r":255,"g":235,"b":210}`
+impl From<Calibration> for RGB8 {
+    fn from(calibration: Calibration) -> Self {
+        RGB8::new(calibration.r, calibration.g, calibration.b)
+    }
+}
+
+impl From<RGB8> for Calibration {
+    fn from(rgb: RGB8) -> Self {
+        Calibration::new(rgb.r, rgb.g, rgb.b)
+    }
+}
+
+/// Kalibriermodus: zeigt die Testfarben nacheinander in Endlosschleife
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalibrationPattern {
+    pub start_ms: u64,
+    /// Helligkeit der Testfarben (255 = volle Helligkeit)
+    pub brightness: u8,
+}
+
+impl CalibrationPattern {
+    /// Startet das Testmuster ab `start_ms`
+    pub const fn new(start_ms: u64, brightness: u8) -> Self {
+        Self {
+            start_ms,
+            brightness,
+        }
+    }
+
+    /// Index der Testfarbe zum Zeitpunkt `now_ms`
+    pub fn step_at(&self, now_ms: u64) -> usize {
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        (elapsed / CALIBRATION_STEP_MS as u64 % CALIBRATION_COLORS.len() as u64) as usize
+    }
+
+    /// Farbe zum Zeitpunkt `now_ms`
+    pub fn color_at(&self, now_ms: u64) -> RGB8 {
+        scale_brightness(CALIBRATION_COLORS[self.step_at(now_ms)], self.brightness)
+    }
+}
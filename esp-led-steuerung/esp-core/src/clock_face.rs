@@ -0,0 +1,141 @@
+//! Uhrzeit-Anzeige auf Matrix oder Strip
+//!
+//! Auf ausreichend großen Matrizen wird die Uhrzeit mit der Schrift aus
+//! [`crate::text`] gezeigt (`HH:MM` in einer Zeile, sonst Stunden über
+//! Minuten), der Doppelpunkt blinkt im Sekundentakt. Passt beides nicht,
+//! zeigt der Strip eine Binäruhr: Stunden-, Minuten- und Sekunden-Bits
+//! nacheinander, jede Gruppe in ihrer eigenen Farbe.
+
+use rgb::RGB8;
+
+use crate::matrix::MatrixLayout;
+use crate::text::{FONT_HEIGHT, GLYPH_ADVANCE, draw_text};
+use crate::time::LocalTime;
+
+/// Bits der Minuten und Sekunden in der Binäruhr (0-59)
+const MINUTE_BITS: u32 = 6;
+
+/// 12- oder 24-Stunden-Anzeige
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ClockFormat {
+    #[default]
+    H24,
+    /// 1-12 ohne führende Null
+    H12,
+}
+
+impl ClockFormat {
+    /// Anzuzeigende Stunde für eine Stunde 0-23
+    pub fn display_hour(self, hour: u8) -> u8 {
+        match self {
+            ClockFormat::H24 => hour,
+            ClockFormat::H12 => match hour % 12 {
+                0 => 12,
+                h => h,
+            },
+        }
+    }
+
+    /// Anzahl der Stunden-Bits in der Binäruhr
+    pub fn hour_bits(self) -> u32 {
+        match self {
+            ClockFormat::H24 => 5,
+            ClockFormat::H12 => 4,
+        }
+    }
+}
+
+/// Farben und Format der Uhrzeit-Anzeige
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockFace {
+    pub hour_color: RGB8,
+    pub minute_color: RGB8,
+    /// Doppelpunkt bzw. Sekunden-Bits der Binäruhr
+    pub second_color: RGB8,
+    pub format: ClockFormat,
+}
+
+impl ClockFace {
+    /// Gleiche Farben mit anderem Format
+    pub const fn with_format(self, format: ClockFormat) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Stunden und Minuten als Text, z.B. `07:05` bzw. `7:05` im 12-Stunden-Format
+    ///
+    /// Liefert den Text und die Länge des Stunden-Teils (in Zeichen).
+    pub fn time_text<'a>(&self, time: &LocalTime, buf: &'a mut [u8; 5]) -> (&'a str, usize) {
+        let hour = self.format.display_hour(time.hour);
+        let digits = |value: u8| [b'0' + value / 10, b'0' + value % 10];
+        let hour_len = match self.format {
+            ClockFormat::H12 if hour < 10 => 1,
+            _ => 2,
+        };
+        buf[..hour_len].copy_from_slice(&digits(hour)[2 - hour_len..]);
+        buf[hour_len] = b':';
+        buf[hour_len + 1..hour_len + 3].copy_from_slice(&digits(time.minute));
+        let text = core::str::from_utf8(&buf[..hour_len + 3]).unwrap_or("");
+        (text, hour_len)
+    }
+
+    /// Füllt `pixels` mit der Anzeige für `time`
+    pub fn render(&self, time: &LocalTime, layout: MatrixLayout, pixels: &mut [RGB8]) {
+        pixels.fill(RGB8::default());
+        let mut buf = [0; 5];
+        let (text, hour_len) = self.time_text(time, &mut buf);
+        let width = |chars: usize| (chars * GLYPH_ADVANCE - 1) as isize;
+        let (columns, rows) = (layout.width as isize, layout.height as isize);
+        let font_height = FONT_HEIGHT as isize;
+
+        if rows >= font_height && columns >= width(text.len()) {
+            // Eine Zeile, Doppelpunkt blinkt im Sekundentakt
+            let colon = time.second.is_multiple_of(2);
+            let color = |i: usize| match i {
+                i if i < hour_len => self.hour_color,
+                i if i == hour_len && colon => self.second_color,
+                i if i == hour_len => RGB8::default(),
+                _ => self.minute_color,
+            };
+            let origin = ((columns - width(text.len())) / 2, (rows - font_height) / 2);
+            draw_text(text, origin, color, layout, pixels);
+        } else if rows > 2 * font_height && columns >= width(2) {
+            // Stunden über Minuten
+            let (hours, minutes) = (&text[..hour_len], &text[hour_len + 1..]);
+            let top = (rows - 2 * font_height - 1) / 2;
+            let left = |part: &str| (columns - width(part.len())) / 2;
+            let hour_color = |_| self.hour_color;
+            let minute_color = |_| self.minute_color;
+            draw_text(hours, (left(hours), top), hour_color, layout, pixels);
+            let origin = (left(minutes), top + font_height + 1);
+            draw_text(minutes, origin, minute_color, layout, pixels);
+        } else {
+            self.render_binary(time, pixels);
+        }
+    }
+
+    /// Binäruhr: Stunden-, Minuten-, Sekunden-Bits (höchstes Bit zuerst)
+    ///
+    /// Gesetzte Bits leuchten in der Farbe ihrer Gruppe, die übrigen sind aus.
+    pub fn render_binary(&self, time: &LocalTime, pixels: &mut [RGB8]) {
+        pixels.fill(RGB8::default());
+        let groups = [
+            (
+                self.format.display_hour(time.hour),
+                self.format.hour_bits(),
+                self.hour_color,
+            ),
+            (time.minute, MINUTE_BITS, self.minute_color),
+            (time.second, MINUTE_BITS, self.second_color),
+        ];
+        let bits = groups.into_iter().flat_map(|(value, bits, color)| {
+            (0..bits)
+                .rev()
+                .map(move |bit| (value >> bit & 1 == 1).then_some(color))
+        });
+        for (pixel, lit) in pixels.iter_mut().zip(bits) {
+            *pixel = lit.unwrap_or_default();
+        }
+    }
+}
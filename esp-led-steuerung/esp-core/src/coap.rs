@@ -0,0 +1,436 @@
+//! CoAP Nachrichten-Format (RFC 7252) mit Observe-Option (RFC 7641)
+//!
+//! Minimaler Codec für den CoAP-Server der Firmware. Unterstützt werden nur
+//! die Optionen, die für LED-Zustand und -Steuerung gebraucht werden:
+//! `Observe`, `Uri-Path` und `Content-Format`. Unbekannte elektive Optionen
+//! werden beim Dekodieren übersprungen, unbekannte kritische abgelehnt.
+//!
+//! # Header (4 Bytes)
+//!
+//! | Bits  | Inhalt                                  |
+//! |-------|-----------------------------------------|
+//! | 2     | Version (`1`)                           |
+//! | 2     | Typ (CON, NON, ACK, RST)                |
+//! | 4     | Token-Länge (0-8)                       |
+//! | 8     | Code (`class.detail`, z.B. 2.05 = 0x45) |
+//! | 16    | Message-ID                              |
+
+/// Standard-UDP-Port für CoAP
+pub const COAP_PORT: u16 = 5683;
+
+/// Maximale Token-Länge laut RFC 7252
+pub const MAX_TOKEN_LEN: usize = 8;
+
+/// Payload-Marker zwischen Optionen und Payload
+const PAYLOAD_MARKER: u8 = 0xFF;
+
+/// Protokoll-Version
+const VERSION: u8 = 1;
+
+// Options-Nummern
+const OPTION_OBSERVE: u16 = 6;
+const OPTION_URI_PATH: u16 = 11;
+const OPTION_CONTENT_FORMAT: u16 = 12;
+
+/// Request- und Response-Codes (`class << 5 | detail`)
+pub mod code {
+    pub const EMPTY: u8 = 0x00;
+    pub const GET: u8 = 0x01;
+    pub const POST: u8 = 0x02;
+    pub const PUT: u8 = 0x03;
+    /// 2.04 Changed
+    pub const CHANGED: u8 = 0x44;
+    /// 2.05 Content
+    pub const CONTENT: u8 = 0x45;
+    /// 4.00 Bad Request
+    pub const BAD_REQUEST: u8 = 0x80;
+    /// 4.02 Bad Option
+    pub const BAD_OPTION: u8 = 0x82;
+    /// 4.04 Not Found
+    pub const NOT_FOUND: u8 = 0x84;
+    /// 4.05 Method Not Allowed
+    pub const METHOD_NOT_ALLOWED: u8 = 0x85;
+    /// 5.03 Service Unavailable
+    pub const SERVICE_UNAVAILABLE: u8 = 0xA3;
+}
+
+/// Content-Format Kennungen
+pub mod content_format {
+    pub const TEXT_PLAIN: u16 = 0;
+    pub const LINK_FORMAT: u16 = 40;
+    pub const JSON: u16 = 50;
+}
+
+/// Nachrichten-Typ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoapType {
+    /// Confirmable: Empfänger muss mit ACK antworten
+    Confirmable = 0,
+    /// Non-confirmable: keine Bestätigung
+    NonConfirmable = 1,
+    /// Acknowledgement (Piggybacked Response)
+    Acknowledgement = 2,
+    /// Reset: Empfänger kann Nachricht nicht verarbeiten
+    Reset = 3,
+}
+
+impl CoapType {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0x03 {
+            0 => CoapType::Confirmable,
+            1 => CoapType::NonConfirmable,
+            2 => CoapType::Acknowledgement,
+            _ => CoapType::Reset,
+        }
+    }
+}
+
+/// Fehler beim Dekodieren oder Kodieren
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoapError {
+    /// Nachricht zu kurz oder abgeschnitten
+    Truncated,
+    /// Falsche Protokoll-Version
+    UnsupportedVersion,
+    /// Ungültiges Options-Format oder Token-Länge
+    MalformedOption,
+    /// Unbekannte kritische Option (muss mit 4.02 beantwortet werden)
+    UnknownCriticalOption,
+    /// Ausgabe-Buffer zu klein
+    BufferTooSmall,
+}
+
+/// Dekodierte CoAP-Nachricht (Request oder Empty Message)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoapRequest<'a> {
+    pub msg_type: CoapType,
+    pub code: u8,
+    pub message_id: u16,
+    pub token: &'a [u8],
+    /// Observe-Option (0 = registrieren, 1 = abmelden)
+    pub observe: Option<u32>,
+    pub content_format: Option<u16>,
+    pub payload: &'a [u8],
+    /// Rohe Options-Bytes (für `uri_path()`)
+    options: &'a [u8],
+}
+
+impl<'a> CoapRequest<'a> {
+    /// Iterator über die Uri-Path Segmente (`/a/b` → `"a"`, `"b"`)
+    ///
+    /// Segmente mit ungültigem UTF-8 werden als leerer String geliefert.
+    pub fn uri_path(&self) -> impl Iterator<Item = &'a str> + 'a {
+        OptionIter {
+            data: self.options,
+            number: 0,
+        }
+        .filter_map(|option| option.ok())
+        .filter(|(number, _)| *number == OPTION_URI_PATH)
+        .map(|(_, value)| core::str::from_utf8(value).unwrap_or(""))
+    }
+
+    /// Prüft ob der Uri-Path exakt den Segmenten entspricht
+    pub fn path_is(&self, segments: &[&str]) -> bool {
+        self.uri_path().eq(segments.iter().copied())
+    }
+}
+
+/// Iterator über (Options-Nummer, Wert) mit Delta-Dekodierung
+struct OptionIter<'a> {
+    data: &'a [u8],
+    number: u16,
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+    type Item = Result<(u16, &'a [u8]), CoapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, rest) = self.data.split_first()?;
+        let result = (|| {
+            let mut rest = rest;
+            let delta = read_extended(first >> 4, &mut rest)?;
+            let len = read_extended(first & 0x0F, &mut rest)? as usize;
+            if rest.len() < len {
+                return Err(CoapError::Truncated);
+            }
+            let (value, remaining) = rest.split_at(len);
+            self.number = self
+                .number
+                .checked_add(delta)
+                .ok_or(CoapError::MalformedOption)?;
+            self.data = remaining;
+            Ok((self.number, value))
+        })();
+        if result.is_err() {
+            self.data = &[];
+        }
+        Some(result)
+    }
+}
+
+/// Liest einen erweiterten Delta-/Längenwert (Nibble 13/14 = 1/2 Zusatzbytes)
+fn read_extended(nibble: u8, data: &mut &[u8]) -> Result<u16, CoapError> {
+    match nibble {
+        0..=12 => Ok(nibble as u16),
+        13 => {
+            let (&byte, rest) = data.split_first().ok_or(CoapError::Truncated)?;
+            *data = rest;
+            Ok(byte as u16 + 13)
+        }
+        14 => {
+            let (&[hi, lo], rest) = data.split_first_chunk::<2>().ok_or(CoapError::Truncated)?;
+            *data = rest;
+            u16::from_be_bytes([hi, lo])
+                .checked_add(269)
+                .ok_or(CoapError::MalformedOption)
+        }
+        _ => Err(CoapError::MalformedOption),
+    }
+}
+
+/// Dekodiert eine unsigned-Integer Option (Big Endian, 0-4 Bytes)
+fn decode_uint(value: &[u8]) -> Result<u32, CoapError> {
+    if value.len() > 4 {
+        return Err(CoapError::MalformedOption);
+    }
+    Ok(value.iter().fold(0u32, |acc, &b| acc << 8 | b as u32))
+}
+
+/// Dekodiert eine CoAP-Nachricht
+pub fn decode_coap_message(packet: &[u8]) -> Result<CoapRequest<'_>, CoapError> {
+    let (&[first, code, id_hi, id_lo], rest) = packet
+        .split_first_chunk::<4>()
+        .ok_or(CoapError::Truncated)?;
+    if first >> 6 != VERSION {
+        return Err(CoapError::UnsupportedVersion);
+    }
+    let token_len = (first & 0x0F) as usize;
+    if token_len > MAX_TOKEN_LEN {
+        return Err(CoapError::MalformedOption);
+    }
+    if rest.len() < token_len {
+        return Err(CoapError::Truncated);
+    }
+    let (token, rest) = rest.split_at(token_len);
+
+    // Options-Bereich endet am Payload-Marker (oder am Paketende)
+    let mut request = CoapRequest {
+        msg_type: CoapType::from_bits(first >> 4),
+        code,
+        message_id: u16::from_be_bytes([id_hi, id_lo]),
+        token,
+        observe: None,
+        content_format: None,
+        payload: &[],
+        options: rest,
+    };
+    let mut options = OptionIter {
+        data: rest,
+        number: 0,
+    };
+    loop {
+        if let Some((&PAYLOAD_MARKER, payload)) = options.data.split_first() {
+            if payload.is_empty() {
+                return Err(CoapError::Truncated);
+            }
+            request.options = &rest[..rest.len() - options.data.len()];
+            request.payload = payload;
+            break;
+        }
+        let Some(option) = options.next() else {
+            break;
+        };
+        let (number, value) = option?;
+        match number {
+            OPTION_OBSERVE => request.observe = Some(decode_uint(value)?),
+            OPTION_CONTENT_FORMAT => request.content_format = Some(decode_uint(value)? as u16),
+            OPTION_URI_PATH => {}
+            // Ungerade Nummern sind kritisch (RFC 7252, Abschnitt 5.4.1)
+            n if n % 2 == 1 => return Err(CoapError::UnknownCriticalOption),
+            _ => {}
+        }
+    }
+    Ok(request)
+}
+
+/// CoAP-Antwort oder Benachrichtigung zum Kodieren
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoapResponse<'a> {
+    pub msg_type: CoapType,
+    pub code: u8,
+    pub message_id: u16,
+    pub token: &'a [u8],
+    /// Observe-Sequenznummer (nur bei Benachrichtigungen / Registrierung)
+    pub observe: Option<u32>,
+    pub content_format: Option<u16>,
+    pub payload: &'a [u8],
+}
+
+impl CoapResponse<'_> {
+    /// Kodiert die Nachricht in `buf`, gibt die Länge zurück
+    pub fn encode(&self, buf: &mut [u8]) -> Result<usize, CoapError> {
+        if self.token.len() > MAX_TOKEN_LEN {
+            return Err(CoapError::MalformedOption);
+        }
+        let mut writer = Writer { buf, pos: 0 };
+        let [id_hi, id_lo] = self.message_id.to_be_bytes();
+        writer.put(&[
+            VERSION << 6 | (self.msg_type as u8) << 4 | self.token.len() as u8,
+            self.code,
+            id_hi,
+            id_lo,
+        ])?;
+        writer.put(self.token)?;
+
+        // Optionen in aufsteigender Reihenfolge (Delta-Kodierung)
+        let mut last = 0;
+        if let Some(observe) = self.observe {
+            writer.put_uint_option(&mut last, OPTION_OBSERVE, observe & 0x00FF_FFFF)?;
+        }
+        if let Some(format) = self.content_format {
+            writer.put_uint_option(&mut last, OPTION_CONTENT_FORMAT, format as u32)?;
+        }
+
+        if !self.payload.is_empty() {
+            writer.put(&[PAYLOAD_MARKER])?;
+            writer.put(self.payload)?;
+        }
+        Ok(writer.pos)
+    }
+}
+
+/// Einfacher Schreib-Cursor über einen Byte-Buffer
+struct Writer<'b> {
+    buf: &'b mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn put(&mut self, bytes: &[u8]) -> Result<(), CoapError> {
+        let end = self.pos + bytes.len();
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or(CoapError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+
+    /// Schreibt eine uint-Option (Nummern < 13, Werte minimal kodiert)
+    fn put_uint_option(
+        &mut self,
+        last: &mut u16,
+        number: u16,
+        value: u32,
+    ) -> Result<(), CoapError> {
+        let bytes = value.to_be_bytes();
+        let skip = bytes.iter().take_while(|&&b| b == 0).count();
+        let value = &bytes[skip..];
+        let delta = (number - *last) as u8;
+        *last = number;
+        self.put(&[delta << 4 | value.len() as u8])?;
+        self.put(value)
+    }
+}
+
+// ============================================================================
+// Observe (RFC 7641)
+// ============================================================================
+
+/// Registrierter Observer einer Ressource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Observer<E> {
+    pub endpoint: E,
+    token: [u8; MAX_TOKEN_LEN],
+    token_len: u8,
+}
+
+impl<E> Observer<E> {
+    /// Token der Registrierung (wird in jeder Benachrichtigung wiederholt)
+    pub fn token(&self) -> &[u8] {
+        &self.token[..self.token_len as usize]
+    }
+}
+
+/// Feste Liste von Observern, `E` ist die Adresse des Clients
+///
+/// Pro Endpoint gibt es höchstens eine Registrierung; eine erneute
+/// Registrierung ersetzt das Token. Ist die Liste voll, wird die
+/// Registrierung abgelehnt (Client erhält eine normale Antwort ohne Observe).
+#[derive(Debug)]
+pub struct Observers<E, const N: usize> {
+    slots: [Option<Observer<E>>; N],
+    sequence: u32,
+}
+
+impl<E: Copy + PartialEq, const N: usize> Default for Observers<E, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Copy + PartialEq, const N: usize> Observers<E, N> {
+    /// Erstellt eine leere Liste
+    pub const fn new() -> Self {
+        Self {
+            slots: [None; N],
+            sequence: 0,
+        }
+    }
+
+    /// Registriert einen Observer, `false` wenn die Liste voll ist
+    pub fn register(&mut self, endpoint: E, token: &[u8]) -> bool {
+        if token.len() > MAX_TOKEN_LEN {
+            return false;
+        }
+        let index = self
+            .slots
+            .iter()
+            .position(|slot| slot.is_some_and(|o| o.endpoint == endpoint))
+            .or_else(|| self.slots.iter().position(Option::is_none));
+        let Some(index) = index else {
+            return false;
+        };
+        let mut stored = [0u8; MAX_TOKEN_LEN];
+        stored[..token.len()].copy_from_slice(token);
+        self.slots[index] = Some(Observer {
+            endpoint,
+            token: stored,
+            token_len: token.len() as u8,
+        });
+        true
+    }
+
+    /// Meldet einen Observer ab (Observe=1 oder RST), `true` wenn gefunden
+    pub fn remove(&mut self, endpoint: E) -> bool {
+        let mut found = false;
+        for slot in &mut self.slots {
+            if slot.is_some_and(|o| o.endpoint == endpoint) {
+                *slot = None;
+                found = true;
+            }
+        }
+        found
+    }
+
+    /// Alle aktiven Observer
+    pub fn iter(&self) -> impl Iterator<Item = &Observer<E>> {
+        self.slots.iter().flatten()
+    }
+
+    /// Anzahl aktiver Observer
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Prüft ob keine Observer registriert sind
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Nächste Observe-Sequenznummer (24 Bit, monoton steigend)
+    pub fn next_sequence(&mut self) -> u32 {
+        self.sequence = (self.sequence + 1) & 0x00FF_FFFF;
+        self.sequence
+    }
+}
@@ -0,0 +1,222 @@
+//! Farb-Hilfsfunktionen
+//!
+//! Farbtemperatur (Kelvin → RGB), Helligkeits-Skalierung, wahrnehmungs-
+//! gerechte Helligkeitskurve, HSV-Umrechnung mit Farbton-Rotation und
+//! lineare Interpolation. Nur Integer-Arithmetik (kein libm, kein
+//! Float-Support nötig).
+
+use rgb::RGB8;
+
+/// Kleinste unterstützte Farbtemperatur in Kelvin (Kerzenlicht)
+pub const MIN_KELVIN: u16 = 1000;
+
+/// Größte unterstützte Farbtemperatur in Kelvin (Tageslicht)
+pub const MAX_KELVIN: u16 = 6500;
+
+/// Schrittweite der Stützstellen-Tabelle in Kelvin
+const KELVIN_STEP: u16 = 500;
+
+/// Stützstellen für Farbtemperaturen von 1000 K bis 6500 K (500-K-Schritte)
+///
+/// Werte nach der Näherung von Tanner Helland (Schwarzkörper-Strahlung).
+const KELVIN_TABLE: [RGB8; 12] = [
+    RGB8::new(255, 56, 0),    // 1000 K
+    RGB8::new(255, 109, 0),   // 1500 K
+    RGB8::new(255, 137, 18),  // 2000 K
+    RGB8::new(255, 161, 72),  // 2500 K
+    RGB8::new(255, 180, 107), // 3000 K
+    RGB8::new(255, 196, 137), // 3500 K
+    RGB8::new(255, 209, 163), // 4000 K
+    RGB8::new(255, 219, 186), // 4500 K
+    RGB8::new(255, 228, 206), // 5000 K
+    RGB8::new(255, 236, 224), // 5500 K
+    RGB8::new(255, 243, 239), // 6000 K
+    RGB8::new(255, 249, 253), // 6500 K
+];
+
+/// Maximaler Fortschritt für Interpolationen (Promille)
+pub const PERMILLE_MAX: u16 = 1000;
+
+/// Wandelt eine Farbtemperatur in eine RGB-Farbe (volle Helligkeit)
+///
+/// Werte außerhalb von [`MIN_KELVIN`]..=[`MAX_KELVIN`] werden begrenzt,
+/// zwischen den Stützstellen wird linear interpoliert.
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::color::color_temperature;
+/// # use rgb::RGB8;
+/// assert_eq!(color_temperature(1000), RGB8::new(255, 56, 0));
+/// ```
+pub fn color_temperature(kelvin: u16) -> RGB8 {
+    let kelvin = kelvin.clamp(MIN_KELVIN, MAX_KELVIN);
+    let offset = kelvin - MIN_KELVIN;
+    let index = (offset / KELVIN_STEP) as usize;
+    let Some(&upper) = KELVIN_TABLE.get(index + 1) else {
+        return KELVIN_TABLE[KELVIN_TABLE.len() - 1];
+    };
+    let permille =
+        ((offset % KELVIN_STEP) as u32 * PERMILLE_MAX as u32 / KELVIN_STEP as u32) as u16;
+    lerp_color(KELVIN_TABLE[index], upper, permille)
+}
+
+/// Skaliert eine Farbe auf eine Helligkeit (0 = aus, 255 = unverändert)
+pub fn scale_brightness(color: RGB8, brightness: u8) -> RGB8 {
+    let scale = |c: u8| ((c as u16 * brightness as u16 + 127) / 255) as u8;
+    RGB8::new(scale(color.r), scale(color.g), scale(color.b))
+}
+
+/// Wahrnehmungsgerechte Helligkeit nach CIE 1931 (L* → Leuchtdichte)
+///
+/// Das Auge empfindet Helligkeit annähernd logarithmisch: ein linearer
+/// Regler ändert unten viel und oben kaum etwas. Die Einstellung
+/// (0-255) wird als Helligkeit L* (0-100) gedeutet und in die relative
+/// Leuchtdichte für die PWM umgerechnet:
+///
+/// - L* ≤ 8: `Y = L* / 903,3`
+/// - sonst: `Y = ((L* + 16) / 116)³`
+///
+/// Kleine Werte ungleich 0 ergeben mindestens 1, damit die LED nicht
+/// unerwartet aus ist.
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::color::perceived_brightness;
+/// assert_eq!(perceived_brightness(128), 47);
+/// assert_eq!(perceived_brightness(255), 255);
+/// ```
+pub const fn perceived_brightness(level: u8) -> u8 {
+    let level = level as u64;
+    let luminance = if level * 100 <= 8 * 255 {
+        // L* / 903,3 · 255 = level · 100 / 903,3
+        (level * 1000 + 9033 / 2) / 9033
+    } else {
+        // ((level · 100 / 255 + 16) / 116)³ · 255, Bruch auf gemeinsamen Nenner
+        let numerator = level * 100 + 16 * 255;
+        let denominator = (116 * 255) * (116 * 255) * (116 * 255);
+        (255 * numerator * numerator * numerator + denominator / 2) / denominator
+    };
+    if level > 0 && luminance == 0 {
+        1
+    } else {
+        luminance as u8
+    }
+}
+
+/// Lineare Interpolation zwischen zwei Farben
+///
+/// `permille` = 0 liefert `from`, `permille` >= 1000 liefert `to`.
+pub fn lerp_color(from: RGB8, to: RGB8, permille: u16) -> RGB8 {
+    let permille = permille.min(PERMILLE_MAX) as i32;
+    let lerp = |a: u8, b: u8| {
+        let a = a as i32;
+        let b = b as i32;
+        (a + (b - a) * permille / PERMILLE_MAX as i32) as u8
+    };
+    RGB8::new(lerp(from.r, to.r), lerp(from.g, to.g), lerp(from.b, to.b))
+}
+
+/// Farbton-Vollkreis in internen Einheiten (6 Sektoren à 255, ≈ 0,24° pro Einheit)
+pub const HUE_CIRCLE: u16 = 6 * 255;
+
+/// Farbe im HSV-Modell mit ganzzahligen Komponenten
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hsv {
+    /// Farbton in Einheiten von [`HUE_CIRCLE`] (0 = Rot, 510 = Grün, 1020 = Blau)
+    pub hue: u16,
+    /// Sättigung (0 = Grau, 255 = rein)
+    pub saturation: u8,
+    /// Hellwert (größter RGB-Kanal)
+    pub value: u8,
+}
+
+impl Hsv {
+    /// Zerlegt eine RGB-Farbe (Grautöne erhalten Farbton 0)
+    pub fn from_rgb(color: RGB8) -> Self {
+        let max = color.r.max(color.g).max(color.b);
+        let min = color.r.min(color.g).min(color.b);
+        let delta = (max - min) as u32;
+        if delta == 0 {
+            return Self {
+                hue: 0,
+                saturation: 0,
+                value: max,
+            };
+        }
+        let saturation = ((delta * 255 + max as u32 / 2) / max as u32) as u8;
+
+        // Position im Sektor (0-255), gerundet
+        let fraction =
+            |a: u8, b: u8| (((a as i32 - b as i32) * 255).unsigned_abs() + delta / 2) / delta;
+        let circle = HUE_CIRCLE as u32;
+        let h = if max == color.r {
+            if color.g >= color.b {
+                fraction(color.g, color.b)
+            } else {
+                circle - fraction(color.b, color.g)
+            }
+        } else if max == color.g {
+            if color.b >= color.r {
+                2 * 255 + fraction(color.b, color.r)
+            } else {
+                2 * 255 - fraction(color.r, color.b)
+            }
+        } else if color.r >= color.g {
+            4 * 255 + fraction(color.r, color.g)
+        } else {
+            4 * 255 - fraction(color.g, color.r)
+        };
+        Self {
+            hue: (h % circle) as u16,
+            saturation,
+            value: max,
+        }
+    }
+
+    /// Setzt die Farbe wieder als RGB zusammen
+    pub fn to_rgb(self) -> RGB8 {
+        let h = (self.hue % HUE_CIRCLE) as u32;
+        let s = self.saturation as u32;
+        let v = self.value as u32;
+        let sector = h / 255;
+        let f = h % 255;
+        let p = (v * (255 - s) / 255) as u8;
+        let q = (v * (255 * 255 - s * f) / (255 * 255)) as u8;
+        let t = (v * (255 * 255 - s * (255 - f)) / (255 * 255)) as u8;
+        let v = self.value;
+        match sector {
+            0 => RGB8::new(v, t, p),
+            1 => RGB8::new(q, v, p),
+            2 => RGB8::new(p, v, t),
+            3 => RGB8::new(p, q, v),
+            4 => RGB8::new(t, p, v),
+            _ => RGB8::new(v, p, q),
+        }
+    }
+}
+
+/// Dreht den Farbton um `degrees` Grad (negativ = rückwärts)
+///
+/// Sättigung und Hellwert bleiben erhalten, Grautöne unverändert. Für
+/// fließende Verläufe (statt des harten Kanaltauschs von
+/// [`crate::rotate_color`]) den Farbton im Aufrufer führen und per
+/// [`Hsv::to_rgb`] umrechnen: wiederholtes Drehen in kleinen Schritten
+/// sammelt Rundungsfehler.
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::color::rotate_hue;
+/// # use rgb::RGB8;
+/// assert_eq!(rotate_hue(RGB8::new(255, 0, 0), 120), RGB8::new(0, 255, 0));
+/// ```
+pub fn rotate_hue(color: RGB8, degrees: i16) -> RGB8 {
+    let mut hsv = Hsv::from_rgb(color);
+    if hsv.saturation == 0 {
+        return color;
+    }
+    let circle = HUE_CIRCLE as i32;
+    // Grad → Einheiten (gerundet), beliebig viele Umdrehungen
+    let offset = (degrees as i32 * circle + degrees.signum() as i32 * 180) / 360;
+    hsv.hue = (hsv.hue as i32 + offset).rem_euclid(circle) as u16;
+    hsv.to_rgb()
+}
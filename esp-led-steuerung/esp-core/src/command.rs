@@ -0,0 +1,199 @@
+//! Text-Kommandos (MQTT-Payloads, Konsolen-Eingaben)
+//!
+//! Einfache, menschenlesbare Syntax, Groß-/Kleinschreibung egal:
+//!
+//! | Eingabe            | Kommando                         |
+//! |--------------------|----------------------------------|
+//! | `rot`, `grün`, `blau` (auch `red`, `green`, `blue`) `[<ms>]` | Farbe setzen, optional mit Übergang |
+//! | `auto`             | Auto-Rotation                    |
+//! | `zufall`, `random` | Zufallsfarben statt Auto-Rotation |
+//! | `aus`, `off` `[<ms>]` | LED ausschalten, optional mit Übergang |
+//! | `sleep <min>`      | Sleep-Timer (`sleep 0` = abbrechen) |
+//! | `sunrise [<min>]`  | Sonnenaufgang starten            |
+//! | `preset <id>`      | Preset anwenden                  |
+//! | `kalibrieren`, `calibrate` | Kalibriermodus (Testfarben) |
+//! | `testmuster`, `testpattern` | Testmuster pro Pixel         |
+//! | `diagnose`         | Blinkcode des letzten Verbindungsproblems |
+//! | `text <nachricht>` | Lauftext auf der LED-Matrix (Rest der Eingabe) |
+//! | `uhr`, `clock` `[12\|24]` | Uhrzeit anzeigen, optional im 12-/24-Stunden-Format |
+//! | `sequence <farbe>...` | Farbfolge der Auto-Rotation (`sequence` allein = Rot → Grün → Blau) |
+//! | `verlauf`, `gradient` `<farbe> <farbe>...` | Farbverlauf über alle Pixel (2 bis 8 Farben) |
+//! | `meteor [<px/s> [<schweif> [<farbe>]]]` | Meteor mit Schweif (Standard 30 px/s, 8 Pixel, aktuelle Farbe) |
+//! | `plasma [<tempo>]` | Plasma in den Farben der Farbfolge (Standard-Tempo 64) |
+//! | `param`, `set_effect_param` `<name> <wert>` | Parameter des laufenden Effekts ändern (z.B. `param speed 40`) |
+
+use crate::clock_face::ClockFormat;
+use crate::effect_param::{ParamName, ParamValue};
+use crate::gradient::Gradient;
+use crate::i18n::parse_color_name;
+use crate::meteor::{DEFAULT_METEOR_SPEED, DEFAULT_METEOR_TAIL};
+use crate::plasma::DEFAULT_PLASMA_SPEED;
+use crate::sequence::{ColorSequence, parse_color_word};
+use crate::text::TextMessage;
+use crate::types::{ColorId, LedCommand};
+
+/// Standard-Dauer für `sunrise` ohne Argument (Minuten)
+pub const DEFAULT_SUNRISE_MINUTES: u32 = 20;
+
+/// Fehler beim Parsen eines Text-Kommandos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandParseError {
+    /// Leere Eingabe
+    Empty,
+    /// Unbekanntes Kommando
+    Unknown,
+    /// Argument fehlt oder ist keine gültige Zahl
+    InvalidArgument,
+}
+
+impl CommandParseError {
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            CommandParseError::Empty => "empty",
+            CommandParseError::Unknown => "unknown_command",
+            CommandParseError::InvalidArgument => "invalid_argument",
+        }
+    }
+}
+
+/// Parst ein Text-Kommando
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::{LedCommand, parse_text_command};
+/// assert!(matches!(
+///     parse_text_command("sleep 30"),
+///     Ok(LedCommand::SleepTimer { minutes: 30 })
+/// ));
+/// ```
+pub fn parse_text_command(input: &str) -> Result<LedCommand, CommandParseError> {
+    let mut words = input.split_whitespace();
+    let keyword = words.next().ok_or(CommandParseError::Empty)?;
+    let argument = words.next();
+    let is = |name: &str| keyword.eq_ignore_ascii_case(name);
+
+    // Farbnamen in allen Sprachen ("rot", "green", ...), "aus"/"off" siehe unten
+    if let Some(id) = parse_color_name(keyword).filter(|id| *id != ColorId::Off) {
+        let command = LedCommand::try_from(id.name()).map_err(|_| CommandParseError::Unknown)?;
+        return Ok(command.with_transition(transition_ms(argument)?));
+    }
+    if is("auto") {
+        return Ok(LedCommand::EnableAuto);
+    }
+    if is("zufall") || is("random") {
+        return Ok(LedCommand::EnableRandom);
+    }
+    if is("aus") || is("off") {
+        return Ok(LedCommand::Off.with_transition(transition_ms(argument)?));
+    }
+    if is("sleep") {
+        let minutes = argument
+            .and_then(|arg| arg.parse().ok())
+            .ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(LedCommand::SleepTimer { minutes });
+    }
+    if is("sunrise") {
+        let minutes: u32 = match argument {
+            Some(arg) => arg
+                .parse()
+                .map_err(|_| CommandParseError::InvalidArgument)?,
+            None => DEFAULT_SUNRISE_MINUTES,
+        };
+        return Ok(LedCommand::Sunrise {
+            duration_secs: minutes.saturating_mul(60),
+        });
+    }
+    if is("preset") {
+        let id = argument
+            .and_then(|arg| arg.parse().ok())
+            .ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(LedCommand::ApplyPreset { id });
+    }
+    if is("kalibrieren") || is("calibrate") {
+        return Ok(LedCommand::Calibrate);
+    }
+    if is("testmuster") || is("testpattern") {
+        return Ok(LedCommand::TestPattern);
+    }
+    if is("diagnose") {
+        return Ok(LedCommand::Diagnose);
+    }
+    if is("text") {
+        // Leerzeichen innerhalb der Nachricht bleiben erhalten
+        let message = input.trim_start()[keyword.len()..].trim();
+        let text = TextMessage::new(message).ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(LedCommand::ScrollText { text });
+    }
+    if is("uhr") || is("clock") {
+        let format = match argument {
+            None => None,
+            Some("24") => Some(ClockFormat::H24),
+            Some("12") => Some(ClockFormat::H12),
+            Some(_) => return Err(CommandParseError::InvalidArgument),
+        };
+        return Ok(LedCommand::ShowClock { format });
+    }
+    if is("sequence") {
+        // Farbliste = Rest der Eingabe, leer = klassische Folge
+        let colors = input.trim_start()[keyword.len()..].trim();
+        let sequence = match colors {
+            "" => None,
+            colors => Some(ColorSequence::parse(colors).ok_or(CommandParseError::InvalidArgument)?),
+        };
+        return Ok(LedCommand::SetSequence { sequence });
+    }
+    if is("verlauf") || is("gradient") {
+        let colors = input.trim_start()[keyword.len()..].trim();
+        let gradient = Gradient::parse(colors).ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(LedCommand::ShowGradient { gradient });
+    }
+    if is("meteor") {
+        let speed: u8 = match argument {
+            Some(arg) => arg
+                .parse()
+                .ok()
+                .filter(|&speed| speed > 0)
+                .ok_or(CommandParseError::InvalidArgument)?,
+            None => DEFAULT_METEOR_SPEED,
+        };
+        let tail: u8 = match words.next() {
+            Some(arg) => arg
+                .parse()
+                .map_err(|_| CommandParseError::InvalidArgument)?,
+            None => DEFAULT_METEOR_TAIL,
+        };
+        let color = match words.next() {
+            Some(word) => Some(parse_color_word(word).ok_or(CommandParseError::InvalidArgument)?),
+            None => None,
+        };
+        return Ok(LedCommand::Meteor { color, speed, tail });
+    }
+    if is("plasma") {
+        let speed = match argument {
+            Some(arg) => arg
+                .parse()
+                .map_err(|_| CommandParseError::InvalidArgument)?,
+            None => DEFAULT_PLASMA_SPEED,
+        };
+        return Ok(LedCommand::Plasma { speed });
+    }
+    if is("param") || is("set_effect_param") {
+        let name = argument.ok_or(CommandParseError::InvalidArgument)?;
+        // Wert = Rest der Eingabe (Paletten enthalten Leerzeichen)
+        let rest = input.trim_start()[keyword.len()..].trim_start();
+        let value = rest[name.len()..].trim();
+        let value = ParamName::parse(name)
+            .and_then(|name| ParamValue::parse(name, value).ok())
+            .ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(LedCommand::SetEffectParam { value });
+    }
+    Err(CommandParseError::Unknown)
+}
+
+/// Optionale Übergangsdauer in Millisekunden (fehlt = sofort)
+fn transition_ms(argument: Option<&str>) -> Result<u32, CommandParseError> {
+    argument.map_or(Ok(0), |arg| {
+        arg.parse().map_err(|_| CommandParseError::InvalidArgument)
+    })
+}
@@ -0,0 +1,173 @@
+//! Konsolen-Kommandos (TCP-Konsole und weitere zeilenbasierte Schnittstellen)
+//!
+//! Zeilenbasierte Syntax, Groß-/Kleinschreibung egal:
+//!
+//! | Eingabe                 | Kommando                                |
+//! |-------------------------|-----------------------------------------|
+//! | `help`, `?`             | Befehlsübersicht                        |
+//! | `status`                | LED-Zustand, Uptime, IP, Version        |
+//! | `heap`                  | Heap-Auslastung                         |
+//! | `dns`                   | DNS-Cache der Broker-Adresse            |
+//! | `net`                   | Netzwerk-Zähler                         |
+//! | `trace [on\|off]`        | Ablauf-Tracing anzeigen / ein / aus     |
+//! | `reboot`                | Neustart                                |
+//! | `loglevel [<level>]`    | Log-Level anzeigen / setzen             |
+//! | `set [color\|mode] <cmd>` | LED-Kommando (siehe [`crate::command`]) |
+//!
+//! Zusätzlich werden alle Text-Kommandos direkt akzeptiert (`rot`, `auto`, ...).
+
+use crate::command::{CommandParseError, parse_text_command};
+use crate::types::LedCommand;
+
+/// Log-Level (aufsteigend nach Ausführlichkeit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+    Trace = 4,
+}
+
+impl LogLevel {
+    /// Alle Level in aufsteigender Reihenfolge
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+
+    /// Name des Levels (wie in `DEFMT_LOG`)
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+
+    /// Level aus dem numerischen Wert (z.B. aus einem Atomic)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+
+    /// Level aus dem Namen (Groß-/Kleinschreibung egal)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| name.eq_ignore_ascii_case(level.as_str()))
+    }
+}
+
+/// Unterbefehl von `trace`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceAction {
+    /// Zusammenfassung und letzte Abschnitte anzeigen
+    Show,
+    /// Tracing einschalten (verwirft bisherige Messungen)
+    On,
+    /// Tracing ausschalten (Messungen bleiben lesbar)
+    Off,
+}
+
+/// Dekodiertes Konsolen-Kommando
+#[derive(Clone, Copy)]
+pub enum ConsoleCommand {
+    Help,
+    Status,
+    Heap,
+    /// DNS-Cache der Broker-Adresse
+    Dns,
+    /// Netzwerk-Zähler
+    Net,
+    /// Ablauf-Tracing (siehe [`crate::trace`])
+    Trace(TraceAction),
+    Reboot,
+    /// `None` = aktuelles Level anzeigen
+    LogLevel(Option<LogLevel>),
+    /// LED-Kommando an den LED-Task
+    Led(LedCommand),
+}
+
+/// Parst eine Konsolen-Zeile
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::{ColorId, ConsoleCommand, LedCommand, parse_console_command};
+/// assert!(matches!(
+///     parse_console_command("set color rot"),
+///     Ok(ConsoleCommand::Led(LedCommand::SetColor { color_id: ColorId::Red, .. }))
+/// ));
+/// ```
+pub fn parse_console_command(line: &str) -> Result<ConsoleCommand, CommandParseError> {
+    let (keyword, rest) = split_word(line);
+    if keyword.is_empty() {
+        return Err(CommandParseError::Empty);
+    }
+    let is = |name: &str| keyword.eq_ignore_ascii_case(name);
+
+    if is("help") || is("?") {
+        return Ok(ConsoleCommand::Help);
+    }
+    if is("status") {
+        return Ok(ConsoleCommand::Status);
+    }
+    if is("heap") {
+        return Ok(ConsoleCommand::Heap);
+    }
+    if is("dns") {
+        return Ok(ConsoleCommand::Dns);
+    }
+    if is("net") {
+        return Ok(ConsoleCommand::Net);
+    }
+    if is("trace") {
+        let action = match rest {
+            "" => TraceAction::Show,
+            _ if rest.eq_ignore_ascii_case("on") => TraceAction::On,
+            _ if rest.eq_ignore_ascii_case("off") => TraceAction::Off,
+            _ => return Err(CommandParseError::InvalidArgument),
+        };
+        return Ok(ConsoleCommand::Trace(action));
+    }
+    if is("reboot") {
+        return Ok(ConsoleCommand::Reboot);
+    }
+    if is("loglevel") {
+        if rest.is_empty() {
+            return Ok(ConsoleCommand::LogLevel(None));
+        }
+        let level = LogLevel::from_name(rest).ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(ConsoleCommand::LogLevel(Some(level)));
+    }
+    if is("set") {
+        // Optionales Schlüsselwort: `set color rot`, `set mode auto`
+        let (target, value) = split_word(rest);
+        let command = if target.eq_ignore_ascii_case("color") || target.eq_ignore_ascii_case("mode")
+        {
+            value
+        } else {
+            rest
+        };
+        return parse_text_command(command)
+            .map(ConsoleCommand::Led)
+            .map_err(|e| match e {
+                CommandParseError::Empty => CommandParseError::InvalidArgument,
+                other => other,
+            });
+    }
+    parse_text_command(line).map(ConsoleCommand::Led)
+}
+
+/// Trennt das erste Wort vom Rest (beides ohne umgebende Leerzeichen)
+fn split_word(input: &str) -> (&str, &str) {
+    let input = input.trim();
+    match input.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (input, ""),
+    }
+}
@@ -0,0 +1,212 @@
+//! Absturz-Protokoll (Core-Dump light)
+//!
+//! Der Panic-Handler der Firmware sammelt Panic-Meldung, Rücksprungadressen
+//! des Stacks und einige Register in einem [`CrashRecord`] und schreibt ihn in
+//! den Flash. Nach dem Neustart liest die Firmware ihn einmal aus und stellt
+//! ihn über die API bereit – statt nur "es hat neu gestartet".
+//!
+//! Adressen lassen sich nur mit dem ELF desselben Builds auflösen
+//! (`addr2line -e <elf> 0x42001234`), deshalb wird der Build (Git-Hash) mit
+//! gespeichert.
+//!
+//! # Layout (Little Endian)
+//!
+//! | Offset | Größe | Inhalt                      |
+//! |--------|-------|-----------------------------|
+//! | 0      | 8     | Uptime in ms                |
+//! | 8      | 12    | Register `ra`, `sp`, `fp`   |
+//! | 20     | 1     | Anzahl Frames `n`           |
+//! | 21     | 4·n   | Rücksprungadressen          |
+//! | …      | 1 + b | Länge + Build (UTF-8)       |
+//! | …      | 1 + m | Länge + Panic-Meldung       |
+
+use core::fmt;
+
+/// Maximale Anzahl gespeicherter Stack-Frames
+pub const CRASH_MAX_FRAMES: usize = 16;
+
+/// Maximale Länge der Panic-Meldung (Bytes, wird gekürzt)
+pub const CRASH_MESSAGE_LEN: usize = 120;
+
+/// Maximale Länge der Build-Kennung (Bytes)
+pub const CRASH_BUILD_LEN: usize = 16;
+
+/// Maximale kodierte Größe eines Records
+pub const CRASH_RECORD_MAX_LEN: usize =
+    21 + 4 * CRASH_MAX_FRAMES + 1 + CRASH_BUILD_LEN + 1 + CRASH_MESSAGE_LEN;
+
+/// Register zum Zeitpunkt des Panics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CrashRegisters {
+    /// Rücksprungadresse
+    pub ra: u32,
+    /// Stack-Pointer
+    pub sp: u32,
+    /// Frame-Pointer (`s0`)
+    pub fp: u32,
+}
+
+/// Ein gespeicherter Absturz
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashRecord {
+    /// Laufzeit seit Boot beim Absturz
+    pub uptime_ms: u64,
+    pub registers: CrashRegisters,
+    frames: [u32; CRASH_MAX_FRAMES],
+    frame_count: usize,
+    build: Text<CRASH_BUILD_LEN>,
+    message: Text<CRASH_MESSAGE_LEN>,
+}
+
+impl CrashRecord {
+    pub const fn new(uptime_ms: u64, registers: CrashRegisters) -> Self {
+        Self {
+            uptime_ms,
+            registers,
+            frames: [0; CRASH_MAX_FRAMES],
+            frame_count: 0,
+            build: Text::new(),
+            message: Text::new(),
+        }
+    }
+
+    /// Hängt eine Rücksprungadresse an, `false` wenn kein Platz mehr ist
+    pub fn push_frame(&mut self, address: u32) -> bool {
+        if self.frame_count == CRASH_MAX_FRAMES {
+            return false;
+        }
+        self.frames[self.frame_count] = address;
+        self.frame_count += 1;
+        true
+    }
+
+    /// Rücksprungadressen, innerster Frame zuerst
+    pub fn frames(&self) -> &[u32] {
+        &self.frames[..self.frame_count]
+    }
+
+    /// Setzt die Build-Kennung (wird gekürzt)
+    pub fn set_build(&mut self, build: &str) {
+        self.build = Text::new();
+        let _ = fmt::Write::write_str(&mut self.build, build);
+    }
+
+    pub fn build(&self) -> &str {
+        self.build.as_str()
+    }
+
+    /// Hängt formatierten Text an die Meldung an (wird gekürzt)
+    pub fn write_message(&mut self, args: fmt::Arguments<'_>) {
+        let _ = fmt::write(&mut self.message, args);
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// Serialisiert den Record
+    ///
+    /// Gibt die Länge zurück, oder `None` wenn `out` zu klein ist.
+    pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+        let total = 21 + 4 * self.frame_count + 1 + self.build.len + 1 + self.message.len;
+        if out.len() < total {
+            return None;
+        }
+        out[0..8].copy_from_slice(&self.uptime_ms.to_le_bytes());
+        out[8..12].copy_from_slice(&self.registers.ra.to_le_bytes());
+        out[12..16].copy_from_slice(&self.registers.sp.to_le_bytes());
+        out[16..20].copy_from_slice(&self.registers.fp.to_le_bytes());
+        out[20] = self.frame_count as u8;
+        let mut pos = 21;
+        for frame in self.frames() {
+            out[pos..pos + 4].copy_from_slice(&frame.to_le_bytes());
+            pos += 4;
+        }
+        for text in [self.build.as_bytes(), self.message.as_bytes()] {
+            out[pos] = text.len() as u8;
+            out[pos + 1..pos + 1 + text.len()].copy_from_slice(text);
+            pos += 1 + text.len();
+        }
+        Some(total)
+    }
+
+    /// Deserialisiert einen Record (`None` bei ungültigen Längen oder UTF-8)
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let word = |offset: usize| -> Option<u32> {
+            Some(u32::from_le_bytes(
+                data.get(offset..offset + 4)?.try_into().ok()?,
+            ))
+        };
+        let uptime_ms = u64::from_le_bytes(data.get(0..8)?.try_into().ok()?);
+        let registers = CrashRegisters {
+            ra: word(8)?,
+            sp: word(12)?,
+            fp: word(16)?,
+        };
+        let mut record = Self::new(uptime_ms, registers);
+
+        let frame_count = usize::from(*data.get(20)?);
+        if frame_count > CRASH_MAX_FRAMES {
+            return None;
+        }
+        let mut pos = 21;
+        for _ in 0..frame_count {
+            record.push_frame(word(pos)?);
+            pos += 4;
+        }
+
+        let build = read_text(data, &mut pos)?;
+        let message = read_text(data, &mut pos)?;
+        if build.len() > CRASH_BUILD_LEN || message.len() > CRASH_MESSAGE_LEN {
+            return None;
+        }
+        record.set_build(build);
+        record.write_message(format_args!("{}", message));
+        Some(record)
+    }
+}
+
+/// Liest einen Text mit vorangestellter Länge und rückt `pos` weiter
+fn read_text<'a>(data: &'a [u8], pos: &mut usize) -> Option<&'a str> {
+    let len = usize::from(*data.get(*pos)?);
+    let bytes = data.get(*pos + 1..*pos + 1 + len)?;
+    *pos += 1 + len;
+    core::str::from_utf8(bytes).ok()
+}
+
+/// Text fester Kapazität, kürzt an Zeichengrenzen
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Text<const LEN: usize> {
+    len: usize,
+    bytes: [u8; LEN],
+}
+
+impl<const LEN: usize> Text<LEN> {
+    const fn new() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; LEN],
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    fn as_str(&self) -> &str {
+        // Nur über `fmt::Write` befüllt, abgeschnitten an Zeichengrenzen
+        core::str::from_utf8(self.as_bytes()).unwrap_or("")
+    }
+}
+
+impl<const LEN: usize> fmt::Write for Text<LEN> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut take = s.len().min(LEN - self.len);
+        while !s.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.bytes[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        Ok(())
+    }
+}
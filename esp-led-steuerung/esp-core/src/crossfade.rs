@@ -0,0 +1,131 @@
+//! Überblenden beim Effektwechsel
+//!
+//! Statt hart umzuschalten, mischt [`Crossfade`] beim Wechsel des
+//! pixelweisen Effekts das Bild des alten und des neuen Effekts über
+//! `duration_ms` (pro Pixel [`lerp_color`], wie die Farbverläufe in
+//! [`crate::transition`]). Beide Effekte laufen währenddessen weiter.
+//!
+//! [`PixelEffect`] fasst die überblendbaren Effekte zusammen, damit der
+//! LED-Task beliebige Paare ohne Heap überblenden kann.
+
+use rgb::RGB8;
+
+use crate::color::{PERMILLE_MAX, lerp_color};
+use crate::effect_param::{LedEffect, ParamValue};
+use crate::effects::{EffectId, EffectParam};
+use crate::gradient::Gradient;
+use crate::matrix::MatrixLayout;
+use crate::meteor::Meteor;
+use crate::plasma::Plasma;
+
+/// Einer der pixelweisen Effekte mit [`LedEffect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelEffect {
+    Gradient(Gradient),
+    Meteor(Meteor),
+    Plasma(Plasma),
+}
+
+impl PixelEffect {
+    fn effect(&self) -> &dyn LedEffect {
+        match self {
+            PixelEffect::Gradient(gradient) => gradient,
+            PixelEffect::Meteor(meteor) => meteor,
+            PixelEffect::Plasma(plasma) => plasma,
+        }
+    }
+
+    fn effect_mut(&mut self) -> &mut dyn LedEffect {
+        match self {
+            PixelEffect::Gradient(gradient) => gradient,
+            PixelEffect::Meteor(meteor) => meteor,
+            PixelEffect::Plasma(plasma) => plasma,
+        }
+    }
+}
+
+impl LedEffect for PixelEffect {
+    fn id(&self) -> EffectId {
+        self.effect().id()
+    }
+
+    fn params(&self) -> &'static [EffectParam] {
+        self.effect().params()
+    }
+
+    fn set_param(&mut self, value: ParamValue, now_ms: u64) {
+        self.effect_mut().set_param(value, now_ms);
+    }
+
+    fn render(&self, now_ms: u64, layout: MatrixLayout, pixels: &mut [RGB8]) {
+        self.effect().render(now_ms, layout, pixels);
+    }
+
+    fn is_animated(&self) -> bool {
+        self.effect().is_animated()
+    }
+}
+
+/// Überblendung von `from` nach `to` ab `start_ms`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crossfade<A, B> {
+    /// Bisheriger Effekt (blendet aus)
+    pub from: A,
+    /// Neuer Effekt (blendet ein, empfängt Live-Parameter)
+    pub to: B,
+    pub start_ms: u64,
+    /// Dauer der Überblendung (0 = harter Schnitt)
+    pub duration_ms: u32,
+}
+
+impl<A: LedEffect, B: LedEffect> Crossfade<A, B> {
+    pub const fn new(from: A, to: B, start_ms: u64, duration_ms: u32) -> Self {
+        Self {
+            from,
+            to,
+            start_ms,
+            duration_ms,
+        }
+    }
+
+    /// Anteil des neuen Effekts in Promille (0..=[`PERMILLE_MAX`])
+    pub fn progress(&self, now_ms: u64) -> u16 {
+        if self.duration_ms == 0 {
+            return PERMILLE_MAX;
+        }
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        let permille = elapsed * u64::from(PERMILLE_MAX) / u64::from(self.duration_ms);
+        permille.min(u64::from(PERMILLE_MAX)) as u16
+    }
+
+    /// Überblendung abgeschlossen, nur noch `to` sichtbar
+    pub fn is_finished(&self, now_ms: u64) -> bool {
+        self.progress(now_ms) >= PERMILLE_MAX
+    }
+
+    /// Zeichnet das Mischbild zum Zeitpunkt `now_ms` in `pixels`
+    ///
+    /// `incoming` nimmt das Bild des neuen Effekts auf und sollte so lang
+    /// sein wie `pixels`; Pixel darüber hinaus zeigen nur den alten Effekt.
+    /// Nach Ende der Überblendung zeichnet nur noch `to`.
+    pub fn render(
+        &self,
+        now_ms: u64,
+        layout: MatrixLayout,
+        pixels: &mut [RGB8],
+        incoming: &mut [RGB8],
+    ) {
+        let progress = self.progress(now_ms);
+        if progress >= PERMILLE_MAX {
+            self.to.render(now_ms, layout, pixels);
+            return;
+        }
+        self.from.render(now_ms, layout, pixels);
+        let len = pixels.len().min(incoming.len());
+        let incoming = &mut incoming[..len];
+        self.to.render(now_ms, layout, incoming);
+        for (pixel, &color) in pixels.iter_mut().zip(incoming.iter()) {
+            *pixel = lerp_color(*pixel, color, progress);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Entprellung digitaler Eingänge (Taster, Reed-Kontakte, Bewegungsmelder)
+//!
+//! Ein neuer Pegel gilt erst als stabil, wenn er für die Beruhigungszeit
+//! unverändert anliegt. Die Zeit kommt über den [`Clock`]-Trait.
+//!
+//! [`PressDetector`] unterscheidet auf dem entprellten Pegel eines Tasters
+//! kurze und lange Drücke (z.B. BOOT-Taster: Preset vs. Werksreset).
+
+use crate::traits::Clock;
+
+/// Entpreller für einen digitalen Eingang
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Debouncer {
+    stable: bool,
+    candidate: bool,
+    candidate_since_ms: u64,
+    settle_ms: u32,
+}
+
+impl Debouncer {
+    /// Erstellt einen Entpreller mit Startpegel und Beruhigungszeit
+    pub const fn new(initial: bool, settle_ms: u32) -> Self {
+        Self {
+            stable: initial,
+            candidate: initial,
+            candidate_since_ms: 0,
+            settle_ms,
+        }
+    }
+
+    /// Aktueller stabiler Pegel
+    pub fn state(&self) -> bool {
+        self.stable
+    }
+
+    /// Übernimmt einen Messwert, gibt den neuen Pegel bei stabiler Änderung zurück
+    pub fn update(&mut self, level: bool, clock: &impl Clock) -> Option<bool> {
+        let now_ms = clock.now_ms();
+        if level != self.candidate {
+            self.candidate = level;
+            self.candidate_since_ms = now_ms;
+        }
+        let settled = now_ms.saturating_sub(self.candidate_since_ms) >= self.settle_ms as u64;
+        if settled && self.candidate != self.stable {
+            self.stable = self.candidate;
+            return Some(self.stable);
+        }
+        None
+    }
+}
+
+/// Art eines Tastendrucks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Press {
+    /// Losgelassen vor Ablauf der Haltezeit
+    Short,
+    /// Haltezeit erreicht (wird noch während des Drückens gemeldet)
+    Long,
+}
+
+/// Erkennt kurze und lange Drücke eines entprellten Tasters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressDetector {
+    pressed_since_ms: Option<u64>,
+    long_reported: bool,
+    long_ms: u32,
+}
+
+impl PressDetector {
+    /// Erstellt einen Detektor mit Haltezeit `long_ms` für einen langen Druck
+    pub const fn new(long_ms: u32) -> Self {
+        Self {
+            pressed_since_ms: None,
+            long_reported: false,
+            long_ms,
+        }
+    }
+
+    /// Übernimmt das Ergebnis von [`Debouncer::update`] (`true` = gedrückt)
+    ///
+    /// Muss auch ohne Pegelwechsel regelmäßig aufgerufen werden, damit ein
+    /// langer Druck schon beim Erreichen der Haltezeit gemeldet wird. Nach
+    /// einem langen Druck folgt beim Loslassen kein kurzer.
+    pub fn update(&mut self, change: Option<bool>, clock: &impl Clock) -> Option<Press> {
+        let now_ms = clock.now_ms();
+        match change {
+            Some(true) => {
+                self.pressed_since_ms = Some(now_ms);
+                self.long_reported = false;
+                None
+            }
+            Some(false) => match self.pressed_since_ms.take() {
+                Some(_) if !self.long_reported => Some(Press::Short),
+                _ => None,
+            },
+            None => match self.pressed_since_ms {
+                Some(since)
+                    if !self.long_reported
+                        && now_ms.saturating_sub(since) >= self.long_ms as u64 =>
+                {
+                    self.long_reported = true;
+                    Some(Press::Long)
+                }
+                _ => None,
+            },
+        }
+    }
+}
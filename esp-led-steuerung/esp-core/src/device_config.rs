@@ -0,0 +1,179 @@
+//! Gerätekonfiguration zur Laufzeit (über `/api/config` einstellbar)
+//!
+//! Einstellungen, die von der verbauten Hardware abhängen und sich ohne
+//! neues Flashen ändern lassen sollen. Binär serialisierbar für die
+//! Flash-Persistenz; fehlende Felder älterer Records erhalten Standardwerte.
+
+use rgb::RGB8;
+
+/// Serialisierte Größe der Konfiguration in Bytes
+pub const DEVICE_CONFIG_LEN: usize = 3;
+
+/// Reihenfolge der Farbkanäle auf der Datenleitung
+///
+/// WS2812 erwartet Grün, Rot, Blau; manche Klone tauschen die Kanäle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[repr(u8)]
+pub enum ColorOrder {
+    Rgb = 0,
+    #[default]
+    Grb = 1,
+    Brg = 2,
+    Rbg = 3,
+    Gbr = 4,
+    Bgr = 5,
+}
+
+impl ColorOrder {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ColorOrder::Rgb),
+            1 => Some(ColorOrder::Grb),
+            2 => Some(ColorOrder::Brg),
+            3 => Some(ColorOrder::Rbg),
+            4 => Some(ColorOrder::Gbr),
+            5 => Some(ColorOrder::Bgr),
+            _ => None,
+        }
+    }
+
+    /// Kanalwerte in der Reihenfolge, in der sie gesendet werden
+    pub fn wire_bytes(self, color: RGB8) -> [u8; 3] {
+        let RGB8 { r, g, b } = color;
+        match self {
+            ColorOrder::Rgb => [r, g, b],
+            ColorOrder::Grb => [g, r, b],
+            ColorOrder::Brg => [b, r, g],
+            ColorOrder::Rbg => [r, b, g],
+            ColorOrder::Gbr => [g, b, r],
+            ColorOrder::Bgr => [b, g, r],
+        }
+    }
+}
+
+/// Zuordnung logischer Pixel (Effekt-Ausgabe) zu physischen Pixeln (Strip)
+///
+/// Wird nach dem Rendern angewendet, damit Effekte unabhängig davon richtig
+/// aussehen, an welchem Ende die Datenleitung eingespeist wird.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StripMapping {
+    /// Richtung umkehren (logischer Pixel 0 am Ende des Strips)
+    pub reverse: bool,
+    /// Hälften spiegeln: die erste Hälfte des Frames erscheint symmetrisch
+    /// auf beiden Hälften (mit `reverse` von der Mitte nach außen)
+    pub mirror: bool,
+}
+
+impl StripMapping {
+    /// Logischer Index für den physischen Pixel `physical` eines Strips mit `len` Pixeln
+    pub fn logical_index(self, physical: usize, len: usize) -> usize {
+        if len == 0 {
+            return 0;
+        }
+        let (index, span) = if self.mirror {
+            (
+                physical.min((len - 1).saturating_sub(physical)),
+                len.div_ceil(2),
+            )
+        } else {
+            (physical, len)
+        };
+        if self.reverse {
+            span - 1 - index
+        } else {
+            index
+        }
+    }
+}
+
+/// Gerätekonfiguration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct DeviceConfig {
+    /// Kanal-Reihenfolge der LEDs
+    pub color_order: ColorOrder,
+    /// Strip-Richtung umkehren (siehe [`StripMapping`])
+    pub reverse: bool,
+    /// Strip-Hälften spiegeln (siehe [`StripMapping`])
+    pub mirror: bool,
+    /// GPIO der LED-Datenleitung (`None` = Standard des Board-Profils)
+    ///
+    /// Wird nur beim Start ausgewertet und muss zu den erlaubten Pins des
+    /// Boards gehören (siehe [`DeviceConfig::led_pin_or`]).
+    pub led_pin: Option<u8>,
+}
+
+/// Bits im Flag-Byte der Serialisierung
+const FLAG_REVERSE: u8 = 1 << 0;
+const FLAG_MIRROR: u8 = 1 << 1;
+
+/// Kein LED-Pin gespeichert (Standard des Board-Profils)
+const NO_PIN: u8 = 0xFF;
+
+impl DeviceConfig {
+    /// Pixel-Zuordnung aus `reverse` und `mirror`
+    pub fn mapping(&self) -> StripMapping {
+        StripMapping {
+            reverse: self.reverse,
+            mirror: self.mirror,
+        }
+    }
+
+    /// Prüft ob der LED-Pin fehlt oder in `allowed` enthalten ist
+    pub fn has_allowed_led_pin(&self, allowed: &[u8]) -> bool {
+        self.led_pin.is_none_or(|pin| allowed.contains(&pin))
+    }
+
+    /// LED-Pin für den Start: konfigurierter Pin, falls erlaubt, sonst `default`
+    pub fn led_pin_or(&self, allowed: &[u8], default: u8) -> u8 {
+        match self.led_pin {
+            Some(pin) if allowed.contains(&pin) => pin,
+            Some(pin) => {
+                crate::log_warn!("LED-Pin GPIO{} nicht erlaubt, nutze GPIO{}", pin, default);
+                default
+            }
+            None => default,
+        }
+    }
+
+    /// Serialisiert die Konfiguration für die Flash-Persistenz
+    ///
+    /// Layout: Kanal-Reihenfolge, Flags (Bit 0 = reverse, Bit 1 = mirror),
+    /// LED-Pin (`0xFF` = Standard des Board-Profils)
+    pub fn to_bytes(&self) -> [u8; DEVICE_CONFIG_LEN] {
+        let mut flags = 0;
+        if self.reverse {
+            flags |= FLAG_REVERSE;
+        }
+        if self.mirror {
+            flags |= FLAG_MIRROR;
+        }
+        [
+            self.color_order as u8,
+            flags,
+            self.led_pin.unwrap_or(NO_PIN),
+        ]
+    }
+
+    /// Deserialisiert eine Konfiguration
+    ///
+    /// Fehlende Felder erhalten Standardwerte, unbekannte werden ignoriert;
+    /// `None` bei ungültigen Werten.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut config = Self::default();
+        if let Some(&order) = bytes.first() {
+            config.color_order = ColorOrder::from_u8(order)?;
+        }
+        if let Some(&flags) = bytes.get(1) {
+            config.reverse = flags & FLAG_REVERSE != 0;
+            config.mirror = flags & FLAG_MIRROR != 0;
+        }
+        if let Some(&pin) = bytes.get(2) {
+            config.led_pin = (pin != NO_PIN).then_some(pin);
+        }
+        Some(config)
+    }
+}
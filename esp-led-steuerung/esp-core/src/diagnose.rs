@@ -0,0 +1,209 @@
+//! Diagnose-Blinkcodes für Verbindungsprobleme (Kommando `diagnose`)
+//!
+//! [`ConnectivityDiagnosis`] merkt sich aus den [`SystemEvent`]s die Ursache
+//! des letzten Verbindungsproblems. Das Kommando `diagnose` lässt die ganze
+//! Lampe diese als Zahl blinken, damit Nutzer ohne Laptop den Fehler melden
+//! können ("blinkt dreimal rot"):
+//!
+//! | Code | Muster           | Ursache                                        |
+//! |------|------------------|------------------------------------------------|
+//! | 1    | Rot, 1×          | Kein WLAN (Access Point fehlt, Passwort falsch) |
+//! | 2    | Rot, 2×          | Keine IP-Adresse (DHCP antwortet nicht)        |
+//! | 3    | Rot, 3×          | DNS fehlgeschlagen (Broker-Name unbekannt)     |
+//! | 4    | Rot, 4×          | Broker lehnt ab (TCP oder CONNECT abgewiesen)  |
+//! | –    | Grün, dauerhaft  | Kein Problem bekannt                           |
+//!
+//! Ein Code wird [`DIAGNOSE_REPEATS`] mal gezeigt, jeweils nach einer Pause.
+//! Danach kehrt die Lampe in den vorherigen Modus zurück.
+
+use rgb::RGB8;
+
+use crate::error::{ErrorKind, ErrorSource, FirmwareError};
+use crate::events::SystemEvent;
+
+/// Wiederholungen eines Fehlercodes
+pub const DIAGNOSE_REPEATS: u64 = 3;
+
+/// Dauer der grünen Anzeige ohne Fehler
+const OK_DURATION_MS: u64 = 3000;
+
+/// Pause vor jeder Wiederholung (trennt die Zählungen)
+const PAUSE_MS: u64 = 1500;
+
+/// Dauer eines Blitzes
+const FLASH_ON_MS: u64 = 400;
+
+/// Dunkelphase nach einem Blitz
+const FLASH_OFF_MS: u64 = 400;
+
+/// Ursache des letzten Verbindungsproblems
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnoseCode {
+    NoWifi,
+    NoIp,
+    DnsFailed,
+    BrokerRefused,
+}
+
+impl DiagnoseCode {
+    /// Nummer des Codes = Anzahl der Blitze
+    pub fn number(self) -> u8 {
+        match self {
+            DiagnoseCode::NoWifi => 1,
+            DiagnoseCode::NoIp => 2,
+            DiagnoseCode::DnsFailed => 3,
+            DiagnoseCode::BrokerRefused => 4,
+        }
+    }
+
+    /// Kurzname für Logs und Protokolle
+    pub fn name(self) -> &'static str {
+        match self {
+            DiagnoseCode::NoWifi => "no_wifi",
+            DiagnoseCode::NoIp => "no_ip",
+            DiagnoseCode::DnsFailed => "dns_failed",
+            DiagnoseCode::BrokerRefused => "broker_refused",
+        }
+    }
+}
+
+/// Verbindungszustand und letzte Fehlerursache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectivityDiagnosis {
+    /// Erfolgreich erst mit MQTT-Verbindung (sonst schon mit IP-Adresse)
+    require_mqtt: bool,
+    wifi_connected: bool,
+    has_ip: bool,
+    mqtt_connected: bool,
+    /// Letzter Fehlschlag seit der letzten erfolgreichen Verbindung
+    last_failure: Option<DiagnoseCode>,
+}
+
+impl ConnectivityDiagnosis {
+    pub const fn new(require_mqtt: bool) -> Self {
+        Self {
+            require_mqtt,
+            wifi_connected: false,
+            has_ip: false,
+            mqtt_connected: false,
+            last_failure: None,
+        }
+    }
+
+    /// Übernimmt ein Ereignis
+    pub fn handle(&mut self, event: &SystemEvent) {
+        match *event {
+            SystemEvent::WifiConnected => self.wifi_connected = true,
+            SystemEvent::WifiDisconnected => {
+                *self = Self {
+                    last_failure: Some(DiagnoseCode::NoWifi),
+                    ..Self::new(self.require_mqtt)
+                };
+            }
+            SystemEvent::IpAcquired { .. } => {
+                self.has_ip = true;
+                if !self.require_mqtt {
+                    self.last_failure = None;
+                }
+            }
+            SystemEvent::MqttConnected => {
+                self.mqtt_connected = true;
+                self.last_failure = None;
+            }
+            SystemEvent::MqttDisconnected => self.mqtt_connected = false,
+            SystemEvent::Error(error) => {
+                if let Some(code) = self.classify(&error) {
+                    self.last_failure = Some(code);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Ordnet einen Fehler einer Ursache zu, `None` = kein Verbindungsproblem
+    ///
+    /// MQTT-Fehler einer bestehenden Sitzung (Publish, Abo) zählen nicht als
+    /// Ablehnung durch den Broker.
+    fn classify(&self, error: &FirmwareError) -> Option<DiagnoseCode> {
+        match (error.source, error.kind) {
+            (ErrorSource::Wifi, _) => Some(DiagnoseCode::NoWifi),
+            (_, ErrorKind::DnsFailed) => Some(DiagnoseCode::DnsFailed),
+            (
+                ErrorSource::Mqtt,
+                ErrorKind::ConnectionFailed
+                | ErrorKind::ProtocolViolation
+                | ErrorKind::Unauthorized,
+            ) if !self.mqtt_connected => Some(DiagnoseCode::BrokerRefused),
+            _ => None,
+        }
+    }
+
+    /// Anzuzeigender Code, `None` = kein Problem bekannt
+    ///
+    /// Ein aktuelles Problem (kein WLAN, keine IP-Adresse) geht vor, sonst
+    /// gilt der letzte Fehlschlag seit der letzten erfolgreichen Verbindung.
+    pub fn code(&self) -> Option<DiagnoseCode> {
+        if !self.wifi_connected {
+            Some(DiagnoseCode::NoWifi)
+        } else if !self.has_ip {
+            Some(DiagnoseCode::NoIp)
+        } else {
+            self.last_failure
+        }
+    }
+}
+
+/// Blinkfolge eines Diagnose-Codes ab `start_ms`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiagnoseBlink {
+    pub code: Option<DiagnoseCode>,
+    pub start_ms: u64,
+}
+
+impl DiagnoseBlink {
+    pub const fn new(code: Option<DiagnoseCode>, start_ms: u64) -> Self {
+        Self { code, start_ms }
+    }
+
+    /// Gesamtdauer der Anzeige
+    pub fn duration_ms(&self) -> u64 {
+        match self.code {
+            Some(code) => DIAGNOSE_REPEATS * period_ms(code),
+            None => OK_DURATION_MS,
+        }
+    }
+
+    /// Farbe aller Pixel zum Zeitpunkt `now_ms`, `None` = Anzeige beendet
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::{DiagnoseBlink, DiagnoseCode};
+    /// # use rgb::RGB8;
+    /// let blink = DiagnoseBlink::new(Some(DiagnoseCode::NoIp), 0);
+    /// assert_eq!(blink.frame(1500, 64), Some(RGB8::new(64, 0, 0)));
+    /// assert_eq!(blink.frame(1900, 64), Some(RGB8::default()));
+    /// assert_eq!(blink.frame(blink.duration_ms(), 64), None);
+    /// ```
+    pub fn frame(&self, now_ms: u64, brightness: u8) -> Option<RGB8> {
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        if elapsed >= self.duration_ms() {
+            return None;
+        }
+        let Some(code) = self.code else {
+            return Some(RGB8::new(0, brightness, 0));
+        };
+        let lit = (elapsed % period_ms(code))
+            .checked_sub(PAUSE_MS)
+            .is_some_and(|flash| flash % (FLASH_ON_MS + FLASH_OFF_MS) < FLASH_ON_MS);
+        Some(if lit {
+            RGB8::new(brightness, 0, 0)
+        } else {
+            RGB8::default()
+        })
+    }
+}
+
+/// Dauer einer Wiederholung: Pause, dann `number` Blitze
+fn period_ms(code: DiagnoseCode) -> u64 {
+    PAUSE_MS + u64::from(code.number()) * (FLASH_ON_MS + FLASH_OFF_MS)
+}
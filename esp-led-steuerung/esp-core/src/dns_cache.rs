@@ -0,0 +1,101 @@
+//! Cache für die aufgelöste Broker-Adresse
+//!
+//! Der MQTT-Task löst den Broker-Hostnamen nur auf, wenn kein gültiger
+//! Eintrag vorliegt: beim ersten Connect, nach Ablauf der TTL oder nachdem
+//! eine Verbindung zur gecachten Adresse fehlgeschlagen ist
+//! ([`DnsCache::invalidate`]). Reconnects nach Verbindungsabbrüchen sparen
+//! sich so die DNS-Anfrage. embassy-net liefert keine TTL aus der Antwort,
+//! daher gilt eine feste Lebensdauer.
+
+/// Gecachte Adresse mit Zeitpunkt der Auflösung
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DnsEntry {
+    pub address: [u8; 4],
+    pub resolved_ms: u64,
+}
+
+/// Zustand des Caches (für die Debug-Konsole)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnsCacheState {
+    /// Noch nie aufgelöst oder nach Verbindungsfehler verworfen
+    Empty,
+    Valid {
+        address: [u8; 4],
+        remaining_ms: u64,
+    },
+    /// TTL abgelaufen, wird beim nächsten Connect neu aufgelöst
+    Expired {
+        address: [u8; 4],
+    },
+}
+
+/// DNS-Cache mit fester TTL und Zählern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DnsCache {
+    entry: Option<DnsEntry>,
+    ttl_ms: u64,
+    /// Durchgeführte Auflösungen
+    pub resolutions: u32,
+    /// Connects mit gecachter Adresse
+    pub hits: u32,
+    /// Nach Verbindungsfehler verworfene Einträge
+    pub invalidations: u32,
+}
+
+impl DnsCache {
+    pub const fn new(ttl_ms: u64) -> Self {
+        Self {
+            entry: None,
+            ttl_ms,
+            resolutions: 0,
+            hits: 0,
+            invalidations: 0,
+        }
+    }
+
+    /// Gültige Adresse für einen Connect (zählt als Treffer)
+    pub fn lookup(&mut self, now_ms: u64) -> Option<[u8; 4]> {
+        match self.state(now_ms) {
+            DnsCacheState::Valid { address, .. } => {
+                self.hits = self.hits.saturating_add(1);
+                Some(address)
+            }
+            _ => None,
+        }
+    }
+
+    /// Übernimmt eine frisch aufgelöste Adresse
+    pub fn store(&mut self, address: [u8; 4], now_ms: u64) {
+        self.entry = Some(DnsEntry {
+            address,
+            resolved_ms: now_ms,
+        });
+        self.resolutions = self.resolutions.saturating_add(1);
+    }
+
+    /// Verwirft den Eintrag (Verbindung zur Adresse fehlgeschlagen)
+    pub fn invalidate(&mut self) {
+        if self.entry.take().is_some() {
+            self.invalidations = self.invalidations.saturating_add(1);
+        }
+    }
+
+    pub fn state(&self, now_ms: u64) -> DnsCacheState {
+        match self.entry {
+            None => DnsCacheState::Empty,
+            Some(entry) => {
+                let age_ms = now_ms.saturating_sub(entry.resolved_ms);
+                if age_ms < self.ttl_ms {
+                    DnsCacheState::Valid {
+                        address: entry.address,
+                        remaining_ms: self.ttl_ms - age_ms,
+                    }
+                } else {
+                    DnsCacheState::Expired {
+                        address: entry.address,
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Live-Parameter des laufenden Effekts
+//!
+//! `set_effect_param` ändert Tempo, Intensität, Palette usw. des gerade
+//! laufenden Effekts, ohne ihn neu zu starten (Regler in der Web UI):
+//!
+//! ```text
+//! {"type":"set_effect_param","name":"speed","value":40}
+//! {"type":"set_effect_param","name":"palette","colors":"rot #FF8000 blau"}
+//! param speed 40
+//! ```
+//!
+//! Welche Parameter ein Effekt annimmt, erklärt er selbst über
+//! [`LedEffect::params`] (Schema wie im Katalog, siehe [`crate::effects`]).
+//! [`LedEffect::apply_param`] prüft den Wert dagegen und übernimmt ihn.
+
+use rgb::RGB8;
+
+use crate::effects::{EffectId, EffectParam};
+use crate::matrix::MatrixLayout;
+use crate::sequence::{ColorSequence, parse_color_word};
+
+/// Name eines Live-Parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParamName {
+    /// Tempo (Einheit je nach Effekt)
+    Speed,
+    /// Intensität (Bedeutung je nach Effekt)
+    Intensity,
+    /// Schweiflänge in Pixeln
+    Tail,
+    /// Einzelne Farbe
+    Color,
+    /// Farbliste (Syntax siehe [`crate::sequence`])
+    Palette,
+}
+
+impl ParamName {
+    /// Name in Kommandos und im Katalog
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParamName::Speed => "speed",
+            ParamName::Intensity => "intensity",
+            ParamName::Tail => "tail",
+            ParamName::Color => "color",
+            ParamName::Palette => "palette",
+        }
+    }
+
+    /// Parst einen Parameternamen (Groß-/Kleinschreibung egal)
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            ParamName::Speed,
+            ParamName::Intensity,
+            ParamName::Tail,
+            ParamName::Color,
+            ParamName::Palette,
+        ]
+        .into_iter()
+        .find(|param| param.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Neuer Wert eines Live-Parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ParamValue {
+    Speed(u8),
+    Intensity(u8),
+    Tail(u8),
+    Color(RGB8),
+    Palette(ColorSequence),
+}
+
+impl ParamValue {
+    /// Ganzzahliger Parameter `name` (nur `speed`, `intensity`, `tail`)
+    pub fn integer(name: ParamName, value: u32) -> Result<Self, ParamError> {
+        let value = u8::try_from(value).map_err(|_| ParamError::OutOfRange)?;
+        match name {
+            ParamName::Speed => Ok(ParamValue::Speed(value)),
+            ParamName::Intensity => Ok(ParamValue::Intensity(value)),
+            ParamName::Tail => Ok(ParamValue::Tail(value)),
+            ParamName::Color | ParamName::Palette => Err(ParamError::InvalidValue),
+        }
+    }
+
+    /// Parameter `name` mit Wert als Text (Zahl, Farbe oder Farbliste)
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::effect_param::{ParamName, ParamValue};
+    /// assert_eq!(
+    ///     ParamValue::parse(ParamName::Speed, "40"),
+    ///     Ok(ParamValue::Speed(40))
+    /// );
+    /// ```
+    pub fn parse(name: ParamName, value: &str) -> Result<Self, ParamError> {
+        match name {
+            ParamName::Color => parse_color_word(value)
+                .map(ParamValue::Color)
+                .ok_or(ParamError::InvalidValue),
+            ParamName::Palette => ColorSequence::parse(value)
+                .map(ParamValue::Palette)
+                .ok_or(ParamError::InvalidValue),
+            _ => {
+                let value = value.parse().map_err(|_| ParamError::InvalidValue)?;
+                Self::integer(name, value)
+            }
+        }
+    }
+
+    /// Name des Parameters
+    pub fn name(&self) -> ParamName {
+        match self {
+            ParamValue::Speed(_) => ParamName::Speed,
+            ParamValue::Intensity(_) => ParamName::Intensity,
+            ParamValue::Tail(_) => ParamName::Tail,
+            ParamValue::Color(_) => ParamName::Color,
+            ParamValue::Palette(_) => ParamName::Palette,
+        }
+    }
+
+    /// Zahlenwert für die Bereichsprüfung, `None` bei Farben
+    fn as_integer(&self) -> Option<u32> {
+        match *self {
+            ParamValue::Speed(value) | ParamValue::Intensity(value) | ParamValue::Tail(value) => {
+                Some(u32::from(value))
+            }
+            ParamValue::Color(_) | ParamValue::Palette(_) => None,
+        }
+    }
+}
+
+/// Fehler beim Setzen eines Live-Parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamError {
+    /// Unbekannter Parametername
+    UnknownName,
+    /// Wert passt nicht zum Parameter (keine Zahl, keine Farbe)
+    InvalidValue,
+    /// Wert außerhalb von `min..=max`
+    OutOfRange,
+    /// Der laufende Effekt hat diesen Parameter nicht
+    Unsupported,
+}
+
+impl ParamError {
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParamError::UnknownName => "unknown_param",
+            ParamError::InvalidValue => "invalid_value",
+            ParamError::OutOfRange => "out_of_range",
+            ParamError::Unsupported => "unsupported_param",
+        }
+    }
+}
+
+/// Pixelweiser Effekt mit live einstellbaren Parametern
+///
+/// Der LED-Task zeichnet ihn per [`render`](Self::render) und blendet beim
+/// Wechsel zwischen zwei Effekten über (siehe [`crate::crossfade`]).
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::effect_param::{LedEffect, ParamError, ParamValue};
+/// # use esp_core::Meteor;
+/// # use rgb::RGB8;
+/// let mut meteor = Meteor::new(0, RGB8::new(255, 255, 255), 30, 8);
+/// assert_eq!(meteor.apply_param(ParamValue::Speed(60), 1000), Ok(()));
+/// assert_eq!(meteor.speed, 60);
+/// assert_eq!(
+///     meteor.apply_param(ParamValue::Speed(0), 1000),
+///     Err(ParamError::OutOfRange)
+/// );
+/// ```
+pub trait LedEffect {
+    /// Kennung im Effekt-Katalog
+    fn id(&self) -> EffectId;
+
+    /// Live einstellbare Parameter mit Wertebereich
+    fn params(&self) -> &'static [EffectParam];
+
+    /// Übernimmt einen gegen [`params`](Self::params) geprüften Wert ab `now_ms`
+    fn set_param(&mut self, value: ParamValue, now_ms: u64);
+
+    /// Zeichnet das Bild zum Zeitpunkt `now_ms` in `pixels`
+    fn render(&self, now_ms: u64, layout: MatrixLayout, pixels: &mut [RGB8]);
+
+    /// Bild ändert sich mit der Zeit (sonst genügt seltenes Neuschreiben)
+    fn is_animated(&self) -> bool {
+        true
+    }
+
+    /// Prüft `value` gegen [`params`](Self::params) und übernimmt ihn
+    fn apply_param(&mut self, value: ParamValue, now_ms: u64) -> Result<(), ParamError> {
+        let name = value.name().name();
+        let param = self
+            .params()
+            .iter()
+            .find(|param| param.name == name)
+            .ok_or(ParamError::Unsupported)?;
+        if let Some(value) = value.as_integer() {
+            let below = param.min.is_some_and(|min| value < min);
+            let above = param.max.is_some_and(|max| value > max);
+            if below || above {
+                return Err(ParamError::OutOfRange);
+            }
+        }
+        self.set_param(value, now_ms);
+        Ok(())
+    }
+}
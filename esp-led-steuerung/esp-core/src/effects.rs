@@ -0,0 +1,334 @@
+//! Effekt-Katalog für Auswahllisten (GET /api/effects)
+//!
+//! Beschreibt alle Modi und Effekte der Lampe mit Name, Text-Kommando
+//! (siehe [`crate::command`]) und Parametern samt Typ und Wertebereich.
+//! Web UI und fremde Oberflächen füllen ihre Effekt-Auswahl daraus, statt
+//! Namen fest einzubauen. Den laufenden Effekt liefert
+//! [`LedState::active_effect`](crate::LedState::active_effect) bzw. für die
+//! pixelweisen Modi der LED-Task.
+//!
+//! # Beispiel
+//! ```
+//! # use esp_core::effects::{EffectId, effect_info};
+//! let sunrise = effect_info(EffectId::Sunrise);
+//! assert_eq!(sunrise.usage, Some("sunrise [<min>]"));
+//! assert_eq!(sunrise.params[0].default, Some(20));
+//! ```
+
+use crate::command::DEFAULT_SUNRISE_MINUTES;
+use crate::meteor::{DEFAULT_METEOR_SPEED, DEFAULT_METEOR_TAIL, Meteor};
+use crate::plasma::{DEFAULT_PLASMA_SPEED, Plasma};
+use crate::text::TEXT_LEN;
+
+/// Kennung eines Effekts (JSON: `snake_case`-Name)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[repr(u8)]
+pub enum EffectId {
+    /// Feste Farbe (manueller Modus, auch "aus")
+    Solid,
+    /// Auto-Rotation durch die Farbfolge
+    Auto,
+    /// Zufallsfarben statt der Farbfolge
+    Random,
+    /// Übergang zu einer Farbe
+    Fade,
+    /// Lichtwecker
+    Sunrise,
+    /// Testfarben für den Weißabgleich
+    Calibration,
+    /// Testmuster pro Pixel
+    TestPattern,
+    /// Lauftext auf der LED-Matrix
+    ScrollText,
+    /// Uhrzeit-Anzeige
+    Clock,
+    /// Statischer Farbverlauf über alle Pixel
+    Gradient,
+    /// Meteor mit ausblendendem Schweif
+    Meteor,
+    /// Plasma in den Farben der Farbfolge
+    Plasma,
+    /// UDP-Realtime-Frames (nur per Protokoll, kein Text-Kommando)
+    Realtime,
+}
+
+impl EffectId {
+    /// Alle Effekte in Katalog-Reihenfolge
+    pub const ALL: [EffectId; 13] = [
+        EffectId::Solid,
+        EffectId::Auto,
+        EffectId::Random,
+        EffectId::Fade,
+        EffectId::Sunrise,
+        EffectId::Calibration,
+        EffectId::TestPattern,
+        EffectId::ScrollText,
+        EffectId::Clock,
+        EffectId::Gradient,
+        EffectId::Meteor,
+        EffectId::Plasma,
+        EffectId::Realtime,
+    ];
+
+    /// Effekt aus Index (Umkehrung von `as u8`)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(value)).copied()
+    }
+
+    /// Name wie im JSON (für Logs)
+    pub fn name(self) -> &'static str {
+        match self {
+            EffectId::Solid => "solid",
+            EffectId::Auto => "auto",
+            EffectId::Random => "random",
+            EffectId::Fade => "fade",
+            EffectId::Sunrise => "sunrise",
+            EffectId::Calibration => "calibration",
+            EffectId::TestPattern => "test_pattern",
+            EffectId::ScrollText => "scroll_text",
+            EffectId::Clock => "clock",
+            EffectId::Gradient => "gradient",
+            EffectId::Meteor => "meteor",
+            EffectId::Plasma => "plasma",
+            EffectId::Realtime => "realtime",
+        }
+    }
+}
+
+/// Typ eines Effekt-Parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ParamKind {
+    /// Ganzzahl in `min..=max`
+    Integer,
+    /// Freier Text, höchstens `max` Bytes UTF-8
+    Text,
+    /// Einer der Werte aus `values`
+    Choice,
+}
+
+/// Parameter eines Effekts (Position im Text-Kommando = Reihenfolge in `params`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EffectParam {
+    pub name: &'static str,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: ParamKind,
+    /// Muss angegeben werden (sonst gilt `default` bzw. das Verhalten des Effekts)
+    pub required: bool,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub min: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub max: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub default: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "no_values"))]
+    pub values: &'static [&'static str],
+}
+
+#[cfg(feature = "serde")]
+fn no_values(values: &&'static [&'static str]) -> bool {
+    values.is_empty()
+}
+
+#[cfg(feature = "serde")]
+fn no_params(params: &&'static [EffectParam]) -> bool {
+    params.is_empty()
+}
+
+impl EffectParam {
+    pub(crate) const fn integer(name: &'static str, required: bool, min: u32) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Integer,
+            required,
+            min: Some(min),
+            max: None,
+            default: None,
+            values: &[],
+        }
+    }
+
+    /// Freier Text ohne Längenangabe (z.B. Farben, Syntax wie bei `sequence`)
+    pub(crate) const fn text(name: &'static str, required: bool) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Text,
+            required,
+            min: None,
+            max: None,
+            default: None,
+            values: &[],
+        }
+    }
+
+    const fn choice(name: &'static str, required: bool, values: &'static [&'static str]) -> Self {
+        Self {
+            name,
+            kind: ParamKind::Choice,
+            required,
+            min: None,
+            max: None,
+            default: None,
+            values,
+        }
+    }
+}
+
+/// Katalog-Eintrag eines Effekts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EffectInfo {
+    #[cfg_attr(feature = "serde", serde(rename = "name"))]
+    pub id: EffectId,
+    /// Aufruf als Text-Kommando (MQTT, CoAP, Konsole), `None` = nicht per Kommando startbar
+    pub usage: Option<&'static str>,
+    pub params: &'static [EffectParam],
+    /// Live per `set_effect_param` einstellbar, siehe [`crate::effect_param`]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "no_params"))]
+    pub live_params: &'static [EffectParam],
+}
+
+/// Farbnamen der Text-Kommandos (Deutsch und Englisch werden angenommen)
+const COLORS: &[&str] = &["rot", "grün", "blau", "aus"];
+
+/// Alle Effekte, Reihenfolge wie [`EffectId::ALL`]
+pub static EFFECTS: [EffectInfo; 13] = [
+    EffectInfo {
+        id: EffectId::Solid,
+        usage: Some("<color> [<transition_ms>]"),
+        params: &[
+            EffectParam::choice("color", true, COLORS),
+            EffectParam::integer("transition_ms", false, 0),
+        ],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Auto,
+        usage: Some("auto"),
+        params: &[],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Random,
+        usage: Some("random"),
+        params: &[],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Fade,
+        usage: Some("<color> <transition_ms>"),
+        params: &[
+            EffectParam::choice("color", true, COLORS),
+            EffectParam::integer("transition_ms", true, 1),
+        ],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Sunrise,
+        usage: Some("sunrise [<min>]"),
+        params: &[EffectParam {
+            default: Some(DEFAULT_SUNRISE_MINUTES),
+            ..EffectParam::integer("min", false, 1)
+        }],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Calibration,
+        usage: Some("calibrate"),
+        params: &[],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::TestPattern,
+        usage: Some("testpattern"),
+        params: &[],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::ScrollText,
+        usage: Some("text <message>"),
+        params: &[EffectParam {
+            name: "message",
+            kind: ParamKind::Text,
+            required: true,
+            min: Some(1),
+            max: Some(TEXT_LEN as u32),
+            default: None,
+            values: &[],
+        }],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Clock,
+        usage: Some("clock [12|24]"),
+        params: &[EffectParam::choice("hours", false, &["12", "24"])],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Gradient,
+        usage: Some("gradient <color> <color>..."),
+        params: &[EffectParam {
+            name: "colors",
+            kind: ParamKind::Text,
+            required: true,
+            // Farbliste wie bei `sequence`, 2 bis 8 Farben
+            min: None,
+            max: None,
+            default: None,
+            values: &[],
+        }],
+        live_params: &[],
+    },
+    EffectInfo {
+        id: EffectId::Meteor,
+        usage: Some("meteor [<speed> [<tail> [<color>]]]"),
+        params: &[
+            EffectParam {
+                max: Some(u8::MAX as u32),
+                default: Some(DEFAULT_METEOR_SPEED as u32),
+                ..EffectParam::integer("speed", false, 1)
+            },
+            EffectParam {
+                max: Some(u8::MAX as u32),
+                default: Some(DEFAULT_METEOR_TAIL as u32),
+                ..EffectParam::integer("tail", false, 0)
+            },
+            // Farbe wie bei `sequence`, fehlt = aktuelle Farbe
+            EffectParam {
+                name: "color",
+                kind: ParamKind::Text,
+                required: false,
+                min: None,
+                max: None,
+                default: None,
+                values: &[],
+            },
+        ],
+        live_params: Meteor::PARAMS,
+    },
+    EffectInfo {
+        id: EffectId::Plasma,
+        usage: Some("plasma [<speed>]"),
+        params: &[EffectParam {
+            max: Some(u8::MAX as u32),
+            default: Some(DEFAULT_PLASMA_SPEED as u32),
+            ..EffectParam::integer("speed", false, 0)
+        }],
+        live_params: Plasma::PARAMS,
+    },
+    EffectInfo {
+        id: EffectId::Realtime,
+        usage: None,
+        params: &[],
+        live_params: &[],
+    },
+];
+
+/// Katalog-Eintrag zu einem Effekt
+pub fn effect_info(id: EffectId) -> &'static EffectInfo {
+    &EFFECTS[id as usize]
+}
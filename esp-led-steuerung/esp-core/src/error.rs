@@ -0,0 +1,327 @@
+//! Gemeinsame Fehler-Taxonomie aller Tasks
+//!
+//! Jeder Task behält seine private Fehler-Enum (z.B. `MqttError`) für Details
+//! im Log, meldet nach außen aber einen [`FirmwareError`]: Komponente + Art.
+//! Daraus leiten sich Kategorie und Schweregrad ab, die Event-Bus,
+//! API-Fehlerantworten und Telemetrie-Zähler gemeinsam nutzen.
+//!
+//! ```text
+//! FirmwareError { source, kind }
+//!                         └─ ErrorKind ─→ ErrorCategory ─→ Severity
+//! ```
+
+use crate::coap::CoapError;
+use crate::osc::OscError;
+use crate::preset::PresetError;
+use crate::schedule::ScheduleError;
+use crate::traits::LedError;
+
+/// Komponente, in der ein Fehler aufgetreten ist
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSource {
+    Wifi,
+    Network,
+    Mqtt,
+    Http,
+    Led,
+    Storage,
+    Schedule,
+    Preset,
+    Sntp,
+    Mdns,
+    Coap,
+    Osc,
+}
+
+impl ErrorSource {
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorSource::Wifi => "wifi",
+            ErrorSource::Network => "network",
+            ErrorSource::Mqtt => "mqtt",
+            ErrorSource::Http => "http",
+            ErrorSource::Led => "led",
+            ErrorSource::Storage => "storage",
+            ErrorSource::Schedule => "schedule",
+            ErrorSource::Preset => "preset",
+            ErrorSource::Sntp => "sntp",
+            ErrorSource::Mdns => "mdns",
+            ErrorSource::Coap => "coap",
+            ErrorSource::Osc => "osc",
+        }
+    }
+}
+
+/// Art des Fehlers (komponentenübergreifend)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Hostname konnte nicht aufgelöst werden
+    DnsFailed,
+    /// Verbindung konnte nicht aufgebaut werden oder ist abgebrochen
+    ConnectionFailed,
+    /// Socket-Bind oder Multicast-Join fehlgeschlagen
+    BindFailed,
+    /// Senden fehlgeschlagen
+    SendFailed,
+    /// Empfangen fehlgeschlagen
+    ReceiveFailed,
+    /// Keine Antwort innerhalb der Frist
+    Timeout,
+    /// Gegenstelle verletzt das Protokoll
+    ProtocolViolation,
+    /// Ungültige Eingabe (Kommando, JSON, Parameter)
+    InvalidInput,
+    /// Angeforderter Eintrag existiert nicht
+    NotFound,
+    /// Fehlendes oder falsches Token
+    Unauthorized,
+    /// Feste Kapazität erschöpft (Tabelle voll, Buffer zu klein)
+    CapacityExceeded,
+    /// Peripherie (RMT, Flash) meldet einen Fehler
+    HardwareFailure,
+    /// Empfänger ausgelastet (Warteschlange voll), später erneut versuchen
+    Busy,
+}
+
+/// Oberkategorie eines Fehlers (für Zähler und Status-Codes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// Netzwerk: WLAN, DNS, Sockets
+    Network,
+    /// Protokoll-Verletzung der Gegenstelle
+    Protocol,
+    /// Fehlerhafte Eingabe eines Clients
+    Input,
+    /// Speicher oder Tabellen voll
+    Resource,
+    /// Peripherie-Fehler
+    Hardware,
+}
+
+impl ErrorCategory {
+    /// Anzahl Kategorien (Größe von [`ErrorCounters`])
+    pub const COUNT: usize = 5;
+
+    /// Alle Kategorien in Index-Reihenfolge
+    pub const ALL: [ErrorCategory; Self::COUNT] = [
+        ErrorCategory::Network,
+        ErrorCategory::Protocol,
+        ErrorCategory::Input,
+        ErrorCategory::Resource,
+        ErrorCategory::Hardware,
+    ];
+
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorCategory::Network => "network",
+            ErrorCategory::Protocol => "protocol",
+            ErrorCategory::Input => "input",
+            ErrorCategory::Resource => "resource",
+            ErrorCategory::Hardware => "hardware",
+        }
+    }
+}
+
+/// Schweregrad (aufsteigend sortiert)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Client-Fehler, das Gerät arbeitet normal weiter
+    Info,
+    /// Vorübergehend, wird automatisch wiederholt (Reconnect)
+    Warning,
+    /// Funktion beeinträchtigt, kein automatischer Ausweg
+    Error,
+}
+
+impl ErrorKind {
+    /// Oberkategorie
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ErrorKind::DnsFailed
+            | ErrorKind::ConnectionFailed
+            | ErrorKind::BindFailed
+            | ErrorKind::SendFailed
+            | ErrorKind::ReceiveFailed
+            | ErrorKind::Timeout => ErrorCategory::Network,
+            ErrorKind::ProtocolViolation => ErrorCategory::Protocol,
+            ErrorKind::InvalidInput | ErrorKind::NotFound | ErrorKind::Unauthorized => {
+                ErrorCategory::Input
+            }
+            ErrorKind::CapacityExceeded | ErrorKind::Busy => ErrorCategory::Resource,
+            ErrorKind::HardwareFailure => ErrorCategory::Hardware,
+        }
+    }
+
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::DnsFailed => "dns_failed",
+            ErrorKind::ConnectionFailed => "connection_failed",
+            ErrorKind::BindFailed => "bind_failed",
+            ErrorKind::SendFailed => "send_failed",
+            ErrorKind::ReceiveFailed => "receive_failed",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::ProtocolViolation => "protocol_violation",
+            ErrorKind::InvalidInput => "invalid_input",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::Unauthorized => "unauthorized",
+            ErrorKind::CapacityExceeded => "capacity_exceeded",
+            ErrorKind::HardwareFailure => "hardware_failure",
+            ErrorKind::Busy => "busy",
+        }
+    }
+}
+
+/// Fehler einer Komponente (klein und `Copy`, passt in den Event-Bus)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FirmwareError {
+    pub source: ErrorSource,
+    pub kind: ErrorKind,
+}
+
+impl FirmwareError {
+    pub const fn new(source: ErrorSource, kind: ErrorKind) -> Self {
+        Self { source, kind }
+    }
+
+    /// Oberkategorie (siehe [`ErrorKind::category`])
+    pub fn category(&self) -> ErrorCategory {
+        self.kind.category()
+    }
+
+    /// Schweregrad
+    ///
+    /// - Eingabe-Fehler betreffen nur den Client → `Info`
+    /// - Netzwerk und Protokoll werden per Reconnect behoben → `Warning`
+    /// - Hardware und volle Ressourcen → `Error`, außer `Busy` (vorübergehend) → `Warning`
+    pub fn severity(&self) -> Severity {
+        if self.kind == ErrorKind::Busy {
+            return Severity::Warning;
+        }
+        match self.category() {
+            ErrorCategory::Input => Severity::Info,
+            ErrorCategory::Network | ErrorCategory::Protocol => Severity::Warning,
+            ErrorCategory::Resource | ErrorCategory::Hardware => Severity::Error,
+        }
+    }
+
+    /// HTTP-Status-Code für API-Fehlerantworten
+    pub fn http_status(&self) -> u16 {
+        match self.kind {
+            ErrorKind::InvalidInput => 400,
+            ErrorKind::Unauthorized => 401,
+            ErrorKind::NotFound => 404,
+            ErrorKind::CapacityExceeded => 507,
+            ErrorKind::Timeout => 504,
+            ErrorKind::Busy => 503,
+            ErrorKind::DnsFailed | ErrorKind::ConnectionFailed | ErrorKind::ProtocolViolation => {
+                502
+            }
+            _ => 500,
+        }
+    }
+}
+
+// ============================================================================
+// Konvertierungen aus den Fehler-Typen von esp-core
+// ============================================================================
+
+impl From<LedError> for FirmwareError {
+    fn from(error: LedError) -> Self {
+        match error {
+            LedError::WriteFailed => Self::new(ErrorSource::Led, ErrorKind::HardwareFailure),
+        }
+    }
+}
+
+impl From<ScheduleError> for FirmwareError {
+    fn from(error: ScheduleError) -> Self {
+        let kind = match error {
+            ScheduleError::Full => ErrorKind::CapacityExceeded,
+            ScheduleError::InvalidEntry => ErrorKind::InvalidInput,
+            ScheduleError::NotFound => ErrorKind::NotFound,
+        };
+        Self::new(ErrorSource::Schedule, kind)
+    }
+}
+
+impl From<PresetError> for FirmwareError {
+    fn from(error: PresetError) -> Self {
+        let kind = match error {
+            PresetError::Full => ErrorKind::CapacityExceeded,
+            PresetError::InvalidPreset => ErrorKind::InvalidInput,
+            PresetError::NotFound => ErrorKind::NotFound,
+        };
+        Self::new(ErrorSource::Preset, kind)
+    }
+}
+
+impl From<CoapError> for FirmwareError {
+    fn from(error: CoapError) -> Self {
+        let kind = match error {
+            CoapError::BufferTooSmall => ErrorKind::CapacityExceeded,
+            CoapError::Truncated
+            | CoapError::UnsupportedVersion
+            | CoapError::MalformedOption
+            | CoapError::UnknownCriticalOption => ErrorKind::ProtocolViolation,
+        };
+        Self::new(ErrorSource::Coap, kind)
+    }
+}
+
+impl From<OscError> for FirmwareError {
+    fn from(_: OscError) -> Self {
+        Self::new(ErrorSource::Osc, ErrorKind::ProtocolViolation)
+    }
+}
+
+/// Fehler-Zähler pro Kategorie (für Telemetrie)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ErrorCounters {
+    counts: [u32; ErrorCategory::COUNT],
+}
+
+impl ErrorCounters {
+    pub const fn new() -> Self {
+        Self {
+            counts: [0; ErrorCategory::COUNT],
+        }
+    }
+
+    /// Zählt einen Fehler (sättigend)
+    pub fn record(&mut self, error: &FirmwareError) {
+        let count = &mut self.counts[error.category() as usize];
+        *count = count.saturating_add(1);
+    }
+
+    /// Anzahl Fehler einer Kategorie
+    pub fn get(&self, category: ErrorCategory) -> u32 {
+        self.counts[category as usize]
+    }
+
+    /// Anzahl aller Fehler
+    pub fn total(&self) -> u32 {
+        self.counts
+            .iter()
+            .fold(0u32, |sum, count| sum.saturating_add(*count))
+    }
+}
+
+// ============================================================================
+// defmt::Format Implementations (optional feature)
+// ============================================================================
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for FirmwareError {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "{}: {} ({})",
+            self.source.name(),
+            self.kind.name(),
+            self.category().name()
+        )
+    }
+}
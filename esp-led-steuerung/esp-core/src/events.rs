@@ -0,0 +1,152 @@
+//! System-Ereignisse (Lebenszyklus von WLAN, MQTT, Clients, ...)
+//!
+//! Werden über einen eigenen PubSub-Channel verteilt, damit Status-LED,
+//! Telemetrie und Web UI auf Ereignisse reagieren können, ohne dass die
+//! auslösenden Tasks sie kennen müssen.
+
+use crate::error::FirmwareError;
+use crate::task_stats::TaskId;
+
+/// Art eines verbundenen Clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientKind {
+    WebSocket,
+    Console,
+}
+
+/// Auslöser eines angeforderten Neustarts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebootSource {
+    /// `POST /api/system/reboot`
+    Http,
+    /// MQTT-Topic `.../cmd/restart`
+    Mqtt,
+    /// Konsolen-Kommando `reboot`
+    Console,
+    /// Langer Druck auf den BOOT-Taster (Werksreset)
+    Button,
+}
+
+impl RebootSource {
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            RebootSource::Http => "http",
+            RebootSource::Mqtt => "mqtt",
+            RebootSource::Console => "console",
+            RebootSource::Button => "button",
+        }
+    }
+}
+
+/// Lebenszyklus-Ereignis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemEvent {
+    /// Mit dem Access Point verbunden
+    WifiConnected,
+    /// Verbindung zum Access Point verloren
+    WifiDisconnected,
+    /// IPv4-Adresse per DHCP erhalten
+    IpAcquired { address: [u8; 4] },
+    /// MQTT-Broker hat CONNECT bestätigt
+    MqttConnected,
+    /// MQTT-Verbindung beendet (Reconnect folgt)
+    MqttDisconnected,
+    /// Nach wiederholten Fehlversuchen auf einen anderen Broker gewechselt
+    /// (Index in der Broker-Liste, siehe [`crate::broker`])
+    MqttBrokerSwitched { index: u8 },
+    /// Digitaler Eingang hat den Zustand gewechselt (Index, siehe [`crate::inputs`])
+    InputChanged { input: u8, active: bool },
+    /// Firmware-Update gestartet
+    OtaStarted,
+    /// Client hat sich verbunden
+    ClientConnected { kind: ClientKind },
+    /// Fehler in einer Komponente (Taxonomie siehe [`crate::error`])
+    Error(FirmwareError),
+    /// Task hat seine Frist ohne Lebenszeichen überschritten (siehe [`crate::stall`])
+    TaskStalled { task: TaskId, idle_ms: u64 },
+    /// Zuvor hängender Task meldet sich wieder
+    TaskRecovered { task: TaskId },
+    /// Neustart angefordert: Einstellungen werden gesichert (beim Werksreset
+    /// gelöscht), Verbindungen sauber beendet, danach folgt der Reset
+    RebootRequested {
+        source: RebootSource,
+        factory_reset: bool,
+    },
+}
+
+impl SystemEvent {
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            SystemEvent::WifiConnected => "wifi_connected",
+            SystemEvent::WifiDisconnected => "wifi_disconnected",
+            SystemEvent::IpAcquired { .. } => "ip_acquired",
+            SystemEvent::MqttConnected => "mqtt_connected",
+            SystemEvent::MqttDisconnected => "mqtt_disconnected",
+            SystemEvent::MqttBrokerSwitched { .. } => "mqtt_broker_switched",
+            SystemEvent::InputChanged { .. } => "input_changed",
+            SystemEvent::OtaStarted => "ota_started",
+            SystemEvent::ClientConnected { .. } => "client_connected",
+            SystemEvent::Error(_) => "error",
+            SystemEvent::TaskStalled { .. } => "task_stalled",
+            SystemEvent::TaskRecovered { .. } => "task_recovered",
+            SystemEvent::RebootRequested { .. } => "reboot_requested",
+        }
+    }
+
+    /// Prüft ob das Ereignis einen Fehler meldet
+    pub fn is_error(&self) -> bool {
+        matches!(self, SystemEvent::Error(_))
+    }
+}
+
+// ============================================================================
+// defmt::Format Implementations (optional feature)
+// ============================================================================
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for SystemEvent {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            SystemEvent::IpAcquired { address } => defmt::write!(
+                fmt,
+                "IpAcquired {{ {}.{}.{}.{} }}",
+                address[0],
+                address[1],
+                address[2],
+                address[3]
+            ),
+            SystemEvent::ClientConnected { kind } => {
+                let kind = match kind {
+                    ClientKind::WebSocket => "websocket",
+                    ClientKind::Console => "console",
+                };
+                defmt::write!(fmt, "ClientConnected {{ {} }}", kind)
+            }
+            SystemEvent::MqttBrokerSwitched { index } => {
+                defmt::write!(fmt, "MqttBrokerSwitched {{ {} }}", index)
+            }
+            SystemEvent::InputChanged { input, active } => {
+                defmt::write!(fmt, "InputChanged {{ {}, active: {} }}", input, active)
+            }
+            SystemEvent::Error(error) => defmt::write!(fmt, "Error {{ {} }}", error),
+            SystemEvent::TaskStalled { task, idle_ms } => {
+                defmt::write!(fmt, "TaskStalled {{ {}, {}ms }}", task, idle_ms)
+            }
+            SystemEvent::TaskRecovered { task } => {
+                defmt::write!(fmt, "TaskRecovered {{ {} }}", task)
+            }
+            SystemEvent::RebootRequested {
+                source,
+                factory_reset,
+            } => defmt::write!(
+                fmt,
+                "RebootRequested {{ {}, factory_reset: {} }}",
+                source.name(),
+                factory_reset
+            ),
+            other => defmt::write!(fmt, "{}", other.name()),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Farbverlauf über den Strip
+//!
+//! Statischer Effekt für Akzentbeleuchtung: zwei bis [`GRADIENT_MAX_COLORS`]
+//! Farben, gleichmäßig über alle Pixel verteilt und dazwischen linear
+//! interpoliert ([`crate::lerp_color`], wie die Verläufe in
+//! [`crate::transition`]). Erster Pixel = erste Farbe, letzter Pixel = letzte
+//! Farbe.
+//!
+//! Text-Syntax (MQTT, Konsole, WebSocket) wie bei [`crate::sequence`]:
+//!
+//! ```text
+//! gradient #FF8000 rot 0,0,255
+//! ```
+
+use rgb::RGB8;
+
+use crate::color::{PERMILLE_MAX, lerp_color};
+use crate::effect_param::{LedEffect, ParamValue};
+use crate::effects::{EffectId, EffectParam};
+use crate::matrix::MatrixLayout;
+use crate::sequence::{ColorSequence, SEQUENCE_MAX_COLORS};
+
+/// Maximale Anzahl Farben eines Verlaufs
+pub const GRADIENT_MAX_COLORS: usize = SEQUENCE_MAX_COLORS;
+
+/// Farbverlauf aus mindestens zwei Farben
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gradient {
+    colors: ColorSequence,
+}
+
+impl Gradient {
+    /// Verlauf aus `colors`, `None` bei weniger als zwei oder mehr als
+    /// [`GRADIENT_MAX_COLORS`] Farben
+    pub fn new(colors: &[RGB8]) -> Option<Self> {
+        if colors.len() < 2 {
+            return None;
+        }
+        ColorSequence::new(colors).map(|colors| Self { colors })
+    }
+
+    /// Parst eine durch Leerzeichen getrennte Farbliste (siehe Modul-Doku)
+    pub fn parse(input: &str) -> Option<Self> {
+        Self::new(ColorSequence::parse(input)?.colors())
+    }
+
+    /// Farben in Reihenfolge
+    pub fn colors(&self) -> &[RGB8] {
+        self.colors.colors()
+    }
+
+    /// Derselbe Verlauf mit Helligkeit `brightness` skaliert (255 = unverändert)
+    pub fn scaled(&self, brightness: u8) -> Self {
+        Self {
+            colors: self.colors.scaled(brightness),
+        }
+    }
+
+    /// Farbe an Position `index` von `len` Pixeln
+    pub fn color_at(&self, index: usize, len: usize) -> RGB8 {
+        let colors = self.colors();
+        let Some(last) = len.checked_sub(1).filter(|&last| last > 0) else {
+            return colors[0];
+        };
+        // Position in Promille über alle Abschnitte (ein Abschnitt je Farbpaar)
+        let segments = colors.len() - 1;
+        let position = index.min(last) * segments * PERMILLE_MAX as usize / last;
+        let segment = (position / PERMILLE_MAX as usize).min(segments - 1);
+        let permille = (position - segment * PERMILLE_MAX as usize) as u16;
+        lerp_color(colors[segment], colors[segment + 1], permille)
+    }
+
+    /// Füllt `pixels` mit dem Verlauf
+    pub fn fill(&self, pixels: &mut [RGB8]) {
+        let len = pixels.len();
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            *pixel = self.color_at(index, len);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Gradient {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.colors())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Gradient {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_util::deserialize_colors(deserializer, "2 to 8 colors", Self::new)
+    }
+}
+
+/// Statisch und ohne Live-Parameter, damit der LED-Task dorthin überblenden kann
+impl LedEffect for Gradient {
+    fn id(&self) -> EffectId {
+        EffectId::Gradient
+    }
+
+    fn params(&self) -> &'static [EffectParam] {
+        &[]
+    }
+
+    fn set_param(&mut self, _value: ParamValue, _now_ms: u64) {}
+
+    fn render(&self, _now_ms: u64, _layout: MatrixLayout, pixels: &mut [RGB8]) {
+        self.fill(pixels);
+    }
+
+    fn is_animated(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,243 @@
+//! Gruppen-Zustand per ESP-NOW (ohne Access Point)
+//!
+//! Ergänzt den Multicast-Sync ([`crate::sync`]): statt Frames wird nur der
+//! Modus der Gruppe verteilt (aus, Auto-Rotation oder feste Farbe). Jede
+//! Änderung am eigenen Zustand wird als Broadcast gesendet, Empfänger
+//! übernehmen sie als [`LedCommand`] und leiten sie mit verringertem TTL
+//! weiter (einfaches Flooding-Mesh). So bleiben Lampen auch bei
+//! ausgefallenem Router und außerhalb der Funkreichweite des Absenders
+//! synchron.
+//!
+//! Duplikate erkennt [`GroupSync`] über Absender-ID und Sequenznummer.
+//! Übernommene Zustände werden nicht erneut als eigene Änderung gesendet.
+//!
+//! Paket-Format (16 Bytes, Big Endian):
+//!
+//! | Bytes | Inhalt                                       |
+//! |-------|----------------------------------------------|
+//! | 0-1   | Kennung `LG`                                 |
+//! | 2     | Protokoll-Version ([`GROUP_VERSION`])        |
+//! | 3-6   | Absender-ID (aus der MAC-Adresse)            |
+//! | 7-10  | Sequenznummer des Absenders                  |
+//! | 11    | Verbleibende Weiterleitungen (TTL)           |
+//! | 12    | Zustand: 0 = aus, 1 = Auto, 2 = Farbe        |
+//! | 13-15 | Farbe `r, g, b` (nur bei Zustand 2)          |
+
+use rgb::RGB8;
+
+use crate::types::{LedColorMessage, LedCommand, classify_color};
+
+/// Protokoll-Version, Pakete anderer Versionen werden verworfen
+pub const GROUP_VERSION: u8 = 1;
+
+/// Länge eines Gruppen-Pakets in Bytes
+pub const GROUP_PACKET_LEN: usize = 16;
+
+/// Weiterleitungen eines neuen Pakets (maximale Mesh-Tiefe)
+pub const GROUP_TTL: u8 = 3;
+
+/// Anzahl gemerkter Absender für die Duplikat-Erkennung
+pub const GROUP_MAX_ORIGINS: usize = 8;
+
+/// Absender ohne Pakete in dieser Zeit werden vergessen (Neustart setzt die Sequenz zurück)
+pub const GROUP_ORIGIN_TIMEOUT_MS: u64 = 10_000;
+
+/// Paket-Kennung
+const MAGIC: [u8; 2] = *b"LG";
+
+/// Verteilter Zustand der Gruppe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupState {
+    Off,
+    Auto,
+    Color(RGB8),
+}
+
+impl GroupState {
+    /// Zustand aus einem Farb-Broadcast des LED-Tasks
+    ///
+    /// Farbwechsel der Auto-Rotation ergeben denselben Zustand und werden
+    /// daher nicht gesendet.
+    pub fn from_message(msg: &LedColorMessage) -> Self {
+        match (msg.is_auto_mode, msg.color) {
+            (true, _) => GroupState::Auto,
+            (false, RGB8 { r: 0, g: 0, b: 0 }) => GroupState::Off,
+            (false, color) => GroupState::Color(color),
+        }
+    }
+
+    /// Kommando, das diesen Zustand am LED-Task herstellt
+    pub fn command(self) -> LedCommand {
+        match self {
+            GroupState::Off => LedCommand::Off,
+            GroupState::Auto => LedCommand::EnableAuto,
+            GroupState::Color(color) => LedCommand::SetColor {
+                target_color: color,
+                color_id: classify_color(color),
+            },
+        }
+    }
+}
+
+/// Ein Gruppen-Paket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupPacket {
+    pub origin: u32,
+    pub sequence: u32,
+    pub ttl: u8,
+    pub state: GroupState,
+}
+
+impl GroupPacket {
+    pub fn encode(&self) -> [u8; GROUP_PACKET_LEN] {
+        let mut packet = [0; GROUP_PACKET_LEN];
+        packet[..2].copy_from_slice(&MAGIC);
+        packet[2] = GROUP_VERSION;
+        packet[3..7].copy_from_slice(&self.origin.to_be_bytes());
+        packet[7..11].copy_from_slice(&self.sequence.to_be_bytes());
+        packet[11] = self.ttl;
+        packet[12] = match self.state {
+            GroupState::Off => 0,
+            GroupState::Auto => 1,
+            GroupState::Color(color) => {
+                packet[13..].copy_from_slice(&[color.r, color.g, color.b]);
+                2
+            }
+        };
+        packet
+    }
+
+    /// `None` bei falscher Länge, Kennung, Version oder unbekanntem Zustand
+    pub fn decode(packet: &[u8]) -> Option<Self> {
+        let packet: &[u8; GROUP_PACKET_LEN] = packet.try_into().ok()?;
+        if packet[..2] != MAGIC || packet[2] != GROUP_VERSION {
+            return None;
+        }
+        let state = match packet[12] {
+            0 => GroupState::Off,
+            1 => GroupState::Auto,
+            2 => GroupState::Color(RGB8::new(packet[13], packet[14], packet[15])),
+            _ => return None,
+        };
+        Some(Self {
+            origin: u32::from_be_bytes(packet[3..7].try_into().ok()?),
+            sequence: u32::from_be_bytes(packet[7..11].try_into().ok()?),
+            ttl: packet[11],
+            state,
+        })
+    }
+}
+
+/// Ergebnis eines empfangenen Pakets
+#[derive(Clone, Copy)]
+pub struct GroupReceived {
+    /// Anzuwendendes Kommando (`None`, wenn der Zustand schon gilt)
+    pub command: Option<LedCommand>,
+    /// Weiterzuleitendes Paket (`None` bei TTL 0)
+    pub forward: Option<GroupPacket>,
+}
+
+/// Zuletzt gesehene Sequenznummer eines Absenders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Origin {
+    id: u32,
+    sequence: u32,
+    seen_ms: u64,
+}
+
+/// Gruppen-Sync eines Knotens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupSync {
+    node_id: u32,
+    next_sequence: u32,
+    /// Zuletzt gesendeter oder übernommener Zustand
+    state: Option<GroupState>,
+    origins: [Option<Origin>; GROUP_MAX_ORIGINS],
+}
+
+impl GroupSync {
+    pub const fn new(node_id: u32) -> Self {
+        Self {
+            node_id,
+            next_sequence: 0,
+            state: None,
+            origins: [None; GROUP_MAX_ORIGINS],
+        }
+    }
+
+    pub fn node_id(&self) -> u32 {
+        self.node_id
+    }
+
+    /// Zuletzt gesendeter oder übernommener Zustand
+    pub fn state(&self) -> Option<GroupState> {
+        self.state
+    }
+
+    /// Eigener Zustand hat sich geändert → zu sendendes Paket
+    ///
+    /// `None`, wenn der Zustand schon gilt (z.B. gerade von der Gruppe übernommen).
+    pub fn local_update(&mut self, state: GroupState) -> Option<GroupPacket> {
+        if self.state == Some(state) {
+            return None;
+        }
+        self.state = Some(state);
+        let packet = GroupPacket {
+            origin: self.node_id,
+            sequence: self.next_sequence,
+            ttl: GROUP_TTL,
+            state,
+        };
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        Some(packet)
+    }
+
+    /// Verarbeitet ein empfangenes Paket zum Zeitpunkt `now_ms`
+    ///
+    /// `None` für eigene, doppelte und veraltete Pakete.
+    pub fn receive(&mut self, packet: &GroupPacket, now_ms: u64) -> Option<GroupReceived> {
+        if packet.origin == self.node_id || !self.remember(packet, now_ms) {
+            return None;
+        }
+        let command = (self.state != Some(packet.state)).then(|| packet.state.command());
+        self.state = Some(packet.state);
+        let forward = packet
+            .ttl
+            .checked_sub(1)
+            .map(|ttl| GroupPacket { ttl, ..*packet });
+        Some(GroupReceived { command, forward })
+    }
+
+    /// Merkt die Sequenznummer, `false` für bekannte oder ältere Pakete
+    fn remember(&mut self, packet: &GroupPacket, now_ms: u64) -> bool {
+        let expired =
+            |origin: &Origin| now_ms.saturating_sub(origin.seen_ms) > GROUP_ORIGIN_TIMEOUT_MS;
+        let known = self
+            .origins
+            .iter()
+            .position(|slot| slot.is_some_and(|origin| origin.id == packet.origin));
+        if let Some(origin) = known.and_then(|index| self.origins[index])
+            && !expired(&origin)
+            && (packet.sequence.wrapping_sub(origin.sequence) as i32) <= 0
+        {
+            return false;
+        }
+
+        // Neuer Absender: freier oder abgelaufener Platz, sonst der am längsten stille
+        let index = known.unwrap_or_else(|| {
+            (0..GROUP_MAX_ORIGINS)
+                .min_by_key(|&index| match self.origins[index] {
+                    None => (0, 0),
+                    Some(origin) if expired(&origin) => (1, origin.seen_ms),
+                    Some(origin) => (2, origin.seen_ms),
+                })
+                .unwrap_or(0)
+        });
+        self.origins[index] = Some(Origin {
+            id: packet.origin,
+            sequence: packet.sequence,
+            seen_ms: now_ms,
+        });
+        true
+    }
+}
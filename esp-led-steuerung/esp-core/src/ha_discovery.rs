@@ -0,0 +1,163 @@
+//! Home-Assistant-MQTT-Discovery für Diagnose-Sensoren
+//!
+//! Nach dem Connect published die Firmware pro Sensor eine Konfiguration
+//! (retained) auf `<prefix>/sensor/<node_id>/<object_id>/config`. Home Assistant
+//! legt daraus automatisch Entitäten an, die ihre Werte per `value_template`
+//! aus der Health-Nachricht lesen, z.B. `{"rssi":-61,"uptime":3600,...}`.
+//!
+//! Die Payloads werden ohne serde direkt in einen [`core::fmt::Write`] geschrieben,
+//! damit sie auf dem Host testbar sind.
+
+use core::fmt::{self, Write};
+
+/// Diagnose-Sensoren des Geräts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HaSensor {
+    /// WLAN-Signalstärke in dBm
+    Rssi,
+    /// Zeit seit dem Start in Sekunden
+    Uptime,
+    /// Freier Heap in Bytes
+    FreeHeap,
+    /// Chip-Temperatur in °C
+    Temperature,
+}
+
+impl HaSensor {
+    /// Alle Sensoren in Discovery-Reihenfolge
+    pub const ALL: [HaSensor; 4] = [
+        HaSensor::Rssi,
+        HaSensor::Uptime,
+        HaSensor::FreeHeap,
+        HaSensor::Temperature,
+    ];
+
+    /// Schlüssel in der Health-Nachricht und Teil von Topic und `unique_id`
+    pub fn key(self) -> &'static str {
+        match self {
+            HaSensor::Rssi => "rssi",
+            HaSensor::Uptime => "uptime",
+            HaSensor::FreeHeap => "heap_free",
+            HaSensor::Temperature => "temperature",
+        }
+    }
+
+    /// Anzeigename in Home Assistant
+    pub fn name(self) -> &'static str {
+        match self {
+            HaSensor::Rssi => "WLAN-Signal",
+            HaSensor::Uptime => "Laufzeit",
+            HaSensor::FreeHeap => "Freier Heap",
+            HaSensor::Temperature => "Chip-Temperatur",
+        }
+    }
+
+    /// Home-Assistant-Geräteklasse (`None` für Werte ohne passende Klasse)
+    pub fn device_class(self) -> Option<&'static str> {
+        match self {
+            HaSensor::Rssi => Some("signal_strength"),
+            HaSensor::Uptime => Some("duration"),
+            HaSensor::FreeHeap => Some("data_size"),
+            HaSensor::Temperature => Some("temperature"),
+        }
+    }
+
+    /// Einheit (`unit_of_measurement`)
+    pub fn unit(self) -> &'static str {
+        match self {
+            HaSensor::Rssi => "dBm",
+            HaSensor::Uptime => "s",
+            HaSensor::FreeHeap => "B",
+            HaSensor::Temperature => "°C",
+        }
+    }
+
+    /// Statistik-Art: Laufzeit steigt monoton (Reset beim Neustart), der Rest sind Messwerte
+    pub fn state_class(self) -> &'static str {
+        match self {
+            HaSensor::Uptime => "total_increasing",
+            _ => "measurement",
+        }
+    }
+}
+
+/// Gerät, zu dem die Sensoren in Home Assistant gruppiert werden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HaDevice<'a> {
+    /// Eindeutige Geräte-ID (Topic-Teil, nur `[a-zA-Z0-9_-]`)
+    pub node_id: &'a str,
+    pub name: &'a str,
+    pub model: &'a str,
+    pub sw_version: &'a str,
+    /// Topic der Health-Nachricht mit allen Sensorwerten
+    pub state_topic: &'a str,
+}
+
+/// Schreibt das Discovery-Topic eines Sensors
+pub fn write_discovery_topic(
+    out: &mut impl Write,
+    prefix: &str,
+    node_id: &str,
+    sensor: HaSensor,
+) -> fmt::Result {
+    write!(out, "{}/sensor/{}/{}/config", prefix, node_id, sensor.key())
+}
+
+/// Schreibt die Discovery-Konfiguration eines Sensors (JSON)
+pub fn write_discovery_config(
+    out: &mut impl Write,
+    device: &HaDevice<'_>,
+    sensor: HaSensor,
+) -> fmt::Result {
+    out.write_str("{\"name\":")?;
+    write_json_string(out, sensor.name())?;
+    write!(out, ",\"unique_id\":\"")?;
+    write_json_escaped(out, device.node_id)?;
+    write!(out, "_{}\",\"state_topic\":", sensor.key())?;
+    write_json_string(out, device.state_topic)?;
+    write!(
+        out,
+        ",\"value_template\":\"{{{{ value_json.{} }}}}\"",
+        sensor.key()
+    )?;
+    if let Some(class) = sensor.device_class() {
+        write!(out, ",\"device_class\":\"{}\"", class)?;
+    }
+    write!(
+        out,
+        ",\"unit_of_measurement\":\"{}\",\"state_class\":\"{}\",\"entity_category\":\"diagnostic\"",
+        sensor.unit(),
+        sensor.state_class()
+    )?;
+    out.write_str(",\"device\":{\"identifiers\":[")?;
+    write_json_string(out, device.node_id)?;
+    out.write_str("],\"name\":")?;
+    write_json_string(out, device.name)?;
+    out.write_str(",\"model\":")?;
+    write_json_string(out, device.model)?;
+    out.write_str(",\"sw_version\":")?;
+    write_json_string(out, device.sw_version)?;
+    out.write_str("}}")
+}
+
+/// JSON-String mit Anführungszeichen
+pub(crate) fn write_json_string(out: &mut impl Write, value: &str) -> fmt::Result {
+    out.write_char('"')?;
+    write_json_escaped(out, value)?;
+    out.write_char('"')
+}
+
+/// Maskiert `"` und `\`, Steuerzeichen werden ausgelassen
+pub(crate) fn write_json_escaped(out: &mut impl Write, value: &str) -> fmt::Result {
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                out.write_char('\\')?;
+                out.write_char(c)?;
+            }
+            c if c.is_control() => {}
+            c => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
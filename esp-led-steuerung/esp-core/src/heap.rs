@@ -0,0 +1,76 @@
+//! Heap-Auslastung für Telemetrie
+//!
+//! Der WLAN-Stack alloziert zur Laufzeit aus dem Heap (~100 KB). Läuft der
+//! Heap voll, schlagen zuerst WLAN-Pakete fehl, lange bevor etwas abstürzt.
+//! [`HeapStats`] hält die zuletzt gemessenen Werte und den höchsten bisher
+//! gesehenen Verbrauch, damit sich die Reserve beobachten lässt.
+//!
+//! Der Höchststand wird nur aus Stichproben gebildet: kurze Spitzen zwischen
+//! zwei Messungen sind nicht enthalten.
+
+/// Heap-Auslastung (Bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HeapStats {
+    /// Belegt bei der letzten Messung
+    pub used: u32,
+    /// Frei bei der letzten Messung
+    pub free: u32,
+    /// Höchster gemessener Verbrauch seit dem Start
+    pub high_water: u32,
+}
+
+impl HeapStats {
+    pub const fn new() -> Self {
+        Self {
+            used: 0,
+            free: 0,
+            high_water: 0,
+        }
+    }
+
+    /// Übernimmt eine Messung und aktualisiert den Höchststand
+    pub fn record(&mut self, used: u32, free: u32) {
+        self.used = used;
+        self.free = free;
+        self.high_water = self.high_water.max(used);
+    }
+
+    /// Gesamtgröße des Heaps
+    pub fn size(&self) -> u32 {
+        self.used.saturating_add(self.free)
+    }
+
+    /// Aktuelle Auslastung in Prozent (0 ohne Messung)
+    pub fn usage_percent(&self) -> u8 {
+        Self::percent(self.used, self.size())
+    }
+
+    /// Höchststand in Prozent der Gesamtgröße
+    pub fn high_water_percent(&self) -> u8 {
+        Self::percent(self.high_water, self.size())
+    }
+
+    fn percent(part: u32, total: u32) -> u8 {
+        if total == 0 {
+            return 0;
+        }
+        (u64::from(part) * 100 / u64::from(total)).min(100) as u8
+    }
+}
+
+// ============================================================================
+// defmt::Format Implementation (optional feature)
+// ============================================================================
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for HeapStats {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(
+            fmt,
+            "HeapStats {{ used: {}, free: {}, high_water: {} }}",
+            self.used,
+            self.free,
+            self.high_water
+        )
+    }
+}
@@ -0,0 +1,100 @@
+//! HTTP-Caching der Web-UI: ETag und `If-None-Match` (RFC 9110)
+//!
+//! Die Seite ändert sich nur mit dem Build bzw. einem Upload, der ETag wird
+//! daraus abgeleitet (Git-Hash + Build-Zeit bzw. CRC der hochgeladenen
+//! Oberfläche). Schickt der Browser den ETag in `If-None-Match` zurück,
+//! genügt `304 Not Modified` statt die Seite erneut durch die kleinen
+//! TCP-Puffer zu schieben.
+
+use core::fmt;
+
+/// Maximale Länge eines ETags inkl. Anführungszeichen
+pub const ETAG_MAX_LEN: usize = 40;
+
+/// Länge des Suffix (`-` + 16 Hex-Ziffern)
+const SUFFIX_LEN: usize = 17;
+
+/// Starker ETag der Form `"<tag>-<value hex>"`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ETag {
+    buf: [u8; ETAG_MAX_LEN],
+    len: u8,
+}
+
+impl ETag {
+    /// Bildet einen ETag aus einem Kennzeichen (z.B. Git-Hash) und einem Wert
+    ///
+    /// Zeichen außerhalb von `[0-9A-Za-z._]` im Kennzeichen werden
+    /// ausgelassen, zu lange Kennzeichen abgeschnitten.
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::ETag;
+    /// assert_eq!(ETag::new("a1b2c3d", 0x6650_0000).as_str(), "\"a1b2c3d-66500000\"");
+    /// ```
+    pub fn new(tag: &str, value: u64) -> Self {
+        let mut etag = Self {
+            buf: [0; ETAG_MAX_LEN],
+            len: 0,
+        };
+        etag.push(b'"');
+        let max_tag = ETAG_MAX_LEN - 2 - SUFFIX_LEN;
+        tag.bytes()
+            .filter(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'_'))
+            .take(max_tag)
+            .for_each(|byte| etag.push(byte));
+        etag.push(b'-');
+        let digits = if value == 0 {
+            1
+        } else {
+            (64 - value.leading_zeros()).div_ceil(4)
+        };
+        for shift in (0..digits).rev() {
+            let nibble = ((value >> (shift * 4)) & 0xF) as usize;
+            etag.push(b"0123456789abcdef"[nibble]);
+        }
+        etag.push(b'"');
+        etag
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.buf[self.len as usize] = byte;
+        self.len += 1;
+    }
+
+    /// ETag inkl. Anführungszeichen (Wert für den `ETag` Header)
+    pub fn as_str(&self) -> &str {
+        // Nur ASCII-Zeichen geschrieben
+        core::str::from_utf8(&self.buf[..self.len as usize]).unwrap_or("")
+    }
+}
+
+impl fmt::Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ETag({})", self.as_str())
+    }
+}
+
+/// Prüft einen `If-None-Match` Header gegen den aktuellen ETag
+///
+/// `true` heißt: der Browser hat die Seite schon, Antwort `304 Not Modified`.
+/// Unterstützt `*`, Listen (`"a", "b"`) und schwache ETags (`W/"a"`), die
+/// für `If-None-Match` wie starke verglichen werden (RFC 9110, 13.1.2).
+pub fn if_none_match(header: &[u8], etag: &ETag) -> bool {
+    let current = strip_weak(etag.as_str().as_bytes());
+    header.split(|&byte| byte == b',').any(|candidate| {
+        let candidate = candidate.trim_ascii();
+        candidate == b"*" || strip_weak(candidate) == current
+    })
+}
+
+/// Entfernt das Präfix `W/` eines schwachen ETags
+fn strip_weak(etag: &[u8]) -> &[u8] {
+    etag.strip_prefix(b"W/").unwrap_or(etag)
+}
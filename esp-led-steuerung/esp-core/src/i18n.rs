@@ -0,0 +1,82 @@
+//! Lokalisierung der Anzeigenamen (Farben, Modus)
+//!
+//! Logik arbeitet nur mit [`ColorId`] und `is_auto_mode`, Texte entstehen erst
+//! in der Darstellung (WebSocket-Status, MQTT-Topics). Eingaben werden in
+//! allen Sprachen akzeptiert, damit niemand "Grün" senden muss.
+//!
+//! Protokoll-Bezeichner (JSON `color`, CoAP) bleiben unabhängig von der
+//! Sprache bei [`ColorId::name`].
+
+use crate::types::ColorId;
+
+/// Sprache der Anzeigenamen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    German,
+    English,
+}
+
+/// Farbnamen je Sprache (Spalte = `Language as usize`)
+const COLOR_NAMES: [(ColorId, [&str; 2]); 5] = [
+    (ColorId::Red, ["Rot", "Red"]),
+    (ColorId::Green, ["Grün", "Green"]),
+    (ColorId::Blue, ["Blau", "Blue"]),
+    (ColorId::Off, ["Aus", "Off"]),
+    (ColorId::Custom, ["Benutzerdefiniert", "Custom"]),
+];
+
+/// Modus-Namen je Sprache: Auto-Rotation, manuell
+const MODE_NAMES: [[&str; 2]; 2] = [["Auto", "Auto"], ["Manuell", "Manual"]];
+
+/// Zusätzliche Eingabe-Schreibweisen ohne Umlaut
+const COLOR_ALIASES: [(&str, ColorId); 1] = [("Gruen", ColorId::Green)];
+
+impl Language {
+    /// Alle unterstützten Sprachen
+    pub const ALL: [Language; 2] = [Language::German, Language::English];
+
+    /// Sprache aus ISO-639-1-Kürzel ("de", "en")
+    pub const fn from_code(code: &str) -> Option<Self> {
+        match code.as_bytes() {
+            b"de" => Some(Language::German),
+            b"en" => Some(Language::English),
+            _ => None,
+        }
+    }
+
+    /// ISO-639-1-Kürzel
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::German => "de",
+            Language::English => "en",
+        }
+    }
+
+    /// Anzeigename einer Farbe
+    pub fn color_name(self, id: ColorId) -> &'static str {
+        COLOR_NAMES
+            .iter()
+            .find(|(color, _)| *color == id)
+            .map_or(id.name(), |(_, names)| names[self as usize])
+    }
+
+    /// Anzeigename des Betriebs-Modus
+    pub fn mode_name(self, is_auto_mode: bool) -> &'static str {
+        MODE_NAMES[usize::from(!is_auto_mode)][self as usize]
+    }
+}
+
+/// Erkennt einen Farbnamen in beliebiger Sprache (Groß-/Kleinschreibung egal)
+///
+/// `Custom` ist nicht wählbar und wird nie erkannt.
+pub fn parse_color_name(name: &str) -> Option<ColorId> {
+    let names = COLOR_NAMES
+        .iter()
+        .filter(|(id, _)| *id != ColorId::Custom)
+        .flat_map(|(id, names)| names.iter().map(move |name| (*name, *id)));
+    names
+        .chain(COLOR_ALIASES)
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+        .map(|(_, id)| id)
+}
@@ -0,0 +1,79 @@
+//! Digitale Eingänge (Tür-/Reed-Kontakte, Schalter) mit Entprellung
+//!
+//! Jeder Eingang hat einen Namen (MQTT-Topic `<prefix>/<name>`), einen
+//! aktiven Pegel und Zustandsnamen für aktiv/inaktiv (z.B. `open`/`closed`).
+//! Optional löst ein Wechsel ein LED-Kommando aus, etwa ein Preset beim
+//! Öffnen einer Schranktür und `Off` beim Schließen.
+
+use crate::debounce::Debouncer;
+use crate::traits::Clock;
+use crate::types::LedCommand;
+
+/// Konfiguration eines Eingangs
+#[derive(Clone, Copy)]
+pub struct DigitalInputConfig {
+    /// Name für MQTT und Logs
+    pub name: &'static str,
+    /// Aktiv bei Low-Pegel (z.B. Kontakt gegen Masse geschlossen)
+    pub active_low: bool,
+    /// Zustandsname im aktiven Zustand (MQTT-Payload)
+    pub active_state: &'static str,
+    /// Zustandsname im inaktiven Zustand (MQTT-Payload)
+    pub inactive_state: &'static str,
+    /// LED-Kommando beim Wechsel nach aktiv
+    pub on_active: Option<LedCommand>,
+    /// LED-Kommando beim Wechsel nach inaktiv
+    pub on_inactive: Option<LedCommand>,
+}
+
+impl DigitalInputConfig {
+    /// Wandelt den Pin-Pegel in den logischen Zustand
+    pub fn is_active(&self, level_high: bool) -> bool {
+        level_high != self.active_low
+    }
+
+    pub fn state_name(&self, active: bool) -> &'static str {
+        if active {
+            self.active_state
+        } else {
+            self.inactive_state
+        }
+    }
+
+    /// Auszulösendes LED-Kommando beim Wechsel in den Zustand `active`
+    pub fn command(&self, active: bool) -> Option<LedCommand> {
+        if active {
+            self.on_active
+        } else {
+            self.on_inactive
+        }
+    }
+}
+
+/// Ein entprellter Eingang
+#[derive(Clone, Copy)]
+pub struct DigitalInput {
+    pub config: DigitalInputConfig,
+    debouncer: Debouncer,
+}
+
+impl DigitalInput {
+    /// Erstellt den Eingang mit dem aktuellen Pin-Pegel (kein Ereignis beim Start)
+    pub fn new(config: DigitalInputConfig, level_high: bool, settle_ms: u32) -> Self {
+        Self {
+            config,
+            debouncer: Debouncer::new(config.is_active(level_high), settle_ms),
+        }
+    }
+
+    /// Aktueller entprellter Zustand
+    pub fn is_active(&self) -> bool {
+        self.debouncer.state()
+    }
+
+    /// Übernimmt einen Messwert, gibt den neuen Zustand bei stabiler Änderung zurück
+    pub fn update(&mut self, level_high: bool, clock: &impl Clock) -> Option<bool> {
+        self.debouncer
+            .update(self.config.is_active(level_high), clock)
+    }
+}
@@ -0,0 +1,196 @@
+//! LED-Hauptschleife ohne Async- und Hardware-Abhängigkeiten
+//!
+//! Ein Durchlauf ([`LedLoop::step`]): Kommando holen → Zustand fortschreiben →
+//! LED schreiben → bei Änderung publishen. Warten und Realtime-Frames bleiben
+//! im Firmware-Task, damit die komplette Logik auf dem Host testbar ist.
+
+use crate::calibration::Calibration;
+use crate::random::SeededRng;
+use crate::render_stats::FrameTiming;
+use crate::state::{LedState, LedStateConfig, StateTransition};
+use crate::traits::{
+    Clock, ColorSink, CommandSource, NoPresets, PresetSource, Rng, SmartLedWriter,
+};
+use crate::types::{LedColorMessage, LedCommand};
+
+/// Ergebnis eines Durchlaufs (für Logging und Timing im Task)
+#[derive(Clone, Copy)]
+pub struct LedCycle {
+    /// Verarbeitetes Kommando
+    pub command: Option<LedCommand>,
+    /// Zustandsänderungen aus Kommando und Tick
+    pub transition: StateTransition,
+    /// Veröffentlichte Nachricht (nur bei Farb- oder Modus-Änderung)
+    pub published: Option<LedColorMessage>,
+    /// Schreiben auf die LED ist fehlgeschlagen
+    pub write_failed: bool,
+    /// Verlauf läuft → nächster Durchlauf mit höherer Bildrate
+    pub animating: bool,
+    /// Laufzeit von Berechnung und Schreiben (für `/metrics`)
+    pub timing: FrameTiming,
+}
+
+impl LedCycle {
+    /// Wartezeit bis zum nächsten Durchlauf in ms
+    ///
+    /// Während eines Verlaufs `frame_ms` (flüssige Übergänge), sonst `interval_ms`.
+    pub fn next_delay_ms(&self, interval_ms: u64, frame_ms: u64) -> u64 {
+        if self.animating {
+            frame_ms
+        } else {
+            interval_ms
+        }
+    }
+}
+
+/// LED-Hauptschleife mit allen Abhängigkeiten als Trait-Objekte
+///
+/// # Parameter
+/// - `L`: LED Writer (Hardware oder Mock)
+/// - `C`: Monotone Zeitquelle
+/// - `S`: Ziel für Farb-Broadcasts
+/// - `Q`: Quelle für LED-Kommandos
+/// - `P`: Auflösung von Preset-IDs (Standard: keine Presets)
+/// - `R`: Zufallsquelle für den Zufallsmodus (Standard: [`SeededRng`])
+pub struct LedLoop<L, C, S, Q, P = NoPresets, R = SeededRng> {
+    pub state: LedState,
+    pub led: L,
+    pub clock: C,
+    pub sink: S,
+    pub source: Q,
+    pub presets: P,
+    pub rng: R,
+    /// Weißabgleich, wird vor dem Schreiben angewendet (nicht auf Realtime-Frames)
+    pub calibration: Calibration,
+    /// Kommando, das vor der Quelle verarbeitet wird (z.B. nach Realtime-Modus)
+    pub pending: Option<LedCommand>,
+    /// Sequenznummer des nächsten Broadcasts
+    next_sequence: u32,
+}
+
+impl<L, C, S, Q> LedLoop<L, C, S, Q>
+where
+    L: SmartLedWriter,
+    C: Clock,
+    S: ColorSink,
+    Q: CommandSource,
+{
+    /// Erstellt die Schleife im Startzustand (siehe [`LedState::new`])
+    pub fn new(config: LedStateConfig, led: L, clock: C, sink: S, source: Q) -> Self {
+        Self {
+            state: LedState::new(config),
+            led,
+            clock,
+            sink,
+            source,
+            presets: NoPresets,
+            rng: SeededRng::default(),
+            calibration: Calibration::NEUTRAL,
+            pending: None,
+            next_sequence: 0,
+        }
+    }
+}
+
+impl<L, C, S, Q, P, R> LedLoop<L, C, S, Q, P, R>
+where
+    L: SmartLedWriter,
+    C: Clock,
+    S: ColorSink,
+    Q: CommandSource,
+    P: PresetSource,
+    R: Rng,
+{
+    /// Ersetzt die Preset-Quelle (für `LedCommand::ApplyPreset`)
+    pub fn with_presets<P2: PresetSource>(self, presets: P2) -> LedLoop<L, C, S, Q, P2, R> {
+        LedLoop {
+            state: self.state,
+            led: self.led,
+            clock: self.clock,
+            sink: self.sink,
+            source: self.source,
+            presets,
+            rng: self.rng,
+            calibration: self.calibration,
+            pending: self.pending,
+            next_sequence: self.next_sequence,
+        }
+    }
+
+    /// Ersetzt die Zufallsquelle (für `LedCommand::EnableRandom`)
+    pub fn with_rng<R2: Rng>(self, rng: R2) -> LedLoop<L, C, S, Q, P, R2> {
+        LedLoop {
+            state: self.state,
+            led: self.led,
+            clock: self.clock,
+            sink: self.sink,
+            source: self.source,
+            presets: self.presets,
+            rng,
+            calibration: self.calibration,
+            pending: self.pending,
+            next_sequence: self.next_sequence,
+        }
+    }
+
+    /// Führt einen Durchlauf aus
+    pub fn step(&mut self) -> LedCycle {
+        let mut transition = StateTransition::default();
+
+        // Höchstens ein Kommando pro Durchlauf (wie bisher im Task)
+        let command = self.pending.take().or_else(|| self.source.try_next());
+        if let Some(cmd) = command {
+            let now_ms = self.clock.now_ms();
+            transition = match cmd {
+                LedCommand::ApplyPreset { id } => match self.presets.preset(id) {
+                    Some(preset) => self.state.apply_preset(&preset, now_ms),
+                    None => {
+                        crate::log_warn!("Unbekanntes Preset: {}", id);
+                        StateTransition::default()
+                    }
+                },
+                cmd => self.state.apply(cmd, now_ms),
+            };
+        }
+
+        let now_ms = self.clock.now_ms();
+        let tick = self.state.tick(now_ms);
+        transition.color_changed |= self.state.tick_random(&mut self.rng).color_changed;
+        transition.color_changed |= tick.color_changed;
+        transition.effect_finished |= tick.effect_finished;
+        transition.sleep_expired |= tick.sleep_expired;
+
+        let render_start = self.clock.now_us();
+        let color = self.calibration.apply(self.state.output(now_ms));
+        let write_start = self.clock.now_us();
+        let write_failed = self.led.write(color).is_err();
+        let timing = FrameTiming {
+            render_us: elapsed_us(render_start, write_start),
+            write_us: elapsed_us(write_start, self.clock.now_us()),
+        };
+        if write_failed {
+            crate::log_warn!("LED-Schreibvorgang fehlgeschlagen");
+        }
+
+        let published = transition.color_changed.then(|| {
+            let msg = self.state.message().stamped(self.next_sequence, now_ms);
+            self.next_sequence = self.next_sequence.wrapping_add(1);
+            self.sink.publish(msg);
+            msg
+        });
+
+        LedCycle {
+            command,
+            transition,
+            published,
+            write_failed,
+            animating: self.state.is_animating(self.clock.now_ms()),
+            timing,
+        }
+    }
+}
+
+/// Dauer zwischen zwei Zeitpunkten in Mikrosekunden (sättigend)
+fn elapsed_us(start_us: u64, end_us: u64) -> u32 {
+    u32::try_from(end_us.saturating_sub(start_us)).unwrap_or(u32::MAX)
+}
@@ -5,11 +5,161 @@
 
 #![no_std]
 
+#[cfg(feature = "test-util")]
+extern crate alloc;
+
+pub mod auth;
+pub mod ble;
+pub mod broker;
+pub mod calibration;
+pub mod clock_face;
+pub mod coap;
+pub mod color;
+pub mod command;
+pub mod console;
+pub mod crash;
+pub mod crossfade;
+pub mod debounce;
+pub mod device_config;
+pub mod diagnose;
+pub mod dns_cache;
+pub mod effect_param;
+pub mod effects;
+pub mod error;
+pub mod events;
+pub mod gradient;
+pub mod group_sync;
+pub mod ha_discovery;
+pub mod heap;
+pub mod http_cache;
+pub mod i18n;
+pub mod inputs;
+pub mod led_loop;
+pub mod lifx;
+pub mod log_ring;
+pub mod logging;
 pub mod logic;
+pub mod matrix;
+pub mod matter;
+pub mod meteor;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod motion;
+pub mod mqtt;
+pub mod mqtt_sn;
+pub mod net_stats;
+pub mod offline_queue;
+pub mod osc;
+pub mod plasma;
+pub mod preset;
+pub mod publish_throttle;
+pub mod queue;
+pub mod random;
+pub mod realtime;
+pub mod record;
+pub mod render_stats;
+pub mod schedule;
+pub mod sequence;
+#[cfg(feature = "serde")]
+mod serde_util;
+pub mod sntp;
+pub mod ssdp;
+pub mod stall;
+pub mod state;
+pub mod status_led;
+pub mod sync;
+pub mod syslog;
+pub mod task_stats;
+pub mod tasmota;
+pub mod test_pattern;
+pub mod text;
+pub mod time;
+pub mod trace;
 pub mod traits;
+pub mod transition;
 pub mod types;
+pub mod ui_bundle;
+#[cfg(feature = "serde")]
+pub mod wot;
+#[cfg(feature = "serde")]
+pub mod ws;
+pub mod ws_deflate;
 
 // Re-exports für einfachen Zugriff
+pub use auth::{bearer_matches, token_matches};
+pub use ble::{BLE_COMPANY_ID, BleLedState, decode_ble_payload, encode_ble_payload};
+pub use broker::{BrokerAddress, BrokerRotation};
+pub use calibration::{Calibration, CalibrationPattern};
+pub use clock_face::{ClockFace, ClockFormat};
+pub use coap::{CoapError, CoapRequest, CoapResponse, CoapType, Observers, decode_coap_message};
+pub use color::{
+    Hsv, color_temperature, lerp_color, perceived_brightness, rotate_hue, scale_brightness,
+};
+pub use command::{CommandParseError, parse_text_command};
+pub use console::{ConsoleCommand, LogLevel, TraceAction, parse_console_command};
+pub use crash::{CrashRecord, CrashRegisters};
+pub use crossfade::{Crossfade, PixelEffect};
+pub use debounce::{Debouncer, Press, PressDetector};
+pub use device_config::{ColorOrder, DeviceConfig, StripMapping};
+pub use diagnose::{ConnectivityDiagnosis, DiagnoseBlink, DiagnoseCode};
+pub use dns_cache::{DnsCache, DnsCacheState};
+pub use effect_param::{LedEffect, ParamError, ParamName, ParamValue};
+pub use effects::{EFFECTS, EffectId, EffectInfo, EffectParam, ParamKind};
+pub use error::{ErrorCategory, ErrorCounters, ErrorKind, ErrorSource, FirmwareError, Severity};
+pub use events::{ClientKind, RebootSource, SystemEvent};
+pub use gradient::Gradient;
+pub use group_sync::{GroupPacket, GroupState, GroupSync};
+pub use ha_discovery::{HaDevice, HaSensor};
+pub use heap::HeapStats;
+pub use http_cache::{ETag, if_none_match};
+pub use i18n::{Language, parse_color_name};
+pub use inputs::{DigitalInput, DigitalInputConfig};
+pub use led_loop::{LedCycle, LedLoop};
+pub use lifx::{
+    Hsbk, LifxError, LifxHeader, LifxLight, LifxRequest, LifxResponse, decode_lifx_packet,
+    encode_lifx_response, lifx_response,
+};
+pub use log_ring::{LogLine, LogRing};
 pub use logic::rotate_color;
-pub use traits::{LedError, SmartLedWriter};
-pub use types::{LedColorMessage, LedCommand};
+pub use matrix::MatrixLayout;
+pub use matter::{MatterAttribute, MatterLight};
+pub use meteor::Meteor;
+pub use motion::{MotionAction, MotionTrigger};
+pub use mqtt::{BirthInfo, CommandAck, HealthInfo};
+pub use mqtt_sn::{MqttSnError, MqttSnPacket, decode_mqtt_sn};
+pub use net_stats::{NetCounter, NetStats};
+pub use offline_queue::{OfflineChange, OfflineQueue};
+pub use osc::{OscArg, OscError, OscMessage, decode_osc_message, osc_led_command};
+pub use plasma::Plasma;
+pub use preset::{Preset, PresetEffect, PresetError, PresetName, Presets};
+pub use publish_throttle::PublishThrottle;
+pub use queue::{CommandPriority, CommandQueue, PushOutcome, QueueStats};
+pub use random::{SeededRng, random_color};
+pub use realtime::{RealtimePacket, decode_realtime_packet};
+pub use record::{Crc32, RecordKind, decode_record, encode_record};
+pub use render_stats::{FrameTiming, RenderStats, RenderSummary};
+pub use schedule::{Schedule, ScheduleAction, ScheduleEntry, ScheduleError};
+pub use sequence::ColorSequence;
+pub use ssdp::{
+    DeviceDescription, DeviceUuid, SsdpDevice, SsdpSearch, parse_m_search, write_device_description,
+};
+pub use stall::{StallChange, StallDetector, TaskDeadline};
+pub use state::{Effect, LedState, LedStateConfig, StateTransition};
+pub use status_led::{StatusCode, StatusIndicator};
+pub use sync::{SyncFollower, SyncFrame, SyncPacket, SyncRole};
+pub use syslog::{SYSLOG_PORT, syslog_severity, write_syslog_frame};
+pub use task_stats::{SystemStats, TaskId, TaskStats};
+pub use tasmota::{PowerAction, TasmotaCommand, parse_tasmota_command};
+pub use test_pattern::{TestPattern, TestPatternPhase};
+pub use text::{ScrollingText, TextMessage};
+pub use time::{LocalTime, TimeSource, TimeZone, Weekday, local_time};
+pub use trace::{Span, SpanKind, SpanStats, TraceRing, TraceSummary};
+pub use traits::{
+    Clock, ColorSink, CommandSource, LedError, NoPresets, PresetSource, Rng, SmartLedWriter,
+};
+pub use transition::{BrightnessRamp, SleepTimer, Sunrise, Transition};
+pub use types::{
+    COLOR_LABEL_LEN, COLOR_TOLERANCE_PERCENT, ColorId, ColorLabel, LedColorMessage, LedCommand,
+    classify_color, color_name,
+};
+pub use ui_bundle::{UiBundleHeader, UiEncoding, UiUpload, UiUploadError};
@@ -0,0 +1,501 @@
+//! LIFX LAN-Protokoll (UDP)
+//!
+//! Emuliert eine LIFX-Farblampe, damit vorhandene LIFX-Apps und
+//! -Bibliotheken (lifxlan, aiolifx/Home Assistant, Photons) das Gerät im
+//! LAN finden und steuern. Jedes Paket beginnt mit einem 36-Byte-Header
+//! (Little Endian), danach folgt die Nutzlast des Nachrichtentyps:
+//!
+//! | Anfrage         | Typ | Antwort           | Typ |
+//! |-----------------|-----|-------------------|-----|
+//! | `GetService`    | 2   | `StateService`    | 3   |
+//! | `GetPower`      | 20  | `StatePower`      | 22  |
+//! | `SetPower`      | 21  | `StatePower`*     | 22  |
+//! | `GetLabel`      | 23  | `StateLabel`      | 25  |
+//! | `GetVersion`    | 32  | `StateVersion`    | 33  |
+//! | `Get` (Farbe)   | 101 | `LightState`      | 107 |
+//! | `SetColor`      | 102 | `LightState`*     | 107 |
+//! | `GetLightPower` | 116 | `StateLightPower` | 118 |
+//! | `SetLightPower` | 117 | `StateLightPower`*| 118 |
+//!
+//! `*` nur mit gesetztem `res_required`-Flag. Mit `ack_required` folgt
+//! zusätzlich ein `Acknowledgement` (45), andere Typen werden nur quittiert.
+//! Farben sind HSBK (Farbton, Sättigung, Helligkeit je 0-65535 plus Kelvin),
+//! bei Sättigung 0 bestimmt die Farbtemperatur das Weiß. Wie bei echten
+//! Lampen ändert `SetColor` im ausgeschalteten Zustand nur die gespeicherte
+//! Farbe.
+
+use rgb::RGB8;
+
+use crate::color::{HUE_CIRCLE, Hsv, color_temperature, scale_brightness};
+use crate::types::{LedCommand, classify_color};
+
+/// Standard-UDP-Port des LIFX-LAN-Protokolls
+pub const LIFX_PORT: u16 = 56700;
+
+/// Länge des Paket-Headers in Bytes
+pub const HEADER_LEN: usize = 36;
+
+/// Länge des Namens in `StateLabel` und `LightState` (mit Null-Bytes aufgefüllt)
+pub const LABEL_LEN: usize = 32;
+
+/// Größte Antwort (`LightState`) in Bytes
+pub const MAX_RESPONSE_LEN: usize = HEADER_LEN + 52;
+
+/// Hersteller-ID in `StateVersion` (LIFX)
+pub const VENDOR_ID: u32 = 1;
+
+/// Produkt-ID in `StateVersion` (LIFX A19, Farbe)
+pub const PRODUCT_ID: u32 = 27;
+
+/// Protokollnummer im Header
+const PROTOCOL: u16 = 1024;
+
+/// Header-Bit: Ziel ist eine Adresse (immer gesetzt)
+const ADDRESSABLE: u16 = 1 << 12;
+
+/// Header-Bit: Broadcast an alle Geräte
+const TAGGED: u16 = 1 << 13;
+
+/// Flag: Client erwartet eine Zustands-Antwort
+const RES_REQUIRED: u8 = 0x01;
+
+/// Flag: Client erwartet ein `Acknowledgement`
+const ACK_REQUIRED: u8 = 0x02;
+
+/// Dienst-Kennung UDP in `StateService`
+const SERVICE_UDP: u8 = 1;
+
+/// Leistungsstufe "an" (LIFX kennt nur 0 und 65535)
+const POWER_ON: u16 = u16::MAX;
+
+/// Nachrichtentypen
+pub mod message_type {
+    pub const GET_SERVICE: u16 = 2;
+    pub const STATE_SERVICE: u16 = 3;
+    pub const GET_POWER: u16 = 20;
+    pub const SET_POWER: u16 = 21;
+    pub const STATE_POWER: u16 = 22;
+    pub const GET_LABEL: u16 = 23;
+    pub const STATE_LABEL: u16 = 25;
+    pub const GET_VERSION: u16 = 32;
+    pub const STATE_VERSION: u16 = 33;
+    pub const ACKNOWLEDGEMENT: u16 = 45;
+    pub const GET_COLOR: u16 = 101;
+    pub const SET_COLOR: u16 = 102;
+    pub const LIGHT_STATE: u16 = 107;
+    pub const GET_LIGHT_POWER: u16 = 116;
+    pub const SET_LIGHT_POWER: u16 = 117;
+    pub const STATE_LIGHT_POWER: u16 = 118;
+}
+
+/// Fehler beim Dekodieren oder Kodieren eines LIFX-Pakets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifxError {
+    /// Paket kürzer als Header bzw. Nutzlast des Typs
+    Truncated,
+    /// Protokollnummer ist nicht 1024
+    InvalidProtocol,
+    /// Ausgabe-Buffer zu klein
+    BufferTooSmall,
+}
+
+/// Farbe im LIFX-Format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hsbk {
+    /// Farbton 0-65535 (0 = Rot, einmal um den Farbkreis)
+    pub hue: u16,
+    /// Sättigung 0-65535 (0 = Weiß laut `kelvin`)
+    pub saturation: u16,
+    /// Helligkeit 0-65535
+    pub brightness: u16,
+    /// Farbtemperatur für Weiß (LIFX: 2500-9000 K)
+    pub kelvin: u16,
+}
+
+impl Hsbk {
+    /// Umrechnung in RGB (Weiß über die Farbtemperatur, Farbe über HSV)
+    pub fn to_rgb(&self) -> RGB8 {
+        let circle = HUE_CIRCLE as u32;
+        let value = to_channel(self.brightness);
+        if self.saturation == 0 {
+            return scale_brightness(color_temperature(self.kelvin), value);
+        }
+        Hsv {
+            hue: (((self.hue as u32 * circle + 0x8000) >> 16) % circle) as u16,
+            saturation: to_channel(self.saturation),
+            value,
+        }
+        .to_rgb()
+    }
+
+    /// Zerlegt eine RGB-Farbe (`kelvin` bleibt für spätere Weißtöne erhalten)
+    pub fn from_rgb(color: RGB8, kelvin: u16) -> Self {
+        let hsv = Hsv::from_rgb(color);
+        Self {
+            hue: ((((hsv.hue as u32) << 16) + HUE_CIRCLE as u32 / 2) / HUE_CIRCLE as u32) as u16,
+            saturation: hsv.saturation as u16 * 257,
+            brightness: hsv.value as u16 * 257,
+            kelvin,
+        }
+    }
+}
+
+/// 0-65535 → 0-255 (gerundet)
+fn to_channel(value: u16) -> u8 {
+    ((value as u32 * 255 + 32767) / 65535) as u8
+}
+
+/// Header eines empfangenen Pakets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifxHeader {
+    /// Kennung des Clients (wird in Antworten zurückgegeben)
+    pub source: u32,
+    /// Ziel-MAC (6 Bytes plus 2 Null-Bytes), nur Nullen = alle Geräte
+    pub target: [u8; 8],
+    /// Broadcast an alle Geräte
+    pub tagged: bool,
+    pub ack_required: bool,
+    pub res_required: bool,
+    /// Laufnummer des Clients (wird in Antworten zurückgegeben)
+    pub sequence: u8,
+    pub message_type: u16,
+}
+
+impl LifxHeader {
+    /// Prüft ob das Paket an das Gerät mit dieser MAC gerichtet ist
+    pub fn is_for(&self, mac: [u8; 6]) -> bool {
+        self.tagged || self.target == [0; 8] || self.target[..6] == mac
+    }
+}
+
+/// Dekodierte Anfrage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifxRequest {
+    GetService,
+    GetPower,
+    SetPower {
+        on: bool,
+    },
+    GetLabel,
+    GetVersion,
+    GetColor,
+    SetColor {
+        color: Hsbk,
+        duration_ms: u32,
+    },
+    GetLightPower,
+    SetLightPower {
+        on: bool,
+        duration_ms: u32,
+    },
+    /// Nicht unterstützter Nachrichtentyp (wird nur quittiert)
+    Unsupported(u16),
+}
+
+/// Dekodiert ein LIFX-Paket in Header und Anfrage
+///
+/// Die Paketlänge laut Header hat Vorrang, überzählige Bytes (Padding)
+/// werden ignoriert.
+pub fn decode_lifx_packet(packet: &[u8]) -> Result<(LifxHeader, LifxRequest), LifxError> {
+    if packet.len() < HEADER_LEN {
+        return Err(LifxError::Truncated);
+    }
+    let size = read_u16(packet, 0) as usize;
+    if size < HEADER_LEN || size > packet.len() {
+        return Err(LifxError::Truncated);
+    }
+    let protocol = read_u16(packet, 2);
+    if protocol & 0x0FFF != PROTOCOL {
+        return Err(LifxError::InvalidProtocol);
+    }
+
+    let mut target = [0u8; 8];
+    target.copy_from_slice(&packet[8..16]);
+    let flags = packet[22];
+    let header = LifxHeader {
+        source: read_u32(packet, 4),
+        target,
+        tagged: protocol & TAGGED != 0,
+        ack_required: flags & ACK_REQUIRED != 0,
+        res_required: flags & RES_REQUIRED != 0,
+        sequence: packet[23],
+        message_type: read_u16(packet, 32),
+    };
+
+    let payload = &packet[HEADER_LEN..size];
+    let needed = match header.message_type {
+        message_type::SET_POWER => 2,
+        message_type::SET_COLOR => 13,
+        message_type::SET_LIGHT_POWER => 6,
+        _ => 0,
+    };
+    if payload.len() < needed {
+        return Err(LifxError::Truncated);
+    }
+
+    let request = match header.message_type {
+        message_type::GET_SERVICE => LifxRequest::GetService,
+        message_type::GET_POWER => LifxRequest::GetPower,
+        message_type::SET_POWER => LifxRequest::SetPower {
+            on: read_u16(payload, 0) != 0,
+        },
+        message_type::GET_LABEL => LifxRequest::GetLabel,
+        message_type::GET_VERSION => LifxRequest::GetVersion,
+        message_type::GET_COLOR => LifxRequest::GetColor,
+        // Byte 0 ist reserviert
+        message_type::SET_COLOR => LifxRequest::SetColor {
+            color: Hsbk {
+                hue: read_u16(payload, 1),
+                saturation: read_u16(payload, 3),
+                brightness: read_u16(payload, 5),
+                kelvin: read_u16(payload, 7),
+            },
+            duration_ms: read_u32(payload, 9),
+        },
+        message_type::GET_LIGHT_POWER => LifxRequest::GetLightPower,
+        message_type::SET_LIGHT_POWER => LifxRequest::SetLightPower {
+            on: read_u16(payload, 0) != 0,
+            duration_ms: read_u32(payload, 2),
+        },
+        other => LifxRequest::Unsupported(other),
+    };
+    Ok((header, request))
+}
+
+/// Zustand der emulierten Lampe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifxLight {
+    pub on: bool,
+    /// Gespeicherte Farbe (bleibt beim Ausschalten erhalten)
+    pub color: Hsbk,
+}
+
+impl Default for LifxLight {
+    /// Aus, warmweiß bei voller Helligkeit
+    fn default() -> Self {
+        Self {
+            on: false,
+            color: Hsbk {
+                hue: 0,
+                saturation: 0,
+                brightness: u16::MAX,
+                kelvin: 3500,
+            },
+        }
+    }
+}
+
+impl LifxLight {
+    /// Übernimmt eine Set-Anfrage und liefert das passende LED-Kommando
+    ///
+    /// `None` für Get-Anfragen und für `SetColor` im ausgeschalteten Zustand.
+    pub fn apply(&mut self, request: &LifxRequest) -> Option<LedCommand> {
+        match *request {
+            LifxRequest::SetColor { color, duration_ms } => {
+                self.color = color;
+                self.on.then(|| self.command(duration_ms))
+            }
+            LifxRequest::SetPower { on } => {
+                self.on = on;
+                Some(self.command(0))
+            }
+            LifxRequest::SetLightPower { on, duration_ms } => {
+                self.on = on;
+                Some(self.command(duration_ms))
+            }
+            _ => None,
+        }
+    }
+
+    /// LED-Kommando für den aktuellen Zustand (mit Übergang ab 1 ms)
+    pub fn command(&self, duration_ms: u32) -> LedCommand {
+        let color = if self.on {
+            self.color.to_rgb()
+        } else {
+            RGB8::default()
+        };
+        if duration_ms > 0 {
+            return LedCommand::FadeTo {
+                target_color: color,
+                color_id: classify_color(color),
+                transition_ms: duration_ms,
+            };
+        }
+        if color == RGB8::default() {
+            return LedCommand::Off;
+        }
+        LedCommand::SetColor {
+            target_color: color,
+            color_id: classify_color(color),
+        }
+    }
+
+    /// Gleicht den Zustand mit der tatsächlichen LED-Farbe ab
+    ///
+    /// Für Änderungen aus anderen Quellen (Web UI, MQTT, Auto-Modus). Die
+    /// eigene HSBK-Farbe bleibt unverändert, solange sie die LED-Farbe
+    /// ergibt, damit Apps ihre gesetzten Werte zurücklesen.
+    pub fn sync_from_color(&mut self, color: RGB8) {
+        if color == RGB8::default() {
+            self.on = false;
+            return;
+        }
+        if !self.on || self.color.to_rgb() != color {
+            self.color = Hsbk::from_rgb(color, self.color.kelvin);
+        }
+        self.on = true;
+    }
+}
+
+/// Antwort an den Client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifxResponse<'a> {
+    StateService { port: u16 },
+    StatePower { on: bool },
+    StateLabel { label: &'a str },
+    StateVersion,
+    LightState { light: LifxLight, label: &'a str },
+    StateLightPower { on: bool },
+    Acknowledgement,
+}
+
+impl LifxResponse<'_> {
+    /// Nachrichtentyp im Header
+    pub fn message_type(&self) -> u16 {
+        match self {
+            LifxResponse::StateService { .. } => message_type::STATE_SERVICE,
+            LifxResponse::StatePower { .. } => message_type::STATE_POWER,
+            LifxResponse::StateLabel { .. } => message_type::STATE_LABEL,
+            LifxResponse::StateVersion => message_type::STATE_VERSION,
+            LifxResponse::LightState { .. } => message_type::LIGHT_STATE,
+            LifxResponse::StateLightPower { .. } => message_type::STATE_LIGHT_POWER,
+            LifxResponse::Acknowledgement => message_type::ACKNOWLEDGEMENT,
+        }
+    }
+}
+
+/// Zustands-Antwort auf eine Anfrage (nach [`LifxLight::apply`] aufrufen)
+///
+/// Set-Anfragen werden nur mit `res_required` beantwortet, unbekannte
+/// Typen nie (das `Acknowledgement` sendet der Aufrufer).
+pub fn lifx_response<'a>(
+    header: &LifxHeader,
+    request: &LifxRequest,
+    light: &LifxLight,
+    label: &'a str,
+) -> Option<LifxResponse<'a>> {
+    let response = match request {
+        LifxRequest::GetService => LifxResponse::StateService { port: LIFX_PORT },
+        LifxRequest::GetPower => LifxResponse::StatePower { on: light.on },
+        LifxRequest::GetLabel => LifxResponse::StateLabel { label },
+        LifxRequest::GetVersion => LifxResponse::StateVersion,
+        LifxRequest::GetColor => LifxResponse::LightState {
+            light: *light,
+            label,
+        },
+        LifxRequest::GetLightPower => LifxResponse::StateLightPower { on: light.on },
+        LifxRequest::SetPower { .. } if header.res_required => {
+            LifxResponse::StatePower { on: light.on }
+        }
+        LifxRequest::SetColor { .. } if header.res_required => LifxResponse::LightState {
+            light: *light,
+            label,
+        },
+        LifxRequest::SetLightPower { .. } if header.res_required => {
+            LifxResponse::StateLightPower { on: light.on }
+        }
+        _ => return None,
+    };
+    Some(response)
+}
+
+/// Kodiert eine Antwort an den Absender von `request`
+///
+/// Übernimmt `source` und `sequence` der Anfrage, `mac` ist die eigene
+/// Adresse im Ziel-Feld. Namen werden nach [`LABEL_LEN`] Bytes abgeschnitten.
+/// Gibt die Paketlänge zurück.
+pub fn encode_lifx_response(
+    out: &mut [u8],
+    request: &LifxHeader,
+    mac: [u8; 6],
+    response: &LifxResponse,
+) -> Result<usize, LifxError> {
+    let payload_len = match response {
+        LifxResponse::StateService { .. } => 5,
+        LifxResponse::StatePower { .. } | LifxResponse::StateLightPower { .. } => 2,
+        LifxResponse::StateLabel { .. } => LABEL_LEN,
+        LifxResponse::StateVersion => 12,
+        LifxResponse::LightState { .. } => MAX_RESPONSE_LEN - HEADER_LEN,
+        LifxResponse::Acknowledgement => 0,
+    };
+    let len = HEADER_LEN + payload_len;
+    if out.len() < len {
+        return Err(LifxError::BufferTooSmall);
+    }
+    let out = &mut out[..len];
+    out.fill(0);
+
+    write_u16(out, 0, len as u16);
+    write_u16(out, 2, PROTOCOL | ADDRESSABLE);
+    write_u32(out, 4, request.source);
+    out[8..14].copy_from_slice(&mac);
+    out[23] = request.sequence;
+    write_u16(out, 32, response.message_type());
+
+    let payload = &mut out[HEADER_LEN..];
+    match *response {
+        LifxResponse::StateService { port } => {
+            payload[0] = SERVICE_UDP;
+            write_u32(payload, 1, port as u32);
+        }
+        LifxResponse::StatePower { on } | LifxResponse::StateLightPower { on } => {
+            write_u16(payload, 0, power_level(on));
+        }
+        LifxResponse::StateLabel { label } => write_label(payload, label),
+        LifxResponse::StateVersion => {
+            write_u32(payload, 0, VENDOR_ID);
+            write_u32(payload, 4, PRODUCT_ID);
+        }
+        LifxResponse::LightState { light, label } => {
+            write_u16(payload, 0, light.color.hue);
+            write_u16(payload, 2, light.color.saturation);
+            write_u16(payload, 4, light.color.brightness);
+            write_u16(payload, 6, light.color.kelvin);
+            write_u16(payload, 10, power_level(light.on));
+            write_label(&mut payload[12..], label);
+        }
+        LifxResponse::Acknowledgement => {}
+    }
+    Ok(len)
+}
+
+fn power_level(on: bool) -> u16 {
+    if on { POWER_ON } else { 0 }
+}
+
+fn write_label(out: &mut [u8], label: &str) {
+    let bytes = label.as_bytes();
+    let len = bytes.len().min(LABEL_LEN);
+    out[..len].copy_from_slice(&bytes[..len]);
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+fn write_u16(out: &mut [u8], offset: usize, value: u16) {
+    out[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(out: &mut [u8], offset: usize, value: u32) {
+    out[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
@@ -0,0 +1,154 @@
+//! Ringpuffer für Log-Zeilen im RAM
+//!
+//! Hält die letzten `N` Zeilen mit je höchstens `LEN` Bytes, damit Geräte
+//! ohne angeschlossene Debug-Probe über HTTP diagnostiziert werden können.
+//! Jede Zeile erhält eine fortlaufende Nummer: Clients holen mit
+//! [`LogRing::since`] nur neue Zeilen und erkennen überschriebene an
+//! Lücken in der Nummerierung.
+//!
+//! Zu lange Zeilen werden an einer Zeichengrenze abgeschnitten.
+
+use core::fmt;
+
+use crate::console::LogLevel;
+
+/// Eine gespeicherte Log-Zeile
+#[derive(Clone, Copy)]
+pub struct LogLine<const LEN: usize> {
+    /// Fortlaufende Nummer (ab 0, mit Überlauf)
+    pub seq: u32,
+    /// Zeitpunkt in Millisekunden seit Boot
+    pub timestamp_ms: u64,
+    pub level: LogLevel,
+    len: usize,
+    text: [u8; LEN],
+}
+
+impl<const LEN: usize> LogLine<LEN> {
+    const EMPTY: Self = Self {
+        seq: 0,
+        timestamp_ms: 0,
+        level: LogLevel::Info,
+        len: 0,
+        text: [0; LEN],
+    };
+
+    /// Text der Zeile (ggf. gekürzt)
+    pub fn text(&self) -> &str {
+        // Nur über `fmt::Write` befüllt, abgeschnitten an Zeichengrenzen
+        core::str::from_utf8(&self.text[..self.len]).unwrap_or("")
+    }
+}
+
+/// Textform für Konsole und `GET /api/logs/stream`: `[    12.345] info  Text`
+impl<const LEN: usize> fmt::Display for LogLine<LEN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = self.timestamp_ms;
+        write!(
+            f,
+            "[{:>6}.{:03}] {:<5} {}",
+            ms / 1000,
+            ms % 1000,
+            self.level.as_str(),
+            self.text()
+        )
+    }
+}
+
+impl<const LEN: usize> fmt::Write for LogLine<LEN> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let free = LEN - self.len;
+        let mut take = s.len().min(free);
+        while !s.is_char_boundary(take) {
+            take -= 1;
+        }
+        self.text[self.len..self.len + take].copy_from_slice(&s.as_bytes()[..take]);
+        self.len += take;
+        Ok(())
+    }
+}
+
+/// Ringpuffer mit `N` Zeilen zu je `LEN` Bytes
+#[derive(Clone)]
+pub struct LogRing<const N: usize, const LEN: usize> {
+    lines: [LogLine<LEN>; N],
+    /// Index der ältesten Zeile
+    head: usize,
+    len: usize,
+    next_seq: u32,
+}
+
+impl<const N: usize, const LEN: usize> LogRing<N, LEN> {
+    pub const fn new() -> Self {
+        Self {
+            lines: [LogLine::EMPTY; N],
+            head: 0,
+            len: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// Hängt eine formatierte Zeile an, überschreibt bei vollem Puffer die älteste
+    ///
+    /// Gibt die vergebene Nummer zurück.
+    pub fn push(&mut self, timestamp_ms: u64, level: LogLevel, args: fmt::Arguments<'_>) -> u32 {
+        let index = (self.head + self.len) % N;
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+        } else {
+            self.len += 1;
+        }
+
+        let seq = self.next_seq;
+        self.next_seq = self.next_seq.wrapping_add(1);
+
+        let line = &mut self.lines[index];
+        *line = LogLine {
+            seq,
+            timestamp_ms,
+            level,
+            ..LogLine::EMPTY
+        };
+        // Schreiben in den festen Puffer schlägt nie fehl (kürzt stattdessen)
+        let _ = fmt::write(line, args);
+        seq
+    }
+
+    /// Anzahl gespeicherter Zeilen
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Nummer, die die nächste Zeile erhält
+    pub fn next_seq(&self) -> u32 {
+        self.next_seq
+    }
+
+    /// Anzahl bisher überschriebener Zeilen
+    pub fn dropped(&self) -> u32 {
+        self.next_seq.wrapping_sub(self.len as u32)
+    }
+
+    /// Alle Zeilen, älteste zuerst
+    pub fn iter(&self) -> impl Iterator<Item = &LogLine<LEN>> {
+        (0..self.len).map(move |offset| &self.lines[(self.head + offset) % N])
+    }
+
+    /// Zeilen ab Nummer `seq` (einschließlich), älteste zuerst
+    pub fn since(&self, seq: u32) -> impl Iterator<Item = &LogLine<LEN>> {
+        // Abstand zur nächsten Nummer: übersteht den Überlauf von `seq`
+        let newest = self.next_seq;
+        self.iter()
+            .filter(move |line| newest.wrapping_sub(line.seq) <= newest.wrapping_sub(seq))
+    }
+}
+
+impl<const N: usize, const LEN: usize> Default for LogRing<N, LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,82 @@
+//! Logging-Fassade für die gemeinsame Logik
+//!
+//! esp-core läuft auf dem Chip, im Simulator und in den Host-Tests. defmt gibt
+//! es nur auf dem Chip, deshalb loggt esp-core über eigene Makros, deren
+//! Backend per Feature gewählt wird:
+//!
+//! - `defmt`: Ausgabe über defmt (Firmware)
+//! - `log`: Ausgabe über die `log`-Crate (Simulator, Tests mit Logger)
+//! - keins: Aufrufe entfallen, Argumente werden nur typgeprüft
+//!
+//! Sind beide Features aktiv, gewinnt defmt.
+//!
+//! Format-Strings müssen für beide Backends gültig sein: nur `{}` und `{:?}`
+//! ohne defmt-Typangaben (`{=u8}`). Argumente brauchen `Display` bzw. `Debug`
+//! und `defmt::Format`, in der Praxis also Zahlen und `&str`.
+
+#[doc(hidden)]
+#[cfg(feature = "defmt")]
+pub use defmt as __defmt;
+
+#[doc(hidden)]
+#[cfg(all(feature = "log", not(feature = "defmt")))]
+pub use log as __log;
+
+#[doc(hidden)]
+#[cfg(feature = "defmt")]
+#[macro_export]
+macro_rules! __log_backend {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::logging::__defmt::$level!($($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[cfg(all(feature = "log", not(feature = "defmt")))]
+#[macro_export]
+macro_rules! __log_backend {
+    ($level:ident, $($arg:tt)+) => {
+        $crate::logging::__log::$level!($($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(any(feature = "defmt", feature = "log")))]
+#[macro_export]
+macro_rules! __log_backend {
+    ($level:ident, $($arg:tt)+) => {
+        if false {
+            let _ = ::core::format_args!($($arg)+);
+        }
+    };
+}
+
+/// Trace-Meldung über das gewählte Backend
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)+) => { $crate::__log_backend!(trace, $($arg)+) };
+}
+
+/// Debug-Meldung über das gewählte Backend
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)+) => { $crate::__log_backend!(debug, $($arg)+) };
+}
+
+/// Info-Meldung über das gewählte Backend
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)+) => { $crate::__log_backend!(info, $($arg)+) };
+}
+
+/// Warnung über das gewählte Backend
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)+) => { $crate::__log_backend!(warn, $($arg)+) };
+}
+
+/// Fehlermeldung über das gewählte Backend
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)+) => { $crate::__log_backend!(error, $($arg)+) };
+}
@@ -0,0 +1,48 @@
+//! LED-Matrix: Zuordnung von Koordinaten zu Pixel-Indizes
+//!
+//! Matrizen sind ein zeilenweise verlegter Strip. Bei Schlangenlinien-
+//! Verkabelung (`serpentine`) läuft jede zweite Zeile von rechts nach links.
+//! Ein einfacher Strip ist eine Matrix mit einer Zeile.
+
+/// Abmessungen und Verkabelung einer LED-Matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixLayout {
+    pub width: usize,
+    pub height: usize,
+    /// Ungerade Zeilen laufen rückwärts (Zickzack-Verkabelung)
+    pub serpentine: bool,
+}
+
+impl MatrixLayout {
+    pub const fn new(width: usize, height: usize, serpentine: bool) -> Self {
+        Self {
+            width,
+            height,
+            serpentine,
+        }
+    }
+
+    /// Anzahl der Pixel
+    pub const fn len(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pixel-Index für Spalte `x` und Zeile `y` (0,0 = oben links)
+    ///
+    /// `None` außerhalb der Matrix.
+    pub fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let column = if self.serpentine && y % 2 == 1 {
+            self.width - 1 - x
+        } else {
+            x
+        };
+        Some(y * self.width + column)
+    }
+}
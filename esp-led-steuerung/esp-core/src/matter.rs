@@ -0,0 +1,175 @@
+//! Matter-Lichtmodell (Extended Color Light)
+//!
+//! Bildet die Attribute der Matter-Cluster On/Off, Level Control und
+//! Color Control auf LED-Kommandos ab und umgekehrt:
+//!
+//! | Cluster         | Attribut                        | Wertebereich      |
+//! |-----------------|---------------------------------|-------------------|
+//! | On/Off          | `OnOff`                         | bool              |
+//! | Level Control   | `CurrentLevel`                  | 1-254             |
+//! | Color Control   | `CurrentHue`, `CurrentSaturation` | 0-254           |
+//! | Color Control   | `ColorTemperatureMireds`        | 153-1000          |
+//!
+//! Der eigentliche Matter-Stack (Commissioning, Fabrics, Transport) läuft in
+//! der Firmware, hier liegt nur die Umrechnung. Nur Integer-Arithmetik.
+
+use rgb::RGB8;
+
+use crate::color::{HUE_CIRCLE, Hsv, color_temperature};
+use crate::types::{LedCommand, classify_color};
+
+/// Endpoint des Lichts (Endpoint 0 ist der Root-Node)
+pub const LIGHT_ENDPOINT_ID: u16 = 1;
+
+/// Device-Type "Extended Color Light"
+pub const DEVICE_TYPE_EXTENDED_COLOR_LIGHT: u32 = 0x010D;
+
+/// Cluster-IDs
+pub mod cluster {
+    pub const ON_OFF: u32 = 0x0006;
+    pub const LEVEL_CONTROL: u32 = 0x0008;
+    pub const COLOR_CONTROL: u32 = 0x0300;
+}
+
+/// Kleinster gültiger Level (0 ist laut Spezifikation reserviert)
+pub const MIN_LEVEL: u8 = 1;
+
+/// Größter gültiger Level, Hue und Saturation
+pub const MAX_LEVEL: u8 = 254;
+
+/// Farbtemperatur-Grenzen in Mireds (6500 K bzw. 1000 K)
+pub const MIN_MIREDS: u16 = 153;
+pub const MAX_MIREDS: u16 = 1000;
+
+/// Attribut-Änderung durch einen Matter-Controller (Apple Home, Google Home, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatterAttribute {
+    OnOff(bool),
+    Level(u8),
+    HueSaturation { hue: u8, saturation: u8 },
+    ColorTemperature { mireds: u16 },
+}
+
+/// Zustand des Matter-Lichts
+///
+/// Hue, Saturation und Level bleiben beim Ausschalten erhalten, damit
+/// `On` die letzte Farbe wiederherstellt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatterLight {
+    pub on: bool,
+    pub level: u8,
+    pub hue: u8,
+    pub saturation: u8,
+}
+
+impl Default for MatterLight {
+    /// Aus, Weiß bei voller Helligkeit
+    fn default() -> Self {
+        Self {
+            on: false,
+            level: MAX_LEVEL,
+            hue: 0,
+            saturation: 0,
+        }
+    }
+}
+
+impl MatterLight {
+    /// Aktuelle Farbe (unabhängig von `on`)
+    pub fn color(&self) -> RGB8 {
+        hsv_to_rgb(self.hue, self.saturation, level_to_value(self.level))
+    }
+
+    /// Übernimmt eine Attribut-Änderung und liefert das passende LED-Kommando
+    pub fn apply(&mut self, attribute: MatterAttribute) -> LedCommand {
+        match attribute {
+            MatterAttribute::OnOff(on) => self.on = on,
+            MatterAttribute::Level(level) => {
+                self.level = level.clamp(MIN_LEVEL, MAX_LEVEL);
+                self.on = true;
+            }
+            MatterAttribute::HueSaturation { hue, saturation } => {
+                self.hue = hue.min(MAX_LEVEL);
+                self.saturation = saturation.min(MAX_LEVEL);
+                self.on = true;
+            }
+            MatterAttribute::ColorTemperature { mireds } => {
+                let mireds = mireds.clamp(MIN_MIREDS, MAX_MIREDS) as u32;
+                let white = color_temperature((1_000_000 / mireds) as u16);
+                let (hue, saturation, _) = rgb_to_hsv(white);
+                self.hue = hue;
+                self.saturation = saturation;
+                self.on = true;
+            }
+        }
+        self.command()
+    }
+
+    /// LED-Kommando für den aktuellen Zustand
+    pub fn command(&self) -> LedCommand {
+        let color = self.color();
+        if !self.on || color == RGB8::default() {
+            return LedCommand::Off;
+        }
+        LedCommand::SetColor {
+            target_color: color,
+            color_id: classify_color(color),
+        }
+    }
+
+    /// Gleicht den Zustand mit der tatsächlichen LED-Farbe ab
+    ///
+    /// Für Änderungen aus anderen Quellen (Web UI, MQTT, Auto-Modus), damit
+    /// Controller den echten Zustand anzeigen. Gibt `true` zurück, wenn sich
+    /// ein Attribut geändert hat.
+    pub fn sync_from_color(&mut self, color: RGB8) -> bool {
+        let previous = *self;
+        if color == RGB8::default() {
+            self.on = false;
+        } else {
+            let (hue, saturation, value) = rgb_to_hsv(color);
+            self.on = true;
+            self.hue = hue;
+            self.saturation = saturation;
+            self.level = value_to_level(value);
+        }
+        *self != previous
+    }
+}
+
+/// Level (1-254) → Helligkeit (0-255)
+fn level_to_value(level: u8) -> u8 {
+    (level.min(MAX_LEVEL) as u32 * 255 / MAX_LEVEL as u32) as u8
+}
+
+/// Helligkeit (0-255) → Level (1-254)
+fn value_to_level(value: u8) -> u8 {
+    ((value as u32 * MAX_LEVEL as u32 + 127) / 255).max(MIN_LEVEL as u32) as u8
+}
+
+/// HSV → RGB mit Matter-Wertebereichen (Hue/Saturation 0-254, Value 0-255)
+pub fn hsv_to_rgb(hue: u8, saturation: u8, value: u8) -> RGB8 {
+    let circle = HUE_CIRCLE as u32;
+    let hue = (hue.min(MAX_LEVEL) as u32 * circle / MAX_LEVEL as u32) % circle;
+    let saturation = saturation.min(MAX_LEVEL) as u32 * 255 / MAX_LEVEL as u32;
+    Hsv {
+        hue: hue as u16,
+        saturation: saturation as u8,
+        value,
+    }
+    .to_rgb()
+}
+
+/// RGB → HSV mit Matter-Wertebereichen (Hue/Saturation 0-254, Value 0-255)
+pub fn rgb_to_hsv(color: RGB8) -> (u8, u8, u8) {
+    let max = color.r.max(color.g).max(color.b);
+    let min = color.r.min(color.g).min(color.b);
+    let delta = (max - min) as u32;
+    if delta == 0 {
+        return (0, 0, max);
+    }
+    let saturation = ((delta * MAX_LEVEL as u32 + max as u32 / 2) / max as u32) as u8;
+    let circle = HUE_CIRCLE as u32;
+    let hue = (Hsv::from_rgb(color).hue as u32 * MAX_LEVEL as u32 + circle / 2) / circle;
+    (hue as u8, saturation, max)
+}
@@ -0,0 +1,130 @@
+//! Meteor-Effekt für Strips
+//!
+//! Ein heller Kopf läuft den Strip entlang und zieht einen Schweif hinter
+//! sich her, der linear ausblendet. Ist der Schweif hinter dem Strip-Ende
+//! verschwunden, beginnt der Meteor von vorn. Wie das Testmuster
+//! ([`crate::test_pattern`]) eine reine Funktion der Zeit.
+//!
+//! Tempo, Schweiflänge und Farbe sind live einstellbar ([`LedEffect`]).
+
+use rgb::RGB8;
+
+use crate::color::scale_brightness;
+use crate::effect_param::{LedEffect, ParamValue};
+use crate::effects::{EffectId, EffectParam};
+use crate::matrix::MatrixLayout;
+
+/// Standard-Geschwindigkeit in Pixeln pro Sekunde
+pub const DEFAULT_METEOR_SPEED: u8 = 30;
+
+/// Standard-Schweiflänge in Pixeln (ohne Kopf)
+pub const DEFAULT_METEOR_TAIL: u8 = 8;
+
+/// Meteor ab `start_ms`, wiederholt sich bis zum nächsten Kommando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Meteor {
+    pub start_ms: u64,
+    /// Farbe des Kopfs
+    pub color: RGB8,
+    /// Pixel pro Sekunde
+    pub speed: u8,
+    /// Schweiflänge in Pixeln (ohne Kopf)
+    pub tail: u8,
+    /// Bis `start_ms` zurückgelegte Pixel (nach Tempo-Wechsel)
+    pub offset: u64,
+}
+
+impl Meteor {
+    /// Live-Parameter (siehe [`LedEffect`])
+    pub const PARAMS: &'static [EffectParam] = &[
+        EffectParam {
+            max: Some(u8::MAX as u32),
+            default: Some(DEFAULT_METEOR_SPEED as u32),
+            ..EffectParam::integer("speed", false, 1)
+        },
+        EffectParam {
+            max: Some(u8::MAX as u32),
+            default: Some(DEFAULT_METEOR_TAIL as u32),
+            ..EffectParam::integer("tail", false, 0)
+        },
+        EffectParam::text("color", false),
+    ];
+
+    pub const fn new(start_ms: u64, color: RGB8, speed: u8, tail: u8) -> Self {
+        Self {
+            start_ms,
+            color,
+            speed,
+            tail,
+            offset: 0,
+        }
+    }
+
+    /// Zurückgelegte Pixel zum Zeitpunkt `now_ms`
+    fn travelled(&self, now_ms: u64) -> u64 {
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        self.offset + elapsed * u64::from(self.speed) / 1000
+    }
+
+    /// Position des Kopfs zum Zeitpunkt `now_ms` für `led_count` Pixel
+    ///
+    /// Läuft bis `led_count + tail - 1`, damit der Schweif den Strip
+    /// vollständig verlässt, bevor der Kopf wieder bei 0 beginnt.
+    pub fn head_at(&self, now_ms: u64, led_count: usize) -> usize {
+        let cycle = (led_count + usize::from(self.tail)) as u64;
+        if cycle == 0 {
+            return 0;
+        }
+        (self.travelled(now_ms) % cycle) as usize
+    }
+
+    /// Helligkeit eines Pixels `distance` Pixel hinter dem Kopf (255 = Kopf)
+    pub fn tail_brightness(&self, distance: usize) -> u8 {
+        let steps = usize::from(self.tail) + 1;
+        if distance >= steps {
+            return 0;
+        }
+        ((steps - distance) * 255 / steps) as u8
+    }
+
+    /// Füllt `pixels` mit dem Bild zum Zeitpunkt `now_ms`
+    pub fn fill(&self, now_ms: u64, pixels: &mut [RGB8]) {
+        let head = self.head_at(now_ms, pixels.len());
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            // Pixel vor dem Kopf bleiben dunkel
+            let brightness = head
+                .checked_sub(index)
+                .map_or(0, |distance| self.tail_brightness(distance));
+            *pixel = scale_brightness(self.color, brightness);
+        }
+    }
+}
+
+impl LedEffect for Meteor {
+    fn id(&self) -> EffectId {
+        EffectId::Meteor
+    }
+
+    fn params(&self) -> &'static [EffectParam] {
+        Self::PARAMS
+    }
+
+    fn set_param(&mut self, value: ParamValue, now_ms: u64) {
+        match value {
+            ParamValue::Speed(speed) => {
+                // Kopf läuft ab der aktuellen Position mit neuem Tempo weiter
+                self.offset = self.travelled(now_ms);
+                self.start_ms = self.start_ms.max(now_ms);
+                self.speed = speed;
+            }
+            ParamValue::Tail(tail) => self.tail = tail,
+            ParamValue::Color(color) => self.color = color,
+            ParamValue::Intensity(_) | ParamValue::Palette(_) => {}
+        }
+    }
+
+    /// Läuft den Strip entlang, das Matrix-Layout spielt keine Rolle
+    fn render(&self, now_ms: u64, _layout: MatrixLayout, pixels: &mut [RGB8]) {
+        self.fill(now_ms, pixels);
+    }
+}
@@ -0,0 +1,162 @@
+//! Gemeinsame Mocks für Tests und Simulator (Feature `test-util`)
+//!
+//! Ersetzt die bisher mehrfach kopierten Mocks in Firmware und esp-tests:
+//!
+//! - [`MockLedWriter`]: zeichnet jeden Schreibvorgang mit Zeitstempel auf,
+//!   Fehler lassen sich pro Schreibvorgang vorgeben
+//! - [`MockClock`]: manuell vorgestellte Zeit, Kopien teilen sich die Zeit
+//!
+//! Benötigt `alloc` und 64-Bit-Atomics, ist also nur für Host-Builds gedacht.
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use rgb::RGB8;
+
+use crate::traits::{Clock, LedError, SmartLedWriter};
+
+/// Uhr mit manuell vorgestellter Zeit
+///
+/// `clone()` liefert eine Uhr mit derselben Zeitbasis, so können LED-Loop
+/// und [`MockLedWriter`] dieselbe Zeit sehen.
+#[derive(Debug, Clone, Default)]
+pub struct MockClock {
+    now_ms: Arc<AtomicU64>,
+}
+
+impl MockClock {
+    /// Uhr mit Startzeit `start_ms`
+    pub fn new(start_ms: u64) -> Self {
+        Self {
+            now_ms: Arc::new(AtomicU64::new(start_ms)),
+        }
+    }
+
+    /// Stellt die Zeit um `ms` Millisekunden vor
+    pub fn advance(&self, ms: u64) {
+        self.now_ms.fetch_add(ms, Ordering::Relaxed);
+    }
+
+    /// Setzt die Zeit auf `now_ms`
+    pub fn set(&self, now_ms: u64) {
+        self.now_ms.store(now_ms, Ordering::Relaxed);
+    }
+}
+
+impl Clock for MockClock {
+    fn now_ms(&self) -> u64 {
+        self.now_ms.load(Ordering::Relaxed)
+    }
+}
+
+/// Ein aufgezeichneter Schreibvorgang
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedWrite {
+    /// Zeit des Schreibvorgangs (0 ohne Uhr)
+    pub timestamp_ms: u64,
+    /// Geschriebene Pixel (`write()` ergibt genau ein Pixel)
+    pub pixels: Vec<RGB8>,
+    /// Schreibvorgang wurde per Fehler-Skript abgelehnt
+    pub failed: bool,
+}
+
+/// Mock LED Writer mit vollständiger Schreib-Historie
+///
+/// Zeichnet erfolgreiche und fehlgeschlagene Schreibvorgänge auf.
+/// Fehler werden über ein Skript vorgegeben: jeder Schreibvorgang entnimmt
+/// den nächsten Eintrag (`true` = Fehler), ein leeres Skript bedeutet Erfolg.
+#[derive(Debug, Clone, Default)]
+pub struct MockLedWriter {
+    /// Alle Schreibvorgänge in Reihenfolge
+    pub history: Vec<LedWrite>,
+    failures: VecDeque<bool>,
+    clock: Option<MockClock>,
+}
+
+impl MockLedWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writer, der Zeitstempel von `clock` übernimmt
+    pub fn with_clock(clock: MockClock) -> Self {
+        Self {
+            clock: Some(clock),
+            ..Self::default()
+        }
+    }
+
+    /// Lässt den nächsten Schreibvorgang fehlschlagen
+    pub fn fail_next_write(&mut self) {
+        self.failures.push_front(true);
+    }
+
+    /// Hängt ein Fehler-Skript an (`true` = Fehler, `false` = Erfolg)
+    pub fn script_failures(&mut self, script: impl IntoIterator<Item = bool>) {
+        self.failures.extend(script);
+    }
+
+    /// Lässt die nächsten `count` Schreibvorgänge fehlschlagen
+    pub fn fail_writes(&mut self, count: usize) {
+        self.script_failures(core::iter::repeat_n(true, count));
+    }
+
+    /// Anzahl erfolgreicher Schreibvorgänge
+    pub fn write_count(&self) -> usize {
+        self.successful().count()
+    }
+
+    /// Anzahl fehlgeschlagener Schreibvorgänge
+    pub fn failed_count(&self) -> usize {
+        self.history.iter().filter(|write| write.failed).count()
+    }
+
+    /// Erstes Pixel des letzten erfolgreichen Schreibvorgangs
+    pub fn last_color(&self) -> Option<RGB8> {
+        self.last_frame().and_then(|pixels| pixels.first().copied())
+    }
+
+    /// Pixel des letzten erfolgreichen Schreibvorgangs
+    pub fn last_frame(&self) -> Option<&[RGB8]> {
+        self.successful()
+            .last()
+            .map(|write| write.pixels.as_slice())
+    }
+
+    /// Erfolgreiche Schreibvorgänge in Reihenfolge
+    pub fn successful(&self) -> impl DoubleEndedIterator<Item = &LedWrite> {
+        self.history.iter().filter(|write| !write.failed)
+    }
+
+    /// Leert die Historie (das Fehler-Skript bleibt erhalten)
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    fn record(&mut self, pixels: Vec<RGB8>) -> Result<(), LedError> {
+        let failed = self.failures.pop_front().unwrap_or(false);
+        self.history.push(LedWrite {
+            timestamp_ms: self.clock.as_ref().map_or(0, Clock::now_ms),
+            pixels,
+            failed,
+        });
+        if failed {
+            Err(LedError::WriteFailed)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl SmartLedWriter for MockLedWriter {
+    fn write(&mut self, color: RGB8) -> Result<(), LedError> {
+        self.record(vec![color])
+    }
+
+    fn write_frame(&mut self, pixels: &[RGB8]) -> Result<(), LedError> {
+        self.record(pixels.to_vec())
+    }
+}
@@ -0,0 +1,78 @@
+//! Bewegungsmelder: Licht bei Bewegung für eine Haltezeit einschalten
+//!
+//! Bei der ersten Bewegung wird ein Preset angewendet und ein Sleep-Timer
+//! über die Haltezeit gestartet, der das Licht danach ausblendet
+//! ([`crate::transition::SleepTimer`]). Weitere Bewegung verlängert die
+//! Haltezeit, neue Sleep-Timer aber höchstens alle
+//! [`MOTION_EXTEND_INTERVAL_MS`], damit ein dauerhaft aktiver Melder den
+//! Kommando-Channel nicht flutet.
+
+use crate::types::LedCommand;
+
+/// Mindestabstand zwischen zwei Verlängerungen der Haltezeit
+pub const MOTION_EXTEND_INTERVAL_MS: u64 = 60_000;
+
+/// Reaktion auf eine erkannte Bewegung
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionAction {
+    /// Licht war aus: Preset anwenden und Sleep-Timer starten
+    SwitchOn,
+    /// Licht ist an: Sleep-Timer neu starten
+    Extend,
+}
+
+/// Zustand des Bewegungsmelders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MotionTrigger {
+    preset_id: u8,
+    hold_minutes: u16,
+    /// Ende der Haltezeit des zuletzt gesendeten Sleep-Timers
+    active_until_ms: Option<u64>,
+    /// Zeitpunkt des zuletzt gesendeten Sleep-Timers
+    last_sent_ms: u64,
+}
+
+impl MotionTrigger {
+    /// Preset `preset_id`, Haltezeit `hold_minutes` (mindestens 1)
+    pub const fn new(preset_id: u8, hold_minutes: u16) -> Self {
+        Self {
+            preset_id,
+            hold_minutes: if hold_minutes == 0 { 1 } else { hold_minutes },
+            active_until_ms: None,
+            last_sent_ms: 0,
+        }
+    }
+
+    /// Prüft, ob die Haltezeit zum Zeitpunkt `now_ms` läuft
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        self.active_until_ms.is_some_and(|until| now_ms < until)
+    }
+
+    /// Bewegung zum Zeitpunkt `now_ms`, `None` wenn nichts zu senden ist
+    pub fn motion(&mut self, now_ms: u64) -> Option<MotionAction> {
+        let action = if !self.is_active(now_ms) {
+            MotionAction::SwitchOn
+        } else if now_ms.saturating_sub(self.last_sent_ms) >= MOTION_EXTEND_INTERVAL_MS {
+            MotionAction::Extend
+        } else {
+            return None;
+        };
+        self.last_sent_ms = now_ms;
+        self.active_until_ms = Some(now_ms + u64::from(self.hold_minutes) * 60_000);
+        Some(action)
+    }
+
+    /// Zu sendende Kommandos einer Aktion (in dieser Reihenfolge)
+    ///
+    /// Das Preset beendet einen laufenden Sleep-Timer, daher folgt der neue
+    /// Timer immer danach.
+    pub fn commands(&self, action: MotionAction) -> impl Iterator<Item = LedCommand> {
+        let preset = (action == MotionAction::SwitchOn)
+            .then_some(LedCommand::ApplyPreset { id: self.preset_id });
+        preset
+            .into_iter()
+            .chain(core::iter::once(LedCommand::SleepTimer {
+                minutes: self.hold_minutes,
+            }))
+    }
+}
@@ -0,0 +1,256 @@
+//! MQTT-Payloads und Topics
+//!
+//! Alles, was der MQTT-Task an den Broker schickt, entsteht hier: Zustand,
+//! Birth-Message, Telemetrie (Heap, Health), Eingänge und Tasmota-Statusmeldungen.
+//! Wie bei [`crate::ha_discovery`] schreiben die Funktionen ohne serde direkt in
+//! einen [`core::fmt::Write`], damit die exakten Bytes auf dem Host testbar sind.
+//!
+//! | Topic                      | Payload                                          |
+//! |----------------------------|--------------------------------------------------|
+//! | `MQTT_TOPIC_COLOR`         | lokalisierter Farbname, z.B. `Rot`               |
+//! | `MQTT_TOPIC_MODE`          | lokalisierter Modus, z.B. `Auto`                 |
+//! | `MQTT_TOPIC_STATE`         | `{"color":"Rot","rgb":{...},"mode":"auto","seq":7,"timestamp_ms":1200}` |
+//! | `MQTT_TOPIC_INFO`          | `{"status":"online","client_id":...,"version":...,...}` |
+//! | `MQTT_TOPIC_HEAP`          | `{"used":41230,"free":61170,...}`                |
+//! | `MQTT_TOPIC_HEALTH`        | `{"rssi":-61,"uptime":3600,...}`                 |
+//! | `MQTT_TOPIC_INPUTS/<name>` | Zustandsname des Eingangs, z.B. `open`           |
+//! | `MQTT_TOPIC_COMMAND_RESULT`| `{"id":"42","command":"rot","status":"ok"}`      |
+//! | `stat/<topic>/RESULT`      | `{"POWER":"ON","Color":"FF0000"}`                |
+
+use core::fmt::{self, Write};
+
+use rgb::RGB8;
+
+use crate::command::CommandParseError;
+use crate::ha_discovery::write_json_string;
+use crate::heap::HeapStats;
+use crate::i18n::Language;
+use crate::tasmota::{format_hex_color, power_state};
+use crate::types::{ColorLabel, LedColorMessage};
+
+/// Inhalt der Birth-Message (retained, Firmware-Stand am Broker sichtbar)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BirthInfo<'a> {
+    pub client_id: &'a str,
+    pub version: &'a str,
+    pub git_hash: &'a str,
+    pub build_timestamp: u64,
+}
+
+/// Diagnose-Werte der Health-Nachricht
+///
+/// Schlüssel entsprechen [`crate::HaSensor::key`], fehlende Messwerte werden als `null` gesendet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthInfo<'a> {
+    /// WLAN-Signalstärke in dBm (`None` ohne Verbindung)
+    pub rssi: Option<i32>,
+    /// Sekunden seit dem Start
+    pub uptime: u64,
+    pub heap_free: u32,
+    /// Chip-Temperatur in °C
+    pub temperature: Option<f32>,
+    /// Aktiver Broker (wechselt bei Fallback)
+    pub broker: &'a str,
+}
+
+/// Farb-Payload für `MQTT_TOPIC_COLOR` in der Anzeigesprache
+///
+/// Siehe [`LedColorMessage::display_name`]: Preset-Name bzw. `RGB(…)` für
+/// Farben ohne Namen.
+pub fn color_payload(language: Language, msg: &LedColorMessage) -> ColorLabel {
+    msg.display_name(language)
+}
+
+/// Modus-Payload für `MQTT_TOPIC_MODE` in der Anzeigesprache
+pub fn mode_payload(language: Language, msg: &LedColorMessage) -> &'static str {
+    language.mode_name(msg.is_auto_mode)
+}
+
+/// Schreibt den LED-Zustand (JSON mit Sequenznummer, Lücken = verpasste Updates)
+pub fn write_state_payload(out: &mut impl Write, msg: &LedColorMessage) -> fmt::Result {
+    out.write_str("{\"color\":")?;
+    write_json_string(out, msg.color_id.name())?;
+    write!(
+        out,
+        ",\"rgb\":{{\"r\":{},\"g\":{},\"b\":{}}},\"mode\":\"{}\",\"seq\":{},\"timestamp_ms\":{}}}",
+        msg.color.r,
+        msg.color.g,
+        msg.color.b,
+        if msg.is_auto_mode { "auto" } else { "manual" },
+        msg.sequence,
+        msg.timestamp_ms
+    )
+}
+
+/// Schreibt die Birth-Message (`status` ist immer `online`)
+pub fn write_birth_payload(out: &mut impl Write, info: &BirthInfo<'_>) -> fmt::Result {
+    out.write_str("{\"status\":\"online\",\"client_id\":")?;
+    write_json_string(out, info.client_id)?;
+    out.write_str(",\"version\":")?;
+    write_json_string(out, info.version)?;
+    out.write_str(",\"git_hash\":")?;
+    write_json_string(out, info.git_hash)?;
+    write!(out, ",\"build_timestamp\":{}}}", info.build_timestamp)
+}
+
+/// Schreibt die Heap-Telemetrie (gleiche Felder wie `GET /api/heap`)
+pub fn write_heap_payload(out: &mut impl Write, stats: &HeapStats) -> fmt::Result {
+    write!(
+        out,
+        "{{\"used\":{},\"free\":{},\"high_water\":{},\"size\":{},\"usage_percent\":{}}}",
+        stats.used,
+        stats.free,
+        stats.high_water,
+        stats.size(),
+        stats.usage_percent()
+    )
+}
+
+/// Schreibt die Diagnose-Werte für die Home-Assistant-Sensoren
+pub fn write_health_payload(out: &mut impl Write, health: &HealthInfo<'_>) -> fmt::Result {
+    out.write_str("{\"rssi\":")?;
+    match health.rssi {
+        Some(rssi) => write!(out, "{}", rssi)?,
+        None => out.write_str("null")?,
+    }
+    write!(
+        out,
+        ",\"uptime\":{},\"heap_free\":{},\"temperature\":",
+        health.uptime, health.heap_free
+    )?;
+    match health.temperature {
+        // NaN/Unendlich sind kein gültiges JSON
+        Some(temperature) if temperature.is_finite() => write!(out, "{}", temperature)?,
+        _ => out.write_str("null")?,
+    }
+    out.write_str(",\"broker\":")?;
+    write_json_string(out, health.broker)?;
+    out.write_char('}')
+}
+
+/// Maximale Länge (Bytes) des Kommandos in der Quittung, längere werden gekürzt
+pub const COMMAND_ACK_ECHO_LEN: usize = 64;
+
+/// Maximale Länge (Bytes) der Anfrage-ID eines Text-Kommandos
+pub const COMMAND_ID_MAX_LEN: usize = 32;
+
+/// Trennt die optionale Anfrage-ID vom Text-Kommando
+///
+/// `@42 rot` ergibt `(Some("42"), "rot")`. Die ID (höchstens
+/// [`COMMAND_ID_MAX_LEN`] Bytes aus `A-Z a-z 0-9 - _ .`) geht unverändert in
+/// die Quittung, damit mehrere Absender ihre Antworten auf dem gemeinsamen
+/// Quittungs-Topic zuordnen können. Ohne gültige ID bleibt der Text unverändert.
+pub fn split_command_id(text: &str) -> (Option<&str>, &str) {
+    let Some(rest) = text.trim_start().strip_prefix('@') else {
+        return (None, text);
+    };
+    let (id, command) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let valid = !id.is_empty()
+        && id.len() <= COMMAND_ID_MAX_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+    if valid {
+        (Some(id), command.trim_start())
+    } else {
+        (None, text)
+    }
+}
+
+/// Ergebnis eines Text-Kommandos (Quittung auf `MQTT_TOPIC_COMMAND_RESULT`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandAck {
+    /// Kommando an den LED-Task übergeben
+    Applied,
+    /// Kommando ungültig, nichts geändert
+    Invalid(CommandParseError),
+    /// Warteschlange des LED-Tasks voll, Kommando verworfen
+    Busy,
+}
+
+impl CommandAck {
+    /// Fehlername für `error`, `None` bei Erfolg
+    pub fn error_name(&self) -> Option<&'static str> {
+        match self {
+            CommandAck::Applied => None,
+            CommandAck::Invalid(error) => Some(error.name()),
+            CommandAck::Busy => Some("busy"),
+        }
+    }
+}
+
+/// Schreibt die Quittung eines Text-Kommandos
+///
+/// `{"command":"rot","status":"ok"}` bzw.
+/// `{"command":"lila","status":"error","error":"unknown_command"}`. Das Kommando
+/// wird zurückgeschickt, gekürzt auf [`COMMAND_ACK_ECHO_LEN`] Bytes. Eine
+/// Anfrage-ID (siehe [`split_command_id`]) steht vorne: `{"id":"42","command":...}`.
+pub fn write_command_ack(
+    out: &mut impl Write,
+    id: Option<&str>,
+    command: &str,
+    ack: CommandAck,
+) -> fmt::Result {
+    let mut end = command.len().min(COMMAND_ACK_ECHO_LEN);
+    while !command.is_char_boundary(end) {
+        end -= 1;
+    }
+    out.write_char('{')?;
+    if let Some(id) = id {
+        out.write_str("\"id\":")?;
+        write_json_string(out, id)?;
+        out.write_char(',')?;
+    }
+    out.write_str("\"command\":")?;
+    write_json_string(out, command[..end].trim())?;
+    match ack.error_name() {
+        None => out.write_str(",\"status\":\"ok\"}"),
+        Some(error) => write!(out, ",\"status\":\"error\",\"error\":\"{}\"}}", error),
+    }
+}
+
+/// Bestätigungs-Payload für `MQTT_TOPIC_RESTART`
+pub const RESTART_PAYLOAD: &str = "restart";
+
+/// Bestätigungs-Payload für `MQTT_TOPIC_FACTORY_RESET`
+pub const FACTORY_RESET_PAYLOAD: &str = "factory-reset";
+
+/// Prüft den Payload einer Systemaktion (Neustart, Werksreset)
+///
+/// Nur das Bestätigungswort löst die Aktion aus (Leerraum und
+/// Groß-/Kleinschreibung egal). Ein leerer Payload, mit dem die Firmware eine
+/// retained Anforderung löscht, und versehentliche Publishes bleiben wirkungslos.
+pub fn system_action_confirmed(payload: &[u8], confirm: &str) -> bool {
+    payload
+        .trim_ascii()
+        .eq_ignore_ascii_case(confirm.as_bytes())
+}
+
+/// Schreibt das Topic eines digitalen Eingangs (`<prefix>/<name>`)
+pub fn write_input_topic(out: &mut impl Write, prefix: &str, name: &str) -> fmt::Result {
+    write!(out, "{}/{}", prefix, name)
+}
+
+/// Schreibt ein Tasmota-Topic (`<prefix><device_topic>/<suffix>`)
+///
+/// `prefix` ist [`crate::tasmota::TASMOTA_COMMAND_PREFIX`] oder
+/// [`crate::tasmota::TASMOTA_STAT_PREFIX`].
+pub fn write_tasmota_topic(
+    out: &mut impl Write,
+    prefix: &str,
+    device_topic: &str,
+    suffix: &str,
+) -> fmt::Result {
+    write!(out, "{}{}/{}", prefix, device_topic, suffix)
+}
+
+/// Schreibt die Tasmota-Antwort für `stat/<topic>/RESULT`
+pub fn write_tasmota_result(out: &mut impl Write, color: RGB8) -> fmt::Result {
+    let hex = format_hex_color(color);
+    write!(
+        out,
+        "{{\"POWER\":\"{}\",\"Color\":\"{}\"}}",
+        power_state(color),
+        core::str::from_utf8(&hex).unwrap_or("000000")
+    )
+}
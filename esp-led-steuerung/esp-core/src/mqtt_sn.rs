@@ -0,0 +1,404 @@
+//! MQTT-SN 1.2 (MQTT for Sensor Networks) über UDP
+//!
+//! Kodiert und dekodiert die Nachrichten, die ein Client gegenüber einem
+//! MQTT-SN-Gateway braucht (z.B. Eclipse Paho MQTT-SN Gateway): Verbindungsaufbau,
+//! Registrierung von Topic-Namen, Abonnieren, Publizieren (QoS 0/1) und
+//! Keep-Alive. Statt Topic-Namen tragen PUBLISH-Nachrichten 16-Bit-Topic-IDs,
+//! die das Gateway per REGACK bzw. SUBACK vergibt.
+//!
+//! Aufbau jeder Nachricht: Länge (1 Byte, ab 256 Bytes `0x01` + u16),
+//! Nachrichtentyp (1 Byte), typabhängige Felder. Mehrbyte-Felder sind Big Endian.
+//! Gateway-Discovery (ADVERTISE/SEARCHGW), Will-Nachrichten und Sleep-Modus
+//! werden nicht unterstützt.
+
+/// Standard-Port des Paho MQTT-SN Gateways
+pub const MQTT_SN_PORT: u16 = 10000;
+
+/// Protokoll-ID in CONNECT
+const PROTOCOL_ID: u8 = 0x01;
+
+/// Nachrichtentypen
+pub mod msg_type {
+    pub const CONNECT: u8 = 0x04;
+    pub const CONNACK: u8 = 0x05;
+    pub const REGISTER: u8 = 0x0A;
+    pub const REGACK: u8 = 0x0B;
+    pub const PUBLISH: u8 = 0x0C;
+    pub const PUBACK: u8 = 0x0D;
+    pub const SUBSCRIBE: u8 = 0x12;
+    pub const SUBACK: u8 = 0x13;
+    pub const PINGREQ: u8 = 0x16;
+    pub const PINGRESP: u8 = 0x17;
+    pub const DISCONNECT: u8 = 0x18;
+}
+
+/// Bits im Flags-Feld
+const FLAG_QOS_SHIFT: u8 = 5;
+const FLAG_RETAIN: u8 = 0x10;
+const FLAG_CLEAN_SESSION: u8 = 0x04;
+const FLAG_TOPIC_ID_TYPE: u8 = 0x03;
+
+/// Fehler beim Kodieren bzw. Dekodieren
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MqttSnError {
+    /// Nachricht kürzer als im Längenfeld angegeben oder Felder fehlen
+    Truncated,
+    /// Längenfeld kleiner als der Header
+    InvalidLength,
+    /// Topic-Name bzw. Client-ID kein gültiges UTF-8
+    InvalidString,
+    /// Ausgabe-Buffer zu klein
+    BufferTooSmall,
+    /// Nachrichtentyp oder Topic-ID-Typ wird nicht unterstützt
+    Unsupported(u8),
+}
+
+/// Antwortcode in CONNACK, REGACK, PUBACK und SUBACK
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnCode {
+    Accepted,
+    Congestion,
+    InvalidTopicId,
+    /// Auch für unbekannte Codes
+    NotSupported,
+}
+
+impl ReturnCode {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0x00 => ReturnCode::Accepted,
+            0x01 => ReturnCode::Congestion,
+            0x02 => ReturnCode::InvalidTopicId,
+            _ => ReturnCode::NotSupported,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            ReturnCode::Accepted => 0x00,
+            ReturnCode::Congestion => 0x01,
+            ReturnCode::InvalidTopicId => 0x02,
+            ReturnCode::NotSupported => 0x03,
+        }
+    }
+}
+
+/// Eine MQTT-SN-Nachricht
+///
+/// Topic-Namen in REGISTER und SUBSCRIBE sind immer normale Namen
+/// (Topic-ID-Typ 0), PUBLISH nutzt die vom Gateway vergebene Topic-ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MqttSnPacket<'a> {
+    Connect {
+        clean_session: bool,
+        /// Keep-Alive in Sekunden
+        duration_secs: u16,
+        client_id: &'a str,
+    },
+    Connack {
+        return_code: ReturnCode,
+    },
+    Register {
+        /// 0 in Anfragen des Clients
+        topic_id: u16,
+        msg_id: u16,
+        topic_name: &'a str,
+    },
+    Regack {
+        topic_id: u16,
+        msg_id: u16,
+        return_code: ReturnCode,
+    },
+    Publish {
+        /// 0, 1 oder 2 (3 = QoS -1 wird wie 0 behandelt)
+        qos: u8,
+        retain: bool,
+        topic_id: u16,
+        /// 0 bei QoS 0
+        msg_id: u16,
+        payload: &'a [u8],
+    },
+    Puback {
+        topic_id: u16,
+        msg_id: u16,
+        return_code: ReturnCode,
+    },
+    Subscribe {
+        /// Maximale QoS für Nachrichten an den Client
+        qos: u8,
+        msg_id: u16,
+        topic_name: &'a str,
+    },
+    Suback {
+        /// Gewährte QoS
+        qos: u8,
+        /// 0 bei Wildcard-Abonnements (IDs folgen per REGISTER)
+        topic_id: u16,
+        msg_id: u16,
+        return_code: ReturnCode,
+    },
+    PingReq,
+    PingResp,
+    Disconnect,
+}
+
+/// Dekodiert eine empfangene MQTT-SN-Nachricht
+///
+/// Daten hinter der angegebenen Länge werden ignoriert.
+pub fn decode_mqtt_sn(packet: &[u8]) -> Result<MqttSnPacket<'_>, MqttSnError> {
+    let (&first, rest) = packet.split_first().ok_or(MqttSnError::Truncated)?;
+    let (length, header_len) = if first == 0x01 {
+        let length = read_u16(rest, 0)? as usize;
+        (length, 3)
+    } else {
+        (first as usize, 1)
+    };
+    if length <= header_len {
+        return Err(MqttSnError::InvalidLength);
+    }
+    let packet = packet.get(..length).ok_or(MqttSnError::Truncated)?;
+    let message_type = packet[header_len];
+    let body = &packet[header_len + 1..];
+
+    match message_type {
+        msg_type::CONNECT => {
+            let flags = read_u8(body, 0)?;
+            if read_u8(body, 1)? != PROTOCOL_ID {
+                return Err(MqttSnError::Unsupported(message_type));
+            }
+            Ok(MqttSnPacket::Connect {
+                clean_session: flags & FLAG_CLEAN_SESSION != 0,
+                duration_secs: read_u16(body, 2)?,
+                client_id: read_str(&body[4..])?,
+            })
+        }
+        msg_type::CONNACK => Ok(MqttSnPacket::Connack {
+            return_code: ReturnCode::from_u8(read_u8(body, 0)?),
+        }),
+        msg_type::REGISTER => Ok(MqttSnPacket::Register {
+            topic_id: read_u16(body, 0)?,
+            msg_id: read_u16(body, 2)?,
+            topic_name: read_str(&body[4..])?,
+        }),
+        msg_type::REGACK => Ok(MqttSnPacket::Regack {
+            topic_id: read_u16(body, 0)?,
+            msg_id: read_u16(body, 2)?,
+            return_code: ReturnCode::from_u8(read_u8(body, 4)?),
+        }),
+        msg_type::PUBLISH => {
+            let flags = read_u8(body, 0)?;
+            let topic_id = read_u16(body, 1)?;
+            let msg_id = read_u16(body, 3)?;
+            Ok(MqttSnPacket::Publish {
+                qos: (flags >> FLAG_QOS_SHIFT) & 0x03,
+                retain: flags & FLAG_RETAIN != 0,
+                topic_id,
+                msg_id,
+                payload: &body[5..],
+            })
+        }
+        msg_type::PUBACK => Ok(MqttSnPacket::Puback {
+            topic_id: read_u16(body, 0)?,
+            msg_id: read_u16(body, 2)?,
+            return_code: ReturnCode::from_u8(read_u8(body, 4)?),
+        }),
+        msg_type::SUBSCRIBE => {
+            let flags = read_u8(body, 0)?;
+            // Nur normale Topic-Namen (keine vordefinierten IDs oder Kurznamen)
+            if flags & FLAG_TOPIC_ID_TYPE != 0 {
+                return Err(MqttSnError::Unsupported(message_type));
+            }
+            let msg_id = read_u16(body, 1)?;
+            Ok(MqttSnPacket::Subscribe {
+                qos: (flags >> FLAG_QOS_SHIFT) & 0x03,
+                msg_id,
+                topic_name: read_str(&body[3..])?,
+            })
+        }
+        msg_type::SUBACK => {
+            let flags = read_u8(body, 0)?;
+            Ok(MqttSnPacket::Suback {
+                qos: (flags >> FLAG_QOS_SHIFT) & 0x03,
+                topic_id: read_u16(body, 1)?,
+                msg_id: read_u16(body, 3)?,
+                return_code: ReturnCode::from_u8(read_u8(body, 5)?),
+            })
+        }
+        // Optionale Felder (Client-ID bzw. Sleep-Dauer) werden ignoriert
+        msg_type::PINGREQ => Ok(MqttSnPacket::PingReq),
+        msg_type::PINGRESP => Ok(MqttSnPacket::PingResp),
+        msg_type::DISCONNECT => Ok(MqttSnPacket::Disconnect),
+        other => Err(MqttSnError::Unsupported(other)),
+    }
+}
+
+impl MqttSnPacket<'_> {
+    /// Schreibt die Nachricht nach `out`, gibt die Länge zurück
+    pub fn encode(&self, out: &mut [u8]) -> Result<usize, MqttSnError> {
+        let mut writer = Writer { out, pos: 0 };
+        // Platz für das kurze Längenfeld, bei Bedarf unten auf 3 Bytes erweitert
+        writer.u8(0)?;
+        match *self {
+            MqttSnPacket::Connect {
+                clean_session,
+                duration_secs,
+                client_id,
+            } => {
+                writer.u8(msg_type::CONNECT)?;
+                writer.u8(if clean_session { FLAG_CLEAN_SESSION } else { 0 })?;
+                writer.u8(PROTOCOL_ID)?;
+                writer.u16(duration_secs)?;
+                writer.bytes(client_id.as_bytes())?;
+            }
+            MqttSnPacket::Connack { return_code } => {
+                writer.u8(msg_type::CONNACK)?;
+                writer.u8(return_code.to_u8())?;
+            }
+            MqttSnPacket::Register {
+                topic_id,
+                msg_id,
+                topic_name,
+            } => {
+                writer.u8(msg_type::REGISTER)?;
+                writer.u16(topic_id)?;
+                writer.u16(msg_id)?;
+                writer.bytes(topic_name.as_bytes())?;
+            }
+            MqttSnPacket::Regack {
+                topic_id,
+                msg_id,
+                return_code,
+            } => {
+                writer.u8(msg_type::REGACK)?;
+                writer.u16(topic_id)?;
+                writer.u16(msg_id)?;
+                writer.u8(return_code.to_u8())?;
+            }
+            MqttSnPacket::Publish {
+                qos,
+                retain,
+                topic_id,
+                msg_id,
+                payload,
+            } => {
+                writer.u8(msg_type::PUBLISH)?;
+                let retain = if retain { FLAG_RETAIN } else { 0 };
+                writer.u8((qos & 0x03) << FLAG_QOS_SHIFT | retain)?;
+                writer.u16(topic_id)?;
+                writer.u16(msg_id)?;
+                writer.bytes(payload)?;
+            }
+            MqttSnPacket::Puback {
+                topic_id,
+                msg_id,
+                return_code,
+            } => {
+                writer.u8(msg_type::PUBACK)?;
+                writer.u16(topic_id)?;
+                writer.u16(msg_id)?;
+                writer.u8(return_code.to_u8())?;
+            }
+            MqttSnPacket::Subscribe {
+                qos,
+                msg_id,
+                topic_name,
+            } => {
+                writer.u8(msg_type::SUBSCRIBE)?;
+                writer.u8((qos & 0x03) << FLAG_QOS_SHIFT)?;
+                writer.u16(msg_id)?;
+                writer.bytes(topic_name.as_bytes())?;
+            }
+            MqttSnPacket::Suback {
+                qos,
+                topic_id,
+                msg_id,
+                return_code,
+            } => {
+                writer.u8(msg_type::SUBACK)?;
+                writer.u8((qos & 0x03) << FLAG_QOS_SHIFT)?;
+                writer.u16(topic_id)?;
+                writer.u16(msg_id)?;
+                writer.u8(return_code.to_u8())?;
+            }
+            MqttSnPacket::PingReq => writer.u8(msg_type::PINGREQ)?,
+            MqttSnPacket::PingResp => writer.u8(msg_type::PINGRESP)?,
+            MqttSnPacket::Disconnect => writer.u8(msg_type::DISCONNECT)?,
+        }
+
+        let len = writer.pos;
+        let out = writer.out;
+        if len < 256 {
+            out[0] = len as u8;
+            return Ok(len);
+        }
+        // Langes Längenfeld: Inhalt um 2 Bytes nach hinten schieben
+        let total = len + 2;
+        if total > u16::MAX as usize {
+            return Err(MqttSnError::InvalidLength);
+        }
+        if total > out.len() {
+            return Err(MqttSnError::BufferTooSmall);
+        }
+        out.copy_within(1..len, 3);
+        out[0] = 0x01;
+        out[1..3].copy_from_slice(&(total as u16).to_be_bytes());
+        Ok(total)
+    }
+}
+
+/// Fortlaufende Nachrichten-IDs für REGISTER, SUBSCRIBE und PUBLISH (QoS 1)
+///
+/// 0 ist für QoS-0-Nachrichten reserviert und wird übersprungen.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsgIdCounter(u16);
+
+impl MsgIdCounter {
+    pub fn next_id(&mut self) -> u16 {
+        self.0 = self.0.wrapping_add(1);
+        if self.0 == 0 {
+            self.0 = 1;
+        }
+        self.0
+    }
+}
+
+fn read_u8(bytes: &[u8], offset: usize) -> Result<u8, MqttSnError> {
+    bytes.get(offset).copied().ok_or(MqttSnError::Truncated)
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, MqttSnError> {
+    let field = bytes
+        .get(offset..offset + 2)
+        .ok_or(MqttSnError::Truncated)?;
+    Ok(u16::from_be_bytes([field[0], field[1]]))
+}
+
+fn read_str(bytes: &[u8]) -> Result<&str, MqttSnError> {
+    core::str::from_utf8(bytes).map_err(|_| MqttSnError::InvalidString)
+}
+
+/// Schreibt Felder fortlaufend in einen Buffer
+struct Writer<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    fn bytes(&mut self, bytes: &[u8]) -> Result<(), MqttSnError> {
+        let end = self.pos + bytes.len();
+        self.out
+            .get_mut(self.pos..end)
+            .ok_or(MqttSnError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+
+    fn u8(&mut self, value: u8) -> Result<(), MqttSnError> {
+        self.bytes(&[value])
+    }
+
+    fn u16(&mut self, value: u16) -> Result<(), MqttSnError> {
+        self.bytes(&value.to_be_bytes())
+    }
+}
@@ -0,0 +1,164 @@
+//! Netzwerk-Zähler pro Subsystem
+//!
+//! MQTT, WebSocket, HTTP und die UDP-Protokolle zählen ihren Verkehr in
+//! [`NetStats`]. Vergleicht man z.B. Publishes mit den Acks des Brokers
+//! oder steigen die Reconnects sprunghaft, fallen stiller Paketverlust und
+//! Reconnect-Stürme auf, ohne dass ein Task einen Fehler meldet.
+//!
+//! Alle Zähler laufen seit dem Start, sättigend und ohne Reset.
+
+use core::fmt;
+
+/// Gezählte Vorgänge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetCounter {
+    /// Gesendete MQTT-Publishes
+    MqttPublishes,
+    /// Empfangene MQTT-Nachrichten (abonnierte Topics)
+    MqttReceived,
+    /// Bestätigungen des Brokers (CONNACK, SUBACK; Publishes sind QoS 0)
+    MqttAcks,
+    /// Verbindungsabbrüche mit anschließendem Neuaufbau
+    MqttReconnects,
+    /// Empfangene WebSocket-Nachrichten (Text und Binär)
+    WsMessagesIn,
+    /// Gesendete WebSocket-Nachrichten
+    WsMessagesOut,
+    /// Beantwortete HTTP-Requests (inkl. WebSocket-Upgrade)
+    HttpRequests,
+    /// Empfangene UDP-Pakete (Realtime, Sync, CoAP, OSC, LIFX, MQTT-SN, SSDP, SNTP)
+    UdpPacketsIn,
+    /// Gesendete UDP-Pakete
+    UdpPacketsOut,
+}
+
+impl NetCounter {
+    /// Anzahl Zähler (Größe von [`NetStats`])
+    pub const COUNT: usize = 9;
+
+    /// Alle Zähler in Index-Reihenfolge
+    pub const ALL: [NetCounter; Self::COUNT] = [
+        NetCounter::MqttPublishes,
+        NetCounter::MqttReceived,
+        NetCounter::MqttAcks,
+        NetCounter::MqttReconnects,
+        NetCounter::WsMessagesIn,
+        NetCounter::WsMessagesOut,
+        NetCounter::HttpRequests,
+        NetCounter::UdpPacketsIn,
+        NetCounter::UdpPacketsOut,
+    ];
+
+    /// Kurzname für Logs und Konsole
+    pub fn name(&self) -> &'static str {
+        match self {
+            NetCounter::MqttPublishes => "mqtt_publishes",
+            NetCounter::MqttReceived => "mqtt_received",
+            NetCounter::MqttAcks => "mqtt_acks",
+            NetCounter::MqttReconnects => "mqtt_reconnects",
+            NetCounter::WsMessagesIn => "ws_messages_in",
+            NetCounter::WsMessagesOut => "ws_messages_out",
+            NetCounter::HttpRequests => "http_requests",
+            NetCounter::UdpPacketsIn => "udp_packets_in",
+            NetCounter::UdpPacketsOut => "udp_packets_out",
+        }
+    }
+
+    /// Prometheus-Metrik, Richtung als Label (`in`/`out`) und Beschreibung
+    fn metric(&self) -> (&'static str, Option<&'static str>, &'static str) {
+        match self {
+            NetCounter::MqttPublishes => {
+                ("net_mqtt_publishes_total", None, "Gesendete MQTT-Publishes")
+            }
+            NetCounter::MqttReceived => (
+                "net_mqtt_received_total",
+                None,
+                "Empfangene MQTT-Nachrichten",
+            ),
+            NetCounter::MqttAcks => (
+                "net_mqtt_acks_total",
+                None,
+                "Bestätigungen des MQTT-Brokers (CONNACK, SUBACK)",
+            ),
+            NetCounter::MqttReconnects => (
+                "net_mqtt_reconnects_total",
+                None,
+                "MQTT-Verbindungsabbrüche mit Neuaufbau",
+            ),
+            NetCounter::WsMessagesIn => ("net_ws_messages_total", Some("in"), WS_HELP),
+            NetCounter::WsMessagesOut => ("net_ws_messages_total", Some("out"), WS_HELP),
+            NetCounter::HttpRequests => (
+                "net_http_requests_total",
+                None,
+                "Beantwortete HTTP-Requests",
+            ),
+            NetCounter::UdpPacketsIn => ("net_udp_packets_total", Some("in"), UDP_HELP),
+            NetCounter::UdpPacketsOut => ("net_udp_packets_total", Some("out"), UDP_HELP),
+        }
+    }
+}
+
+const WS_HELP: &str = "WebSocket-Nachrichten nach Richtung";
+const UDP_HELP: &str = "UDP-Pakete aller Protokolle nach Richtung";
+
+/// Zähler aller Subsysteme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NetStats {
+    counts: [u32; NetCounter::COUNT],
+}
+
+impl NetStats {
+    pub const fn new() -> Self {
+        Self {
+            counts: [0; NetCounter::COUNT],
+        }
+    }
+
+    /// Zählt einen Vorgang (sättigend)
+    pub fn record(&mut self, counter: NetCounter) {
+        self.add(counter, 1);
+    }
+
+    /// Zählt mehrere Vorgänge auf einmal (sättigend)
+    pub fn add(&mut self, counter: NetCounter, amount: u32) {
+        let count = &mut self.counts[counter as usize];
+        *count = count.saturating_add(amount);
+    }
+
+    /// Stand eines Zählers
+    pub fn count(&self, counter: NetCounter) -> u32 {
+        self.counts[counter as usize]
+    }
+
+    /// Schreibt alle Zähler im Prometheus-Textformat
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::{NetCounter, NetStats};
+    /// let mut stats = NetStats::new();
+    /// stats.record(NetCounter::WsMessagesOut);
+    /// let mut out = String::new();
+    /// stats.write_prometheus(&mut out).unwrap();
+    /// assert!(out.contains("net_ws_messages_total{direction=\"out\"} 1\n"));
+    /// ```
+    pub fn write_prometheus(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let mut previous = "";
+        for counter in NetCounter::ALL {
+            let (name, direction, help) = counter.metric();
+            // Beide Richtungen teilen sich HELP und TYPE
+            if name != previous {
+                write!(out, "# HELP {name} {help}\n# TYPE {name} counter\n")?;
+                previous = name;
+            }
+            match direction {
+                Some(direction) => writeln!(
+                    out,
+                    "{name}{{direction=\"{direction}\"}} {}",
+                    self.count(counter)
+                )?,
+                None => writeln!(out, "{name} {}", self.count(counter))?,
+            }
+        }
+        Ok(())
+    }
+}
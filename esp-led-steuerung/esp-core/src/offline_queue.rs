@@ -0,0 +1,155 @@
+//! Puffer für MQTT-Zustandsänderungen während eines Broker-Ausfalls
+//!
+//! Ohne Verbindung liest der MQTT-Task Farb-Updates und Eingangswechsel
+//! weiter und legt sie in einer [`OfflineQueue`] ab. Nach dem Reconnect
+//! publisht er sie in der ursprünglichen Reihenfolge (Zustand mit `seq` und
+//! `timestamp_ms`), damit der Broker die Übergänge nachholt statt nur den
+//! letzten Stand zu sehen.
+//!
+//! Aufeinanderfolgende Farben der Auto-Rotation fasst die Queue zusammen,
+//! nur die neueste zählt. Sonst füllte der Farbwechsel im Sekundentakt den
+//! Puffer und würde nach dem Reconnect ungedrosselt nachgeholt (vgl.
+//! [`crate::PublishThrottle`]).
+//!
+//! Ist die Queue voll, fällt zuerst der älteste Eintrag weg, zu dessen Topic
+//! ein neuerer existiert. So bleibt mindestens der letzte Stand je Topic
+//! erhalten, solange es nicht mehr Topics als Plätze gibt.
+
+use crate::types::LedColorMessage;
+
+/// Verpasste Zustandsänderung
+#[derive(Clone, Copy)]
+pub enum OfflineChange {
+    /// Neue Farbe bzw. neuer Modus (Topics Farbe, Modus, Zustand, Tasmota)
+    Color(LedColorMessage),
+    /// Digitaler Eingang (Index in `DIGITAL_INPUTS`)
+    Input { input: u8, active: bool },
+}
+
+impl OfflineChange {
+    /// Prüft ob beide Änderungen dieselben Topics betreffen
+    pub fn same_topic(&self, other: &OfflineChange) -> bool {
+        match (self, other) {
+            (OfflineChange::Color(_), OfflineChange::Color(_)) => true,
+            (OfflineChange::Input { input, .. }, OfflineChange::Input { input: other, .. }) => {
+                input == other
+            }
+            _ => false,
+        }
+    }
+}
+
+/// FIFO mit fester Kapazität `N` (ohne Allokation)
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::{OfflineChange, OfflineQueue};
+/// let mut queue: OfflineQueue<2> = OfflineQueue::new();
+/// queue.push(OfflineChange::Input { input: 0, active: true });
+/// queue.push(OfflineChange::Input { input: 0, active: false });
+/// queue.push(OfflineChange::Input { input: 1, active: true });
+/// // Erster Wechsel von Eingang 0 verdrängt, letzter Stand beider Eingänge bleibt
+/// assert_eq!(queue.len(), 2);
+/// assert_eq!(queue.dropped(), 1);
+/// ```
+pub struct OfflineQueue<const N: usize> {
+    entries: [Option<OfflineChange>; N],
+    len: usize,
+    dropped: u32,
+}
+
+impl<const N: usize> Default for OfflineQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> OfflineQueue<N> {
+    /// Leere Queue
+    pub const fn new() -> Self {
+        Self {
+            entries: [None; N],
+            len: 0,
+            dropped: 0,
+        }
+    }
+
+    /// Anzahl wartender Änderungen
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Prüft ob keine Änderungen warten
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Verdrängte Änderungen seit dem letzten [`OfflineQueue::take_dropped`]
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    /// Liefert und setzt den Zähler verdrängter Änderungen zurück
+    pub fn take_dropped(&mut self) -> u32 {
+        core::mem::take(&mut self.dropped)
+    }
+
+    /// Hängt eine Änderung an (Zusammenfassen und volle Queue siehe Modul-Dokumentation)
+    pub fn push(&mut self, change: OfflineChange) {
+        // Folgt eine Auto-Farbe auf eine Auto-Farbe, ersetzt sie diese
+        let coalesce = matches!(
+            (&change, self.entries[..self.len].last()),
+            (OfflineChange::Color(new), Some(Some(OfflineChange::Color(last))))
+                if new.is_auto_mode && last.is_auto_mode
+        );
+        if coalesce {
+            self.entries[self.len - 1] = Some(change);
+            return;
+        }
+        if N == 0 {
+            self.dropped = self.dropped.saturating_add(1);
+            return;
+        }
+        if self.len == N {
+            // Ältesten Eintrag mit neuerem Eintrag gleichen Topics suchen,
+            // sonst den ältesten überhaupt
+            let victim = (0..N)
+                .find(|&index| {
+                    let Some(entry) = &self.entries[index] else {
+                        return false;
+                    };
+                    self.entries[index + 1..]
+                        .iter()
+                        .flatten()
+                        .chain(core::iter::once(&change))
+                        .any(|newer| newer.same_topic(entry))
+                })
+                .unwrap_or(0);
+            self.remove(victim);
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        self.entries[self.len] = Some(change);
+        self.len += 1;
+    }
+
+    /// Älteste Änderung, ohne sie zu entnehmen (erst nach erfolgreichem Publish [`OfflineQueue::pop`])
+    pub fn front(&self) -> Option<&OfflineChange> {
+        self.entries.first().and_then(Option::as_ref)
+    }
+
+    /// Entnimmt die älteste Änderung
+    pub fn pop(&mut self) -> Option<OfflineChange> {
+        if self.len == 0 {
+            return None;
+        }
+        let change = self.entries[0];
+        self.remove(0);
+        change
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.entries[index..self.len].rotate_left(1);
+        self.len -= 1;
+        self.entries[self.len] = None;
+    }
+}
@@ -0,0 +1,202 @@
+//! OSC (Open Sound Control 1.0) über UDP
+//!
+//! Dekodiert einzelne OSC-Nachrichten von Musik-/VJ-Software (TouchOSC,
+//! Ableton, Resolume) und bildet sie auf LED-Kommandos ab:
+//!
+//! | Adresse           | Argumente                                   |
+//! |-------------------|---------------------------------------------|
+//! | `/led/color`      | `r g b` (int 0-255 oder float 0.0-1.0), RGBA-Farbe (`r`) oder Name (`s`) |
+//! | `/led/brightness` | int 0-255 oder float 0.0-1.0                |
+//! | `/led/auto`       | optional: 0/false ignorieren                |
+//! | `/led/off`        | optional: 0/false ignorieren                |
+//! | `/led/command`    | Text-Kommando (siehe [`crate::command`])    |
+//!
+//! Buttons in TouchOSC senden beim Loslassen `0.0`, daher lösen `auto` und
+//! `off` nur bei einem Wert ungleich 0 (oder ohne Argument) aus.
+//! Bundles (`#bundle`) werden nicht unterstützt.
+
+use rgb::RGB8;
+
+use crate::command::parse_text_command;
+use crate::types::{LedCommand, classify_color};
+
+/// Standard-UDP-Port für OSC-Empfang
+pub const OSC_PORT: u16 = 8000;
+
+/// Fehler beim Dekodieren einer OSC-Nachricht
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OscError {
+    /// Paket abgeschnitten oder String ohne Null-Terminator
+    Truncated,
+    /// Adresse beginnt nicht mit `/` (oder ist ein Bundle)
+    InvalidAddress,
+    /// Typ-Tag-String fehlt oder beginnt nicht mit `,`
+    InvalidTypeTags,
+    /// Nicht unterstützter Argument-Typ
+    UnsupportedType(u8),
+}
+
+/// Einzelnes OSC-Argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OscArg<'a> {
+    Int(i32),
+    Float(f32),
+    Str(&'a str),
+    /// RGBA-Farbe (Typ `r`)
+    Color {
+        r: u8,
+        g: u8,
+        b: u8,
+        a: u8,
+    },
+    Bool(bool),
+}
+
+impl OscArg<'_> {
+    /// Wert als Kanal 0-255 (Float 0.0-1.0 wird skaliert, Werte werden begrenzt)
+    pub fn as_channel(&self) -> Option<u8> {
+        match *self {
+            OscArg::Int(value) => Some(value.clamp(0, 255) as u8),
+            OscArg::Float(value) => Some((value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8),
+            OscArg::Bool(value) => Some(if value { 255 } else { 0 }),
+            _ => None,
+        }
+    }
+
+    /// Prüft ob das Argument "aktiv" ist (Button gedrückt)
+    fn is_active(&self) -> bool {
+        self.as_channel().is_none_or(|value| value > 0)
+    }
+}
+
+/// Dekodierte OSC-Nachricht
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OscMessage<'a> {
+    pub address: &'a str,
+    type_tags: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> OscMessage<'a> {
+    /// Iterator über die Argumente (bricht bei ungültigen Daten ab)
+    pub fn args(&self) -> impl Iterator<Item = OscArg<'a>> + 'a {
+        let mut data = self.data;
+        self.type_tags
+            .iter()
+            .map_while(move |&tag| read_arg(tag, &mut data).ok())
+    }
+}
+
+/// Dekodiert eine OSC-Nachricht
+pub fn decode_osc_message(packet: &[u8]) -> Result<OscMessage<'_>, OscError> {
+    let (address, rest) = read_string(packet)?;
+    if !address.starts_with('/') {
+        return Err(OscError::InvalidAddress);
+    }
+    // Ältere Sender lassen den Typ-Tag-String weg
+    if rest.is_empty() {
+        return Ok(OscMessage {
+            address,
+            type_tags: &[],
+            data: &[],
+        });
+    }
+    let (tags, data) = read_string(rest)?;
+    let type_tags = tags
+        .strip_prefix(',')
+        .ok_or(OscError::InvalidTypeTags)?
+        .as_bytes();
+
+    // Alle Argumente einmal validieren, damit `args()` vollständig ist
+    let mut remaining = data;
+    for &tag in type_tags {
+        read_arg(tag, &mut remaining)?;
+    }
+    Ok(OscMessage {
+        address,
+        type_tags,
+        data,
+    })
+}
+
+/// Liest einen null-terminierten, auf 4 Bytes aufgefüllten String
+fn read_string(data: &[u8]) -> Result<(&str, &[u8]), OscError> {
+    let end = data
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or(OscError::Truncated)?;
+    let padded = (end + 4) & !3;
+    if data.len() < padded {
+        return Err(OscError::Truncated);
+    }
+    let text = core::str::from_utf8(&data[..end]).map_err(|_| OscError::Truncated)?;
+    Ok((text, &data[padded..]))
+}
+
+/// Liest ein Argument passend zum Typ-Tag
+fn read_arg<'a>(tag: u8, data: &mut &'a [u8]) -> Result<OscArg<'a>, OscError> {
+    let mut word = || -> Result<[u8; 4], OscError> {
+        let (&bytes, rest) = data.split_first_chunk::<4>().ok_or(OscError::Truncated)?;
+        *data = rest;
+        Ok(bytes)
+    };
+    match tag {
+        b'i' => Ok(OscArg::Int(i32::from_be_bytes(word()?))),
+        b'f' => Ok(OscArg::Float(f32::from_be_bytes(word()?))),
+        b'r' => {
+            let [r, g, b, a] = word()?;
+            Ok(OscArg::Color { r, g, b, a })
+        }
+        b's' => {
+            let (text, rest) = read_string(data)?;
+            *data = rest;
+            Ok(OscArg::Str(text))
+        }
+        b'T' => Ok(OscArg::Bool(true)),
+        b'F' => Ok(OscArg::Bool(false)),
+        other => Err(OscError::UnsupportedType(other)),
+    }
+}
+
+/// Bildet eine OSC-Nachricht auf ein LED-Kommando ab
+///
+/// `None` bei unbekannter Adresse, unpassenden Argumenten oder losgelassenem Button.
+pub fn osc_led_command(message: &OscMessage<'_>) -> Option<LedCommand> {
+    let mut args = message.args();
+    match message.address {
+        "/led/color" => {
+            let color = match args.next()? {
+                OscArg::Color { r, g, b, .. } => RGB8::new(r, g, b),
+                OscArg::Str(name) => return parse_text_command(name).ok(),
+                first => RGB8::new(
+                    first.as_channel()?,
+                    args.next()?.as_channel()?,
+                    args.next()?.as_channel()?,
+                ),
+            };
+            if color == RGB8::default() {
+                return Some(LedCommand::Off);
+            }
+            Some(LedCommand::SetColor {
+                target_color: color,
+                color_id: classify_color(color),
+            })
+        }
+        "/led/brightness" => Some(LedCommand::SetBrightness {
+            brightness: args.next()?.as_channel()?,
+        }),
+        "/led/auto" => args
+            .next()
+            .is_none_or(|arg| arg.is_active())
+            .then_some(LedCommand::EnableAuto),
+        "/led/off" => args
+            .next()
+            .is_none_or(|arg| arg.is_active())
+            .then_some(LedCommand::Off),
+        "/led/command" => match args.next()? {
+            OscArg::Str(text) => parse_text_command(text).ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
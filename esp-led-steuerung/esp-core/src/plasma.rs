@@ -0,0 +1,171 @@
+//! Plasma-Effekt für LED-Matrizen
+//!
+//! Klassisches Demoszenen-Plasma: pro Pixel die Summe dreier Sinuswellen
+//! (waagerecht, senkrecht, diagonal), die mit unterschiedlichem Tempo
+//! wandern. Der Wert wählt eine Farbe aus der Palette, der Farbfolge der
+//! Auto-Rotation ([`crate::ColorSequence`]), zyklisch interpoliert.
+//!
+//! Nur Integer-Arithmetik: Winkel in 256 Schritten pro Vollkreis, Sinus aus
+//! einer Viertelwellen-Tabelle. Auf einem Strip (eine Zeile) entsteht ein
+//! eindimensionales Plasma.
+//!
+//! Tempo, Intensität (Wellendichte) und Palette sind live einstellbar
+//! ([`LedEffect`]).
+
+use rgb::RGB8;
+
+use crate::color::{PERMILLE_MAX, lerp_color};
+use crate::effect_param::{LedEffect, ParamValue};
+use crate::effects::{EffectId, EffectParam};
+use crate::matrix::MatrixLayout;
+use crate::sequence::ColorSequence;
+
+/// Standard-Tempo in Winkelschritten pro Sekunde (256 = eine Welle pro Sekunde)
+pub const DEFAULT_PLASMA_SPEED: u8 = 64;
+
+/// Standard-Wellendichte in Winkelschritten pro Pixel (32 = eine Welle auf 8 Pixeln)
+pub const DEFAULT_PLASMA_SCALE: u8 = 32;
+
+/// Viertelwelle: `127 * sin(i / 64 * 90°)` für `i` in `0..=64`
+const QUARTER_SINE: [u8; 65] = [
+    0, 3, 6, 9, 12, 16, 19, 22, 25, 28, 31, 34, 37, 40, 43, 46, 49, 51, 54, 57, 60, 63, 65, 68, 71,
+    73, 76, 78, 81, 83, 85, 88, 90, 92, 94, 96, 98, 100, 102, 104, 106, 107, 109, 111, 112, 113,
+    115, 116, 117, 118, 120, 121, 122, 122, 123, 124, 125, 125, 126, 126, 126, 127, 127, 127, 127,
+];
+
+/// Sinus mit Winkel in 256 Schritten pro Vollkreis, Ergebnis `1..=255` (Nulllinie 128)
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::plasma::sin8;
+/// assert_eq!(sin8(0), 128);
+/// assert_eq!(sin8(64), 255);
+/// assert_eq!(sin8(192), 1);
+/// ```
+pub fn sin8(angle: u8) -> u8 {
+    let step = usize::from(angle % 64);
+    match angle / 64 {
+        0 => 128 + QUARTER_SINE[step],
+        1 => 128 + QUARTER_SINE[64 - step],
+        2 => 128 - QUARTER_SINE[step],
+        _ => 128 - QUARTER_SINE[64 - step],
+    }
+}
+
+/// Farbe an Position `position` (0..=255) einer zyklischen Palette
+///
+/// Die Farben liegen gleichmäßig verteilt, nach der letzten folgt wieder
+/// die erste. Dazwischen wird linear interpoliert.
+pub fn palette_color(colors: &[RGB8], position: u8) -> RGB8 {
+    let len = colors.len();
+    if len < 2 {
+        return colors.first().copied().unwrap_or_default();
+    }
+    let scaled = usize::from(position) * len * PERMILLE_MAX as usize / 256;
+    let segment = scaled / PERMILLE_MAX as usize;
+    let permille = (scaled % PERMILLE_MAX as usize) as u16;
+    lerp_color(colors[segment], colors[(segment + 1) % len], permille)
+}
+
+/// Plasma ab `start_ms`, läuft bis zum nächsten Kommando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plasma {
+    pub start_ms: u64,
+    /// Winkelschritte pro Sekunde
+    pub speed: u8,
+    /// Palette (Farbfolge der Auto-Rotation)
+    pub palette: ColorSequence,
+    /// Wellendichte in Winkelschritten pro Pixel (Parameter `intensity`)
+    pub scale: u8,
+    /// Phase bei `start_ms` (nach Tempo-Wechsel)
+    pub phase_offset: u8,
+}
+
+impl Plasma {
+    /// Live-Parameter (siehe [`LedEffect`])
+    pub const PARAMS: &'static [EffectParam] = &[
+        EffectParam {
+            max: Some(u8::MAX as u32),
+            default: Some(DEFAULT_PLASMA_SPEED as u32),
+            ..EffectParam::integer("speed", false, 0)
+        },
+        EffectParam {
+            max: Some(u8::MAX as u32),
+            default: Some(DEFAULT_PLASMA_SCALE as u32),
+            ..EffectParam::integer("intensity", false, 1)
+        },
+        EffectParam::text("palette", false),
+    ];
+
+    pub const fn new(start_ms: u64, speed: u8, palette: ColorSequence) -> Self {
+        Self {
+            start_ms,
+            speed,
+            palette,
+            scale: DEFAULT_PLASMA_SCALE,
+            phase_offset: 0,
+        }
+    }
+
+    /// Phase der Wellen zum Zeitpunkt `now_ms`
+    fn phase(&self, now_ms: u64) -> u8 {
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        // Phase läuft modulo Vollkreis, Abschneiden auf u8 ist gewollt
+        let travelled = (elapsed * u64::from(self.speed) / 1000) as u8;
+        self.phase_offset.wrapping_add(travelled)
+    }
+
+    /// Plasma-Wert (0..=255) für Spalte `x`, Zeile `y` zum Zeitpunkt `now_ms`
+    pub fn value_at(&self, x: usize, y: usize, now_ms: u64) -> u8 {
+        let phase = self.phase(now_ms);
+        // Winkel modulo Vollkreis, die Diagonale mit halber Wellenlänge
+        let angle = |pixels: usize, scale: u8| (pixels * usize::from(scale)) as u8;
+        let horizontal = sin8(angle(x, self.scale).wrapping_add(phase));
+        let vertical = sin8(angle(y, self.scale).wrapping_add(phase.wrapping_mul(2)));
+        let diagonal = sin8(angle(x + y, self.scale / 2).wrapping_sub(phase));
+        ((u16::from(horizontal) + u16::from(vertical) + u16::from(diagonal)) / 3) as u8
+    }
+
+    /// Zeichnet das Plasma zum Zeitpunkt `now_ms` in `pixels`
+    ///
+    /// Pixel außerhalb von `layout` bleiben unverändert.
+    pub fn render(&self, now_ms: u64, layout: MatrixLayout, pixels: &mut [RGB8]) {
+        let colors = self.palette.colors();
+        for y in 0..layout.height {
+            for x in 0..layout.width {
+                let Some(pixel) = layout.index(x, y).and_then(|index| pixels.get_mut(index)) else {
+                    continue;
+                };
+                *pixel = palette_color(colors, self.value_at(x, y, now_ms));
+            }
+        }
+    }
+}
+
+impl LedEffect for Plasma {
+    fn id(&self) -> EffectId {
+        EffectId::Plasma
+    }
+
+    fn params(&self) -> &'static [EffectParam] {
+        Self::PARAMS
+    }
+
+    fn set_param(&mut self, value: ParamValue, now_ms: u64) {
+        match value {
+            ParamValue::Speed(speed) => {
+                // Wellen laufen ohne Sprung mit neuem Tempo weiter
+                self.phase_offset = self.phase(now_ms);
+                self.start_ms = self.start_ms.max(now_ms);
+                self.speed = speed;
+            }
+            ParamValue::Intensity(scale) => self.scale = scale,
+            ParamValue::Palette(palette) => self.palette = palette,
+            ParamValue::Tail(_) | ParamValue::Color(_) => {}
+        }
+    }
+
+    fn render(&self, now_ms: u64, layout: MatrixLayout, pixels: &mut [RGB8]) {
+        Plasma::render(self, now_ms, layout, pixels);
+    }
+}
@@ -0,0 +1,312 @@
+//! Presets (benannte Kombinationen aus Farbe, Effekt, Helligkeit, Tempo)
+//!
+//! Ein Preset wird per ID ausgewählt ([`LedCommand::ApplyPreset`]) und vom
+//! LED-Task über [`PresetSource`](crate::traits::PresetSource) aufgelöst.
+//! Feste Kapazität (kein Heap), IDs wie beim [`Schedule`](crate::Schedule),
+//! binär serialisierbar für Flash-Persistenz.
+
+use rgb::RGB8;
+
+use crate::traits::PresetSource;
+use crate::types::{LedCommand, classify_color};
+
+/// Maximale Länge eines Preset-Namens in Bytes
+pub const PRESET_NAME_LEN: usize = 16;
+
+/// Serialisierte Größe eines Presets in Bytes
+pub const PRESET_LEN: usize = 8 + PRESET_NAME_LEN;
+
+/// Name eines Presets (1 bis [`PRESET_NAME_LEN`] Bytes UTF-8)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct PresetName {
+    len: u8,
+    bytes: [u8; PRESET_NAME_LEN],
+}
+
+impl PresetName {
+    /// `None` bei leerem oder zu langem Namen
+    pub const fn new(name: &str) -> Option<Self> {
+        let src = name.as_bytes();
+        if src.is_empty() || src.len() > PRESET_NAME_LEN {
+            return None;
+        }
+        let mut bytes = [0; PRESET_NAME_LEN];
+        let mut i = 0;
+        while i < src.len() {
+            bytes[i] = src[i];
+            i += 1;
+        }
+        Some(Self {
+            len: src.len() as u8,
+            bytes,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Nur aus `&str` befüllt, daher immer gültiges UTF-8
+        core::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or("")
+    }
+}
+
+impl core::fmt::Debug for PresetName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Effekt eines Presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetEffect {
+    /// Feste Farbe (manueller Modus)
+    Solid,
+    /// Auto-Rotation
+    Auto,
+    /// Sonnenaufgang, Dauer = `speed` in Minuten
+    Sunrise,
+}
+
+/// Ein Preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Preset {
+    /// Eindeutige ID (1-255), wird von `Presets::add` vergeben
+    pub id: u8,
+    pub name: PresetName,
+    /// Farbe für [`PresetEffect::Solid`], sonst ohne Wirkung
+    pub color: RGB8,
+    pub effect: PresetEffect,
+    /// Gesamthelligkeit (wie `LedCommand::SetBrightness`)
+    pub brightness: u8,
+    /// Tempo des Effekts (Sonnenaufgang: Minuten), sonst ohne Wirkung
+    pub speed: u8,
+}
+
+impl Preset {
+    /// Preset ohne ID (für `Presets::add` und Standard-Presets)
+    pub const fn new(
+        name: PresetName,
+        color: RGB8,
+        effect: PresetEffect,
+        brightness: u8,
+        speed: u8,
+    ) -> Self {
+        Self {
+            id: 0,
+            name,
+            color,
+            effect,
+            brightness,
+            speed,
+        }
+    }
+
+    /// Prüft Wertebereiche (Sonnenaufgang braucht eine Dauer > 0)
+    pub fn is_valid(&self) -> bool {
+        !(self.effect == PresetEffect::Sunrise && self.speed == 0)
+    }
+
+    /// Serialisiert das Preset für die Flash-Persistenz
+    ///
+    /// Layout: ID, Effekt, Helligkeit, Tempo, R, G, B, Namenslänge, Name (aufgefüllt)
+    pub fn to_bytes(&self) -> [u8; PRESET_LEN] {
+        let effect = match self.effect {
+            PresetEffect::Solid => 0,
+            PresetEffect::Auto => 1,
+            PresetEffect::Sunrise => 2,
+        };
+        let mut bytes = [0; PRESET_LEN];
+        bytes[..8].copy_from_slice(&[
+            self.id,
+            effect,
+            self.brightness,
+            self.speed,
+            self.color.r,
+            self.color.g,
+            self.color.b,
+            self.name.len,
+        ]);
+        bytes[8..].copy_from_slice(&self.name.bytes);
+        bytes
+    }
+
+    /// Deserialisiert ein Preset, `None` bei ungültigen Daten
+    pub fn from_bytes(bytes: &[u8; PRESET_LEN]) -> Option<Self> {
+        let effect = match bytes[1] {
+            0 => PresetEffect::Solid,
+            1 => PresetEffect::Auto,
+            2 => PresetEffect::Sunrise,
+            _ => return None,
+        };
+        let name_bytes = bytes[8..].get(..usize::from(bytes[7]))?;
+        let name = PresetName::new(core::str::from_utf8(name_bytes).ok()?)?;
+        let preset = Preset {
+            id: bytes[0],
+            name,
+            color: RGB8::new(bytes[4], bytes[5], bytes[6]),
+            effect,
+            brightness: bytes[2],
+            speed: bytes[3],
+        };
+        (preset.id != 0 && preset.is_valid()).then_some(preset)
+    }
+
+    /// Kommando für Farbe bzw. Effekt (Helligkeit setzt `LedState::apply_preset`)
+    pub fn command(&self) -> LedCommand {
+        match self.effect {
+            PresetEffect::Solid => LedCommand::SetColor {
+                target_color: self.color,
+                color_id: classify_color(self.color),
+            },
+            PresetEffect::Auto => LedCommand::EnableAuto,
+            PresetEffect::Sunrise => LedCommand::Sunrise {
+                duration_secs: u32::from(self.speed) * 60,
+            },
+        }
+    }
+}
+
+/// Fehler bei Preset-Änderungen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetError {
+    /// Maximale Anzahl Presets erreicht
+    Full,
+    /// Ungültige Werte (z.B. Sonnenaufgang ohne Dauer)
+    InvalidPreset,
+    /// Kein Preset mit dieser ID
+    NotFound,
+}
+
+/// Presets mit fester Kapazität `N`
+#[derive(Debug, Clone)]
+pub struct Presets<const N: usize> {
+    presets: [Option<Preset>; N],
+}
+
+impl<const N: usize> Default for Presets<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Presets<N> {
+    /// Keine Presets
+    pub const fn new() -> Self {
+        Self { presets: [None; N] }
+    }
+
+    /// Alle Presets (sortiert nach Slot)
+    pub fn iter(&self) -> impl Iterator<Item = &Preset> {
+        self.presets.iter().flatten()
+    }
+
+    /// Anzahl Presets
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fügt ein Preset hinzu und vergibt eine freie ID
+    ///
+    /// Die ID im übergebenen Preset wird ignoriert.
+    pub fn add(&mut self, mut preset: Preset) -> Result<u8, PresetError> {
+        if !preset.is_valid() {
+            return Err(PresetError::InvalidPreset);
+        }
+        let slot = self
+            .presets
+            .iter()
+            .position(Option::is_none)
+            .ok_or(PresetError::Full)?;
+        let id = (1..=u8::MAX)
+            .find(|id| self.get(*id).is_none())
+            .ok_or(PresetError::Full)?;
+        preset.id = id;
+        self.presets[slot] = Some(preset);
+        Ok(id)
+    }
+
+    /// Sucht ein Preset per ID
+    pub fn get(&self, id: u8) -> Option<&Preset> {
+        self.iter().find(|preset| preset.id == id)
+    }
+
+    /// Entfernt ein Preset per ID
+    pub fn remove(&mut self, id: u8) -> Result<Preset, PresetError> {
+        self.presets
+            .iter_mut()
+            .find(|slot| matches!(slot, Some(preset) if preset.id == id))
+            .and_then(Option::take)
+            .ok_or(PresetError::NotFound)
+    }
+
+    /// Ersetzt ein Preset, die ID bleibt erhalten
+    pub fn update(&mut self, id: u8, mut preset: Preset) -> Result<(), PresetError> {
+        if !preset.is_valid() {
+            return Err(PresetError::InvalidPreset);
+        }
+        let slot = self
+            .presets
+            .iter_mut()
+            .flatten()
+            .find(|existing| existing.id == id)
+            .ok_or(PresetError::NotFound)?;
+        preset.id = id;
+        *slot = preset;
+        Ok(())
+    }
+
+    /// ID des Presets nach `current` (zyklisch, z.B. für den Taster)
+    ///
+    /// Ohne `current` oder bei unbekannter ID das erste Preset.
+    pub fn next_id(&self, current: Option<u8>) -> Option<u8> {
+        let position = current.and_then(|id| self.iter().position(|preset| preset.id == id));
+        let next = match position {
+            Some(position) => self.iter().nth(position + 1).or_else(|| self.iter().next()),
+            None => self.iter().next(),
+        };
+        next.map(|preset| preset.id)
+    }
+
+    /// Serialisiert die Presets (1 Byte Anzahl + Presets)
+    ///
+    /// Gibt die geschriebene Länge zurück, oder `None` wenn `out` zu klein ist.
+    pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+        let total = 1 + self.len() * PRESET_LEN;
+        if out.len() < total {
+            return None;
+        }
+        out[0] = self.len() as u8;
+        let (chunks, _) = out[1..total].as_chunks_mut::<PRESET_LEN>();
+        for (chunk, preset) in chunks.iter_mut().zip(self.iter()) {
+            chunk.copy_from_slice(&preset.to_bytes());
+        }
+        Some(total)
+    }
+
+    /// Deserialisiert Presets
+    ///
+    /// Ungültige Presets werden übersprungen, überzählige verworfen.
+    pub fn decode(data: &[u8]) -> Self {
+        let mut presets = Self::new();
+        let Some((&count, rest)) = data.split_first() else {
+            return presets;
+        };
+        let (chunks, _) = rest.as_chunks::<PRESET_LEN>();
+        let decoded = chunks
+            .iter()
+            .take(count as usize)
+            .filter_map(Preset::from_bytes);
+        for (slot, preset) in presets.presets.iter_mut().zip(decoded) {
+            *slot = Some(preset);
+        }
+        presets
+    }
+}
+
+impl<const N: usize> PresetSource for Presets<N> {
+    fn preset(&self, id: u8) -> Option<Preset> {
+        self.get(id).copied()
+    }
+}
@@ -0,0 +1,88 @@
+//! Drosselung der MQTT-Farbmeldungen im Auto-Modus
+//!
+//! Im Auto-Modus wechselt die Farbe jede Sekunde, jeder Wechsel wären
+//! mehrere Publishes (Farbe, Modus, Zustand, ggf. Tasmota). [`PublishThrottle`]
+//! lässt im Auto-Modus höchstens eine Meldung je Intervall durch und hält die
+//! jeweils neueste zurück. Sie geht zum Ende des Intervalls raus, damit der
+//! Broker den tatsächlichen Zustand kennt, auch wenn danach nichts mehr kommt.
+//! Manuelle Änderungen (inkl. Wechsel aus dem Auto-Modus) gehen sofort raus
+//! und ersetzen eine zurückgehaltene Meldung.
+
+/// Rate-Limit für Farbmeldungen, Zeiten in Millisekunden seit Boot
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::PublishThrottle;
+/// let mut throttle = PublishThrottle::new(5000);
+/// assert!(throttle.offer(true, 0));
+/// assert!(!throttle.offer(true, 1000));
+/// assert_eq!(throttle.deadline_ms(), Some(5000));
+/// assert!(throttle.take_due(5000));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PublishThrottle {
+    interval_ms: u64,
+    last_publish_ms: Option<u64>,
+    pending: bool,
+}
+
+impl PublishThrottle {
+    /// Neues Rate-Limit, `interval_ms = 0` lässt jede Meldung sofort durch
+    pub const fn new(interval_ms: u64) -> Self {
+        Self {
+            interval_ms,
+            last_publish_ms: None,
+            pending: false,
+        }
+    }
+
+    /// Neue Farbmeldung: `true` heißt sofort publishen
+    ///
+    /// Bei `false` hält der Aufrufer die Meldung zurück (eine ältere
+    /// zurückgehaltene wird verworfen) und publisht sie, sobald
+    /// [`PublishThrottle::take_due`] `true` liefert.
+    pub fn offer(&mut self, is_auto_mode: bool, now_ms: u64) -> bool {
+        let due = match self.last_publish_ms {
+            None => true,
+            Some(last) => now_ms.saturating_sub(last) >= self.interval_ms,
+        };
+        if !is_auto_mode || self.interval_ms == 0 || due {
+            self.pending = false;
+            self.last_publish_ms = Some(now_ms);
+            true
+        } else {
+            self.pending = true;
+            false
+        }
+    }
+
+    /// Vermerkt einen Publish an `offer` vorbei (z.B. nachgeholte Offline-Meldungen)
+    ///
+    /// Das Intervall beginnt neu, eine zurückgehaltene Meldung ist überholt.
+    pub fn published(&mut self, now_ms: u64) {
+        self.pending = false;
+        self.last_publish_ms = Some(now_ms);
+    }
+
+    /// Zeitpunkt, zu dem die zurückgehaltene Meldung fällig wird
+    ///
+    /// `None`, wenn keine Meldung zurückgehalten wird.
+    pub fn deadline_ms(&self) -> Option<u64> {
+        match self.last_publish_ms {
+            Some(last) if self.pending => Some(last + self.interval_ms),
+            _ => None,
+        }
+    }
+
+    /// `true`, wenn die zurückgehaltene Meldung jetzt zu publishen ist
+    pub fn take_due(&mut self, now_ms: u64) -> bool {
+        match self.deadline_ms() {
+            Some(deadline) if now_ms >= deadline => {
+                self.pending = false;
+                self.last_publish_ms = Some(now_ms);
+                true
+            }
+            _ => false,
+        }
+    }
+}
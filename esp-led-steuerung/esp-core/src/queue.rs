@@ -0,0 +1,264 @@
+//! Prioritäts-Warteschlange für LED-Kommandos
+//!
+//! Ersetzt den FIFO-Channel der Tiefe 1: Sicherheits-Kommandos (`Off`) werden
+//! vor wartenden Farbwechseln ausgeführt, und Kommando-Ströme (z.B. ein
+//! Helligkeits-Fader per OSC) blockieren keine anderen Quellen.
+//!
+//! Regeln:
+//! - Ausgabe nach Priorität (hoch zuerst), innerhalb einer Priorität FIFO
+//! - Gleichartige Zustands-Kommandos (`SetColor`/`FadeTo`, `SetBrightness`, `ScrollText`)
+//!   ersetzen ältere wartende Einträge (nur der neueste Wert zählt)
+//! - Queue voll: der älteste Eintrag der niedrigsten Priorität wird verdrängt,
+//!   sofern seine Priorität nicht höher als die des neuen Kommandos ist;
+//!   andernfalls wird das neue Kommando verworfen
+//!
+//! Sender warten nie: ein verworfenes Kommando meldet der Aufrufer als
+//! [`ErrorKind::Busy`](crate::error::ErrorKind::Busy) an seinen Client,
+//! [`QueueStats`] zählt alle Ergebnisse für die Telemetrie.
+
+use core::cmp::Reverse;
+
+use crate::types::{ColorId, LedCommand};
+
+/// Priorität eines Kommandos
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommandPriority {
+    /// Kontinuierliche Ströme (Helligkeits-Fader)
+    Low,
+    /// Farb- und Modus-Wechsel, Effekte, Timer, Presets
+    Normal,
+    /// Sicherheits-Kommandos (Ausschalten, auch mit Übergang)
+    High,
+}
+
+impl LedCommand {
+    /// Priorität für die Kommando-Warteschlange
+    pub fn priority(&self) -> CommandPriority {
+        match self {
+            LedCommand::Off
+            | LedCommand::FadeTo {
+                color_id: ColorId::Off,
+                ..
+            } => CommandPriority::High,
+            LedCommand::SetBrightness { .. }
+            | LedCommand::SetSequence { .. }
+            | LedCommand::SetEffectParam { .. } => CommandPriority::Low,
+            LedCommand::SetColor { .. }
+            | LedCommand::FadeTo { .. }
+            | LedCommand::EnableAuto
+            | LedCommand::EnableRandom
+            | LedCommand::Sunrise { .. }
+            | LedCommand::Calibrate
+            | LedCommand::TestPattern
+            | LedCommand::Diagnose
+            | LedCommand::ScrollText { .. }
+            | LedCommand::ShowClock { .. }
+            | LedCommand::ShowGradient { .. }
+            | LedCommand::Meteor { .. }
+            | LedCommand::Plasma { .. }
+            | LedCommand::SleepTimer { .. }
+            | LedCommand::ApplyPreset { .. } => CommandPriority::Normal,
+        }
+    }
+
+    /// Prüft ob `self` ein wartendes `other` ersetzt (nur der neueste Wert zählt)
+    fn supersedes(&self, other: &LedCommand) -> bool {
+        if let (
+            LedCommand::SetEffectParam { value },
+            LedCommand::SetEffectParam { value: waiting },
+        ) = (self, other)
+        {
+            // Nur derselbe Parameter, z.B. beim Ziehen eines Reglers
+            return value.name() == waiting.name();
+        }
+        matches!(
+            (self, other),
+            (
+                LedCommand::SetColor { .. } | LedCommand::FadeTo { .. },
+                LedCommand::SetColor { .. } | LedCommand::FadeTo { .. }
+            ) | (
+                LedCommand::SetBrightness { .. },
+                LedCommand::SetBrightness { .. }
+            ) | (LedCommand::ScrollText { .. }, LedCommand::ScrollText { .. })
+                | (
+                    LedCommand::ShowGradient { .. },
+                    LedCommand::ShowGradient { .. }
+                )
+                | (LedCommand::Meteor { .. }, LedCommand::Meteor { .. })
+                | (LedCommand::Plasma { .. }, LedCommand::Plasma { .. })
+                | (
+                    LedCommand::SetSequence { .. },
+                    LedCommand::SetSequence { .. }
+                )
+        )
+    }
+}
+
+/// Ergebnis von [`CommandQueue::push`]
+#[derive(Clone, Copy)]
+pub enum PushOutcome {
+    /// Kommando eingereiht
+    Queued,
+    /// Kommando hat einen älteren gleichartigen Eintrag ersetzt
+    Replaced,
+    /// Queue war voll, der zurückgegebene Eintrag wurde verdrängt
+    Evicted(LedCommand),
+    /// Queue war voll mit höher priorisierten Einträgen, Kommando verworfen
+    Rejected,
+}
+
+impl PushOutcome {
+    /// Prüft ob das neue Kommando in der Warteschlange steht
+    pub fn is_accepted(&self) -> bool {
+        !matches!(self, PushOutcome::Rejected)
+    }
+}
+
+/// Zähler der Push-Ergebnisse seit dem Start (sättigend)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QueueStats {
+    /// Neu eingereiht (ohne Verdrängung)
+    pub queued: u32,
+    /// Älteren gleichartigen Eintrag ersetzt
+    pub replaced: u32,
+    /// Wartenden Eintrag verdrängt
+    pub evicted: u32,
+    /// Neues Kommando verworfen
+    pub rejected: u32,
+}
+
+impl QueueStats {
+    pub const fn new() -> Self {
+        Self {
+            queued: 0,
+            replaced: 0,
+            evicted: 0,
+            rejected: 0,
+        }
+    }
+
+    /// Zählt ein Push-Ergebnis
+    pub fn record(&mut self, outcome: &PushOutcome) {
+        let counter = match outcome {
+            PushOutcome::Queued => &mut self.queued,
+            PushOutcome::Replaced => &mut self.replaced,
+            PushOutcome::Evicted(_) => &mut self.evicted,
+            PushOutcome::Rejected => &mut self.rejected,
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Kommandos, die nie ausgeführt werden (verdrängt oder verworfen)
+    pub fn dropped(&self) -> u32 {
+        self.evicted.saturating_add(self.rejected)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Entry {
+    command: LedCommand,
+    sequence: u32,
+}
+
+/// Prioritäts-Warteschlange mit fester Kapazität `N` (ohne Allokation)
+pub struct CommandQueue<const N: usize> {
+    entries: [Option<Entry>; N],
+    next_sequence: u32,
+    stats: QueueStats,
+}
+
+impl<const N: usize> Default for CommandQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> CommandQueue<N> {
+    /// Leere Warteschlange
+    pub const fn new() -> Self {
+        Self {
+            entries: [None; N],
+            next_sequence: 0,
+            stats: QueueStats::new(),
+        }
+    }
+
+    /// Anzahl wartender Kommandos
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    /// Prüft ob keine Kommandos warten
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(Option::is_none)
+    }
+
+    /// Zähler aller bisherigen `push`-Ergebnisse
+    pub fn stats(&self) -> QueueStats {
+        self.stats
+    }
+
+    /// Reiht ein Kommando ein (Regeln siehe Modul-Dokumentation)
+    pub fn push(&mut self, command: LedCommand) -> PushOutcome {
+        let outcome = self.push_entry(command);
+        self.stats.record(&outcome);
+        outcome
+    }
+
+    fn push_entry(&mut self, command: LedCommand) -> PushOutcome {
+        let mut outcome = PushOutcome::Queued;
+
+        // Ältere gleichartige Einträge entfernen, das neue Kommando kommt ans Ende
+        for slot in &mut self.entries {
+            if slot.is_some_and(|entry| command.supersedes(&entry.command)) {
+                *slot = None;
+                outcome = PushOutcome::Replaced;
+            }
+        }
+
+        let index = match self.entries.iter().position(Option::is_none) {
+            Some(index) => index,
+            None => {
+                // Voll: ältesten Eintrag der niedrigsten Priorität suchen
+                let Some((index, victim)) = self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, slot)| slot.map(|entry| (i, entry)))
+                    .min_by_key(|(_, entry)| (entry.command.priority(), Reverse(self.age(entry))))
+                else {
+                    // Nur bei Kapazität 0
+                    return PushOutcome::Rejected;
+                };
+                if victim.command.priority() > command.priority() {
+                    return PushOutcome::Rejected;
+                }
+                outcome = PushOutcome::Evicted(victim.command);
+                index
+            }
+        };
+
+        self.entries[index] = Some(Entry {
+            command,
+            sequence: self.next_sequence,
+        });
+        self.next_sequence = self.next_sequence.wrapping_add(1);
+        outcome
+    }
+
+    /// Entnimmt das Kommando mit der höchsten Priorität (bei Gleichstand das älteste)
+    pub fn pop(&mut self) -> Option<LedCommand> {
+        let (index, _) = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| slot.map(|entry| (i, entry)))
+            .max_by_key(|(_, entry)| (entry.command.priority(), self.age(entry)))?;
+        self.entries[index].take().map(|entry| entry.command)
+    }
+
+    /// Alter eines Eintrags relativ zur nächsten Sequenznummer (übersteht Überlauf)
+    fn age(&self, entry: &Entry) -> u32 {
+        self.next_sequence.wrapping_sub(entry.sequence)
+    }
+}
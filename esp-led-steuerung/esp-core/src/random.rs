@@ -0,0 +1,77 @@
+//! Zufallsfarben und reproduzierbarer Zufallsgenerator
+//!
+//! Der Zufallsmodus (`LedCommand::EnableRandom`) wählt pro Durchlauf der
+//! LED-Schleife eine neue, voll gesättigte Farbe. Damit der Wechsel sichtbar
+//! ist, liegt ihr Farbton mindestens [`RANDOM_MIN_HUE_DISTANCE`] vom
+//! vorherigen entfernt. Die Zufallsquelle ist der Trait [`Rng`]: in der
+//! Firmware der Hardware-RNG, in Tests und im Simulator [`SeededRng`].
+
+use rgb::RGB8;
+
+use crate::color::{HUE_CIRCLE, Hsv};
+use crate::traits::Rng;
+
+/// Mindestabstand des Farbtons zur vorherigen Farbe (60°)
+pub const RANDOM_MIN_HUE_DISTANCE: u16 = HUE_CIRCLE / 6;
+
+/// Startwert für Seed 0 (Xorshift bleibt bei 0 stehen)
+const ZERO_SEED_REPLACEMENT: u32 = 0x9E37_79B9;
+
+/// Xorshift32-Generator: reproduzierbar bei gleichem Seed, nicht kryptografisch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u32,
+}
+
+impl SeededRng {
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            state: if seed == 0 {
+                ZERO_SEED_REPLACEMENT
+            } else {
+                seed
+            },
+        }
+    }
+}
+
+impl Default for SeededRng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Rng for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+}
+
+/// Zufällige, voll gesättigte Farbe mit Hellwert `value`
+///
+/// Der Farbton hält mindestens [`RANDOM_MIN_HUE_DISTANCE`] Abstand zu
+/// `previous` (in beide Richtungen). Ist `previous` grau oder schwarz, ist
+/// jeder Farbton erlaubt.
+pub fn random_color(rng: &mut impl Rng, previous: RGB8, value: u8) -> RGB8 {
+    let circle = HUE_CIRCLE as u32;
+    let previous = Hsv::from_rgb(previous);
+    let hue = if previous.saturation == 0 || previous.value == 0 {
+        rng.next_u32() % circle
+    } else {
+        // Erlaubter Bereich: hinter dem Mindestabstand bis kurz vor dem Abstand auf der Gegenseite
+        let distance = RANDOM_MIN_HUE_DISTANCE as u32;
+        let span = circle - 2 * distance + 1;
+        (previous.hue as u32 + distance + rng.next_u32() % span) % circle
+    };
+    Hsv {
+        hue: hue as u16,
+        saturation: u8::MAX,
+        value,
+    }
+    .to_rgb()
+}
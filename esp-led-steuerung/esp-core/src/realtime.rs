@@ -0,0 +1,74 @@
+//! UDP-Realtime-Protokoll (Ambilight / Screen-Sync)
+//!
+//! Zustandsloses Protokoll für latenzarmes Streaming vom PC, kompatibel
+//! zum DRGB-Format von WLED (Standard-Port 21324):
+//!
+//! - `[r, g, b]` (genau 3 Bytes): alle LEDs in einer Farbe
+//! - `[2, timeout, r, g, b, r, g, b, ...]`: DRGB, eine Farbe pro LED
+//!
+//! `timeout` ist die Zeit in Sekunden, nach der ohne neue Pakete wieder der
+//! vorherige Modus aktiv wird (0 = Standard-Timeout des Geräts).
+
+use rgb::RGB8;
+
+/// Standard-UDP-Port (wie WLED)
+pub const REALTIME_PORT: u16 = 21324;
+
+/// Protokoll-Kennung für DRGB-Pakete
+pub const PROTOCOL_DRGB: u8 = 2;
+
+/// Dekodiertes Realtime-Paket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RealtimePacket<'a> {
+    /// Eine Farbe für alle LEDs
+    Fill(RGB8),
+    /// Eine Farbe pro LED (ab LED 0)
+    Pixels {
+        timeout_secs: Option<u8>,
+        data: &'a [[u8; 3]],
+    },
+}
+
+impl RealtimePacket<'_> {
+    /// Timeout aus dem Paket (`None` = Standard-Timeout verwenden)
+    pub fn timeout_secs(&self) -> Option<u8> {
+        match self {
+            RealtimePacket::Fill(_) => None,
+            RealtimePacket::Pixels { timeout_secs, .. } => *timeout_secs,
+        }
+    }
+
+    /// Schreibt das Paket in einen Frame-Buffer
+    ///
+    /// Überzählige Pixel im Paket werden ignoriert, nicht enthaltene
+    /// Pixel im Buffer bleiben unverändert.
+    pub fn render(&self, frame: &mut [RGB8]) {
+        match self {
+            RealtimePacket::Fill(color) => frame.fill(*color),
+            RealtimePacket::Pixels { data, .. } => {
+                for (pixel, &[r, g, b]) in frame.iter_mut().zip(data.iter()) {
+                    *pixel = RGB8::new(r, g, b);
+                }
+            }
+        }
+    }
+}
+
+/// Dekodiert ein Realtime-Paket, `None` bei ungültigem Format
+pub fn decode_realtime_packet(packet: &[u8]) -> Option<RealtimePacket<'_>> {
+    if let &[r, g, b] = packet {
+        return Some(RealtimePacket::Fill(RGB8::new(r, g, b)));
+    }
+    let (&[protocol, timeout], payload) = packet.split_first_chunk::<2>()?;
+    if protocol != PROTOCOL_DRGB || payload.is_empty() {
+        return None;
+    }
+    let (data, rest) = payload.as_chunks::<3>();
+    if !rest.is_empty() {
+        return None;
+    }
+    Some(RealtimePacket::Pixels {
+        timeout_secs: (timeout > 0).then_some(timeout),
+        data,
+    })
+}
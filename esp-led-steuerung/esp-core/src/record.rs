@@ -0,0 +1,133 @@
+//! Persistenz-Record-Format
+//!
+//! Einheitliches Container-Format für Daten im Flash (Schedule, Settings, …).
+//! Die Firmware schreibt Records in eigene Flash-Sektoren; hier liegt nur
+//! die (testbare) Kodierung mit Header und CRC32-Prüfsumme.
+//!
+//! # Layout
+//!
+//! | Offset | Größe | Inhalt                        |
+//! |--------|-------|-------------------------------|
+//! | 0      | 4     | Magic `LED1`                  |
+//! | 4      | 1     | Record-Typ                    |
+//! | 5      | 1     | Reserviert (0)                |
+//! | 6      | 2     | Payload-Länge (Little Endian) |
+//! | 8      | 4     | CRC32 des Payloads (LE)       |
+//! | 12     | n     | Payload                       |
+
+/// Magic-Bytes am Anfang jedes Records
+pub const RECORD_MAGIC: [u8; 4] = *b"LED1";
+
+/// Header-Größe in Bytes
+pub const RECORD_HEADER_LEN: usize = 12;
+
+/// Record-Typen (ein Typ pro Flash-Slot)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RecordKind {
+    Schedule = 1,
+    /// Letzter Absturz (siehe [`crate::crash`])
+    Crash = 2,
+    /// Benutzerdefinierte Presets (siehe [`crate::preset`])
+    Presets = 3,
+    /// Weißabgleich (siehe [`crate::calibration`])
+    Calibration = 4,
+    /// Gerätekonfiguration (siehe [`crate::device_config`])
+    Config = 5,
+}
+
+impl RecordKind {
+    /// Alle Record-Arten (z.B. zum Löschen beim Werksreset)
+    pub const ALL: [RecordKind; 5] = [
+        RecordKind::Schedule,
+        RecordKind::Crash,
+        RecordKind::Presets,
+        RecordKind::Calibration,
+        RecordKind::Config,
+    ];
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(RecordKind::Schedule),
+            2 => Some(RecordKind::Crash),
+            3 => Some(RecordKind::Presets),
+            4 => Some(RecordKind::Calibration),
+            5 => Some(RecordKind::Config),
+            _ => None,
+        }
+    }
+}
+
+/// CRC32 (IEEE 802.3, wie zlib/PNG)
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.finish()
+}
+
+/// Fortlaufende CRC32 über mehrere Stücke (z.B. einen Upload)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    pub const fn new() -> Self {
+        Self(0xFFFF_FFFF)
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 ^= byte as u32;
+            for _ in 0..8 {
+                let mask = (self.0 & 1).wrapping_neg();
+                self.0 = (self.0 >> 1) ^ (0xEDB8_8320 & mask);
+            }
+        }
+    }
+
+    /// CRC der bisherigen Daten (der Zustand bleibt erhalten)
+    pub fn finish(&self) -> u32 {
+        !self.0
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Schreibt Header + Payload in `out`
+///
+/// Gibt die Gesamtlänge zurück, oder `None` wenn `out` zu klein ist.
+pub fn encode_record(kind: RecordKind, payload: &[u8], out: &mut [u8]) -> Option<usize> {
+    let total = RECORD_HEADER_LEN + payload.len();
+    if out.len() < total || payload.len() > u16::MAX as usize {
+        return None;
+    }
+    out[0..4].copy_from_slice(&RECORD_MAGIC);
+    out[4] = kind as u8;
+    out[5] = 0;
+    out[6..8].copy_from_slice(&(payload.len() as u16).to_le_bytes());
+    out[8..12].copy_from_slice(&crc32(payload).to_le_bytes());
+    out[RECORD_HEADER_LEN..total].copy_from_slice(payload);
+    Some(total)
+}
+
+/// Liest einen Record und prüft Magic, Typ, Länge und CRC
+///
+/// Gibt `None` für gelöschten Flash (0xFF), beschädigte oder fremde Daten zurück.
+pub fn decode_record(data: &[u8], expected: RecordKind) -> Option<&[u8]> {
+    if data.len() < RECORD_HEADER_LEN || data[0..4] != RECORD_MAGIC {
+        return None;
+    }
+    if RecordKind::from_u8(data[4])? != expected {
+        return None;
+    }
+    let len = u16::from_le_bytes([data[6], data[7]]) as usize;
+    let crc = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+    let payload = data.get(RECORD_HEADER_LEN..RECORD_HEADER_LEN + len)?;
+    if crc32(payload) != crc {
+        return None;
+    }
+    Some(payload)
+}
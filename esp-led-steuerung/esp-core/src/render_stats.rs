@@ -0,0 +1,170 @@
+//! Zeitmessung des LED-Render-Pfads
+//!
+//! Pro Frame werden zwei Abschnitte gemessen: Berechnung der Ausgabe
+//! (Effekt, Verlauf, Helligkeit) und Schreiben auf die LEDs (RMT). Lange
+//! Strips und aufwendige Effekte dürfen das Frame-Budget nicht überschreiten,
+//! sonst ruckeln Verläufe und Realtime-Streams.
+//!
+//! [`RenderStats`] hält die letzten `N` Messungen und berechnet daraus
+//! Perzentile (p50/p95). Ältere Frames fallen aus dem Fenster, die Zähler
+//! laufen seit dem Start.
+
+use core::fmt;
+
+/// Messung eines Frames (Mikrosekunden)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FrameTiming {
+    /// Berechnung der Ausgabe-Farbe
+    pub render_us: u32,
+    /// Schreiben auf die LEDs
+    pub write_us: u32,
+}
+
+impl FrameTiming {
+    /// Gesamtdauer des Frames
+    pub fn total_us(&self) -> u32 {
+        self.render_us.saturating_add(self.write_us)
+    }
+}
+
+/// Zusammenfassung für `/metrics`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RenderSummary {
+    /// Gemessene Frames seit dem Start
+    pub frames: u32,
+    /// Frames über dem Budget seit dem Start
+    pub over_budget: u32,
+    pub budget_us: u32,
+    pub render_p50_us: u32,
+    pub render_p95_us: u32,
+    pub write_p50_us: u32,
+    pub write_p95_us: u32,
+}
+
+impl RenderSummary {
+    /// Schreibt die Werte im Prometheus-Textformat
+    pub fn write_prometheus(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            out,
+            "# HELP led_frames_total Gemessene LED-Frames\n\
+             # TYPE led_frames_total counter\n\
+             led_frames_total {}\n\
+             # HELP led_frames_over_budget_total Frames über dem Frame-Budget\n\
+             # TYPE led_frames_over_budget_total counter\n\
+             led_frames_over_budget_total {}\n\
+             # HELP led_frame_budget_us Frame-Budget in Mikrosekunden\n\
+             # TYPE led_frame_budget_us gauge\n\
+             led_frame_budget_us {}\n",
+            self.frames, self.over_budget, self.budget_us
+        )?;
+        for (name, help, p50, p95) in [
+            (
+                "led_frame_render_us",
+                "Berechnung der Ausgabe in Mikrosekunden",
+                self.render_p50_us,
+                self.render_p95_us,
+            ),
+            (
+                "led_frame_write_us",
+                "Schreiben auf die LEDs in Mikrosekunden",
+                self.write_p50_us,
+                self.write_p95_us,
+            ),
+        ] {
+            write!(
+                out,
+                "# HELP {name} {help}\n\
+                 # TYPE {name} summary\n\
+                 {name}{{quantile=\"0.5\"}} {p50}\n\
+                 {name}{{quantile=\"0.95\"}} {p95}\n"
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Messfenster der letzten `N` Frames
+#[derive(Debug, Clone)]
+pub struct RenderStats<const N: usize> {
+    render_us: [u32; N],
+    write_us: [u32; N],
+    /// Nächster Schreib-Index im Fenster
+    next: usize,
+    len: usize,
+    frames: u32,
+    over_budget: u32,
+    budget_us: u32,
+}
+
+impl<const N: usize> RenderStats<N> {
+    /// Leeres Fenster mit Frame-Budget `budget_us`
+    pub const fn new(budget_us: u32) -> Self {
+        Self {
+            render_us: [0; N],
+            write_us: [0; N],
+            next: 0,
+            len: 0,
+            frames: 0,
+            over_budget: 0,
+            budget_us,
+        }
+    }
+
+    /// Übernimmt eine Messung (verdrängt bei vollem Fenster die älteste)
+    pub fn record(&mut self, timing: FrameTiming) {
+        self.render_us[self.next] = timing.render_us;
+        self.write_us[self.next] = timing.write_us;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+        self.frames = self.frames.saturating_add(1);
+        if timing.total_us() > self.budget_us {
+            self.over_budget = self.over_budget.saturating_add(1);
+        }
+    }
+
+    /// Anzahl Messungen im Fenster
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Perzentil der Berechnungszeit (0 ohne Messung)
+    pub fn render_percentile(&self, percent: u8) -> u32 {
+        Self::percentile(&self.render_us[..self.len], percent)
+    }
+
+    /// Perzentil der Schreibzeit (0 ohne Messung)
+    pub fn write_percentile(&self, percent: u8) -> u32 {
+        Self::percentile(&self.write_us[..self.len], percent)
+    }
+
+    /// p50/p95 und Zähler
+    pub fn summary(&self) -> RenderSummary {
+        RenderSummary {
+            frames: self.frames,
+            over_budget: self.over_budget,
+            budget_us: self.budget_us,
+            render_p50_us: self.render_percentile(50),
+            render_p95_us: self.render_percentile(95),
+            write_p50_us: self.write_percentile(50),
+            write_p95_us: self.write_percentile(95),
+        }
+    }
+
+    /// Perzentil nach dem Nearest-Rank-Verfahren (kleinster Wert, den mindestens
+    /// `percent` % der Messungen nicht überschreiten)
+    fn percentile(samples: &[u32], percent: u8) -> u32 {
+        if samples.is_empty() {
+            return 0;
+        }
+        let mut sorted = [0u32; N];
+        let sorted = &mut sorted[..samples.len()];
+        sorted.copy_from_slice(samples);
+        sorted.sort_unstable();
+        let rank = (usize::from(percent.min(100)) * samples.len()).div_ceil(100);
+        sorted[rank.saturating_sub(1)]
+    }
+}
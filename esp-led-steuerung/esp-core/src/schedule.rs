@@ -0,0 +1,270 @@
+//! Zeitplan (cron-ähnliche Timer)
+//!
+//! Einträge mit Wochentagen, Uhrzeit (HH:MM) und Aktion.
+//! Feste Kapazität (kein Heap), binär serialisierbar für Flash-Persistenz.
+
+use rgb::RGB8;
+
+use crate::time::{LocalTime, Weekday};
+use crate::types::{LedCommand, classify_color};
+
+/// Serialisierte Größe eines Eintrags in Bytes
+pub const SCHEDULE_ENTRY_LEN: usize = 9;
+
+/// Bitmaske für alle Wochentage (Bit 0 = Montag … Bit 6 = Sonntag)
+pub const ALL_DAYS: u8 = 0b0111_1111;
+
+/// Aktion die ein Zeitplan-Eintrag auslöst
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleAction {
+    /// Feste Farbe setzen (manueller Modus)
+    SetColor(RGB8),
+    /// Auto-Rotation aktivieren
+    Auto,
+    /// LED ausschalten
+    Off,
+    /// Sonnenaufgang über `duration_min` Minuten (Lichtwecker)
+    Sunrise { duration_min: u8 },
+}
+
+impl From<ScheduleAction> for LedCommand {
+    fn from(action: ScheduleAction) -> Self {
+        match action {
+            ScheduleAction::SetColor(color) => LedCommand::SetColor {
+                target_color: color,
+                color_id: classify_color(color),
+            },
+            ScheduleAction::Auto => LedCommand::EnableAuto,
+            ScheduleAction::Off => LedCommand::Off,
+            ScheduleAction::Sunrise { duration_min } => LedCommand::Sunrise {
+                duration_secs: duration_min as u32 * 60,
+            },
+        }
+    }
+}
+
+/// Ein Zeitplan-Eintrag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleEntry {
+    /// Eindeutige ID (1-255), wird von `Schedule::add` vergeben
+    pub id: u8,
+    /// Wochentage als Bitmaske (Bit 0 = Montag)
+    pub days: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub action: ScheduleAction,
+    pub enabled: bool,
+}
+
+impl ScheduleEntry {
+    /// Prüft ob der Eintrag zur gegebenen lokalen Zeit fällig ist
+    pub fn is_due(&self, now: &LocalTime) -> bool {
+        self.enabled
+            && self.runs_on(now.weekday)
+            && self.hour == now.hour
+            && self.minute == now.minute
+    }
+
+    /// Prüft ob der Eintrag an einem Wochentag aktiv ist
+    pub fn runs_on(&self, weekday: Weekday) -> bool {
+        self.days & (1 << weekday.index()) != 0
+    }
+
+    /// Prüft Wertebereiche (Stunde, Minute, mindestens ein Wochentag, Dauer > 0)
+    pub fn is_valid(&self) -> bool {
+        let action_valid = !matches!(self.action, ScheduleAction::Sunrise { duration_min: 0 });
+        self.hour < 24
+            && self.minute < 60
+            && self.days & ALL_DAYS != 0
+            && self.days <= ALL_DAYS
+            && action_valid
+    }
+
+    /// Serialisiert den Eintrag für die Flash-Persistenz
+    pub fn to_bytes(&self) -> [u8; SCHEDULE_ENTRY_LEN] {
+        let (kind, color) = match self.action {
+            ScheduleAction::SetColor(color) => (0, color),
+            ScheduleAction::Auto => (1, RGB8::default()),
+            ScheduleAction::Off => (2, RGB8::default()),
+            // Dauer im ersten Farb-Byte
+            ScheduleAction::Sunrise { duration_min } => (3, RGB8::new(duration_min, 0, 0)),
+        };
+        [
+            self.id,
+            self.days,
+            self.hour,
+            self.minute,
+            self.enabled as u8,
+            kind,
+            color.r,
+            color.g,
+            color.b,
+        ]
+    }
+
+    /// Deserialisiert einen Eintrag, `None` bei ungültigen Daten
+    pub fn from_bytes(bytes: &[u8; SCHEDULE_ENTRY_LEN]) -> Option<Self> {
+        let action = match bytes[5] {
+            0 => ScheduleAction::SetColor(RGB8 {
+                r: bytes[6],
+                g: bytes[7],
+                b: bytes[8],
+            }),
+            1 => ScheduleAction::Auto,
+            2 => ScheduleAction::Off,
+            3 => ScheduleAction::Sunrise {
+                duration_min: bytes[6],
+            },
+            _ => return None,
+        };
+        let entry = ScheduleEntry {
+            id: bytes[0],
+            days: bytes[1],
+            hour: bytes[2],
+            minute: bytes[3],
+            enabled: bytes[4] != 0,
+            action,
+        };
+        (entry.id != 0 && entry.is_valid()).then_some(entry)
+    }
+}
+
+/// Fehler bei Zeitplan-Änderungen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// Maximale Anzahl Einträge erreicht
+    Full,
+    /// Ungültige Uhrzeit oder keine Wochentage gesetzt
+    InvalidEntry,
+    /// Kein Eintrag mit dieser ID
+    NotFound,
+}
+
+/// Zeitplan mit fester Kapazität `N`
+#[derive(Debug, Clone)]
+pub struct Schedule<const N: usize> {
+    entries: [Option<ScheduleEntry>; N],
+}
+
+impl<const N: usize> Default for Schedule<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Schedule<N> {
+    /// Leerer Zeitplan
+    pub const fn new() -> Self {
+        Self { entries: [None; N] }
+    }
+
+    /// Alle Einträge (sortiert nach Slot, nicht nach Uhrzeit)
+    pub fn entries(&self) -> impl Iterator<Item = &ScheduleEntry> {
+        self.entries.iter().flatten()
+    }
+
+    /// Anzahl Einträge
+    pub fn len(&self) -> usize {
+        self.entries().count()
+    }
+
+    /// Prüft ob der Zeitplan leer ist
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fügt einen Eintrag hinzu und vergibt eine freie ID
+    ///
+    /// Die ID im übergebenen Eintrag wird ignoriert.
+    pub fn add(&mut self, mut entry: ScheduleEntry) -> Result<u8, ScheduleError> {
+        if !entry.is_valid() {
+            return Err(ScheduleError::InvalidEntry);
+        }
+        let slot = self
+            .entries
+            .iter()
+            .position(Option::is_none)
+            .ok_or(ScheduleError::Full)?;
+        let id = (1..=u8::MAX)
+            .find(|id| self.get(*id).is_none())
+            .ok_or(ScheduleError::Full)?;
+        entry.id = id;
+        self.entries[slot] = Some(entry);
+        Ok(id)
+    }
+
+    /// Sucht einen Eintrag per ID
+    pub fn get(&self, id: u8) -> Option<&ScheduleEntry> {
+        self.entries().find(|entry| entry.id == id)
+    }
+
+    /// Ersetzt einen Eintrag, die ID bleibt erhalten
+    pub fn update(&mut self, id: u8, mut entry: ScheduleEntry) -> Result<(), ScheduleError> {
+        if !entry.is_valid() {
+            return Err(ScheduleError::InvalidEntry);
+        }
+        let slot = self
+            .entries
+            .iter_mut()
+            .flatten()
+            .find(|existing| existing.id == id)
+            .ok_or(ScheduleError::NotFound)?;
+        entry.id = id;
+        *slot = entry;
+        Ok(())
+    }
+
+    /// Entfernt einen Eintrag per ID
+    pub fn remove(&mut self, id: u8) -> Result<ScheduleEntry, ScheduleError> {
+        self.entries
+            .iter_mut()
+            .find(|slot| matches!(slot, Some(entry) if entry.id == id))
+            .and_then(Option::take)
+            .ok_or(ScheduleError::NotFound)
+    }
+
+    /// Alle zur gegebenen Zeit fälligen Aktionen
+    pub fn due_actions<'a>(
+        &'a self,
+        now: &'a LocalTime,
+    ) -> impl Iterator<Item = ScheduleAction> + 'a {
+        self.entries()
+            .filter(move |entry| entry.is_due(now))
+            .map(|entry| entry.action)
+    }
+
+    /// Serialisiert den Zeitplan (1 Byte Anzahl + Einträge)
+    ///
+    /// Gibt die geschriebene Länge zurück, oder `None` wenn `out` zu klein ist.
+    pub fn encode(&self, out: &mut [u8]) -> Option<usize> {
+        let total = 1 + self.len() * SCHEDULE_ENTRY_LEN;
+        if out.len() < total {
+            return None;
+        }
+        out[0] = self.len() as u8;
+        let (chunks, _) = out[1..total].as_chunks_mut::<SCHEDULE_ENTRY_LEN>();
+        for (chunk, entry) in chunks.iter_mut().zip(self.entries()) {
+            chunk.copy_from_slice(&entry.to_bytes());
+        }
+        Some(total)
+    }
+
+    /// Deserialisiert einen Zeitplan
+    ///
+    /// Ungültige Einträge werden übersprungen, überzählige verworfen.
+    pub fn decode(data: &[u8]) -> Self {
+        let mut schedule = Self::new();
+        let Some((&count, rest)) = data.split_first() else {
+            return schedule;
+        };
+        let (chunks, _) = rest.as_chunks::<SCHEDULE_ENTRY_LEN>();
+        let entries = chunks
+            .iter()
+            .take(count as usize)
+            .filter_map(ScheduleEntry::from_bytes);
+        for (slot, entry) in schedule.entries.iter_mut().zip(entries) {
+            *slot = Some(entry);
+        }
+        schedule
+    }
+}
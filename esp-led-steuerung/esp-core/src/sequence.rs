@@ -0,0 +1,117 @@
+//! Farbfolge der Auto-Rotation
+//!
+//! Verallgemeinert [`crate::rotate_color`]: statt immer Rot → Grün → Blau
+//! durchläuft die Auto-Rotation eine frei wählbare Liste von bis zu
+//! [`SEQUENCE_MAX_COLORS`] Farben. Standard ist die klassische Folge
+//! ([`ColorSequence::classic`]).
+//!
+//! Text-Syntax (MQTT, Konsole, WebSocket): Farben durch Leerzeichen getrennt,
+//! je `RRGGBB`, `#RRGGBB`, `r,g,b` oder ein Farbname (wie bei `rot`):
+//!
+//! ```text
+//! sequence #FF8000 rot 0,0,255
+//! ```
+
+use rgb::RGB8;
+
+use crate::color::scale_brightness;
+use crate::tasmota::parse_color;
+use crate::types::LedCommand;
+
+/// Maximale Anzahl Farben einer Folge
+pub const SEQUENCE_MAX_COLORS: usize = 8;
+
+/// Liste von Farben, die die Auto-Rotation zyklisch durchläuft
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorSequence {
+    colors: [RGB8; SEQUENCE_MAX_COLORS],
+    len: u8,
+}
+
+impl ColorSequence {
+    /// Klassische Folge Rot → Grün → Blau mit Helligkeit `brightness`
+    pub const fn classic(brightness: u8) -> Self {
+        let mut colors = [RGB8::new(0, 0, 0); SEQUENCE_MAX_COLORS];
+        colors[0] = RGB8::new(brightness, 0, 0);
+        colors[1] = RGB8::new(0, brightness, 0);
+        colors[2] = RGB8::new(0, 0, brightness);
+        Self { colors, len: 3 }
+    }
+
+    /// Folge aus `colors`, `None` wenn leer oder länger als [`SEQUENCE_MAX_COLORS`]
+    pub fn new(colors: &[RGB8]) -> Option<Self> {
+        if colors.is_empty() || colors.len() > SEQUENCE_MAX_COLORS {
+            return None;
+        }
+        let mut sequence = Self {
+            colors: [RGB8::default(); SEQUENCE_MAX_COLORS],
+            len: colors.len() as u8,
+        };
+        sequence.colors[..colors.len()].copy_from_slice(colors);
+        Some(sequence)
+    }
+
+    /// Parst eine durch Leerzeichen getrennte Farbliste (siehe Modul-Doku)
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut colors = [RGB8::default(); SEQUENCE_MAX_COLORS];
+        let mut len = 0;
+        for word in input.split_whitespace() {
+            *colors.get_mut(len)? = parse_color_word(word)?;
+            len += 1;
+        }
+        Self::new(&colors[..len])
+    }
+
+    /// Farben in Reihenfolge
+    pub fn colors(&self) -> &[RGB8] {
+        &self.colors[..self.len as usize]
+    }
+
+    /// Dieselbe Folge mit Helligkeit `brightness` skaliert (255 = unverändert)
+    pub fn scaled(&self, brightness: u8) -> Self {
+        let mut scaled = *self;
+        for color in &mut scaled.colors {
+            *color = scale_brightness(*color, brightness);
+        }
+        scaled
+    }
+
+    /// Startfarbe der Folge
+    pub fn first(&self) -> RGB8 {
+        self.colors[0]
+    }
+
+    /// Nächste Farbe nach `current`
+    ///
+    /// Steht `current` nicht in der Folge (z.B. nach einer manuell gesetzten
+    /// Farbe), beginnt sie von vorn.
+    pub fn next_after(&self, current: RGB8) -> RGB8 {
+        let colors = self.colors();
+        match colors.iter().position(|&color| color == current) {
+            Some(index) => colors[(index + 1) % colors.len()],
+            None => self.first(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ColorSequence {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.colors())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ColorSequence {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_util::deserialize_colors(deserializer, "1 to 8 colors", Self::new)
+    }
+}
+
+/// Eine Farbe als `RRGGBB`, `#RRGGBB`, `r,g,b` oder Farbname
+pub(crate) fn parse_color_word(word: &str) -> Option<RGB8> {
+    parse_color(word).or_else(|| match LedCommand::try_from(word) {
+        Ok(LedCommand::SetColor { target_color, .. }) => Some(target_color),
+        _ => None,
+    })
+}
@@ -0,0 +1,80 @@
+//! Gemeinsame serde-Hilfen für die fest allokierten Core-Typen
+//!
+//! Texte (Lauftext, Farbname) werden als JSON-String, Farblisten (Farbfolge,
+//! Verlauf) als Array von `{"r":..,"g":..,"b":..}` abgebildet. Geprüft wird
+//! beim Deserialisieren mit denselben Konstruktoren wie im Code, ohne Heap.
+
+use core::fmt;
+
+use rgb::RGB8;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+
+use crate::sequence::SEQUENCE_MAX_COLORS;
+
+/// Liest einen String und baut daraus mit `parse` den Zielwert
+pub(crate) fn deserialize_str<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&str) -> Option<T>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct StrVisitor<T> {
+        expecting: &'static str,
+        parse: fn(&str) -> Option<T>,
+    }
+
+    impl<T> Visitor<'_> for StrVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.expecting)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+            (self.parse)(value).ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    deserializer.deserialize_str(StrVisitor { expecting, parse })
+}
+
+/// Liest ein Array aus höchstens [`SEQUENCE_MAX_COLORS`] Farben und baut
+/// daraus mit `build` den Zielwert
+pub(crate) fn deserialize_colors<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    build: fn(&[RGB8]) -> Option<T>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct ColorsVisitor<T> {
+        expecting: &'static str,
+        build: fn(&[RGB8]) -> Option<T>,
+    }
+
+    impl<'de, T> Visitor<'de> for ColorsVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.expecting)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+            let mut colors = [RGB8::default(); SEQUENCE_MAX_COLORS];
+            let mut len = 0;
+            while let Some(color) = seq.next_element::<RGB8>()? {
+                let Some(slot) = colors.get_mut(len) else {
+                    return Err(de::Error::invalid_length(len + 1, &self));
+                };
+                *slot = color;
+                len += 1;
+            }
+            (self.build)(&colors[..len]).ok_or_else(|| de::Error::invalid_length(len, &self))
+        }
+    }
+
+    deserializer.deserialize_seq(ColorsVisitor { expecting, build })
+}
@@ -0,0 +1,49 @@
+//! SNTP Paket-Kodierung (RFC 4330)
+//!
+//! Baut SNTP-Requests und wertet Antworten aus.
+//! Der UDP-Transport liegt in der Firmware (sntp_task).
+
+/// Größe eines SNTP-Pakets ohne Extensions (Bytes)
+pub const SNTP_PACKET_LEN: usize = 48;
+
+/// Standard-Port für NTP/SNTP
+pub const SNTP_PORT: u16 = 123;
+
+/// Sekunden zwischen NTP-Epoch (1900) und Unix-Epoch (1970)
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// LI = 0, Version = 4, Mode = 3 (Client)
+const CLIENT_HEADER: u8 = 0b00_100_011;
+
+/// Mode 4 = Server
+const MODE_SERVER: u8 = 4;
+
+/// Erstellt einen SNTP Client-Request
+pub fn sntp_request() -> [u8; SNTP_PACKET_LEN] {
+    let mut packet = [0u8; SNTP_PACKET_LEN];
+    packet[0] = CLIENT_HEADER;
+    packet
+}
+
+/// Wertet eine SNTP-Antwort aus
+///
+/// Gibt den Transmit-Timestamp als Unix-Zeit in Millisekunden zurück.
+/// `None` bei zu kurzem Paket, falschem Modus, Kiss-of-Death (Stratum 0)
+/// oder Timestamp vor der Unix-Epoch.
+pub fn parse_sntp_response(packet: &[u8]) -> Option<u64> {
+    if packet.len() < SNTP_PACKET_LEN {
+        return None;
+    }
+    let mode = packet[0] & 0x07;
+    let stratum = packet[1];
+    if mode != MODE_SERVER || stratum == 0 {
+        return None;
+    }
+
+    // Transmit Timestamp: Bytes 40..48 (Sekunden + Bruchteil, Big Endian)
+    let secs = u32::from_be_bytes([packet[40], packet[41], packet[42], packet[43]]) as u64;
+    let fraction = u32::from_be_bytes([packet[44], packet[45], packet[46], packet[47]]) as u64;
+    let unix_secs = secs.checked_sub(NTP_UNIX_OFFSET)?;
+    let millis = (fraction * 1000) >> 32;
+    Some(unix_secs * 1000 + millis)
+}
@@ -0,0 +1,308 @@
+//! SSDP (Simple Service Discovery Protocol) und UPnP-Gerätebeschreibung
+//!
+//! Macht die Lampe für UPnP Control Points sichtbar (Windows-Netzwerkumgebung,
+//! UPnP-Browser): der Responder beantwortet `M-SEARCH`-Anfragen auf
+//! 239.255.255.250:1900 und kündigt sich per `NOTIFY` an, die Antworten
+//! verweisen auf `/description.xml`. Das Gerät meldet sich als
+//! `urn:schemas-upnp-org:device:Basic:1` ohne Services, die
+//! `presentationURL` führt zur Web UI.
+//!
+//! Jede Ankündigung erfolgt dreifach (UPnP Device Architecture 1.1, 1.1.2):
+//! als Root-Device, über die UUID und über den Gerätetyp.
+
+use core::fmt::{self, Write};
+
+/// UDP-Port für SSDP
+pub const SSDP_PORT: u16 = 1900;
+
+/// Multicast-Gruppe für SSDP (IPv4)
+pub const SSDP_MULTICAST_ADDR: [u8; 4] = [239, 255, 255, 250];
+
+/// Gerätetyp in Ankündigungen und Beschreibung
+pub const DEVICE_TYPE: &str = "urn:schemas-upnp-org:device:Basic:1";
+
+/// Pfad der Gerätebeschreibung auf dem HTTP-Server
+pub const DESCRIPTION_PATH: &str = "/description.xml";
+
+/// Gültigkeit einer Ankündigung in Sekunden (`CACHE-CONTROL: max-age`)
+pub const MAX_AGE_SECS: u32 = 1800;
+
+/// Obergrenze für `MX` (maximale Antwortverzögerung in Sekunden)
+pub const MAX_MX_SECS: u8 = 5;
+
+/// Fester Anteil der Geräte-UUID, die letzten 12 Stellen sind die MAC
+const UUID_PREFIX: &str = "4c454453-7465-7565-7275-";
+
+/// Länge der UUID in Textform (8-4-4-4-12)
+const UUID_LEN: usize = 36;
+
+/// Stabile Geräte-UUID, abgeleitet aus der MAC-Adresse
+///
+/// Bleibt über Neustarts und Firmware-Updates gleich, damit Windows die
+/// Lampe nicht bei jedem Start als neues Gerät einträgt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeviceUuid([u8; UUID_LEN]);
+
+impl DeviceUuid {
+    pub fn from_mac(mac: [u8; 6]) -> Self {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let mut text = [0u8; UUID_LEN];
+        text[..UUID_PREFIX.len()].copy_from_slice(UUID_PREFIX.as_bytes());
+        for (i, byte) in mac.iter().enumerate() {
+            let pos = UUID_PREFIX.len() + i * 2;
+            text[pos] = HEX[(byte >> 4) as usize];
+            text[pos + 1] = HEX[(byte & 0x0F) as usize];
+        }
+        DeviceUuid(text)
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Nur ASCII aus Präfix und Hex-Ziffern
+        core::str::from_utf8(&self.0).unwrap_or("")
+    }
+}
+
+/// Art einer Ankündigung (`NT` bzw. `ST`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationType {
+    /// `upnp:rootdevice`
+    RootDevice,
+    /// `uuid:<uuid>`
+    Uuid,
+    /// `urn:schemas-upnp-org:device:Basic:1`
+    DeviceType,
+}
+
+impl NotificationType {
+    /// Alle Ankündigungen eines Geräts ohne Services
+    pub const ALL: [NotificationType; 3] = [
+        NotificationType::RootDevice,
+        NotificationType::Uuid,
+        NotificationType::DeviceType,
+    ];
+
+    fn write_target(self, out: &mut impl Write, uuid: &DeviceUuid) -> fmt::Result {
+        match self {
+            NotificationType::RootDevice => out.write_str("upnp:rootdevice"),
+            NotificationType::Uuid => write!(out, "uuid:{}", uuid.as_str()),
+            NotificationType::DeviceType => out.write_str(DEVICE_TYPE),
+        }
+    }
+
+    fn write_usn(self, out: &mut impl Write, uuid: &DeviceUuid) -> fmt::Result {
+        write!(out, "uuid:{}", uuid.as_str())?;
+        if self != NotificationType::Uuid {
+            out.write_str("::")?;
+            self.write_target(out, uuid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Gesuchtes Ziel einer `M-SEARCH`-Anfrage, soweit es dieses Gerät betrifft
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchTarget {
+    /// `ssdp:all`: alle Ankündigungen beantworten
+    All,
+    /// Genau eine Ankündigung
+    Only(NotificationType),
+}
+
+impl SearchTarget {
+    /// Zu sendende Antworten (eine pro Ankündigung)
+    pub fn responses(self) -> &'static [NotificationType] {
+        match self {
+            SearchTarget::All => &NotificationType::ALL,
+            SearchTarget::Only(NotificationType::RootDevice) => &[NotificationType::RootDevice],
+            SearchTarget::Only(NotificationType::Uuid) => &[NotificationType::Uuid],
+            SearchTarget::Only(NotificationType::DeviceType) => &[NotificationType::DeviceType],
+        }
+    }
+}
+
+/// Gültige `M-SEARCH`-Anfrage an dieses Gerät
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SsdpSearch {
+    pub target: SearchTarget,
+    /// Maximale Antwortverzögerung in Sekunden (begrenzt auf `MAX_MX_SECS`)
+    pub mx: u8,
+}
+
+/// Prüft ein empfangenes SSDP-Paket auf eine `M-SEARCH`-Anfrage an dieses Gerät
+///
+/// `None` für Ankündigungen anderer Geräte (`NOTIFY`), Antworten, fehlendes
+/// `MAN: "ssdp:discover"` und Suchziele, die nicht zu diesem Gerät passen.
+pub fn parse_m_search(packet: &[u8], uuid: &DeviceUuid) -> Option<SsdpSearch> {
+    let text = core::str::from_utf8(packet).ok()?;
+    let mut lines = text.lines();
+    let request_line = lines.next()?;
+    if !request_line.eq_ignore_ascii_case("M-SEARCH * HTTP/1.1") {
+        return None;
+    }
+
+    let mut discover = false;
+    let mut target = None;
+    let mut mx = 0;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.trim().eq_ignore_ascii_case("MAN") {
+            discover = value == "\"ssdp:discover\"";
+        } else if name.trim().eq_ignore_ascii_case("ST") {
+            target = Some(value);
+        } else if name.trim().eq_ignore_ascii_case("MX") {
+            mx = value.parse::<u8>().unwrap_or(0).min(MAX_MX_SECS);
+        }
+    }
+    if !discover {
+        return None;
+    }
+
+    let target = match target? {
+        "ssdp:all" => SearchTarget::All,
+        "upnp:rootdevice" => SearchTarget::Only(NotificationType::RootDevice),
+        DEVICE_TYPE => SearchTarget::Only(NotificationType::DeviceType),
+        st => {
+            let id = st.strip_prefix("uuid:")?;
+            if !id.eq_ignore_ascii_case(uuid.as_str()) {
+                return None;
+            }
+            SearchTarget::Only(NotificationType::Uuid)
+        }
+    };
+    Some(SsdpSearch { target, mx })
+}
+
+/// Angaben für Ankündigungen und Suchantworten
+#[derive(Debug, Clone, Copy)]
+pub struct SsdpDevice<'a> {
+    pub uuid: DeviceUuid,
+    /// IPv4-Adresse des HTTP-Servers (für `LOCATION`)
+    pub ip: [u8; 4],
+    /// Firmware-Version für den `SERVER`-Header
+    pub version: &'a str,
+}
+
+impl SsdpDevice<'_> {
+    /// Schreibt die Unicast-Antwort auf eine `M-SEARCH`-Anfrage
+    pub fn write_search_response(
+        &self,
+        out: &mut impl Write,
+        target: NotificationType,
+    ) -> fmt::Result {
+        out.write_str("HTTP/1.1 200 OK\r\n")?;
+        write!(out, "CACHE-CONTROL: max-age={}\r\n", MAX_AGE_SECS)?;
+        out.write_str("EXT:\r\n")?;
+        self.write_location(out)?;
+        self.write_server(out)?;
+        out.write_str("ST: ")?;
+        target.write_target(out, &self.uuid)?;
+        out.write_str("\r\nUSN: ")?;
+        target.write_usn(out, &self.uuid)?;
+        out.write_str("\r\n\r\n")
+    }
+
+    /// Schreibt eine Multicast-Ankündigung (`ssdp:alive` bzw. `ssdp:byebye`)
+    pub fn write_notify(
+        &self,
+        out: &mut impl Write,
+        target: NotificationType,
+        alive: bool,
+    ) -> fmt::Result {
+        out.write_str("NOTIFY * HTTP/1.1\r\n")?;
+        let [a, b, c, d] = SSDP_MULTICAST_ADDR;
+        write!(out, "HOST: {}.{}.{}.{}:{}\r\n", a, b, c, d, SSDP_PORT)?;
+        if alive {
+            write!(out, "CACHE-CONTROL: max-age={}\r\n", MAX_AGE_SECS)?;
+            self.write_location(out)?;
+            self.write_server(out)?;
+        }
+        out.write_str("NT: ")?;
+        target.write_target(out, &self.uuid)?;
+        out.write_str("\r\nNTS: ")?;
+        out.write_str(if alive { "ssdp:alive" } else { "ssdp:byebye" })?;
+        out.write_str("\r\nUSN: ")?;
+        target.write_usn(out, &self.uuid)?;
+        out.write_str("\r\n\r\n")
+    }
+
+    fn write_location(&self, out: &mut impl Write) -> fmt::Result {
+        let [a, b, c, d] = self.ip;
+        write!(
+            out,
+            "LOCATION: http://{}.{}.{}.{}{}\r\n",
+            a, b, c, d, DESCRIPTION_PATH
+        )
+    }
+
+    fn write_server(&self, out: &mut impl Write) -> fmt::Result {
+        write!(
+            out,
+            "SERVER: esp-hal/1.0 UPnP/1.1 esp-led-steuerung/{}\r\n",
+            self.version
+        )
+    }
+}
+
+/// Inhalt der Gerätebeschreibung (`/description.xml`)
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceDescription<'a> {
+    /// Anzeigename (Windows-Netzwerkumgebung)
+    pub friendly_name: &'a str,
+    pub manufacturer: &'a str,
+    pub model_name: &'a str,
+    /// Firmware-Version
+    pub model_number: &'a str,
+    /// IPv4-Adresse der Web UI (`presentationURL`)
+    pub ip: [u8; 4],
+}
+
+/// Schreibt die UPnP-Gerätebeschreibung (XML, `text/xml`)
+pub fn write_device_description(
+    out: &mut impl Write,
+    uuid: &DeviceUuid,
+    description: &DeviceDescription,
+) -> fmt::Result {
+    out.write_str("<?xml version=\"1.0\"?>\n")?;
+    out.write_str("<root xmlns=\"urn:schemas-upnp-org:device-1-0\">\n")?;
+    out.write_str("<specVersion><major>1</major><minor>1</minor></specVersion>\n")?;
+    out.write_str("<device>\n")?;
+    writeln!(out, "<deviceType>{}</deviceType>", DEVICE_TYPE)?;
+    write_xml_element(out, "friendlyName", description.friendly_name)?;
+    write_xml_element(out, "manufacturer", description.manufacturer)?;
+    write_xml_element(out, "modelName", description.model_name)?;
+    write_xml_element(out, "modelNumber", description.model_number)?;
+    // Die MAC (letzte Stellen der UUID) dient als Seriennummer
+    write_xml_element(out, "serialNumber", &uuid.as_str()[UUID_PREFIX.len()..])?;
+    writeln!(out, "<UDN>uuid:{}</UDN>", uuid.as_str())?;
+    let [a, b, c, d] = description.ip;
+    writeln!(
+        out,
+        "<presentationURL>http://{}.{}.{}.{}/</presentationURL>",
+        a, b, c, d
+    )?;
+    out.write_str("</device>\n</root>\n")
+}
+
+fn write_xml_element(out: &mut impl Write, name: &str, value: &str) -> fmt::Result {
+    write!(out, "<{}>", name)?;
+    write_xml_escaped(out, value)?;
+    writeln!(out, "</{}>", name)
+}
+
+/// Maskiert `&`, `<`, `>` und `"`, Steuerzeichen werden ausgelassen
+fn write_xml_escaped(out: &mut impl Write, value: &str) -> fmt::Result {
+    for c in value.chars() {
+        match c {
+            '&' => out.write_str("&amp;")?,
+            '<' => out.write_str("&lt;")?,
+            '>' => out.write_str("&gt;")?,
+            '"' => out.write_str("&quot;")?,
+            c if c.is_control() => {}
+            c => out.write_char(c)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Software-Stall-Erkennung für Tasks
+//!
+//! Der Hardware-Watchdog greift nur, wenn der gesamte Executor steht. Hängt
+//! ein einzelner Task in einem `await`, das nie fertig wird (Deadlock, verlorenes
+//! Signal), laufen die anderen Tasks weiter und füttern den Watchdog.
+//!
+//! [`StallDetector`] vergleicht deshalb die letzten Lebenszeichen aus
+//! [`SystemStats`] mit einer Frist pro Task. Gemeldet werden nur Wechsel
+//! (hängt / wieder aktiv), nicht jede Prüfung. Tasks, die noch nie aktiv waren
+//! oder als wartend markiert sind ([`SystemStats::record_parked`]), werden
+//! nicht bewertet.
+
+use crate::task_stats::{SystemStats, TaskId};
+
+/// Frist eines überwachten Tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskDeadline {
+    pub task: TaskId,
+    /// Maximale Zeit ohne Lebenszeichen in Millisekunden
+    pub timeout_ms: u64,
+}
+
+/// Zustandswechsel eines überwachten Tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallChange {
+    /// Frist überschritten
+    Stalled { task: TaskId, idle_ms: u64 },
+    /// Lebenszeichen nach einem Stall
+    Recovered { task: TaskId },
+}
+
+/// Überwacht `N` Tasks mit je einer Frist
+#[derive(Debug, Clone)]
+pub struct StallDetector<const N: usize> {
+    deadlines: [TaskDeadline; N],
+    stalled: [bool; N],
+}
+
+impl<const N: usize> StallDetector<N> {
+    pub const fn new(deadlines: [TaskDeadline; N]) -> Self {
+        Self {
+            deadlines,
+            stalled: [false; N],
+        }
+    }
+
+    /// Prüft alle Fristen und meldet jeden Zustandswechsel an `on_change`
+    pub fn check(
+        &mut self,
+        stats: &SystemStats,
+        now_ms: u64,
+        mut on_change: impl FnMut(StallChange),
+    ) {
+        for (deadline, stalled) in self.deadlines.iter().zip(self.stalled.iter_mut()) {
+            let task_stats = stats.get(deadline.task);
+            let idle_ms = match task_stats.idle_ms(now_ms) {
+                Some(idle_ms) if !task_stats.parked => idle_ms,
+                _ => 0,
+            };
+            let is_stalled = idle_ms > deadline.timeout_ms;
+
+            if is_stalled && !*stalled {
+                on_change(StallChange::Stalled {
+                    task: deadline.task,
+                    idle_ms,
+                });
+            } else if !is_stalled && *stalled {
+                on_change(StallChange::Recovered {
+                    task: deadline.task,
+                });
+            }
+            *stalled = is_stalled;
+        }
+    }
+
+    /// Prüft ob `task` zuletzt als hängend erkannt wurde
+    pub fn is_stalled(&self, task: TaskId) -> bool {
+        self.deadlines
+            .iter()
+            .zip(self.stalled)
+            .any(|(deadline, stalled)| deadline.task == task && stalled)
+    }
+}
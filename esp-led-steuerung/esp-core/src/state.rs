@@ -0,0 +1,380 @@
+//! LED-Zustandsautomat
+//!
+//! Bündelt den Zustand des LED-Tasks (Auto/Manuell, Farbfolge, Farbe,
+//! Helligkeit samt Rampe, laufender Effekt, Sleep-Timer) und alle Regeln, wie Kommandos und
+//! Zeitablauf ihn verändern. Der LED-Task ist nur noch Treiber:
+//!
+//! 1. Kommando empfangen → [`LedState::apply`]
+//! 2. Einmal pro Durchlauf → [`LedState::tick`], im Zufallsmodus danach
+//!    [`LedState::tick_random`]
+//! 3. [`LedState::output`] auf die LED schreiben
+//! 4. Bei `color_changed` → [`LedState::message`] publishen
+//!
+//! Zeiten sind Millisekunden eines monotonen Zählers (wie in [`crate::transition`]).
+
+use rgb::RGB8;
+
+use crate::calibration::CalibrationPattern;
+use crate::color::{perceived_brightness, scale_brightness};
+use crate::effects::EffectId;
+use crate::preset::Preset;
+use crate::random::random_color;
+use crate::sequence::ColorSequence;
+use crate::traits::Rng;
+use crate::transition::{BrightnessRamp, SleepTimer, Sunrise, Transition};
+use crate::types::{ColorLabel, LedColorMessage, LedCommand};
+
+/// Feste Parameter des Zustandsautomaten (aus der Firmware-Konfiguration)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedStateConfig {
+    /// Helligkeit der Grundfarben (Startfarbe und Neustart der Auto-Rotation)
+    pub base_brightness: u8,
+    /// Endhelligkeit des Sonnenaufgangs (auch für die Testfarben der Kalibrierung)
+    pub sunrise_max_brightness: u8,
+    /// Dauer der Ausblendphase des Sleep-Timers
+    pub sleep_fade_ms: u32,
+    /// Dauer der Helligkeitsrampe bei `SetBrightness` (0 = sofort)
+    pub brightness_fade_ms: u32,
+}
+
+/// Laufender Effekt (zeitbasierter Verlauf, überschreibt die Farbe)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    /// Lichtwecker
+    Sunrise(Sunrise),
+    /// Übergang zu einer Farbe (`LedCommand::FadeTo`)
+    Fade(Transition),
+    /// Testfarben für den Weißabgleich (endlos, `LedCommand::Calibrate`)
+    Calibration(CalibrationPattern),
+}
+
+/// Ergebnis von [`LedState::apply`] bzw. [`LedState::tick`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StateTransition {
+    /// Farbe oder Modus hat sich geändert → Broadcast nötig
+    pub color_changed: bool,
+    /// Effekt ist in diesem Schritt regulär zu Ende gegangen
+    pub effect_finished: bool,
+    /// Sleep-Timer ist in diesem Schritt abgelaufen (LED jetzt aus)
+    pub sleep_expired: bool,
+}
+
+/// Zustand des LED-Tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedState {
+    /// Aktuelle Farbe (ohne Helligkeit und Sleep-Ausblendung)
+    pub color: RGB8,
+    /// Automatische Farb-Rotation aktiv
+    pub auto_rotate: bool,
+    /// Farbfolge der Auto-Rotation (Standard: Rot → Grün → Blau)
+    pub sequence: ColorSequence,
+    /// Zufallsmodus: Auto-Rotation mit Zufallsfarben statt der Folge
+    pub random: bool,
+    /// Gesamthelligkeit (255 = Farbe unverändert), bei laufender Rampe das Ziel
+    pub brightness: u8,
+    /// Laufende Helligkeitsrampe zu `brightness`, läuft unabhängig vom Effekt
+    pub brightness_ramp: Option<BrightnessRamp>,
+    /// Laufender Effekt, wird von jedem Farb-/Modus-Kommando abgebrochen
+    pub effect: Option<Effect>,
+    /// Aktiver Sleep-Timer, wird ebenfalls von jedem Kommando abgebrochen
+    pub sleep_timer: Option<SleepTimer>,
+    /// Name des zuletzt angewendeten Presets, gilt bis zum nächsten Kommando
+    pub name: Option<ColorLabel>,
+    config: LedStateConfig,
+}
+
+impl LedState {
+    /// Startzustand: Auto-Rotation ab Rot, volle Gesamthelligkeit
+    pub fn new(config: LedStateConfig) -> Self {
+        let sequence = ColorSequence::classic(config.base_brightness);
+        Self {
+            color: sequence.first(),
+            auto_rotate: true,
+            sequence,
+            random: false,
+            brightness: u8::MAX,
+            brightness_ramp: None,
+            effect: None,
+            sleep_timer: None,
+            name: None,
+            config,
+        }
+    }
+
+    /// Verarbeitet ein Kommando
+    ///
+    /// Jedes Kommando außer `SetBrightness` und `SetSequence` beendet laufenden
+    /// Effekt und Sleep-Timer.
+    /// `ApplyPreset` wird hier ignoriert: die ID löst [`crate::LedLoop`] auf und
+    /// ruft dann [`LedState::apply_preset`]. `TestPattern`, `Diagnose`, `ScrollText`,
+    /// `ShowClock`, `ShowGradient`, `Meteor` und `Plasma` gibt der LED-Task
+    /// pixelweise selbst aus, der Zustand bleibt dabei unverändert.
+    /// `SetEffectParam` gilt nur für den laufenden pixelweisen Effekt.
+    pub fn apply(&mut self, command: LedCommand, now_ms: u64) -> StateTransition {
+        if !matches!(
+            command,
+            LedCommand::SetBrightness { .. }
+                | LedCommand::SetSequence { .. }
+                | LedCommand::ApplyPreset { .. }
+                | LedCommand::TestPattern
+                | LedCommand::Diagnose
+                | LedCommand::ScrollText { .. }
+                | LedCommand::ShowClock { .. }
+                | LedCommand::ShowGradient { .. }
+                | LedCommand::Meteor { .. }
+                | LedCommand::Plasma { .. }
+                | LedCommand::SetEffectParam { .. }
+        ) {
+            self.effect = None;
+            self.sleep_timer = None;
+            self.name = None;
+        }
+        let mut transition = StateTransition::default();
+        match command {
+            LedCommand::SetColor { target_color, .. } => {
+                self.color = target_color;
+                self.auto_rotate = false;
+                transition.color_changed = true;
+            }
+            LedCommand::EnableAuto => {
+                // Keine Farb-Änderung, nur Modus-Wechsel (Broadcast beim nächsten Tick)
+                // Ausnahme: nach "Aus" am Anfang der Folge starten (Schwarz rotiert nicht)
+                self.auto_rotate = true;
+                self.random = false;
+                if self.color == RGB8::default() {
+                    self.color = self.sequence.first();
+                }
+            }
+            LedCommand::EnableRandom => {
+                // Erste Zufallsfarbe beim nächsten Durchlauf (`tick_random`)
+                self.auto_rotate = true;
+                self.random = true;
+            }
+            LedCommand::FadeTo {
+                target_color,
+                transition_ms: 0,
+                ..
+            } => {
+                self.color = target_color;
+                self.auto_rotate = false;
+                transition.color_changed = true;
+            }
+            LedCommand::FadeTo {
+                target_color,
+                transition_ms,
+                ..
+            } => {
+                // Start bei der aktuellen Farbe, Broadcast meldet schon die Zielfarbe
+                let fade = Transition::new(self.color, target_color, now_ms, transition_ms);
+                self.effect = Some(Effect::Fade(fade));
+                self.auto_rotate = false;
+                transition.color_changed = true;
+            }
+            LedCommand::Off => {
+                self.color = RGB8::default();
+                self.auto_rotate = false;
+                transition.color_changed = true;
+            }
+            LedCommand::Sunrise { duration_secs } => {
+                let sunrise = Sunrise::new(
+                    now_ms,
+                    duration_secs.saturating_mul(1000),
+                    self.config.sunrise_max_brightness,
+                );
+                self.color = sunrise.color_at(now_ms);
+                self.effect = Some(Effect::Sunrise(sunrise));
+                self.auto_rotate = false;
+                transition.color_changed = true;
+            }
+            LedCommand::Calibrate => {
+                let pattern = CalibrationPattern::new(now_ms, self.config.sunrise_max_brightness);
+                self.color = pattern.color_at(now_ms);
+                self.effect = Some(Effect::Calibration(pattern));
+                self.auto_rotate = false;
+                transition.color_changed = true;
+            }
+            LedCommand::SleepTimer { minutes: 0 } => {}
+            LedCommand::SleepTimer { minutes } => {
+                crate::log_debug!("Sleep-Timer gestartet: {} min", minutes);
+                // Farbe und Modus bleiben bis zur Ausblendphase unverändert
+                self.sleep_timer = Some(SleepTimer::new(
+                    now_ms,
+                    minutes as u32 * 60_000,
+                    self.config.sleep_fade_ms,
+                ));
+            }
+            LedCommand::SetBrightness { brightness } => self.set_brightness(brightness, now_ms),
+            LedCommand::SetSequence { sequence } => {
+                self.sequence =
+                    sequence.unwrap_or(ColorSequence::classic(self.config.base_brightness));
+                // Laufende Rotation springt sofort in die neue Folge
+                if self.auto_rotate {
+                    self.color = self.sequence.first();
+                    transition.color_changed = true;
+                }
+            }
+            LedCommand::ApplyPreset { .. }
+            | LedCommand::TestPattern
+            | LedCommand::Diagnose
+            | LedCommand::ScrollText { .. }
+            | LedCommand::ShowClock { .. }
+            | LedCommand::ShowGradient { .. }
+            | LedCommand::Meteor { .. }
+            | LedCommand::Plasma { .. }
+            | LedCommand::SetEffectParam { .. } => {}
+        }
+        // Jeder Wechsel in den manuellen Modus beendet auch den Zufallsmodus
+        self.random &= self.auto_rotate;
+        transition
+    }
+
+    /// Wendet ein Preset an: Farbe bzw. Effekt wie [`Preset::command`], dann Helligkeit
+    pub fn apply_preset(&mut self, preset: &Preset, now_ms: u64) -> StateTransition {
+        crate::log_debug!("Preset {} angewendet", preset.id);
+        let mut transition = self.apply(preset.command(), now_ms);
+        self.set_brightness(preset.brightness, now_ms);
+        self.name = ColorLabel::new(preset.name.as_str());
+        // Neuer Name wird auch ohne Farbwechsel gemeldet
+        transition.color_changed = true;
+        transition
+    }
+
+    /// Setzt die Gesamthelligkeit, gleitend über `brightness_fade_ms`
+    ///
+    /// Die Rampe startet bei der aktuell sichtbaren Helligkeit, auch
+    /// mitten in einer laufenden Rampe (Regler ziehen).
+    fn set_brightness(&mut self, brightness: u8, now_ms: u64) {
+        let current = self.brightness_at(now_ms);
+        self.brightness = brightness;
+        self.brightness_ramp =
+            (current != brightness && self.config.brightness_fade_ms > 0).then(|| {
+                BrightnessRamp::new(current, brightness, now_ms, self.config.brightness_fade_ms)
+            });
+    }
+
+    /// Sichtbare Gesamthelligkeit zum Zeitpunkt `now_ms` (während der Rampe gleitend)
+    pub fn brightness_at(&self, now_ms: u64) -> u8 {
+        match self.brightness_ramp {
+            Some(ramp) => ramp.level_at(now_ms),
+            None => self.brightness,
+        }
+    }
+
+    /// Schreitet einen Durchlauf fort: Effekt, Auto-Rotation, Helligkeit, Sleep-Timer
+    pub fn tick(&mut self, now_ms: u64) -> StateTransition {
+        let mut transition = StateTransition::default();
+
+        if self
+            .brightness_ramp
+            .is_some_and(|ramp| ramp.is_finished(now_ms))
+        {
+            self.brightness_ramp = None;
+        }
+
+        match self.effect {
+            Some(Effect::Sunrise(sunrise)) => {
+                self.color = sunrise.color_at(now_ms);
+                if sunrise.is_finished(now_ms) {
+                    crate::log_debug!("Sonnenaufgang beendet");
+                    self.effect = None;
+                    transition.effect_finished = true;
+                    transition.color_changed = true;
+                }
+            }
+            Some(Effect::Fade(fade)) => {
+                self.color = fade.color_at(now_ms);
+                if fade.is_finished(now_ms) {
+                    // Zielfarbe wurde bereits beim Start veröffentlicht
+                    self.effect = None;
+                    transition.effect_finished = true;
+                }
+            }
+            Some(Effect::Calibration(pattern)) => {
+                let color = pattern.color_at(now_ms);
+                transition.color_changed = color != self.color;
+                self.color = color;
+            }
+            None => {}
+        }
+
+        if self.auto_rotate && !self.random {
+            self.color = self.sequence.next_after(self.color);
+            transition.color_changed = true;
+        }
+
+        if self
+            .sleep_timer
+            .is_some_and(|timer| timer.is_finished(now_ms))
+        {
+            crate::log_info!("Sleep-Timer abgelaufen, LED aus");
+            self.sleep_timer = None;
+            self.effect = None;
+            self.color = RGB8::default();
+            self.auto_rotate = false;
+            self.random = false;
+            transition.sleep_expired = true;
+            transition.color_changed = true;
+        }
+        transition
+    }
+
+    /// Zufallsmodus: neue Farbe aus `rng` (nach [`LedState::tick`] aufrufen)
+    ///
+    /// Hellwert wie die Grundfarben (`base_brightness`), siehe [`random_color`].
+    pub fn tick_random(&mut self, rng: &mut impl Rng) -> StateTransition {
+        let mut transition = StateTransition::default();
+        if self.auto_rotate && self.random {
+            self.color = random_color(rng, self.color, self.config.base_brightness);
+            transition.color_changed = true;
+        }
+        transition
+    }
+
+    /// Farbe für die LED (mit Sleep-Ausblendung und Gesamthelligkeit)
+    ///
+    /// Die Gesamthelligkeit wirkt über [`perceived_brightness`], damit
+    /// Regler in UI und Home Assistant gleichmäßig wirken, während einer
+    /// Rampe mit dem Zwischenwert ([`LedState::brightness_at`]).
+    pub fn output(&self, now_ms: u64) -> RGB8 {
+        let color = match self.sleep_timer {
+            Some(timer) => timer.apply(self.color, now_ms),
+            None => self.color,
+        };
+        scale_brightness(color, perceived_brightness(self.brightness_at(now_ms)))
+    }
+
+    /// Prüft ob gerade ein Verlauf läuft (höhere Bildrate nötig)
+    pub fn is_animating(&self, now_ms: u64) -> bool {
+        self.effect.is_some()
+            || self.brightness_ramp.is_some()
+            || self
+                .sleep_timer
+                .is_some_and(|timer| timer.is_fading(now_ms))
+    }
+
+    /// Aktiver Effekt laut Zustand (siehe [`crate::effects`])
+    ///
+    /// Die pixelweisen Modi (Testmuster, Lauftext, Uhrzeit, Realtime) laufen
+    /// im LED-Task am Zustand vorbei und werden dort gemeldet.
+    pub fn active_effect(&self) -> EffectId {
+        match self.effect {
+            Some(Effect::Sunrise(_)) => EffectId::Sunrise,
+            Some(Effect::Fade(_)) => EffectId::Fade,
+            Some(Effect::Calibration(_)) => EffectId::Calibration,
+            None if self.auto_rotate && self.random => EffectId::Random,
+            None if self.auto_rotate => EffectId::Auto,
+            None => EffectId::Solid,
+        }
+    }
+
+    /// Broadcast-Nachricht für den aktuellen Zustand
+    ///
+    /// Während eines Übergangs wird die Zielfarbe gemeldet.
+    pub fn message(&self) -> LedColorMessage {
+        let color = match self.effect {
+            Some(Effect::Fade(fade)) => fade.to,
+            _ => self.color,
+        };
+        LedColorMessage::from_color(color, self.auto_rotate).with_name(self.name)
+    }
+}
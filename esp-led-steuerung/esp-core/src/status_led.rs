@@ -0,0 +1,108 @@
+//! Status-Blinkcodes für die Verbindung (Diagnose ohne Laptop)
+//!
+//! [`StatusIndicator`] leitet aus den [`SystemEvent`]s einen Status ab und
+//! liefert pro Zeitpunkt die Farbe der Status-LED:
+//!
+//! | Status                | Muster                                   |
+//! |-----------------------|------------------------------------------|
+//! | Verbindung wird aufgebaut | Blau, langsames Blinken (1 s an, 1 s aus) |
+//! | Online (MQTT bzw. IP) | Grün, dauerhaft                          |
+//! | Fehler                | Rot, Doppelblinken                       |
+//!
+//! Ein Fehler (`Error`, `TaskStalled`) wird [`STATUS_ERROR_HOLD_MS`] lang
+//! angezeigt, danach wieder der Verbindungsstatus. `TaskRecovered` beendet
+//! die Fehleranzeige sofort.
+
+use rgb::RGB8;
+
+use crate::events::SystemEvent;
+
+/// Dauer der Fehleranzeige nach dem letzten Fehler-Ereignis
+pub const STATUS_ERROR_HOLD_MS: u64 = 10_000;
+
+/// Periode des Blinkens beim Verbindungsaufbau
+const CONNECTING_PERIOD_MS: u64 = 2000;
+
+/// Periode des Doppelblinkens bei Fehlern
+const ERROR_PERIOD_MS: u64 = 1500;
+
+/// Dauer eines Blitzes beim Doppelblinken (an, aus, an, dann Pause)
+const ERROR_FLASH_MS: u64 = 150;
+
+/// Angezeigter Status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusCode {
+    Connecting,
+    Online,
+    Error,
+}
+
+impl StatusCode {
+    pub fn name(self) -> &'static str {
+        match self {
+            StatusCode::Connecting => "connecting",
+            StatusCode::Online => "online",
+            StatusCode::Error => "error",
+        }
+    }
+}
+
+/// Zustand der Status-LED
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusIndicator {
+    /// Online erst mit MQTT-Verbindung (sonst schon mit IP-Adresse)
+    require_mqtt: bool,
+    online: bool,
+    /// Ende der Fehleranzeige
+    error_until_ms: Option<u64>,
+}
+
+impl StatusIndicator {
+    pub const fn new(require_mqtt: bool) -> Self {
+        Self {
+            require_mqtt,
+            online: false,
+            error_until_ms: None,
+        }
+    }
+
+    /// Übernimmt ein Ereignis zum Zeitpunkt `now_ms`
+    pub fn handle(&mut self, event: &SystemEvent, now_ms: u64) {
+        match event {
+            SystemEvent::WifiDisconnected => self.online = false,
+            SystemEvent::IpAcquired { .. } if !self.require_mqtt => self.online = true,
+            SystemEvent::MqttConnected => self.online = true,
+            SystemEvent::MqttDisconnected if self.require_mqtt => self.online = false,
+            SystemEvent::Error(_) | SystemEvent::TaskStalled { .. } => {
+                self.error_until_ms = Some(now_ms + STATUS_ERROR_HOLD_MS);
+            }
+            SystemEvent::TaskRecovered { .. } => self.error_until_ms = None,
+            _ => {}
+        }
+    }
+
+    pub fn code(&self, now_ms: u64) -> StatusCode {
+        match self.error_until_ms {
+            Some(until) if now_ms < until => StatusCode::Error,
+            _ if self.online => StatusCode::Online,
+            _ => StatusCode::Connecting,
+        }
+    }
+
+    /// Farbe der Status-LED zum Zeitpunkt `now_ms` mit Helligkeit `brightness`
+    pub fn frame(&self, now_ms: u64, brightness: u8) -> RGB8 {
+        match self.code(now_ms) {
+            StatusCode::Connecting if now_ms % CONNECTING_PERIOD_MS < CONNECTING_PERIOD_MS / 2 => {
+                RGB8::new(0, 0, brightness)
+            }
+            StatusCode::Online => RGB8::new(0, brightness, 0),
+            StatusCode::Error
+                if (now_ms % ERROR_PERIOD_MS / ERROR_FLASH_MS).is_multiple_of(2)
+                    && now_ms % ERROR_PERIOD_MS < 4 * ERROR_FLASH_MS =>
+            {
+                RGB8::new(brightness, 0, 0)
+            }
+            _ => RGB8::default(),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Synchronisation mehrerer Geräte per UDP-Multicast (Leader/Follower)
+//!
+//! Der Leader sendet regelmäßig die Farbe, die er zu einem Zeitpunkt kurz in
+//! der Zukunft zeigen wird (der Zustand ist eine reine Funktion der Zeit,
+//! siehe [`crate::state`]). Follower schätzen den Versatz zwischen seiner und
+//! ihrer Uhr und geben den Frame zum umgerechneten Zeitpunkt aus, so laufen
+//! Verläufe und Auto-Rotation auf allen Lampen im Gleichschritt.
+//!
+//! Paket-Format (22 Bytes, Big Endian):
+//!
+//! | Bytes | Inhalt                                               |
+//! |-------|------------------------------------------------------|
+//! | 0-3   | Kennung `LSYN`                                       |
+//! | 4     | Protokoll-Version ([`SYNC_VERSION`])                 |
+//! | 5-8   | Sequenznummer                                        |
+//! | 9-16  | Sendezeitpunkt in ms (Uhr des Leaders)               |
+//! | 17-18 | Vorlauf in ms: Frame gilt ab Sendezeitpunkt + Vorlauf |
+//! | 19-21 | Farbe `r, g, b` (ohne Weißabgleich)                  |
+
+use rgb::RGB8;
+
+/// Standard-UDP-Port (neben dem Realtime-Port)
+pub const SYNC_PORT: u16 = 21325;
+
+/// Multicast-Gruppe (organisationslokaler Bereich 239.255.0.0/16)
+pub const SYNC_MULTICAST_ADDR: [u8; 4] = [239, 255, 21, 25];
+
+/// Protokoll-Version, Pakete anderer Versionen werden verworfen
+pub const SYNC_VERSION: u8 = 1;
+
+/// Länge eines Sync-Pakets in Bytes
+pub const SYNC_PACKET_LEN: usize = 22;
+
+/// Ohne Pakete in dieser Zeit gilt der Leader als verloren (Neustart möglich)
+pub const SYNC_TIMEOUT_MS: u64 = 3000;
+
+/// Paket-Kennung
+const MAGIC: [u8; 4] = *b"LSYN";
+
+/// Erlaubte Zunahme des geschätzten Versatzes pro Paket (Uhren-Drift)
+const OFFSET_DRIFT_MS: i64 = 1;
+
+/// Rolle des Geräts im Sync-Verbund
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncRole {
+    /// Keine Synchronisation
+    #[default]
+    Off,
+    /// Sendet den eigenen Zustand
+    Leader,
+    /// Übernimmt den Zustand des Leaders
+    Follower,
+}
+
+impl SyncRole {
+    /// Rolle aus der Konfiguration (`off`, `leader`, `follower`)
+    pub const fn from_name(name: &str) -> Option<Self> {
+        match name.as_bytes() {
+            b"off" => Some(SyncRole::Off),
+            b"leader" => Some(SyncRole::Leader),
+            b"follower" => Some(SyncRole::Follower),
+            _ => None,
+        }
+    }
+}
+
+/// Ein Sync-Paket des Leaders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncPacket {
+    pub sequence: u32,
+    /// Sendezeitpunkt (ms, Uhr des Leaders)
+    pub sent_ms: u64,
+    /// Abstand zwischen Sendezeitpunkt und Anzeige
+    pub lead_ms: u16,
+    pub color: RGB8,
+}
+
+impl SyncPacket {
+    pub fn encode(&self) -> [u8; SYNC_PACKET_LEN] {
+        let mut packet = [0; SYNC_PACKET_LEN];
+        packet[..4].copy_from_slice(&MAGIC);
+        packet[4] = SYNC_VERSION;
+        packet[5..9].copy_from_slice(&self.sequence.to_be_bytes());
+        packet[9..17].copy_from_slice(&self.sent_ms.to_be_bytes());
+        packet[17..19].copy_from_slice(&self.lead_ms.to_be_bytes());
+        packet[19..].copy_from_slice(&[self.color.r, self.color.g, self.color.b]);
+        packet
+    }
+
+    /// `None` bei falscher Länge, Kennung oder Version
+    pub fn decode(packet: &[u8]) -> Option<Self> {
+        let packet: &[u8; SYNC_PACKET_LEN] = packet.try_into().ok()?;
+        if packet[..4] != MAGIC || packet[4] != SYNC_VERSION {
+            return None;
+        }
+        let [r, g, b] = [packet[19], packet[20], packet[21]];
+        Some(Self {
+            sequence: u32::from_be_bytes(packet[5..9].try_into().ok()?),
+            sent_ms: u64::from_be_bytes(packet[9..17].try_into().ok()?),
+            lead_ms: u16::from_be_bytes(packet[17..19].try_into().ok()?),
+            color: RGB8::new(r, g, b),
+        })
+    }
+}
+
+/// Vom Follower auszugebender Frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncFrame {
+    /// Anzeigezeitpunkt (ms, eigene Uhr)
+    pub show_at_ms: u64,
+    pub color: RGB8,
+}
+
+/// Empfangsseite: verwirft veraltete Pakete und rechnet Zeitpunkte um
+///
+/// Der Versatz wird als Minimum von `Empfang - Sendezeitpunkt` geschätzt
+/// (Uhrenversatz plus kürzeste Laufzeit); verzögerte Pakete verschieben die
+/// Schätzung also nicht. Damit ein Leader mit langsamerer Uhr nicht dauerhaft
+/// abgehängt wird, darf die Schätzung pro Paket um [`OFFSET_DRIFT_MS`] steigen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyncFollower {
+    /// Geschätzter Versatz `eigene Uhr - Uhr des Leaders`
+    offset_ms: Option<i64>,
+    last_sequence: u32,
+    /// Empfangszeitpunkt des letzten angenommenen Pakets
+    last_received_ms: u64,
+}
+
+impl SyncFollower {
+    pub const fn new() -> Self {
+        Self {
+            offset_ms: None,
+            last_sequence: 0,
+            last_received_ms: 0,
+        }
+    }
+
+    /// Geschätzter Versatz, `None` vor dem ersten Paket
+    pub fn offset_ms(&self) -> Option<i64> {
+        self.offset_ms
+    }
+
+    /// Verarbeitet ein Paket, empfangen zum Zeitpunkt `now_ms`
+    ///
+    /// `None` für doppelte oder vertauschte Pakete. Zu spät angekommene
+    /// Frames werden sofort ausgegeben (`show_at_ms == now_ms`).
+    pub fn receive(&mut self, packet: &SyncPacket, now_ms: u64) -> Option<SyncFrame> {
+        let lost = now_ms.saturating_sub(self.last_received_ms) > SYNC_TIMEOUT_MS;
+        if lost {
+            // Leader neu gestartet oder gewechselt: Sequenz und Versatz neu lernen
+            self.offset_ms = None;
+        } else if self.offset_ms.is_some()
+            && (packet.sequence.wrapping_sub(self.last_sequence) as i32) <= 0
+        {
+            return None;
+        }
+        self.last_sequence = packet.sequence;
+        self.last_received_ms = now_ms;
+
+        let sample = now_ms as i64 - packet.sent_ms as i64;
+        let offset = match self.offset_ms {
+            Some(offset) => sample.min(offset + OFFSET_DRIFT_MS),
+            None => sample,
+        };
+        self.offset_ms = Some(offset);
+
+        let show_at = packet.sent_ms as i64 + i64::from(packet.lead_ms) + offset;
+        Some(SyncFrame {
+            show_at_ms: (show_at.max(0) as u64).max(now_ms),
+            color: packet.color,
+        })
+    }
+}
@@ -0,0 +1,97 @@
+//! Syslog-Rahmen für Log-Zeilen (RFC 5424 über UDP, RFC 5426)
+//!
+//! Der Log-Bridge-Task der Firmware schickt jede Zeile des Log-Ringpuffers
+//! als eigenes Datagramm an einen Syslog-Empfänger (rsyslog, syslog-ng,
+//! Graylog, `nc -ul 514`). So ist das Log im Feld ohne Debug-Probe lesbar.
+//!
+//! ```text
+//! <134>1 2026-10-15T08:30:00.250Z led esp-led - - [meta sequenceId="13" sysUpTime="425"] WiFi connected
+//! ```
+//!
+//! - PRI: Facility `local0`, Severity aus dem [`LogLevel`]
+//! - TIMESTAMP: nur mit Uhrzeit (SNTP/RTC), sonst `-`
+//! - `sequenceId`: Zeilennummer + 1 (RFC 5424 verlangt ab 1); Lücken zeigen
+//!   verlorene Zeilen
+//! - `sysUpTime`: Zeit seit Boot in Hundertstelsekunden (wie SNMP)
+
+use core::fmt;
+
+use crate::console::LogLevel;
+use crate::log_ring::LogLine;
+use crate::time::civil_from_days;
+
+/// Standard-Port für Syslog über UDP
+pub const SYSLOG_PORT: u16 = 514;
+
+/// Facility `local0` (frei für Anwendungen)
+const FACILITY_LOCAL0: u8 = 16;
+
+/// APP-NAME im Header
+const APP_NAME: &str = "esp-led";
+
+/// Größter `sequenceId` laut RFC 5424 (danach Neubeginn bei 1)
+const MAX_SEQUENCE_ID: u32 = 2_147_483_647;
+
+/// Syslog-Severity eines Log-Levels (Trace wie Debug)
+pub fn syslog_severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Error => 3,
+        LogLevel::Warn => 4,
+        LogLevel::Info => 6,
+        LogLevel::Debug | LogLevel::Trace => 7,
+    }
+}
+
+/// Schreibt eine Log-Zeile als Syslog-Nachricht
+///
+/// `hostname` erscheint als HOSTNAME (z.B. der mDNS-Name), `boot_unix_ms`
+/// ist die Unix-Zeit des Boots (`None` ohne Uhrzeit).
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::{LogLevel, LogRing, write_syslog_frame};
+/// let mut ring: LogRing<4, 32> = LogRing::new();
+/// ring.push(1234, LogLevel::Warn, format_args!("MQTT: Timeout"));
+/// let line = ring.iter().next().unwrap();
+/// let mut out = String::new();
+/// write_syslog_frame(&mut out, "led", None, line).unwrap();
+/// assert_eq!(
+///     out,
+///     "<132>1 - led esp-led - - [meta sequenceId=\"1\" sysUpTime=\"123\"] MQTT: Timeout"
+/// );
+/// ```
+pub fn write_syslog_frame<const LEN: usize>(
+    out: &mut impl fmt::Write,
+    hostname: &str,
+    boot_unix_ms: Option<u64>,
+    line: &LogLine<LEN>,
+) -> fmt::Result {
+    let pri = FACILITY_LOCAL0 * 8 + syslog_severity(line.level);
+    write!(out, "<{pri}>1 ")?;
+    match boot_unix_ms {
+        Some(boot_unix_ms) => write_timestamp(out, boot_unix_ms + line.timestamp_ms)?,
+        None => out.write_char('-')?,
+    }
+    let sequence_id = line.seq % MAX_SEQUENCE_ID + 1;
+    write!(
+        out,
+        " {hostname} {APP_NAME} - - [meta sequenceId=\"{sequence_id}\" sysUpTime=\"{}\"] {}",
+        line.timestamp_ms / 10,
+        line.text()
+    )
+}
+
+/// Zeitstempel nach RFC 3339 in UTC mit Millisekunden
+fn write_timestamp(out: &mut impl fmt::Write, unix_ms: u64) -> fmt::Result {
+    let secs = unix_ms / 1000;
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let secs_of_day = secs % 86_400;
+    write!(
+        out,
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        unix_ms % 1000
+    )
+}
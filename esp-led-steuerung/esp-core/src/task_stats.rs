@@ -0,0 +1,180 @@
+//! Laufzeit-Statistik pro Task
+//!
+//! Jeder Task meldet pro Schleifendurchlauf ein Lebenszeichen
+//! ([`SystemStats::record_iteration`]), Fehler werden über die
+//! [`ErrorSource`] dem zuständigen Task zugeordnet. Steht der Zeitpunkt der
+//! letzten Aktivität eines Tasks still, während die anderen weiterlaufen,
+//! hängt genau dieser Task.
+//!
+//! Alle Zähler laufen seit dem Start, sättigend und ohne Reset.
+
+use crate::error::ErrorSource;
+
+/// Überwachte Tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskId {
+    Led,
+    Wifi,
+    Mqtt,
+    Http,
+    Mdns,
+    Sntp,
+    Scheduler,
+    Coap,
+    Osc,
+    Realtime,
+    Console,
+    Heap,
+    Sync,
+}
+
+impl TaskId {
+    /// Anzahl Tasks (Größe von [`SystemStats`])
+    pub const COUNT: usize = 13;
+
+    /// Alle Tasks in Index-Reihenfolge
+    pub const ALL: [TaskId; Self::COUNT] = [
+        TaskId::Led,
+        TaskId::Wifi,
+        TaskId::Mqtt,
+        TaskId::Http,
+        TaskId::Mdns,
+        TaskId::Sntp,
+        TaskId::Scheduler,
+        TaskId::Coap,
+        TaskId::Osc,
+        TaskId::Realtime,
+        TaskId::Console,
+        TaskId::Heap,
+        TaskId::Sync,
+    ];
+
+    /// Kurzname für Logs und Protokolle
+    pub fn name(&self) -> &'static str {
+        match self {
+            TaskId::Led => "led",
+            TaskId::Wifi => "wifi",
+            TaskId::Mqtt => "mqtt",
+            TaskId::Http => "http",
+            TaskId::Mdns => "mdns",
+            TaskId::Sntp => "sntp",
+            TaskId::Scheduler => "scheduler",
+            TaskId::Coap => "coap",
+            TaskId::Osc => "osc",
+            TaskId::Realtime => "realtime",
+            TaskId::Console => "console",
+            TaskId::Heap => "heap",
+            TaskId::Sync => "sync",
+        }
+    }
+
+    /// Task, der Fehler dieser Komponente meldet
+    ///
+    /// `Network` betrifft den gemeinsamen Netzwerk-Stack und gehört zu keinem Task.
+    pub fn for_source(source: ErrorSource) -> Option<Self> {
+        match source {
+            ErrorSource::Wifi => Some(TaskId::Wifi),
+            ErrorSource::Network => None,
+            ErrorSource::Mqtt => Some(TaskId::Mqtt),
+            ErrorSource::Http => Some(TaskId::Http),
+            ErrorSource::Led => Some(TaskId::Led),
+            ErrorSource::Storage | ErrorSource::Schedule | ErrorSource::Preset => {
+                Some(TaskId::Scheduler)
+            }
+            ErrorSource::Sntp => Some(TaskId::Sntp),
+            ErrorSource::Mdns => Some(TaskId::Mdns),
+            ErrorSource::Coap => Some(TaskId::Coap),
+            ErrorSource::Osc => Some(TaskId::Osc),
+        }
+    }
+}
+
+/// Zähler eines Tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TaskStats {
+    /// Schleifendurchläufe
+    pub iterations: u32,
+    /// Gemeldete Fehler
+    pub errors: u32,
+    /// Zeitpunkt der letzten Aktivität (ms seit Boot, `None` = noch nie aktiv)
+    pub last_activity_ms: Option<u64>,
+    /// Wartet bis zum nächsten Durchlauf legitim auf Eingaben (z.B. keine Clients)
+    pub parked: bool,
+}
+
+impl TaskStats {
+    pub const fn new() -> Self {
+        Self {
+            iterations: 0,
+            errors: 0,
+            last_activity_ms: None,
+            parked: false,
+        }
+    }
+
+    /// Millisekunden seit der letzten Aktivität (`None` = noch nie aktiv)
+    pub fn idle_ms(&self, now_ms: u64) -> Option<u64> {
+        self.last_activity_ms
+            .map(|last| now_ms.saturating_sub(last))
+    }
+}
+
+/// Zähler aller Tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SystemStats {
+    tasks: [TaskStats; TaskId::COUNT],
+}
+
+impl SystemStats {
+    pub const fn new() -> Self {
+        Self {
+            tasks: [TaskStats::new(); TaskId::COUNT],
+        }
+    }
+
+    /// Zählt einen Schleifendurchlauf (Lebenszeichen)
+    pub fn record_iteration(&mut self, task: TaskId, now_ms: u64) {
+        let stats = &mut self.tasks[task as usize];
+        stats.iterations = stats.iterations.saturating_add(1);
+        stats.last_activity_ms = Some(now_ms);
+        stats.parked = false;
+    }
+
+    /// Markiert den Task als wartend, bis er den nächsten Durchlauf meldet
+    ///
+    /// Für Tasks ohne eigenen Takt, die ohne Eingaben nichts zu tun haben
+    /// (z.B. HTTP ohne WebSocket-Clients). Die Stall-Erkennung lässt sie aus.
+    pub fn record_parked(&mut self, task: TaskId, now_ms: u64) {
+        let stats = &mut self.tasks[task as usize];
+        stats.last_activity_ms = Some(now_ms);
+        stats.parked = true;
+    }
+
+    /// Zählt einen Fehler (gilt auch als Aktivität)
+    pub fn record_error(&mut self, task: TaskId, now_ms: u64) {
+        let stats = &mut self.tasks[task as usize];
+        stats.errors = stats.errors.saturating_add(1);
+        stats.last_activity_ms = Some(now_ms);
+    }
+
+    /// Zähler eines Tasks
+    pub fn get(&self, task: TaskId) -> TaskStats {
+        self.tasks[task as usize]
+    }
+
+    /// Alle Tasks mit ihren Zählern in Index-Reihenfolge
+    pub fn iter(&self) -> impl Iterator<Item = (TaskId, TaskStats)> + '_ {
+        TaskId::ALL.into_iter().map(|task| (task, self.get(task)))
+    }
+}
+
+// ============================================================================
+// defmt::Format Implementations (optional feature)
+// ============================================================================
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for TaskId {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{}", self.name())
+    }
+}
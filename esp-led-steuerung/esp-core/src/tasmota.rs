@@ -0,0 +1,146 @@
+//! Tasmota-kompatible MQTT-Kommandos
+//!
+//! Bildet die wichtigsten Tasmota-Light-Kommandos ab, damit bestehende
+//! Automationen und Dashboards unverändert weiterlaufen:
+//!
+//! | Topic                      | Payload                          |
+//! |----------------------------|----------------------------------|
+//! | `cmnd/<topic>/POWER`       | `ON`, `OFF`, `TOGGLE`, leer = Abfrage |
+//! | `cmnd/<topic>/Color`       | `RRGGBB`, `#RRGGBB`, `r,g,b`, leer = Abfrage |
+//! | `stat/<topic>/POWER`       | `ON` / `OFF`                     |
+//! | `stat/<topic>/RESULT`      | `{"POWER":"ON","Color":"FF0000"}` |
+//!
+//! Kommando-Namen sind wie bei Tasmota unabhängig von Groß-/Kleinschreibung,
+//! der Index `1` (`POWER1`, `Color1`) wird akzeptiert.
+
+use rgb::RGB8;
+
+use crate::command::CommandParseError;
+
+/// Topic-Präfix für Kommandos
+pub const TASMOTA_COMMAND_PREFIX: &str = "cmnd/";
+
+/// Topic-Präfix für Status-Antworten
+pub const TASMOTA_STAT_PREFIX: &str = "stat/";
+
+/// Antwort auf `stat/<topic>/RESULT`, wenn die Kommando-Warteschlange voll ist
+pub const TASMOTA_BUSY_RESULT: &str = r#"{"Command":"Busy"}"#;
+
+/// Aktion des POWER-Kommandos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerAction {
+    On,
+    Off,
+    Toggle,
+    /// Leerer Payload: nur aktuellen Zustand melden
+    Query,
+}
+
+/// Dekodiertes Tasmota-Kommando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TasmotaCommand {
+    Power(PowerAction),
+    /// `None` = aktuelle Farbe abfragen
+    Color(Option<RGB8>),
+}
+
+/// Parst ein Tasmota-Kommando aus Kommando-Name und Payload
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::tasmota::{PowerAction, TasmotaCommand, parse_tasmota_command};
+/// assert_eq!(
+///     parse_tasmota_command("POWER", "toggle"),
+///     Ok(TasmotaCommand::Power(PowerAction::Toggle))
+/// );
+/// ```
+pub fn parse_tasmota_command(
+    command: &str,
+    payload: &str,
+) -> Result<TasmotaCommand, CommandParseError> {
+    let payload = payload.trim();
+    let is = |name: &str| {
+        command.eq_ignore_ascii_case(name)
+            || command
+                .strip_suffix('1')
+                .is_some_and(|base| base.eq_ignore_ascii_case(name))
+    };
+
+    if is("power") {
+        let action = if payload.is_empty() {
+            PowerAction::Query
+        } else if payload.eq_ignore_ascii_case("on") || payload == "1" {
+            PowerAction::On
+        } else if payload.eq_ignore_ascii_case("off") || payload == "0" {
+            PowerAction::Off
+        } else if payload.eq_ignore_ascii_case("toggle") || payload == "2" {
+            PowerAction::Toggle
+        } else {
+            return Err(CommandParseError::InvalidArgument);
+        };
+        return Ok(TasmotaCommand::Power(action));
+    }
+    if is("color") {
+        if payload.is_empty() {
+            return Ok(TasmotaCommand::Color(None));
+        }
+        let color = parse_color(payload).ok_or(CommandParseError::InvalidArgument)?;
+        return Ok(TasmotaCommand::Color(Some(color)));
+    }
+    Err(CommandParseError::Unknown)
+}
+
+/// Extrahiert den Kommando-Namen aus `cmnd/<topic>/<Kommando>`
+///
+/// Gibt `None` zurück, wenn das Topic nicht zum Geräte-Topic passt.
+pub fn tasmota_command_name<'a>(topic: &'a str, device_topic: &str) -> Option<&'a str> {
+    topic
+        .strip_prefix(TASMOTA_COMMAND_PREFIX)?
+        .strip_prefix(device_topic)?
+        .strip_prefix('/')
+        .filter(|name| !name.is_empty() && !name.contains('/'))
+}
+
+/// Parst eine Farbe als `RRGGBB`, `#RRGGBB` oder `r,g,b`
+pub(crate) fn parse_color(input: &str) -> Option<RGB8> {
+    if input.contains(',') {
+        let mut parts = input.split(',').map(|part| part.trim().parse::<u8>());
+        let (Some(Ok(r)), Some(Ok(g)), Some(Ok(b)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        return Some(RGB8::new(r, g, b));
+    }
+    let hex = input.strip_prefix('#').unwrap_or(input);
+    if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(RGB8::new(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Formatiert eine Farbe als Tasmota-Hex-String (`RRGGBB`, Großbuchstaben)
+pub fn format_hex_color(color: RGB8) -> [u8; 6] {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut out = [0u8; 6];
+    for (chunk, value) in out
+        .as_chunks_mut::<2>()
+        .0
+        .iter_mut()
+        .zip([color.r, color.g, color.b])
+    {
+        chunk[0] = DIGITS[(value >> 4) as usize];
+        chunk[1] = DIGITS[(value & 0x0F) as usize];
+    }
+    out
+}
+
+/// POWER-Zustand einer Farbe (Schwarz = `OFF`)
+pub fn power_state(color: RGB8) -> &'static str {
+    if color == RGB8::default() {
+        "OFF"
+    } else {
+        "ON"
+    }
+}
@@ -0,0 +1,71 @@
+//! Testmuster für die Inbetriebnahme
+//!
+//! Ein heller Pixel läuft einmal den Strip entlang (tote Pixel, Verkabelung),
+//! danach leuchten alle Pixel nacheinander Rot, Grün und Blau (Kanal-Reihenfolge).
+//! Wie die Verläufe in [`crate::transition`] eine reine Funktion der Zeit.
+
+use rgb::RGB8;
+
+use crate::color::scale_brightness;
+
+/// Anzeigedauer eines Schritts (Pixel-Position bzw. Farbe)
+pub const TEST_PATTERN_STEP_MS: u32 = 500;
+
+/// Farben für alle Pixel nach dem Lauflicht
+const FILL_COLORS: [RGB8; 3] = [
+    RGB8::new(255, 0, 0),
+    RGB8::new(0, 255, 0),
+    RGB8::new(0, 0, 255),
+];
+
+/// Abschnitt des Testmusters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPatternPhase {
+    /// Nur der Pixel mit diesem Index leuchtet (weiß)
+    Walk(usize),
+    /// Alle Pixel in dieser Farbe
+    Fill(RGB8),
+}
+
+/// Testmuster ab `start_ms`, wiederholt sich bis zum nächsten Kommando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestPattern {
+    pub start_ms: u64,
+    /// Helligkeit der Testfarben (255 = volle Helligkeit)
+    pub brightness: u8,
+}
+
+impl TestPattern {
+    pub const fn new(start_ms: u64, brightness: u8) -> Self {
+        Self {
+            start_ms,
+            brightness,
+        }
+    }
+
+    /// Abschnitt zum Zeitpunkt `now_ms` für einen Strip mit `led_count` Pixeln
+    pub fn phase_at(&self, now_ms: u64, led_count: usize) -> TestPatternPhase {
+        let steps = (led_count + FILL_COLORS.len()) as u64;
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        let step = (elapsed / TEST_PATTERN_STEP_MS as u64 % steps) as usize;
+        match step.checked_sub(led_count) {
+            None => TestPatternPhase::Walk(step),
+            Some(fill) => TestPatternPhase::Fill(FILL_COLORS[fill]),
+        }
+    }
+
+    /// Füllt `pixels` mit dem Bild zum Zeitpunkt `now_ms`
+    pub fn fill(&self, now_ms: u64, pixels: &mut [RGB8]) {
+        match self.phase_at(now_ms, pixels.len()) {
+            TestPatternPhase::Walk(index) => {
+                let white = scale_brightness(RGB8::new(255, 255, 255), self.brightness);
+                for (i, pixel) in pixels.iter_mut().enumerate() {
+                    *pixel = if i == index { white } else { RGB8::default() };
+                }
+            }
+            TestPatternPhase::Fill(color) => {
+                pixels.fill(scale_brightness(color, self.brightness));
+            }
+        }
+    }
+}
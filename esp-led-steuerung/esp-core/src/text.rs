@@ -0,0 +1,219 @@
+//! Lauftext auf einer LED-Matrix
+//!
+//! Kleine 5x7-Bitmap-Schrift (Ziffern, Großbuchstaben, Umlaute, einige
+//! Satzzeichen; Kleinbuchstaben werden groß dargestellt) und ein Lauftext,
+//! der von rechts herein- und links hinausläuft und sich dann wiederholt.
+//! Wie die Verläufe in [`crate::transition`] eine reine Funktion der Zeit.
+
+use rgb::RGB8;
+
+use crate::matrix::MatrixLayout;
+
+/// Maximale Länge einer Lauftext-Nachricht in Bytes (UTF-8)
+pub const TEXT_LEN: usize = 32;
+
+/// Höhe der Schrift in Pixeln
+pub const FONT_HEIGHT: usize = 7;
+
+/// Breite eines Zeichens in Pixeln
+pub const GLYPH_WIDTH: usize = 5;
+
+/// Spaltenabstand pro Zeichen (Zeichen + eine leere Spalte)
+pub const GLYPH_ADVANCE: usize = GLYPH_WIDTH + 1;
+
+/// Nachricht für den Lauftext (fest allokiert, damit `LedCommand` `Copy` bleibt)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TextMessage {
+    len: u8,
+    bytes: [u8; TEXT_LEN],
+}
+
+impl TextMessage {
+    /// `None` bei leerer oder zu langer Nachricht
+    pub const fn new(text: &str) -> Option<Self> {
+        let src = text.as_bytes();
+        if src.is_empty() || src.len() > TEXT_LEN {
+            return None;
+        }
+        let mut bytes = [0; TEXT_LEN];
+        let mut i = 0;
+        while i < src.len() {
+            bytes[i] = src[i];
+            i += 1;
+        }
+        Some(Self {
+            len: src.len() as u8,
+            bytes,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Nur aus `&str` befüllt, daher immer gültiges UTF-8
+        core::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or("")
+    }
+}
+
+impl core::fmt::Debug for TextMessage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TextMessage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TextMessage {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_util::deserialize_str(deserializer, "text of 1 to 32 bytes", Self::new)
+    }
+}
+
+/// Spalten eines Zeichens, Bit 0 = oberste Zeile
+///
+/// Unbekannte Zeichen werden als `?` dargestellt.
+pub fn glyph(c: char) -> [u8; GLYPH_WIDTH] {
+    match c.to_ascii_uppercase() {
+        ' ' => [0x00, 0x00, 0x00, 0x00, 0x00],
+        '!' => [0x00, 0x00, 0x5F, 0x00, 0x00],
+        '"' => [0x00, 0x07, 0x00, 0x07, 0x00],
+        '%' => [0x23, 0x13, 0x08, 0x64, 0x62],
+        '\'' => [0x00, 0x05, 0x03, 0x00, 0x00],
+        '(' => [0x00, 0x1C, 0x22, 0x41, 0x00],
+        ')' => [0x00, 0x41, 0x22, 0x1C, 0x00],
+        '+' => [0x08, 0x08, 0x3E, 0x08, 0x08],
+        ',' => [0x00, 0x50, 0x30, 0x00, 0x00],
+        '-' => [0x08, 0x08, 0x08, 0x08, 0x08],
+        '.' => [0x00, 0x60, 0x60, 0x00, 0x00],
+        '/' => [0x20, 0x10, 0x08, 0x04, 0x02],
+        '0' => [0x3E, 0x51, 0x49, 0x45, 0x3E],
+        '1' => [0x00, 0x42, 0x7F, 0x40, 0x00],
+        '2' => [0x42, 0x61, 0x51, 0x49, 0x46],
+        '3' => [0x21, 0x41, 0x45, 0x4B, 0x31],
+        '4' => [0x18, 0x14, 0x12, 0x7F, 0x10],
+        '5' => [0x27, 0x45, 0x45, 0x45, 0x39],
+        '6' => [0x3C, 0x4A, 0x49, 0x49, 0x30],
+        '7' => [0x01, 0x71, 0x09, 0x05, 0x03],
+        '8' => [0x36, 0x49, 0x49, 0x49, 0x36],
+        '9' => [0x06, 0x49, 0x49, 0x29, 0x1E],
+        ':' => [0x00, 0x36, 0x36, 0x00, 0x00],
+        'A' => [0x7E, 0x11, 0x11, 0x11, 0x7E],
+        'B' => [0x7F, 0x49, 0x49, 0x49, 0x36],
+        'C' => [0x3E, 0x41, 0x41, 0x41, 0x22],
+        'D' => [0x7F, 0x41, 0x41, 0x22, 0x1C],
+        'E' => [0x7F, 0x49, 0x49, 0x49, 0x41],
+        'F' => [0x7F, 0x09, 0x09, 0x01, 0x01],
+        'G' => [0x3E, 0x41, 0x41, 0x51, 0x32],
+        'H' => [0x7F, 0x08, 0x08, 0x08, 0x7F],
+        'I' => [0x00, 0x41, 0x7F, 0x41, 0x00],
+        'J' => [0x20, 0x40, 0x41, 0x3F, 0x01],
+        'K' => [0x7F, 0x08, 0x14, 0x22, 0x41],
+        'L' => [0x7F, 0x40, 0x40, 0x40, 0x40],
+        'M' => [0x7F, 0x02, 0x04, 0x02, 0x7F],
+        'N' => [0x7F, 0x04, 0x08, 0x10, 0x7F],
+        'O' => [0x3E, 0x41, 0x41, 0x41, 0x3E],
+        'P' => [0x7F, 0x09, 0x09, 0x09, 0x06],
+        'Q' => [0x3E, 0x41, 0x51, 0x21, 0x5E],
+        'R' => [0x7F, 0x09, 0x19, 0x29, 0x46],
+        'S' => [0x46, 0x49, 0x49, 0x49, 0x31],
+        'T' => [0x01, 0x01, 0x7F, 0x01, 0x01],
+        'U' => [0x3F, 0x40, 0x40, 0x40, 0x3F],
+        'V' => [0x1F, 0x20, 0x40, 0x20, 0x1F],
+        'W' => [0x7F, 0x20, 0x18, 0x20, 0x7F],
+        'X' => [0x63, 0x14, 0x08, 0x14, 0x63],
+        'Y' => [0x03, 0x04, 0x78, 0x04, 0x03],
+        'Z' => [0x61, 0x51, 0x49, 0x45, 0x43],
+        'Ä' | 'ä' => [0x7D, 0x12, 0x11, 0x12, 0x7D],
+        'Ö' | 'ö' => [0x3D, 0x42, 0x42, 0x42, 0x3D],
+        'Ü' | 'ü' => [0x3D, 0x40, 0x40, 0x40, 0x3D],
+        _ => [0x02, 0x01, 0x51, 0x09, 0x06],
+    }
+}
+
+/// Breite eines Textes in Spalten (inklusive Abstand nach jedem Zeichen)
+pub fn text_width(text: &str) -> usize {
+    text.chars().count() * GLYPH_ADVANCE
+}
+
+/// Bits der Spalte `column` eines Textes (Bit 0 = oberste Zeile)
+pub fn text_column(text: &str, column: usize) -> u8 {
+    let offset = column % GLYPH_ADVANCE;
+    match text.chars().nth(column / GLYPH_ADVANCE) {
+        Some(c) if offset < GLYPH_WIDTH => glyph(c)[offset],
+        _ => 0,
+    }
+}
+
+/// Lauftext ab `start_ms`, wiederholt sich bis zum nächsten Kommando
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollingText {
+    pub start_ms: u64,
+    pub color: RGB8,
+    /// Zeit pro Spalte (kleiner = schneller)
+    pub step_ms: u32,
+}
+
+impl ScrollingText {
+    pub const fn new(start_ms: u64, color: RGB8, step_ms: u32) -> Self {
+        Self {
+            start_ms,
+            color,
+            step_ms,
+        }
+    }
+
+    /// Um wie viele Spalten der Text zum Zeitpunkt `now_ms` gelaufen ist
+    ///
+    /// Ein Durchlauf umfasst `text_width + width` Spalten: der Text startet
+    /// rechts außerhalb der Matrix und endet links außerhalb.
+    pub fn offset_at(&self, now_ms: u64, text_width: usize, width: usize) -> usize {
+        let cycle = (text_width + width).max(1) as u64;
+        let elapsed = now_ms.saturating_sub(self.start_ms);
+        (elapsed / u64::from(self.step_ms.max(1)) % cycle) as usize
+    }
+
+    /// Füllt `pixels` mit dem Bild zum Zeitpunkt `now_ms`
+    ///
+    /// Die Schrift wird vertikal zentriert; bei Matrizen mit weniger als
+    /// [`FONT_HEIGHT`] Zeilen ist nur der mittlere Teil sichtbar.
+    pub fn render(&self, now_ms: u64, text: &str, layout: MatrixLayout, pixels: &mut [RGB8]) {
+        pixels.fill(RGB8::default());
+        let offset = self.offset_at(now_ms, text_width(text), layout.width);
+        let left = layout.width as isize - offset as isize;
+        let top = (layout.height as isize - FONT_HEIGHT as isize) / 2;
+        draw_text(text, (left, top), |_| self.color, layout, pixels);
+    }
+}
+
+/// Zeichnet `text` mit der linken oberen Ecke bei `origin` (Spalte, Zeile)
+///
+/// `color` liefert die Farbe pro Zeichen-Index. Pixel außerhalb der Matrix
+/// werden übersprungen, der Rest von `pixels` bleibt unverändert.
+pub fn draw_text(
+    text: &str,
+    origin: (isize, isize),
+    color: impl Fn(usize) -> RGB8,
+    layout: MatrixLayout,
+    pixels: &mut [RGB8],
+) {
+    let (left, top) = origin;
+    for (i, c) in text.chars().enumerate() {
+        let x0 = left + (i * GLYPH_ADVANCE) as isize;
+        for (dx, bits) in glyph(c).into_iter().enumerate() {
+            for row in (0..FONT_HEIGHT).filter(|row| bits & (1 << row) != 0) {
+                let (x, y) = (x0 + dx as isize, top + row as isize);
+                if x < 0 || y < 0 {
+                    continue;
+                }
+                let index = layout.index(x as usize, y as usize);
+                if let Some(pixel) = index.and_then(|index| pixels.get_mut(index)) {
+                    *pixel = color(i);
+                }
+            }
+        }
+    }
+}
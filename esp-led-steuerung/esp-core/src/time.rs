@@ -0,0 +1,198 @@
+//! Kalender- und Uhrzeit-Berechnungen
+//!
+//! Wandelt Unix-Timestamps (UTC) in lokale Uhrzeit um.
+//! Keine Hardware-Dependencies, keine Zeitzonen-Datenbank:
+//! feste UTC-Offsets plus optionale EU-Sommerzeit-Regel.
+
+/// Sekunden pro Tag
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Frühester plausibler Unix-Zeitpunkt (2024-01-01 00:00:00 UTC)
+///
+/// Kleinere Werte stammen von einer nie gestellten Uhr, z.B. der RTC nach
+/// einem Stromausfall (sie zählt dann ab 0).
+pub const MIN_PLAUSIBLE_UNIX_SECS: u64 = 1_704_067_200;
+
+/// Herkunft der aktuellen Wall-Clock-Zeit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeSource {
+    /// Von einem NTP-Server synchronisiert
+    Sntp,
+    /// Aus der RTC übernommen (nach Neustart oder Aufwachen, vor dem nächsten SNTP-Abgleich)
+    Rtc,
+}
+
+impl TimeSource {
+    /// Kurzname für Logs
+    pub fn name(self) -> &'static str {
+        match self {
+            TimeSource::Sntp => "sntp",
+            TimeSource::Rtc => "rtc",
+        }
+    }
+}
+
+/// Prüft eine aus der RTC gelesene Unix-Zeit (ms)
+///
+/// Gibt die Zeit zurück, wenn sie vor dem Neustart gestellt wurde, sonst `None`.
+///
+/// # Beispiele
+///
+/// ```
+/// # use esp_core::time::restored_unix_ms;
+/// // Nach einem Stromausfall: RTC zählt ab 0
+/// assert_eq!(restored_unix_ms(12_000), None);
+/// assert_eq!(restored_unix_ms(1_720_000_000_000), Some(1_720_000_000_000));
+/// ```
+pub fn restored_unix_ms(rtc_unix_ms: u64) -> Option<u64> {
+    (rtc_unix_ms / 1000 >= MIN_PLAUSIBLE_UNIX_SECS).then_some(rtc_unix_ms)
+}
+
+/// Wochentag (Montag = 0 … Sonntag = 6)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Wochentag aus Index (0 = Montag), Werte ≥ 7 werden modulo 7 gerechnet
+    pub fn from_index(index: u8) -> Self {
+        match index % 7 {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    /// Index des Wochentags (Montag = 0)
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Lokale Uhrzeit (Wochentag + Tageszeit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalTime {
+    pub weekday: Weekday,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+/// Zeitzonen-Konfiguration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeZone {
+    /// Offset zu UTC in Minuten (z.B. 60 für MEZ)
+    pub utc_offset_minutes: i32,
+    /// EU-Sommerzeit anwenden (+1h zwischen letztem Sonntag im März und Oktober)
+    pub eu_dst: bool,
+}
+
+impl TimeZone {
+    /// Mitteleuropäische Zeit (MEZ/MESZ)
+    pub const CET: TimeZone = TimeZone {
+        utc_offset_minutes: 60,
+        eu_dst: true,
+    };
+
+    /// Koordinierte Weltzeit ohne Sommerzeit
+    pub const UTC: TimeZone = TimeZone {
+        utc_offset_minutes: 0,
+        eu_dst: false,
+    };
+
+    /// Effektiver Offset in Sekunden für einen UTC-Zeitpunkt
+    pub fn offset_secs(&self, unix_utc: u64) -> i64 {
+        let dst = if self.eu_dst && eu_dst_active(unix_utc) {
+            3600
+        } else {
+            0
+        };
+        self.utc_offset_minutes as i64 * 60 + dst
+    }
+
+    /// Lokale Sekunden seit Epoch (UTC + Offset)
+    pub fn local_secs(&self, unix_utc: u64) -> u64 {
+        (unix_utc as i64 + self.offset_secs(unix_utc)).max(0) as u64
+    }
+}
+
+/// Wandelt einen Unix-Timestamp in lokale Uhrzeit um
+///
+/// # Beispiele
+///
+/// ```
+/// # use esp_core::time::{local_time, TimeZone, Weekday};
+/// // 2024-01-01 00:00:00 UTC war ein Montag
+/// let t = local_time(1_704_067_200, TimeZone::UTC);
+/// assert_eq!(t.weekday, Weekday::Monday);
+/// assert_eq!((t.hour, t.minute), (0, 0));
+/// ```
+pub fn local_time(unix_utc: u64, tz: TimeZone) -> LocalTime {
+    let local = tz.local_secs(unix_utc);
+    let days = local / SECS_PER_DAY;
+    let secs_of_day = local % SECS_PER_DAY;
+    LocalTime {
+        // 1970-01-01 war ein Donnerstag (Index 3)
+        weekday: Weekday::from_index(((days + 3) % 7) as u8),
+        hour: (secs_of_day / 3600) as u8,
+        minute: (secs_of_day % 3600 / 60) as u8,
+        second: (secs_of_day % 60) as u8,
+    }
+}
+
+/// Kalenderdatum (Jahr, Monat 1-12, Tag 1-31) aus Tagen seit 1970-01-01
+///
+/// Algorithmus nach Howard Hinnant ("civil_from_days").
+pub fn civil_from_days(days: u64) -> (u32, u8, u8) {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as u32;
+    (year, month, day)
+}
+
+/// Tage seit 1970-01-01 für ein Kalenderdatum (Umkehrung von `civil_from_days`)
+pub fn days_from_civil(year: u32, month: u8, day: u8) -> u64 {
+    let y = year as i64 - if month <= 2 { 1 } else { 0 };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    (era * 146_097 + doe - 719_468) as u64
+}
+
+/// Tag des letzten Sonntags eines Monats (in Tagen seit Epoch)
+fn last_sunday(year: u32, month: u8, days_in_month: u8) -> u64 {
+    let last_day = days_from_civil(year, month, days_in_month);
+    // Wochentag-Index mit Montag = 0, Sonntag = 6
+    let weekday = (last_day + 3) % 7;
+    last_day - (weekday + 1) % 7
+}
+
+/// Prüft ob die EU-Sommerzeit für einen UTC-Zeitpunkt aktiv ist
+///
+/// Sommerzeit gilt vom letzten Sonntag im März 01:00 UTC
+/// bis zum letzten Sonntag im Oktober 01:00 UTC.
+pub fn eu_dst_active(unix_utc: u64) -> bool {
+    let (year, _, _) = civil_from_days(unix_utc / SECS_PER_DAY);
+    let start = last_sunday(year, 3, 31) * SECS_PER_DAY + 3600;
+    let end = last_sunday(year, 10, 31) * SECS_PER_DAY + 3600;
+    (start..end).contains(&unix_utc)
+}
@@ -0,0 +1,184 @@
+//! Ablauf-Tracing: Ein- und Austrittszeit ausgewählter Task-Abschnitte
+//!
+//! Ruckeln Animationen unter Netzwerklast, zeigt der Trace, welcher Abschnitt
+//! wie lange lief und ob sich Abschnitte verschiedener Tasks überlappen (z.B.
+//! ein langer MQTT-Publish zwischen zwei LED-Frames). Die Firmware misst nur
+//! bei eingeschaltetem Tracing (Konsole `trace on`) und legt jeden beendeten
+//! Abschnitt als [`Span`] im [`TraceRing`] ab; die ältesten werden
+//! überschrieben. [`TraceRing::summary`] fasst alle Abschnitte seit dem
+//! Einschalten zusammen, auch bereits überschriebene.
+//!
+//! Zeiten in Mikrosekunden seit Boot.
+
+/// Gemessene Abschnitte
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanKind {
+    /// LED-Task: Frame berechnen und schreiben (`LedLoop::step`)
+    LedFrame,
+    /// MQTT-Task: Farbe, Modus und Zustand publishen
+    MqttPublish,
+    /// MQTT-Task: empfangene Nachricht auswerten und quittieren
+    MqttCommand,
+    /// HTTP-Task: Zustand an einen WebSocket-Client senden
+    WsSend,
+    /// HTTP-Task: empfangene WebSocket-Nachricht auswerten
+    WsCommand,
+    /// Realtime-Task: UDP-Frame dekodieren und weiterreichen
+    Realtime,
+    /// Konsole: Befehl ausführen
+    Console,
+}
+
+impl SpanKind {
+    /// Anzahl Abschnitte (Größe von [`TraceSummary`])
+    pub const COUNT: usize = 7;
+
+    /// Alle Abschnitte in Index-Reihenfolge
+    pub const ALL: [SpanKind; Self::COUNT] = [
+        SpanKind::LedFrame,
+        SpanKind::MqttPublish,
+        SpanKind::MqttCommand,
+        SpanKind::WsSend,
+        SpanKind::WsCommand,
+        SpanKind::Realtime,
+        SpanKind::Console,
+    ];
+
+    /// Kurzname für die Konsole
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpanKind::LedFrame => "led_frame",
+            SpanKind::MqttPublish => "mqtt_publish",
+            SpanKind::MqttCommand => "mqtt_command",
+            SpanKind::WsSend => "ws_send",
+            SpanKind::WsCommand => "ws_command",
+            SpanKind::Realtime => "realtime",
+            SpanKind::Console => "console",
+        }
+    }
+}
+
+/// Ein beendeter Abschnitt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub kind: SpanKind,
+    /// Eintritt (µs seit Boot)
+    pub start_us: u64,
+    /// Austritt (µs seit Boot)
+    pub end_us: u64,
+}
+
+impl Span {
+    const EMPTY: Self = Self {
+        kind: SpanKind::LedFrame,
+        start_us: 0,
+        end_us: 0,
+    };
+
+    /// Dauer des Abschnitts (inkl. Wartezeiten auf `.await`)
+    pub fn duration_us(&self) -> u64 {
+        self.end_us.saturating_sub(self.start_us)
+    }
+}
+
+/// Zusammenfassung eines Abschnitts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SpanStats {
+    pub count: u32,
+    pub total_us: u64,
+    pub max_us: u64,
+}
+
+impl SpanStats {
+    /// Mittlere Dauer, 0 ohne Messung
+    pub fn avg_us(&self) -> u64 {
+        self.total_us
+            .checked_div(u64::from(self.count))
+            .unwrap_or(0)
+    }
+}
+
+/// Zusammenfassung aller Abschnitte, Index = [`SpanKind`]
+pub type TraceSummary = [SpanStats; SpanKind::COUNT];
+
+/// Ringpuffer der letzten `N` Abschnitte
+#[derive(Debug, Clone)]
+pub struct TraceRing<const N: usize> {
+    spans: [Span; N],
+    /// Index des ältesten Abschnitts
+    head: usize,
+    len: usize,
+    summary: TraceSummary,
+}
+
+impl<const N: usize> TraceRing<N> {
+    pub const fn new() -> Self {
+        Self {
+            spans: [Span::EMPTY; N],
+            head: 0,
+            len: 0,
+            summary: [SpanStats {
+                count: 0,
+                total_us: 0,
+                max_us: 0,
+            }; SpanKind::COUNT],
+        }
+    }
+
+    /// Legt einen beendeten Abschnitt ab, überschreibt bei vollem Puffer den ältesten
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::{Span, SpanKind, TraceRing};
+    /// let mut ring: TraceRing<8> = TraceRing::new();
+    /// ring.push(Span { kind: SpanKind::LedFrame, start_us: 1_000, end_us: 1_800 });
+    /// ring.push(Span { kind: SpanKind::LedFrame, start_us: 21_000, end_us: 22_200 });
+    /// let stats = ring.summary()[SpanKind::LedFrame as usize];
+    /// assert_eq!((stats.count, stats.avg_us(), stats.max_us), (2, 1_000, 1_200));
+    /// ```
+    pub fn push(&mut self, span: Span) {
+        let index = (self.head + self.len) % N;
+        if self.len == N {
+            self.head = (self.head + 1) % N;
+        } else {
+            self.len += 1;
+        }
+        self.spans[index] = span;
+
+        let duration_us = span.duration_us();
+        let stats = &mut self.summary[span.kind as usize];
+        stats.count = stats.count.saturating_add(1);
+        stats.total_us = stats.total_us.saturating_add(duration_us);
+        stats.max_us = stats.max_us.max(duration_us);
+    }
+
+    /// Verwirft alle Abschnitte und die Zusammenfassung
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Anzahl gespeicherter Abschnitte
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gespeicherte Abschnitte in Reihenfolge ihres Austritts, älteste zuerst
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Span> {
+        (0..self.len).map(move |offset| &self.spans[(self.head + offset) % N])
+    }
+
+    /// Anzahl, Summe und Maximum je Abschnitt seit dem letzten `clear()`
+    pub fn summary(&self) -> &TraceSummary {
+        &self.summary
+    }
+}
+
+impl<const N: usize> Default for TraceRing<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -5,6 +5,10 @@
 
 use rgb::RGB8;
 
+use crate::device_config::{ColorOrder, StripMapping};
+use crate::preset::Preset;
+use crate::types::{LedColorMessage, LedCommand};
+
 /// Fehler-Typ für LED-Operationen
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LedError {
@@ -24,4 +28,116 @@ pub trait SmartLedWriter: Send {
     /// # Fehlerbehandlung
     /// Gibt `LedError::WriteFailed` zurück wenn Hardware-Zugriff fehlschlägt
     fn write(&mut self, color: RGB8) -> Result<(), LedError>;
+
+    /// Schreibt eine Farbe pro LED (z.B. für Realtime-Streaming)
+    ///
+    /// Standard-Implementierung für Single-LED-Hardware: nur das erste Pixel
+    /// wird geschrieben, ein leerer Frame ist ein No-Op.
+    fn write_frame(&mut self, pixels: &[RGB8]) -> Result<(), LedError> {
+        match pixels.first() {
+            Some(&color) => self.write(color),
+            None => Ok(()),
+        }
+    }
+
+    /// Setzt die Reihenfolge der Farbkanäle auf der Datenleitung
+    ///
+    /// Standard-Implementierung ohne Wirkung (z.B. Mocks, die nur Farben aufzeichnen).
+    fn set_color_order(&mut self, _order: ColorOrder) {}
+
+    /// Setzt die Zuordnung logischer zu physischen Pixeln (für `write_frame`)
+    ///
+    /// Standard-Implementierung ohne Wirkung (Single-LED-Hardware, Mocks).
+    fn set_strip_mapping(&mut self, _mapping: StripMapping) {}
+}
+
+/// Trait für eine monotone Zeitquelle in Millisekunden
+///
+/// Zeitabhängige Logik (Verläufe, Effekte, Entprellung) fragt die Zeit über
+/// diesen Trait ab, damit Tests die Zeit deterministisch vorgeben können.
+///
+/// # Implementierungen
+/// - **Production:** EmbassyClock (embassy-time, Millisekunden seit Boot)
+/// - **Testing:** MockClock (manuell vorgestellte Zeit)
+pub trait Clock {
+    /// Millisekunden seit einem beliebigen, festen Startpunkt (monoton steigend)
+    fn now_ms(&self) -> u64;
+
+    /// Mikrosekunden für Laufzeitmessungen (Standard: aus `now_ms()` abgeleitet)
+    fn now_us(&self) -> u64 {
+        self.now_ms().saturating_mul(1000)
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now_ms(&self) -> u64 {
+        (**self).now_ms()
+    }
+
+    fn now_us(&self) -> u64 {
+        (**self).now_us()
+    }
+}
+
+/// Trait für das Verteilen von Farb-Updates an andere Komponenten
+///
+/// # Implementierungen
+/// - **Production:** PublisherSink (embassy PubSubChannel, Broadcast an alle Tasks)
+/// - **Testing:** Vec-basierter Mock (sammelt alle Nachrichten)
+pub trait ColorSink {
+    /// Veröffentlicht eine Farb-Nachricht (darf nicht blockieren)
+    fn publish(&mut self, msg: LedColorMessage);
+}
+
+/// Trait für eingehende LED-Kommandos
+///
+/// # Implementierungen
+/// - **Production:** CommandReceiver (Prioritäts-Channel aller Quellen)
+/// - **Testing:** Vec-basierter Mock (vorgegebene Kommando-Folge)
+pub trait CommandSource {
+    /// Nächstes Kommando ohne Warten, `None` wenn keines ansteht
+    fn try_next(&mut self) -> Option<LedCommand>;
+}
+
+/// Trait für das Auflösen von Preset-IDs ([`LedCommand::ApplyPreset`])
+///
+/// # Implementierungen
+/// - **Production:** PresetStore (Mutex-geschützte Presets der Firmware)
+/// - **Testing:** [`Presets`](crate::preset::Presets) direkt
+/// - [`NoPresets`]: keine Presets (Standard der `LedLoop`)
+pub trait PresetSource {
+    /// Preset mit dieser ID, `None` wenn unbekannt
+    fn preset(&self, id: u8) -> Option<Preset>;
+}
+
+impl<P: PresetSource + ?Sized> PresetSource for &P {
+    fn preset(&self, id: u8) -> Option<Preset> {
+        (**self).preset(id)
+    }
+}
+
+/// Preset-Quelle ohne Presets
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoPresets;
+
+impl PresetSource for NoPresets {
+    fn preset(&self, _id: u8) -> Option<Preset> {
+        None
+    }
+}
+
+/// Trait für Zufallszahlen (z.B. Farbwahl im Zufallsmodus)
+///
+/// # Implementierungen
+/// - **Production:** HardwareRng (ESP32 Hardware-RNG)
+/// - **Testing/Simulator:** [`SeededRng`](crate::random::SeededRng) (reproduzierbar)
+pub trait Rng {
+    /// Nächste gleichverteilte 32-Bit-Zahl
+    fn next_u32(&mut self) -> u32;
+}
+
+impl<R: Rng + ?Sized> Rng for &mut R {
+    fn next_u32(&mut self) -> u32 {
+        (**self).next_u32()
+    }
 }
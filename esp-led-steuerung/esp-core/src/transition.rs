@@ -0,0 +1,211 @@
+//! Transition-Engine: zeitbasierte Farbverläufe (Übergang, Helligkeit, Sonnenaufgang, Sleep-Timer)
+//!
+//! Alle Verläufe sind reine Funktionen der Zeit (Millisekunden seit einem
+//! beliebigen, monotonen Startpunkt). Der LED-Task fragt in jedem Durchlauf
+//! die aktuelle Farbe ab – kein interner Zustand, keine Timer.
+
+use rgb::RGB8;
+
+use crate::color::{PERMILLE_MAX, color_temperature, lerp_color, scale_brightness};
+
+/// Linearer Übergang zwischen zwei Farben
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub from: RGB8,
+    pub to: RGB8,
+    pub start_ms: u64,
+    pub duration_ms: u32,
+}
+
+impl Transition {
+    /// Erstellt einen Übergang ab `start_ms`
+    pub const fn new(from: RGB8, to: RGB8, start_ms: u64, duration_ms: u32) -> Self {
+        Self {
+            from,
+            to,
+            start_ms,
+            duration_ms,
+        }
+    }
+
+    /// Fortschritt in Promille (0..=1000)
+    pub fn progress(&self, now_ms: u64) -> u16 {
+        progress(self.start_ms, self.duration_ms, now_ms)
+    }
+
+    /// Farbe zum Zeitpunkt `now_ms`
+    pub fn color_at(&self, now_ms: u64) -> RGB8 {
+        lerp_color(self.from, self.to, self.progress(now_ms))
+    }
+
+    /// Prüft ob der Übergang abgeschlossen ist
+    pub fn is_finished(&self, now_ms: u64) -> bool {
+        self.progress(now_ms) >= PERMILLE_MAX
+    }
+}
+
+/// Gleitender Wechsel der Gesamthelligkeit (`LedCommand::SetBrightness`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrightnessRamp {
+    pub from: u8,
+    pub to: u8,
+    pub start_ms: u64,
+    pub duration_ms: u32,
+}
+
+impl BrightnessRamp {
+    /// Erstellt eine Rampe ab `start_ms`
+    pub const fn new(from: u8, to: u8, start_ms: u64, duration_ms: u32) -> Self {
+        Self {
+            from,
+            to,
+            start_ms,
+            duration_ms,
+        }
+    }
+
+    /// Helligkeit zum Zeitpunkt `now_ms`
+    pub fn level_at(&self, now_ms: u64) -> u8 {
+        let permille = progress(self.start_ms, self.duration_ms, now_ms) as i32;
+        let from = self.from as i32;
+        let to = self.to as i32;
+        (from + (to - from) * permille / PERMILLE_MAX as i32) as u8
+    }
+
+    /// Prüft ob die Rampe abgeschlossen ist
+    pub fn is_finished(&self, now_ms: u64) -> bool {
+        progress(self.start_ms, self.duration_ms, now_ms) >= PERMILLE_MAX
+    }
+}
+
+/// Fortschritt eines Zeitfensters in Promille (0..=1000)
+fn progress(start_ms: u64, duration_ms: u32, now_ms: u64) -> u16 {
+    if duration_ms == 0 {
+        return PERMILLE_MAX;
+    }
+    let elapsed = now_ms.saturating_sub(start_ms);
+    (elapsed.min(duration_ms as u64) * PERMILLE_MAX as u64 / duration_ms as u64) as u16
+}
+
+// ============================================================================
+// Sonnenaufgang (Lichtwecker)
+// ============================================================================
+
+/// Stützstellen des Sonnenaufgangs: (Fortschritt ‰, Farbtemperatur K, Helligkeit 0-255)
+///
+/// Beginnt mit gedimmtem Dunkelrot, geht über Orange zu warmem Weiß.
+const SUNRISE_KEYFRAMES: [(u16, u16, u8); 4] = [
+    (0, 1000, 2),
+    (300, 1500, 60),
+    (700, 2500, 170),
+    (1000, 3000, 255),
+];
+
+/// Sonnenaufgangs-Verlauf von Dunkelrot bis warmweiß
+///
+/// `max_brightness` begrenzt die Endhelligkeit (alle Stützstellen werden skaliert).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sunrise {
+    pub start_ms: u64,
+    pub duration_ms: u32,
+    pub max_brightness: u8,
+}
+
+impl Sunrise {
+    /// Erstellt einen Sonnenaufgang ab `start_ms`
+    pub const fn new(start_ms: u64, duration_ms: u32, max_brightness: u8) -> Self {
+        Self {
+            start_ms,
+            duration_ms,
+            max_brightness,
+        }
+    }
+
+    /// Farbe zum Zeitpunkt `now_ms`
+    pub fn color_at(&self, now_ms: u64) -> RGB8 {
+        let progress = progress(self.start_ms, self.duration_ms, now_ms);
+        let segment = SUNRISE_KEYFRAMES
+            .windows(2)
+            .find(|pair| progress <= pair[1].0)
+            .unwrap_or(&SUNRISE_KEYFRAMES[2..]);
+        let (from_at, ..) = segment[0];
+        let (to_at, ..) = segment[1];
+        let local =
+            ((progress - from_at) as u32 * PERMILLE_MAX as u32 / (to_at - from_at) as u32) as u16;
+        lerp_color(
+            self.keyframe_color(segment[0]),
+            self.keyframe_color(segment[1]),
+            local,
+        )
+    }
+
+    /// Endfarbe des Sonnenaufgangs
+    pub fn final_color(&self) -> RGB8 {
+        self.keyframe_color(SUNRISE_KEYFRAMES[SUNRISE_KEYFRAMES.len() - 1])
+    }
+
+    /// Prüft ob der Sonnenaufgang abgeschlossen ist
+    pub fn is_finished(&self, now_ms: u64) -> bool {
+        progress(self.start_ms, self.duration_ms, now_ms) >= PERMILLE_MAX
+    }
+
+    fn keyframe_color(&self, (_, kelvin, brightness): (u16, u16, u8)) -> RGB8 {
+        let brightness = (brightness as u16 * self.max_brightness as u16 / 255) as u8;
+        scale_brightness(color_temperature(kelvin), brightness)
+    }
+}
+
+// ============================================================================
+// Sleep-Timer
+// ============================================================================
+
+/// Sleep-Timer: blendet die LED zum Ablaufzeitpunkt sanft aus
+///
+/// Bis zum Beginn der Ausblendphase bleibt die Farbe unverändert, danach
+/// wird linear bis Schwarz abgedunkelt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SleepTimer {
+    pub off_at_ms: u64,
+    pub fade_ms: u32,
+}
+
+impl SleepTimer {
+    /// Startet einen Timer der `duration_ms` nach `now_ms` abläuft
+    ///
+    /// Die Ausblendphase wird auf die Gesamtdauer begrenzt.
+    pub fn new(now_ms: u64, duration_ms: u32, fade_ms: u32) -> Self {
+        Self {
+            off_at_ms: now_ms + duration_ms as u64,
+            fade_ms: fade_ms.min(duration_ms),
+        }
+    }
+
+    /// Beginn der Ausblendphase
+    fn fade_start_ms(&self) -> u64 {
+        self.off_at_ms - self.fade_ms as u64
+    }
+
+    /// Wendet den Timer auf die aktuelle Farbe an
+    pub fn apply(&self, color: RGB8, now_ms: u64) -> RGB8 {
+        if now_ms < self.fade_start_ms() {
+            return color;
+        }
+        let permille = progress(self.fade_start_ms(), self.fade_ms, now_ms);
+        lerp_color(color, RGB8::default(), permille)
+    }
+
+    /// Prüft ob die Ausblendphase läuft
+    pub fn is_fading(&self, now_ms: u64) -> bool {
+        now_ms >= self.fade_start_ms() && !self.is_finished(now_ms)
+    }
+
+    /// Prüft ob der Timer abgelaufen ist
+    pub fn is_finished(&self, now_ms: u64) -> bool {
+        now_ms >= self.off_at_ms
+    }
+
+    /// Verbleibende Zeit in Millisekunden
+    pub fn remaining_ms(&self, now_ms: u64) -> u64 {
+        self.off_at_ms.saturating_sub(now_ms)
+    }
+}
@@ -4,47 +4,381 @@
 
 use rgb::RGB8;
 
+use crate::clock_face::ClockFormat;
+use crate::effect_param::ParamValue;
+use crate::gradient::Gradient;
+use crate::i18n::Language;
+use crate::sequence::ColorSequence;
+use crate::text::TextMessage;
+
+/// Identität einer benannten Farbe
+///
+/// Ersetzt die bisher als `&'static str` ("Rot", "Grün", ...) herumgereichten
+/// Farbnamen. Logik vergleicht nur noch `ColorId`, der Anzeigename kommt
+/// zentral aus [`ColorId::name`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorId {
+    #[cfg_attr(feature = "serde", serde(rename = "Rot", alias = "Red"))]
+    Red,
+    #[cfg_attr(feature = "serde", serde(rename = "Grün", alias = "Green"))]
+    Green,
+    #[cfg_attr(feature = "serde", serde(rename = "Blau", alias = "Blue"))]
+    Blue,
+    #[cfg_attr(feature = "serde", serde(rename = "Aus", alias = "Off"))]
+    Off,
+    /// Beliebige Farbe, keiner benannten Farbe nahe genug (siehe [`classify_color`])
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "Benutzerdefiniert", alias = "Custom", alias = "Unbekannt")
+    )]
+    Custom,
+}
+
+impl ColorId {
+    /// Kanonischer Name (Protokoll-Bezeichner in JSON, unabhängig von der Sprache)
+    ///
+    /// Lokalisierte Anzeigenamen liefert [`crate::i18n::Language::color_name`].
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorId::Red => "Rot",
+            ColorId::Green => "Grün",
+            ColorId::Blue => "Blau",
+            ColorId::Off => "Aus",
+            ColorId::Custom => "Benutzerdefiniert",
+        }
+    }
+
+    /// Gegenstück zu [`ColorId::name`] (`Custom` ist nicht wählbar)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Rot" => Some(ColorId::Red),
+            "Grün" => Some(ColorId::Green),
+            "Blau" => Some(ColorId::Blue),
+            "Aus" => Some(ColorId::Off),
+            _ => None,
+        }
+    }
+
+    /// RGB-Wert der Farbe bei gegebener Helligkeit (`None` für `Custom`)
+    pub fn color(self, brightness: u8) -> Option<RGB8> {
+        match self {
+            ColorId::Red => Some(RGB8::new(brightness, 0, 0)),
+            ColorId::Green => Some(RGB8::new(0, brightness, 0)),
+            ColorId::Blue => Some(RGB8::new(0, 0, brightness)),
+            ColorId::Off => Some(RGB8::default()),
+            ColorId::Custom => None,
+        }
+    }
+
+    /// `SetColor`-Kommando für eine Grundfarbe (`None` für `Off` und `Custom`)
+    pub fn command(self, brightness: u8) -> Option<LedCommand> {
+        match self {
+            ColorId::Off | ColorId::Custom => None,
+            _ => self
+                .color(brightness)
+                .map(|target_color| LedCommand::SetColor {
+                    target_color,
+                    color_id: self,
+                }),
+        }
+    }
+}
+
+/// Toleranz von [`classify_color`] in Prozent des hellsten Kanals
+pub const COLOR_TOLERANCE_PERCENT: u32 = 20;
+
+/// Ordnet eine Farbe der nächstgelegenen benannten Farbe zu
+///
+/// Unabhängig von der Helligkeit: verglichen wird mit den Grundfarben in
+/// der Helligkeit des hellsten Kanals (Summe der Kanal-Abstände). Liegt die
+/// nächste Grundfarbe mehr als [`COLOR_TOLERANCE_PERCENT`] davon entfernt,
+/// ist die Farbe `Custom`. Nur Schwarz ist `Off`, auch sehr dunkle Farben
+/// behalten ihren Namen (Grundhelligkeit 10/255).
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::{ColorId, classify_color};
+/// # use rgb::RGB8;
+/// assert_eq!(classify_color(RGB8::new(200, 30, 0)), ColorId::Red);
+/// assert_eq!(classify_color(RGB8::new(255, 128, 0)), ColorId::Custom);
+/// ```
+pub fn classify_color(color: RGB8) -> ColorId {
+    let level = color.r.max(color.g).max(color.b);
+    if level == 0 {
+        return ColorId::Off;
+    }
+    let distance = |reference: RGB8| {
+        color.r.abs_diff(reference.r) as u32
+            + color.g.abs_diff(reference.g) as u32
+            + color.b.abs_diff(reference.b) as u32
+    };
+    [ColorId::Red, ColorId::Green, ColorId::Blue]
+        .into_iter()
+        .filter_map(|id| id.color(level).map(|reference| (id, distance(reference))))
+        .min_by_key(|&(_, distance)| distance)
+        .filter(|&(_, distance)| distance * 100 <= COLOR_TOLERANCE_PERCENT * level as u32)
+        .map_or(ColorId::Custom, |(id, _)| id)
+}
+
+/// Maximale Länge eines Farb-Anzeigenamens in Bytes (passt für `RGB(255,255,255)`)
+pub const COLOR_LABEL_LEN: usize = 20;
+
+/// Dynamischer Anzeigename einer Farbe (Preset-Name, `RGB(12,34,56)`)
+///
+/// Fest allokiert, damit [`LedColorMessage`] `Copy` bleibt und ohne Heap
+/// durch die Channels geht.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ColorLabel {
+    len: u8,
+    bytes: [u8; COLOR_LABEL_LEN],
+}
+
+impl ColorLabel {
+    /// `None` bei leerem oder zu langem Namen
+    pub const fn new(name: &str) -> Option<Self> {
+        let src = name.as_bytes();
+        if src.is_empty() || src.len() > COLOR_LABEL_LEN {
+            return None;
+        }
+        let mut bytes = [0; COLOR_LABEL_LEN];
+        let mut i = 0;
+        while i < src.len() {
+            bytes[i] = src[i];
+            i += 1;
+        }
+        Some(Self {
+            len: src.len() as u8,
+            bytes,
+        })
+    }
+
+    /// Name aus den RGB-Werten, z.B. `RGB(12,34,56)`
+    pub fn rgb(color: RGB8) -> Self {
+        let mut label = Self {
+            len: 0,
+            bytes: [0; COLOR_LABEL_LEN],
+        };
+        // Längster Fall `RGB(255,255,255)` passt immer
+        let _ = core::fmt::write(
+            &mut label,
+            format_args!("RGB({},{},{})", color.r, color.g, color.b),
+        );
+        label
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Nur aus `&str` befüllt, daher immer gültiges UTF-8
+        core::str::from_utf8(&self.bytes[..usize::from(self.len)]).unwrap_or("")
+    }
+}
+
+impl core::fmt::Write for ColorLabel {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let start = usize::from(self.len);
+        let end = start + s.len();
+        if end > COLOR_LABEL_LEN {
+            return Err(core::fmt::Error);
+        }
+        self.bytes[start..end].copy_from_slice(s.as_bytes());
+        self.len = end as u8;
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for ColorLabel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ColorLabel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ColorLabel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_util::deserialize_str(deserializer, "label of 1 to 20 bytes", Self::new)
+    }
+}
+
 /// LED Color Message für Channel-Kommunikation
 ///
 /// Wird zwischen LED-Task und anderen Tasks ausgetauscht.
+///
+/// `sequence` und `timestamp_ms` vergibt der LED-Loop beim Publishen. Damit
+/// erkennen Empfänger verpasste Updates (PubSub-Queue übergelaufen) und
+/// veraltete Nachrichten.
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LedColorMessage {
     pub color: RGB8,
-    pub name: &'static str,
+    pub color_id: ColorId,
+    /// Eigener Anzeigename (z.B. Name des angewendeten Presets)
+    pub name: Option<ColorLabel>,
     pub is_auto_mode: bool,
+    /// Fortlaufende Nummer (steigt pro Broadcast um 1, mit Überlauf)
+    pub sequence: u32,
+    /// Zeitpunkt des Broadcasts in Millisekunden seit Boot
+    pub timestamp_ms: u64,
 }
 
 impl LedColorMessage {
     /// Erstellt eine LedColorMessage aus einer RGB8-Farbe und Modus
     ///
-    /// Die Funktion erkennt automatisch die Farbe basierend auf RGB-Werten.
+    /// Der Farbname kommt aus [`classify_color`].
     pub fn from_color(color: RGB8, is_auto_mode: bool) -> Self {
-        let name = match (color.r, color.g, color.b) {
-            (r, 0, 0) if r > 0 => "Rot",
-            (0, g, 0) if g > 0 => "Grün",
-            (0, 0, b) if b > 0 => "Blau",
-            _ => "Unbekannt",
-        };
         Self {
             color,
-            name,
+            color_id: classify_color(color),
+            name: None,
             is_auto_mode,
+            sequence: 0,
+            timestamp_ms: 0,
         }
     }
+
+    /// Versieht die Nachricht mit eigenem Anzeigenamen
+    pub fn with_name(self, name: Option<ColorLabel>) -> Self {
+        Self { name, ..self }
+    }
+
+    /// Anzeigename: eigener Name, sonst Farbname in `language`, für
+    /// `Custom` die RGB-Werte
+    pub fn display_name(&self, language: Language) -> ColorLabel {
+        if let Some(name) = self.name {
+            return name;
+        }
+        match self.color_id {
+            ColorId::Custom => ColorLabel::rgb(self.color),
+            id => ColorLabel::new(language.color_name(id)).unwrap_or(ColorLabel::rgb(self.color)),
+        }
+    }
+
+    /// Versieht die Nachricht mit Sequenznummer und Zeitstempel
+    pub fn stamped(self, sequence: u32, timestamp_ms: u64) -> Self {
+        Self {
+            sequence,
+            timestamp_ms,
+            ..self
+        }
+    }
+
+    /// Anzahl verpasster Nachrichten zwischen `previous` und dieser Nachricht
+    ///
+    /// 0 bei direkter Folge-Nachricht. Nur sinnvoll, wenn
+    /// [`is_newer_than`](Self::is_newer_than) gilt.
+    pub fn missed_since(&self, previous: u32) -> u32 {
+        self.sequence.wrapping_sub(previous).wrapping_sub(1)
+    }
+
+    /// Prüft ob die Nachricht neuer ist als Sequenznummer `previous` (übersteht Überlauf)
+    pub fn is_newer_than(&self, previous: u32) -> bool {
+        let distance = self.sequence.wrapping_sub(previous);
+        distance != 0 && distance < 1 << 31
+    }
+}
+
+/// Erkennt den Farbnamen anhand der RGB-Werte
+///
+/// Kurzform für `classify_color(color).name()`.
+pub fn color_name(color: RGB8) -> &'static str {
+    classify_color(color).name()
 }
 
 /// LED Command für manuelle Steuerung
 ///
 /// Wird vom WebSocket an den LED-Task gesendet.
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum LedCommand {
     /// Setze LED auf eine spezifische Farbe (manueller Modus)
     SetColor {
         target_color: RGB8,
-        name: &'static str,
+        color_id: ColorId,
     },
     /// Aktiviere Auto-Rotation
     EnableAuto,
+    /// Zufallsmodus: pro Intervall eine neue Zufallsfarbe, siehe [`crate::random`]
+    EnableRandom,
+    /// LED ausschalten (manueller Modus, Farbe Schwarz)
+    Off,
+    /// Lichtwecker: Sonnenaufgang von Dunkelrot bis warmweiß
+    Sunrise { duration_secs: u32 },
+    /// Sleep-Timer: LED nach `minutes` Minuten ausblenden (0 = Timer abbrechen)
+    SleepTimer { minutes: u16 },
+    /// Gesamthelligkeit setzen (0 = aus, 255 = unverändert), Farbe und Modus bleiben
+    SetBrightness { brightness: u8 },
+    /// Preset per ID anwenden (Farbe/Effekt, Helligkeit), siehe [`crate::preset`]
+    ApplyPreset { id: u8 },
+    /// Wie `SetColor`, aber mit Übergang über `transition_ms` (0 = sofort)
+    FadeTo {
+        target_color: RGB8,
+        color_id: ColorId,
+        transition_ms: u32,
+    },
+    /// Kalibriermodus: Testfarben nacheinander, siehe [`crate::calibration`]
+    Calibrate,
+    /// Testmuster pro Pixel (Lauflicht, dann R/G/B), siehe [`crate::test_pattern`]
+    TestPattern,
+    /// Blinkcode des letzten Verbindungsproblems, danach zurück zum vorherigen Modus,
+    /// siehe [`crate::diagnose`]
+    Diagnose,
+    /// Lauftext auf der LED-Matrix, siehe [`crate::text`]
+    ScrollText { text: TextMessage },
+    /// Uhrzeit anzeigen (`None` = Format aus der Konfiguration), siehe [`crate::clock_face`]
+    ShowClock { format: Option<ClockFormat> },
+    /// Farbfolge der Auto-Rotation setzen (`None` = Rot → Grün → Blau), siehe [`crate::sequence`]
+    SetSequence { sequence: Option<ColorSequence> },
+    /// Statischer Farbverlauf über alle Pixel, siehe [`crate::gradient`]
+    ShowGradient { gradient: Gradient },
+    /// Meteor mit ausblendendem Schweif (`color` fehlt = aktuelle Farbe), siehe [`crate::meteor`]
+    Meteor {
+        color: Option<RGB8>,
+        speed: u8,
+        tail: u8,
+    },
+    /// Plasma aus der Farbfolge als Palette (vor allem für Matrizen), siehe [`crate::plasma`]
+    Plasma { speed: u8 },
+    /// Parameter des laufenden Effekts live ändern, siehe [`crate::effect_param`]
+    SetEffectParam { value: ParamValue },
+}
+
+impl LedCommand {
+    /// Ergänzt Farb-Kommandos (`SetColor`, `Off`) um eine Übergangsdauer
+    ///
+    /// Andere Kommandos und `transition_ms = 0` bleiben unverändert.
+    pub fn with_transition(self, transition_ms: u32) -> Self {
+        if transition_ms == 0 {
+            return self;
+        }
+        match self {
+            LedCommand::SetColor {
+                target_color,
+                color_id,
+            }
+            | LedCommand::FadeTo {
+                target_color,
+                color_id,
+                ..
+            } => LedCommand::FadeTo {
+                target_color,
+                color_id,
+                transition_ms,
+            },
+            LedCommand::Off => LedCommand::FadeTo {
+                target_color: RGB8::default(),
+                color_id: ColorId::Off,
+                transition_ms,
+            },
+            other => other,
+        }
+    }
 }
 
 impl core::convert::TryFrom<&str> for LedCommand {
@@ -55,33 +389,10 @@ impl core::convert::TryFrom<&str> for LedCommand {
         // Daher nutzen wir einen Default-Wert
         const DEFAULT_BRIGHTNESS: u8 = 10;
 
-        match name {
-            "Rot" => Ok(Self::SetColor {
-                target_color: RGB8 {
-                    r: DEFAULT_BRIGHTNESS,
-                    g: 0,
-                    b: 0,
-                },
-                name: "Rot",
-            }),
-            "Grün" => Ok(Self::SetColor {
-                target_color: RGB8 {
-                    r: 0,
-                    g: DEFAULT_BRIGHTNESS,
-                    b: 0,
-                },
-                name: "Grün",
-            }),
-            "Blau" => Ok(Self::SetColor {
-                target_color: RGB8 {
-                    r: 0,
-                    g: 0,
-                    b: DEFAULT_BRIGHTNESS,
-                },
-                name: "Blau",
-            }),
-            _ => Err(()),
-        }
+        // Farbnamen in allen Sprachen (siehe crate::i18n)
+        crate::i18n::parse_color_name(name)
+            .and_then(|id| id.command(DEFAULT_BRIGHTNESS))
+            .ok_or(())
     }
 }
 
@@ -89,17 +400,26 @@ impl core::convert::TryFrom<&str> for LedCommand {
 // defmt::Format Implementations (optional feature)
 // ============================================================================
 
+#[cfg(feature = "defmt")]
+impl defmt::Format for ColorId {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{}", self.name())
+    }
+}
+
 #[cfg(feature = "defmt")]
 impl defmt::Format for LedColorMessage {
     fn format(&self, fmt: defmt::Formatter) {
         defmt::write!(
             fmt,
-            "LedColorMessage {{ name: {}, rgb: ({}, {}, {}), auto: {} }}",
-            self.name,
+            "LedColorMessage {{ #{}, name: {}, rgb: ({}, {}, {}), auto: {}, t: {}ms }}",
+            self.sequence,
+            self.color_id.name(),
             self.color.r,
             self.color.g,
             self.color.b,
-            self.is_auto_mode
+            self.is_auto_mode,
+            self.timestamp_ms
         )
     }
 }
@@ -108,11 +428,14 @@ impl defmt::Format for LedColorMessage {
 impl defmt::Format for LedCommand {
     fn format(&self, fmt: defmt::Formatter) {
         match self {
-            LedCommand::SetColor { target_color, name } => {
+            LedCommand::SetColor {
+                target_color,
+                color_id,
+            } => {
                 defmt::write!(
                     fmt,
                     "SetColor {{ name: {}, rgb: ({}, {}, {}) }}",
-                    name,
+                    color_id.name(),
                     target_color.r,
                     target_color.g,
                     target_color.b
@@ -121,6 +444,73 @@ impl defmt::Format for LedCommand {
             LedCommand::EnableAuto => {
                 defmt::write!(fmt, "EnableAuto")
             }
+            LedCommand::EnableRandom => defmt::write!(fmt, "EnableRandom"),
+            LedCommand::Off => {
+                defmt::write!(fmt, "Off")
+            }
+            LedCommand::Sunrise { duration_secs } => {
+                defmt::write!(fmt, "Sunrise {{ duration: {}s }}", duration_secs)
+            }
+            LedCommand::SleepTimer { minutes } => {
+                defmt::write!(fmt, "SleepTimer {{ minutes: {} }}", minutes)
+            }
+            LedCommand::SetBrightness { brightness } => {
+                defmt::write!(fmt, "SetBrightness {{ brightness: {} }}", brightness)
+            }
+            LedCommand::ApplyPreset { id } => {
+                defmt::write!(fmt, "ApplyPreset {{ id: {} }}", id)
+            }
+            LedCommand::FadeTo {
+                target_color,
+                color_id,
+                transition_ms,
+            } => {
+                defmt::write!(
+                    fmt,
+                    "FadeTo {{ name: {}, rgb: ({}, {}, {}), {}ms }}",
+                    color_id.name(),
+                    target_color.r,
+                    target_color.g,
+                    target_color.b,
+                    transition_ms
+                )
+            }
+            LedCommand::Calibrate => defmt::write!(fmt, "Calibrate"),
+            LedCommand::TestPattern => defmt::write!(fmt, "TestPattern"),
+            LedCommand::Diagnose => defmt::write!(fmt, "Diagnose"),
+            LedCommand::ScrollText { text } => {
+                defmt::write!(fmt, "ScrollText {{ text: {=str} }}", text.as_str())
+            }
+            LedCommand::ShowClock { format } => {
+                let format = match format {
+                    Some(ClockFormat::H24) => "24h",
+                    Some(ClockFormat::H12) => "12h",
+                    None => "default",
+                };
+                defmt::write!(fmt, "ShowClock {{ format: {=str} }}", format)
+            }
+            LedCommand::SetSequence { sequence } => {
+                let len = sequence.map_or(0, |sequence| sequence.colors().len());
+                defmt::write!(fmt, "SetSequence {{ colors: {} }}", len)
+            }
+            LedCommand::ShowGradient { gradient } => {
+                defmt::write!(
+                    fmt,
+                    "ShowGradient {{ colors: {} }}",
+                    gradient.colors().len()
+                )
+            }
+            LedCommand::Meteor { speed, tail, .. } => {
+                defmt::write!(fmt, "Meteor {{ speed: {}, tail: {} }}", speed, tail)
+            }
+            LedCommand::Plasma { speed } => defmt::write!(fmt, "Plasma {{ speed: {} }}", speed),
+            LedCommand::SetEffectParam { value } => {
+                defmt::write!(
+                    fmt,
+                    "SetEffectParam {{ name: {=str} }}",
+                    value.name().name()
+                )
+            }
         }
     }
 }
@@ -0,0 +1,235 @@
+//! Web-UI im Flash: Ersatz für die einkompilierte index.html
+//!
+//! `POST /api/ui` schreibt eine neue Oberfläche (HTML mit eingebetteten
+//! Assets, optional gzip-komprimiert) in die "webui" Partition; `GET /`
+//! liefert sie dann statt der einkompilierten Seite aus. UI-Korrekturen
+//! brauchen so kein Firmware-Update.
+//!
+//! Layout der Partition: [`UiBundleHeader`] (16 Byte) am Anfang, die Daten
+//! direkt dahinter ab [`UI_BUNDLE_HEADER_LEN`]. Die Firmware schreibt den
+//! Header erst, nachdem alle Daten geschrieben sind und die CRC stimmt. Ein
+//! abgebrochener Upload hinterlässt einen gelöschten Header (0xFF), das Gerät
+//! fällt dann auf die einkompilierte Seite zurück.
+
+use crate::record::Crc32;
+
+/// Magic am Anfang des Headers
+pub const UI_BUNDLE_MAGIC: [u8; 4] = *b"LEDU";
+
+/// Header-Länge in Bytes (Vielfaches der Flash-Schreibgranularität)
+pub const UI_BUNDLE_HEADER_LEN: usize = 16;
+
+/// Format-Version des Headers
+const UI_BUNDLE_VERSION: u8 = 1;
+
+/// Flag-Bit: Daten sind gzip-komprimiert
+const FLAG_GZIP: u8 = 0x01;
+
+/// gzip-Magic (RFC 1952), Anfang jedes gzip-Streams
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Kodierung der gespeicherten Oberfläche
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiEncoding {
+    /// Unkomprimiertes HTML
+    Identity,
+    /// gzip, wird mit `Content-Encoding: gzip` ausgeliefert
+    Gzip,
+}
+
+impl UiEncoding {
+    /// Kodierung aus dem `Content-Encoding` Header des Uploads
+    ///
+    /// Ohne Header oder mit `identity` unkomprimiert; andere Kodierungen
+    /// (z.B. `br`) kann nicht jeder Browser und werden abgelehnt.
+    pub fn from_header(value: Option<&[u8]>) -> Option<Self> {
+        match value.map(|value| value.trim_ascii()) {
+            None => Some(UiEncoding::Identity),
+            Some(value) if value.eq_ignore_ascii_case(b"identity") => Some(UiEncoding::Identity),
+            Some(value) if value.eq_ignore_ascii_case(b"gzip") => Some(UiEncoding::Gzip),
+            Some(_) => None,
+        }
+    }
+
+    /// Wert für `Content-Encoding` beim Ausliefern (`None` = Header weglassen)
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        match self {
+            UiEncoding::Identity => None,
+            UiEncoding::Gzip => Some("gzip"),
+        }
+    }
+
+    /// Kurzname für API und Konsole
+    pub fn name(&self) -> &'static str {
+        match self {
+            UiEncoding::Identity => "identity",
+            UiEncoding::Gzip => "gzip",
+        }
+    }
+}
+
+/// Header einer gespeicherten Oberfläche
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiBundleHeader {
+    pub encoding: UiEncoding,
+    /// Datenlänge in Bytes (ohne Header)
+    pub len: u32,
+    /// CRC32 der Daten
+    pub crc: u32,
+}
+
+impl UiBundleHeader {
+    /// Serialisiert den Header (Magic, Version, Flags, Länge, CRC32; Little Endian)
+    pub fn encode(&self) -> [u8; UI_BUNDLE_HEADER_LEN] {
+        let mut out = [0u8; UI_BUNDLE_HEADER_LEN];
+        out[0..4].copy_from_slice(&UI_BUNDLE_MAGIC);
+        out[4] = UI_BUNDLE_VERSION;
+        out[5] = match self.encoding {
+            UiEncoding::Identity => 0,
+            UiEncoding::Gzip => FLAG_GZIP,
+        };
+        out[8..12].copy_from_slice(&self.len.to_le_bytes());
+        out[12..16].copy_from_slice(&self.crc.to_le_bytes());
+        out
+    }
+
+    /// Liest einen Header und prüft Magic, Version und Länge
+    ///
+    /// Gibt `None` für gelöschten Flash (0xFF), fremde Daten oder eine Länge
+    /// zurück, die nicht in `capacity` (Partitionsgröße ohne Header) passt.
+    /// Die CRC der Daten prüft [`UiBundleHeader::verify`].
+    pub fn decode(data: &[u8], capacity: usize) -> Option<Self> {
+        let data = data.get(..UI_BUNDLE_HEADER_LEN)?;
+        if data[0..4] != UI_BUNDLE_MAGIC || data[4] != UI_BUNDLE_VERSION {
+            return None;
+        }
+        let encoding = if data[5] & FLAG_GZIP != 0 {
+            UiEncoding::Gzip
+        } else {
+            UiEncoding::Identity
+        };
+        let len = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+        let crc = u32::from_le_bytes([data[12], data[13], data[14], data[15]]);
+        if len == 0 || len as usize > capacity {
+            return None;
+        }
+        Some(Self { encoding, len, crc })
+    }
+
+    /// Prüft die CRC der vollständig gelesenen Daten
+    pub fn verify(&self, crc: u32) -> bool {
+        self.crc == crc
+    }
+}
+
+/// Fehler beim Upload einer Oberfläche
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiUploadError {
+    /// Leerer Body
+    Empty,
+    /// Body größer als die Partition
+    TooLarge,
+    /// `Content-Encoding` weder fehlend, `identity` noch `gzip`
+    UnsupportedEncoding,
+    /// Als gzip angekündigt, aber ohne gzip-Magic
+    NotGzip,
+    /// Body kürzer oder länger als `Content-Length`
+    LengthMismatch,
+}
+
+impl UiUploadError {
+    /// Fehlermeldung für die API
+    pub fn message(&self) -> &'static str {
+        match self {
+            UiUploadError::Empty => "Empty UI bundle",
+            UiUploadError::TooLarge => "UI bundle too large",
+            UiUploadError::UnsupportedEncoding => "Unsupported Content-Encoding",
+            UiUploadError::NotGzip => "Body is not gzip",
+            UiUploadError::LengthMismatch => "Incomplete UI bundle",
+        }
+    }
+}
+
+/// Prüft und zählt einen Upload, der in Stücken aus dem Netzwerk kommt
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::ui_bundle::{UiEncoding, UiUpload};
+/// let mut upload = UiUpload::new(UiEncoding::Identity, 11, 4096).unwrap();
+/// upload.push(b"<html>").unwrap();
+/// upload.push(b"</ht>").unwrap();
+/// let header = upload.finish().unwrap();
+/// assert_eq!(header.len, 11);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UiUpload {
+    encoding: UiEncoding,
+    expected: u32,
+    received: u32,
+    crc: Crc32,
+}
+
+impl UiUpload {
+    /// Beginnt einen Upload mit `len` Bytes (`Content-Length`)
+    ///
+    /// `capacity` ist die Partitionsgröße ohne Header.
+    pub fn new(encoding: UiEncoding, len: usize, capacity: usize) -> Result<Self, UiUploadError> {
+        if len == 0 {
+            return Err(UiUploadError::Empty);
+        }
+        if len > capacity {
+            return Err(UiUploadError::TooLarge);
+        }
+        Ok(Self {
+            encoding,
+            expected: len as u32,
+            received: 0,
+            crc: Crc32::new(),
+        })
+    }
+
+    /// Verarbeitet das nächste Stück des Bodys
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), UiUploadError> {
+        if self.received == 0
+            && self.encoding == UiEncoding::Gzip
+            && chunk.len() >= GZIP_MAGIC.len()
+            && chunk[..GZIP_MAGIC.len()] != GZIP_MAGIC
+        {
+            return Err(UiUploadError::NotGzip);
+        }
+        let remaining = (self.expected - self.received) as usize;
+        if chunk.len() > remaining {
+            return Err(UiUploadError::LengthMismatch);
+        }
+        self.crc.update(chunk);
+        self.received += chunk.len() as u32;
+        Ok(())
+    }
+
+    /// Bisher empfangene Bytes (= Schreib-Offset hinter dem Header)
+    pub fn received(&self) -> usize {
+        self.received as usize
+    }
+
+    /// Noch erwartete Bytes
+    pub fn remaining(&self) -> usize {
+        (self.expected - self.received) as usize
+    }
+
+    /// Schließt den Upload ab und liefert den zu schreibenden Header
+    pub fn finish(&self) -> Result<UiBundleHeader, UiUploadError> {
+        if self.received != self.expected {
+            return Err(UiUploadError::LengthMismatch);
+        }
+        Ok(UiBundleHeader {
+            encoding: self.encoding,
+            len: self.expected,
+            crc: self.crc.finish(),
+        })
+    }
+}
+
+/// Anzahl zu löschender Sektoren für Header + `len` Bytes Daten
+pub fn sectors_needed(len: usize, sector_size: usize) -> usize {
+    (UI_BUNDLE_HEADER_LEN + len).div_ceil(sector_size)
+}
@@ -0,0 +1,345 @@
+//! Web of Things (W3C WoT Thing Description, WebThings)
+//!
+//! Beschreibt die Lampe als "Thing" mit Properties und Actions. WoT-Clients
+//! (WebThings Gateway, node-wot, Home-Assistant-Integrationen) laden die
+//! Beschreibung von `/.well-known/wot` und steuern über die darin genannten
+//! Endpunkte:
+//!
+//! | Endpunkt                          | Methode | Inhalt                                  |
+//! |-----------------------------------|---------|-----------------------------------------|
+//! | `/things/led/properties`          | GET     | `{"on":true,"color":"#ff0000","auto":false}` |
+//! | `/things/led/properties/<name>`   | GET     | Wert, z.B. `true` bzw. `"#ff0000"`      |
+//! | `/things/led/properties/<name>`   | PUT     | Wert oder `{"<name>":<wert>}`           |
+//! | `/things/led/actions/fade`        | POST    | `{"color":"#0000ff","transition_ms":1500}` |
+//! | `/things/led/actions/command`     | POST    | Text-Kommando, z.B. `"sunrise 10"`      |
+//!
+//! Schreibende Requests nehmen sowohl den nackten Wert (W3C WoT) als auch die
+//! Objekt-Form der Web Thing API an (`{"on":false}`, Actions als
+//! `{"fade":{"input":{...}}}`). Farben sind `#rrggbb`.
+
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+use rgb::RGB8;
+use serde::Deserialize;
+
+use crate::command::parse_text_command;
+use crate::ha_discovery::{write_json_escaped, write_json_string};
+use crate::tasmota::parse_color;
+use crate::types::{LedColorMessage, LedCommand, classify_color};
+
+/// Pfad des Things, Basis aller Property- und Action-Endpunkte
+pub const THING_PATH: &str = "/things/led";
+
+/// Properties des Things
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WotProperty {
+    /// Lampe an (`false` = aus)
+    On,
+    /// Aktuelle Farbe als `#rrggbb`
+    Color,
+    /// Auto-Rotation durch die Farbfolge
+    Auto,
+}
+
+impl WotProperty {
+    /// Alle Properties in Beschreibungs-Reihenfolge
+    pub const ALL: [WotProperty; 3] = [WotProperty::On, WotProperty::Color, WotProperty::Auto];
+
+    /// Name im Pfad und in JSON-Objekten
+    pub fn name(self) -> &'static str {
+        match self {
+            WotProperty::On => "on",
+            WotProperty::Color => "color",
+            WotProperty::Auto => "auto",
+        }
+    }
+
+    /// Property aus dem Pfad-Segment
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|property| property.name() == name)
+    }
+}
+
+/// Pfad-Segment → Property (für `parse_path_segment`, unbekannt = 404)
+impl FromStr for WotProperty {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, ()> {
+        Self::from_name(name).ok_or(())
+    }
+}
+
+/// Actions des Things
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WotAction {
+    /// Übergang zu einer Farbe
+    Fade,
+    /// Text-Kommando (siehe [`crate::command`])
+    Command,
+}
+
+impl WotAction {
+    /// Alle Actions in Beschreibungs-Reihenfolge
+    pub const ALL: [WotAction; 2] = [WotAction::Fade, WotAction::Command];
+
+    /// Name im Pfad
+    pub fn name(self) -> &'static str {
+        match self {
+            WotAction::Fade => "fade",
+            WotAction::Command => "command",
+        }
+    }
+
+    /// Action aus dem Pfad-Segment
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+/// Pfad-Segment → Action (für `parse_path_segment`, unbekannt = 404)
+impl FromStr for WotAction {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, ()> {
+        Self::from_name(name).ok_or(())
+    }
+}
+
+/// Fehler bei schreibenden Requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WotError {
+    /// Body ist kein passender JSON-Wert
+    InvalidValue,
+    /// Farbe ist nicht `#rrggbb`
+    InvalidColor,
+    /// Text-Kommando unbekannt oder mit ungültigem Argument
+    InvalidCommand,
+}
+
+impl WotError {
+    /// Meldung für die Fehler-Antwort
+    pub fn message(self) -> &'static str {
+        match self {
+            WotError::InvalidValue => "Invalid property value",
+            WotError::InvalidColor => "Invalid color, expected #rrggbb",
+            WotError::InvalidCommand => "Invalid command",
+        }
+    }
+}
+
+/// Schreibt die Thing Description (JSON-LD, `application/td+json`)
+///
+/// `id` wird als `urn:dev:ops:<id>` eingetragen (z.B. der mDNS-Hostname).
+pub fn write_thing_description(out: &mut impl Write, id: &str, title: &str) -> fmt::Result {
+    out.write_str(
+        "{\"@context\":[\"https://www.w3.org/2022/wot/td/v1.1\",\
+         {\"@vocab\":\"https://webthings.io/schemas/\"}],\
+         \"@type\":[\"Light\",\"OnOffSwitch\",\"ColorControl\"],\"id\":\"urn:dev:ops:",
+    )?;
+    write_json_escaped(out, id)?;
+    out.write_str("\",\"title\":")?;
+    write_json_string(out, title)?;
+    out.write_str(
+        ",\"securityDefinitions\":{\"nosec_sc\":{\"scheme\":\"nosec\"}},\"security\":\"nosec_sc\"",
+    )?;
+
+    out.write_str(",\"properties\":{")?;
+    for (index, property) in WotProperty::ALL.into_iter().enumerate() {
+        if index > 0 {
+            out.write_char(',')?;
+        }
+        let (semantic, title, kind) = match property {
+            WotProperty::On => ("OnOffProperty", "An/Aus", "boolean"),
+            WotProperty::Color => ("ColorProperty", "Farbe", "string"),
+            WotProperty::Auto => ("BooleanProperty", "Auto-Rotation", "boolean"),
+        };
+        write!(
+            out,
+            "\"{name}\":{{\"@type\":\"{semantic}\",\"title\":\"{title}\",\"type\":\"{kind}\",\
+             \"observable\":false,\"forms\":[{{\"href\":\"{THING_PATH}/properties/{name}\"}}]}}",
+            name = property.name(),
+        )?;
+    }
+
+    write!(
+        out,
+        "}},\"actions\":{{\"fade\":{{\"@type\":\"FadeAction\",\"title\":\"Übergang\",\
+         \"input\":{{\"type\":\"object\",\"properties\":{{\"color\":{{\"type\":\"string\"}},\
+         \"transition_ms\":{{\"type\":\"integer\",\"minimum\":0}}}},\"required\":[\"color\"]}},\
+         \"forms\":[{{\"href\":\"{THING_PATH}/actions/fade\"}}]}},\
+         \"command\":{{\"title\":\"Text-Kommando\",\"input\":{{\"type\":\"string\"}},\
+         \"forms\":[{{\"href\":\"{THING_PATH}/actions/command\"}}]}}}}"
+    )?;
+    write!(
+        out,
+        ",\"forms\":[{{\"op\":[\"readallproperties\"],\"href\":\"{THING_PATH}/properties\"}}]}}"
+    )
+}
+
+/// Schreibt den Wert einer Property als JSON
+pub fn write_property_value(
+    out: &mut impl Write,
+    property: WotProperty,
+    state: &LedColorMessage,
+) -> fmt::Result {
+    match property {
+        WotProperty::On => write!(out, "{}", state.color != RGB8::default()),
+        WotProperty::Color => write!(
+            out,
+            "\"#{:02x}{:02x}{:02x}\"",
+            state.color.r, state.color.g, state.color.b
+        ),
+        WotProperty::Auto => write!(out, "{}", state.is_auto_mode),
+    }
+}
+
+/// Schreibt alle Properties als JSON-Objekt
+pub fn write_properties(out: &mut impl Write, state: &LedColorMessage) -> fmt::Result {
+    out.write_char('{')?;
+    for (index, property) in WotProperty::ALL.into_iter().enumerate() {
+        if index > 0 {
+            out.write_char(',')?;
+        }
+        write!(out, "\"{}\":", property.name())?;
+        write_property_value(out, property, state)?;
+    }
+    out.write_char('}')
+}
+
+/// Web-Thing-API-Form `{"on":true}`
+#[derive(Deserialize)]
+struct OnBody {
+    on: bool,
+}
+
+/// Web-Thing-API-Form `{"color":"#ff0000"}`
+#[derive(Deserialize)]
+struct ColorBody<'a> {
+    color: &'a str,
+}
+
+/// Web-Thing-API-Form `{"auto":true}`
+#[derive(Deserialize)]
+struct AutoBody {
+    auto: bool,
+}
+
+/// Eingabe der Action `fade`
+#[derive(Deserialize)]
+struct FadeInput<'a> {
+    color: &'a str,
+    #[serde(default)]
+    transition_ms: u32,
+}
+
+/// Web-Thing-API-Form einer Action: `{"<name>":{"input":...}}`
+#[derive(Deserialize)]
+struct ActionRequest<T> {
+    input: T,
+}
+
+#[derive(Deserialize)]
+struct FadeRequest<'a> {
+    #[serde(borrow)]
+    fade: ActionRequest<FadeInput<'a>>,
+}
+
+#[derive(Deserialize)]
+struct CommandRequest<'a> {
+    #[serde(borrow)]
+    command: ActionRequest<&'a str>,
+}
+
+/// Liest einen JSON-Wert, `None` bei ungültigem JSON oder anderem Typ
+fn parse<'a, T: Deserialize<'a>>(body: &'a [u8]) -> Option<T> {
+    serde_json_core::from_slice::<T>(body)
+        .ok()
+        .map(|(value, _)| value)
+}
+
+/// LED-Kommando für das Schreiben einer Property
+///
+/// `state` ist der aktuelle Zustand: `on = true` startet bei ausgeschalteter
+/// Lampe die Auto-Rotation, `auto = false` hält die aktuelle Farbe fest.
+/// `Ok(None)` wenn die Lampe schon im gewünschten Zustand ist.
+pub fn property_command(
+    property: WotProperty,
+    body: &[u8],
+    state: &LedColorMessage,
+) -> Result<Option<LedCommand>, WotError> {
+    let is_on = state.color != RGB8::default();
+    match property {
+        WotProperty::On => {
+            let on = parse::<bool>(body)
+                .or_else(|| parse::<OnBody>(body).map(|body| body.on))
+                .ok_or(WotError::InvalidValue)?;
+            Ok(match (on, is_on) {
+                (true, false) => Some(LedCommand::EnableAuto),
+                (false, true) => Some(LedCommand::Off),
+                _ => None,
+            })
+        }
+        WotProperty::Color => {
+            let color = parse::<&str>(body)
+                .or_else(|| parse::<ColorBody>(body).map(|body| body.color))
+                .ok_or(WotError::InvalidValue)?;
+            let color = parse_hex(color)?;
+            Ok(Some(color_command(color)))
+        }
+        WotProperty::Auto => {
+            let auto = parse::<bool>(body)
+                .or_else(|| parse::<AutoBody>(body).map(|body| body.auto))
+                .ok_or(WotError::InvalidValue)?;
+            Ok(match (auto, state.is_auto_mode) {
+                (true, false) => Some(LedCommand::EnableAuto),
+                (false, true) => Some(color_command(state.color)),
+                _ => None,
+            })
+        }
+    }
+}
+
+/// LED-Kommando für eine Action
+pub fn action_command(action: WotAction, body: &[u8]) -> Result<LedCommand, WotError> {
+    match action {
+        WotAction::Fade => {
+            let input = parse::<FadeInput>(body)
+                .or_else(|| parse::<FadeRequest>(body).map(|request| request.fade.input))
+                .ok_or(WotError::InvalidValue)?;
+            let color = parse_hex(input.color)?;
+            Ok(LedCommand::FadeTo {
+                target_color: color,
+                color_id: classify_color(color),
+                transition_ms: input.transition_ms,
+            })
+        }
+        WotAction::Command => {
+            let text = parse::<&str>(body)
+                .or_else(|| parse::<CommandRequest>(body).map(|request| request.command.input))
+                .ok_or(WotError::InvalidValue)?;
+            parse_text_command(text).map_err(|_| WotError::InvalidCommand)
+        }
+    }
+}
+
+/// Farbe nur als `#rrggbb` (nicht die weiteren Tasmota-Formen)
+fn parse_hex(color: &str) -> Result<RGB8, WotError> {
+    color
+        .strip_prefix('#')
+        .filter(|hex| hex.len() == 6)
+        .and_then(parse_color)
+        .ok_or(WotError::InvalidColor)
+}
+
+fn color_command(color: RGB8) -> LedCommand {
+    if color == RGB8::default() {
+        return LedCommand::Off;
+    }
+    LedCommand::SetColor {
+        target_color: color,
+        color_id: classify_color(color),
+    }
+}
@@ -0,0 +1,358 @@
+//! WebSocket-Protokoll: Client-Nachrichten parsen und zuordnen
+//!
+//! Der Browser sendet JSON-Objekte mit `type` und optionalen Feldern, z.B.
+//! `{"type":"set_color","color":"Blau","transition_ms":1500}`. [`parse_ws_message`]
+//! liest die Nachricht und bildet sie auf eine [`WsRequest`] ab: ein
+//! [`LedCommand`] für den LED-Task, eine Zeitplan-Operation oder eine
+//! Fehlermeldung für `{"type":"error","message":"..."}`.
+//!
+//! Antworten an den Browser beschreibt [`WsServerMessage`].
+//!
+//! Der Zeitplan-Eintrag (`entry`) sowie Zeitplan- und Heap-Antwort sind
+//! generisch, damit die JSON-Darstellung (`ScheduleEntryDto`, `HeapInfo` in
+//! der Firmware) samt Kapazität dort bleibt.
+//!
+//! # Beispiel
+//! ```
+//! # use esp_core::LedCommand;
+//! # use esp_core::ws::{WsRequest, parse_ws_message};
+//! let request = parse_ws_message::<()>(br#"{"type":"apply_preset","id":2}"#, 10);
+//! assert!(matches!(
+//!     request,
+//!     WsRequest::Command(LedCommand::ApplyPreset { id: 2 })
+//! ));
+//! ```
+
+use rgb::RGB8;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::token_matches;
+use crate::clock_face::ClockFormat;
+use crate::effect_param::{ParamError, ParamName, ParamValue};
+use crate::gradient::Gradient;
+use crate::i18n::Language;
+use crate::meteor::{DEFAULT_METEOR_SPEED, DEFAULT_METEOR_TAIL};
+use crate::plasma::DEFAULT_PLASMA_SPEED;
+use crate::sequence::{ColorSequence, parse_color_word};
+use crate::text::TextMessage;
+use crate::types::{ColorId, ColorLabel, LedColorMessage, LedCommand};
+
+/// Client → Server Nachrichten
+/// Kommandos vom Browser an den ESP32
+///
+/// Hinweis: Verwendet einfache untagged enum Struktur für serde-json-core Kompatibilität
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(bound(deserialize = "E: Deserialize<'de>"))]
+pub struct WsClientMessage<'a, E> {
+    #[serde(rename = "type")]
+    pub msg_type: MessageType,
+    #[serde(default)]
+    pub color: Option<ColorId>,
+    #[serde(default)]
+    pub mode: Option<OperationMode>,
+    /// Neuer Zeitplan-Eintrag (für schedule_add)
+    #[serde(default)]
+    pub entry: Option<E>,
+    /// Zeitplan-Eintrag-ID (für schedule_remove) bzw. Preset-ID (für apply_preset)
+    #[serde(default)]
+    pub id: Option<u8>,
+    /// Sleep-Timer in Minuten (für sleep_timer, 0 = abbrechen)
+    #[serde(default)]
+    pub minutes: Option<u16>,
+    /// Übergangsdauer in ms (für set_color, fehlt oder 0 = sofort)
+    /// Beispiel: {"type":"set_color","color":"Blau","transition_ms":1500}
+    #[serde(default)]
+    pub transition_ms: Option<u32>,
+    /// Lauftext-Nachricht (für scroll_text, max. [`crate::text::TEXT_LEN`] Bytes)
+    /// Beispiel: {"type":"scroll_text","text":"Hallo Welt"}
+    #[serde(default)]
+    pub text: Option<&'a str>,
+    /// Stunden-Format (für show_clock: 12 oder 24, fehlt = Konfiguration)
+    /// Beispiel: {"type":"show_clock","hours":12}
+    #[serde(default)]
+    pub hours: Option<u8>,
+    /// Farbliste für set_sequence (Syntax siehe [`crate::sequence`], fehlt = Rot → Grün → Blau)
+    /// bzw. show_gradient (2 bis 8 Farben) und meteor (eine Farbe, fehlt = aktuelle Farbe)
+    /// Beispiel: {"type":"set_sequence","colors":"#FF8000 #00FF80 #8000FF"}
+    #[serde(default)]
+    pub colors: Option<&'a str>,
+    /// Tempo (meteor: Pixel pro Sekunde, plasma: Winkelschritte pro Sekunde, fehlt = Standard)
+    #[serde(default)]
+    pub speed: Option<u8>,
+    /// Schweiflänge in Pixeln (für meteor, fehlt = Standard)
+    /// Beispiel: {"type":"meteor","speed":40,"tail":12,"colors":"#FF8000"}
+    #[serde(default)]
+    pub tail: Option<u8>,
+    /// Parametername (für set_effect_param, siehe [`crate::effect_param`])
+    #[serde(default)]
+    pub name: Option<&'a str>,
+    /// Zahlenwert (für set_effect_param, Farben stattdessen in `colors`)
+    /// Beispiel: {"type":"set_effect_param","name":"speed","value":40}
+    #[serde(default)]
+    pub value: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageType {
+    SetColor,
+    SetMode,
+    ScheduleGet,
+    ScheduleAdd,
+    ScheduleRemove,
+    SleepTimer,
+    /// Preset per ID anwenden, Beispiel: {"type":"apply_preset","id":2}
+    ApplyPreset,
+    /// Kalibriermodus (Testfarben) starten, Beispiel: {"type":"calibrate"}
+    Calibrate,
+    /// Testmuster pro Pixel starten, Beispiel: {"type":"test_pattern"}
+    TestPattern,
+    /// Lauftext auf der LED-Matrix starten, Beispiel: {"type":"scroll_text","text":"Hallo"}
+    ScrollText,
+    /// Uhrzeit anzeigen, Beispiel: {"type":"show_clock"}
+    ShowClock,
+    /// Farbfolge der Auto-Rotation setzen, Beispiel: {"type":"set_sequence","colors":"rot blau"}
+    SetSequence,
+    /// Farbverlauf über alle Pixel, Beispiel: {"type":"show_gradient","colors":"#FF8000 #8000FF"}
+    ShowGradient,
+    /// Meteor mit Schweif, Beispiel: {"type":"meteor","speed":40}
+    Meteor,
+    /// Plasma in den Farben der Farbfolge, Beispiel: {"type":"plasma","speed":64}
+    Plasma,
+    /// Live-Parameter, Beispiel: {"type":"set_effect_param","name":"palette","colors":"rot blau"}
+    SetEffectParam,
+    /// Authentifizierung (erste Nachricht, siehe WsAuthMessage)
+    Auth,
+}
+
+/// Betriebs-Modus der LED
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationMode {
+    Auto,   // Automatische Farb-Rotation
+    Manual, // Manuelle Steuerung vom Browser
+    Random, // Zufallsfarben (nur set_mode, Status meldet "auto")
+}
+
+/// Server → Client Nachrichten
+/// Status-Updates und Fehler vom ESP32 an den Browser
+///
+/// `S` ist die Liste der Zeitplan-Einträge, `H` die Heap-Telemetrie.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type")]
+pub enum WsServerMessage<S, H> {
+    #[serde(rename = "status")]
+    Status {
+        /// Protokoll-Bezeichner (sprachunabhängig)
+        color: ColorId,
+        /// Anzeigename in der konfigurierten Sprache (UI_LANGUAGE), Preset-Name
+        /// bzw. `RGB(…)` für Farben ohne Namen
+        label: ColorLabel,
+        rgb: RGB8,
+        /// Broadcast-Zeitpunkt (ms seit Boot)
+        timestamp_ms: u64,
+        mode: OperationMode,
+        /// Fortlaufende Nummer, Lücken = verpasste Updates
+        seq: u32,
+    },
+    #[serde(rename = "error")]
+    Error { message: &'static str },
+    /// Kommando nicht angenommen (LED-Task ausgelastet), Client kann wiederholen
+    /// Beispiel: {"type":"busy","message":"LED busy","category":"resource","kind":"busy","dropped":3}
+    #[serde(rename = "busy")]
+    Busy {
+        message: &'static str,
+        category: &'static str,
+        kind: &'static str,
+        /// Verdrängte und verworfene Kommandos seit dem Start
+        dropped: u32,
+    },
+    #[serde(rename = "hello")]
+    Hello {
+        version: &'static str,
+        git_hash: &'static str,
+        build_timestamp: u64,
+    },
+    #[serde(rename = "schedule")]
+    Schedule { entries: S },
+    /// Periodische Heap-Telemetrie
+    /// Beispiel: {"type":"heap","used":41230,"free":61170,"high_water":52008,"size":102400,"usage_percent":40}
+    #[serde(rename = "heap")]
+    Heap(H),
+}
+
+impl<S, H> WsServerMessage<S, H> {
+    /// Status-Update zu einer Farbmeldung des LED-Tasks
+    ///
+    /// Zufallsfarben melden wie der Auto-Modus `"mode":"auto"`.
+    pub fn status(msg: &LedColorMessage, language: Language) -> Self {
+        WsServerMessage::Status {
+            color: msg.color_id,
+            label: msg.display_name(language),
+            rgb: msg.color,
+            timestamp_ms: msg.timestamp_ms,
+            mode: if msg.is_auto_mode {
+                OperationMode::Auto
+            } else {
+                OperationMode::Manual
+            },
+            seq: msg.sequence,
+        }
+    }
+}
+
+/// Client → Server: Authentifizierung
+/// Beispiel: {"type":"auth","token":"geheim"}
+///
+/// Nur die erste Nachricht wird so gelesen, und nur wenn WS_AUTH_TOKEN gesetzt ist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct WsAuthMessage<'a> {
+    #[serde(rename = "type")]
+    pub msg_type: MessageType,
+    pub token: &'a str,
+}
+
+/// Prüft, ob `data` eine `auth`-Nachricht mit dem erwarteten Token ist
+pub fn is_valid_auth(data: &[u8], token: &str) -> bool {
+    matches!(
+        serde_json_core::from_slice::<WsAuthMessage>(data),
+        Ok((auth, _)) if auth.msg_type == MessageType::Auth && token_matches(token, auth.token)
+    )
+}
+
+/// Ergebnis einer Client-Nachricht
+pub enum WsRequest<E> {
+    /// An den LED-Task weiterreichen
+    Command(LedCommand),
+    /// Zeitplan senden
+    ScheduleGet,
+    /// Eintrag anlegen, danach Zeitplan senden
+    ScheduleAdd(E),
+    /// Eintrag per ID löschen, danach Zeitplan senden
+    ScheduleRemove(u8),
+    /// Fehlermeldung an den Client (`{"type":"error","message":...}`)
+    Error(&'static str),
+    /// Nichts zu tun (fehlende optionale Felder, `auth` nach der Anmeldung)
+    Ignored,
+}
+
+/// Parst eine Text-Nachricht des Clients und ordnet sie zu
+///
+/// `brightness` gilt für Farben aus `set_color` (wie `LED_BRIGHTNESS`).
+/// Ungültiges JSON und unbekannte Typen ergeben `Error("JSON parse error")`.
+pub fn parse_ws_message<'a, E: Deserialize<'a>>(data: &'a [u8], brightness: u8) -> WsRequest<E> {
+    let Ok((msg, _)) = serde_json_core::from_slice::<WsClientMessage<'a, E>>(data) else {
+        crate::log_debug!("WS: JSON parse error");
+        return WsRequest::Error("JSON parse error");
+    };
+    dispatch(msg, brightness)
+}
+
+/// Ordnet eine bereits gelesene Nachricht zu
+pub fn dispatch<E>(msg: WsClientMessage<'_, E>, brightness: u8) -> WsRequest<E> {
+    let command = match msg.msg_type {
+        MessageType::SetColor => {
+            // Nicht wählbare Farben ("Unbekannt") werden ignoriert
+            let command = msg.color.and_then(|color| color.command(brightness));
+            match command {
+                Some(command) => command.with_transition(msg.transition_ms.unwrap_or(0)),
+                None => return WsRequest::Ignored,
+            }
+        }
+        MessageType::SetMode => match msg.mode {
+            Some(OperationMode::Auto) => LedCommand::EnableAuto,
+            Some(OperationMode::Random) => LedCommand::EnableRandom,
+            // Manuell wird durch das nächste Farb-Kommando gesetzt
+            Some(OperationMode::Manual) | None => return WsRequest::Ignored,
+        },
+        MessageType::SleepTimer => match msg.minutes {
+            Some(minutes) => LedCommand::SleepTimer { minutes },
+            None => return WsRequest::Ignored,
+        },
+        MessageType::ApplyPreset => match msg.id {
+            Some(id) => LedCommand::ApplyPreset { id },
+            None => return WsRequest::Error("Missing id"),
+        },
+        MessageType::Calibrate => LedCommand::Calibrate,
+        MessageType::TestPattern => LedCommand::TestPattern,
+        MessageType::ScrollText => match msg.text.and_then(TextMessage::new) {
+            Some(text) => LedCommand::ScrollText { text },
+            None => return WsRequest::Error("Invalid text"),
+        },
+        MessageType::ShowClock => match msg.hours {
+            None => LedCommand::ShowClock { format: None },
+            Some(24) => LedCommand::ShowClock {
+                format: Some(ClockFormat::H24),
+            },
+            Some(12) => LedCommand::ShowClock {
+                format: Some(ClockFormat::H12),
+            },
+            Some(_) => return WsRequest::Error("Invalid hours"),
+        },
+        MessageType::SetSequence => match msg.colors.map(str::trim) {
+            None | Some("") => LedCommand::SetSequence { sequence: None },
+            Some(colors) => match ColorSequence::parse(colors) {
+                Some(sequence) => LedCommand::SetSequence {
+                    sequence: Some(sequence),
+                },
+                None => return WsRequest::Error("Invalid color sequence"),
+            },
+        },
+        MessageType::ShowGradient => match msg.colors.and_then(Gradient::parse) {
+            Some(gradient) => LedCommand::ShowGradient { gradient },
+            None => return WsRequest::Error("Invalid gradient"),
+        },
+        MessageType::Meteor => {
+            let color = match msg.colors.map(str::trim) {
+                None | Some("") => None,
+                Some(word) => match parse_color_word(word) {
+                    Some(color) => Some(color),
+                    None => return WsRequest::Error("Invalid color"),
+                },
+            };
+            let speed = match msg.speed {
+                None => DEFAULT_METEOR_SPEED,
+                Some(0) => return WsRequest::Error("Invalid speed"),
+                Some(speed) => speed,
+            };
+            LedCommand::Meteor {
+                color,
+                speed,
+                tail: msg.tail.unwrap_or(DEFAULT_METEOR_TAIL),
+            }
+        }
+        MessageType::Plasma => LedCommand::Plasma {
+            speed: msg.speed.unwrap_or(DEFAULT_PLASMA_SPEED),
+        },
+        MessageType::SetEffectParam => {
+            let Some(name) = msg.name.and_then(ParamName::parse) else {
+                return WsRequest::Error("Invalid param");
+            };
+            let value = match (msg.value, msg.colors) {
+                (Some(value), _) => ParamValue::integer(name, value),
+                (None, Some(colors)) => ParamValue::parse(name, colors),
+                (None, None) => Err(ParamError::InvalidValue),
+            };
+            match value {
+                Ok(value) => LedCommand::SetEffectParam { value },
+                Err(_) => return WsRequest::Error("Invalid value"),
+            }
+        }
+        MessageType::ScheduleGet => return WsRequest::ScheduleGet,
+        MessageType::ScheduleAdd => {
+            return match msg.entry {
+                Some(entry) => WsRequest::ScheduleAdd(entry),
+                None => WsRequest::Error("Missing entry"),
+            };
+        }
+        MessageType::ScheduleRemove => {
+            return match msg.id {
+                Some(id) => WsRequest::ScheduleRemove(id),
+                None => WsRequest::Error("Missing id"),
+            };
+        }
+        // Bereits verbunden (oder kein Token konfiguriert)
+        MessageType::Auth => return WsRequest::Ignored,
+    };
+    WsRequest::Command(command)
+}
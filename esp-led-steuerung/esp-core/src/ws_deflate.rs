@@ -0,0 +1,898 @@
+//! WebSocket-Kompression `permessage-deflate` (RFC 7692)
+//!
+//! Dashboards mit vielen offenen Geräten empfangen ständig Status- und
+//! Telemetrie-JSON, das sich von Nachricht zu Nachricht kaum ändert. Mit
+//! `permessage-deflate` verweist eine Nachricht auf die vorherigen, aus
+//! ~200 Bytes werden oft unter 40.
+//!
+//! - Aushandlung: [`negotiate_permessage_deflate`] prüft das Angebot des
+//!   Browsers (`Sec-WebSocket-Extensions`), [`DeflateParams::write_response`]
+//!   schreibt die Antwort; [`RequestScanner`] und [`ResponseScanner`] finden
+//!   Angebot bzw. Einfügestelle im laufenden Datenstrom
+//! - Senden: [`Deflater`] komprimiert mit den festen Huffman-Codes (keine
+//!   dynamischen Tabellen) gegen ein kleines Wörterbuch fester Größe: die
+//!   zuletzt gesendeten Bytes derselben Verbindung (Context Takeover)
+//! - Empfangen: [`inflate_message`] entpackt Nachrichten des Browsers; die
+//!   Antwort verlangt `client_no_context_takeover`, damit reicht der
+//!   Nachrichtenpuffer selbst als Fenster
+//! - Frames: [`parse_frame_header`] und [`write_frame_header`] für die
+//!   Umschreibung zwischen Socket und HTTP-Server (RSV1 = komprimiert)
+//!
+//! Ein vorab vereinbartes Wörterbuch sieht RFC 7692 nicht vor (der Browser
+//! entpackt selbst), daher dienen die bereits gesendeten Nachrichten als
+//! Wörterbuch.
+
+use core::fmt;
+
+/// Name der Erweiterung im Header `Sec-WebSocket-Extensions`
+pub const PERMESSAGE_DEFLATE: &str = "permessage-deflate";
+
+/// Header mit Angebot (Request) bzw. Zusage (Antwort)
+pub const EXTENSIONS_HEADER: &str = "Sec-WebSocket-Extensions";
+
+/// Angehängter Rest des leeren Stored-Blocks, den RFC 7692 pro Nachricht entfernt
+const SYNC_FLUSH_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Größte Fenstergröße (`max_window_bits`) laut RFC 1951
+const MAX_WINDOW_BITS: u8 = 15;
+
+/// Ausgehandelte Parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeflateParams {
+    /// Browser verlangt jede Nachricht unabhängig (`server_no_context_takeover`)
+    pub server_no_context_takeover: bool,
+    /// Größte erlaubte Rückwärts-Distanz als Zweierpotenz (8..=15)
+    pub server_max_window_bits: u8,
+}
+
+impl DeflateParams {
+    /// Schreibt den Wert für `Sec-WebSocket-Extensions` der Antwort
+    ///
+    /// Nachrichten des Browsers sind immer unabhängig (`client_no_context_takeover`).
+    pub fn write_response(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str(PERMESSAGE_DEFLATE)?;
+        if self.server_no_context_takeover {
+            out.write_str("; server_no_context_takeover")?;
+        }
+        if self.server_max_window_bits < MAX_WINDOW_BITS {
+            write!(
+                out,
+                "; server_max_window_bits={}",
+                self.server_max_window_bits
+            )?;
+        }
+        out.write_str("; client_no_context_takeover")
+    }
+}
+
+/// Wählt das erste erfüllbare `permessage-deflate`-Angebot
+///
+/// `header` ist der Wert von `Sec-WebSocket-Extensions` (mehrere Angebote
+/// durch Komma getrennt). Angebote mit unbekannten oder ungültigen Parametern
+/// werden übergangen.
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::ws_deflate::negotiate_permessage_deflate;
+/// let params =
+///     negotiate_permessage_deflate("permessage-deflate; client_max_window_bits").unwrap();
+/// let mut response = String::new();
+/// params.write_response(&mut response).unwrap();
+/// assert_eq!(response, "permessage-deflate; client_no_context_takeover");
+/// ```
+pub fn negotiate_permessage_deflate(header: &str) -> Option<DeflateParams> {
+    header.split(',').find_map(parse_offer)
+}
+
+/// Ein Angebot: Name, dann Parameter durch `;` getrennt
+fn parse_offer(offer: &str) -> Option<DeflateParams> {
+    let mut parts = offer.split(';').map(str::trim);
+    if !parts.next()?.eq_ignore_ascii_case(PERMESSAGE_DEFLATE) {
+        return None;
+    }
+    let mut params = DeflateParams {
+        server_no_context_takeover: false,
+        server_max_window_bits: MAX_WINDOW_BITS,
+    };
+    for part in parts {
+        let (name, value) = match part.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+            None => (part, None),
+        };
+        match (name, value) {
+            ("server_no_context_takeover", None) => params.server_no_context_takeover = true,
+            ("client_no_context_takeover", None) => {}
+            // Ohne Wert nur ein Hinweis, dass der Browser kleinere Fenster beherrscht
+            ("client_max_window_bits", None) => {}
+            ("client_max_window_bits" | "server_max_window_bits", Some(value)) => {
+                let bits: u8 = value.parse().ok()?;
+                if !(8..=MAX_WINDOW_BITS).contains(&bits) {
+                    return None;
+                }
+                if name == "server_max_window_bits" {
+                    params.server_max_window_bits = bits;
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(params)
+}
+
+// ============================================================================
+// HTTP-Kopf (Aushandlung im Datenstrom)
+// ============================================================================
+
+/// Liest den Request-Kopf zeilenweise mit und merkt sich das Angebot
+///
+/// Zeilen länger als `LINE` Bytes werden übergangen (das Angebot ist kurz).
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::ws_deflate::RequestScanner;
+/// let mut scanner: RequestScanner<128> = RequestScanner::new();
+/// assert!(!scanner.feed(b"GET /ws HTTP/1.1\r\nSec-WebSocket-Extensions: permessage-"));
+/// assert!(scanner.feed(b"deflate\r\n\r\n"));
+/// assert!(scanner.offer().is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RequestScanner<const LINE: usize> {
+    line: [u8; LINE],
+    len: usize,
+    /// Aktuelle Zeile zu lang, Rest bis zum Zeilenende verwerfen
+    overflow: bool,
+    offer: Option<DeflateParams>,
+    finished: bool,
+}
+
+impl<const LINE: usize> RequestScanner<LINE> {
+    pub const fn new() -> Self {
+        Self {
+            line: [0; LINE],
+            len: 0,
+            overflow: false,
+            offer: None,
+            finished: false,
+        }
+    }
+
+    /// Verarbeitet empfangene Bytes, `true` sobald der Kopf vollständig ist
+    pub fn feed(&mut self, bytes: &[u8]) -> bool {
+        for &byte in bytes {
+            if self.finished {
+                break;
+            }
+            if byte == b'\n' {
+                self.end_line();
+            } else if self.len < LINE {
+                self.line[self.len] = byte;
+                self.len += 1;
+            } else {
+                self.overflow = true;
+            }
+        }
+        self.finished
+    }
+
+    /// Erfüllbares Angebot aus dem Kopf (erst nach Ende des Kopfs endgültig)
+    pub fn offer(&self) -> Option<DeflateParams> {
+        self.offer
+    }
+
+    fn end_line(&mut self) {
+        let line = self.line[..self.len]
+            .strip_suffix(b"\r")
+            .unwrap_or(&self.line[..self.len]);
+        if line.is_empty() && !self.overflow {
+            self.finished = true;
+        } else if !self.overflow && self.offer.is_none() {
+            self.offer = core::str::from_utf8(line)
+                .ok()
+                .and_then(|line| line.split_once(':'))
+                .filter(|(name, _)| name.trim().eq_ignore_ascii_case(EXTENSIONS_HEADER))
+                .and_then(|(_, value)| negotiate_permessage_deflate(value));
+        }
+        self.len = 0;
+        self.overflow = false;
+    }
+}
+
+impl<const LINE: usize> Default for RequestScanner<LINE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sucht im gesendeten Antwort-Kopf die Stelle für die Zusage
+///
+/// Nur `101 Switching Protocols` (Upgrade angenommen) bekommt die Zeile
+/// `Sec-WebSocket-Extensions`, eingefügt vor der Leerzeile am Kopfende.
+#[derive(Debug, Clone)]
+pub struct ResponseScanner {
+    pos: usize,
+    upgrade: bool,
+    line_start: bool,
+    finished: bool,
+}
+
+/// Statuszeile eines angenommenen Upgrades
+const SWITCHING_PROTOCOLS: &[u8] = b"HTTP/1.1 101 ";
+
+impl ResponseScanner {
+    pub const fn new() -> Self {
+        Self {
+            pos: 0,
+            upgrade: true,
+            line_start: false,
+            finished: false,
+        }
+    }
+
+    /// Verarbeitet gesendete Bytes; liefert bei einem Upgrade den Index in
+    /// `bytes`, vor dem die Zusage einzufügen ist
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::ws_deflate::ResponseScanner;
+    /// let mut scanner = ResponseScanner::new();
+    /// let head = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n";
+    /// assert_eq!(scanner.feed(head), Some(head.len() - 2));
+    /// assert!(scanner.is_finished() && scanner.is_upgrade());
+    /// ```
+    pub fn feed(&mut self, bytes: &[u8]) -> Option<usize> {
+        for (index, &byte) in bytes.iter().enumerate() {
+            if self.finished {
+                break;
+            }
+            // Kürzere Statuszeilen sind kein Upgrade
+            let expected = SWITCHING_PROTOCOLS.get(self.pos);
+            self.upgrade &= expected.is_none_or(|&expected| byte == expected);
+            self.pos += 1;
+            // Leerzeile: Kopf zu Ende
+            if self.line_start && (byte == b'\r' || byte == b'\n') {
+                self.finished = true;
+                self.upgrade &= self.pos > SWITCHING_PROTOCOLS.len();
+                return self.upgrade.then_some(index);
+            }
+            self.line_start = byte == b'\n';
+        }
+        None
+    }
+
+    /// Kopf vollständig gesendet
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Statuszeile war `101 Switching Protocols`
+    pub fn is_upgrade(&self) -> bool {
+        self.finished && self.upgrade
+    }
+}
+
+impl Default for ResponseScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Kompression (feste Huffman-Codes, RFC 1951 Abschnitt 3.2.6)
+// ============================================================================
+
+/// Basislängen der Längen-Codes 257..=285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// Basisdistanzen der Distanz-Codes 0..=29
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Kürzeste und längste Übereinstimmung
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+/// Einträge der Hash-Tabelle (Zweierpotenz, je 2 Bytes Stack)
+const HASH_SIZE: usize = 512;
+
+/// Schreibt Bits LSB-zuerst (Huffman-Codes MSB-zuerst, siehe `write_code`)
+struct BitWriter<'a> {
+    out: &'a mut [u8],
+    len: usize,
+    bits: u32,
+    count: u32,
+}
+
+impl<'a> BitWriter<'a> {
+    fn new(out: &'a mut [u8]) -> Self {
+        Self {
+            out,
+            len: 0,
+            bits: 0,
+            count: 0,
+        }
+    }
+
+    fn write_bits(&mut self, value: u32, count: u32) -> Option<()> {
+        self.bits |= value << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            *self.out.get_mut(self.len)? = self.bits as u8;
+            self.len += 1;
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+        Some(())
+    }
+
+    /// Huffman-Code mit `count` Bits, höchstwertiges Bit zuerst
+    fn write_code(&mut self, code: u32, count: u32) -> Option<()> {
+        self.write_bits(code.reverse_bits() >> (32 - count), count)
+    }
+
+    /// Füllt bis zur Byte-Grenze auf und liefert die Länge
+    fn finish(mut self) -> Option<usize> {
+        if self.count > 0 {
+            self.write_bits(0, 8 - self.count)?;
+        }
+        Some(self.len)
+    }
+}
+
+/// Literal bzw. Längen-Symbol (0..=287) mit festem Code
+fn write_fixed_symbol(writer: &mut BitWriter, symbol: u16) -> Option<()> {
+    let symbol = u32::from(symbol);
+    match symbol {
+        0..=143 => writer.write_code(0x30 + symbol, 8),
+        144..=255 => writer.write_code(0x190 + symbol - 144, 9),
+        256..=279 => writer.write_code(symbol - 256, 7),
+        _ => writer.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+/// Index des größten Basiswerts ≤ `value`
+fn code_index(bases: &[u16], value: usize) -> usize {
+    bases
+        .iter()
+        .rposition(|&base| usize::from(base) <= value)
+        .unwrap_or(0)
+}
+
+fn write_match(writer: &mut BitWriter, length: usize, distance: usize) -> Option<()> {
+    let index = code_index(&LENGTH_BASE, length);
+    write_fixed_symbol(writer, 257 + index as u16)?;
+    writer.write_bits(
+        (length - usize::from(LENGTH_BASE[index])) as u32,
+        u32::from(LENGTH_EXTRA[index]),
+    )?;
+    let index = code_index(&DIST_BASE, distance);
+    writer.write_code(index as u32, 5)?;
+    writer.write_bits(
+        (distance - usize::from(DIST_BASE[index])) as u32,
+        u32::from(DIST_EXTRA[index]),
+    )
+}
+
+/// Komprimiert die Nachrichten einer Verbindung (Server → Browser)
+///
+/// Das Wörterbuch umfasst die letzten `WINDOW` gesendeten Bytes; es wächst nur
+/// mit tatsächlich komprimiert gesendeten Nachrichten, denn unkomprimierte
+/// sieht der Dekompressor des Browsers nicht.
+#[derive(Debug, Clone)]
+pub struct Deflater<const WINDOW: usize> {
+    history: [u8; WINDOW],
+    history_len: usize,
+    context_takeover: bool,
+    max_distance: usize,
+}
+
+impl<const WINDOW: usize> Deflater<WINDOW> {
+    pub fn new(params: DeflateParams) -> Self {
+        Self {
+            history: [0; WINDOW],
+            history_len: 0,
+            context_takeover: !params.server_no_context_takeover,
+            max_distance: 1usize << params.server_max_window_bits.min(MAX_WINDOW_BITS),
+        }
+    }
+
+    /// Komprimiert eine Nachricht nach `out` (ohne `00 00 ff ff`)
+    ///
+    /// `None`, wenn das Ergebnis nicht kleiner als `input` wäre oder nicht in
+    /// `out` passt; die Nachricht geht dann unkomprimiert raus.
+    ///
+    /// # Beispiel
+    /// ```
+    /// # use esp_core::ws_deflate::{Deflater, negotiate_permessage_deflate};
+    /// let params = negotiate_permessage_deflate("permessage-deflate").unwrap();
+    /// let mut deflater: Deflater<256> = Deflater::new(params);
+    /// let mut out = [0u8; 64];
+    /// // Beispiel aus RFC 7692 Abschnitt 7.2.3.1
+    /// let len = deflater.compress_any(b"Hello", &mut out).unwrap();
+    /// assert_eq!(&out[..len], &[0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00]);
+    /// ```
+    pub fn compress(&mut self, input: &[u8], out: &mut [u8]) -> Option<usize> {
+        let len = self.encode(input, out).filter(|&len| len < input.len())?;
+        self.remember(input);
+        Some(len)
+    }
+
+    /// Wie [`Self::compress`], aber auch wenn das Ergebnis nicht kleiner ist
+    pub fn compress_any(&mut self, input: &[u8], out: &mut [u8]) -> Option<usize> {
+        let len = self.encode(input, out)?;
+        self.remember(input);
+        Some(len)
+    }
+
+    fn encode(&self, input: &[u8], out: &mut [u8]) -> Option<usize> {
+        // Position p im Wörterbuch gefolgt von der Nachricht
+        let history = &self.history[..self.history_len];
+        let total = history.len() + input.len();
+        // Positionen passen in die Hash-Tabelle (u16)
+        if total >= usize::from(u16::MAX) {
+            return None;
+        }
+        let byte_at = |p: usize| {
+            if p < history.len() {
+                history[p]
+            } else {
+                input[p - history.len()]
+            }
+        };
+        let hash_at = |p: usize| {
+            (usize::from(byte_at(p)) << 6
+                ^ usize::from(byte_at(p + 1)) << 3
+                ^ usize::from(byte_at(p + 2)))
+                % HASH_SIZE
+        };
+
+        // Letzte Position + 1 je Hash (0 = leer)
+        let mut head = [0u16; HASH_SIZE];
+        for p in 0..history.len().min(total.saturating_sub(MIN_MATCH - 1)) {
+            head[hash_at(p)] = (p + 1) as u16;
+        }
+
+        let mut writer = BitWriter::new(out);
+        // Block-Kopf: BFINAL = 0, BTYPE = 01 (feste Codes)
+        writer.write_bits(0b010, 3)?;
+        let mut p = history.len();
+        while p < total {
+            let mut length = 0;
+            let mut distance = 0;
+            if p + MIN_MATCH <= total {
+                let hash = hash_at(p);
+                let candidate = usize::from(head[hash]);
+                head[hash] = (p + 1) as u16;
+                if candidate > 0 && p - (candidate - 1) <= self.max_distance {
+                    let start = candidate - 1;
+                    let limit = MAX_MATCH.min(total - p);
+                    while length < limit && byte_at(start + length) == byte_at(p + length) {
+                        length += 1;
+                    }
+                    distance = p - start;
+                }
+            }
+            if length >= MIN_MATCH {
+                write_match(&mut writer, length, distance)?;
+                // Positionen innerhalb der Übereinstimmung für spätere Treffer merken
+                for q in p + 1..(p + length).min(total.saturating_sub(MIN_MATCH - 1)) {
+                    head[hash_at(q)] = (q + 1) as u16;
+                }
+                p += length;
+            } else {
+                write_fixed_symbol(&mut writer, u16::from(byte_at(p)))?;
+                p += 1;
+            }
+        }
+        // Blockende, dann leerer Stored-Block (BFINAL = 0, BTYPE = 00); dessen
+        // LEN/NLEN (00 00 ff ff) entfällt nach RFC 7692
+        write_fixed_symbol(&mut writer, 256)?;
+        writer.write_bits(0, 3)?;
+        writer.finish()
+    }
+
+    /// Hängt gesendete Bytes ans Wörterbuch, älteste fallen heraus
+    fn remember(&mut self, input: &[u8]) {
+        if !self.context_takeover {
+            return;
+        }
+        if input.len() >= WINDOW {
+            self.history.copy_from_slice(&input[input.len() - WINDOW..]);
+            self.history_len = WINDOW;
+            return;
+        }
+        let keep = self.history_len.min(WINDOW - input.len());
+        self.history
+            .copy_within(self.history_len - keep..self.history_len, 0);
+        self.history[keep..keep + input.len()].copy_from_slice(input);
+        self.history_len = keep + input.len();
+    }
+}
+
+// ============================================================================
+// Dekompression (RFC 1951, alle Blocktypen)
+// ============================================================================
+
+/// Fehler beim Entpacken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InflateError {
+    /// Daten enden mitten in einem Block
+    Truncated,
+    /// Unbekannter Blocktyp oder LEN/NLEN passen nicht
+    InvalidBlock,
+    /// Ungültige Huffman-Tabelle oder unbekanntes Symbol
+    InvalidCode,
+    /// Rückverweis vor den Anfang der Nachricht
+    InvalidDistance,
+    /// Entpackte Nachricht passt nicht in den Ausgabe-Buffer
+    OutputFull,
+}
+
+/// Liest Bits LSB-zuerst, nach den Daten folgt `00 00 ff ff`
+struct BitReader<'a> {
+    data: &'a [u8],
+    /// Position in Bits über Daten und Anhang
+    pos: usize,
+}
+
+impl BitReader<'_> {
+    fn total_bits(&self) -> usize {
+        (self.data.len() + SYNC_FLUSH_TAIL.len()) * 8
+    }
+
+    fn byte(&self, index: usize) -> u8 {
+        match self.data.get(index) {
+            Some(&byte) => byte,
+            None => SYNC_FLUSH_TAIL[index - self.data.len()],
+        }
+    }
+
+    fn bits(&mut self, count: u32) -> Result<u32, InflateError> {
+        if self.pos + count as usize > self.total_bits() {
+            return Err(InflateError::Truncated);
+        }
+        let mut value = 0;
+        for i in 0..count {
+            let bit = (self.byte(self.pos / 8) >> (self.pos % 8)) & 1;
+            value |= u32::from(bit) << i;
+            self.pos += 1;
+        }
+        Ok(value)
+    }
+
+    fn align(&mut self) {
+        self.pos = self.pos.div_ceil(8) * 8;
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.total_bits()
+    }
+}
+
+/// Kanonische Huffman-Tabelle: Anzahl Codes je Länge, Symbole nach Code sortiert
+struct Huffman {
+    counts: [u16; 16],
+    symbols: [u16; 288],
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, InflateError> {
+        let mut table = Self {
+            counts: [0; 16],
+            symbols: [0; 288],
+        };
+        for &length in lengths {
+            table.counts[usize::from(length)] += 1;
+        }
+        table.counts[0] = 0;
+
+        // Überbelegte Tabellen ablehnen
+        let mut left: i32 = 1;
+        for &count in &table.counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(InflateError::InvalidCode);
+            }
+        }
+
+        let mut offsets = [0u16; 16];
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + table.counts[length];
+        }
+        for (symbol, &length) in lengths.iter().enumerate() {
+            if length != 0 {
+                let offset = &mut offsets[usize::from(length)];
+                table.symbols[usize::from(*offset)] = symbol as u16;
+                *offset += 1;
+            }
+        }
+        Ok(table)
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, InflateError> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+        for &count in &self.counts[1..] {
+            code |= reader.bits(1)? as i32;
+            let count = i32::from(count);
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(InflateError::InvalidCode)
+    }
+}
+
+/// Reihenfolge der Code-Längen-Codes in dynamischen Blöcken
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Entpackt eine komprimierte Nachricht des Browsers (ohne `00 00 ff ff`)
+///
+/// Jede Nachricht ist unabhängig (`client_no_context_takeover`), Rückverweise
+/// zeigen nur in `out`.
+///
+/// # Beispiel
+/// ```
+/// # use esp_core::ws_deflate::inflate_message;
+/// let mut out = [0u8; 16];
+/// let len = inflate_message(&[0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00], &mut out).unwrap();
+/// assert_eq!(&out[..len], b"Hello");
+/// ```
+pub fn inflate_message(input: &[u8], out: &mut [u8]) -> Result<usize, InflateError> {
+    let mut reader = BitReader {
+        data: input,
+        pos: 0,
+    };
+    let mut len = 0;
+    while !reader.at_end() {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => len = inflate_stored(&mut reader, out, len)?,
+            1 => {
+                let (literals, distances) = fixed_tables()?;
+                len = inflate_block(&mut reader, out, len, &literals, &distances)?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_tables(&mut reader)?;
+                len = inflate_block(&mut reader, out, len, &literals, &distances)?;
+            }
+            _ => return Err(InflateError::InvalidBlock),
+        }
+        if last {
+            break;
+        }
+    }
+    Ok(len)
+}
+
+fn inflate_stored(
+    reader: &mut BitReader,
+    out: &mut [u8],
+    mut len: usize,
+) -> Result<usize, InflateError> {
+    reader.align();
+    let size = reader.bits(16)?;
+    if reader.bits(16)? != !size & 0xffff {
+        return Err(InflateError::InvalidBlock);
+    }
+    for _ in 0..size {
+        *out.get_mut(len).ok_or(InflateError::OutputFull)? = reader.bits(8)? as u8;
+        len += 1;
+    }
+    Ok(len)
+}
+
+fn fixed_tables() -> Result<(Huffman, Huffman), InflateError> {
+    let mut lengths = [0u8; 288];
+    for (symbol, length) in lengths.iter_mut().enumerate() {
+        *length = match symbol {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?))
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), InflateError> {
+    let literal_count = reader.bits(5)? as usize + 257;
+    let distance_count = reader.bits(5)? as usize + 1;
+    let code_length_count = reader.bits(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(InflateError::InvalidCode);
+    }
+
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[index] = reader.bits(3)? as u8;
+    }
+    let code_length_table = Huffman::new(&code_lengths)?;
+
+    let mut lengths = [0u8; 286 + 30];
+    let total = literal_count + distance_count;
+    let mut index = 0;
+    while index < total {
+        let symbol = code_length_table.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                // Wiederholt die vorige Länge
+                if index == 0 {
+                    return Err(InflateError::InvalidCode);
+                }
+                (lengths[index - 1], 3 + reader.bits(2)? as usize)
+            }
+            17 => (0, 3 + reader.bits(3)? as usize),
+            _ => (0, 11 + reader.bits(7)? as usize),
+        };
+        if index + repeat > total {
+            return Err(InflateError::InvalidCode);
+        }
+        lengths[index..index + repeat].fill(value);
+        index += repeat;
+    }
+    if lengths[256] == 0 {
+        return Err(InflateError::InvalidCode);
+    }
+    Ok((
+        Huffman::new(&lengths[..literal_count])?,
+        Huffman::new(&lengths[literal_count..total])?,
+    ))
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut [u8],
+    mut len: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Result<usize, InflateError> {
+    loop {
+        let symbol = usize::from(literals.decode(reader)?);
+        match symbol {
+            0..=255 => {
+                *out.get_mut(len).ok_or(InflateError::OutputFull)? = symbol as u8;
+                len += 1;
+            }
+            256 => return Ok(len),
+            257..=285 => {
+                let index = symbol - 257;
+                let length = usize::from(LENGTH_BASE[index])
+                    + reader.bits(u32::from(LENGTH_EXTRA[index]))? as usize;
+                let index = usize::from(distances.decode(reader)?);
+                if index >= DIST_BASE.len() {
+                    return Err(InflateError::InvalidCode);
+                }
+                let distance = usize::from(DIST_BASE[index])
+                    + reader.bits(u32::from(DIST_EXTRA[index]))? as usize;
+                if distance > len {
+                    return Err(InflateError::InvalidDistance);
+                }
+                if len + length > out.len() {
+                    return Err(InflateError::OutputFull);
+                }
+                // Byteweise, Quelle und Ziel dürfen sich überlappen
+                for _ in 0..length {
+                    out[len] = out[len - distance];
+                    len += 1;
+                }
+            }
+            _ => return Err(InflateError::InvalidCode),
+        }
+    }
+}
+
+// ============================================================================
+// Frames (RFC 6455 Abschnitt 5.2)
+// ============================================================================
+
+/// Opcodes von Datenframes
+pub const OPCODE_TEXT: u8 = 0x1;
+pub const OPCODE_BINARY: u8 = 0x2;
+
+/// Längster Frame-Kopf (2 + 8 Bytes Länge + 4 Bytes Maske)
+pub const MAX_FRAME_HEADER_LEN: usize = 14;
+
+/// Kopf eines WebSocket-Frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    pub fin: bool,
+    /// Bei `permessage-deflate`: Nachricht komprimiert
+    pub rsv1: bool,
+    pub opcode: u8,
+    /// Maske (nur Frames vom Browser)
+    pub mask: Option<[u8; 4]>,
+    pub payload_len: u64,
+    /// Länge des Kopfs in Bytes
+    pub header_len: usize,
+}
+
+impl FrameHeader {
+    /// Datenframe (Text, Binär, Fortsetzung) statt Steuerframe
+    pub fn is_data(&self) -> bool {
+        self.opcode & 0x8 == 0
+    }
+}
+
+/// Liest einen Frame-Kopf, `None` solange `bytes` ihn noch nicht vollständig enthält
+pub fn parse_frame_header(bytes: &[u8]) -> Option<FrameHeader> {
+    let [first, second, ..] = *bytes else {
+        return None;
+    };
+    let masked = second & 0x80 != 0;
+    let (payload_len, mut header_len) = match second & 0x7f {
+        126 => (
+            u64::from(u16::from_be_bytes(bytes.get(2..4)?.try_into().ok()?)),
+            4,
+        ),
+        127 => (u64::from_be_bytes(bytes.get(2..10)?.try_into().ok()?), 10),
+        len => (u64::from(len), 2),
+    };
+    let mask = if masked {
+        let mask = bytes.get(header_len..header_len + 4)?.try_into().ok()?;
+        header_len += 4;
+        Some(mask)
+    } else {
+        None
+    };
+    Some(FrameHeader {
+        fin: first & 0x80 != 0,
+        rsv1: first & 0x40 != 0,
+        opcode: first & 0x0f,
+        mask,
+        payload_len,
+        header_len,
+    })
+}
+
+/// Schreibt einen Frame-Kopf, liefert seine Länge (`out` ≥ [`MAX_FRAME_HEADER_LEN`])
+pub fn write_frame_header(out: &mut [u8], header: &FrameHeader) -> usize {
+    out[0] = u8::from(header.fin) << 7 | u8::from(header.rsv1) << 6 | header.opcode & 0x0f;
+    let mask_bit = if header.mask.is_some() { 0x80 } else { 0 };
+    let mut len = match header.payload_len {
+        0..=125 => {
+            out[1] = mask_bit | header.payload_len as u8;
+            2
+        }
+        126..=0xffff => {
+            out[1] = mask_bit | 126;
+            out[2..4].copy_from_slice(&(header.payload_len as u16).to_be_bytes());
+            4
+        }
+        _ => {
+            out[1] = mask_bit | 127;
+            out[2..10].copy_from_slice(&header.payload_len.to_be_bytes());
+            10
+        }
+    };
+    if let Some(mask) = header.mask {
+        out[len..len + 4].copy_from_slice(&mask);
+        len += 4;
+    }
+    len
+}
+
+/// Maskiert bzw. demaskiert Nutzdaten (XOR, symmetrisch)
+pub fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
@@ -1,40 +1,52 @@
 // Build-Script: Wird vor dem Kompilieren ausgeführt
 // Konfiguriert den Linker für ESP32-C6 Embedded Rust
 
+#[path = "build/device_config.rs"]
+mod device_config;
+
 fn main() {
-    // Lade .env file für WiFi-Credentials
-    // Fehler ignorieren wenn .env nicht existiert (dann müssen ENV vars gesetzt sein)
-    if let Err(e) = dotenvy::dotenv() {
-        eprintln!("⚠️  .env file nicht gefunden: {}", e);
-        eprintln!("   Setze WIFI_SSID und WIFI_PASSWORD als Environment-Variablen");
+    // Lade .env file: Environment-Variablen überschreiben Werte aus device.toml
+    // (z.B. WIFI_PASSWORD nur lokal oder in CI, nicht im Repository)
+    if dotenvy::dotenv().is_ok() {
+        println!("cargo:rerun-if-changed=.env");
     }
 
-    // Gebe WiFi-Credentials an Rust-Compiler weiter
-    // Die Werte werden zur Compile-Zeit in den Code eingebacken
-    if let Ok(ssid) = std::env::var("WIFI_SSID") {
-        println!("cargo:rustc-env=WIFI_SSID={}", ssid);
-    }
-    if let Ok(password) = std::env::var("WIFI_PASSWORD") {
-        println!("cargo:rustc-env=WIFI_PASSWORD={}", password);
-    }
+    // device.toml prüfen und als typisierte Konstanten nach $OUT_DIR schreiben
+    // (eingebunden in src/config.rs, Pflichtwerte für MQTT nur mit Feature `mqtt` bzw. `mqtt-sn`,
+    // der Syslog-Empfänger nur mit `log-udp`)
+    let manifest_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    device_config::generate(
+        &manifest_dir,
+        &out_dir,
+        std::env::var_os("CARGO_FEATURE_MQTT").is_some(),
+        std::env::var_os("CARGO_FEATURE_MQTT_SN").is_some(),
+        std::env::var_os("CARGO_FEATURE_LOG_UDP").is_some(),
+    );
 
-    // Gebe MQTT-Konfiguration an Rust-Compiler weiter
-    // Die Werte werden zur Compile-Zeit in den Code eingebacken
-    if let Ok(broker) = std::env::var("MQTT_BROKER") {
-        println!("cargo:rustc-env=MQTT_BROKER={}", broker);
-    }
-    if let Ok(port) = std::env::var("MQTT_PORT") {
-        println!("cargo:rustc-env=MQTT_PORT={}", port);
-    }
-    if let Ok(client_id) = std::env::var("MQTT_CLIENT_ID") {
-        println!("cargo:rustc-env=MQTT_CLIENT_ID={}", client_id);
-    }
-    if let Ok(topic_color) = std::env::var("MQTT_TOPIC_COLOR") {
-        println!("cargo:rustc-env=MQTT_TOPIC_COLOR={}", topic_color);
-    }
-    if let Ok(topic_mode) = std::env::var("MQTT_TOPIC_MODE") {
-        println!("cargo:rustc-env=MQTT_TOPIC_MODE={}", topic_mode);
-    }
+    // Build-Informationen für Versions-Anzeige (/api/version, WebSocket, MQTT)
+    // Mit rerun-if-changed oben nur bei Quelltext-Änderungen neu ermitteln
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build");
+    println!("cargo:rerun-if-changed=src");
+    // Git-Hash: kurzer Commit-Hash, "unknown" wenn git nicht verfügbar ist
+    let git_hash = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_HASH={}", git_hash);
+
+    // Build-Zeitpunkt als Unix-Timestamp (Sekunden seit 1970-01-01 UTC)
+    let build_timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
 
     // Registriere hilfsbereiten Error-Handler für Linker-Fehler
     linker_be_nice();
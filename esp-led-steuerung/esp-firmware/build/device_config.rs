@@ -0,0 +1,666 @@
+// Build-Zeit-Konfiguration: device.toml → $OUT_DIR/device_config.rs
+//
+// Liest `device.toml` (Pfad über DEVICE_CONFIG änderbar), ergänzt Standardwerte,
+// prüft alle Werte und erzeugt daraus typisierte Konstanten für `config.rs`.
+// Environment-Variablen (auch aus .env) überschreiben einzelne Werte, z.B.
+// WIFI_PASSWORD in CI. Fehler werden gesammelt und gemeinsam ausgegeben.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Inhalt von device.toml (alle Abschnitte und Felder optional)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DeviceToml {
+    wifi: WifiSection,
+    mqtt: MqttSection,
+    mqtt_sn: MqttSnSection,
+    log_udp: LogUdpSection,
+    mdns: MdnsSection,
+    led: LedSection,
+    ui: UiSection,
+    sync: SyncSection,
+    system: SystemSection,
+    tcp: TcpSection,
+    websocket: WebsocketSection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WifiSection {
+    ssid: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MqttSection {
+    broker: Option<String>,
+    fallbacks: Option<Vec<String>>,
+    port: Option<u16>,
+    auto_publish_secs: Option<u16>,
+    client_id: Option<String>,
+    ha_discovery_prefix: Option<String>,
+    tasmota_topic: Option<String>,
+    topics: MqttTopics,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MqttTopics {
+    color: Option<String>,
+    mode: Option<String>,
+    info: Option<String>,
+    state: Option<String>,
+    heap: Option<String>,
+    health: Option<String>,
+    inputs: Option<String>,
+    command: Option<String>,
+    command_result: Option<String>,
+    group: Option<String>,
+    restart: Option<String>,
+    factory_reset: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MqttSnSection {
+    gateway: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LogUdpSection {
+    host: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MdnsSection {
+    hostname: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LedSection {
+    count: Option<usize>,
+    brightness: Option<u8>,
+    crossfade_ms: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct UiSection {
+    language: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SyncSection {
+    role: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SystemSection {
+    stall_reboot: Option<bool>,
+    status_led: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TcpSection {
+    keepalive_secs: Option<u16>,
+    timeout_secs: Option<u16>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct WebsocketSection {
+    auth_token: Option<String>,
+}
+
+/// Alle Environment-Variablen, die einen Wert aus device.toml überschreiben
+const ENV_OVERRIDES: &[&str] = &[
+    "WIFI_SSID",
+    "WIFI_PASSWORD",
+    "MQTT_BROKER",
+    "MQTT_BROKER_FALLBACKS",
+    "MQTT_PORT",
+    "MQTT_AUTO_PUBLISH_SECS",
+    "MQTT_CLIENT_ID",
+    "MQTT_TOPIC_COLOR",
+    "MQTT_TOPIC_MODE",
+    "MQTT_TOPIC_INFO",
+    "MQTT_TOPIC_STATE",
+    "MQTT_TOPIC_HEAP",
+    "MQTT_TOPIC_HEALTH",
+    "MQTT_TOPIC_INPUTS",
+    "MQTT_TOPIC_COMMAND",
+    "MQTT_TOPIC_COMMAND_RESULT",
+    "MQTT_TOPIC_GROUP",
+    "MQTT_TOPIC_RESTART",
+    "MQTT_TOPIC_FACTORY_RESET",
+    "MQTT_TASMOTA_TOPIC",
+    "HA_DISCOVERY_PREFIX",
+    "MQTT_SN_GATEWAY",
+    "LOG_UDP_HOST",
+    "MDNS_HOSTNAME",
+    "LED_COUNT",
+    "LED_BRIGHTNESS",
+    "LED_CROSSFADE_MS",
+    "UI_LANGUAGE",
+    "SYNC_ROLE",
+    "STALL_REBOOT",
+    "STATUS_LED",
+    "TCP_KEEPALIVE_SECS",
+    "TCP_TIMEOUT_SECS",
+    "WS_AUTH_TOKEN",
+];
+
+/// Sammelt Fehlermeldungen und erzeugt den Quelltext
+struct Generator {
+    out: String,
+    errors: Vec<String>,
+}
+
+impl Generator {
+    /// Wert aus der Environment-Variable `env`, sonst aus device.toml
+    fn string(&self, env: &str, value: Option<String>) -> Option<String> {
+        std::env::var(env).ok().or(value)
+    }
+
+    /// Zahl/Bool aus der Environment-Variable `env` (mit Prüfung), sonst aus device.toml
+    fn parsed<T: std::str::FromStr>(&mut self, env: &str, value: Option<T>) -> Option<T> {
+        match std::env::var(env) {
+            Ok(raw) => match raw.trim().parse() {
+                Ok(parsed) => Some(parsed),
+                Err(_) => {
+                    self.errors
+                        .push(format!("{env}: ungültiger Wert \"{raw}\""));
+                    None
+                }
+            },
+            Err(_) => value,
+        }
+    }
+
+    /// Pflichtwert: fehlt er, wird `key` als Fehler gemeldet (Konstante bleibt leer)
+    fn required(&mut self, key: &str, env: &str, value: Option<String>, needed: bool) -> String {
+        match value {
+            Some(value) if !value.is_empty() => value,
+            _ => {
+                if needed {
+                    self.errors
+                        .push(format!("{key} fehlt (device.toml oder Environment {env})"));
+                }
+                String::new()
+            }
+        }
+    }
+
+    /// Prüft ein MQTT-Topic (keine Wildcards, nicht leer)
+    fn check_topic(&mut self, key: &str, topic: &str) {
+        if topic.is_empty() || topic.contains(['+', '#']) || topic.contains(char::is_whitespace) {
+            self.errors.push(format!(
+                "{key}: ungültiges Topic \"{topic}\" (leer, Leerzeichen oder Wildcard)"
+            ));
+        }
+    }
+
+    fn str_const(&mut self, doc: &str, name: &str, value: &str) {
+        writeln!(self.out, "/// {doc}\npub const {name}: &str = {value:?};").unwrap();
+    }
+
+    fn opt_str_const(&mut self, doc: &str, name: &str, value: Option<&str>) {
+        match value {
+            Some(value) => writeln!(
+                self.out,
+                "/// {doc}\npub const {name}: Option<&str> = Some({value:?});"
+            ),
+            None => writeln!(
+                self.out,
+                "/// {doc}\npub const {name}: Option<&str> = None;"
+            ),
+        }
+        .unwrap();
+    }
+
+    fn raw_const(&mut self, doc: &str, name: &str, ty: &str, value: impl std::fmt::Display) {
+        writeln!(self.out, "/// {doc}\npub const {name}: {ty} = {value};").unwrap();
+    }
+}
+
+/// Liest device.toml und schreibt `$OUT_DIR/device_config.rs`
+///
+/// Bei ungültiger Konfiguration werden alle Fehler ausgegeben und der Build abgebrochen.
+/// Client-ID und Farb-/Modus-Topics sind mit `mqtt` oder `mqtt-sn` Pflicht,
+/// der Syslog-Empfänger mit `log-udp`.
+pub fn generate(
+    manifest_dir: &Path,
+    out_dir: &Path,
+    mqtt_enabled: bool,
+    mqtt_sn_enabled: bool,
+    log_udp_enabled: bool,
+) {
+    let path = std::env::var_os("DEVICE_CONFIG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join("device.toml"));
+    println!("cargo:rerun-if-changed={}", path.display());
+    println!("cargo:rerun-if-env-changed=DEVICE_CONFIG");
+    for env in ENV_OVERRIDES {
+        println!("cargo:rerun-if-env-changed={env}");
+    }
+
+    let config = match std::fs::read_to_string(&path) {
+        Ok(text) => match toml::from_str::<DeviceToml>(&text) {
+            Ok(config) => config,
+            Err(err) => fail(&[format!("{}: {}", path.display(), err)]),
+        },
+        Err(_) => {
+            println!(
+                "cargo:warning={} nicht gefunden, nur Environment-Variablen und Standardwerte (siehe device.toml.example)",
+                path.display()
+            );
+            DeviceToml::default()
+        }
+    };
+
+    let mut generator = Generator {
+        out: String::from("// Erzeugt von build.rs aus device.toml - nicht bearbeiten\n\n"),
+        errors: Vec::new(),
+    };
+
+    // WiFi
+    let ssid = generator.string("WIFI_SSID", config.wifi.ssid);
+    let ssid = generator.required("wifi.ssid", "WIFI_SSID", ssid, true);
+    if ssid.len() > 32 {
+        generator
+            .errors
+            .push(format!("wifi.ssid: \"{ssid}\" ist länger als 32 Bytes"));
+    }
+    let password = generator.string("WIFI_PASSWORD", config.wifi.password);
+    let password = generator.required("wifi.password", "WIFI_PASSWORD", password, true);
+    if password.len() > 64 {
+        generator
+            .errors
+            .push("wifi.password: länger als 64 Bytes".into());
+    }
+    generator.str_const("WLAN-Name (wifi.ssid)", "WIFI_SSID", &ssid);
+    generator.str_const("WLAN-Passwort (wifi.password)", "WIFI_PASSWORD", &password);
+
+    // MQTT (Pflichtwerte nur mit Feature `mqtt` bzw. `mqtt-sn`)
+    let topics_needed = mqtt_enabled || mqtt_sn_enabled;
+    let mqtt = config.mqtt;
+    let broker = generator.string("MQTT_BROKER", mqtt.broker);
+    let broker = generator.required("mqtt.broker", "MQTT_BROKER", broker, mqtt_enabled);
+    generator.str_const("MQTT-Broker (mqtt.broker)", "MQTT_BROKER", &broker);
+
+    let fallbacks = generator.string(
+        "MQTT_BROKER_FALLBACKS",
+        mqtt.fallbacks.map(|list| list.join(",")),
+    );
+    generator.opt_str_const(
+        "Fallback-Broker, kommagetrennt (mqtt.fallbacks)",
+        "MQTT_BROKER_FALLBACKS",
+        fallbacks.as_deref().filter(|list| !list.is_empty()),
+    );
+
+    let port = generator.parsed("MQTT_PORT", mqtt.port).unwrap_or(1883);
+    if port == 0 {
+        generator
+            .errors
+            .push("mqtt.port: 0 ist kein gültiger Port".into());
+    }
+    generator.raw_const("MQTT-Port (mqtt.port)", "MQTT_PORT", "u16", port);
+
+    let auto_publish_secs = generator
+        .parsed("MQTT_AUTO_PUBLISH_SECS", mqtt.auto_publish_secs)
+        .unwrap_or(5);
+    generator.raw_const(
+        "Mindestabstand der Farbmeldungen im Auto-Modus in Sekunden (mqtt.auto_publish_secs)",
+        "MQTT_AUTO_PUBLISH_SECS",
+        "u16",
+        auto_publish_secs,
+    );
+
+    let client_id = generator.string("MQTT_CLIENT_ID", mqtt.client_id);
+    let client_id =
+        generator.required("mqtt.client_id", "MQTT_CLIENT_ID", client_id, topics_needed);
+    if client_id.len() > 23 {
+        println!(
+            "cargo:warning=mqtt.client_id \"{client_id}\" ist länger als 23 Zeichen (nicht jeder Broker akzeptiert das)"
+        );
+    }
+    generator.str_const(
+        "MQTT Client ID (mqtt.client_id)",
+        "MQTT_CLIENT_ID",
+        &client_id,
+    );
+
+    let topics = mqtt.topics;
+    let color = generator.string("MQTT_TOPIC_COLOR", topics.color);
+    let color = generator.required(
+        "mqtt.topics.color",
+        "MQTT_TOPIC_COLOR",
+        color,
+        topics_needed,
+    );
+    let mode = generator.string("MQTT_TOPIC_MODE", topics.mode);
+    let mode = generator.required("mqtt.topics.mode", "MQTT_TOPIC_MODE", mode, topics_needed);
+    let required_topics = [
+        ("mqtt.topics.color", "MQTT_TOPIC_COLOR", color),
+        ("mqtt.topics.mode", "MQTT_TOPIC_MODE", mode),
+    ];
+    // (Schlüssel, Konstante, Wert, Standard)
+    let default_topics = [
+        (
+            "mqtt.topics.info",
+            "MQTT_TOPIC_INFO",
+            topics.info,
+            "devices/esp32c6/info",
+        ),
+        (
+            "mqtt.topics.state",
+            "MQTT_TOPIC_STATE",
+            topics.state,
+            "devices/esp32c6/state",
+        ),
+        (
+            "mqtt.topics.heap",
+            "MQTT_TOPIC_HEAP",
+            topics.heap,
+            "devices/esp32c6/heap",
+        ),
+        (
+            "mqtt.topics.health",
+            "MQTT_TOPIC_HEALTH",
+            topics.health,
+            "devices/esp32c6/health",
+        ),
+        (
+            "mqtt.topics.inputs",
+            "MQTT_TOPIC_INPUTS",
+            topics.inputs,
+            "devices/esp32c6/input",
+        ),
+        (
+            "mqtt.topics.command",
+            "MQTT_TOPIC_COMMAND",
+            topics.command,
+            "devices/esp32c6/cmnd",
+        ),
+        (
+            "mqtt.topics.command_result",
+            "MQTT_TOPIC_COMMAND_RESULT",
+            topics.command_result,
+            "devices/esp32c6/cmnd/result",
+        ),
+        (
+            "mqtt.topics.restart",
+            "MQTT_TOPIC_RESTART",
+            topics.restart,
+            "devices/esp32c6/cmd/restart",
+        ),
+        (
+            "mqtt.topics.factory_reset",
+            "MQTT_TOPIC_FACTORY_RESET",
+            topics.factory_reset,
+            "devices/esp32c6/cmd/factory-reset",
+        ),
+        (
+            "mqtt.ha_discovery_prefix",
+            "HA_DISCOVERY_PREFIX",
+            mqtt.ha_discovery_prefix,
+            "homeassistant",
+        ),
+    ];
+    for (key, name, topic) in required_topics {
+        if topics_needed {
+            generator.check_topic(key, &topic);
+        }
+        generator.str_const(&format!("MQTT-Topic ({key})"), name, &topic);
+    }
+    for (key, name, value, default) in default_topics {
+        let topic = generator
+            .string(name, value)
+            .unwrap_or_else(|| default.to_string());
+        generator.check_topic(key, &topic);
+        generator.str_const(&format!("MQTT-Topic ({key})"), name, &topic);
+    }
+
+    let group = generator.string("MQTT_TOPIC_GROUP", topics.group);
+    let tasmota = generator.string("MQTT_TASMOTA_TOPIC", mqtt.tasmota_topic);
+    for (key, topic) in [
+        ("mqtt.topics.group", &group),
+        ("mqtt.tasmota_topic", &tasmota),
+    ] {
+        if let Some(topic) = topic {
+            generator.check_topic(key, topic);
+        }
+    }
+    generator.opt_str_const(
+        "Gruppen-Topic für Text-Kommandos (mqtt.topics.group)",
+        "MQTT_TOPIC_GROUP",
+        group.as_deref(),
+    );
+    generator.opt_str_const(
+        "Tasmota-Geräte-Topic (mqtt.tasmota_topic)",
+        "MQTT_TASMOTA_TOPIC",
+        tasmota.as_deref(),
+    );
+
+    // MQTT-SN-Gateway (Pflicht nur mit Feature `mqtt-sn`): host oder host:port
+    let gateway = generator.string("MQTT_SN_GATEWAY", config.mqtt_sn.gateway);
+    let gateway = generator.required(
+        "mqtt_sn.gateway",
+        "MQTT_SN_GATEWAY",
+        gateway,
+        mqtt_sn_enabled,
+    );
+    if !valid_host_port(&gateway) {
+        generator.errors.push(format!(
+            "mqtt_sn.gateway: \"{gateway}\" ist keine gültige Adresse (host oder host:port)"
+        ));
+    }
+    generator.str_const(
+        "MQTT-SN-Gateway, host oder host:port (mqtt_sn.gateway)",
+        "MQTT_SN_GATEWAY",
+        &gateway,
+    );
+
+    // Syslog-Empfänger (Pflicht nur mit Feature `log-udp`): host oder host:port
+    let log_host = generator.string("LOG_UDP_HOST", config.log_udp.host);
+    let log_host = generator.required("log_udp.host", "LOG_UDP_HOST", log_host, log_udp_enabled);
+    if !valid_host_port(&log_host) {
+        generator.errors.push(format!(
+            "log_udp.host: \"{log_host}\" ist keine gültige Adresse (host oder host:port)"
+        ));
+    }
+    generator.str_const(
+        "Syslog-Empfänger, host oder host:port (log_udp.host)",
+        "LOG_UDP_HOST",
+        &log_host,
+    );
+
+    // mDNS: Label nach RFC 1123 (a-z, 0-9, Bindestrich, max. 63 Zeichen)
+    let hostname = generator
+        .string("MDNS_HOSTNAME", config.mdns.hostname)
+        .unwrap_or_else(|| "led".to_string());
+    let valid_hostname = !hostname.is_empty()
+        && hostname.len() <= 63
+        && !hostname.starts_with('-')
+        && !hostname.ends_with('-')
+        && hostname
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-');
+    if !valid_hostname {
+        generator.errors.push(format!(
+            "mdns.hostname: \"{hostname}\" ist kein gültiger Hostname (a-z, 0-9, -, max. 63 Zeichen)"
+        ));
+    }
+    generator.str_const(
+        "mDNS-Hostname ohne .local (mdns.hostname)",
+        "MDNS_HOSTNAME",
+        &hostname,
+    );
+
+    // LED
+    let count = generator.parsed("LED_COUNT", config.led.count);
+    match count {
+        Some(0) => generator.errors.push("led.count: mindestens 1 LED".into()),
+        Some(count) if count > 1024 => generator.errors.push(format!(
+            "led.count: {count} LEDs übersteigen den RMT-Buffer (max. 1024)"
+        )),
+        _ => {}
+    }
+    generator.raw_const(
+        "LED-Anzahl (led.count, None = Board-Profil)",
+        "LED_COUNT",
+        "Option<usize>",
+        format!("{count:?}"),
+    );
+    let brightness = generator
+        .parsed("LED_BRIGHTNESS", config.led.brightness)
+        .unwrap_or(10);
+    generator.raw_const(
+        "Grundhelligkeit 0-255 (led.brightness)",
+        "LED_BRIGHTNESS",
+        "u8",
+        brightness,
+    );
+    let crossfade_ms = generator
+        .parsed("LED_CROSSFADE_MS", config.led.crossfade_ms)
+        .unwrap_or(1000);
+    generator.raw_const(
+        "Überblendung beim Effektwechsel in ms, 0 = harter Schnitt (led.crossfade_ms)",
+        "LED_CROSSFADE_MS",
+        "u32",
+        crossfade_ms,
+    );
+
+    // Sprache und Sync-Rolle als Enum-Varianten
+    let language = generator
+        .string("UI_LANGUAGE", config.ui.language)
+        .unwrap_or_else(|| "de".to_string());
+    let language = match language.as_str() {
+        "de" => "German",
+        "en" => "English",
+        other => {
+            generator.errors.push(format!(
+                "ui.language: \"{other}\" (erlaubt: \"de\", \"en\")"
+            ));
+            "German"
+        }
+    };
+    generator.raw_const(
+        "Sprache der Anzeigenamen (ui.language)",
+        "UI_LANGUAGE",
+        "esp_core::Language",
+        format!("esp_core::Language::{language}"),
+    );
+
+    let role = generator
+        .string("SYNC_ROLE", config.sync.role)
+        .unwrap_or_else(|| "off".to_string());
+    let role = match role.as_str() {
+        "off" => "Off",
+        "leader" => "Leader",
+        "follower" => "Follower",
+        other => {
+            generator.errors.push(format!(
+                "sync.role: \"{other}\" (erlaubt: \"off\", \"leader\", \"follower\")"
+            ));
+            "Off"
+        }
+    };
+    generator.raw_const(
+        "Rolle im Sync-Verbund (sync.role)",
+        "SYNC_ROLE",
+        "esp_core::SyncRole",
+        format!("esp_core::SyncRole::{role}"),
+    );
+
+    // System
+    let stall_reboot = generator
+        .parsed("STALL_REBOOT", config.system.stall_reboot)
+        .unwrap_or(false);
+    generator.raw_const(
+        "Neustart bei hängendem Task (system.stall_reboot)",
+        "STALL_REBOOT",
+        "bool",
+        stall_reboot,
+    );
+    let status_led = generator
+        .parsed("STATUS_LED", config.system.status_led)
+        .unwrap_or(false);
+    generator.raw_const(
+        "Blinkcodes im Leerlauf (system.status_led)",
+        "STATUS_LED",
+        "bool",
+        status_led,
+    );
+
+    // TCP (MQTT, HTTP/WebSocket): Timeout muss länger als das Keepalive-Intervall sein,
+    // sonst bricht eine ruhige, aber gesunde Verbindung vor der ersten Probe ab
+    let keepalive_secs = generator
+        .parsed("TCP_KEEPALIVE_SECS", config.tcp.keepalive_secs)
+        .unwrap_or(5);
+    let timeout_secs = generator
+        .parsed("TCP_TIMEOUT_SECS", config.tcp.timeout_secs)
+        .unwrap_or(15);
+    if keepalive_secs > 0 && timeout_secs > 0 && timeout_secs <= keepalive_secs {
+        generator.errors.push(format!(
+            "tcp.timeout_secs ({timeout_secs}) muss größer als tcp.keepalive_secs ({keepalive_secs}) sein"
+        ));
+    }
+    generator.raw_const(
+        "TCP-Keepalive nach so vielen Sekunden Stille, 0 = aus (tcp.keepalive_secs)",
+        "TCP_KEEPALIVE_SECS",
+        "u16",
+        keepalive_secs,
+    );
+    generator.raw_const(
+        "Abbruch ohne Antwort der Gegenstelle in Sekunden, 0 = nie (tcp.timeout_secs)",
+        "TCP_TIMEOUT_SECS",
+        "u16",
+        timeout_secs,
+    );
+
+    // WebSocket (leeres Token = keine Prüfung)
+    let token = generator
+        .string("WS_AUTH_TOKEN", config.websocket.auth_token)
+        .filter(|token| !token.is_empty());
+    generator.opt_str_const(
+        "Token für die WebSocket-Authentifizierung (websocket.auth_token)",
+        "WS_AUTH_TOKEN",
+        token.as_deref(),
+    );
+
+    if !generator.errors.is_empty() {
+        fail(&generator.errors);
+    }
+    std::fs::write(out_dir.join("device_config.rs"), generator.out)
+        .expect("device_config.rs konnte nicht geschrieben werden");
+}
+
+/// Prüft den Port in `host:port` (ohne Port gilt der Standard-Port)
+fn valid_host_port(address: &str) -> bool {
+    match address.rsplit_once(':') {
+        Some((_, port)) => port.parse::<u16>().is_ok_and(|port| port != 0),
+        None => true,
+    }
+}
+
+/// Gibt alle Konfigurationsfehler aus und bricht den Build ab
+fn fail(errors: &[String]) -> ! {
+    eprintln!();
+    eprintln!("❌ Ungültige Gerätekonfiguration (device.toml, siehe device.toml.example):");
+    for error in errors {
+        eprintln!("   - {error}");
+    }
+    eprintln!();
+    std::process::exit(1);
+}
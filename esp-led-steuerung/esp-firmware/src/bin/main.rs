@@ -19,33 +19,70 @@ use embassy_executor::Spawner;
 use embassy_net::{Config as NetConfig, Stack, StackResources};
 use embassy_time::{Duration, Timer};
 
-// ESP32-C6 HAL
+// ESP-HAL (Chip per Feature, siehe hal/chip.rs)
 use esp_hal::clock::CpuClock;
+use esp_hal::interrupt::Priority;
 use esp_hal::rng::Rng;
+use esp_hal::rtc_cntl::Rtc;
 use esp_hal::timer::timg::TimerGroup;
+use esp_rtos::embassy::InterruptExecutor;
 
-// Backtrace bei Panic und println!() Support
+// Backtrace bei Exceptions und println!() Support
 use {esp_backtrace as _, esp_println as _};
 
 // Projekt-Module und Konfiguration
+use esp_core::LogLevel;
+use esp_led_steuerung::boards::{ALLOWED_LED_PINS, BOARD_NAME, BUTTON_GPIO_PIN, LED_GPIO_PIN};
+use esp_led_steuerung::calibration::CalibrationStore;
 use esp_led_steuerung::config::{EXTRA_HEAP_SIZE, WIFI_HEAP_SIZE};
+use esp_led_steuerung::crash::take_crash_record;
+use esp_led_steuerung::device_config::DeviceConfigStore;
+#[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+use esp_led_steuerung::device_health::init_temperature_sensor;
+use esp_led_steuerung::hal::chip::CHIP_NAME;
+use esp_led_steuerung::log_ring::log_line;
+use esp_led_steuerung::preset::PresetStore;
+use esp_led_steuerung::reboot::take_safe_mode;
+use esp_led_steuerung::schedule::ScheduleStore;
+use esp_led_steuerung::storage::Storage;
+#[cfg(feature = "ble")]
+use esp_led_steuerung::tasks::ble_advertise_task;
+#[cfg(feature = "mdns")]
+use esp_led_steuerung::tasks::mdns_responder_task;
+#[cfg(feature = "mqtt")]
+use esp_led_steuerung::tasks::mqtt_task;
 use esp_led_steuerung::tasks::{
-    connection_task, dhcp_task, http_server_task, led_blink_task, mdns_responder_task, mqtt_task,
-    net_task,
+    button_task, coap_server_task, connection_task, console_task, dhcp_task, heap_monitor_task,
+    http_server_task, led_blink_task, net_task, osc_task, realtime_udp_task, scheduler_task,
+    sntp_task, stall_supervisor_task, status_led_task, sync_task,
 };
-use esp_led_steuerung::{LedColorChannel, LedCommandChannel};
+use esp_led_steuerung::version::{FIRMWARE_VERSION, GIT_HASH};
+use esp_led_steuerung::wall_clock;
+use esp_led_steuerung::web_ui;
+use esp_led_steuerung::{
+    LedColorChannel, LedCommandChannel, RealtimeSignal, SharedStorage, SyncSignal,
+    SystemEventChannel,
+};
+use esp_storage::FlashStorage;
 
 // ESP-IDF App Descriptor - erforderlich für den Bootloader!
 // Ohne diesen schlägt das Flashen mit "ESP-IDF App Descriptor missing" fehl
 esp_bootloader_esp_idf::esp_app_desc!();
 
+/// Panic-Handler: speichert Meldung und Backtrace im Flash, dann Neustart
+/// (nach dem Boot über GET /api/crash abrufbar)
+#[panic_handler]
+fn panic(info: &core::panic::PanicInfo) -> ! {
+    esp_led_steuerung::crash::handle_panic(info)
+}
+
 /// Main Entry Point
 ///
 /// Initialisiert Hardware, WiFi, startet Embassy Runtime und spawnt Tasks.
 /// Danach schläft main() - alle Arbeit läuft in Tasks.
 #[esp_rtos::main]
 async fn main(spawner: Spawner) -> ! {
-    // ESP32-C6 Konfiguration: CPU auf maximale Taktfrequenz (160 MHz)
+    // CPU auf maximale Taktfrequenz (ESP32-C6/C3: 160 MHz, ESP32-S3: 240 MHz)
     let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
     let peripherals = esp_hal::init(config);
 
@@ -63,6 +100,27 @@ async fn main(spawner: Spawner) -> ! {
         esp_hal::interrupt::software::SoftwareInterruptControl::new(peripherals.SW_INTERRUPT);
     esp_rtos::start(timg0.timer0, sw_interrupt.software_interrupt0);
 
+    // Erste Zeile im Log-Ringpuffer (GET /api/logs)
+    log_line(
+        LogLevel::Info,
+        format_args!("Boot: firmware v{} ({})", FIRMWARE_VERSION, GIT_HASH),
+    );
+    log_line(
+        LogLevel::Info,
+        format_args!(
+            "Board: {} ({}, LED GPIO{}, Taster GPIO{})",
+            BOARD_NAME, CHIP_NAME, LED_GPIO_PIN, BUTTON_GPIO_PIN
+        ),
+    );
+
+    // Uhrzeit aus der RTC übernehmen (überdauert Neustarts und Deep Sleep, nicht aber Stromausfälle)
+    if let Some(unix_ms) = wall_clock::init_rtc(Rtc::new(peripherals.LPWR)) {
+        log_line(
+            LogLevel::Info,
+            format_args!("Clock: restored from RTC (unix {} s)", unix_ms / 1000),
+        );
+    }
+
     // WiFi Hardware initialisieren
     static RADIO_INIT: static_cell::StaticCell<esp_radio::Controller> =
         static_cell::StaticCell::new();
@@ -73,14 +131,25 @@ async fn main(spawner: Spawner) -> ! {
         esp_radio::wifi::new(radio_init, peripherals.WIFI, Default::default())
             .expect("Failed to initialize Wi-Fi");
 
+    // BLE Hardware initialisieren (Coexistence mit WiFi über esp-radio, nur mit Feature `ble`)
+    #[cfg(feature = "ble")]
+    let ble_connector = esp_radio::ble::controller::BleConnector::new(
+        radio_init,
+        peripherals.BT,
+        Default::default(),
+    )
+    .expect("Failed to initialize BLE");
+
     // Netzwerk-Stack erstellen
     // Random seed für TCP/IP Stack (von Hardware RNG)
     let rng = Rng::new();
     let seed = (rng.random() as u64) << 32 | rng.random() as u64;
 
     // Static resources für embassy-net
-    // Erhöht auf 12 Sockets: MQTT (1) + HTTP-Listener (1) + ~10 WebSocket-Clients
-    static RESOURCES: static_cell::StaticCell<StackResources<12>> = static_cell::StaticCell::new();
+    // 21 Sockets: MQTT (1) + MQTT-SN (1, nur mit Feature `mqtt-sn`) + SNTP (1) + UDP-Realtime (1) + CoAP (1) + OSC (1) + Konsole (1)
+    //             + Matter (1, nur mit Feature `matter`) + LIFX (1, nur mit Feature `lifx`)
+    //             + SSDP (1, nur mit Feature `ssdp`) + HTTP-Listener (1) + ~10 WebSocket-Clients
+    static RESOURCES: static_cell::StaticCell<StackResources<21>> = static_cell::StaticCell::new();
     let resources = RESOURCES.init(StackResources::new());
 
     // embassy-net erstellt Stack + Runner (nutzt STA interface für Client-Modus)
@@ -98,36 +167,214 @@ async fn main(spawner: Spawner) -> ! {
     // LED Farb-Channel erstellen (für LED → MQTT + HTTP Kommunikation)
     // PubSubChannel für Broadcast: alle Subscribers bekommen jede Nachricht
     // Params: <Mutex, Message, Capacity, MaxSubscribers, MaxPublishers>
-    // 10 Subscribers: 1 MQTT + bis zu 9 WebSocket-Connections (mehr als genug)
+    // 17 Subscribers: 1 MQTT + 1 BLE + 1 CoAP + 1 Konsole + 1 UART-Konsole + 1 USB-Konsole
+    // + 1 Matter + 1 ESP-NOW + bis zu 9 WebSocket-Connections
     static COLOR_CHANNEL: static_cell::StaticCell<LedColorChannel> = static_cell::StaticCell::new();
     let color_channel = &*COLOR_CHANNEL.init(LedColorChannel::new());
     let color_publisher = color_channel.publisher().unwrap();
 
-    // LED Command-Channel erstellen (für HTTP → LED Kommunikation)
+    // LED Command-Channel erstellen (alle Quellen → LED, Prioritäts-Warteschlange)
     static COMMAND_CHANNEL: static_cell::StaticCell<LedCommandChannel> =
         static_cell::StaticCell::new();
     let command_channel = COMMAND_CHANNEL.init(LedCommandChannel::new());
     let command_sender = command_channel.sender();
     let command_receiver = command_channel.receiver();
 
+    // Flash-Storage für persistente Daten (Zeitplan, Presets, Absturz-Protokoll)
+    static STORAGE: static_cell::StaticCell<SharedStorage> = static_cell::StaticCell::new();
+    let mut flash_storage = Storage::new(FlashStorage::new(peripherals.FLASH));
+    // Absturz des vorherigen Laufs übernehmen (GET /api/crash)
+    take_crash_record(&mut flash_storage);
+
+    // Abgesicherter Modus (POST /api/system/safe-mode): Standard-Gerätekonfiguration,
+    // keine optionalen Protokolle, gilt nur für diesen Boot
+    let safe_mode = take_safe_mode();
+    if safe_mode {
+        log_line(
+            LogLevel::Warn,
+            format_args!(
+                "Boot: safe mode (stored device config, uploaded UI and optional protocols skipped)"
+            ),
+        );
+    }
+
+    // Gerätekonfiguration (Kanal-Reihenfolge, LED-Pin; HTTP-API stellt ein, Scheduler speichert).
+    // Schon hier geladen, weil der LED-Pin vor dem Start des LED-Tasks feststehen muss.
+    static DEVICE_CONFIG: static_cell::StaticCell<DeviceConfigStore> =
+        static_cell::StaticCell::new();
+    let device_config = &*DEVICE_CONFIG.init(DeviceConfigStore::new());
+    let led_gpio = if safe_mode {
+        LED_GPIO_PIN
+    } else {
+        device_config
+            .load(&mut flash_storage)
+            .led_pin_or(ALLOWED_LED_PINS, LED_GPIO_PIN)
+    };
+    if led_gpio != LED_GPIO_PIN {
+        log_line(
+            LogLevel::Info,
+            format_args!(
+                "Config: LED data on GPIO{} (board default GPIO{})",
+                led_gpio, LED_GPIO_PIN
+            ),
+        );
+    }
+
+    // Hochgeladene Web-UI (POST /api/ui), im abgesicherten Modus die einkompilierte Seite
+    if !safe_mode {
+        web_ui::load(&mut flash_storage);
+    }
+
+    let storage = &*STORAGE.init(SharedStorage::new(flash_storage));
+    web_ui::attach(storage);
+
+    // Geteilter Zeitplan (Scheduler, HTTP-API und WebSocket)
+    static SCHEDULE: static_cell::StaticCell<ScheduleStore> = static_cell::StaticCell::new();
+    let schedule = &*SCHEDULE.init(ScheduleStore::new());
+
+    // Presets (LED-Task löst `ApplyPreset` auf, Taster schaltet durch, HTTP-API bearbeitet)
+    static PRESETS: static_cell::StaticCell<PresetStore> = static_cell::StaticCell::new();
+    let presets = &*PRESETS.init(PresetStore::new());
+
+    // Weißabgleich (LED-Task wendet an, HTTP-API stellt ein, Scheduler speichert)
+    static CALIBRATION: static_cell::StaticCell<CalibrationStore> = static_cell::StaticCell::new();
+    let calibration = &*CALIBRATION.init(CalibrationStore::new());
+
+    // Realtime-Signal (UDP → LED), hält nur den neuesten Frame
+    static REALTIME: static_cell::StaticCell<RealtimeSignal> = static_cell::StaticCell::new();
+    let realtime = &*REALTIME.init(RealtimeSignal::new());
+
+    // Sync-Signal (LED → Sync Task), hält nur den neuesten Frame des Leaders
+    static SYNC: static_cell::StaticCell<SyncSignal> = static_cell::StaticCell::new();
+    let sync = &*SYNC.init(SyncSignal::new());
+
+    // System-Ereignis-Channel (WLAN, MQTT, Clients → Status-LED, Telemetrie, Web UI)
+    static EVENTS: static_cell::StaticCell<SystemEventChannel> = static_cell::StaticCell::new();
+    let events = &*EVENTS.init(SystemEventChannel::new());
+
+    // LED-Task auf eigenem Interrupt-Executor: unterbricht Netzwerk-Tasks
+    // (HTTP, MQTT, WiFi) im Thread-Executor, damit Animationen auch unter
+    // Last ohne Ruckeln laufen. Geteilte Channels/Stores nutzen deshalb
+    // CriticalSectionRawMutex. Software-Interrupt 0 gehört dem Scheduler.
+    static LED_EXECUTOR: static_cell::StaticCell<InterruptExecutor<2>> =
+        static_cell::StaticCell::new();
+    let led_executor = LED_EXECUTOR.init(InterruptExecutor::new(sw_interrupt.software_interrupt2));
+    let led_spawner = led_executor.start(Priority::Priority2);
+
+    // LED-Pin aus der Gerätekonfiguration, Taster-Pin aus dem Board-Profil (Feature board-*)
+    let (led_pin, button_pin) = esp_led_steuerung::board_pins!(peripherals, led_gpio);
+
     // Spawn LED Task (mit Publisher für Farb-Broadcasts und Receiver für Kommandos)
-    spawner
+    led_spawner
         .spawn(led_blink_task(
-            peripherals.GPIO8,
+            led_pin,
             peripherals.RMT,
             color_publisher,
             command_receiver,
+            presets,
+            calibration,
+            device_config,
+            realtime,
+            sync,
         ))
         .unwrap();
 
+    // Spawn Taster Task (BOOT-Taster schaltet durch die Presets)
+    spawner
+        .spawn(button_task(button_pin, presets, command_sender))
+        .unwrap();
+
+    // Spawn Bewegungsmelder (PIR an GPIO4 schaltet das Licht für eine Haltezeit ein)
+    #[cfg(feature = "motion")]
+    spawner
+        .spawn(esp_led_steuerung::tasks::motion_task(
+            peripherals.GPIO4,
+            command_sender,
+        ))
+        .unwrap();
+
+    // Spawn Eingangs-Task (Tür-/Reed-Kontakte an GPIO5/GPIO6 → MQTT und LED-Kommandos)
+    #[cfg(feature = "inputs")]
+    {
+        use esp_hal::gpio::Pin;
+        spawner
+            .spawn(esp_led_steuerung::tasks::inputs_task(
+                [peripherals.GPIO5.degrade(), peripherals.GPIO6.degrade()],
+                command_sender,
+                events,
+            ))
+            .unwrap();
+    }
+
+    // Interner Temperatursensor für die Diagnose-Telemetrie (MQTT, Home Assistant),
+    // ESP32-S3 ohne Treiber in esp-hal
+    #[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+    match esp_hal::tsens::TemperatureSensor::new(peripherals.TSENS, Default::default()) {
+        Ok(sensor) => init_temperature_sensor(sensor),
+        Err(_) => log_line(
+            LogLevel::Warn,
+            format_args!("Temperature sensor unavailable"),
+        ),
+    }
+
+    // Spawn Heap-Monitor (Telemetrie: Auslastung und Höchststand)
+    spawner.spawn(heap_monitor_task()).unwrap();
+
+    // Spawn Supervisor (Stall-Erkennung für LED, MQTT, mDNS, HTTP)
+    spawner.spawn(stall_supervisor_task(events)).unwrap();
+
+    // Spawn Status-LED (Blinkcodes aus den System-Ereignissen, auch für `diagnose`)
+    spawner
+        .spawn(status_led_task(events.subscriber().unwrap()))
+        .unwrap();
+
     // Spawn WiFi Tasks
-    spawner.spawn(connection_task(wifi_controller)).unwrap();
+    spawner
+        .spawn(connection_task(wifi_controller, events))
+        .unwrap();
     spawner.spawn(net_task(runner)).unwrap();
-    spawner.spawn(dhcp_task(stack)).unwrap();
+    spawner.spawn(dhcp_task(stack, events)).unwrap();
 
-    // Spawn MQTT Task (mit Subscriber für LED-Farb-Updates)
-    let mqtt_subscriber = color_channel.subscriber().unwrap();
-    spawner.spawn(mqtt_task(stack, mqtt_subscriber)).unwrap();
+    // Spawn MQTT Task (mit Subscriber für LED-Farb-Updates und Sender für Kommandos)
+    #[cfg(feature = "mqtt")]
+    if !safe_mode {
+        let mqtt_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(mqtt_task(
+                stack,
+                mqtt_subscriber,
+                command_sender,
+                events,
+                events.subscriber().unwrap(),
+            ))
+            .unwrap();
+    }
+
+    // Spawn BLE Advertising Task (broadcastet LED-Zustand als Manufacturer Data)
+    #[cfg(feature = "ble")]
+    if !safe_mode {
+        let ble_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(ble_advertise_task(ble_connector, ble_subscriber))
+            .unwrap();
+    }
+
+    // Spawn SNTP Task (synchronisiert die Uhrzeit für den Zeitplan)
+    spawner.spawn(sntp_task(stack, events)).unwrap();
+
+    // Spawn Scheduler Task (führt Zeitplan-Einträge aus, speichert Zeitplan, Presets,
+    // Kalibrierung und Gerätekonfiguration im Flash)
+    spawner
+        .spawn(scheduler_task(
+            schedule,
+            presets,
+            calibration,
+            device_config,
+            storage,
+            command_sender,
+            events,
+        ))
+        .unwrap();
 
     // Spawn HTTP Server Tasks (4x für concurrent connections)
     // Jede Task-Instanz kann eine Connection gleichzeitig handeln
@@ -139,12 +386,147 @@ async fn main(spawner: Spawner) -> ! {
                 stack,
                 color_channel,
                 command_sender,
+                schedule,
+                presets,
+                calibration,
+                device_config,
+                events,
             ))
             .unwrap();
     }
 
     // Spawn mDNS Responder Task (für led.local Hostname)
-    spawner.spawn(mdns_responder_task(stack)).unwrap();
+    #[cfg(feature = "mdns")]
+    spawner.spawn(mdns_responder_task(stack, events)).unwrap();
+
+    // Optionale Protokolle (im abgesicherten Modus nicht gestartet)
+    if !safe_mode {
+        // Spawn UDP-Realtime Task (Ambilight / Screen-Sync)
+        spawner.spawn(realtime_udp_task(stack, realtime)).unwrap();
+
+        // Spawn Sync Task (Multicast Leader/Follower, nur mit SYNC_ROLE in .env)
+        spawner
+            .spawn(sync_task(stack, sync, realtime, calibration))
+            .unwrap();
+
+        // Spawn CoAP Server Task (Zustand mit Observe + Text-Kommandos)
+        let coap_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(coap_server_task(stack, coap_subscriber, command_sender))
+            .unwrap();
+
+        // Spawn OSC Task (Steuerung aus Musik-/VJ-Software)
+        spawner.spawn(osc_task(stack, command_sender)).unwrap();
+    }
+
+    // Spawn Debug-Konsole Task (Telnet-artige TCP-Konsole)
+    let console_subscriber = color_channel.subscriber().unwrap();
+    spawner
+        .spawn(console_task(
+            stack,
+            console_subscriber,
+            command_sender,
+            events,
+        ))
+        .unwrap();
+
+    // Spawn UART-Konsole (Befehlssatz der TCP-Konsole über UART1, nur mit Feature `uart-console`)
+    #[cfg(feature = "uart-console")]
+    {
+        let uart_console_subscriber = color_channel.subscriber().unwrap();
+        let (uart_tx, uart_rx) = esp_led_steuerung::uart_console_pins!(peripherals);
+        spawner
+            .spawn(esp_led_steuerung::tasks::uart_console_task(
+                peripherals.UART1,
+                uart_tx,
+                uart_rx,
+                stack,
+                uart_console_subscriber,
+                command_sender,
+            ))
+            .unwrap();
+    }
+
+    // Spawn USB-Konsole (Befehlssatz der TCP-Konsole über USB-Serial-JTAG, nur mit Feature `usb-console`)
+    #[cfg(feature = "usb-console")]
+    {
+        let usb_console_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(esp_led_steuerung::tasks::usb_console_task(
+                peripherals.USB_SERIAL_JTAG,
+                stack,
+                usb_console_subscriber,
+                command_sender,
+            ))
+            .unwrap();
+    }
+
+    // Spawn Log-Bridge (Log-Ringpuffer als Syslog über UDP, nur mit Feature `log-udp`),
+    // auch im abgesicherten Modus
+    #[cfg(feature = "log-udp")]
+    spawner
+        .spawn(esp_led_steuerung::tasks::log_udp_task(stack))
+        .unwrap();
+
+    // Spawn Matter Task (Apple Home / Google Home, nur mit Feature `matter`)
+    #[cfg(feature = "matter")]
+    if !safe_mode {
+        let matter_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(esp_led_steuerung::tasks::matter_task(
+                stack,
+                matter_subscriber,
+                command_sender,
+            ))
+            .unwrap();
+    }
+
+    // Spawn ESP-NOW Task (Gruppen-Zustand ohne Router, nur mit Feature `espnow`)
+    #[cfg(feature = "espnow")]
+    if !safe_mode {
+        let espnow_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(esp_led_steuerung::tasks::espnow_task(
+                wifi_interface.esp_now,
+                espnow_subscriber,
+                command_sender,
+            ))
+            .unwrap();
+    }
+
+    // Spawn LIFX Task (Steuerung aus LIFX-Apps, nur mit Feature `lifx`)
+    #[cfg(feature = "lifx")]
+    if !safe_mode {
+        let lifx_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(esp_led_steuerung::tasks::lifx_task(
+                stack,
+                lifx_subscriber,
+                command_sender,
+            ))
+            .unwrap();
+    }
+
+    // Spawn MQTT-SN Task (MQTT über UDP-Gateway, nur mit Feature `mqtt-sn`)
+    #[cfg(feature = "mqtt-sn")]
+    if !safe_mode {
+        let mqtt_sn_subscriber = color_channel.subscriber().unwrap();
+        spawner
+            .spawn(esp_led_steuerung::tasks::mqtt_sn_task(
+                stack,
+                mqtt_sn_subscriber,
+                command_sender,
+            ))
+            .unwrap();
+    }
+
+    // Spawn SSDP Task (UPnP-Discovery, nur mit Feature `ssdp`)
+    #[cfg(feature = "ssdp")]
+    if !safe_mode {
+        spawner
+            .spawn(esp_led_steuerung::tasks::ssdp_task(stack))
+            .unwrap();
+    }
 
     // Main-Loop: schläft (alle Arbeit läuft in Tasks)
     loop {
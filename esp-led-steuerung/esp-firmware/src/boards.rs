@@ -0,0 +1,266 @@
+// Board-Profile: Pin-Zuordnung und LED-Ausbau pro Hardware
+//
+// Das Profil wird zur Compile-Zeit über ein Cargo-Feature gewählt:
+//
+// | Feature          | Chip       | Board              | LED-Daten  | Taster       | LEDs     |
+// |------------------|------------|--------------------|------------|--------------|----------|
+// | `board-devkitc6` | `esp32c6`  | ESP32-C6-DevKitC-1 | GPIO8      | GPIO9 (BOOT) | 1        |
+// | `board-xiao-c6`  | `esp32c6`  | Seeed XIAO ESP32C6 | GPIO0 (D0) | GPIO9 (BOOT) | 8        |
+// | (Standard)       | `esp32c3`  | ESP32-C3-DevKitM-1 | GPIO8      | GPIO9 (BOOT) | 1        |
+// | (Standard)       | `esp32s3`  | ESP32-S3-DevKitC-1 | GPIO48     | GPIO0 (BOOT) | 1        |
+// | `board-custom`   | beliebig   | eigene Hardware    | anpassen   | anpassen     | anpassen |
+//
+// Ohne Board-Feature gilt das DevKit-Profil des gewählten Chips (siehe
+// `hal/chip.rs`), beim ESP32-C6 also DevKitC-6 (auch mit --no-default-features).
+// Für `board-custom` den Block "Eigene Hardware" unten anpassen: erlaubte Pins,
+// Makro `board_pins!` und LED-Anzahl müssen zusammenpassen.
+//
+// Die LED-Datenleitung ist zur Laufzeit wählbar (`led_pin` in /api/config,
+// wirkt nach Neustart), aber nur aus `ALLOWED_LED_PINS`: Pins anderer Funktionen
+// (Taster, Bewegungsmelder, Eingänge, UART, USB, Flash) sind ausgeschlossen.
+
+#[cfg(any(
+    all(feature = "board-devkitc6", feature = "board-xiao-c6"),
+    all(feature = "board-devkitc6", feature = "board-custom"),
+    all(feature = "board-xiao-c6", feature = "board-custom"),
+))]
+compile_error!("Nur ein Board-Feature aktivieren (board-devkitc6, board-xiao-c6, board-custom)");
+
+#[cfg(all(
+    any(feature = "board-devkitc6", feature = "board-xiao-c6"),
+    not(feature = "esp32c6")
+))]
+compile_error!("board-devkitc6 und board-xiao-c6 setzen das Chip-Feature esp32c6 voraus");
+
+pub use profile::*;
+
+/// Pin der LED-Datenleitung (zur Laufzeit aus `ALLOWED_LED_PINS` gewählt)
+pub type LedPin = esp_hal::gpio::AnyPin<'static>;
+
+/// RMT-Buffer für `config::LED_COUNT` LEDs (je 3 Farben * 8 Bits, + 1 Reset)
+pub const LED_BUFFER_SIZE: usize = crate::config::LED_COUNT * 24 + 1;
+
+// ============================================================================
+// ESP32-C6-DevKitC-1 (Standard): eingebaute WS2812 an GPIO8
+// ============================================================================
+
+#[cfg(all(
+    feature = "esp32c6",
+    not(any(feature = "board-xiao-c6", feature = "board-custom"))
+))]
+mod profile {
+    /// Anzeigename für Logs und Geräte-Info
+    pub const BOARD_NAME: &str = "ESP32-C6-DevKitC-1";
+
+    /// Standard-GPIO der LED-Datenleitung (eingebaute WS2812)
+    pub const LED_GPIO_PIN: u8 = 8;
+
+    /// Zur Laufzeit wählbare LED-Pins (freie GPIOs der Stiftleisten)
+    pub const ALLOWED_LED_PINS: &[u8] = &[8, 0, 1, 2, 3, 7, 10, 11];
+
+    /// GPIO-Nummer des Tasters (nur Anzeige, Pin-Typ siehe [`ButtonPin`])
+    pub const BUTTON_GPIO_PIN: u8 = 9;
+
+    /// Anzahl der LEDs im Strip (device.toml `led.count` hat Vorrang)
+    pub const LED_COUNT: usize = 1;
+
+    /// Pin des Tasters (aktiv low)
+    pub type ButtonPin = esp_hal::peripherals::GPIO9<'static>;
+
+    /// Entnimmt LED- und Taster-Pin aus den Peripherals: `(LedPin, ButtonPin)`
+    ///
+    /// `$led_gpio` muss aus `ALLOWED_LED_PINS` stammen (siehe `DeviceConfig::led_pin_or`).
+    #[macro_export]
+    macro_rules! board_pins {
+        ($peripherals:expr, $led_gpio:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            let led_pin: $crate::boards::LedPin = match $led_gpio {
+                0 => $peripherals.GPIO0.degrade(),
+                1 => $peripherals.GPIO1.degrade(),
+                2 => $peripherals.GPIO2.degrade(),
+                3 => $peripherals.GPIO3.degrade(),
+                7 => $peripherals.GPIO7.degrade(),
+                10 => $peripherals.GPIO10.degrade(),
+                11 => $peripherals.GPIO11.degrade(),
+                _ => $peripherals.GPIO8.degrade(),
+            };
+            (led_pin, $peripherals.GPIO9)
+        }};
+    }
+}
+
+// ============================================================================
+// Seeed Studio XIAO ESP32C6: externer Strip an D0
+// ============================================================================
+
+#[cfg(feature = "board-xiao-c6")]
+mod profile {
+    /// Anzeigename für Logs und Geräte-Info
+    pub const BOARD_NAME: &str = "XIAO ESP32C6";
+
+    /// Standard-GPIO der LED-Datenleitung (Pin D0)
+    pub const LED_GPIO_PIN: u8 = 0;
+
+    /// Zur Laufzeit wählbare LED-Pins (D0, D1, D2, D3, D4, D5)
+    pub const ALLOWED_LED_PINS: &[u8] = &[0, 1, 2, 21, 22, 23];
+
+    /// GPIO-Nummer des Tasters (BOOT)
+    pub const BUTTON_GPIO_PIN: u8 = 9;
+
+    /// Anzahl der LEDs im Strip (keine eingebaute RGB-LED)
+    pub const LED_COUNT: usize = 8;
+
+    /// Pin des Tasters (aktiv low)
+    pub type ButtonPin = esp_hal::peripherals::GPIO9<'static>;
+
+    /// Entnimmt LED- und Taster-Pin aus den Peripherals: `(LedPin, ButtonPin)`
+    ///
+    /// `$led_gpio` muss aus `ALLOWED_LED_PINS` stammen (siehe `DeviceConfig::led_pin_or`).
+    #[macro_export]
+    macro_rules! board_pins {
+        ($peripherals:expr, $led_gpio:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            let led_pin: $crate::boards::LedPin = match $led_gpio {
+                1 => $peripherals.GPIO1.degrade(),
+                2 => $peripherals.GPIO2.degrade(),
+                21 => $peripherals.GPIO21.degrade(),
+                22 => $peripherals.GPIO22.degrade(),
+                23 => $peripherals.GPIO23.degrade(),
+                _ => $peripherals.GPIO0.degrade(),
+            };
+            (led_pin, $peripherals.GPIO9)
+        }};
+    }
+}
+
+// ============================================================================
+// ESP32-C3-DevKitM-1: eingebaute WS2812 an GPIO8
+// ============================================================================
+
+#[cfg(all(feature = "esp32c3", not(feature = "board-custom")))]
+mod profile {
+    /// Anzeigename für Logs und Geräte-Info
+    pub const BOARD_NAME: &str = "ESP32-C3-DevKitM-1";
+
+    /// Standard-GPIO der LED-Datenleitung (eingebaute WS2812)
+    pub const LED_GPIO_PIN: u8 = 8;
+
+    /// Zur Laufzeit wählbare LED-Pins (ohne USB GPIO18/19, UART0 GPIO20/21, Flash GPIO11-17)
+    pub const ALLOWED_LED_PINS: &[u8] = &[8, 0, 1, 2, 7];
+
+    /// GPIO-Nummer des Tasters (nur Anzeige, Pin-Typ siehe [`ButtonPin`])
+    pub const BUTTON_GPIO_PIN: u8 = 9;
+
+    /// Anzahl der LEDs im Strip (device.toml `led.count` hat Vorrang)
+    pub const LED_COUNT: usize = 1;
+
+    /// Pin des Tasters (aktiv low)
+    pub type ButtonPin = esp_hal::peripherals::GPIO9<'static>;
+
+    /// Entnimmt LED- und Taster-Pin aus den Peripherals: `(LedPin, ButtonPin)`
+    ///
+    /// `$led_gpio` muss aus `ALLOWED_LED_PINS` stammen (siehe `DeviceConfig::led_pin_or`).
+    #[macro_export]
+    macro_rules! board_pins {
+        ($peripherals:expr, $led_gpio:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            let led_pin: $crate::boards::LedPin = match $led_gpio {
+                0 => $peripherals.GPIO0.degrade(),
+                1 => $peripherals.GPIO1.degrade(),
+                2 => $peripherals.GPIO2.degrade(),
+                7 => $peripherals.GPIO7.degrade(),
+                _ => $peripherals.GPIO8.degrade(),
+            };
+            (led_pin, $peripherals.GPIO9)
+        }};
+    }
+}
+
+// ============================================================================
+// ESP32-S3-DevKitC-1: eingebaute WS2812 an GPIO48 (ab Rev. 1.1 GPIO38)
+// ============================================================================
+
+#[cfg(all(feature = "esp32s3", not(feature = "board-custom")))]
+mod profile {
+    /// Anzeigename für Logs und Geräte-Info
+    pub const BOARD_NAME: &str = "ESP32-S3-DevKitC-1";
+
+    /// Standard-GPIO der LED-Datenleitung (eingebaute WS2812, Rev. 1.0)
+    pub const LED_GPIO_PIN: u8 = 48;
+
+    /// Zur Laufzeit wählbare LED-Pins (38 = eingebaute LED ab Rev. 1.1, ohne USB GPIO19/20)
+    pub const ALLOWED_LED_PINS: &[u8] = &[48, 38, 1, 2, 7, 8, 9, 10, 11, 12, 13];
+
+    /// GPIO-Nummer des Tasters (nur Anzeige, Pin-Typ siehe [`ButtonPin`])
+    pub const BUTTON_GPIO_PIN: u8 = 0;
+
+    /// Anzahl der LEDs im Strip (device.toml `led.count` hat Vorrang)
+    pub const LED_COUNT: usize = 1;
+
+    /// Pin des Tasters (aktiv low)
+    pub type ButtonPin = esp_hal::peripherals::GPIO0<'static>;
+
+    /// Entnimmt LED- und Taster-Pin aus den Peripherals: `(LedPin, ButtonPin)`
+    ///
+    /// `$led_gpio` muss aus `ALLOWED_LED_PINS` stammen (siehe `DeviceConfig::led_pin_or`).
+    #[macro_export]
+    macro_rules! board_pins {
+        ($peripherals:expr, $led_gpio:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            let led_pin: $crate::boards::LedPin = match $led_gpio {
+                38 => $peripherals.GPIO38.degrade(),
+                1 => $peripherals.GPIO1.degrade(),
+                2 => $peripherals.GPIO2.degrade(),
+                7 => $peripherals.GPIO7.degrade(),
+                8 => $peripherals.GPIO8.degrade(),
+                9 => $peripherals.GPIO9.degrade(),
+                10 => $peripherals.GPIO10.degrade(),
+                11 => $peripherals.GPIO11.degrade(),
+                12 => $peripherals.GPIO12.degrade(),
+                13 => $peripherals.GPIO13.degrade(),
+                _ => $peripherals.GPIO48.degrade(),
+            };
+            (led_pin, $peripherals.GPIO0)
+        }};
+    }
+}
+
+// ============================================================================
+// Eigene Hardware: Vorlage zum Anpassen
+// ============================================================================
+
+#[cfg(feature = "board-custom")]
+mod profile {
+    /// Anzeigename für Logs und Geräte-Info
+    pub const BOARD_NAME: &str = "Custom";
+
+    /// Standard-GPIO der LED-Datenleitung
+    pub const LED_GPIO_PIN: u8 = 8;
+
+    /// Zur Laufzeit wählbare LED-Pins (jeder braucht einen Zweig in `board_pins!`)
+    pub const ALLOWED_LED_PINS: &[u8] = &[8];
+
+    /// GPIO-Nummer des Tasters
+    pub const BUTTON_GPIO_PIN: u8 = 9;
+
+    /// Anzahl der LEDs im Strip
+    pub const LED_COUNT: usize = 1;
+
+    /// Pin des Tasters (aktiv low)
+    pub type ButtonPin = esp_hal::peripherals::GPIO9<'static>;
+
+    /// Entnimmt LED- und Taster-Pin aus den Peripherals: `(LedPin, ButtonPin)`
+    ///
+    /// `$led_gpio` muss aus `ALLOWED_LED_PINS` stammen (siehe `DeviceConfig::led_pin_or`).
+    #[macro_export]
+    macro_rules! board_pins {
+        ($peripherals:expr, $led_gpio:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            // Weitere Pins als eigene Zweige ergänzen, z.B. `2 => $peripherals.GPIO2.degrade(),`
+            let led_pin: $crate::boards::LedPin = match $led_gpio {
+                _ => $peripherals.GPIO8.degrade(),
+            };
+            (led_pin, $peripherals.GPIO9)
+        }};
+    }
+}
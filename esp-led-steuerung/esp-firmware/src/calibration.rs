@@ -0,0 +1,67 @@
+// Kalibrier-Store: geteilter Weißabgleich für LED-Task und HTTP-API
+//
+// Der LED-Task übernimmt den aktuellen Wert in jedem Durchlauf
+// (esp_core::LedLoop::calibration). Änderungen über die API werden dem
+// Scheduler-Task signalisiert, der sie dann in den Flash schreibt
+// (CriticalSectionRawMutex: LED-Task läuft auf dem Interrupt-Executor).
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use esp_core::Calibration;
+
+use crate::config::DEFAULT_CALIBRATION;
+
+/// Geteilte Kalibrierung mit Änderungs-Signal
+pub struct CalibrationStore {
+    calibration: Mutex<CriticalSectionRawMutex, Cell<Calibration>>,
+    changed: Signal<CriticalSectionRawMutex, ()>,
+}
+
+impl Default for CalibrationStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CalibrationStore {
+    /// Store mit der Standard-Kalibrierung aus der Konfiguration
+    ///
+    /// Eine gespeicherte Kalibrierung ersetzt sie beim Start (siehe `replace`).
+    pub const fn new() -> Self {
+        Self {
+            calibration: Mutex::new(Cell::new(DEFAULT_CALIBRATION)),
+            changed: Signal::new(),
+        }
+    }
+
+    /// Aktuelle Kalibrierung
+    pub fn get(&self) -> Calibration {
+        self.calibration.lock(Cell::get)
+    }
+
+    /// Ersetzt die Kalibrierung (z.B. beim Laden aus dem Flash)
+    ///
+    /// Löst kein Änderungs-Signal aus.
+    pub fn replace(&self, calibration: Calibration) {
+        self.calibration.lock(|current| current.set(calibration));
+    }
+
+    /// Setzt die Kalibrierung und signalisiert die Änderung
+    pub fn set(&self, calibration: Calibration) {
+        self.replace(calibration);
+        self.changed.signal(());
+    }
+
+    /// Wartet auf die nächste Änderung
+    pub async fn wait_changed(&self) {
+        self.changed.wait().await
+    }
+
+    /// Setzt ein ausstehendes Änderungs-Signal zurück und meldet, ob es gesetzt war
+    pub fn take_changed(&self) -> bool {
+        self.changed.try_take().is_some()
+    }
+}
@@ -0,0 +1,142 @@
+// Kommando-Channel mit Prioritäts-Warteschlange (alle Quellen → LED-Task)
+//
+// Ersetzt embassy_sync::channel::Channel: `send()` blockiert nie, volle
+// Warteschlangen werden nach der Policy von esp_core::CommandQueue aufgelöst
+// (Off verdrängt Farbwechsel, Fader-Ströme verdrängen keine Sicherheits-Kommandos).
+//
+// Backpressure: Clients mit Rückkanal (WebSocket) nutzen `try_send()` und melden
+// ein verworfenes Kommando als "busy". Verdrängte und verworfene Kommandos
+// zählt `stats()` für die Telemetrie.
+//
+// Generisch über den Mutex: Die Firmware nutzt `SharedRawMutex`
+// (CriticalSectionRawMutex), da Sender im Thread-Executor laufen, der
+// Empfänger (LED-Task) auf dem Interrupt-Executor mit höherer Priorität.
+
+use core::cell::RefCell;
+
+use defmt::warn;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::signal::Signal;
+use esp_core::{CommandQueue, CommandSource, LedCommand, PushOutcome, QueueStats};
+
+/// Prioritäts-Channel für LED-Kommandos (N Sender → 1 Empfänger)
+pub struct CommandChannel<M: RawMutex, const N: usize> {
+    queue: Mutex<M, RefCell<CommandQueue<N>>>,
+    /// Weckt den wartenden Empfänger nach einem `send()`
+    signal: Signal<M, ()>,
+}
+
+impl<M: RawMutex, const N: usize> CommandChannel<M, N> {
+    /// Leerer Channel
+    pub const fn new() -> Self {
+        Self {
+            queue: Mutex::new(RefCell::new(CommandQueue::new())),
+            signal: Signal::new(),
+        }
+    }
+
+    /// Sender-Handle (kopierbar, für beliebig viele Tasks)
+    pub fn sender(&self) -> CommandSender<'_, M, N> {
+        CommandSender { channel: self }
+    }
+
+    /// Empfänger-Handle (nur für den LED-Task)
+    pub fn receiver(&self) -> CommandReceiver<'_, M, N> {
+        CommandReceiver { channel: self }
+    }
+
+    /// Zähler aller Sende-Ergebnisse seit dem Start (Telemetrie)
+    pub fn stats(&self) -> QueueStats {
+        self.queue.lock(|queue| queue.borrow().stats())
+    }
+}
+
+impl<M: RawMutex, const N: usize> Default for CommandChannel<M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sender für LED-Kommandos
+pub struct CommandSender<'a, M: RawMutex, const N: usize> {
+    channel: &'a CommandChannel<M, N>,
+}
+
+// Manuell statt derive: `M` selbst muss nicht Clone/Copy sein
+impl<M: RawMutex, const N: usize> Clone for CommandSender<'_, M, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: RawMutex, const N: usize> Copy for CommandSender<'_, M, N> {}
+
+impl<M: RawMutex, const N: usize> CommandSender<'_, M, N> {
+    /// Reiht ein Kommando ein (blockiert nie)
+    ///
+    /// Async für Kompatibilität mit den bisherigen Aufrufern des Channels.
+    pub async fn send(&self, command: LedCommand) {
+        self.try_send(command);
+    }
+
+    /// Reiht ein Kommando ein und liefert das Ergebnis der Warteschlangen-Policy
+    pub fn try_send(&self, command: LedCommand) -> PushOutcome {
+        let outcome = self
+            .channel
+            .queue
+            .lock(|queue| queue.borrow_mut().push(command));
+        match outcome {
+            PushOutcome::Evicted(dropped) => {
+                warn!("Command queue full, dropped queued {}", dropped)
+            }
+            PushOutcome::Rejected => warn!("Command queue full, rejected {}", command),
+            PushOutcome::Queued | PushOutcome::Replaced => {}
+        }
+        if outcome.is_accepted() {
+            self.channel.signal.signal(());
+        }
+        outcome
+    }
+
+    /// Zähler aller Sende-Ergebnisse seit dem Start (Telemetrie)
+    pub fn stats(&self) -> QueueStats {
+        self.channel.stats()
+    }
+}
+
+/// Empfänger für LED-Kommandos
+pub struct CommandReceiver<'a, M: RawMutex, const N: usize> {
+    channel: &'a CommandChannel<M, N>,
+}
+
+impl<M: RawMutex, const N: usize> Clone for CommandReceiver<'_, M, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: RawMutex, const N: usize> Copy for CommandReceiver<'_, M, N> {}
+
+impl<M: RawMutex, const N: usize> CommandReceiver<'_, M, N> {
+    /// Nächstes Kommando ohne Warten (höchste Priorität zuerst)
+    pub fn try_receive(&self) -> Option<LedCommand> {
+        self.channel.queue.lock(|queue| queue.borrow_mut().pop())
+    }
+
+    /// Wartet auf das nächste Kommando
+    pub async fn receive(&self) -> LedCommand {
+        loop {
+            if let Some(command) = self.try_receive() {
+                return command;
+            }
+            self.channel.signal.wait().await;
+        }
+    }
+}
+
+impl<M: RawMutex, const N: usize> CommandSource for CommandReceiver<'_, M, N> {
+    fn try_next(&mut self) -> Option<LedCommand> {
+        self.try_receive()
+    }
+}
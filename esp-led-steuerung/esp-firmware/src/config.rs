@@ -1,46 +1,118 @@
 // Projekt-Konfiguration: Konstanten und Hardware-Zuordnungen
 #![allow(dead_code)]
 
+use esp_core::{
+    Calibration, ClockFace, ClockFormat, ColorOrder, DeviceConfig, DigitalInputConfig, Language,
+    LedCommand, MatrixLayout, Preset, PresetEffect, PresetName, SyncRole, TaskDeadline, TaskId,
+    TimeZone,
+};
+use rgb::RGB8;
+
+/// Werte aus device.toml (erzeugt von build.rs, geprüft zur Build-Zeit)
+mod device {
+    include!(concat!(env!("OUT_DIR"), "/device_config.rs"));
+}
+
 // ============================================================================
 // LED Konfiguration
 // ============================================================================
 
-/// GPIO-Pin für die RGB LED (WS2812/Neopixel)
-pub const LED_GPIO_PIN: u8 = 8;
-
 /// Helligkeits-Level für die LED (0-255)
-/// Wert ist gedimmt für Augenschonung
-pub const LED_BRIGHTNESS: u8 = 10;
+/// Wert ist gedimmt für Augenschonung, device.toml: `led.brightness` (Standard: 10)
+pub const LED_BRIGHTNESS: u8 = device::LED_BRIGHTNESS;
+
+/// Überblendung beim Wechsel zwischen Pixel-Effekten in Millisekunden (0 = harter Schnitt)
+/// device.toml: `led.crossfade_ms` (Standard: 1000), siehe `esp_core::crossfade`
+pub const EFFECT_CROSSFADE_MS: u32 = device::LED_CROSSFADE_MS;
 
 /// RMT Taktfrequenz in MHz
 /// 80 MHz ist optimal für WS2812 LED-Timing
 pub const RMT_CLOCK_MHZ: u32 = 80;
 
 /// Anzahl der LEDs im Strip
-pub const LED_COUNT: usize = 1;
+/// device.toml: `led.count`, ohne Angabe aus dem Board-Profil (siehe `boards`)
+pub const LED_COUNT: usize = match device::LED_COUNT {
+    Some(count) => count,
+    None => crate::boards::LED_COUNT,
+};
+
+/// Aufbau der LEDs als Matrix (Breite, Höhe, Zickzack-Verkabelung) für den Lauftext
+/// Ein Strip ist eine Zeile; für ein 16x8-Panel z.B. `MatrixLayout::new(16, 8, true)`
+/// und LED_COUNT = 128
+pub const MATRIX_LAYOUT: MatrixLayout = MatrixLayout::new(LED_COUNT, 1, false);
+const _: () = assert!(MATRIX_LAYOUT.len() == LED_COUNT);
+
+/// Gerätekonfiguration ohne gespeicherten Wert
+/// Kanal-Reihenfolge GRB = Original-WS2812, Strip nicht gedreht/gespiegelt,
+/// LED-Pin aus dem Board-Profil, änderbar über PUT /api/config
+pub const DEFAULT_DEVICE_CONFIG: DeviceConfig = DeviceConfig {
+    color_order: ColorOrder::Grb,
+    reverse: false,
+    mirror: false,
+    led_pin: None,
+};
 
 /// Blink-Intervall in Sekunden
 pub const BLINK_INTERVAL_SECS: u64 = 1;
 
+/// Bild-Intervall während Farbverläufen in Millisekunden (10 FPS)
+pub const TRANSITION_FRAME_MS: u64 = 100;
+
+/// Frame-Budget für Berechnung + Schreiben in Mikrosekunden (ein Verlaufs-Frame)
+pub const FRAME_BUDGET_US: u32 = (TRANSITION_FRAME_MS * 1000) as u32;
+
+/// Anzahl der letzten Frames für p50/p95 in `/metrics`
+pub const RENDER_STATS_WINDOW: usize = 64;
+
+/// Maximale Helligkeit am Ende des Sonnenaufgangs (0-255)
+/// Bewusst heller als LED_BRIGHTNESS, damit der Lichtwecker auch weckt
+pub const SUNRISE_MAX_BRIGHTNESS: u8 = 128;
+
+/// Helligkeit des Testmusters (Lauflicht und R/G/B, 0-255)
+/// Hell genug, um tote Pixel auch bei Tageslicht zu erkennen
+pub const TEST_PATTERN_BRIGHTNESS: u8 = 128;
+
+/// Laufgeschwindigkeit des Lauftexts: Millisekunden pro Spalte
+pub const SCROLL_TEXT_STEP_MS: u32 = 100;
+
+/// Uhrzeit-Anzeige: Farben für Stunden, Minuten und Sekunden (Doppelpunkt bzw.
+/// Sekunden-Bits der Binäruhr) und Standard-Format (`uhr 12`/`uhr 24` überschreibt es)
+pub const CLOCK_FACE: ClockFace = ClockFace {
+    hour_color: RGB8::new(64, 24, 0),
+    minute_color: RGB8::new(0, 32, 64),
+    second_color: RGB8::new(16, 16, 16),
+    format: ClockFormat::H24,
+};
+
+/// Standard-Dauer des Sonnenaufgangs in Minuten (wenn im Zeitplan nicht angegeben)
+pub const SUNRISE_DEFAULT_DURATION_MIN: u8 = 20;
+
+/// Ausblend-Dauer am Ende des Sleep-Timers in Sekunden
+pub const SLEEP_FADE_SECS: u32 = 60;
+
+/// Dauer der Helligkeitsrampe bei `SetBrightness` in Millisekunden (0 = sofort)
+pub const BRIGHTNESS_FADE_MS: u32 = 300;
+
+/// Kapazität der Kommando-Warteschlange (alle Quellen → LED-Task)
+/// Bei voller Queue greift die Prioritäts-Policy (siehe esp_core::queue)
+pub const COMMAND_QUEUE_DEPTH: usize = 8;
+
+/// Sprache der Anzeigenamen (WebSocket-Status, MQTT-Topics für Farbe und Modus)
+/// device.toml: `ui.language` ("de" oder "en", Standard: de)
+/// Eingaben werden unabhängig davon in allen Sprachen akzeptiert.
+pub const UI_LANGUAGE: Language = device::UI_LANGUAGE;
+
 // ============================================================================
 // WiFi Konfiguration
 // ============================================================================
 
 /// WiFi SSID (Netzwerk-Name)
-/// Wird zur Build-Zeit aus der Environment Variable WIFI_SSID geladen
-/// Setze diese in .env file (siehe .env.example)
-pub const WIFI_SSID: &str = env!(
-    "WIFI_SSID",
-    "WiFi SSID nicht gesetzt! Erstelle .env file (siehe .env.example)"
-);
+/// device.toml: `wifi.ssid` (Pflicht, siehe device.toml.example)
+pub const WIFI_SSID: &str = device::WIFI_SSID;
 
 /// WiFi Passwort
-/// Wird zur Build-Zeit aus der Environment Variable WIFI_PASSWORD geladen
-/// Setze diese in .env file (siehe .env.example)
-pub const WIFI_PASSWORD: &str = env!(
-    "WIFI_PASSWORD",
-    "WiFi Password nicht gesetzt! Erstelle .env file (siehe .env.example)"
-);
+/// device.toml: `wifi.password` (Pflicht, besser per WIFI_PASSWORD in .env)
+pub const WIFI_PASSWORD: &str = device::WIFI_PASSWORD;
 
 /// Heap-Größe für WiFi (Bytes)
 /// WiFi benötigt dynamischen Speicher für Pakete
@@ -51,54 +123,233 @@ pub const EXTRA_HEAP_SIZE: usize = 36864; // 36 KB
 
 // Gesamt-Heap: ~100 KB für WiFi-Stack
 
+/// Mess-Intervall des Heap-Monitors in Millisekunden
+/// Kurz genug, um Spitzen beim WLAN-Verbindungsaufbau für den Höchststand zu erfassen
+pub const HEAP_SAMPLE_INTERVAL_MS: u64 = 500;
+
+/// Intervall für Heap-Telemetrie an WebSocket-Clients und MQTT in Sekunden
+pub const HEAP_TELEMETRY_INTERVAL_SECS: u64 = 10;
+
+/// Warnschwelle für die Heap-Auslastung in Prozent (Log-Warnung beim Überschreiten)
+pub const HEAP_WARN_PERCENT: u8 = 85;
+
+/// Anzahl Zeilen im Log-Ringpuffer (GET /api/logs)
+pub const LOG_RING_LINES: usize = 32;
+
+/// Maximale Länge einer Log-Zeile in Bytes (längere werden gekürzt)
+pub const LOG_LINE_LEN: usize = 96;
+
+/// Gleichzeitige Clients auf GET /api/logs/stream
+/// Jeder belegt einen der 4 HTTP-Tasks, solange er verbunden ist
+pub const LOG_STREAM_MAX_CLIENTS: u8 = 1;
+
+/// Abfrage-Intervall für neue Log-Zeilen im Stream in Millisekunden
+pub const LOG_STREAM_POLL_MS: u64 = 250;
+
+/// Leerzeile nach so vielen Sekunden ohne neue Zeilen
+/// Nur beim Schreiben fällt auf, dass der Client getrennt hat
+pub const LOG_STREAM_HEARTBEAT_SECS: u64 = 15;
+
+/// Prüf-Intervall der Stall-Erkennung in Sekunden
+pub const STALL_CHECK_INTERVAL_SECS: u64 = 5;
+
+/// Fristen der Stall-Erkennung: maximale Zeit ohne Lebenszeichen pro Task
+/// - LED: Durchlauf mindestens jede Sekunde
+/// - MQTT/HTTP: Telemetrie-Takt alle HEAP_TELEMETRY_INTERVAL_SECS (HTTP nur mit WebSocket-Client)
+/// - mDNS: eigener Takt während der Responder läuft
+pub const STALL_DEADLINES: [TaskDeadline; 4] = [
+    TaskDeadline {
+        task: TaskId::Led,
+        timeout_ms: 5_000,
+    },
+    TaskDeadline {
+        task: TaskId::Mqtt,
+        timeout_ms: 60_000,
+    },
+    TaskDeadline {
+        task: TaskId::Mdns,
+        timeout_ms: 60_000,
+    },
+    TaskDeadline {
+        task: TaskId::Http,
+        timeout_ms: 30_000,
+    },
+];
+
+/// Neustart, wenn die Stall-Erkennung einen hängenden Task meldet
+/// device.toml: `system.stall_reboot = true` (Standard: nur melden)
+pub const STALL_REBOOT: bool = device::STALL_REBOOT;
+
+/// Wartezeit zwischen Sicherung der Einstellungen und Reset bei angefordertem Neustart
+/// Genug, damit HTTP-Antwort, MQTT-DISCONNECT und Konsolen-Ausgabe rausgehen
+pub const REBOOT_GRACE_MS: u64 = 500;
+
+/// Blinkcodes auf der ersten LED, solange die Lampe aus ist
+/// (blau blinkend = verbinde, grün = online, rot doppelt = Fehler)
+/// device.toml: `system.status_led = true` (Standard: aus)
+pub const STATUS_LED: bool = device::STATUS_LED;
+
+/// Helligkeit der Status-LED (gedimmt, damit sie nachts nicht stört)
+pub const STATUS_LED_BRIGHTNESS: u8 = 16;
+
+/// Bildrate der Blinkcodes in Millisekunden
+pub const STATUS_LED_FRAME_MS: u64 = 50;
+
+/// Helligkeit der Diagnose-Blinkcodes auf allen Pixeln (Kommando `diagnose`)
+/// Deutlich heller als die Status-LED, damit sich die Blitze zählen lassen
+pub const DIAGNOSE_BRIGHTNESS: u8 = 96;
+
 // ============================================================================
 // MQTT Konfiguration
 // ============================================================================
+//
+// Pflichtwerte (mqtt.broker, ...) werden nur mit Feature `mqtt` benötigt,
+// build.rs meldet fehlende Werte mit ihrem Schlüssel in device.toml
 
 /// MQTT Broker Hostname oder IP-Adresse
-/// Wird zur Build-Zeit aus der Environment Variable MQTT_BROKER geladen
-/// Setze diese in .env file (siehe .env.example)
-pub const MQTT_BROKER: &str = env!(
-    "MQTT_BROKER",
-    "MQTT Broker nicht gesetzt! Erstelle .env file (siehe .env.example)"
-);
+/// device.toml: `mqtt.broker`
+#[cfg(feature = "mqtt")]
+pub const MQTT_BROKER: &str = device::MQTT_BROKER;
+
+/// Fallback-Broker in Prioritätsreihenfolge, kommagetrennt (`host[:port]`)
+/// Optional: device.toml `mqtt.fallbacks = ["mqtt2.home", "192.168.1.20:1884"]`
+pub const MQTT_BROKER_FALLBACKS: Option<&str> = device::MQTT_BROKER_FALLBACKS;
+
+/// Fehlversuche in Folge, nach denen zum nächsten Broker gewechselt wird
+pub const MQTT_BROKER_SWITCH_FAILURES: u8 = 3;
 
 /// MQTT Broker Port
 /// Standard: 1883 (unverschlüsselt), 8883 (TLS)
-/// Kann in .env überschrieben werden, falls nötig
-pub const MQTT_PORT: u16 = 1883;
+/// device.toml: `mqtt.port`
+pub const MQTT_PORT: u16 = device::MQTT_PORT;
+
+/// Mindestabstand der Farbmeldungen im Auto-Modus in Sekunden
+/// Im Auto-Modus wechselt die Farbe jede Sekunde; dazwischen wird nur die
+/// jeweils neueste Farbe zurückgehalten und zum Intervallende gesendet.
+/// Manuelle Änderungen gehen immer sofort raus. 0 = jede Änderung senden
+/// device.toml: `mqtt.auto_publish_secs`
+pub const MQTT_AUTO_PUBLISH_SECS: u16 = device::MQTT_AUTO_PUBLISH_SECS;
 
 /// MQTT Client ID
 /// Eindeutige Kennung für diesen ESP32-C6
-/// Wird zur Build-Zeit aus der Environment Variable MQTT_CLIENT_ID geladen
-/// Setze diese in .env file (siehe .env.example)
-pub const MQTT_CLIENT_ID: &str = env!(
-    "MQTT_CLIENT_ID",
-    "MQTT Client ID nicht gesetzt! Erstelle .env file (siehe .env.example)"
-);
+/// device.toml: `mqtt.client_id`
+#[cfg(feature = "mqtt")]
+pub const MQTT_CLIENT_ID: &str = device::MQTT_CLIENT_ID;
 
 /// MQTT Publish Topic für LED-Farbe
-/// Topic für LED-Farb-Updates (z.B. "Rot", "Grün", "Blau")
-/// Wird zur Build-Zeit aus der Environment Variable MQTT_TOPIC_COLOR geladen
-/// Setze diese in .env file (siehe .env.example)
-pub const MQTT_TOPIC_COLOR: &str = env!(
-    "MQTT_TOPIC_COLOR",
-    "MQTT Topic Color nicht gesetzt! Erstelle .env file (siehe .env.example)"
-);
+/// Topic für LED-Farb-Updates (z.B. "Rot", "Grün", "Blau", Sprache siehe UI_LANGUAGE)
+/// device.toml: `mqtt.topics.color`
+#[cfg(feature = "mqtt")]
+pub const MQTT_TOPIC_COLOR: &str = device::MQTT_TOPIC_COLOR;
 
 /// MQTT Publish Topic für LED-Modus
-/// Topic für LED-Modus-Updates (z.B. "Auto", "Manuell")
-/// Wird zur Build-Zeit aus der Environment Variable MQTT_TOPIC_MODE geladen
-/// Setze diese in .env file (siehe .env.example)
-pub const MQTT_TOPIC_MODE: &str = env!(
-    "MQTT_TOPIC_MODE",
-    "MQTT Topic Mode nicht gesetzt! Erstelle .env file (siehe .env.example)"
-);
+/// Topic für LED-Modus-Updates (z.B. "Auto", "Manuell", Sprache siehe UI_LANGUAGE)
+/// device.toml: `mqtt.topics.mode`
+#[cfg(feature = "mqtt")]
+pub const MQTT_TOPIC_MODE: &str = device::MQTT_TOPIC_MODE;
+
+/// MQTT Topic für Geräte-Informationen (Birth-Message)
+/// Nach jedem Connect wird hier Version, Git-Hash und Build-Zeit published (retained)
+/// Optional: device.toml `mqtt.topics.info` (Standard: "devices/esp32c6/info")
+pub const MQTT_TOPIC_INFO: &str = device::MQTT_TOPIC_INFO;
+
+/// MQTT Topic für den LED-Zustand als JSON (Farbe, RGB, Modus, Sequenznummer, Zeitstempel)
+/// Über `seq` erkennen Consumer verpasste oder vertauschte Updates
+/// Optional: device.toml `mqtt.topics.state` (Standard: "devices/esp32c6/state")
+pub const MQTT_TOPIC_STATE: &str = device::MQTT_TOPIC_STATE;
+
+/// MQTT Topic für Heap-Telemetrie als JSON (used, free, high_water, size)
+/// Wird alle HEAP_TELEMETRY_INTERVAL_SECS published
+/// Optional: device.toml `mqtt.topics.heap` (Standard: "devices/esp32c6/heap")
+pub const MQTT_TOPIC_HEAP: &str = device::MQTT_TOPIC_HEAP;
+
+/// MQTT Topic für Diagnose-Werte als JSON (rssi, uptime, heap_free, temperature)
+/// Wird alle HEAP_TELEMETRY_INTERVAL_SECS published, Home Assistant liest daraus die Sensoren
+/// Optional: device.toml `mqtt.topics.health` (Standard: "devices/esp32c6/health")
+pub const MQTT_TOPIC_HEALTH: &str = device::MQTT_TOPIC_HEALTH;
+
+/// MQTT Topic-Präfix für digitale Eingänge (`<prefix>/<name>`, Payload z.B. "open"/"closed", retained)
+/// Optional: device.toml `mqtt.topics.inputs` (Standard: "devices/esp32c6/input")
+pub const MQTT_TOPIC_INPUTS: &str = device::MQTT_TOPIC_INPUTS;
+
+/// Präfix für Home-Assistant-MQTT-Discovery (`<prefix>/sensor/<client_id>/<sensor>/config`)
+/// Optional: device.toml `mqtt.ha_discovery_prefix` (Standard: "homeassistant")
+pub const HA_DISCOVERY_PREFIX: &str = device::HA_DISCOVERY_PREFIX;
+
+/// Anzeigename des Geräts in Home Assistant
+pub const HA_DEVICE_NAME: &str = crate::hal::chip::DEVICE_NAME;
+
+/// Modell des Geräts in Home Assistant (Chip, siehe Feature `esp32c6`/`esp32c3`/`esp32s3`)
+pub const HA_DEVICE_MODEL: &str = crate::hal::chip::CHIP_NAME;
+
+/// MQTT Subscribe Topic für Text-Kommandos
+/// Payload z.B. "rot", "auto", "aus", "sleep 30" (siehe esp_core::command)
+/// Optional: device.toml `mqtt.topics.command` (Standard: "devices/esp32c6/cmnd")
+pub const MQTT_TOPIC_COMMAND: &str = device::MQTT_TOPIC_COMMAND;
+
+/// MQTT Topic für die Quittung jedes Text-Kommandos (Kommando- und Gruppen-Topic)
+/// Payload z.B. {"command":"rot","status":"ok"}, mit Anfrage-ID ("@42 rot")
+/// {"id":"42","command":"rot","status":"ok"} (siehe esp_core::mqtt::write_command_ack)
+/// Optional: device.toml `mqtt.topics.command_result` (Standard: "devices/esp32c6/cmnd/result")
+pub const MQTT_TOPIC_COMMAND_RESULT: &str = device::MQTT_TOPIC_COMMAND_RESULT;
+
+/// Gruppen-Topic für Text-Kommandos an mehrere Geräte (z.B. alle Lampen eines Raums)
+/// Wird zusätzlich zu MQTT_TOPIC_COMMAND abonniert, Payload wie dort
+/// Optional: Nur aktiv, wenn device.toml `mqtt.topics.group` gesetzt ist (z.B. "rooms/wohnzimmer/cmnd")
+pub const MQTT_TOPIC_GROUP: Option<&str> = device::MQTT_TOPIC_GROUP;
+
+/// MQTT Subscribe Topic für Neustart-Anforderungen (Payload `restart`, sonst ignoriert)
+/// Eine retained Anforderung löscht die Firmware vor dem Neustart
+/// Optional: device.toml `mqtt.topics.restart` (Standard: "devices/esp32c6/cmd/restart")
+pub const MQTT_TOPIC_RESTART: &str = device::MQTT_TOPIC_RESTART;
+
+/// MQTT Subscribe Topic für den Werksreset (Payload `factory-reset`, sonst ignoriert)
+/// Eine retained Anforderung löscht die Firmware vor dem Werksreset
+/// Optional: device.toml `mqtt.topics.factory_reset` (Standard: "devices/esp32c6/cmd/factory-reset")
+pub const MQTT_TOPIC_FACTORY_RESET: &str = device::MQTT_TOPIC_FACTORY_RESET;
+
+/// Tasmota-Kompatibilitätsmodus: Geräte-Topic für `cmnd/<topic>/...` und `stat/<topic>/...`
+/// Optional: Nur aktiv, wenn device.toml `mqtt.tasmota_topic` gesetzt ist (z.B. "led")
+pub const MQTT_TASMOTA_TOPIC: Option<&str> = device::MQTT_TASMOTA_TOPIC;
+
+/// Maximale Länge der zusammengesetzten Tasmota-Topics
+pub const MQTT_TASMOTA_TOPIC_LEN: usize = 64;
+
+/// JSON Serialisierungs-Buffer für die MQTT Birth-Message
+pub const MQTT_BIRTH_BUFFER_SIZE: usize = 256;
+
+/// JSON Serialisierungs-Buffer für die MQTT Zustands-Nachricht
+pub const MQTT_STATE_BUFFER_SIZE: usize = 160;
+
+/// JSON Serialisierungs-Buffer für die MQTT Heap-Telemetrie
+pub const MQTT_HEAP_BUFFER_SIZE: usize = 128;
+
+/// JSON Serialisierungs-Buffer für die MQTT Diagnose-Nachricht
+pub const MQTT_HEALTH_BUFFER_SIZE: usize = 192;
+
+/// JSON Serialisierungs-Buffer für die Kommando-Quittung (Kommando maskiert höchstens doppelt so lang)
+pub const MQTT_COMMAND_ACK_BUFFER_SIZE: usize =
+    64 + 2 * esp_core::mqtt::COMMAND_ACK_ECHO_LEN + esp_core::mqtt::COMMAND_ID_MAX_LEN;
+
+/// Buffer für Topic und Payload einer Home-Assistant-Discovery-Konfiguration
+pub const MQTT_HA_TOPIC_LEN: usize = 96;
+pub const MQTT_HA_CONFIG_BUFFER_SIZE: usize = 512;
+
+/// Maximale Länge der Eingangs-Topics (`<prefix>/<name>`)
+pub const MQTT_INPUT_TOPIC_LEN: usize = 64;
+
+/// Abstand der WLAN-Signalmessungen in Sekunden
+pub const RSSI_SAMPLE_INTERVAL_SECS: u64 = 10;
 
 /// MQTT Reconnect Delay in Sekunden
 /// Wartezeit nach Verbindungsfehler vor erneutem Versuch
 pub const MQTT_RECONNECT_DELAY_SECS: u64 = 5;
 
+/// Plätze für Zustandsänderungen während eines Broker-Ausfalls
+/// Werden nach dem Reconnect in Originalreihenfolge nachgeholt; ist der
+/// Puffer voll, bleibt mindestens der letzte Stand je Topic erhalten
+pub const MQTT_OFFLINE_QUEUE_LEN: usize = 16;
+
 /// MQTT Buffer-Größe in Bytes
 /// Muss groß genug für MQTT-Pakete sein
 pub const MQTT_BUFFER_SIZE: usize = 1024;
@@ -106,13 +357,20 @@ pub const MQTT_BUFFER_SIZE: usize = 1024;
 /// DNS Query Timeout in Sekunden
 pub const DNS_TIMEOUT_SECS: u64 = 10;
 
+/// Gültigkeit der gecachten Broker-Adresse in Sekunden
+///
+/// Innerhalb dieser Zeit verwenden Reconnects die gecachte Adresse; nach
+/// einem fehlgeschlagenen TCP-Connect wird sofort neu aufgelöst.
+pub const DNS_CACHE_TTL_SECS: u64 = 3600;
+
 // ============================================================================
 // mDNS-Konfiguration
 // ============================================================================
 
 /// mDNS Hostname (ohne .local suffix)
 /// Der ESP32 wird erreichbar sein unter: <MDNS_HOSTNAME>.local
-pub const MDNS_HOSTNAME: &str = "led";
+/// device.toml: `mdns.hostname` (Standard: led)
+pub const MDNS_HOSTNAME: &str = device::MDNS_HOSTNAME;
 
 /// mDNS TTL (Time To Live) in Sekunden
 /// Gibt an, wie lange andere Geräte die mDNS-Antwort cachen dürfen
@@ -122,6 +380,9 @@ pub const MDNS_TTL_SECS: u32 = 120;
 /// Wartezeit nach Fehler vor erneutem Versuch
 pub const MDNS_RECONNECT_DELAY_SECS: u64 = 5;
 
+/// Takt der mDNS-Lebenszeichen für die Stall-Erkennung in Sekunden
+pub const MDNS_HEARTBEAT_SECS: u64 = 10;
+
 /// mDNS Port (Standard: 5353)
 /// Multicast DNS nutzt Port 5353 laut RFC 6762
 pub const MDNS_PORT: u16 = 5353;
@@ -138,6 +399,351 @@ pub const MDNS_UDP_BUFFER_SIZE: usize = 512;
 /// 1500 Bytes = Standard MTU für Ethernet/WiFi
 pub const MDNS_PACKET_BUFFER_SIZE: usize = 1500;
 
+// ============================================================================
+// UDP-Realtime Konfiguration
+// ============================================================================
+
+/// UDP-Port für Realtime-Farbdaten (WLED-kompatibel)
+pub const REALTIME_UDP_PORT: u16 = esp_core::realtime::REALTIME_PORT;
+
+/// Empfangs-Buffer für Realtime-Pakete in Bytes
+/// DRGB: 2 Bytes Header + 3 Bytes pro LED
+pub const REALTIME_PACKET_BUFFER_SIZE: usize = 2 + 3 * LED_COUNT;
+
+/// Standard-Timeout für UDP-Realtime-Streaming in Millisekunden
+/// Ohne neue Pakete wird danach wieder der vorherige Modus angezeigt
+pub const REALTIME_TIMEOUT_MS: u64 = 2500;
+
+// ============================================================================
+// Multicast-Sync Konfiguration
+// ============================================================================
+
+/// Rolle im Sync-Verbund mehrerer Lampen (siehe esp_core::sync)
+/// device.toml: `sync.role` ("off", "leader" oder "follower", Standard: off)
+pub const SYNC_ROLE: SyncRole = device::SYNC_ROLE;
+
+/// UDP-Port für Sync-Pakete
+pub const SYNC_UDP_PORT: u16 = esp_core::sync::SYNC_PORT;
+
+/// Vorlauf des Leaders in Millisekunden
+/// Muss die Laufzeit im WLAN abdecken, sonst zeigen Follower Frames verspätet
+pub const SYNC_LEAD_MS: u16 = 100;
+
+/// Mindestabstand zwischen zwei Sync-Paketen in Millisekunden
+pub const SYNC_INTERVAL_MS: u64 = TRANSITION_FRAME_MS;
+
+/// Sync-Paket ohne Änderung spätestens nach dieser Zeit wiederholen
+/// Hält Follower im Sync-Modus, solange der Leader nicht rendert (z.B. Lauftext)
+pub const SYNC_KEEPALIVE_MS: u64 = 1000;
+
+// ============================================================================
+// OSC Konfiguration
+// ============================================================================
+
+/// UDP-Port für OSC-Nachrichten (TouchOSC-Standard: 8000)
+pub const OSC_UDP_PORT: u16 = esp_core::osc::OSC_PORT;
+
+/// Empfangs-Buffer für OSC-Pakete in Bytes
+/// Reicht für Adresse, Typ-Tags und einige Argumente
+pub const OSC_PACKET_BUFFER_SIZE: usize = 256;
+
+// ============================================================================
+// LIFX Konfiguration (nur mit Feature `lifx`)
+// ============================================================================
+
+/// UDP-Port des LIFX-LAN-Protokolls (fest, Apps suchen nur dort)
+pub const LIFX_UDP_PORT: u16 = esp_core::lifx::LIFX_PORT;
+
+/// Empfangs-Buffer für LIFX-Pakete in Bytes
+/// Größte unterstützte Anfrage: SetColor mit 49 Bytes
+pub const LIFX_PACKET_BUFFER_SIZE: usize = 128;
+
+/// Gerätename in LIFX-Apps (höchstens 32 Bytes)
+pub const LIFX_LABEL: &str = HA_DEVICE_NAME;
+
+// ============================================================================
+// MQTT-SN Konfiguration (nur mit Feature `mqtt-sn`)
+// ============================================================================
+
+/// MQTT-SN-Gateway als `host` oder `host:port` (device.toml `mqtt_sn.gateway`)
+/// Client-ID und Topics wie bei MQTT (MQTT_CLIENT_ID, MQTT_TOPIC_*)
+pub const MQTT_SN_GATEWAY: &str = device::MQTT_SN_GATEWAY;
+
+/// Gateway-Port ohne Angabe in MQTT_SN_GATEWAY (Paho MQTT-SN Gateway: 10000)
+pub const MQTT_SN_DEFAULT_PORT: u16 = esp_core::mqtt_sn::MQTT_SN_PORT;
+
+/// Keep-Alive in Sekunden (CONNECT-Duration), PINGREQ im selben Abstand
+pub const MQTT_SN_KEEPALIVE_SECS: u16 = 60;
+
+/// Wartezeit auf CONNACK/REGACK/SUBACK/PINGRESP in Millisekunden
+pub const MQTT_SN_RETRY_MS: u64 = 3000;
+
+/// Sendeversuche je Anfrage, bevor die Verbindung neu aufgebaut wird
+pub const MQTT_SN_RETRIES: u8 = 3;
+
+/// Wartezeit nach Verbindungsfehler vor erneutem Versuch in Sekunden
+pub const MQTT_SN_RECONNECT_DELAY_SECS: u64 = 5;
+
+/// Buffer für eine MQTT-SN-Nachricht in Bytes (UDP, ohne Fragmentierung)
+pub const MQTT_SN_PACKET_BUFFER_SIZE: usize = 256;
+
+// ============================================================================
+// Log über UDP (nur mit Feature `log-udp`)
+// ============================================================================
+
+/// Syslog-Empfänger als `host` oder `host:port` (device.toml `log_udp.host`)
+pub const LOG_UDP_HOST: &str = device::LOG_UDP_HOST;
+
+/// Empfänger-Port ohne Angabe in LOG_UDP_HOST (Syslog: 514)
+pub const LOG_UDP_DEFAULT_PORT: u16 = esp_core::SYSLOG_PORT;
+
+/// Abfrage-Intervall für neue Log-Zeilen in Millisekunden
+pub const LOG_UDP_POLL_MS: u64 = 250;
+
+/// Wartezeit nach fehlgeschlagener Adressauflösung in Sekunden
+pub const LOG_UDP_RETRY_SECS: u64 = 30;
+
+/// Buffer für eine Syslog-Nachricht: Header (max. ~150 Bytes) plus Log-Zeile
+pub const LOG_UDP_FRAME_SIZE: usize = LOG_LINE_LEN + 160;
+
+// ============================================================================
+// SSDP Konfiguration (nur mit Feature `ssdp`)
+// ============================================================================
+
+/// UDP-Port für SSDP (fest, Control Points suchen nur dort)
+pub const SSDP_UDP_PORT: u16 = esp_core::ssdp::SSDP_PORT;
+
+/// Empfangs-Buffer für SSDP-Pakete in Bytes
+/// M-SEARCH-Anfragen von Windows sind etwa 200 Bytes lang, größere werden verworfen
+pub const SSDP_PACKET_BUFFER_SIZE: usize = 512;
+
+/// Buffer für eine SSDP-Antwort bzw. -Ankündigung in Bytes
+pub const SSDP_RESPONSE_BUFFER_SIZE: usize = 384;
+
+/// Abstand der `ssdp:alive`-Ankündigungen in Sekunden
+/// Deutlich unter `max-age` (1800 s), damit Control Points das Gerät nicht verwerfen
+pub const SSDP_NOTIFY_INTERVAL_SECS: u64 = 300;
+
+/// Anzeigename in der Windows-Netzwerkumgebung und in UPnP-Browsern
+pub const SSDP_FRIENDLY_NAME: &str = HA_DEVICE_NAME;
+
+/// Hersteller in der UPnP-Gerätebeschreibung
+pub const SSDP_MANUFACTURER: &str = "legacycode-labs";
+
+// ============================================================================
+// CoAP Konfiguration
+// ============================================================================
+
+/// UDP-Port des CoAP-Servers (Standard: 5683)
+pub const COAP_PORT: u16 = esp_core::coap::COAP_PORT;
+
+/// Buffer-Größe für CoAP-Nachrichten in Bytes
+/// Reicht für Header, Token, Optionen und JSON-Zustand
+pub const COAP_BUFFER_SIZE: usize = 256;
+
+/// Maximale Anzahl gleichzeitiger Observer (Zustands-Benachrichtigungen)
+pub const COAP_MAX_OBSERVERS: usize = 4;
+
+// ============================================================================
+// Debug-Konsole Konfiguration
+// ============================================================================
+
+/// TCP-Port der Debug-Konsole (Telnet-Standard: 23)
+pub const CONSOLE_PORT: u16 = 23;
+
+/// TCP Buffer-Größe (RX und TX) der Konsole in Bytes
+pub const CONSOLE_BUFFER_SIZE: usize = 512;
+
+/// Maximale Länge einer Eingabezeile
+/// Längere Zeilen werden verworfen
+pub const CONSOLE_LINE_LENGTH: usize = 128;
+
+/// Inaktivitäts-Timeout in Sekunden, danach wird die Verbindung getrennt
+pub const CONSOLE_IDLE_TIMEOUT_SECS: u64 = 600;
+
+/// Baudrate der UART-Konsole (Feature `uart-console`, UART1 an GPIO18/GPIO19)
+pub const UART_CONSOLE_BAUDRATE: u32 = 115_200;
+
+/// Abfrage-Intervall für neue Log-Zeilen in Konsolen mit Log-Ausgabe (USB-Konsole)
+pub const CONSOLE_LOG_POLL_MS: u64 = 200;
+
+/// Anzahl gespeicherter Abschnitte des Ablauf-Tracings (Konsole `trace`)
+pub const TRACE_SPANS: usize = 48;
+
+/// Von `trace` ausgegebene jüngste Abschnitte
+pub const TRACE_SHOW_SPANS: usize = 16;
+
+// ============================================================================
+// Zeit & Zeitplan Konfiguration
+// ============================================================================
+
+/// SNTP-Server für die Uhrzeit-Synchronisation
+pub const SNTP_SERVER: &str = "pool.ntp.org";
+
+/// Lokaler UDP-Port für SNTP-Anfragen
+pub const SNTP_LOCAL_PORT: u16 = 12300;
+
+/// Intervall für erneute Synchronisation in Sekunden (1 Stunde)
+pub const SNTP_SYNC_INTERVAL_SECS: u64 = 3600;
+
+/// Wartezeit nach fehlgeschlagener Synchronisation in Sekunden
+pub const SNTP_RETRY_DELAY_SECS: u64 = 30;
+
+/// Timeout für die SNTP-Antwort in Sekunden
+pub const SNTP_TIMEOUT_SECS: u64 = 5;
+
+/// Lokale Zeitzone für den Zeitplan
+/// MEZ/MESZ (UTC+1 mit EU-Sommerzeit)
+pub const TIMEZONE: TimeZone = TimeZone::CET;
+
+/// Maximale Anzahl Zeitplan-Einträge
+pub const SCHEDULE_CAPACITY: usize = 8;
+
+/// Prüf-Intervall des Schedulers in Sekunden
+/// Muss < 60 sein, damit keine Minute übersprungen wird
+pub const SCHEDULER_POLL_SECS: u64 = 5;
+
+// ============================================================================
+// Presets & Taster
+// ============================================================================
+
+/// Maximale Anzahl Presets
+pub const PRESET_CAPACITY: usize = 8;
+
+/// Standard-Presets beim Start (IDs 1, 2, … in dieser Reihenfolge)
+pub const DEFAULT_PRESETS: [Preset; 4] = [
+    Preset::new(
+        PresetName::new("Lesen").unwrap(),
+        RGB8::new(40, 30, 18),
+        PresetEffect::Solid,
+        255,
+        0,
+    ),
+    Preset::new(
+        PresetName::new("Nacht").unwrap(),
+        RGB8::new(10, 2, 0),
+        PresetEffect::Solid,
+        64,
+        0,
+    ),
+    Preset::new(
+        PresetName::new("Wecker").unwrap(),
+        RGB8::new(0, 0, 0),
+        PresetEffect::Sunrise,
+        255,
+        SUNRISE_DEFAULT_DURATION_MIN,
+    ),
+    Preset::new(
+        PresetName::new("Party").unwrap(),
+        RGB8::new(0, 0, 0),
+        PresetEffect::Auto,
+        255,
+        0,
+    ),
+];
+
+const _: () = assert!(DEFAULT_PRESETS.len() <= PRESET_CAPACITY);
+
+// ============================================================================
+// Weißabgleich
+// ============================================================================
+
+/// Kalibrierung ohne gespeicherten Wert (Faktoren R, G, B; 255 = unverändert)
+///
+/// Einstellbar über PUT /api/calibration, Testfarben mit dem Text-Kommando
+/// `kalibrieren`.
+pub const DEFAULT_CALIBRATION: Calibration = Calibration::NEUTRAL;
+
+/// Abtast-Intervall des Tasters (Pin aus dem Board-Profil) in Millisekunden
+pub const BUTTON_POLL_MS: u64 = 10;
+
+/// Entprellzeit des Tasters in Millisekunden
+pub const BUTTON_DEBOUNCE_MS: u32 = 30;
+
+/// Haltezeit des BOOT-Tasters für den Werksreset in Millisekunden
+pub const FACTORY_RESET_HOLD_MS: u32 = 10_000;
+
+/// Preset, das der Bewegungsmelder (Feature `motion`, GPIO4) anwendet
+/// Ohne gespeichertes Preset mit dieser ID bleibt das Licht aus.
+pub const MOTION_PRESET_ID: u8 = 0;
+
+/// Haltezeit nach der letzten Bewegung in Minuten, danach blendet das Licht aus
+pub const MOTION_HOLD_MINUTES: u16 = 5;
+
+/// Prüf-Intervall bei dauerhaft aktivem Melder (anhaltende Bewegung) in Sekunden
+pub const MOTION_RECHECK_SECS: u64 = 30;
+
+/// Digitale Eingänge (Feature `inputs`) an GPIO5 und GPIO6, Reihenfolge = Index
+///
+/// Kontakte schalten gegen Masse (interner Pull-up). Ein Reed-Kontakt ist bei
+/// geschlossener Tür geschlossen (Low), offene Tür ergibt also High.
+/// - `door`: Schranktür, Licht mit Preset 0 beim Öffnen, aus beim Schließen
+/// - `window`: nur Meldung per MQTT
+pub const DIGITAL_INPUTS: [DigitalInputConfig; 2] = [
+    DigitalInputConfig {
+        name: "door",
+        active_low: false,
+        active_state: "open",
+        inactive_state: "closed",
+        on_active: Some(LedCommand::ApplyPreset { id: 0 }),
+        on_inactive: Some(LedCommand::Off),
+    },
+    DigitalInputConfig {
+        name: "window",
+        active_low: false,
+        active_state: "open",
+        inactive_state: "closed",
+        on_active: None,
+        on_inactive: None,
+    },
+];
+
+/// Abtast-Intervall der digitalen Eingänge in Millisekunden
+pub const INPUT_POLL_MS: u64 = 10;
+
+/// Entprellzeit der digitalen Eingänge in Millisekunden (Reed-Kontakte prellen länger als Taster)
+pub const INPUT_DEBOUNCE_MS: u32 = 50;
+
+// ============================================================================
+// Flash-Persistenz Konfiguration
+// ============================================================================
+
+/// Start-Offset der Storage-Partition im Flash
+/// Muss mit partitions.csv übereinstimmen ("storage" Partition)
+pub const STORAGE_FLASH_OFFSET: u32 = 0x3F_0000;
+
+/// Größe eines Flash-Sektors (kleinste löschbare Einheit)
+/// Jeder Record-Typ belegt einen eigenen Sektor
+pub const STORAGE_SECTOR_SIZE: u32 = 4096;
+
+/// Anzahl Sektoren in der Storage-Partition (64 KB)
+pub const STORAGE_SECTOR_COUNT: u32 = 16;
+
+/// Buffer-Größe zum Lesen/Schreiben eines Records (Bytes)
+pub const STORAGE_RECORD_BUFFER_SIZE: usize = 256;
+
+/// Start-Offset der Web-UI-Partition im Flash (per POST /api/ui hochgeladene Oberfläche)
+/// Muss mit partitions.csv übereinstimmen ("webui" Partition)
+pub const WEB_UI_FLASH_OFFSET: u32 = 0x3D_0000;
+
+/// Größe der Web-UI-Partition (128 KB inkl. 16 Byte Header)
+pub const WEB_UI_FLASH_SIZE: u32 = 0x2_0000;
+
+/// Chunk-Größe beim Schreiben und Ausliefern der Web-UI (Bytes, Vielfaches von 4)
+/// Liegt auf dem Stack des HTTP-Tasks
+pub const WEB_UI_CHUNK_SIZE: usize = 512;
+
+// ============================================================================
+// BLE-Konfiguration
+// ============================================================================
+
+/// BLE Gerätename im Advertisement (Complete Local Name)
+pub const BLE_DEVICE_NAME: &str = "led";
+
+/// BLE Advertising-Intervall in Millisekunden
+/// Kürzer = schneller sichtbar für Scanner, aber höherer Stromverbrauch
+pub const BLE_ADV_INTERVAL_MS: u64 = 500;
+
 // ============================================================================
 // HTTP Server Konfiguration
 // ============================================================================
@@ -147,6 +753,16 @@ pub const MDNS_PACKET_BUFFER_SIZE: usize = 1500;
 /// 1024 Bytes reicht dank Chunked Transfer Encoding (HTML ist 8 KB, wird in Chunks gesendet)
 pub const HTTP_BUFFER_SIZE: usize = 1024;
 
+/// TCP-Keepalive: Probe nach so vielen Sekunden ohne Verkehr (0 = aus)
+/// Gilt für MQTT und HTTP/WebSocket, siehe `tcp_tuning`
+/// device.toml: `tcp.keepalive_secs` (Standard: 5)
+pub const TCP_KEEPALIVE_SECS: u64 = device::TCP_KEEPALIVE_SECS as u64;
+
+/// TCP-Timeout: Verbindung abbrechen, wenn die Gegenstelle so lange nicht antwortet (0 = nie)
+/// Mit Keepalive werden halboffene Verbindungen damit nach spätestens diesem Wert erkannt
+/// device.toml: `tcp.timeout_secs` (Standard: 15, größer als `tcp.keepalive_secs`)
+pub const TCP_TIMEOUT_SECS: u64 = device::TCP_TIMEOUT_SECS as u64;
+
 /// TCP RX Buffer-Größe in Bytes
 /// Für eingehende TCP-Daten vom Client
 pub const TCP_RX_BUFFER_SIZE: usize = 1024;
@@ -161,9 +777,35 @@ pub const TCP_TX_BUFFER_SIZE: usize = 1024;
 pub const WEBSOCKET_BUFFER_SIZE: usize = 512;
 
 /// JSON Serialisierungs-Buffer für WebSocket Status-Updates
-/// Für {"type":"status","color":"Rot","rgb":{...},"timestamp_ms":...,"mode":"auto"}
+/// Für {"type":"status","color":"Rot","label":"Rot","rgb":{...},"timestamp_ms":...,"mode":"auto","seq":...}
 pub const JSON_STATUS_BUFFER_SIZE: usize = 256;
 
+/// JSON Serialisierungs-Buffer für Zeitplan-Listen (WebSocket + /api/schedule)
+/// ~90 Bytes pro Eintrag × SCHEDULE_CAPACITY
+pub const JSON_SCHEDULE_BUFFER_SIZE: usize = 1024;
+
+/// Token für die WebSocket-Authentifizierung
+/// Optional: Nur aktiv, wenn device.toml `websocket.auth_token` gesetzt ist. Clients müssen dann als
+/// erste Nachricht {"type":"auth","token":"..."} senden, sonst wird die Verbindung getrennt.
+pub const WS_AUTH_TOKEN: Option<&str> = device::WS_AUTH_TOKEN;
+
+/// Frist für die `auth`-Nachricht nach dem WebSocket-Upgrade in Sekunden
+pub const WS_AUTH_TIMEOUT_SECS: u64 = 5;
+
 /// JSON Serialisierungs-Buffer für WebSocket Error-Messages
-/// Für {"type":"error","message":"..."}
+/// Für {"type":"error","message":"..."} und {"type":"busy",...,"dropped":...}
 pub const JSON_ERROR_BUFFER_SIZE: usize = 128;
+
+/// permessage-deflate (Feature `ws-deflate`): größter komprimierter Frame in Bytes
+/// Größere Frames gehen unkomprimiert raus; deckt Zeitplan-Listen (JSON_SCHEDULE_BUFFER_SIZE) ab
+pub const WS_DEFLATE_FRAME_SIZE: usize = JSON_SCHEDULE_BUFFER_SIZE;
+
+/// permessage-deflate: Wörterbuch in Bytes (zuletzt gesendete Nachrichten der Verbindung)
+/// 512 Bytes fassen die letzten zwei Status-Updates
+pub const WS_DEFLATE_WINDOW: usize = 512;
+
+/// permessage-deflate: kürzere Nachrichten werden nicht komprimiert
+pub const WS_DEFLATE_MIN_SIZE: usize = 64;
+
+/// permessage-deflate: längste mitgelesene Header-Zeile im Upgrade-Request
+pub const WS_DEFLATE_HEADER_LINE: usize = 128;
@@ -0,0 +1,163 @@
+// Absturz-Protokoll: Panic → Flash → API nach dem Neustart
+//
+// Der Panic-Handler (bin/main.rs) ruft `handle_panic()` auf. Dort werden
+// Meldung, Register und die Rücksprungadressen entlang der Frame-Pointer
+// (`-C force-frame-pointers`, siehe .cargo/config.toml) als
+// `esp_core::CrashRecord` in den Flash geschrieben, danach startet das Gerät
+// neu. Beim nächsten Boot liest `take_crash_record()` den Record einmal aus,
+// löscht ihn im Flash und hält ihn für GET /api/crash im RAM.
+//
+// Der Panic-Handler greift per `FLASH::steal()` direkt auf den Flash zu, der
+// reguläre `SharedStorage` ist zu diesem Zeitpunkt evtl. gesperrt.
+
+use core::cell::{Cell, RefCell};
+use core::panic::PanicInfo;
+
+use defmt::{error, warn};
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::Instant;
+use esp_core::crash::CRASH_RECORD_MAX_LEN;
+use esp_core::{CrashRecord, CrashRegisters, LogLevel, RecordKind};
+use esp_storage::FlashStorage;
+
+use crate::config::STORAGE_RECORD_BUFFER_SIZE;
+use crate::hal::chip::{SRAM_END, SRAM_START};
+use crate::log_ring::log_line;
+use crate::storage::Storage;
+use crate::version::GIT_HASH;
+
+/// Verhindert Rekursion, wenn der Panic-Handler selbst in Panik gerät
+///
+/// critical_section::Mutex statt Atomic: riscv32imc (ESP32-C3) hat kein `swap`.
+static PANICKING: critical_section::Mutex<Cell<bool>> =
+    critical_section::Mutex::new(Cell::new(false));
+
+/// Absturz des vorherigen Laufs (CriticalSection, da `static`)
+static LAST_CRASH: Mutex<CriticalSectionRawMutex, RefCell<Option<CrashRecord>>> =
+    Mutex::new(RefCell::new(None));
+
+/// Speichert den Absturz im Flash und startet neu
+pub fn handle_panic(info: &PanicInfo) -> ! {
+    if !critical_section::with(|cs| PANICKING.borrow(cs).replace(true)) {
+        let record = capture(info);
+        error!("PANIC: {}", record.message());
+        for frame in record.frames() {
+            error!("  0x{:08x}", frame);
+        }
+        save(&record);
+    }
+    esp_hal::system::software_reset()
+}
+
+/// Liest den Absturz des vorherigen Laufs und löscht ihn im Flash
+///
+/// Einmal beim Boot aufrufen, bevor der Storage geteilt wird.
+pub fn take_crash_record(storage: &mut Storage) {
+    let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+    let Some(record) = storage
+        .load(RecordKind::Crash, &mut buffer)
+        .and_then(CrashRecord::decode)
+    else {
+        return;
+    };
+    warn!(
+        "Crash: previous run panicked after {} ms: {}",
+        record.uptime_ms,
+        record.message()
+    );
+    log_line(
+        LogLevel::Error,
+        format_args!(
+            "Previous run panicked after {} ms: {}",
+            record.uptime_ms,
+            record.message()
+        ),
+    );
+    if storage.erase(RecordKind::Crash).is_err() {
+        warn!("Crash: Failed to erase record");
+    }
+    LAST_CRASH.lock(|crash| *crash.borrow_mut() = Some(record));
+}
+
+/// Absturz des vorherigen Laufs (`None` = sauberer Start)
+pub fn last_crash() -> Option<CrashRecord> {
+    LAST_CRASH.lock(|crash| crash.borrow().clone())
+}
+
+/// Sammelt Meldung, Register und Stack-Frames
+fn capture(info: &PanicInfo) -> CrashRecord {
+    let registers = read_registers();
+    let mut record = CrashRecord::new(Instant::now().as_millis(), registers);
+    record.set_build(GIT_HASH);
+    if let Some(location) = info.location() {
+        record.write_message(format_args!("{}:{}: ", location.file(), location.line()));
+    }
+    record.write_message(format_args!("{}", info.message()));
+
+    // Frame-Layout (RISC-V): ra bei fp-4, vorheriger fp bei fp-8
+    let mut fp = registers.fp;
+    while is_stack_address(fp) {
+        // SAFETY: fp liegt im SRAM und zeigt auf einen Frame des aktuellen Stacks
+        let (ra, previous_fp) = unsafe {
+            (
+                ((fp - 4) as *const u32).read_volatile(),
+                ((fp - 8) as *const u32).read_volatile(),
+            )
+        };
+        // Stack wächst nach unten: äußere Frames liegen höher
+        if ra == 0 || !record.push_frame(ra) || previous_fp <= fp {
+            break;
+        }
+        fp = previous_fp;
+    }
+    record
+}
+
+/// Schreibt den Record direkt in den Flash
+fn save(record: &CrashRecord) {
+    let mut payload = [0u8; CRASH_RECORD_MAX_LEN];
+    let Some(len) = record.encode(&mut payload) else {
+        return;
+    };
+    // SAFETY: Nach einem Panic läuft kein anderer Code mehr, der den Flash nutzt
+    let flash = unsafe { esp_hal::peripherals::FLASH::steal() };
+    let mut storage = Storage::new(FlashStorage::new(flash));
+    if storage.save(RecordKind::Crash, &payload[..len]).is_err() {
+        error!("Crash: Failed to save record");
+    }
+}
+
+fn is_stack_address(address: u32) -> bool {
+    (SRAM_START + 8..SRAM_END).contains(&address) && address % 4 == 0
+}
+
+/// Liest `ra`, `sp` und `s0` (Frame-Pointer)
+#[cfg(target_arch = "riscv32")]
+#[inline(always)]
+fn read_registers() -> CrashRegisters {
+    let (ra, sp, fp): (u32, u32, u32);
+    // SAFETY: Liest nur Register, keine Seiteneffekte
+    unsafe {
+        core::arch::asm!(
+            "mv {0}, ra",
+            "mv {1}, sp",
+            "mv {2}, s0",
+            out(reg) ra,
+            out(reg) sp,
+            out(reg) fp,
+        );
+    }
+    CrashRegisters { ra, sp, fp }
+}
+
+/// Xtensa (ESP32-S3): Register-Fenster, kein Frame-Pointer-Layout wie bei
+/// RISC-V. Der Record enthält dann nur die Meldung, ohne Frames.
+#[cfg(not(target_arch = "riscv32"))]
+fn read_registers() -> CrashRegisters {
+    CrashRegisters {
+        ra: 0,
+        sp: 0,
+        fp: 0,
+    }
+}
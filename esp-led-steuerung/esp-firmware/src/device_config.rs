@@ -0,0 +1,89 @@
+// Konfigurations-Store: geteilte Gerätekonfiguration für LED-Task und HTTP-API
+//
+// Die Konfiguration wird beim Boot synchron aus dem Flash geladen, weil der
+// LED-Pin vor dem Start des LED-Tasks feststehen muss. Der LED-Task übernimmt
+// die Kanal-Reihenfolge in jedem Durchlauf in den LED-Writer. Änderungen über
+// /api/config werden dem Scheduler-Task signalisiert, der sie dann in den
+// Flash schreibt (CriticalSectionRawMutex: LED-Task läuft auf dem
+// Interrupt-Executor). Ein geänderter LED-Pin wirkt erst nach einem Neustart.
+
+use core::cell::Cell;
+
+use defmt::info;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use esp_core::{DeviceConfig, RecordKind};
+
+use crate::config::{DEFAULT_DEVICE_CONFIG, STORAGE_RECORD_BUFFER_SIZE};
+use crate::storage::Storage;
+
+/// Geteilte Gerätekonfiguration mit Änderungs-Signal
+pub struct DeviceConfigStore {
+    config: Mutex<CriticalSectionRawMutex, Cell<DeviceConfig>>,
+    changed: Signal<CriticalSectionRawMutex, ()>,
+}
+
+impl Default for DeviceConfigStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceConfigStore {
+    /// Store mit der Standard-Konfiguration aus config.rs
+    ///
+    /// Eine gespeicherte Konfiguration ersetzt sie beim Start (siehe `replace`).
+    pub const fn new() -> Self {
+        Self {
+            config: Mutex::new(Cell::new(DEFAULT_DEVICE_CONFIG)),
+            changed: Signal::new(),
+        }
+    }
+
+    /// Aktuelle Konfiguration
+    pub fn get(&self) -> DeviceConfig {
+        self.config.lock(Cell::get)
+    }
+
+    /// Lädt die Konfiguration aus dem Flash (Standardwert wenn kein gültiger Record vorhanden)
+    ///
+    /// Einmal beim Boot aufrufen, bevor der Storage geteilt wird.
+    pub fn load(&self, storage: &mut Storage) -> DeviceConfig {
+        let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+        match storage
+            .load(RecordKind::Config, &mut buffer)
+            .and_then(DeviceConfig::from_bytes)
+        {
+            Some(loaded) => {
+                info!("Config: Loaded device config from flash");
+                self.replace(loaded);
+            }
+            None => info!("Config: No stored device config, using default"),
+        }
+        self.get()
+    }
+
+    /// Ersetzt die Konfiguration (z.B. beim Laden aus dem Flash)
+    ///
+    /// Löst kein Änderungs-Signal aus.
+    pub fn replace(&self, config: DeviceConfig) {
+        self.config.lock(|current| current.set(config));
+    }
+
+    /// Setzt die Konfiguration und signalisiert die Änderung
+    pub fn set(&self, config: DeviceConfig) {
+        self.replace(config);
+        self.changed.signal(());
+    }
+
+    /// Wartet auf die nächste Änderung
+    pub async fn wait_changed(&self) {
+        self.changed.wait().await
+    }
+
+    /// Setzt ein ausstehendes Änderungs-Signal zurück und meldet, ob es gesetzt war
+    pub fn take_changed(&self) -> bool {
+        self.changed.try_take().is_some()
+    }
+}
@@ -0,0 +1,71 @@
+// Geräte-Zustand für Diagnose-Telemetrie (WLAN-Signal, Chip-Temperatur)
+//
+// Der WiFi-Task misst die Signalstärke, solange eine Verbindung besteht
+// (`set_rssi`), und merkt sich die BSSID des Access Points aus dem Scan vor
+// dem Verbinden (`set_bssid`, für GET /api/network). Der interne Temperatursensor wird in main() übergeben und bei
+// Bedarf abgefragt (ESP32-S3: kein Treiber in esp-hal, Temperatur bleibt `None`).
+// MQTT liest beides für die Home-Assistant-Sensoren.
+
+use core::cell::Cell;
+#[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+#[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+use esp_hal::tsens::TemperatureSensor;
+
+/// Letzte Signalstärke in dBm, `None` ohne WLAN-Verbindung
+static RSSI: Mutex<CriticalSectionRawMutex, Cell<Option<i32>>> = Mutex::new(Cell::new(None));
+
+/// BSSID des Access Points, `None` ohne WLAN-Verbindung
+static BSSID: Mutex<CriticalSectionRawMutex, Cell<Option<[u8; 6]>>> = Mutex::new(Cell::new(None));
+
+/// Interner Temperatursensor, `None` bis `init_temperature_sensor`
+#[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+static TEMPERATURE_SENSOR: Mutex<
+    CriticalSectionRawMutex,
+    RefCell<Option<TemperatureSensor<'static>>>,
+> = Mutex::new(RefCell::new(None));
+
+/// Setzt die Signalstärke (`None` nach Verbindungsabbruch)
+pub fn set_rssi(rssi: Option<i32>) {
+    RSSI.lock(|cell| cell.set(rssi));
+}
+
+/// Letzte gemessene Signalstärke in dBm
+pub fn rssi() -> Option<i32> {
+    RSSI.lock(Cell::get)
+}
+
+/// Setzt die BSSID (`None` nach Verbindungsabbruch)
+pub fn set_bssid(bssid: Option<[u8; 6]>) {
+    BSSID.lock(|cell| cell.set(bssid));
+}
+
+/// BSSID des verbundenen Access Points
+pub fn bssid() -> Option<[u8; 6]> {
+    BSSID.lock(Cell::get)
+}
+
+/// Übernimmt den Temperatursensor (einmalig beim Start)
+#[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+pub fn init_temperature_sensor(sensor: TemperatureSensor<'static>) {
+    TEMPERATURE_SENSOR.lock(|cell| *cell.borrow_mut() = Some(sensor));
+}
+
+/// Aktuelle Chip-Temperatur in °C
+#[cfg(any(feature = "esp32c6", feature = "esp32c3"))]
+pub fn chip_temperature() -> Option<f32> {
+    TEMPERATURE_SENSOR.lock(|cell| {
+        cell.borrow()
+            .as_ref()
+            .map(|sensor| sensor.get_temperature().to_celsius())
+    })
+}
+
+/// Aktuelle Chip-Temperatur in °C (ESP32-S3: ohne Sensor-Treiber)
+#[cfg(feature = "esp32s3")]
+pub fn chip_temperature() -> Option<f32> {
+    None
+}
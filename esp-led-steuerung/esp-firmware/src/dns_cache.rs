@@ -0,0 +1,52 @@
+// DNS-Cache der Broker-Adresse (MQTT-Task schreibt, Debug-Konsole liest)
+//
+// Der MQTT-Task fragt den Cache vor jedem Connect ab und löst den Hostnamen
+// nur ohne gültigen Eintrag neu auf. Schlägt die TCP-Verbindung zur
+// gecachten Adresse fehl, wird der Eintrag verworfen.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::Instant;
+use esp_core::DnsCache;
+
+use crate::config::DNS_CACHE_TTL_SECS;
+
+static BROKER_CACHE: Mutex<CriticalSectionRawMutex, Cell<DnsCache>> =
+    Mutex::new(Cell::new(DnsCache::new(DNS_CACHE_TTL_SECS * 1000)));
+
+/// Gültige Broker-Adresse aus dem Cache
+pub fn cached_broker_address() -> Option<[u8; 4]> {
+    let now_ms = Instant::now().as_millis();
+    BROKER_CACHE.lock(|cell| {
+        let mut cache = cell.get();
+        let address = cache.lookup(now_ms);
+        cell.set(cache);
+        address
+    })
+}
+
+/// Speichert eine frisch aufgelöste Broker-Adresse
+pub fn store_broker_address(address: [u8; 4]) {
+    let now_ms = Instant::now().as_millis();
+    BROKER_CACHE.lock(|cell| {
+        let mut cache = cell.get();
+        cache.store(address, now_ms);
+        cell.set(cache);
+    });
+}
+
+/// Verwirft die gecachte Adresse (Verbindung fehlgeschlagen)
+pub fn invalidate_broker_address() {
+    BROKER_CACHE.lock(|cell| {
+        let mut cache = cell.get();
+        cache.invalidate();
+        cell.set(cache);
+    });
+}
+
+/// Kopie des Caches für die Anzeige
+pub fn broker_cache() -> DnsCache {
+    BROKER_CACHE.lock(Cell::get)
+}
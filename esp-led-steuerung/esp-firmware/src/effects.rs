@@ -0,0 +1,23 @@
+// Aktiver Effekt (GET /api/effects)
+//
+// Der LED-Task meldet nach jedem Durchlauf den Effekt laut Zustand
+// (`LedState::active_effect`) und beim Start der pixelweisen Modi
+// (Testmuster, Lauftext, Uhrzeit, Realtime) deren Kennung. Den Katalog
+// selbst liefert esp_core::effects.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use esp_core::EffectId;
+
+/// Aktiver Effekt (Start: Auto-Rotation)
+static ACTIVE_EFFECT: AtomicU8 = AtomicU8::new(EffectId::Auto as u8);
+
+/// Aktiver Effekt
+pub fn active_effect() -> EffectId {
+    EffectId::from_u8(ACTIVE_EFFECT.load(Ordering::Relaxed)).unwrap_or(EffectId::Auto)
+}
+
+/// Meldet den aktiven Effekt
+pub fn set_active_effect(effect: EffectId) {
+    ACTIVE_EFFECT.store(effect as u8, Ordering::Relaxed);
+}
@@ -0,0 +1,29 @@
+// Fehler-Zähler für Telemetrie
+//
+// Alle Tasks melden Fehler über `report_error()` (lib.rs), das hier pro
+// Kategorie zählt und das Ereignis auf den Event-Bus legt. Die Zähler laufen
+// seit dem Start und werden nicht zurückgesetzt.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use esp_core::{ErrorCounters, FirmwareError};
+
+/// Zähler seit dem Start (CriticalSection, da `static`)
+static COUNTERS: Mutex<CriticalSectionRawMutex, Cell<ErrorCounters>> =
+    Mutex::new(Cell::new(ErrorCounters::new()));
+
+/// Zählt einen Fehler
+pub fn record_error(error: &FirmwareError) {
+    COUNTERS.lock(|counters| {
+        let mut updated = counters.get();
+        updated.record(error);
+        counters.set(updated);
+    });
+}
+
+/// Aktueller Stand aller Zähler
+pub fn error_counters() -> ErrorCounters {
+    COUNTERS.lock(Cell::get)
+}
@@ -0,0 +1,116 @@
+// Chip-Auswahl: Unterschiede zwischen ESP32-C6, ESP32-C3 und ESP32-S3
+//
+// Der Chip wird zur Compile-Zeit über ein Cargo-Feature gewählt (genau eins,
+// Standard `esp32c6`). Das Feature aktiviert die passenden Chip-Features von
+// esp-hal, esp-radio, esp-rtos usw.; das Target muss dazu passen:
+//
+// | Feature   | Target                         | Toolchain     | Temperatursensor |
+// |-----------|--------------------------------|---------------|------------------|
+// | `esp32c6` | `riscv32imac-unknown-none-elf` | stable        | ja               |
+// | `esp32c3` | `riscv32imc-unknown-none-elf`  | stable        | ja               |
+// | `esp32s3` | `xtensa-esp32s3-none-elf`      | esp (`espup`) | nein             |
+//
+// Beispiel ESP32-C3 (andere Chips analog, Runner siehe .cargo/config.toml):
+//   cargo run --release --target riscv32imc-unknown-none-elf \
+//     --no-default-features --features esp32c3,ble,mdns,mqtt,websocket
+//
+// Hier steht nur, was vom Chip abhängt (Name, SRAM-Bereich, Pins der
+// UART-Konsole). Pins von LED und Taster kommen aus dem Board-Profil
+// (`boards.rs`), dessen Standard ebenfalls dem Chip folgt. Die Logik in
+// esp-core ist chip-unabhängig.
+
+#[cfg(not(any(feature = "esp32c6", feature = "esp32c3", feature = "esp32s3")))]
+compile_error!("Ein Chip-Feature aktivieren (esp32c6, esp32c3, esp32s3)");
+
+#[cfg(any(
+    all(feature = "esp32c6", feature = "esp32c3"),
+    all(feature = "esp32c6", feature = "esp32s3"),
+    all(feature = "esp32c3", feature = "esp32s3"),
+))]
+compile_error!("Nur ein Chip-Feature aktivieren (esp32c6, esp32c3, esp32s3)");
+
+pub use chip::*;
+
+// ============================================================================
+// ESP32-C6 (Standard): RISC-V, WiFi 6, BLE 5, 802.15.4
+// ============================================================================
+
+#[cfg(feature = "esp32c6")]
+mod chip {
+    /// Chip-Bezeichnung für Logs, Geräte-Info und Home Assistant
+    pub const CHIP_NAME: &str = "ESP32-C6";
+
+    /// Internes SRAM (HP-SRAM, 512 KB): gültige Frame-Pointer im Absturz-Protokoll
+    pub const SRAM_START: u32 = 0x4080_0000;
+    pub const SRAM_END: u32 = 0x4088_0000;
+
+    /// Anzeigename des Geräts (Home Assistant, Web of Things)
+    pub const DEVICE_NAME: &str = "ESP32-C6 LED-Steuerung";
+
+    /// Entnimmt TX- und RX-Pin der UART-Konsole: `(AnyPin, AnyPin)`
+    ///
+    /// TX an GPIO18, RX an GPIO19.
+    #[macro_export]
+    macro_rules! uart_console_pins {
+        ($peripherals:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            ($peripherals.GPIO18.degrade(), $peripherals.GPIO19.degrade())
+        }};
+    }
+}
+
+// ============================================================================
+// ESP32-C3: RISC-V ohne Atomics, GPIO18/19 sind USB
+// ============================================================================
+
+#[cfg(feature = "esp32c3")]
+mod chip {
+    /// Chip-Bezeichnung für Logs, Geräte-Info und Home Assistant
+    pub const CHIP_NAME: &str = "ESP32-C3";
+
+    /// Internes SRAM (Datenbus, ohne Cache): gültige Frame-Pointer im Absturz-Protokoll
+    pub const SRAM_START: u32 = 0x3FC8_0000;
+    pub const SRAM_END: u32 = 0x3FCE_0000;
+
+    /// Anzeigename des Geräts (Home Assistant, Web of Things)
+    pub const DEVICE_NAME: &str = "ESP32-C3 LED-Steuerung";
+
+    /// Entnimmt TX- und RX-Pin der UART-Konsole: `(AnyPin, AnyPin)`
+    ///
+    /// TX an GPIO10, RX an GPIO3 (GPIO18/19 gehören dem USB-Serial-JTAG).
+    #[macro_export]
+    macro_rules! uart_console_pins {
+        ($peripherals:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            ($peripherals.GPIO10.degrade(), $peripherals.GPIO3.degrade())
+        }};
+    }
+}
+
+// ============================================================================
+// ESP32-S3: Xtensa Dual-Core, GPIO19/20 sind USB
+// ============================================================================
+
+#[cfg(feature = "esp32s3")]
+mod chip {
+    /// Chip-Bezeichnung für Logs, Geräte-Info und Home Assistant
+    pub const CHIP_NAME: &str = "ESP32-S3";
+
+    /// Internes SRAM (Datenbus, ohne Cache): gültige Frame-Pointer im Absturz-Protokoll
+    pub const SRAM_START: u32 = 0x3FC8_8000;
+    pub const SRAM_END: u32 = 0x3FD0_0000;
+
+    /// Anzeigename des Geräts (Home Assistant, Web of Things)
+    pub const DEVICE_NAME: &str = "ESP32-S3 LED-Steuerung";
+
+    /// Entnimmt TX- und RX-Pin der UART-Konsole: `(AnyPin, AnyPin)`
+    ///
+    /// TX an GPIO17, RX an GPIO18 (GPIO19/20 gehören dem USB-Serial-JTAG).
+    #[macro_export]
+    macro_rules! uart_console_pins {
+        ($peripherals:expr) => {{
+            use esp_hal::gpio::Pin as _;
+            ($peripherals.GPIO17.degrade(), $peripherals.GPIO18.degrade())
+        }};
+    }
+}
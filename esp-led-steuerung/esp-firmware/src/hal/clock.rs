@@ -0,0 +1,21 @@
+// Clock Implementierung auf Basis von embassy-time
+//
+// Monotone Millisekunden seit Boot, Mikrosekunden für Laufzeitmessungen. Tests auf dem Host nutzen stattdessen
+// eine MockClock mit manuell vorgestellter Zeit.
+
+use embassy_time::Instant;
+use esp_core::Clock;
+
+/// Monotone Uhr über embassy-time (Millisekunden seit Boot)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmbassyClock;
+
+impl Clock for EmbassyClock {
+    fn now_ms(&self) -> u64 {
+        Instant::now().as_millis()
+    }
+
+    fn now_us(&self) -> u64 {
+        Instant::now().as_micros()
+    }
+}
@@ -1,27 +1,12 @@
-// SmartLED Writer Trait und Implementierungen
+// SmartLED Writer Implementierungen
 //
 // Abstrahiert den Zugriff auf RGB LEDs (WS2812/Neopixel)
 // um Tests mit Mock-Implementierungen zu ermöglichen.
 
 use rgb::RGB8;
 
-/// Fehler-Typ für LED-Schreiboperationen
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum LedError {
-    WriteFailed,
-}
-
-/// Trait für LED-Writer
-///
-/// Abstrahiert den Zugriff auf SmartLEDs (WS2812/Neopixel).
-/// Ermöglicht Mock-Implementierungen für Tests.
-pub trait SmartLedWriter: Send {
-    /// Schreibt eine RGB-Farbe auf die LED
-    ///
-    /// # Fehlerbehandlung
-    /// Gibt LedError::WriteFailed zurück wenn Hardware-Zugriff fehlschlägt
-    fn write(&mut self, color: RGB8) -> Result<(), LedError>;
-}
+// Trait und Fehler-Typ kommen aus esp-core (gemeinsam mit Tests und LedLoop)
+pub use esp_core::{LedError, SmartLedWriter};
 
 // ============================================================================
 // Real Hardware Implementation (nur für ESP32-Target)
@@ -30,15 +15,15 @@ pub trait SmartLedWriter: Send {
 #[cfg(not(test))]
 mod real_impl {
     use super::*;
+    use crate::boards::{LED_BUFFER_SIZE, LedPin};
+    use crate::config::LED_COUNT;
+    use esp_core::{ColorOrder, StripMapping};
     use esp_hal::Blocking;
     use esp_hal::rmt::Rmt;
     use esp_hal::time::Rate;
     use esp_hal_smartled::SmartLedsAdapter;
     use smart_leds_trait::SmartLedsWrite;
 
-    // Buffer-Größe für 1 LED (3 Farben * 8 Bits + 1 Reset)
-    const LED_BUFFER_SIZE: usize = 25;
-
     /// Real Hardware LED Writer
     ///
     /// Nutzt ESP32 RMT Peripheral um WS2812 LEDs anzusteuern.
@@ -47,18 +32,20 @@ mod real_impl {
     /// und als Parameter übergeben statt im Constructor allokiert.
     pub struct RmtLedWriter<'a> {
         led: SmartLedsAdapter<'a, LED_BUFFER_SIZE>,
+        color_order: ColorOrder,
+        mapping: StripMapping,
     }
 
     impl<'a> RmtLedWriter<'a> {
         /// Erstellt einen neuen RmtLedWriter
         ///
         /// # Parameter
-        /// - `gpio8`: GPIO8 Peripheral für LED-Datenleitung
+        /// - `led_pin`: Pin der LED-Datenleitung (zur Laufzeit gewählt, siehe `board_pins!`)
         /// - `rmt_peripheral`: RMT Peripheral
         /// - `rmt_clock_mhz`: RMT Clock Frequenz in MHz (z.B. 80)
-        /// - `buffer`: Buffer für LED-Daten (erstellt mit smart_led_buffer!(1) Macro)
+        /// - `buffer`: Buffer für LED-Daten (`boards::LED_BUFFER_SIZE` Einträge)
         pub fn new(
-            gpio8: esp_hal::peripherals::GPIO8<'a>,
+            led_pin: LedPin,
             rmt_peripheral: esp_hal::peripherals::RMT<'a>,
             rmt_clock_mhz: u32,
             buffer: &'a mut [esp_hal::rmt::PulseCode; LED_BUFFER_SIZE],
@@ -68,18 +55,59 @@ mod real_impl {
                 Rmt::new(rmt_peripheral, Rate::from_mhz(rmt_clock_mhz)).unwrap();
 
             // SmartLED Adapter erstellen
-            let led = SmartLedsAdapter::new(rmt.channel0, gpio8, buffer);
+            let led = SmartLedsAdapter::new(rmt.channel0, led_pin, buffer);
 
-            Self { led }
+            Self {
+                led,
+                color_order: ColorOrder::default(),
+                mapping: StripMapping::default(),
+            }
         }
     }
 
+    /// Ordnet die Kanäle für den Adapter um
+    ///
+    /// Der Adapter sendet immer G, R, B. Damit die Bytes auf der Leitung
+    /// der eingestellten Reihenfolge entsprechen, wird die Farbe vorher
+    /// passend vertauscht.
+    fn reorder(order: ColorOrder, color: RGB8) -> RGB8 {
+        let [first, second, third] = order.wire_bytes(color);
+        RGB8::new(second, first, third)
+    }
+
     impl<'a> SmartLedWriter for RmtLedWriter<'a> {
+        /// Setzt alle LEDs des Strips auf dieselbe Farbe
         fn write(&mut self, color: RGB8) -> Result<(), LedError> {
             self.led
-                .write([color].into_iter())
+                .write(core::iter::repeat_n(
+                    reorder(self.color_order, color),
+                    LED_COUNT,
+                ))
                 .map_err(|_| LedError::WriteFailed)
         }
+
+        /// Schreibt bis zu LED_COUNT Pixel, fehlende werden schwarz
+        ///
+        /// Jeder physische Pixel holt seine Farbe über die Strip-Zuordnung
+        /// (umgekehrt/gespiegelt) aus dem logischen Frame.
+        fn write_frame(&mut self, pixels: &[RGB8]) -> Result<(), LedError> {
+            let (order, mapping) = (self.color_order, self.mapping);
+            let frame = (0..LED_COUNT).map(|i| {
+                let logical = mapping.logical_index(i, LED_COUNT);
+                reorder(order, pixels.get(logical).copied().unwrap_or_default())
+            });
+            self.led.write(frame).map_err(|_| LedError::WriteFailed)
+        }
+
+        /// Übernimmt die Kanal-Reihenfolge aus der Gerätekonfiguration
+        fn set_color_order(&mut self, order: ColorOrder) {
+            self.color_order = order;
+        }
+
+        /// Übernimmt die Strip-Zuordnung aus der Gerätekonfiguration
+        fn set_strip_mapping(&mut self, mapping: StripMapping) {
+            self.mapping = mapping;
+        }
     }
 }
 
@@ -87,43 +115,11 @@ mod real_impl {
 pub use real_impl::RmtLedWriter;
 
 // ============================================================================
-// Mock Implementation (nur für Tests)
+// Mock Implementation (nur für Tests, gemeinsam mit esp-tests und Simulator)
 // ============================================================================
 
 #[cfg(test)]
-pub struct MockLedWriter {
-    /// Zuletzt geschriebene Farbe (für Assertions in Tests)
-    pub last_color: Option<RGB8>,
-    /// Anzahl der write() Aufrufe
-    pub write_count: usize,
-    /// Simuliere Fehler beim nächsten write()
-    pub fail_next_write: bool,
-}
-
-#[cfg(test)]
-impl MockLedWriter {
-    pub fn new() -> Self {
-        Self {
-            last_color: None,
-            write_count: 0,
-            fail_next_write: false,
-        }
-    }
-}
-
-#[cfg(test)]
-impl SmartLedWriter for MockLedWriter {
-    fn write(&mut self, color: RGB8) -> Result<(), LedError> {
-        if self.fail_next_write {
-            self.fail_next_write = false;
-            return Err(LedError::WriteFailed);
-        }
-
-        self.last_color = Some(color);
-        self.write_count += 1;
-        Ok(())
-    }
-}
+pub use esp_core::mock::MockLedWriter;
 
 // ============================================================================
 // Tests
@@ -138,13 +134,13 @@ mod tests {
         let mut mock = MockLedWriter::new();
         let color = RGB8 { r: 10, g: 0, b: 0 };
 
-        assert_eq!(mock.write_count, 0);
-        assert_eq!(mock.last_color, None);
+        assert_eq!(mock.write_count(), 0);
+        assert_eq!(mock.last_color(), None);
 
         mock.write(color).unwrap();
 
-        assert_eq!(mock.write_count, 1);
-        assert_eq!(mock.last_color, Some(color));
+        assert_eq!(mock.write_count(), 1);
+        assert_eq!(mock.last_color(), Some(color));
     }
 
     #[test]
@@ -155,18 +151,18 @@ mod tests {
         mock.write(RGB8 { r: 0, g: 10, b: 0 }).unwrap();
         mock.write(RGB8 { r: 0, g: 0, b: 10 }).unwrap();
 
-        assert_eq!(mock.write_count, 3);
-        assert_eq!(mock.last_color, Some(RGB8 { r: 0, g: 0, b: 10 }));
+        assert_eq!(mock.write_count(), 3);
+        assert_eq!(mock.last_color(), Some(RGB8 { r: 0, g: 0, b: 10 }));
     }
 
     #[test]
     fn test_mock_led_writer_fail() {
         let mut mock = MockLedWriter::new();
-        mock.fail_next_write = true;
+        mock.fail_next_write();
 
         let result = mock.write(RGB8 { r: 10, g: 0, b: 0 });
         assert_eq!(result, Err(LedError::WriteFailed));
-        assert_eq!(mock.write_count, 0);
-        assert_eq!(mock.last_color, None);
+        assert_eq!(mock.write_count(), 0);
+        assert_eq!(mock.last_color(), None);
     }
 }
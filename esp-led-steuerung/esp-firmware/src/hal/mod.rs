@@ -3,9 +3,14 @@
 // Dieses Modul kapselt Hardware-Zugriffe hinter Traits,
 // um Testbarkeit und Wartbarkeit zu verbessern.
 
+pub mod chip;
+pub mod clock;
 pub mod led_writer;
+pub mod rng;
 
+pub use clock::EmbassyClock;
 pub use led_writer::{LedError, RmtLedWriter, SmartLedWriter};
+pub use rng::HardwareRng;
 
 #[cfg(test)]
 pub use led_writer::MockLedWriter;
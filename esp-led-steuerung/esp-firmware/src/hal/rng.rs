@@ -0,0 +1,28 @@
+// Zufallsquelle auf Basis des Hardware-RNG
+//
+// Liefert echte Zufallszahlen, solange WiFi oder BLE laufen (Rauschquelle
+// des Funkteils), sonst Pseudo-Zufall. Für die Farbwahl im Zufallsmodus
+// reicht beides. Tests auf dem Host nutzen stattdessen esp_core::SeededRng.
+
+use esp_core::Rng;
+
+/// Hardware-RNG des ESP32
+pub struct HardwareRng(esp_hal::rng::Rng);
+
+impl HardwareRng {
+    pub fn new() -> Self {
+        Self(esp_hal::rng::Rng::new())
+    }
+}
+
+impl Default for HardwareRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Rng for HardwareRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.random()
+    }
+}
@@ -0,0 +1,32 @@
+// Heap-Auslastung für Telemetrie
+//
+// Der Heap-Monitor (tasks/heap.rs) misst regelmäßig über `sample_heap()`,
+// HTTP-API, WebSocket und MQTT lesen nur den letzten Stand über `heap_stats()`.
+// Der Höchststand läuft seit dem Start und wird nicht zurückgesetzt.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use esp_core::HeapStats;
+
+/// Letzte Messung (CriticalSection, da `static`)
+static STATS: Mutex<CriticalSectionRawMutex, Cell<HeapStats>> =
+    Mutex::new(Cell::new(HeapStats::new()));
+
+/// Misst den Heap (esp-alloc) und aktualisiert den Höchststand
+pub fn sample_heap() -> HeapStats {
+    let used = esp_alloc::HEAP.used() as u32;
+    let free = esp_alloc::HEAP.free() as u32;
+    STATS.lock(|stats| {
+        let mut updated = stats.get();
+        updated.record(used, free);
+        stats.set(updated);
+        updated
+    })
+}
+
+/// Stand der letzten Messung
+pub fn heap_stats() -> HeapStats {
+    STATS.lock(Cell::get)
+}
@@ -2,25 +2,57 @@
 // Keine Standard-Bibliothek (Embedded System)
 #![no_std]
 
+// Heap (esp-alloc) für dynamische Antworten, z.B. /metrics
+extern crate alloc;
+
 // Module
+pub mod boards;
+pub mod calibration;
+pub mod command_channel;
 pub mod config;
+pub mod crash;
+pub mod device_config;
+pub mod device_health;
+pub mod dns_cache;
+pub mod effects;
+pub mod error_stats;
 pub mod hal;
+pub mod heap_stats;
+pub mod log_level;
+pub mod log_ring;
+pub mod net_stats;
+pub mod preset;
+pub mod reboot;
+pub mod render_stats;
+pub mod schedule;
+pub mod storage;
+pub mod task_stats;
 pub mod tasks;
+pub mod tcp_tuning;
+pub mod trace;
+pub mod version;
+pub mod wall_clock;
 pub mod web;
+pub mod web_ui;
 
 // Re-exports von esp-core
-pub use esp_core::{LedColorMessage, LedCommand, LedError, SmartLedWriter, rotate_color};
+pub use esp_core::{
+    ColorId, FirmwareError, LedColorMessage, LedCommand, LedError, SmartLedWriter, SystemEvent,
+    rotate_color,
+};
 
-// RGB Farb-Typ (direkt von rgb crate)
-use rgb::RGB8;
+use esp_core::{LogLevel, Severity};
 
 // Embassy Channel-Typen
-use embassy_sync::blocking_mutex::raw::NoopRawMutex;
-use embassy_sync::channel::{Receiver, Sender};
+// SharedRawMutex für alles, was der LED-Task (Interrupt-Executor) mit den
+// Netzwerk-Tasks (Thread-Executor) teilt, sonst TaskRawMutex
+use core::cell::Cell;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
 use embassy_sync::pubsub::{PubSubChannel, Publisher, Subscriber};
 
 // Konfigurationswerte
-use crate::config::LED_BRIGHTNESS;
+use crate::config::{COMMAND_QUEUE_DEPTH, LED_BRIGHTNESS};
 
 // ============================================================================
 // Firmware-spezifische Implementierungen
@@ -32,33 +64,9 @@ use crate::config::LED_BRIGHTNESS;
 ///
 /// Dies überschreibt die Default-Implementierung aus esp-core die DEFAULT_BRIGHTNESS = 10 nutzt.
 pub fn led_command_from_name(name: &str) -> Result<LedCommand, ()> {
-    match name {
-        "Rot" => Ok(LedCommand::SetColor {
-            target_color: RGB8 {
-                r: LED_BRIGHTNESS,
-                g: 0,
-                b: 0,
-            },
-            name: "Rot",
-        }),
-        "Grün" => Ok(LedCommand::SetColor {
-            target_color: RGB8 {
-                r: 0,
-                g: LED_BRIGHTNESS,
-                b: 0,
-            },
-            name: "Grün",
-        }),
-        "Blau" => Ok(LedCommand::SetColor {
-            target_color: RGB8 {
-                r: 0,
-                g: 0,
-                b: LED_BRIGHTNESS,
-            },
-            name: "Blau",
-        }),
-        _ => Err(()),
-    }
+    ColorId::from_name(name)
+        .and_then(|id| id.command(LED_BRIGHTNESS))
+        .ok_or(())
 }
 
 // ============================================================================
@@ -66,34 +74,136 @@ pub fn led_command_from_name(name: &str) -> Result<LedCommand, ()> {
 // ============================================================================
 //
 // Diese Type-Aliase vereinfachen die Lesbarkeit der Funktionssignaturen.
-// Statt:  Publisher<'static, NoopRawMutex, LedColorMessage, 2, 10, 1>
+// Statt:  Publisher<'static, SharedRawMutex, LedColorMessage, 2, 19, 1>
 // Nutze:  LedColorPublisher
 
+/// Mutex für Daten, die der LED-Task (Interrupt-Executor) mit anderen Tasks teilt
+pub type SharedRawMutex = CriticalSectionRawMutex;
+
+/// Mutex für Daten, die nur Tasks des Thread-Executors teilen
+///
+/// Auf dem Single-Core ESP32-C6 mit einem Thread-Executor genügt
+/// `NoopRawMutex`. Feature `multicore` (zweiter Thread-Executor oder
+/// Mehrkern-Chip) wählt `CriticalSectionRawMutex`.
+#[cfg(not(feature = "multicore"))]
+pub type TaskRawMutex = embassy_sync::blocking_mutex::raw::NoopRawMutex;
+
+/// Mutex für Daten, die nur Tasks des Thread-Executors teilen
+///
+/// Feature `multicore`: Tasks können parallel auf mehreren Executors laufen.
+#[cfg(feature = "multicore")]
+pub type TaskRawMutex = CriticalSectionRawMutex;
+
+/// ColorSink für den LED-Loop: veröffentlicht über den PubSubChannel
+///
+/// Newtype, da weder Trait (esp-core) noch Publisher (embassy) aus diesem Crate stammen.
+pub struct PublisherSink(pub LedColorPublisher);
+
+impl esp_core::ColorSink for PublisherSink {
+    fn publish(&mut self, msg: LedColorMessage) {
+        LAST_COLOR.lock(|last| last.set(Some(msg)));
+        // Nicht blockieren: langsame Subscriber verpassen ggf. ältere Updates
+        self.0.publish_immediate(msg);
+    }
+}
+
+/// Letzter Farb-Broadcast (None bis zum ersten Durchlauf des LED-Tasks)
+static LAST_COLOR: Mutex<SharedRawMutex, Cell<Option<LedColorMessage>>> =
+    Mutex::new(Cell::new(None));
+
+/// Letzter Farb-Broadcast, für Abfragen ohne eigenen Subscriber (z.B. WoT-Properties)
+pub fn last_color_message() -> Option<LedColorMessage> {
+    LAST_COLOR.lock(|last| last.get())
+}
+
 /// PubSubChannel für LED-Farb-Broadcasts
 /// - 2: Nachrichten-Kapazität im Queue
-/// - 10: Maximale Anzahl Subscribers (1 MQTT + bis zu 9 WebSockets)
+/// - 19: Maximale Anzahl Subscribers (1 MQTT + 1 MQTT-SN + 1 BLE + 1 CoAP + 1 Konsole + 1 UART-Konsole + 1 USB-Konsole + 1 Matter + 1 ESP-NOW + 1 LIFX + bis zu 9 WebSockets)
 /// - 1: Publish WaitResult Slots
-pub type LedColorChannel = PubSubChannel<NoopRawMutex, LedColorMessage, 2, 10, 1>;
+pub type LedColorChannel = PubSubChannel<SharedRawMutex, LedColorMessage, 2, 19, 1>;
 
 /// Publisher für LED-Farb-Broadcasts
 /// Erzeugt aus LedColorChannel
-pub type LedColorPublisher = Publisher<'static, NoopRawMutex, LedColorMessage, 2, 10, 1>;
+pub type LedColorPublisher = Publisher<'static, SharedRawMutex, LedColorMessage, 2, 19, 1>;
 
 /// Subscriber für LED-Farb-Broadcasts
 /// Empfängt Broadcasts von LedColorPublisher
-pub type LedColorSubscriber = Subscriber<'static, NoopRawMutex, LedColorMessage, 2, 10, 1>;
+pub type LedColorSubscriber = Subscriber<'static, SharedRawMutex, LedColorMessage, 2, 19, 1>;
+
+/// PubSubChannel für System-Ereignisse (WLAN, MQTT, Clients, Fehler)
+/// - 8: Nachrichten-Kapazität (Ereignisse kommen in Schüben, z.B. WLAN → IP → MQTT)
+/// - 4: Maximale Anzahl Subscribers (Status-LED, MQTT, Web UI, Reserve)
+/// - 1: Publish WaitResult Slots (Tasks nutzen nur `publish_immediate`)
+pub type SystemEventChannel = PubSubChannel<TaskRawMutex, SystemEvent, 8, 4, 1>;
+
+/// Subscriber für System-Ereignisse
+pub type SystemEventSubscriber = Subscriber<'static, TaskRawMutex, SystemEvent, 8, 4, 1>;
+
+/// Veröffentlicht ein System-Ereignis ohne zu warten
+///
+/// Bei vollem Channel verpassen langsame Subscriber das älteste Ereignis,
+/// auslösende Tasks (WLAN, MQTT, HTTP) werden nie blockiert.
+pub fn publish_event(events: &SystemEventChannel, event: SystemEvent) {
+    match event {
+        // Fehler protokolliert report_error() mit Details
+        SystemEvent::Error(_) => {}
+        SystemEvent::TaskStalled { task, idle_ms } => log_ring::log_line(
+            LogLevel::Error,
+            format_args!("Task '{}' stalled ({} ms idle)", task.name(), idle_ms),
+        ),
+        other => log_ring::log_line(LogLevel::Info, format_args!("Event: {}", other.name())),
+    }
+    events.immediate_publisher().publish_immediate(event);
+}
 
-/// Channel für LED-Kommandos (WebSocket → LED Task)
-/// - 1: Nachrichten-Kapazität (nur ein Command zur Zeit)
-pub type LedCommandChannel = embassy_sync::channel::Channel<NoopRawMutex, LedCommand, 1>;
+/// Meldet einen Fehler: zählt ihn (Telemetrie) und veröffentlicht `SystemEvent::Error`
+///
+/// Details loggt der aufrufende Task selbst (private Fehler-Enum),
+/// hier wird nur die gemeinsame Taxonomie weitergegeben.
+pub fn report_error(events: &SystemEventChannel, error: FirmwareError) {
+    error_stats::record_error(&error);
+    let level = match error.severity() {
+        Severity::Error => LogLevel::Error,
+        Severity::Warning => LogLevel::Warn,
+        Severity::Info => LogLevel::Info,
+    };
+    log_ring::log_line(
+        level,
+        format_args!("{} error: {}", error.source.name(), error.kind.name()),
+    );
+    if let Some(task) = esp_core::TaskId::for_source(error.source) {
+        task_stats::task_error(task);
+    }
+    publish_event(events, SystemEvent::Error(error));
+}
+
+/// Prioritäts-Channel für LED-Kommandos (alle Quellen → LED Task)
+/// - COMMAND_QUEUE_DEPTH: Kapazität der Warteschlange (Policy siehe esp_core::queue)
+pub type LedCommandChannel = command_channel::CommandChannel<SharedRawMutex, COMMAND_QUEUE_DEPTH>;
 
-/// Sender für LED-Kommandos (WebSocket → LED Task)
+/// Sender für LED-Kommandos (WebSocket, MQTT, ... → LED Task)
 /// Erzeugt aus LedCommandChannel
-pub type LedCommandSender = Sender<'static, NoopRawMutex, LedCommand, 1>;
+pub type LedCommandSender =
+    command_channel::CommandSender<'static, SharedRawMutex, COMMAND_QUEUE_DEPTH>;
 
 /// Receiver für LED-Kommandos (LED Task empfängt)
 /// Empfängt Commands von LedCommandSender
-pub type LedCommandReceiver = Receiver<'static, NoopRawMutex, LedCommand, 1>;
+pub type LedCommandReceiver =
+    command_channel::CommandReceiver<'static, SharedRawMutex, COMMAND_QUEUE_DEPTH>;
+
+/// Signal für UDP-Realtime-Frames (Realtime Task → LED Task)
+/// Signal statt Channel: nur der jeweils neueste Frame ist relevant
+pub type RealtimeSignal =
+    embassy_sync::signal::Signal<SharedRawMutex, tasks::realtime::RealtimeFrame>;
+
+/// Signal für Sync-Frames des Leaders (LED Task → Sync Task)
+/// Signal statt Channel: nur der jeweils neueste Frame wird gesendet
+pub type SyncSignal = embassy_sync::signal::Signal<SharedRawMutex, esp_core::SyncFrame>;
+
+/// Geteilter Flash-Storage (Scheduler und spätere Einstellungen)
+/// Async Mutex, da Flash-Zugriffe mehrere Millisekunden dauern können.
+/// `SharedRawMutex`, damit web_ui.rs eine Referenz in einem `static` halten kann.
+pub type SharedStorage = embassy_sync::mutex::Mutex<SharedRawMutex, storage::Storage>;
 
 // ============================================================================
 // Testing-Strategie für Embedded no_std Crates
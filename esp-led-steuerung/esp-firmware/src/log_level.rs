@@ -0,0 +1,27 @@
+// Laufzeit-Log-Level (über die Konsole änderbar)
+//
+// defmt filtert zur Compile-Zeit (DEFMT_LOG), dieses Level kommt als
+// zweite Stufe zur Laufzeit hinzu: Tasks prüfen mit `log_enabled()`, ob
+// ausführliche Ausgaben erwünscht sind, ohne neu zu flashen.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use esp_core::LogLevel;
+
+/// Aktuelles Log-Level (Standard: Info)
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+/// Aktuelles Log-Level
+pub fn log_level() -> LogLevel {
+    LogLevel::from_u8(LOG_LEVEL.load(Ordering::Relaxed)).unwrap_or(LogLevel::Info)
+}
+
+/// Setzt das Log-Level
+pub fn set_log_level(level: LogLevel) {
+    LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Prüft ob Ausgaben dieses Levels aktiv sind
+pub fn log_enabled(level: LogLevel) -> bool {
+    level <= log_level()
+}
@@ -0,0 +1,57 @@
+// Log-Ringpuffer im RAM (GET /api/logs)
+//
+// defmt-Ausgaben werden binär übertragen und erst auf dem Host dekodiert, das
+// Gerät selbst kennt ihren Text nicht. In den Ringpuffer gelangen deshalb nur
+// Zeilen, die ausdrücklich über `log_line()` geschrieben werden: alle
+// System-Ereignisse und gemeldeten Fehler (lib.rs) sowie einzelne Meldungen.
+// Das Laufzeit-Log-Level (log_level.rs) gilt auch hier.
+
+use core::cell::RefCell;
+use core::fmt;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::Instant;
+use esp_core::{LogLevel, LogLine, LogRing};
+
+use crate::config::{LOG_LINE_LEN, LOG_RING_LINES};
+use crate::log_level::log_enabled;
+
+/// Ringpuffer mit der Firmware-Konfiguration
+pub type FirmwareLogRing = LogRing<LOG_RING_LINES, LOG_LINE_LEN>;
+
+/// Einzelne Zeile mit der Firmware-Konfiguration
+pub type FirmwareLogLine = LogLine<LOG_LINE_LEN>;
+
+/// Gespeicherte Zeilen seit dem Start (CriticalSection, da `static`)
+static RING: Mutex<CriticalSectionRawMutex, RefCell<FirmwareLogRing>> =
+    Mutex::new(RefCell::new(LogRing::new()));
+
+/// Schreibt eine Zeile in den Ringpuffer (wenn das Level aktiv ist)
+pub fn log_line(level: LogLevel, args: fmt::Arguments<'_>) {
+    if !log_enabled(level) {
+        return;
+    }
+    let now_ms = Instant::now().as_millis();
+    RING.lock(|ring| {
+        ring.borrow_mut().push(now_ms, level, args);
+    });
+}
+
+/// Nummer, die die nächste Zeile erhält (neue Zeilen erkennen ohne Kopie)
+pub fn log_next_seq() -> u32 {
+    RING.lock(|ring| ring.borrow().next_seq())
+}
+
+/// Kopie aller gespeicherten Zeilen
+pub fn log_snapshot() -> FirmwareLogRing {
+    RING.lock(|ring| ring.borrow().clone())
+}
+
+/// Älteste gespeicherte Zeile ab Nummer `seq` (kopiert nur diese Zeile)
+///
+/// Für lang laufende Leser wie GET /api/logs/stream, die den ganzen Puffer
+/// nicht über `.await` hinweg halten sollen.
+pub fn log_line_since(seq: u32) -> Option<FirmwareLogLine> {
+    RING.lock(|ring| ring.borrow().since(seq).next().copied())
+}
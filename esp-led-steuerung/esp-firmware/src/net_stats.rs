@@ -0,0 +1,34 @@
+// Netzwerk-Zähler (GET /metrics, Konsole `net`)
+//
+// MQTT-, HTTP/WebSocket- und UDP-Tasks melden ihren Verkehr über
+// `record_net()`. Die Zähler (esp_core::net_stats) laufen seit dem Start
+// und werden nicht zurückgesetzt.
+
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use esp_core::{NetCounter, NetStats};
+
+/// Zähler seit dem Start (CriticalSection, da `static`)
+static STATS: Mutex<CriticalSectionRawMutex, Cell<NetStats>> =
+    Mutex::new(Cell::new(NetStats::new()));
+
+/// Zählt einen Vorgang
+pub fn record_net(counter: NetCounter) {
+    record_net_count(counter, 1);
+}
+
+/// Zählt mehrere Vorgänge (z.B. Requests einer Keep-Alive-Verbindung)
+pub fn record_net_count(counter: NetCounter, amount: u32) {
+    STATS.lock(|stats| {
+        let mut updated = stats.get();
+        updated.add(counter, amount);
+        stats.set(updated);
+    });
+}
+
+/// Aktueller Stand aller Zähler
+pub fn net_stats() -> NetStats {
+    STATS.lock(Cell::get)
+}
@@ -0,0 +1,114 @@
+// Preset-Store: geteilte Presets für LED-Task, Taster und HTTP-API
+//
+// Die Presets selbst (esp_core::Presets) sind reine Logik. Dieser Store
+// kapselt sie hinter einem Mutex; der LED-Task löst `LedCommand::ApplyPreset`
+// darüber auf (esp_core::PresetSource). Änderungen über die API werden dem
+// Scheduler-Task signalisiert, der sie dann in den Flash schreibt.
+//
+// CriticalSectionRawMutex, da der LED-Task auf einem eigenen
+// Interrupt-Executor läuft (siehe bin/main.rs).
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use esp_core::{Preset, PresetError, PresetSource, Presets};
+
+use crate::config::{DEFAULT_PRESETS, PRESET_CAPACITY};
+
+/// Presets mit Firmware-Kapazität
+pub type FirmwarePresets = Presets<PRESET_CAPACITY>;
+
+/// Geteilte Presets mit Änderungs-Signal
+pub struct PresetStore {
+    presets: Mutex<CriticalSectionRawMutex, RefCell<FirmwarePresets>>,
+    changed: Signal<CriticalSectionRawMutex, ()>,
+}
+
+impl Default for PresetStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresetStore {
+    /// Store mit den Standard-Presets aus der Konfiguration (IDs ab 1)
+    ///
+    /// Gespeicherte Presets ersetzen sie beim Start (siehe `replace`).
+    pub fn new() -> Self {
+        let mut presets = FirmwarePresets::new();
+        for preset in DEFAULT_PRESETS {
+            // Kapazität und Werte sind zur Compile-Zeit geprüft
+            let _ = presets.add(preset);
+        }
+        Self {
+            presets: Mutex::new(RefCell::new(presets)),
+            changed: Signal::new(),
+        }
+    }
+
+    /// Lesender Zugriff auf die Presets
+    pub fn with<R>(&self, f: impl FnOnce(&FirmwarePresets) -> R) -> R {
+        self.presets.lock(|presets| f(&presets.borrow()))
+    }
+
+    /// Ersetzt alle Presets (z.B. beim Laden aus dem Flash)
+    ///
+    /// Löst kein Änderungs-Signal aus.
+    pub fn replace(&self, presets: FirmwarePresets) {
+        self.presets.lock(|current| *current.borrow_mut() = presets);
+    }
+
+    /// Fügt ein Preset hinzu und gibt die vergebene ID zurück
+    pub fn add(&self, preset: Preset) -> Result<u8, PresetError> {
+        let result = self
+            .presets
+            .lock(|presets| presets.borrow_mut().add(preset));
+        if result.is_ok() {
+            self.changed.signal(());
+        }
+        result
+    }
+
+    /// Ersetzt ein Preset per ID
+    pub fn update(&self, id: u8, preset: Preset) -> Result<(), PresetError> {
+        let result = self
+            .presets
+            .lock(|presets| presets.borrow_mut().update(id, preset));
+        if result.is_ok() {
+            self.changed.signal(());
+        }
+        result
+    }
+
+    /// Entfernt ein Preset per ID
+    pub fn remove(&self, id: u8) -> Result<Preset, PresetError> {
+        let result = self.presets.lock(|presets| presets.borrow_mut().remove(id));
+        if result.is_ok() {
+            self.changed.signal(());
+        }
+        result
+    }
+
+    /// Wartet auf die nächste Änderung
+    pub async fn wait_changed(&self) {
+        self.changed.wait().await
+    }
+
+    /// Setzt ein ausstehendes Änderungs-Signal zurück und meldet, ob es gesetzt war
+    pub fn take_changed(&self) -> bool {
+        self.changed.try_take().is_some()
+    }
+
+    /// ID des Presets nach `current` (zyklisch)
+    pub fn next_id(&self, current: Option<u8>) -> Option<u8> {
+        self.with(|presets| presets.next_id(current))
+    }
+}
+
+impl PresetSource for PresetStore {
+    fn preset(&self, id: u8) -> Option<Preset> {
+        self.with(|presets| presets.get(id).copied())
+    }
+}
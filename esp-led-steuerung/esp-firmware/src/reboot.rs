@@ -0,0 +1,126 @@
+// Neustart und Werksreset auf Anforderung (HTTP, MQTT, Konsole, BOOT-Taster)
+//
+// Auslöser rufen nur `request_reboot()`, `request_factory_reset()` bzw.
+// `request_safe_mode()` auf. Der Scheduler-Task besitzt den Flash und führt
+// den Neustart aus: Ereignis melden (MQTT trennt sauber), ausstehende
+// Einstellungen sichern bzw. beim Werksreset alle Records löschen,
+// REBOOT_GRACE_MS warten, dann Reset.
+//
+// Abgesicherter Modus: ein Merker im RTC-Fast-RAM (überdauert den
+// Software-Reset, nicht aber Stromausfälle) lässt den nächsten Boot ohne
+// gespeicherte Gerätekonfiguration und ohne optionale Protokoll-Tasks starten
+// (siehe bin/main.rs). Er gilt für genau einen Boot.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use esp_core::RebootSource;
+use esp_hal::rtc_cntl::SocResetReason;
+
+/// Angeforderter Neustart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RebootRequest {
+    pub source: RebootSource,
+    /// Alle gespeicherten Einstellungen vor dem Neustart löschen
+    pub factory_reset: bool,
+    /// Nächsten Boot im abgesicherten Modus starten
+    pub safe_mode: bool,
+}
+
+/// Kennung im RTC-Fast-RAM für einen angeforderten abgesicherten Modus
+///
+/// Nach einem Stromausfall steht dort ein zufälliger Wert, daher kein `bool`.
+const SAFE_MODE_MAGIC: u32 = 0x5AFE_B007;
+
+/// Merker für den nächsten Boot (überdauert Software-Resets)
+#[esp_hal::ram(unstable(rtc_fast, persistent))]
+static mut SAFE_MODE_REQUEST: u32 = 0;
+
+/// Läuft der aktuelle Boot im abgesicherten Modus
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Angeforderter Neustart mit Auslöser
+static REBOOT_REQUEST: Signal<CriticalSectionRawMutex, RebootRequest> = Signal::new();
+
+/// Fordert einen sauberen Neustart an (kehrt sofort zurück)
+pub fn request_reboot(source: RebootSource) {
+    REBOOT_REQUEST.signal(RebootRequest {
+        source,
+        factory_reset: false,
+        safe_mode: false,
+    });
+}
+
+/// Fordert einen Werksreset an: Einstellungen löschen, dann Neustart
+///
+/// Die WLAN-Zugangsdaten sind einkompiliert und bleiben, ein Provisioning-Modus existiert nicht.
+pub fn request_factory_reset(source: RebootSource) {
+    REBOOT_REQUEST.signal(RebootRequest {
+        source,
+        factory_reset: true,
+        safe_mode: false,
+    });
+}
+
+/// Fordert einen Neustart in den abgesicherten Modus an
+pub fn request_safe_mode(source: RebootSource) {
+    REBOOT_REQUEST.signal(RebootRequest {
+        source,
+        factory_reset: false,
+        safe_mode: true,
+    });
+}
+
+/// Wartet auf eine Neustart-Anforderung (nur Scheduler-Task)
+pub async fn wait_reboot_request() -> RebootRequest {
+    REBOOT_REQUEST.wait().await
+}
+
+/// Setzt den Merker für den abgesicherten Modus (Scheduler-Task, direkt vor dem Reset)
+pub fn arm_safe_mode() {
+    // SAFETY: einziger Schreiber außerhalb von `take_safe_mode`, das nur beim Boot läuft
+    unsafe { core::ptr::addr_of_mut!(SAFE_MODE_REQUEST).write_volatile(SAFE_MODE_MAGIC) };
+}
+
+/// Liest und löscht den Merker (einmal beim Boot, vor dem Start der Tasks)
+pub fn take_safe_mode() -> bool {
+    // SAFETY: läuft vor dem Start aller Tasks, kein gleichzeitiger Zugriff
+    let requested = unsafe {
+        let flag = core::ptr::addr_of_mut!(SAFE_MODE_REQUEST);
+        let requested = flag.read_volatile() == SAFE_MODE_MAGIC;
+        flag.write_volatile(0);
+        requested
+    };
+    SAFE_MODE.store(requested, Ordering::Relaxed);
+    requested
+}
+
+/// Läuft der aktuelle Boot im abgesicherten Modus
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+/// Grund des letzten Resets (für GET /api/system)
+///
+/// Ein Absturz erscheint als `software`, Details liefert GET /api/crash.
+pub fn reset_reason() -> &'static str {
+    match esp_hal::rtc_cntl::reset_reason(esp_hal::system::Cpu::ProCpu) {
+        Some(SocResetReason::ChipPowerOn) => "power_on",
+        Some(SocResetReason::CoreSw | SocResetReason::Cpu0Sw) => "software",
+        Some(SocResetReason::CoreDeepSleep) => "deep_sleep",
+        Some(SocResetReason::SysBrownOut) => "brownout",
+        Some(
+            SocResetReason::CoreMwdt0
+            | SocResetReason::CoreMwdt1
+            | SocResetReason::CoreRtcWdt
+            | SocResetReason::Cpu0Mwdt0
+            | SocResetReason::Cpu0Mwdt1
+            | SocResetReason::Cpu0RtcWdt
+            | SocResetReason::SysRtcWdt
+            | SocResetReason::SysSuperWdt,
+        ) => "watchdog",
+        Some(_) => "other",
+        None => "unknown",
+    }
+}
@@ -0,0 +1,28 @@
+// Frame-Render-Metriken (GET /metrics)
+//
+// Der LED-Task misst pro Durchlauf Berechnung und RMT-Schreiben
+// (`LedCycle::timing`) und meldet sie über `record_frame()`. Die Auswertung
+// (Fenster, Perzentile) liegt in esp_core::render_stats. Realtime-Frames
+// werden nicht gemessen.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use esp_core::{FrameTiming, RenderStats, RenderSummary};
+
+use crate::config::{FRAME_BUDGET_US, RENDER_STATS_WINDOW};
+
+/// Messfenster (CriticalSection, da `static`)
+static STATS: Mutex<CriticalSectionRawMutex, RefCell<RenderStats<RENDER_STATS_WINDOW>>> =
+    Mutex::new(RefCell::new(RenderStats::new(FRAME_BUDGET_US)));
+
+/// Übernimmt die Messung eines Frames
+pub fn record_frame(timing: FrameTiming) {
+    STATS.lock(|stats| stats.borrow_mut().record(timing));
+}
+
+/// p50/p95 über das aktuelle Fenster
+pub fn render_summary() -> RenderSummary {
+    STATS.lock(|stats| stats.borrow().summary())
+}
@@ -0,0 +1,95 @@
+// Zeitplan-Store: geteilter Zeitplan für Scheduler, HTTP und WebSocket
+//
+// Der Zeitplan selbst (esp_core::Schedule) ist reine Logik. Dieser Store
+// kapselt ihn hinter einem Mutex und signalisiert Änderungen an den
+// Scheduler-Task, der sie dann in den Flash schreibt.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::signal::Signal;
+use esp_core::{Schedule, ScheduleEntry, ScheduleError};
+
+use crate::TaskRawMutex;
+use crate::config::SCHEDULE_CAPACITY;
+
+/// Zeitplan mit Firmware-Kapazität
+pub type FirmwareSchedule = Schedule<SCHEDULE_CAPACITY>;
+
+/// Geteilter Zeitplan mit Änderungs-Signal
+pub struct ScheduleStore {
+    schedule: Mutex<TaskRawMutex, RefCell<FirmwareSchedule>>,
+    changed: Signal<TaskRawMutex, ()>,
+}
+
+impl Default for ScheduleStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScheduleStore {
+    /// Leerer Store
+    pub const fn new() -> Self {
+        Self {
+            schedule: Mutex::new(RefCell::new(FirmwareSchedule::new())),
+            changed: Signal::new(),
+        }
+    }
+
+    /// Lesender Zugriff auf den Zeitplan
+    pub fn with<R>(&self, f: impl FnOnce(&FirmwareSchedule) -> R) -> R {
+        self.schedule.lock(|schedule| f(&schedule.borrow()))
+    }
+
+    /// Ersetzt den kompletten Zeitplan (z.B. beim Laden aus dem Flash)
+    ///
+    /// Löst kein Änderungs-Signal aus.
+    pub fn replace(&self, schedule: FirmwareSchedule) {
+        self.schedule
+            .lock(|current| *current.borrow_mut() = schedule);
+    }
+
+    /// Fügt einen Eintrag hinzu und gibt die vergebene ID zurück
+    pub fn add(&self, entry: ScheduleEntry) -> Result<u8, ScheduleError> {
+        let result = self
+            .schedule
+            .lock(|schedule| schedule.borrow_mut().add(entry));
+        if result.is_ok() {
+            self.changed.signal(());
+        }
+        result
+    }
+
+    /// Ersetzt einen Eintrag per ID
+    pub fn update(&self, id: u8, entry: ScheduleEntry) -> Result<(), ScheduleError> {
+        let result = self
+            .schedule
+            .lock(|schedule| schedule.borrow_mut().update(id, entry));
+        if result.is_ok() {
+            self.changed.signal(());
+        }
+        result
+    }
+
+    /// Entfernt einen Eintrag per ID
+    pub fn remove(&self, id: u8) -> Result<ScheduleEntry, ScheduleError> {
+        let result = self
+            .schedule
+            .lock(|schedule| schedule.borrow_mut().remove(id));
+        if result.is_ok() {
+            self.changed.signal(());
+        }
+        result
+    }
+
+    /// Wartet auf die nächste Änderung
+    pub async fn wait_changed(&self) {
+        self.changed.wait().await
+    }
+
+    /// Setzt ein ausstehendes Änderungs-Signal zurück und meldet, ob es gesetzt war
+    pub fn take_changed(&self) -> bool {
+        self.changed.try_take().is_some()
+    }
+}
@@ -0,0 +1,153 @@
+// Flash-Persistenz: Records in einer eigenen Flash-Partition
+//
+// Jeder Record-Typ (esp_core::RecordKind) belegt einen eigenen 4-KB-Sektor
+// in der "storage" Partition (siehe partitions.csv). Ein Speichervorgang
+// löscht den Sektor und schreibt Header + Payload neu.
+//
+// Das Record-Format (Magic, Länge, CRC32) ist in esp_core::record definiert.
+//
+// Die "webui" Partition (hochgeladene Oberfläche, siehe web_ui.rs) wird
+// ebenfalls über diesen Storage gelesen und geschrieben, damit alle
+// Flash-Zugriffe über denselben Mutex laufen.
+
+use defmt::{info, warn};
+use embedded_storage::nor_flash::{NorFlash, ReadNorFlash};
+use esp_core::{ErrorKind, ErrorSource, FirmwareError, RecordKind, decode_record, encode_record};
+use esp_storage::FlashStorage;
+
+use crate::config::{
+    STORAGE_FLASH_OFFSET, STORAGE_RECORD_BUFFER_SIZE, STORAGE_SECTOR_COUNT, STORAGE_SECTOR_SIZE,
+    WEB_UI_FLASH_OFFSET, WEB_UI_FLASH_SIZE,
+};
+
+/// Flash-Schreibgranularität in Bytes (Writes müssen 4-Byte-aligned sein)
+pub const WRITE_ALIGN: usize = 4;
+
+/// Record-Storage im Flash
+pub struct Storage {
+    flash: FlashStorage<'static>,
+}
+
+impl Storage {
+    /// Erstellt einen Storage auf dem Flash-Peripheral
+    pub fn new(flash: FlashStorage<'static>) -> Self {
+        Self { flash }
+    }
+
+    /// Flash-Offset des Sektors für einen Record-Typ
+    fn slot_offset(kind: RecordKind) -> u32 {
+        let slot = kind as u32 - 1;
+        debug_assert!(slot < STORAGE_SECTOR_COUNT);
+        STORAGE_FLASH_OFFSET + slot * STORAGE_SECTOR_SIZE
+    }
+
+    /// Lädt einen Record
+    ///
+    /// Gibt den Payload zurück, oder `None` wenn der Sektor leer, beschädigt
+    /// oder nicht lesbar ist.
+    pub fn load<'b>(&mut self, kind: RecordKind, buffer: &'b mut [u8]) -> Option<&'b [u8]> {
+        if self.flash.read(Self::slot_offset(kind), buffer).is_err() {
+            warn!("Storage: Flash read failed");
+            return None;
+        }
+        decode_record(buffer, kind)
+    }
+
+    /// Speichert einen Record (löscht den Sektor vorher)
+    pub fn save(&mut self, kind: RecordKind, payload: &[u8]) -> Result<(), StorageError> {
+        let mut buffer = [0xFFu8; STORAGE_RECORD_BUFFER_SIZE];
+        let len = encode_record(kind, payload, &mut buffer).ok_or(StorageError::TooLarge)?;
+        // Auf Schreibgranularität aufrunden (Rest bleibt 0xFF = gelöscht)
+        let aligned_len = len.next_multiple_of(WRITE_ALIGN);
+
+        let offset = Self::slot_offset(kind);
+        self.flash
+            .erase(offset, offset + STORAGE_SECTOR_SIZE)
+            .map_err(|_| StorageError::EraseFailed)?;
+        self.flash
+            .write(offset, &buffer[..aligned_len])
+            .map_err(|_| StorageError::WriteFailed)?;
+
+        info!("Storage: Saved record ({} bytes)", len);
+        Ok(())
+    }
+
+    /// Löscht einen Record (Sektor wird auf 0xFF zurückgesetzt)
+    pub fn erase(&mut self, kind: RecordKind) -> Result<(), StorageError> {
+        let offset = Self::slot_offset(kind);
+        self.flash
+            .erase(offset, offset + STORAGE_SECTOR_SIZE)
+            .map_err(|_| StorageError::EraseFailed)
+    }
+
+    /// Liest aus der Web-UI-Partition (`offset` relativ zum Partitionsanfang)
+    pub fn read_web_ui(&mut self, offset: u32, buffer: &mut [u8]) -> Result<(), StorageError> {
+        if offset as usize + buffer.len() > WEB_UI_FLASH_SIZE as usize {
+            return Err(StorageError::TooLarge);
+        }
+        self.flash
+            .read(WEB_UI_FLASH_OFFSET + offset, buffer)
+            .map_err(|_| StorageError::ReadFailed)
+    }
+
+    /// Löscht einen Sektor der Web-UI-Partition
+    pub fn erase_web_ui(&mut self, sector: u32) -> Result<(), StorageError> {
+        let offset = sector * STORAGE_SECTOR_SIZE;
+        if offset >= WEB_UI_FLASH_SIZE {
+            return Err(StorageError::TooLarge);
+        }
+        let offset = WEB_UI_FLASH_OFFSET + offset;
+        self.flash
+            .erase(offset, offset + STORAGE_SECTOR_SIZE)
+            .map_err(|_| StorageError::EraseFailed)
+    }
+
+    /// Schreibt in die (vorher gelöschte) Web-UI-Partition
+    ///
+    /// `offset` und Länge müssen Vielfache von [`WRITE_ALIGN`] sein.
+    pub fn write_web_ui(&mut self, offset: u32, data: &[u8]) -> Result<(), StorageError> {
+        if offset as usize + data.len() > WEB_UI_FLASH_SIZE as usize {
+            return Err(StorageError::TooLarge);
+        }
+        debug_assert!(offset as usize % WRITE_ALIGN == 0 && data.len() % WRITE_ALIGN == 0);
+        self.flash
+            .write(WEB_UI_FLASH_OFFSET + offset, data)
+            .map_err(|_| StorageError::WriteFailed)
+    }
+}
+
+/// Storage Fehler-Typen
+#[derive(Debug, Clone, Copy)]
+pub enum StorageError {
+    /// Payload passt nicht in STORAGE_RECORD_BUFFER_SIZE bzw. nicht in die Partition
+    TooLarge,
+    /// Sektor konnte nicht gelöscht werden
+    EraseFailed,
+    /// Schreiben ist fehlgeschlagen
+    WriteFailed,
+    /// Lesen ist fehlgeschlagen
+    ReadFailed,
+}
+
+impl From<StorageError> for FirmwareError {
+    fn from(error: StorageError) -> Self {
+        let kind = match error {
+            StorageError::TooLarge => ErrorKind::CapacityExceeded,
+            StorageError::EraseFailed | StorageError::WriteFailed | StorageError::ReadFailed => {
+                ErrorKind::HardwareFailure
+            }
+        };
+        FirmwareError::new(ErrorSource::Storage, kind)
+    }
+}
+
+impl defmt::Format for StorageError {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            StorageError::TooLarge => defmt::write!(fmt, "Record too large"),
+            StorageError::EraseFailed => defmt::write!(fmt, "Erase failed"),
+            StorageError::WriteFailed => defmt::write!(fmt, "Write failed"),
+            StorageError::ReadFailed => defmt::write!(fmt, "Read failed"),
+        }
+    }
+}
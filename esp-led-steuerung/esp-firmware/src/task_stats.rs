@@ -0,0 +1,51 @@
+// Laufzeit-Statistik pro Task für Telemetrie
+//
+// Tasks melden pro Schleifendurchlauf `task_heartbeat()`, ohne Arbeit wartende
+// Tasks `task_parked()` (Stall-Erkennung in tasks/supervisor.rs). Fehler zählt
+// `report_error()` (lib.rs) automatisch beim zuständigen Task mit, Tasks ohne
+// Event-Bus melden sie direkt über `task_error()`. Ausgeliefert über /api/system.
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::Instant;
+use esp_core::{SystemStats, TaskId};
+
+/// Zähler seit dem Start (CriticalSection, da `static`)
+static STATS: Mutex<CriticalSectionRawMutex, RefCell<SystemStats>> =
+    Mutex::new(RefCell::new(SystemStats::new()));
+
+/// Zählt einen Schleifendurchlauf (Lebenszeichen des Tasks)
+pub fn task_heartbeat(task: TaskId) {
+    let now_ms = Instant::now().as_millis();
+    STATS.lock(|stats| stats.borrow_mut().record_iteration(task, now_ms));
+}
+
+/// Markiert den Task als wartend (ohne Eingaben nichts zu tun, keine Stall-Prüfung)
+pub fn task_parked(task: TaskId) {
+    let now_ms = Instant::now().as_millis();
+    STATS.lock(|stats| stats.borrow_mut().record_parked(task, now_ms));
+}
+
+/// Markiert den Task beim Verlassen des Scopes als wartend (auch bei `?`)
+///
+/// Für Verbindungs-Schleifen ohne eigenen Takt danach (WebSocket).
+pub struct ParkOnDrop(pub TaskId);
+
+impl Drop for ParkOnDrop {
+    fn drop(&mut self) {
+        task_parked(self.0);
+    }
+}
+
+/// Zählt einen Fehler des Tasks
+pub fn task_error(task: TaskId) {
+    let now_ms = Instant::now().as_millis();
+    STATS.lock(|stats| stats.borrow_mut().record_error(task, now_ms));
+}
+
+/// Aktueller Stand aller Zähler
+pub fn system_stats() -> SystemStats {
+    STATS.lock(|stats| *stats.borrow())
+}
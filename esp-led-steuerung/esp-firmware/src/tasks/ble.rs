@@ -0,0 +1,126 @@
+// BLE Advertising Task - Broadcastet LED-Zustand als Manufacturer Data
+//
+// Passive Scanner (Handy-Widget, anderer ESP32) können Farbe und Modus
+// mitlesen, ohne eine Verbindung aufzubauen. Das Advertisement ist
+// non-connectable und wird bei jeder Farb-Änderung neu gestartet.
+//
+// Payload-Format: siehe esp_core::ble
+
+use bt_hci::controller::ExternalController;
+use defmt::{Debug2Format, error, info};
+use embassy_futures::select::{Either, select};
+use embassy_time::Duration;
+use esp_radio::ble::controller::BleConnector;
+use trouble_host::prelude::*;
+
+use crate::config::{BLE_ADV_INTERVAL_MS, BLE_DEVICE_NAME};
+use crate::{LedColorMessage, LedColorSubscriber};
+use esp_core::{BLE_COMPANY_ID, encode_ble_payload};
+
+/// Anzahl HCI-Command-Slots für den ExternalController
+const HCI_SLOTS: usize = 20;
+
+/// Maximale Länge eines Legacy-Advertisements (Bytes)
+const ADV_DATA_LEN: usize = 31;
+
+/// BLE Advertising Task
+///
+/// Startet den trouble BLE-Host-Stack und advertised den aktuellen
+/// LED-Zustand. Bei jeder neuen Farbe vom PubSubChannel wird das
+/// laufende Advertisement beendet und mit neuen Daten neu gestartet.
+///
+/// # Parameter
+/// - `connector`: BLE HCI Connector von esp-radio
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+#[embassy_executor::task]
+pub async fn ble_advertise_task(
+    connector: BleConnector<'static>,
+    mut color_subscriber: LedColorSubscriber,
+) {
+    info!("BLE: Task started");
+
+    let controller: ExternalController<_, HCI_SLOTS> = ExternalController::new(connector);
+
+    // Statische Random-Adresse aus der Factory-MAC ableiten
+    // Die zwei höchstwertigen Bits müssen für "static random" gesetzt sein
+    let mut address = esp_hal::efuse::Efuse::mac_address();
+    address[5] |= 0xC0;
+
+    // Keine Connections/L2CAP-Kanäle nötig (nur Advertising)
+    let mut resources: HostResources<DefaultPacketPool, 0, 0> = HostResources::new();
+    let stack =
+        trouble_host::new(controller, &mut resources).set_random_address(Address::random(address));
+    let Host {
+        mut peripheral,
+        mut runner,
+        ..
+    } = stack.build();
+
+    // Host-Runner und Advertising-Loop parallel ausführen
+    let advertise_loop = async {
+        // Startzustand: noch keine Farbe bekannt → warten auf erste Nachricht
+        let mut msg = color_subscriber.next_message_pure().await;
+        loop {
+            match advertise_state(&mut peripheral, &msg, &mut color_subscriber).await {
+                Ok(next) => msg = next,
+                Err(e) => {
+                    error!("BLE: Advertising failed: {}", Debug2Format(&e));
+                    msg = color_subscriber.next_message_pure().await;
+                }
+            }
+        }
+    };
+
+    match select(runner.run(), advertise_loop).await {
+        Either::First(result) => error!("BLE: Host runner stopped: {}", Debug2Format(&result)),
+        Either::Second(()) => {}
+    }
+}
+
+/// Advertised einen LED-Zustand bis eine neue Farbe eintrifft
+///
+/// Das Advertisement bleibt aktiv solange der `Advertiser` lebt.
+/// Gibt die neue Nachricht zurück, sobald sich der Zustand ändert.
+async fn advertise_state<'a, C: Controller>(
+    peripheral: &mut Peripheral<'a, C, DefaultPacketPool>,
+    msg: &LedColorMessage,
+    color_subscriber: &mut LedColorSubscriber,
+) -> Result<LedColorMessage, BleHostError<C::Error>> {
+    let payload = encode_ble_payload(msg);
+
+    let mut adv_data = [0u8; ADV_DATA_LEN];
+    let len = AdStructure::encode_slice(
+        &[
+            AdStructure::Flags(LE_GENERAL_DISCOVERABLE | BR_EDR_NOT_SUPPORTED),
+            AdStructure::CompleteLocalName(BLE_DEVICE_NAME.as_bytes()),
+            AdStructure::ManufacturerSpecificData {
+                company_identifier: BLE_COMPANY_ID,
+                payload: &payload,
+            },
+        ],
+        &mut adv_data[..],
+    )?;
+
+    let params = AdvertisementParameters {
+        interval_min: Duration::from_millis(BLE_ADV_INTERVAL_MS),
+        interval_max: Duration::from_millis(BLE_ADV_INTERVAL_MS),
+        ..Default::default()
+    };
+
+    // Advertiser muss am Leben bleiben - Drop beendet das Advertising
+    let _advertiser = peripheral
+        .advertise(
+            &params,
+            Advertisement::NonconnectableNonscannableUndirected {
+                adv_data: &adv_data[..len],
+            },
+        )
+        .await?;
+    info!(
+        "BLE: Advertising '{}' ({})",
+        msg.color_id,
+        if msg.is_auto_mode { "Auto" } else { "Manuell" }
+    );
+
+    Ok(color_subscriber.next_message_pure().await)
+}
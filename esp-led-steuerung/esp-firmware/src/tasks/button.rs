@@ -0,0 +1,58 @@
+// Taster Task - schaltet per Taster (Board-Profil, meist BOOT) durch die Presets
+//
+// Der Taster zieht gegen Masse (aktiv low, interner Pull-up). Jeder
+// kurze, entprellte Druck sendet beim Loslassen `LedCommand::ApplyPreset`
+// mit dem nächsten Preset, nach dem letzten geht es wieder beim ersten los.
+// Nach FACTORY_RESET_HOLD_MS Gedrückthalten folgt der Werksreset.
+
+use defmt::{info, warn};
+use embassy_time::{Duration, Timer};
+use esp_core::{Debouncer, Press, PressDetector, RebootSource};
+use esp_hal::gpio::{Input, InputConfig, Pull};
+
+use crate::boards::ButtonPin;
+use crate::config::{BUTTON_DEBOUNCE_MS, BUTTON_POLL_MS, FACTORY_RESET_HOLD_MS};
+use crate::hal::EmbassyClock;
+use crate::preset::PresetStore;
+use crate::reboot::request_factory_reset;
+use crate::{LedCommand, LedCommandSender};
+
+/// Taster Task - läuft parallel zu anderen Tasks
+///
+/// # Parameter
+/// - `button_pin`: Pin des Tasters (aus dem Board-Profil)
+/// - `presets`: Geteilte Presets (für die Reihenfolge)
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn button_task(
+    button_pin: ButtonPin,
+    presets: &'static PresetStore,
+    command_sender: LedCommandSender,
+) {
+    let button = Input::new(button_pin, InputConfig::default().with_pull(Pull::Up));
+    let mut debouncer = Debouncer::new(button.is_low(), BUTTON_DEBOUNCE_MS);
+    let mut press = PressDetector::new(FACTORY_RESET_HOLD_MS);
+    let mut current = None;
+    info!("Button: Task started");
+
+    loop {
+        Timer::after(Duration::from_millis(BUTTON_POLL_MS)).await;
+        let change = debouncer.update(button.is_low(), &EmbassyClock);
+        match press.update(change, &EmbassyClock) {
+            Some(Press::Short) => {}
+            Some(Press::Long) => {
+                warn!("Button: Long press, factory reset");
+                request_factory_reset(RebootSource::Button);
+                continue;
+            }
+            None => continue,
+        }
+        let Some(id) = presets.next_id(current) else {
+            warn!("Button: No presets configured");
+            continue;
+        };
+        info!("Button: Applying preset {}", id);
+        current = Some(id);
+        command_sender.send(LedCommand::ApplyPreset { id }).await;
+    }
+}
@@ -0,0 +1,325 @@
+// CoAP Task - LED-Zustand und -Steuerung über CoAP (RFC 7252)
+//
+// Leichtgewichtige Alternative zu HTTP für IoT-Setups (z.B. Thread/Zigbee-Gateways,
+// Node-RED, `coap-client` von libcoap). Ressourcen:
+//
+// - `GET /.well-known/core`  → Ressourcen-Liste (CoRE Link Format)
+// - `GET /led`               → Zustand als JSON (mit Observe: Benachrichtigung bei Änderung)
+// - `PUT|POST /led`          → Text-Kommando (`rot`, `auto`, `sleep 30`, ...)
+//
+// Codec und Observer-Liste: siehe esp_core::coap.
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either, select};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpEndpoint, Stack};
+use embassy_time::{Duration, Timer};
+use esp_core::coap::{code, content_format};
+use esp_core::{
+    CoapRequest, CoapResponse, CoapType, ColorId, LedColorMessage, NetCounter, Observers, TaskId,
+    decode_coap_message, parse_text_command,
+};
+use serde::Serialize;
+
+use crate::config::{COAP_BUFFER_SIZE, COAP_MAX_OBSERVERS, COAP_PORT};
+use crate::net_stats::record_net;
+use crate::task_stats::task_heartbeat;
+use crate::web::protocol::{OperationMode, RgbColor};
+use crate::{LedColorSubscriber, LedCommandSender};
+
+/// Ressourcen-Beschreibung für `/.well-known/core`
+const WELL_KNOWN_CORE: &[u8] = b"</led>;rt=\"led\";obs;ct=50";
+
+/// CoAP Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Lauscht auf UDP-Port COAP_PORT
+/// - Beantwortet Requests (CON → Piggybacked ACK, NON → NON)
+/// - Sendet bei jeder Farbänderung NON-Benachrichtigungen an alle Observer
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn coap_server_task(
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    info!("CoAP: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0u8; COAP_BUFFER_SIZE * 2];
+    let mut tx_buffer = [0u8; COAP_BUFFER_SIZE * 2];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    if socket.bind(COAP_PORT).is_err() {
+        warn!("CoAP: Failed to bind UDP port {}", COAP_PORT);
+        return;
+    }
+    info!("CoAP: Listening on UDP port {}", COAP_PORT);
+
+    let mut server = CoapServer {
+        state: None,
+        observers: Observers::new(),
+        next_message_id: 1,
+        command_sender,
+    };
+    let mut packet = [0u8; COAP_BUFFER_SIZE];
+    let mut response = [0u8; COAP_BUFFER_SIZE];
+
+    loop {
+        task_heartbeat(TaskId::Coap);
+        match select(
+            socket.recv_from(&mut packet),
+            color_subscriber.next_message_pure(),
+        )
+        .await
+        {
+            // Request vom Client
+            Either::First(Ok((len, meta))) => {
+                record_net(NetCounter::UdpPacketsIn);
+                let endpoint = meta.endpoint;
+                let request = match decode_coap_message(&packet[..len]) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        warn!("CoAP: Invalid message: {}", Debug2Format(&e));
+                        continue;
+                    }
+                };
+                if let Some(n) = server.handle(endpoint, &request, &mut response).await {
+                    if socket.send_to(&response[..n], endpoint).await.is_err() {
+                        warn!("CoAP: Failed to send response");
+                    } else {
+                        record_net(NetCounter::UdpPacketsOut);
+                    }
+                }
+            }
+            // Zu große Pakete werden verworfen
+            Either::First(Err(_)) => {}
+            // Neue Farbe vom LED-Task → alle Observer benachrichtigen
+            Either::Second(msg) => {
+                server.state = Some(msg);
+                let sequence = server.observers.next_sequence();
+                for observer in server.observers.iter() {
+                    // Direkter Feldzugriff: `observers` ist hier bereits ausgeliehen
+                    let message_id = server.next_message_id;
+                    server.next_message_id = message_id.wrapping_add(1);
+                    let notification = CoapResponse {
+                        msg_type: CoapType::NonConfirmable,
+                        code: code::CONTENT,
+                        message_id,
+                        token: observer.token(),
+                        observe: Some(sequence),
+                        content_format: Some(content_format::JSON),
+                        payload: &[],
+                    };
+                    let Some(n) = encode_state(&notification, &msg, &mut response) else {
+                        continue;
+                    };
+                    if socket
+                        .send_to(&response[..n], observer.endpoint)
+                        .await
+                        .is_err()
+                    {
+                        warn!("CoAP: Failed to send notification");
+                    } else {
+                        record_net(NetCounter::UdpPacketsOut);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Zustand des CoAP-Servers
+struct CoapServer {
+    /// Letzter bekannter LED-Zustand (None bis zum ersten Broadcast)
+    state: Option<LedColorMessage>,
+    observers: Observers<IpEndpoint, COAP_MAX_OBSERVERS>,
+    next_message_id: u16,
+    command_sender: LedCommandSender,
+}
+
+impl CoapServer {
+    /// Neue Message-ID für NON-Nachrichten
+    fn next_message_id(&mut self) -> u16 {
+        let id = self.next_message_id;
+        self.next_message_id = id.wrapping_add(1);
+        id
+    }
+
+    /// Verarbeitet eine Nachricht, gibt die Länge der Antwort zurück (falls eine nötig ist)
+    async fn handle(
+        &mut self,
+        endpoint: IpEndpoint,
+        request: &CoapRequest<'_>,
+        buf: &mut [u8],
+    ) -> Option<usize> {
+        match (request.msg_type, request.code) {
+            // Client lehnt Benachrichtigung ab → abmelden
+            (CoapType::Reset, _) => {
+                if self.observers.remove(endpoint) {
+                    info!("CoAP: Observer removed (reset)");
+                }
+                return None;
+            }
+            (CoapType::Acknowledgement, _) => return None,
+            // CoAP-Ping (leere CON-Nachricht) → RST
+            (CoapType::Confirmable, code::EMPTY) => {
+                let pong = CoapResponse {
+                    msg_type: CoapType::Reset,
+                    code: code::EMPTY,
+                    message_id: request.message_id,
+                    token: &[],
+                    observe: None,
+                    content_format: None,
+                    payload: &[],
+                };
+                return pong.encode(buf).ok();
+            }
+            _ => {}
+        }
+
+        // Antwort-Grundgerüst: CON → Piggybacked ACK, NON → NON
+        let mut response = CoapResponse {
+            msg_type: CoapType::NonConfirmable,
+            code: code::CONTENT,
+            message_id: request.message_id,
+            token: request.token,
+            observe: None,
+            content_format: None,
+            payload: &[],
+        };
+        if request.msg_type == CoapType::Confirmable {
+            response.msg_type = CoapType::Acknowledgement;
+        } else {
+            response.message_id = self.next_message_id();
+        }
+
+        if request.path_is(&[".well-known", "core"]) {
+            if request.code != code::GET {
+                response.code = code::METHOD_NOT_ALLOWED;
+                return response.encode(buf).ok();
+            }
+            response.content_format = Some(content_format::LINK_FORMAT);
+            response.payload = WELL_KNOWN_CORE;
+            return response.encode(buf).ok();
+        }
+
+        if !request.path_is(&["led"]) {
+            response.code = code::NOT_FOUND;
+            return response.encode(buf).ok();
+        }
+
+        match request.code {
+            code::GET => {
+                match request.observe {
+                    Some(0) => {
+                        if self.observers.register(endpoint, request.token) {
+                            info!(
+                                "CoAP: Observer registered ({} active)",
+                                self.observers.len()
+                            );
+                            response.observe = Some(self.observers.next_sequence());
+                        } else {
+                            warn!("CoAP: Observer list full, answering without observe");
+                        }
+                    }
+                    Some(1) => {
+                        if self.observers.remove(endpoint) {
+                            info!("CoAP: Observer deregistered");
+                        }
+                    }
+                    _ => {}
+                }
+                let Some(state) = self.state else {
+                    response.code = code::SERVICE_UNAVAILABLE;
+                    return response.encode(buf).ok();
+                };
+                response.content_format = Some(content_format::JSON);
+                encode_state(&response, &state, buf)
+            }
+            code::PUT | code::POST => {
+                let text = core::str::from_utf8(request.payload).unwrap_or("");
+                match parse_text_command(text) {
+                    Ok(command) => {
+                        info!("CoAP: Received command '{}'", text);
+                        self.command_sender.send(command).await;
+                        response.code = code::CHANGED;
+                    }
+                    Err(e) => {
+                        warn!("CoAP: Invalid command '{}': {}", text, Debug2Format(&e));
+                        response.code = code::BAD_REQUEST;
+                        response.content_format = Some(content_format::TEXT_PLAIN);
+                        response.payload = b"Unknown command";
+                    }
+                }
+                response.encode(buf).ok()
+            }
+            _ => {
+                response.code = code::METHOD_NOT_ALLOWED;
+                response.encode(buf).ok()
+            }
+        }
+    }
+}
+
+/// LED-Zustand als JSON (Payload von `GET /led`)
+#[derive(Serialize)]
+struct CoapLedState {
+    color: ColorId,
+    rgb: RgbColor,
+    mode: OperationMode,
+}
+
+/// Kodiert eine Antwort mit dem LED-Zustand als JSON-Payload
+fn encode_state(
+    response: &CoapResponse<'_>,
+    msg: &LedColorMessage,
+    buf: &mut [u8],
+) -> Option<usize> {
+    let state = CoapLedState {
+        color: msg.color_id,
+        rgb: RgbColor {
+            r: msg.color.r,
+            g: msg.color.g,
+            b: msg.color.b,
+        },
+        mode: if msg.is_auto_mode {
+            OperationMode::Auto
+        } else {
+            OperationMode::Manual
+        },
+    };
+    let mut json = [0u8; COAP_BUFFER_SIZE / 2];
+    let n = serde_json_core::to_slice(&state, &mut json).ok()?;
+    CoapResponse {
+        payload: &json[..n],
+        ..*response
+    }
+    .encode(buf)
+    .ok()
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
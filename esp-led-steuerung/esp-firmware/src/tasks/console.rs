@@ -0,0 +1,497 @@
+// Console Task - Telnet-artige Debug-Konsole über TCP
+//
+// Zeilenbasierte Befehle für Debugging und Skripte ohne Debug-Probe:
+//   telnet led.local 23
+//   echo "set color rot" | nc -q1 led.local 23
+//
+// Befehlssatz: siehe esp_core::console. Es wird immer nur eine Verbindung
+// gleichzeitig bedient, weitere Clients warten im Backlog.
+//
+// `ConsoleSession` ist unabhängig vom Transport (embedded-io-async) und wird
+// auch von der UART- und USB-Konsole genutzt (siehe uart_console.rs,
+// usb_console.rs). Optional gibt sie neue Zeilen des Log-Ringpuffers
+// zwischen den Eingaben aus (`log_follow`).
+
+use core::fmt::Write as _;
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either, select};
+use embassy_net::Stack;
+use embassy_net::tcp::TcpSocket;
+use embassy_time::{Duration, Instant, Timer};
+use esp_core::{
+    ClientKind, CommandParseError, ConsoleCommand, DnsCacheState, ErrorCategory, LogLevel,
+    NetCounter, RebootSource, SpanKind, SystemEvent, TaskId, TraceAction, parse_console_command,
+};
+use heapless::{String, Vec};
+use picoserve::io::embedded_io_async::{Read, Write};
+
+use crate::config::{
+    CONSOLE_BUFFER_SIZE, CONSOLE_IDLE_TIMEOUT_SECS, CONSOLE_LINE_LENGTH, CONSOLE_LOG_POLL_MS,
+    CONSOLE_PORT, EXTRA_HEAP_SIZE, LOG_LINE_LEN, TRACE_SHOW_SPANS, WIFI_HEAP_SIZE,
+};
+use crate::dns_cache::broker_cache;
+use crate::error_stats::error_counters;
+use crate::heap_stats::sample_heap;
+use crate::log_level::{log_enabled, log_level, set_log_level};
+use crate::log_ring::{log_next_seq, log_snapshot};
+use crate::net_stats::net_stats;
+use crate::reboot::request_reboot;
+use crate::task_stats::task_heartbeat;
+use crate::trace::{set_tracing, span, trace_snapshot, tracing_enabled};
+use crate::version::{FIRMWARE_VERSION, GIT_HASH};
+use crate::{
+    LedColorMessage, LedColorSubscriber, LedCommandSender, SystemEventChannel, publish_event,
+};
+
+/// Befehlsübersicht für `help`
+const HELP_TEXT: &str = "\
+Befehle:\r
+  status                  LED-Zustand, Uptime, IP, Version\r
+  heap                    Heap-Auslastung\r
+  dns                     DNS-Cache der Broker-Adresse\r
+  net                     Netzwerk-Zähler (MQTT, WebSocket, HTTP, UDP)\r
+  trace [on|off]          Ablauf-Tracing anzeigen / ein / aus\r
+  diagnose                Blinkcode des letzten Verbindungsproblems\r
+  set color <rot|grün|blau|aus> [<ms>]\r
+  set mode <auto|zufall>\r
+  set sleep <min>         Sleep-Timer (0 = abbrechen)\r
+  set sunrise [<min>]     Sonnenaufgang\r
+  set preset <id>         Preset anwenden\r
+  set kalibrieren         Kalibriermodus (Testfarben)\r
+  set testmuster          Testmuster (Lauflicht, dann R/G/B)\r
+  set text <nachricht>    Lauftext auf der LED-Matrix\r
+  set uhr [12|24]         Uhrzeit anzeigen\r
+  set sequence [<farben>] Farbfolge der Auto-Rotation\r
+  set verlauf <farben>    Farbverlauf über alle Pixel\r
+  set meteor [<px/s> [<schweif> [<farbe>]]]\r
+  set plasma [<tempo>]    Plasma in den Farben der Farbfolge\r
+  set param <name> <wert> Parameter des laufenden Effekts (speed, ...)\r
+  loglevel [<level>]      error, warn, info, debug, trace\r
+  reboot                  Neustart\r
+  quit                    Verbindung trennen\r
+";
+
+/// Eingabe-Prompt
+const PROMPT: &[u8] = b"> ";
+
+/// Telnet "Interpret As Command" (leitet Options-Verhandlung ein)
+const TELNET_IAC: u8 = 0xFF;
+
+/// Console Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Lauscht auf TCP-Port CONSOLE_PORT
+/// - Liest Zeilen, führt Befehle aus und antwortet im Klartext
+/// - Merkt sich den letzten LED-Zustand für `status`
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+/// - `events`: System-Ereignisse (ClientConnected)
+#[embassy_executor::task]
+pub async fn console_task(
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+    events: &'static SystemEventChannel,
+) {
+    info!("Console: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_buffer = [0u8; CONSOLE_BUFFER_SIZE];
+    let mut tx_buffer = [0u8; CONSOLE_BUFFER_SIZE];
+    let mut state: Option<LedColorMessage> = None;
+
+    loop {
+        task_heartbeat(TaskId::Console);
+        let mut socket = TcpSocket::new(*stack, &mut rx_buffer, &mut tx_buffer);
+        socket.set_timeout(Some(Duration::from_secs(CONSOLE_IDLE_TIMEOUT_SECS)));
+
+        info!("Console: Listening on TCP port {}", CONSOLE_PORT);
+        if socket.accept(CONSOLE_PORT).await.is_err() {
+            warn!("Console: Accept failed");
+            continue;
+        }
+        info!("Console: Client connected");
+        publish_event(
+            events,
+            SystemEvent::ClientConnected {
+                kind: ClientKind::Console,
+            },
+        );
+
+        let mut session = ConsoleSession {
+            io: socket,
+            stack,
+            color_subscriber: &mut color_subscriber,
+            command_sender,
+            state: &mut state,
+            log_follow: None,
+        };
+        match session.run().await {
+            Ok(()) => info!("Console: Client disconnected"),
+            Err(e) => warn!("Console: Connection error: {}", Debug2Format(&e)),
+        }
+        session.io.close();
+        // Ausstehende Daten senden, dann Socket freigeben
+        session.io.flush().await.ok();
+        session.io.abort();
+    }
+}
+
+/// Eine Konsolen-Sitzung über einen beliebigen Transport (TCP, UART, USB)
+pub(crate) struct ConsoleSession<'a, IO> {
+    pub(crate) io: IO,
+    /// Für die IP-Adresse in `status`
+    pub(crate) stack: &'static Stack<'static>,
+    pub(crate) color_subscriber: &'a mut LedColorSubscriber,
+    pub(crate) command_sender: LedCommandSender,
+    /// Letzter bekannter LED-Zustand (bleibt über Verbindungen erhalten)
+    pub(crate) state: &'a mut Option<LedColorMessage>,
+    /// Nummer der nächsten auszugebenden Log-Zeile, `None` = keine Log-Ausgabe
+    pub(crate) log_follow: Option<u32>,
+}
+
+impl<IO: Read + Write> ConsoleSession<'_, IO> {
+    /// Liest Zeilen bis der Client die Verbindung beendet
+    pub(crate) async fn run(&mut self) -> Result<(), IO::Error> {
+        let mut banner: String<96> = String::new();
+        write!(
+            banner,
+            "ESP32-C6 LED-Steuerung v{} ({}) - 'help' für Befehle\r\n",
+            FIRMWARE_VERSION, GIT_HASH
+        )
+        .ok();
+        self.io.write_all(banner.as_bytes()).await?;
+        self.io.write_all(PROMPT).await?;
+
+        let mut line: Vec<u8, CONSOLE_LINE_LENGTH> = Vec::new();
+        let mut overflow = false;
+        let mut telnet_skip = 0u8;
+        let mut buf = [0u8; 64];
+
+        loop {
+            task_heartbeat(TaskId::Console);
+            let n = if self.log_follow.is_some() {
+                let poll = Timer::after(Duration::from_millis(CONSOLE_LOG_POLL_MS));
+                match select(self.io.read(&mut buf), poll).await {
+                    Either::First(n) => n?,
+                    Either::Second(()) => {
+                        self.write_log_lines(&line).await?;
+                        continue;
+                    }
+                }
+            } else {
+                self.io.read(&mut buf).await?
+            };
+            if n == 0 {
+                return Ok(());
+            }
+            for &byte in &buf[..n] {
+                // Telnet-Optionsverhandlung (IAC + 2 Bytes) ignorieren
+                if telnet_skip > 0 {
+                    telnet_skip -= 1;
+                    continue;
+                }
+                match byte {
+                    TELNET_IAC => telnet_skip = 2,
+                    b'\r' => {}
+                    b'\n' => {
+                        if overflow {
+                            self.io.write_all(b"Fehler: Zeile zu lang\r\n").await?;
+                        } else if !self.handle_line(&line).await? {
+                            return Ok(());
+                        }
+                        line.clear();
+                        overflow = false;
+                        self.io.write_all(PROMPT).await?;
+                    }
+                    _ => overflow |= line.push(byte).is_err(),
+                }
+            }
+        }
+    }
+
+    /// Gibt neue Zeilen des Log-Ringpuffers aus
+    ///
+    /// Die angefangene Eingabe wird danach mit Prompt erneut ausgegeben.
+    async fn write_log_lines(&mut self, pending: &[u8]) -> Result<(), IO::Error> {
+        let Some(next) = self.log_follow else {
+            return Ok(());
+        };
+        if log_next_seq() == next {
+            return Ok(());
+        }
+
+        // Eingabezeile löschen (ANSI), Log-Zeilen stehen darüber
+        self.io.write_all(b"\r\x1b[2K").await?;
+        let ring = log_snapshot();
+        for entry in ring.since(next) {
+            let mut out: String<{ LOG_LINE_LEN + 32 }> = String::new();
+            write!(out, "{}\r\n", entry).ok();
+            self.io.write_all(out.as_bytes()).await?;
+            self.log_follow = Some(entry.seq.wrapping_add(1));
+        }
+        self.io.write_all(PROMPT).await?;
+        self.io.write_all(pending).await
+    }
+
+    /// Gibt Zusammenfassung und jüngste Abschnitte des Ablauf-Tracings aus
+    async fn write_trace(&mut self) -> Result<(), IO::Error> {
+        let ring = trace_snapshot();
+        let mut out: String<96> = String::new();
+        let state = if tracing_enabled() {
+            "an"
+        } else {
+            "aus ('trace on' startet)"
+        };
+        write!(
+            out,
+            "Tracing {}, {} Abschnitte gespeichert\r\n",
+            state,
+            ring.len()
+        )
+        .ok();
+        self.io.write_all(out.as_bytes()).await?;
+
+        for kind in SpanKind::ALL {
+            let stats = ring.summary()[kind as usize];
+            if stats.count == 0 {
+                continue;
+            }
+            out.clear();
+            write!(
+                out,
+                "  {:<13} n={:<6} avg={:>7}us max={:>7}us\r\n",
+                kind.name(),
+                stats.count,
+                stats.avg_us(),
+                stats.max_us
+            )
+            .ok();
+            self.io.write_all(out.as_bytes()).await?;
+        }
+
+        // Jüngste Abschnitte mit Eintrittszeit (s.µs seit Boot), älteste zuerst
+        let skip = ring.len().saturating_sub(TRACE_SHOW_SPANS);
+        for span in ring.iter().skip(skip) {
+            out.clear();
+            write!(
+                out,
+                "  [{:>6}.{:06}] {:<13} {:>7}us\r\n",
+                span.start_us / 1_000_000,
+                span.start_us % 1_000_000,
+                span.kind.name(),
+                span.duration_us()
+            )
+            .ok();
+            self.io.write_all(out.as_bytes()).await?;
+        }
+        Ok(())
+    }
+
+    /// Führt eine Zeile aus, `false` beendet die Verbindung
+    async fn handle_line(&mut self, line: &[u8]) -> Result<bool, IO::Error> {
+        let Ok(text) = core::str::from_utf8(line) else {
+            self.io
+                .write_all("Fehler: ungültiges UTF-8\r\n".as_bytes())
+                .await?;
+            return Ok(true);
+        };
+        if log_enabled(LogLevel::Debug) {
+            info!("Console: > {}", text);
+        }
+        if text.trim().eq_ignore_ascii_case("quit") || text.trim().eq_ignore_ascii_case("exit") {
+            self.io.write_all(b"Bye\r\n").await?;
+            return Ok(false);
+        }
+
+        let command = match parse_console_command(text) {
+            Ok(command) => command,
+            Err(CommandParseError::Empty) => return Ok(true),
+            Err(CommandParseError::Unknown) => {
+                self.io
+                    .write_all(b"Fehler: unbekannter Befehl ('help' zeigt alle Befehle)\r\n")
+                    .await?;
+                return Ok(true);
+            }
+            Err(CommandParseError::InvalidArgument) => {
+                self.io
+                    .write_all("Fehler: ungültiges Argument\r\n".as_bytes())
+                    .await?;
+                return Ok(true);
+            }
+        };
+
+        let _span = span(SpanKind::Console);
+        let mut out: String<256> = String::new();
+        match command {
+            ConsoleCommand::Help => {
+                self.io.write_all(HELP_TEXT.as_bytes()).await?;
+            }
+            ConsoleCommand::Status => {
+                // Neuesten Zustand übernehmen (verpasste Updates werden übersprungen)
+                while let Some(msg) = self.color_subscriber.try_next_message_pure() {
+                    *self.state = Some(msg);
+                }
+                if let Some(msg) = *self.state {
+                    let mode = if msg.is_auto_mode { "Auto" } else { "Manuell" };
+                    write!(
+                        out,
+                        "LED:     {} (r={} g={} b={}, {})\r\n",
+                        msg.color_id.name(),
+                        msg.color.r,
+                        msg.color.g,
+                        msg.color.b,
+                        mode
+                    )
+                    .ok();
+                } else {
+                    out.push_str("LED:     unbekannt\r\n").ok();
+                }
+                write!(
+                    out,
+                    "Uptime:  {} s\r\nVersion: {} ({})\r\n",
+                    Instant::now().as_secs(),
+                    FIRMWARE_VERSION,
+                    GIT_HASH
+                )
+                .ok();
+                if let Some(config) = self.stack.config_v4() {
+                    write!(out, "IP:      {}\r\n", config.address.address()).ok();
+                }
+                let errors = error_counters();
+                write!(out, "Fehler:  {}", errors.total()).ok();
+                for category in ErrorCategory::ALL {
+                    write!(out, " {}={}", category.name(), errors.get(category)).ok();
+                }
+                let queue = self.command_sender.stats();
+                write!(
+                    out,
+                    "\r\nQueue:   {} verworfen (verdrängt={} abgelehnt={})\r\n",
+                    queue.dropped(),
+                    queue.evicted,
+                    queue.rejected
+                )
+                .ok();
+                self.io.write_all(out.as_bytes()).await?;
+            }
+            ConsoleCommand::Heap => {
+                let stats = sample_heap();
+                write!(
+                    out,
+                    "Heap: {} / {} Bytes belegt, {} frei, Spitze {} ({}%)\r\n",
+                    stats.used,
+                    WIFI_HEAP_SIZE + EXTRA_HEAP_SIZE,
+                    stats.free,
+                    stats.high_water,
+                    stats.high_water_percent()
+                )
+                .ok();
+                self.io.write_all(out.as_bytes()).await?;
+            }
+            ConsoleCommand::Dns => {
+                let cache = broker_cache();
+                let now_ms = Instant::now().as_millis();
+                match cache.state(now_ms) {
+                    DnsCacheState::Empty => out.push_str("DNS:  kein Eintrag\r\n").ok(),
+                    DnsCacheState::Valid {
+                        address: [a, b, c, d],
+                        remaining_ms,
+                    } => write!(
+                        out,
+                        "DNS:  {}.{}.{}.{} (gültig noch {} s)\r\n",
+                        a,
+                        b,
+                        c,
+                        d,
+                        remaining_ms / 1000
+                    )
+                    .ok(),
+                    DnsCacheState::Expired {
+                        address: [a, b, c, d],
+                    } => write!(out, "DNS:  {}.{}.{}.{} (abgelaufen)\r\n", a, b, c, d).ok(),
+                };
+                write!(
+                    out,
+                    "      {} Auflösungen, {} Treffer, {} verworfen\r\n",
+                    cache.resolutions, cache.hits, cache.invalidations
+                )
+                .ok();
+                self.io.write_all(out.as_bytes()).await?;
+            }
+            ConsoleCommand::Net => {
+                let stats = net_stats();
+                write!(
+                    out,
+                    "MQTT: {} Publishes, {} empfangen, {} Acks, {} Reconnects\r\n\
+                     WS:   {} rein, {} raus\r\n\
+                     HTTP: {} Requests\r\n\
+                     UDP:  {} rein, {} raus\r\n",
+                    stats.count(NetCounter::MqttPublishes),
+                    stats.count(NetCounter::MqttReceived),
+                    stats.count(NetCounter::MqttAcks),
+                    stats.count(NetCounter::MqttReconnects),
+                    stats.count(NetCounter::WsMessagesIn),
+                    stats.count(NetCounter::WsMessagesOut),
+                    stats.count(NetCounter::HttpRequests),
+                    stats.count(NetCounter::UdpPacketsIn),
+                    stats.count(NetCounter::UdpPacketsOut)
+                )
+                .ok();
+                self.io.write_all(out.as_bytes()).await?;
+            }
+            ConsoleCommand::Trace(TraceAction::Show) => self.write_trace().await?,
+            ConsoleCommand::Trace(action) => {
+                let enabled = action == TraceAction::On;
+                set_tracing(enabled);
+                info!("Console: Tracing {}", if enabled { "on" } else { "off" });
+                let reply: &[u8] = if enabled {
+                    b"Tracing an\r\n"
+                } else {
+                    b"Tracing aus\r\n"
+                };
+                self.io.write_all(reply).await?;
+            }
+            ConsoleCommand::Reboot => {
+                info!("Console: Reboot requested");
+                self.io.write_all(b"Neustart...\r\n").await?;
+                self.io.flush().await?;
+                // Scheduler sichert die Einstellungen und startet nach REBOOT_GRACE_MS neu
+                request_reboot(RebootSource::Console);
+            }
+            ConsoleCommand::LogLevel(None) => {
+                write!(out, "Log-Level: {}\r\n", log_level().as_str()).ok();
+                self.io.write_all(out.as_bytes()).await?;
+            }
+            ConsoleCommand::LogLevel(Some(level)) => {
+                set_log_level(level);
+                info!("Console: Log level set to {}", level.as_str());
+                write!(out, "Log-Level: {}\r\n", level.as_str()).ok();
+                self.io.write_all(out.as_bytes()).await?;
+            }
+            ConsoleCommand::Led(command) => {
+                self.command_sender.send(command).await;
+                info!("Console: Sent LED command '{}'", text.trim());
+                self.io.write_all(b"OK\r\n").await?;
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
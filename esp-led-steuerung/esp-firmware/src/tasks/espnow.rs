@@ -0,0 +1,79 @@
+// ESP-NOW Task - Verteilt den Gruppen-Zustand ohne Access Point
+//
+// Jede Änderung des eigenen Modus (aus, Auto, Farbe) geht als Broadcast an
+// alle Geräte in Funkreichweite, empfangene Zustände werden als Kommando an
+// den LED-Task gegeben und mit verringertem TTL weitergeleitet. Funktioniert
+// auch, wenn der Router ausfällt (ESP-NOW braucht keine Verbindung).
+//
+// Paket-Format, Duplikat-Erkennung und Weiterleitung: siehe esp_core::group_sync
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either, select};
+use embassy_time::Instant;
+use esp_core::group_sync::{GroupPacket, GroupState, GroupSync};
+use esp_radio::esp_now::{BROADCAST_ADDRESS, EspNow};
+
+use crate::{LedColorSubscriber, LedCommandSender};
+
+/// ESP-NOW Task
+///
+/// # Parameter
+/// - `esp_now`: ESP-NOW-Schnittstelle von esp-radio
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts (eigene Änderungen)
+/// - `command_sender`: Channel Sender für LED-Kommandos (Zustand der Gruppe)
+#[embassy_executor::task]
+pub async fn espnow_task(
+    mut esp_now: EspNow<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    // Absender-ID aus den unteren 4 Bytes der Factory-MAC
+    let mac = esp_hal::efuse::Efuse::mac_address();
+    let mut group = GroupSync::new(u32::from_be_bytes([mac[2], mac[3], mac[4], mac[5]]));
+    info!("ESP-NOW: Task started (node {:08x})", group.node_id());
+
+    loop {
+        let packet = match select(
+            esp_now.receive_async(),
+            color_subscriber.next_message_pure(),
+        )
+        .await
+        {
+            // Paket eines anderen Geräts
+            Either::First(received) => {
+                let Some(packet) = GroupPacket::decode(received.data()) else {
+                    continue;
+                };
+                let now_ms = Instant::now().as_millis();
+                let Some(result) = group.receive(&packet, now_ms) else {
+                    // Eigenes, doppeltes oder veraltetes Paket
+                    continue;
+                };
+                if let Some(command) = result.command {
+                    info!(
+                        "ESP-NOW: Group state from {:08x}: {}",
+                        packet.origin,
+                        Debug2Format(&packet.state)
+                    );
+                    command_sender.send(command).await;
+                }
+                match result.forward {
+                    Some(forward) => forward,
+                    None => continue,
+                }
+            }
+            // Eigene Änderung (Auto-Rotation ändert den Gruppen-Zustand nicht)
+            Either::Second(msg) => match group.local_update(GroupState::from_message(&msg)) {
+                Some(packet) => packet,
+                None => continue,
+            },
+        };
+
+        if let Err(e) = esp_now
+            .send_async(&BROADCAST_ADDRESS, &packet.encode())
+            .await
+        {
+            warn!("ESP-NOW: Broadcast failed: {}", Debug2Format(&e));
+        }
+    }
+}
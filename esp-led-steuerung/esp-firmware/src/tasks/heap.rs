@@ -0,0 +1,41 @@
+// Heap-Monitor Task - Misst regelmäßig die Heap-Auslastung
+//
+// Aktualisiert die Telemetrie-Werte in crate::heap_stats (letzte Messung und
+// Höchststand). Ausgeliefert werden sie über /api/heap, WebSocket und MQTT.
+
+use defmt::{info, warn};
+use embassy_time::{Duration, Ticker};
+use esp_core::TaskId;
+
+use crate::config::{HEAP_SAMPLE_INTERVAL_MS, HEAP_WARN_PERCENT};
+use crate::heap_stats::sample_heap;
+use crate::task_stats::task_heartbeat;
+
+/// Heap-Monitor Task - läuft parallel zu anderen Tasks
+///
+/// - Misst alle HEAP_SAMPLE_INTERVAL_MS den Heap (esp-alloc)
+/// - Warnt einmalig beim Überschreiten von HEAP_WARN_PERCENT, erneut erst
+///   nachdem die Auslastung wieder darunter lag
+#[embassy_executor::task]
+pub async fn heap_monitor_task() {
+    info!("Heap: Monitor started");
+
+    let mut ticker = Ticker::every(Duration::from_millis(HEAP_SAMPLE_INTERVAL_MS));
+    let mut warned = false;
+    loop {
+        task_heartbeat(TaskId::Heap);
+        let stats = sample_heap();
+        let usage = stats.usage_percent();
+        if usage >= HEAP_WARN_PERCENT && !warned {
+            warn!(
+                "Heap: {}% used ({} of {} bytes, peak {} bytes)",
+                usage,
+                stats.used,
+                stats.size(),
+                stats.high_water
+            );
+        }
+        warned = usage >= HEAP_WARN_PERCENT;
+        ticker.next().await;
+    }
+}
@@ -1,19 +1,62 @@
 // HTTP Server Task - Serviert HTML und WebSocket
-use core::future::pending;
-use defmt::info;
-use embassy_futures::select::{Either, select};
+//
+// Der WebSocket-Endpoint (/ws) ist per Feature `websocket` abschaltbar,
+// HTML-Seite und JSON-API bleiben immer verfügbar.
+use defmt::{info, warn};
 use embassy_net::Stack;
-use embassy_time::{Duration, Instant};
-use picoserve::{io::embedded_io_async, response::IntoResponse, response::ws, routing::get};
+use embassy_net::tcp::TcpSocket;
+use embassy_time::Duration;
+use esp_core::{DeviceConfig, ETag, NetCounter, UiBundleHeader, if_none_match};
+use esp_core::wot::{WotAction, WotProperty};
+use picoserve::{
+    io::embedded_io_async,
+    response::IntoResponse,
+    routing::{get, parse_path_segment, post, put},
+};
+
+use alloc::string::String;
 
+use crate::calibration::CalibrationStore;
 use crate::config::*;
+use crate::device_config::DeviceConfigStore;
+use crate::net_stats::{net_stats, record_net_count};
+use crate::preset::PresetStore;
+use crate::render_stats::render_summary;
+use crate::schedule::ScheduleStore;
+use crate::tcp_tuning::apply_socket_tuning;
+use crate::version::{BUILD_TIMESTAMP, GIT_HASH};
+use crate::web_ui::{FlashUi, installed};
 use crate::web::{
     INDEX_HTML,
-    protocol::{OperationMode, RgbColor, WsClientMessage, WsServerMessage},
+    api::{self, ApiAuth, ApiBody, ApiJson, UiBundleUpload},
+    log_stream,
+    protocol::{
+        HeapInfo, LogList, NetworkInfo, PresetDto, RgbColor, ScheduleEntryDto, SystemInfo,
+        VersionInfo,
+    },
 };
-use crate::{LedColorChannel, LedColorMessage, LedColorSubscriber, LedCommand, LedCommandSender};
-use serde_json_core;
+use crate::{LedColorChannel, LedCommandSender, SystemEventChannel};
 
+#[cfg(feature = "ws-deflate")]
+use crate::web::ws_deflate::{DeflateBuffers, DeflateSocket};
+#[cfg(feature = "websocket")]
+use {
+    crate::net_stats::record_net,
+    crate::task_stats::{ParkOnDrop, task_heartbeat},
+    crate::trace::span,
+    crate::web::protocol::{WsRequest, WsServerMessage},
+    crate::{LedColorMessage, LedColorSubscriber, LedCommand, publish_event},
+    core::future::pending,
+    embassy_futures::select::{Either3, select3},
+    embassy_time::{Ticker, with_timeout},
+    esp_core::{
+        ClientKind, ErrorKind, ErrorSource, FirmwareError, SpanKind, SystemEvent, TaskId,
+        ws::{is_valid_auth, parse_ws_message},
+    },
+    picoserve::response::ws,
+};
+
+#[cfg(feature = "websocket")]
 /// Response-Enum für WebSocket-Endpoint
 /// Ermöglicht Rückgabe von entweder WebSocket-Upgrade oder HTTP-Fehler
 enum WebSocketResponse {
@@ -23,6 +66,7 @@ enum WebSocketResponse {
     ServiceUnavailable,
 }
 
+#[cfg(feature = "websocket")]
 impl IntoResponse for WebSocketResponse {
     async fn write_to<
         R: embedded_io_async::Read,
@@ -37,7 +81,7 @@ impl IntoResponse for WebSocketResponse {
             WebSocketResponse::ServiceUnavailable => {
                 picoserve::response::Response::new(
                     picoserve::response::StatusCode::new(503),
-                    "Service Unavailable: Too many WebSocket connections (max 10)",
+                    "Service Unavailable: Too many WebSocket connections (max 9)",
                 )
                 .with_header("Retry-After", "5")
                 .write_to(connection, response_writer)
@@ -50,7 +94,20 @@ impl IntoResponse for WebSocketResponse {
 /// HTTP Server Task - läuft parallel zu anderen Tasks
 ///
 /// Dieser Task stellt den HTTP-Server bereit:
-/// - Serviert index.html auf GET /
+/// - Serviert index.html auf GET / (per POST /api/ui hochgeladene aus dem Flash, sonst einkompiliert),
+///   mit ETag und 304 bei passendem `If-None-Match`
+/// - Web-UI-Verwaltung auf GET/POST/DELETE /api/ui
+/// - Liefert Firmware-Version auf GET /api/version
+/// - Geräte-Verwaltung auf GET /api/system, POST /api/system/{reboot,factory-reset,safe-mode,diagnose}
+/// - Zeitplan-API auf GET/POST /api/schedule, PUT/DELETE /api/schedule/<id>
+/// - Netzwerk-Details auf GET /api/network
+/// - Log-Zeilen auf GET /api/logs, live als Chunked-Stream auf GET /api/logs/stream
+/// - Preset-API auf GET/POST /api/presets, PUT/DELETE /api/presets/<id>
+/// - Weißabgleich auf GET/PUT /api/calibration
+/// - Gerätekonfiguration auf GET/PUT /api/config
+/// - UPnP-Gerätebeschreibung auf GET /description.xml (nur mit Feature `ssdp`)
+/// - Web of Things: Thing Description auf GET /.well-known/wot,
+///   GET/PUT /things/led/properties/<name>, POST /things/led/actions/<name>
 /// - WebSocket-Endpoint auf /ws für bidirektionale Kommunikation
 /// - Empfängt LED-Farb-Updates via Channel
 /// - Sendet Kommandos an LED Task via Channel
@@ -64,39 +121,172 @@ impl IntoResponse for WebSocketResponse {
 /// - `stack`: embassy-net Stack für Netzwerk-Zugriff
 /// - `color_channel`: PubSub Channel für LED-Farb-Broadcasts (WebSocketHandler erstellt Subscriber)
 /// - `command_sender`: Channel Sender für LED-Kommandos
+/// - `schedule`: Geteilter Zeitplan (für /api/schedule und WebSocket)
+/// - `presets`: Geteilte Presets (für /api/presets)
+/// - `calibration`: Geteilter Weißabgleich (für /api/calibration)
+/// - `device_config`: Geteilte Gerätekonfiguration (für /api/config)
+/// - `events`: System-Ereignisse (ClientConnected bei WebSocket-Upgrade)
 #[embassy_executor::task(pool_size = 4)]
 pub async fn http_server_task(
     task_id: usize,
     stack: &'static Stack<'static>,
     _color_channel: &'static LedColorChannel,
     command_sender: LedCommandSender,
+    schedule: &'static ScheduleStore,
+    presets: &'static PresetStore,
+    calibration: &'static CalibrationStore,
+    device_config: &'static DeviceConfigStore,
+    events: &'static SystemEventChannel,
 ) {
     info!("HTTP: Server task {} starting on port 80...", task_id);
 
     // Router-Konfiguration
-    // WebSocket-Route mit async block
-    let app = picoserve::Router::new().route("/", get(serve_html)).route(
+    let app = picoserve::Router::new()
+        .route("/", get(serve_html))
+        .route("/api/version", get(serve_version))
+        .route("/api/heap", get(serve_heap))
+        .route("/api/system", get(serve_system))
+        .route(
+            "/api/network",
+            get(move || async move { picoserve::response::Json(NetworkInfo::current(*stack)) }),
+        )
+        .route(
+            "/api/system/reboot",
+            post(|_: ApiAuth| async { api::post_reboot() }),
+        )
+        .route(
+            "/api/system/factory-reset",
+            post(|_: ApiAuth| async { api::post_factory_reset() }),
+        )
+        .route(
+            "/api/system/safe-mode",
+            post(|_: ApiAuth| async { api::post_safe_mode() }),
+        )
+        .route(
+            "/api/system/diagnose",
+            post(move |_: ApiAuth| async move { api::post_diagnose(command_sender) }),
+        )
+        .route(
+            "/api/ui",
+            get(|| async { api::get_ui() })
+                .post(|_: ApiAuth, _: UiBundleUpload| async { api::get_ui() })
+                .delete(|_: ApiAuth| async { api::delete_ui().await }),
+        )
+        .route("/api/logs", get(serve_logs))
+        .route(
+            "/api/logs/stream",
+            get(|| async { log_stream::get_log_stream() }),
+        )
+        .route("/api/crash", get(|| async { api::get_crash() }))
+        .route("/api/effects", get(|| async { api::get_effects() }))
+        .route(
+            "/api/config",
+            get(move || async move { api::get_config(device_config) }).put(
+                move |ApiJson(config): ApiJson<DeviceConfig>| async move {
+                    api::put_config(device_config, config)
+                },
+            ),
+        )
+        .route(
+            "/api/calibration",
+            get(move || async move { api::get_calibration(calibration) }).put(
+                move |ApiJson(rgb): ApiJson<RgbColor>| async move {
+                    api::put_calibration(calibration, rgb)
+                },
+            ),
+        )
+        .route("/metrics", get(serve_metrics))
+        .route(
+            "/api/schedule",
+            get(move || async move { api::get_schedule(schedule) }).post(
+                move |ApiJson(dto): ApiJson<ScheduleEntryDto>| async move {
+                    api::post_schedule(schedule, dto)
+                },
+            ),
+        )
+        .route(
+            ("/api/schedule", parse_path_segment::<u8>()),
+            put(
+                move |id, ApiJson(dto): ApiJson<ScheduleEntryDto>| async move {
+                    api::put_schedule(schedule, id, dto)
+                },
+            )
+            .delete(move |id| async move { api::delete_schedule(schedule, id) }),
+        )
+        .route(
+            "/api/presets",
+            get(move || async move { api::get_presets(presets) }).post(
+                move |ApiJson(dto): ApiJson<PresetDto>| async move {
+                    api::post_preset(presets, dto)
+                },
+            ),
+        )
+        .route(
+            ("/api/presets", parse_path_segment::<u8>()),
+            put(
+                move |id, ApiJson(dto): ApiJson<PresetDto>| async move {
+                    api::put_preset(presets, id, dto)
+                },
+            )
+            .delete(move |id| async move { api::delete_preset(presets, id) }),
+        )
+        .route("/.well-known/wot", get(|| async { api::get_thing_description() }))
+        .route("/things/led/properties", get(|| async { api::get_wot_properties() }))
+        .route(
+            ("/things/led/properties", parse_path_segment::<WotProperty>()),
+            get(|property| async move { api::get_wot_property(property) }).put(
+                move |property, ApiBody(body): ApiBody| async move {
+                    api::put_wot_property(command_sender, property, &body)
+                },
+            ),
+        )
+        .route(
+            ("/things/led/actions", parse_path_segment::<WotAction>()),
+            post(move |action, ApiBody(body): ApiBody| async move {
+                api::post_wot_action(command_sender, action, &body)
+            }),
+        );
+
+    // WebSocket-Route mit async block (nur mit Feature `websocket`)
+    #[cfg(feature = "websocket")]
+    let app = app.route(
         "/ws",
         get(
             |upgrade: picoserve::response::WebSocketUpgrade| async move {
                 info!("HTTP: WebSocket upgrade requested");
 
+                // Mit Token: Subscriber erst nach erfolgreicher `auth`-Nachricht belegen,
+                // damit nicht authentifizierte Clients keine Slots blockieren
+                if WS_AUTH_TOKEN.is_some() {
+                    let handler = WebSocketHandler {
+                        command_sender,
+                        color_channel: _color_channel,
+                        color_subscriber: None,
+                        schedule,
+                        events,
+                    };
+                    return WebSocketResponse::Upgrade(upgrade.on_upgrade(handler));
+                }
+
                 // Erstelle Subscriber für diese WebSocket-Connection
-                // Mit 10 max. Subscribers (PubSubChannel<..., 2, 10, 1>) und 4 HTTP-Tasks
-                // kann bei > 10 gleichzeitigen WebSocket-Clients die Subscriber-Allokation fehlschlagen.
+                // Mit 19 max. Subscribers (PubSubChannel<..., 2, 19, 1>, davon MQTT + MQTT-SN + BLE + CoAP + Konsole + UART-Konsole + USB-Konsole + Matter + ESP-NOW + LIFX) und 4 HTTP-Tasks
+                // kann bei > 9 gleichzeitigen WebSocket-Clients die Subscriber-Allokation fehlschlagen.
                 // Statt Panic senden wir HTTP 503 an den Client.
                 match _color_channel.subscriber() {
                     Ok(color_subscriber) => {
                         info!("HTTP: Subscriber created, upgrading to WebSocket");
                         let handler = WebSocketHandler {
                             command_sender,
-                            color_subscriber,
+                            color_channel: _color_channel,
+                            color_subscriber: Some(color_subscriber),
+                            schedule,
+                            events,
                         };
                         WebSocketResponse::Upgrade(upgrade.on_upgrade(handler))
                     }
                     Err(_) => {
                         info!(
-                            "HTTP: No subscriber slots available (10/10 in use), sending HTTP 503"
+                            "HTTP: No subscriber slots available (19/19 in use), sending HTTP 503"
                         );
                         WebSocketResponse::ServiceUnavailable
                     }
@@ -104,6 +294,15 @@ pub async fn http_server_task(
             },
         ),
     );
+    #[cfg(not(feature = "websocket"))]
+    let _ = (_color_channel, command_sender, events);
+
+    // UPnP-Gerätebeschreibung für die SSDP-Discovery (nur mit Feature `ssdp`)
+    #[cfg(feature = "ssdp")]
+    let app = app.route(
+        esp_core::ssdp::DESCRIPTION_PATH,
+        get(move || async move { serve_description(stack) }),
+    );
 
     // Server-Konfiguration
     let config = picoserve::Config::new(picoserve::Timeouts {
@@ -117,35 +316,219 @@ pub async fn http_server_task(
     // HTTP-Buffer für Requests/Responses
     let mut http_buffer = [0u8; HTTP_BUFFER_SIZE];
 
+    // Frame-Puffer und Wörterbuch für permessage-deflate
+    #[cfg(feature = "ws-deflate")]
+    let mut deflate_buffers = DeflateBuffers::new();
+
     // TCP-Buffers für Socket
     let mut rx_buffer = [0u8; TCP_RX_BUFFER_SIZE];
     let mut tx_buffer = [0u8; TCP_TX_BUFFER_SIZE];
 
-    // Server erstellen
-    let server = picoserve::Server::new(&app, &config, &mut http_buffer);
+    // Accept-Schleife auf Port 80: jeder Task bedient eine Verbindung zur Zeit,
+    // task_id unterscheidet die parallel lauschenden Instanzen. Eigene Schleife
+    // statt listen_and_serve, damit Keepalive und Timeout pro Socket greifen
+    // (eine halboffene WebSocket-Verbindung blockiert den Task sonst)
+    loop {
+        let mut socket = TcpSocket::new(*stack, &mut rx_buffer, &mut tx_buffer);
+        apply_socket_tuning(&mut socket);
+
+        if socket.accept(80).await.is_err() {
+            warn!("HTTP: Accept failed (task {})", task_id);
+            continue;
+        }
+
+        let server = picoserve::Server::new(&app, &config, &mut http_buffer);
+        #[cfg(feature = "ws-deflate")]
+        let socket = DeflateSocket::new(socket, &mut deflate_buffers);
+        match server.serve(socket).await {
+            Ok(info) => record_net_count(
+                NetCounter::HttpRequests,
+                u32::try_from(info.handled_requests_count).unwrap_or(u32::MAX),
+            ),
+            Err(_) => info!("HTTP: Connection closed with error (task {})", task_id),
+        }
+    }
+}
+
+/// Serviert die HTML-Hauptseite (hochgeladene aus dem Flash, sonst die einkompilierte)
+///
+/// Mit passendem `If-None-Match` nur `304 Not Modified` (siehe `PageRequest`).
+async fn serve_html(request: PageRequest) -> impl IntoResponse {
+    if request.not_modified {
+        return HtmlResponse::NotModified(request.etag);
+    }
+    match request.installed {
+        Some(header) => HtmlResponse::Flash(header, request.etag),
+        None => HtmlResponse::Builtin(request.etag),
+    }
+}
+
+/// ETag der ausgelieferten Seite
+///
+/// Einkompiliert: Git-Hash + Build-Zeit (ändert sich mit jedem Build),
+/// hochgeladen: CRC der Oberfläche.
+fn page_etag(installed: Option<UiBundleHeader>) -> ETag {
+    match installed {
+        Some(header) => ETag::new("ui", u64::from(header.crc)),
+        None => ETag::new(GIT_HASH, BUILD_TIMESTAMP),
+    }
+}
+
+/// Request auf GET /: aktuelle Seite und Abgleich mit `If-None-Match`
+struct PageRequest {
+    installed: Option<UiBundleHeader>,
+    etag: ETag,
+    not_modified: bool,
+}
+
+impl<'r, State> picoserve::extract::FromRequestParts<'r, State> for PageRequest {
+    type Rejection = api::ApiResponse;
+
+    async fn from_request_parts(
+        _state: &'r State,
+        request_parts: &picoserve::request::RequestParts<'r>,
+    ) -> Result<Self, Self::Rejection> {
+        let installed = installed();
+        let etag = page_etag(installed);
+        let not_modified = request_parts
+            .headers()
+            .get("If-None-Match")
+            .is_some_and(|header| if_none_match(header.as_raw(), &etag));
+        Ok(Self {
+            installed,
+            etag,
+            not_modified,
+        })
+    }
+}
+
+/// Response-Enum für GET /
+///
+/// Alle Varianten mit `ETag` und `Cache-Control: no-cache`: Der Browser
+/// behält die Seite, fragt bei jedem Laden aber kurz per `If-None-Match` nach.
+enum HtmlResponse {
+    /// Einkompilierte index.html
+    Builtin(ETag),
+    /// Per POST /api/ui hochgeladene Oberfläche (ggf. gzip)
+    Flash(UiBundleHeader, ETag),
+    /// 304, der Browser hat die Seite schon
+    NotModified(ETag),
+}
+
+impl IntoResponse for HtmlResponse {
+    async fn write_to<
+        R: embedded_io_async::Read,
+        W: picoserve::response::ResponseWriter<Error = R::Error>,
+    >(
+        self,
+        connection: picoserve::response::Connection<'_, R>,
+        response_writer: W,
+    ) -> Result<picoserve::ResponseSent, W::Error> {
+        let status = picoserve::response::StatusCode::OK;
+        match self {
+            HtmlResponse::Builtin(etag) => {
+                picoserve::response::Response::new(status, INDEX_HTML)
+                    .with_header("Content-Type", "text/html; charset=utf-8")
+                    .with_header("ETag", etag)
+                    .with_header("Cache-Control", "no-cache")
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            HtmlResponse::Flash(header, etag) => match header.encoding.content_encoding() {
+                Some(encoding) => {
+                    picoserve::response::Response::new(status, FlashUi(header))
+                        .with_header("Content-Encoding", encoding)
+                        .with_header("ETag", etag)
+                        .with_header("Cache-Control", "no-cache")
+                        .write_to(connection, response_writer)
+                        .await
+                }
+                None => {
+                    picoserve::response::Response::new(status, FlashUi(header))
+                        .with_header("ETag", etag)
+                        .with_header("Cache-Control", "no-cache")
+                        .write_to(connection, response_writer)
+                        .await
+                }
+            },
+            HtmlResponse::NotModified(etag) => {
+                picoserve::response::Response::new(picoserve::response::StatusCode::new(304), "")
+                    .with_header("ETag", etag)
+                    .with_header("Cache-Control", "no-cache")
+                    .write_to(connection, response_writer)
+                    .await
+            }
+        }
+    }
+}
 
-    // Server starten (lauscht auf Port 80)
-    // task_id ermöglicht mehrere concurrent Server-Instanzen
-    let _ = server
-        .listen_and_serve(task_id, *stack, 80, &mut rx_buffer, &mut tx_buffer)
-        .await;
+/// Liefert Firmware-Version und Build-Informationen als JSON
+async fn serve_version() -> impl IntoResponse {
+    picoserve::response::Json(VersionInfo::current())
+}
 
-    info!("HTTP: Server task {} ended", task_id);
+/// Liefert die Heap-Auslastung (letzte Messung des Heap-Monitors) als JSON
+async fn serve_heap() -> impl IntoResponse {
+    picoserve::response::Json(HeapInfo::current())
 }
 
-/// Serviert die HTML-Hauptseite
-async fn serve_html() -> impl IntoResponse {
-    picoserve::response::Response::new(picoserve::response::StatusCode::OK, INDEX_HTML)
-        .with_header("Content-Type", "text/html; charset=utf-8")
+/// Liefert Laufzeit und Task-Statistik als JSON
+async fn serve_system() -> impl IntoResponse {
+    picoserve::response::Json(SystemInfo::current())
 }
 
+/// Liefert Frame-Render-Metriken (p50/p95) und Netzwerk-Zähler im Prometheus-Textformat
+async fn serve_metrics() -> impl IntoResponse {
+    let mut body = String::new();
+    // Schreiben in einen String schlägt nicht fehl
+    render_summary().write_prometheus(&mut body).ok();
+    net_stats().write_prometheus(&mut body).ok();
+    picoserve::response::Response::new(picoserve::response::StatusCode::OK, body)
+        .with_header("Content-Type", "text/plain; version=0.0.4")
+}
+
+/// Liefert die UPnP-Gerätebeschreibung (Ziel von `LOCATION` in SSDP-Antworten)
+#[cfg(feature = "ssdp")]
+fn serve_description(stack: &'static Stack<'static>) -> impl IntoResponse {
+    use esp_core::{DeviceDescription, DeviceUuid, write_device_description};
+
+    let uuid = DeviceUuid::from_mac(esp_hal::efuse::Efuse::mac_address());
+    let ip = stack
+        .config_v4()
+        .map(|config| config.address.address().octets())
+        .unwrap_or_default();
+    let description = DeviceDescription {
+        friendly_name: SSDP_FRIENDLY_NAME,
+        manufacturer: SSDP_MANUFACTURER,
+        model_name: HA_DEVICE_MODEL,
+        model_number: crate::version::FIRMWARE_VERSION,
+        ip,
+    };
+    let mut body = String::new();
+    // Schreiben in einen String schlägt nicht fehl
+    write_device_description(&mut body, &uuid, &description).ok();
+    picoserve::response::Response::new(picoserve::response::StatusCode::OK, body)
+        .with_header("Content-Type", "text/xml; charset=utf-8")
+}
+
+/// Liefert die letzten Log-Zeilen aus dem RAM-Ringpuffer als JSON
+async fn serve_logs() -> impl IntoResponse {
+    picoserve::response::Json(LogList::current())
+}
+
+#[cfg(feature = "websocket")]
 /// WebSocket-Handler State
 /// Speichert Command Sender und Color Subscriber für bidirektionale Kommunikation
 struct WebSocketHandler {
     command_sender: LedCommandSender,
-    color_subscriber: LedColorSubscriber,
+    color_channel: &'static LedColorChannel,
+    /// Vor dem Upgrade belegt, oder `None` bis zur Authentifizierung (WS_AUTH_TOKEN)
+    color_subscriber: Option<LedColorSubscriber>,
+    schedule: &'static ScheduleStore,
+    events: &'static SystemEventChannel,
 }
 
+#[cfg(feature = "websocket")]
 impl ws::WebSocketCallback for WebSocketHandler {
     async fn run<R: embedded_io_async::Read, W: embedded_io_async::Write<Error = R::Error>>(
         mut self,
@@ -157,100 +540,125 @@ impl ws::WebSocketCallback for WebSocketHandler {
         // Buffer für eingehende WebSocket-Nachrichten
         let mut buffer = [0u8; WEBSOCKET_BUFFER_SIZE];
 
+        // Subscriber belegen, mit Token erst nach erfolgreicher Authentifizierung
+        let mut color_subscriber = match self.color_subscriber.take() {
+            Some(color_subscriber) => color_subscriber,
+            None => {
+                let token = WS_AUTH_TOKEN.unwrap_or_default();
+                if !Self::authenticate(&mut rx, &mut buffer, token).await? {
+                    info!("HTTP: WebSocket authentication failed, closing");
+                    Self::send_error(&mut tx, "Unauthorized").await.ok();
+                    return tx.close(Some((1008, "Unauthorized"))).await;
+                }
+                match self.color_channel.subscriber() {
+                    Ok(color_subscriber) => color_subscriber,
+                    Err(_) => {
+                        info!("HTTP: No subscriber slots available, closing WebSocket");
+                        Self::send_error(&mut tx, "Too many connections").await.ok();
+                        return tx.close(Some((1013, "Try again later"))).await;
+                    }
+                }
+            }
+        };
+        publish_event(
+            self.events,
+            SystemEvent::ClientConnected {
+                kind: ClientKind::WebSocket,
+            },
+        );
+
+        // Hello-Nachricht mit Firmware-Version senden
+        Self::send_hello(&mut tx).await.ok();
+
         // Sende initiales Status-Update wenn Subscriber Messages hat
-        if let Some(msg) = self.color_subscriber.try_next_message_pure() {
-            let mode = if msg.is_auto_mode {
-                OperationMode::Auto
-            } else {
-                OperationMode::Manual
-            };
-            Self::send_status_update(&mut tx, &msg, mode).await.ok();
+        if let Some(msg) = color_subscriber.try_next_message_pure() {
+            Self::send_status_update(&mut tx, &msg).await.ok();
         }
 
+        // Ohne WebSocket-Client hat der HTTP-Task keinen Takt: beim Trennen aus der Stall-Prüfung nehmen
+        let _park = ParkOnDrop(TaskId::Http);
+
+        // Heap-Telemetrie im festen Takt, unabhängig von Farb-Updates (auch Lebenszeichen)
+        let mut heap_ticker = Ticker::every(Duration::from_secs(HEAP_TELEMETRY_INTERVAL_SECS));
+
         let close_reason = loop {
-            // Gleichzeitig auf zwei Events lauschen mit embassy_futures::select:
+            task_heartbeat(TaskId::Http);
+            // Gleichzeitig auf drei Events lauschen mit embassy_futures::select3:
             // 1. WebSocket-Messages vom Browser
             // 2. LED-Color-Broadcasts vom PubSubChannel
+            // 3. Telemetrie-Takt (Heap-Auslastung)
             //
-            // Dies ist effizienter als Polling mit Timer, da beide Futures
+            // Dies ist effizienter als Polling mit Timer, da alle Futures
             // gleichzeitig awaited werden und nur bei tatsächlichen Events aufwachen.
-            match select(
+            match select3(
                 rx.next_message(&mut buffer, pending()),
-                self.color_subscriber.next_message_pure(),
+                color_subscriber.next_message_pure(),
+                heap_ticker.next(),
             )
             .await
             {
                 // WebSocket-Nachricht vom Browser empfangen
-                Either::First(ws_result) => {
+                Either3::First(ws_result) => {
                     let ws_result = ws_result?.ignore_never_b();
 
                     match ws_result {
                         Ok(ws::Message::Text(data)) => {
+                            record_net(NetCounter::WsMessagesIn);
+                            let _span = span(SpanKind::WsCommand);
                             info!("HTTP: Received text message: {} bytes", data.len());
 
-                            // Parse JSON-Nachricht (konvertiere &str zu &[u8])
-                            match serde_json_core::from_slice::<WsClientMessage>(data.as_bytes()) {
-                                Ok((msg, _)) => {
-                                    use crate::web::protocol::MessageType;
-
-                                    match msg.msg_type {
-                                        MessageType::SetColor => {
-                                            info!("HTTP: Received set_color command");
-
-                                            if let Some(color) = msg.color {
-                                                let color_str = color.as_str();
-                                                if let Ok(command) = LedCommand::try_from(color_str)
-                                                {
-                                                    info!(
-                                                        "HTTP: Sending command to LED: {}",
-                                                        color_str
-                                                    );
-
-                                                    // Sende Command an LED Task (infallible)
-                                                    // Der Browser erhält Status-Update automatisch via PubSubChannel,
-                                                    // wenn der LED-Task die Farbe geändert hat (Single Source of Truth)
-                                                    self.command_sender.send(command).await;
-                                                } else {
-                                                    info!(
-                                                        "HTTP: Unknown color name: {}",
-                                                        color_str
-                                                    );
-                                                }
-                                            }
-                                        }
-                                        MessageType::SetMode => {
-                                            info!("HTTP: Received set_mode command");
-
-                                            if let Some(mode) = msg.mode {
-                                                if mode == OperationMode::Auto {
-                                                    info!("HTTP: Enabling auto mode");
-                                                    self.command_sender
-                                                        .send(LedCommand::EnableAuto)
-                                                        .await;
-                                                    info!("HTTP: Auto mode enabled");
-                                                }
-                                            }
+                            // Parse JSON-Nachricht und ordne sie zu (esp_core::ws)
+                            match parse_ws_message::<ScheduleEntryDto>(
+                                data.as_bytes(),
+                                LED_BRIGHTNESS,
+                            ) {
+                                WsRequest::Command(command) => {
+                                    // Sende Command an LED Task (blockiert nie, bei voller Queue "busy")
+                                    // Der Browser erhält Status-Update automatisch via PubSubChannel,
+                                    // wenn der LED-Task die Farbe geändert hat (Single Source of Truth)
+                                    Self::send_command(&mut tx, self.command_sender, command)
+                                        .await?;
+                                }
+                                WsRequest::ScheduleGet => {
+                                    Self::send_schedule(&mut tx, self.schedule).await?;
+                                }
+                                WsRequest::ScheduleAdd(dto) => {
+                                    info!("HTTP: Received schedule_add command");
+
+                                    match api::add_schedule_entry(self.schedule, dto) {
+                                        Ok(_) => {
+                                            Self::send_schedule(&mut tx, self.schedule).await?
                                         }
+                                        Err(e) => Self::send_error(&mut tx, e.error).await?,
                                     }
                                 }
-                                Err(_) => {
-                                    info!("HTTP: JSON parse error");
-                                    // Sende Error-Response
-                                    let error = WsServerMessage::Error {
-                                        message: "JSON parse error",
-                                    };
-                                    let mut json_buffer = [0u8; JSON_ERROR_BUFFER_SIZE];
-                                    if let Ok(n) =
-                                        serde_json_core::to_slice(&error, &mut json_buffer)
-                                    {
-                                        let json_str =
-                                            core::str::from_utf8(&json_buffer[..n]).unwrap();
-                                        let _ = tx.send_text(json_str).await;
+                                WsRequest::ScheduleRemove(id) => {
+                                    info!("HTTP: Received schedule_remove command");
+
+                                    match self.schedule.remove(id) {
+                                        Ok(_) => {
+                                            Self::send_schedule(&mut tx, self.schedule).await?
+                                        }
+                                        Err(e) => {
+                                            Self::send_error(
+                                                &mut tx,
+                                                api::schedule_error_message(e),
+                                            )
+                                            .await?
+                                        }
                                     }
                                 }
+                                WsRequest::Error(message) => {
+                                    info!("HTTP: Rejected message: {}", message);
+                                    Self::send_error(&mut tx, message).await?;
+                                }
+                                WsRequest::Ignored => {
+                                    info!("HTTP: Message ignored");
+                                }
                             }
                         }
                         Ok(ws::Message::Binary(data)) => {
+                            record_net(NetCounter::WsMessagesIn);
                             info!(
                                 "HTTP: Received binary message: {} bytes (ignored)",
                                 data.len()
@@ -274,22 +682,21 @@ impl ws::WebSocketCallback for WebSocketHandler {
                     }
                 }
                 // LED-Color-Update vom PubSubChannel empfangen
-                Either::Second(led_msg) => {
-                    let mode = if led_msg.is_auto_mode {
-                        OperationMode::Auto
-                    } else {
-                        OperationMode::Manual
-                    };
+                Either3::Second(led_msg) => {
                     info!(
                         "HTTP: LED color changed to '{}' ({}), notifying client",
-                        led_msg.name,
+                        led_msg.color_id,
                         if led_msg.is_auto_mode {
                             "Auto"
                         } else {
                             "Manuell"
                         }
                     );
-                    Self::send_status_update(&mut tx, &led_msg, mode).await.ok();
+                    Self::send_status_update(&mut tx, &led_msg).await.ok();
+                }
+                // Telemetrie-Takt
+                Either3::Third(()) => {
+                    Self::send_heap(&mut tx).await.ok();
                 }
             }
         };
@@ -299,39 +706,149 @@ impl ws::WebSocketCallback for WebSocketHandler {
     }
 }
 
+#[cfg(feature = "websocket")]
 impl WebSocketHandler {
-    /// Sendet Status-Update an WebSocket-Client
-    async fn send_status_update<W: embedded_io_async::Write>(
+    /// Wartet auf die `auth`-Nachricht als erste Client-Nachricht
+    ///
+    /// `Ok(false)` bei falschem Token, anderer Nachricht oder Ablauf von WS_AUTH_TIMEOUT_SECS.
+    async fn authenticate<R: embedded_io_async::Read>(
+        rx: &mut ws::SocketRx<R>,
+        buffer: &mut [u8],
+        token: &str,
+    ) -> Result<bool, R::Error> {
+        let Ok(ws_result) = with_timeout(
+            Duration::from_secs(WS_AUTH_TIMEOUT_SECS),
+            rx.next_message(buffer, pending()),
+        )
+        .await
+        else {
+            return Ok(false);
+        };
+        let Ok(ws::Message::Text(data)) = ws_result?.ignore_never_b() else {
+            return Ok(false);
+        };
+        Ok(is_valid_auth(data.as_bytes(), token))
+    }
+
+    /// Reiht ein Kommando ein, ohne zu warten
+    ///
+    /// Bei voller Warteschlange (LED-Task hängt oder ist überlastet) erhält
+    /// der Client eine "busy"-Nachricht statt einer blockierten Verbindung.
+    async fn send_command<W: embedded_io_async::Write>(
         tx: &mut ws::SocketTx<W>,
-        led_msg: &LedColorMessage,
-        mode: OperationMode,
+        command_sender: LedCommandSender,
+        command: LedCommand,
     ) -> Result<(), W::Error> {
-        let rgb = RgbColor {
-            r: led_msg.color.r,
-            g: led_msg.color.g,
-            b: led_msg.color.b,
+        if command_sender.try_send(command).is_accepted() {
+            return Ok(());
+        }
+        info!("HTTP: Command queue full, sending busy to client");
+
+        let error = FirmwareError::new(ErrorSource::Led, ErrorKind::Busy);
+        let busy = WsServerMessage::Busy {
+            message: "LED busy, try again",
+            category: error.category().name(),
+            kind: error.kind.name(),
+            dropped: command_sender.stats().dropped(),
         };
 
-        // ColorName aus dem String-Namen erstellen
-        let color = match led_msg.name {
-            "Rot" => crate::web::protocol::ColorName::Red,
-            "Grün" => crate::web::protocol::ColorName::Green,
-            "Blau" => crate::web::protocol::ColorName::Blue,
-            _ => return Ok(()), // Unbekannte Farbe ignorieren
+        let mut json_buffer = [0u8; JSON_ERROR_BUFFER_SIZE];
+        if let Ok(n) = serde_json_core::to_slice(&busy, &mut json_buffer) {
+            let json_str = core::str::from_utf8(&json_buffer[..n]).unwrap();
+            tx.send_text(json_str).await?;
+            record_net(NetCounter::WsMessagesOut);
+        }
+
+        Ok(())
+    }
+
+    /// Sendet Error-Nachricht an WebSocket-Client
+    async fn send_error<W: embedded_io_async::Write>(
+        tx: &mut ws::SocketTx<W>,
+        message: &'static str,
+    ) -> Result<(), W::Error> {
+        let error = WsServerMessage::Error { message };
+
+        let mut json_buffer = [0u8; JSON_ERROR_BUFFER_SIZE];
+        if let Ok(n) = serde_json_core::to_slice(&error, &mut json_buffer) {
+            let json_str = core::str::from_utf8(&json_buffer[..n]).unwrap();
+            tx.send_text(json_str).await?;
+            record_net(NetCounter::WsMessagesOut);
+        }
+
+        Ok(())
+    }
+
+    /// Sendet den aktuellen Zeitplan an WebSocket-Client
+    async fn send_schedule<W: embedded_io_async::Write>(
+        tx: &mut ws::SocketTx<W>,
+        schedule: &ScheduleStore,
+    ) -> Result<(), W::Error> {
+        let message = WsServerMessage::Schedule {
+            entries: api::schedule_entries(schedule).entries,
         };
 
-        let status = WsServerMessage::Status {
-            color,
-            rgb,
-            timestamp_ms: Instant::now().as_millis(),
-            mode,
+        let mut json_buffer = [0u8; JSON_SCHEDULE_BUFFER_SIZE];
+        if let Ok(n) = serde_json_core::to_slice(&message, &mut json_buffer) {
+            let json_str = core::str::from_utf8(&json_buffer[..n]).unwrap();
+            tx.send_text(json_str).await?;
+            record_net(NetCounter::WsMessagesOut);
+        }
+
+        Ok(())
+    }
+
+    /// Sendet die Heap-Auslastung an WebSocket-Client
+    async fn send_heap<W: embedded_io_async::Write>(
+        tx: &mut ws::SocketTx<W>,
+    ) -> Result<(), W::Error> {
+        let heap = WsServerMessage::Heap(HeapInfo::current());
+
+        let mut json_buffer = [0u8; JSON_STATUS_BUFFER_SIZE];
+        if let Ok(n) = serde_json_core::to_slice(&heap, &mut json_buffer) {
+            let json_str = core::str::from_utf8(&json_buffer[..n]).unwrap();
+            tx.send_text(json_str).await?;
+            record_net(NetCounter::WsMessagesOut);
+        }
+
+        Ok(())
+    }
+
+    /// Sendet Hello-Nachricht mit Firmware-Version an WebSocket-Client
+    async fn send_hello<W: embedded_io_async::Write>(
+        tx: &mut ws::SocketTx<W>,
+    ) -> Result<(), W::Error> {
+        let info = VersionInfo::current();
+        let hello = WsServerMessage::Hello {
+            version: info.version,
+            git_hash: info.git_hash,
+            build_timestamp: info.build_timestamp,
         };
 
+        let mut json_buffer = [0u8; JSON_STATUS_BUFFER_SIZE];
+        if let Ok(n) = serde_json_core::to_slice(&hello, &mut json_buffer) {
+            let json_str = core::str::from_utf8(&json_buffer[..n]).unwrap();
+            tx.send_text(json_str).await?;
+            record_net(NetCounter::WsMessagesOut);
+        }
+
+        Ok(())
+    }
+
+    /// Sendet Status-Update an WebSocket-Client
+    async fn send_status_update<W: embedded_io_async::Write>(
+        tx: &mut ws::SocketTx<W>,
+        led_msg: &LedColorMessage,
+    ) -> Result<(), W::Error> {
+        let _span = span(SpanKind::WsSend);
+        let status = WsServerMessage::status(led_msg, UI_LANGUAGE);
+
         // Serialisiere und sende
         let mut json_buffer = [0u8; JSON_STATUS_BUFFER_SIZE];
         if let Ok(n) = serde_json_core::to_slice(&status, &mut json_buffer) {
             let json_str = core::str::from_utf8(&json_buffer[..n]).unwrap();
             tx.send_text(json_str).await?;
+            record_net(NetCounter::WsMessagesOut);
         }
 
         Ok(())
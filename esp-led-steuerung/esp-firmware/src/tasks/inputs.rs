@@ -0,0 +1,71 @@
+// Eingangs-Task - digitale Eingänge (Tür-/Reed-Kontakte) an GPIO5/GPIO6
+//
+// Tastet die Eingänge aus DIGITAL_INPUTS ab, entprellt sie (Logik in
+// esp_core::inputs) und meldet jeden Wechsel als `SystemEvent::InputChanged`
+// (MQTT published den Zustand). Ist für den neuen Zustand ein LED-Kommando
+// konfiguriert, wird es gesendet.
+
+use defmt::info;
+use embassy_time::{Duration, Timer};
+use esp_core::{DigitalInput, SystemEvent};
+use esp_hal::gpio::{AnyPin, Input, InputConfig, Pull};
+
+use crate::config::{DIGITAL_INPUTS, INPUT_DEBOUNCE_MS, INPUT_POLL_MS};
+use crate::hal::EmbassyClock;
+use crate::{LedCommandSender, SystemEventChannel, publish_event};
+
+/// Anzahl der Eingänge (ein Pin pro Eintrag in DIGITAL_INPUTS)
+pub const INPUT_COUNT: usize = DIGITAL_INPUTS.len();
+
+/// Eingangs-Task - läuft parallel zu anderen Tasks
+///
+/// # Parameter
+/// - `pins`: Pins in der Reihenfolge von DIGITAL_INPUTS
+/// - `command_sender`: Channel Sender für LED-Kommandos
+/// - `events`: System-Ereignisse (InputChanged)
+#[embassy_executor::task]
+pub async fn inputs_task(
+    pins: [AnyPin<'static>; INPUT_COUNT],
+    command_sender: LedCommandSender,
+    events: &'static SystemEventChannel,
+) {
+    let pins = pins.map(|pin| Input::new(pin, InputConfig::default().with_pull(Pull::Up)));
+    let mut inputs: [DigitalInput; INPUT_COUNT] = core::array::from_fn(|index| {
+        DigitalInput::new(
+            DIGITAL_INPUTS[index],
+            pins[index].is_high(),
+            INPUT_DEBOUNCE_MS,
+        )
+    });
+    for input in &inputs {
+        info!(
+            "Inputs: '{}' is {}",
+            input.config.name,
+            input.config.state_name(input.is_active())
+        );
+    }
+
+    loop {
+        Timer::after(Duration::from_millis(INPUT_POLL_MS)).await;
+        for (index, (input, pin)) in inputs.iter_mut().zip(&pins).enumerate() {
+            let Some(active) = input.update(pin.is_high(), &EmbassyClock) else {
+                continue;
+            };
+            info!(
+                "Inputs: '{}' changed to {}",
+                input.config.name,
+                input.config.state_name(active)
+            );
+            publish_event(
+                events,
+                SystemEvent::InputChanged {
+                    input: index as u8,
+                    active,
+                },
+            );
+            if let Some(command) = input.config.command(active) {
+                command_sender.send(command).await;
+            }
+        }
+    }
+}
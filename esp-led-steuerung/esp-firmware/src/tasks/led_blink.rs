@@ -1,90 +1,533 @@
 // LED Blink Task - Steuert RGB LED über RMT Peripheral
-use defmt::{error, info};
+use defmt::{error, info, warn};
+use embassy_futures::select::{Either, Either3, select, select3};
 use embassy_time::{Duration, Timer};
+use esp_core::{
+    Clock, ClockFace, Crossfade, DiagnoseBlink, EffectId, LedEffect, LedLoop, LedStateConfig,
+    Meteor, ParamValue, PixelEffect, Plasma, ScrollingText, SpanKind, SyncFrame, SyncRole, TaskId,
+    TestPattern, TextMessage, scale_brightness,
+};
 use esp_hal_smartled::smart_led_buffer;
 use rgb::RGB8;
 
-use crate::config::{BLINK_INTERVAL_SECS, LED_BRIGHTNESS, RMT_CLOCK_MHZ};
-use crate::hal::{RmtLedWriter, SmartLedWriter};
-use crate::{LedColorMessage, LedColorPublisher, LedCommand, LedCommandReceiver, rotate_color};
+use crate::boards::LedPin;
+use crate::calibration::CalibrationStore;
+use crate::config::{
+    BLINK_INTERVAL_SECS, BRIGHTNESS_FADE_MS, CLOCK_FACE, DIAGNOSE_BRIGHTNESS, EFFECT_CROSSFADE_MS,
+    LED_BRIGHTNESS, LED_COUNT, MATRIX_LAYOUT, RMT_CLOCK_MHZ, SCROLL_TEXT_STEP_MS, SLEEP_FADE_SECS,
+    STATUS_LED, STATUS_LED_FRAME_MS, SUNRISE_MAX_BRIGHTNESS, SYNC_LEAD_MS, SYNC_ROLE,
+    TEST_PATTERN_BRIGHTNESS, TRANSITION_FRAME_MS,
+};
+use crate::device_config::DeviceConfigStore;
+use crate::effects::set_active_effect;
+use crate::hal::{EmbassyClock, HardwareRng, RmtLedWriter, SmartLedWriter};
+use crate::preset::PresetStore;
+use crate::render_stats::record_frame;
+use crate::task_stats::{task_error, task_heartbeat};
+use crate::tasks::realtime::RealtimeFrame;
+use crate::tasks::status_led::{diagnose_code, status_frame};
+use crate::trace::span;
+use crate::wall_clock;
+use crate::{
+    LedColorPublisher, LedCommand, LedCommandReceiver, PublisherSink, RealtimeSignal, SyncSignal,
+};
 
-/// LED Blink Logic - Testbare Business Logic ohne Hardware-Abhängigkeit
+/// LED Blink Logic - Async-Treiber für `esp_core::LedLoop`
 ///
-/// Diese Funktion enthält die komplette LED-Steuerungs-Logik:
-/// - Rotiert Farben automatisch (Rot → Blau → Grün) oder
-/// - Empfängt manuelle Farb-Kommandos vom WebSocket
-/// - Blinkt mit konfigurierbarem Intervall
-/// - Sendet Farb-Updates an MQTT und HTTP Tasks via Channel
+/// Die komplette Schleifen-Logik (Kommandos, Zustand, LED schreiben, Publish)
+/// liegt in `esp_core::LedLoop` und ist auf dem Host testbar.
+/// Diese Funktion übernimmt nur Timing, Logging und Metriken:
+/// - Führt einen Durchlauf pro Blink-Intervall aus
+/// - Erhöht die Bildrate während Verläufen (Sonnenaufgang, Sleep-Timer)
+/// - Gibt UDP-Realtime-Frames aus, bis diese ausbleiben (Timeout)
+/// - Gibt nach `LedCommand::TestPattern` das Testmuster aus, bis ein neues Kommando kommt
+/// - Blinkt nach `LedCommand::Diagnose` den Code des letzten Verbindungsproblems, danach
+///   geht es im vorherigen Modus weiter
+/// - Gibt nach `LedCommand::ScrollText` den Lauftext aus, ebenfalls bis zum nächsten Kommando
+/// - Zeigt nach `LedCommand::ShowClock` die Uhrzeit an, ebenfalls bis zum nächsten Kommando
+/// - Zeigt nach `LedCommand::ShowGradient` den Farbverlauf, ebenfalls bis zum nächsten Kommando
+/// - Lässt nach `LedCommand::Meteor` einen Meteor über den Strip laufen, ebenfalls bis zum nächsten Kommando
+/// - Zeigt nach `LedCommand::Plasma` ein Plasma in den Farben der Farbfolge, ebenfalls bis zum nächsten Kommando
+/// - Blendet zwischen Farbverlauf, Meteor und Plasma über EFFECT_CROSSFADE_MS über
+/// - Übernimmt `LedCommand::SetEffectParam` im laufenden Meteor/Plasma, ohne den Effekt zu beenden
+/// - Zeigt mit STATUS_LED bei ausgeschalteter Lampe Blinkcodes auf der ersten LED
+/// - Meldet den aktiven Effekt für GET /api/effects (crate::effects)
+/// - Als Sync-Leader: berechnet pro Durchlauf den Frame in SYNC_LEAD_MS voraus (Sync Task)
 ///
 /// # Trait-basierte Abstraktion
-/// Der generische Parameter `L: SmartLedWriter` ermöglicht:
-/// - Real Hardware (RmtLedWriter) im Production-Code
-/// - Mock Implementation (MockLedWriter) in Unit Tests
+/// Die generischen Parameter `L: SmartLedWriter` und `C: Clock` ermöglichen:
+/// - Real Hardware (RmtLedWriter, EmbassyClock) im Production-Code
+/// - Mock Implementationen (MockLedWriter, MockClock) in Unit Tests
 ///
 /// # Parameter
 /// - `led`: LED Writer (Hardware oder Mock)
+/// - `clock`: Monotone Zeitquelle für Verläufe und Sleep-Timer
 /// - `color_publisher`: PubSub Publisher für LED-Farb-Broadcasts
-/// - `command_receiver`: Channel Receiver für WebSocket-Kommandos
-pub async fn led_blink_logic<L: SmartLedWriter>(
-    mut led: L,
+/// - `command_receiver`: Channel Receiver für LED-Kommandos
+/// - `presets`: Presets für `LedCommand::ApplyPreset`
+/// - `calibration`: Weißabgleich, wird vor jedem Durchlauf übernommen
+/// - `device_config`: Gerätekonfiguration (Kanal-Reihenfolge und Strip-Zuordnung für den LED-Writer)
+/// - `realtime`: Signal für UDP-Realtime-Frames
+/// - `sync`: Signal für Sync-Frames an die Follower (nur mit `SYNC_ROLE=leader`)
+pub async fn led_blink_logic<L: SmartLedWriter, C: Clock>(
+    led: L,
+    clock: C,
     color_publisher: LedColorPublisher,
     command_receiver: LedCommandReceiver,
+    presets: &'static PresetStore,
+    calibration: &'static CalibrationStore,
+    device_config: &'static DeviceConfigStore,
+    realtime: &'static RealtimeSignal,
+    sync: &'static SyncSignal,
 ) {
-    // Farbe initialisieren: starte mit Rot
-    let mut color: RGB8 = RGB8::default();
-    color.r = LED_BRIGHTNESS;
+    // Startet mit Auto-Rotation ab Rot (Regeln siehe esp_core::state)
+    let config = LedStateConfig {
+        base_brightness: LED_BRIGHTNESS,
+        sunrise_max_brightness: SUNRISE_MAX_BRIGHTNESS,
+        sleep_fade_ms: SLEEP_FADE_SECS * 1000,
+        brightness_fade_ms: BRIGHTNESS_FADE_MS,
+    };
+    let mut led_loop = LedLoop::new(
+        config,
+        led,
+        clock,
+        PublisherSink(color_publisher),
+        command_receiver,
+    )
+    .with_presets(presets)
+    .with_rng(HardwareRng::new());
 
-    // Modus-Flag: automatische Rotation vs. manuelle Steuerung
-    let mut auto_rotate = true;
+    // Zuletzt beendeter Pixel-Effekt (Ausgangspunkt der Überblendung)
+    let mut last_effect: Option<PixelEffect> = None;
 
     // Hauptschleife: blinkt LED endlos
     loop {
-        let mut color_changed = false;
-
-        // Prüfe auf eingehende Kommandos vom WebSocket (non-blocking)
-        if let Ok(cmd) = command_receiver.try_receive() {
-            match cmd {
-                LedCommand::SetColor { target_color, name } => {
-                    info!("Command received: SetColor {}", name);
-                    color = target_color;
-                    auto_rotate = false; // Wechsel zu manueller Steuerung
-                    color_changed = true; // Farbe hat sich geändert
-                }
-                LedCommand::EnableAuto => {
-                    info!("Command received: EnableAuto");
-                    auto_rotate = true; // Wechsel zu Auto-Rotation
-                    // Keine Farb-Änderung, nur Modus-Wechsel
-                }
-            }
-        }
+        task_heartbeat(TaskId::Led);
+        led_loop.calibration = calibration.get();
+        let config = device_config.get();
+        led_loop.led.set_color_order(config.color_order);
+        led_loop.led.set_strip_mapping(config.mapping());
+        let cycle = {
+            let _span = span(SpanKind::LedFrame);
+            led_loop.step()
+        };
+        record_frame(cycle.timing);
+        set_active_effect(led_loop.state.active_effect());
+        // Nur wenn direkt der nächste Effekt startet, wird von hier übergeblendet
+        let from = last_effect.take();
 
-        // Farb-Rotation nur im Auto-Modus
-        if auto_rotate {
-            color = rotate_color(color);
-            color_changed = true; // Farbe hat sich geändert
+        // Follower zeigen den Frame zum selben Zeitpunkt (ohne eigenen Weißabgleich)
+        if SYNC_ROLE == SyncRole::Leader {
+            let show_at_ms = led_loop.clock.now_ms() + u64::from(SYNC_LEAD_MS);
+            sync.signal(SyncFrame {
+                show_at_ms,
+                color: led_loop.state.output(show_at_ms),
+            });
         }
 
-        info!("Blink!");
-
-        // Farbe an LED senden (via Trait - Hardware oder Mock)
-        if let Err(_e) = led.write(color) {
+        if let Some(cmd) = cycle.command {
+            info!("Command received: {}", cmd);
+        }
+        if let Some(LedCommand::TestPattern) = cycle.command {
+            // Kommando, das das Testmuster beendet, wird im nächsten Durchlauf verarbeitet
+            let next =
+                test_pattern_mode(&mut led_loop.led, &led_loop.clock, &led_loop.source).await;
+            led_loop.pending = Some(next);
+            continue;
+        }
+        if let Some(LedCommand::Diagnose) = cycle.command {
+            // Ohne unterbrechendes Kommando geht es danach im bisherigen Zustand weiter
+            led_loop.pending =
+                diagnose_mode(&mut led_loop.led, &led_loop.clock, &led_loop.source).await;
+            continue;
+        }
+        if let Some(LedCommand::ScrollText { text }) = cycle.command {
+            let color = effect_color(led_loop.state.output(led_loop.clock.now_ms()));
+            let next = scroll_text_mode(
+                &mut led_loop.led,
+                &led_loop.clock,
+                &led_loop.source,
+                text,
+                color,
+            )
+            .await;
+            led_loop.pending = Some(next);
+            continue;
+        }
+        if let Some(LedCommand::ShowClock { format }) = cycle.command {
+            let face = format.map_or(CLOCK_FACE, |format| CLOCK_FACE.with_format(format));
+            led_loop.pending = Some(clock_mode(&mut led_loop.led, &led_loop.source, face).await);
+            continue;
+        }
+        let brightness = led_loop.state.brightness;
+        let now_ms = led_loop.clock.now_ms();
+        let effect = match cycle.command {
+            Some(LedCommand::ShowGradient { gradient }) => {
+                Some(PixelEffect::Gradient(gradient.scaled(brightness)))
+            }
+            Some(LedCommand::Meteor { color, speed, tail }) => {
+                let color = match color {
+                    Some(color) => scale_brightness(color, brightness),
+                    None => effect_color(led_loop.state.output(now_ms)),
+                };
+                Some(PixelEffect::Meteor(Meteor::new(now_ms, color, speed, tail)))
+            }
+            Some(LedCommand::Plasma { speed }) => {
+                let palette = led_loop.state.sequence.scaled(brightness);
+                Some(PixelEffect::Plasma(Plasma::new(now_ms, speed, palette)))
+            }
+            _ => None,
+        };
+        if let Some(effect) = effect {
+            let (next, effect) = effect_mode(
+                &mut led_loop.led,
+                &led_loop.clock,
+                &led_loop.source,
+                effect,
+                from,
+                brightness,
+            )
+            .await;
+            led_loop.pending = Some(next);
+            last_effect = Some(effect);
+            continue;
+        }
+        if let Some(LedCommand::SetEffectParam { value }) = cycle.command {
+            warn!(
+                "Effect param {=str} ignored, no tunable effect running",
+                value.name().name()
+            );
+        }
+        if cycle.transition.effect_finished {
+            info!("Effect finished");
+        }
+        if cycle.transition.sleep_expired {
+            info!("Sleep timer expired, LED off");
+        }
+        if led_loop.state.effect.is_none() {
+            info!("Blink!");
+        }
+        if cycle.write_failed {
             error!("Failed to write to LED");
+            task_error(TaskId::Led);
         }
-
-        // Nur publishen wenn sich Farbe geändert hat
-        if color_changed {
-            let msg = LedColorMessage::from_color(color, auto_rotate);
-            color_publisher.publish_immediate(msg); // Broadcast an alle Subscribers
+        if let Some(msg) = cycle.published {
             info!(
                 "Published color update: {} ({})",
-                msg.name,
-                if auto_rotate { "Auto" } else { "Manuell" }
+                msg.color_id,
+                if msg.is_auto_mode { "Auto" } else { "Manuell" }
             );
         }
 
+        // Lampe aus: Leerlauf-Frames zeigen den Verbindungsstatus
+        if STATUS_LED
+            && !cycle.animating
+            && led_loop.state.output(led_loop.clock.now_ms()) == RGB8::default()
+        {
+            led_loop.pending = status_mode(
+                &mut led_loop.led,
+                &led_loop.clock,
+                &led_loop.source,
+                realtime,
+            )
+            .await;
+            continue;
+        }
+
         // Async Delay: gibt CPU an andere Tasks zurück
-        Timer::after(Duration::from_secs(BLINK_INTERVAL_SECS)).await;
+        // Während eines Verlaufs mit höherer Bildrate für flüssige Übergänge
+        let delay = Duration::from_millis(
+            cycle.next_delay_ms(BLINK_INTERVAL_SECS * 1000, TRANSITION_FRAME_MS),
+        );
+        // Ein eingehender Realtime-Frame unterbricht die Wartezeit sofort
+        if let Either::Second(frame) = select(Timer::after(delay), realtime.wait()).await {
+            // Kommando, das den Realtime-Modus beendet, wird im nächsten Durchlauf verarbeitet
+            led_loop.pending =
+                realtime_mode(&mut led_loop.led, frame, &led_loop.source, realtime).await;
+        }
+    }
+}
+
+/// Farbe für Lauftext und Meteor: aktuelle Farbe, nach "Aus" Weiß
+fn effect_color(output: RGB8) -> RGB8 {
+    match output {
+        RGB8 { r: 0, g: 0, b: 0 } => RGB8::new(LED_BRIGHTNESS, LED_BRIGHTNESS, LED_BRIGHTNESS),
+        color => color,
+    }
+}
+
+/// Realtime-Modus: gibt UDP-Frames aus, bis keine mehr kommen
+///
+/// Endet nach Ablauf des Frame-Timeouts (Rückkehr zum vorherigen Modus)
+/// oder bei einem neuen Kommando, das dann zurückgegeben wird.
+async fn realtime_mode<L: SmartLedWriter>(
+    led: &mut L,
+    mut frame: RealtimeFrame,
+    command_receiver: &LedCommandReceiver,
+    realtime: &'static RealtimeSignal,
+) -> Option<LedCommand> {
+    info!("Realtime mode started");
+    set_active_effect(EffectId::Realtime);
+    loop {
+        if let Err(_e) = led.write_frame(&frame.pixels) {
+            error!("Failed to write to LED");
+        }
+        match select3(
+            Timer::after(frame.timeout),
+            realtime.wait(),
+            command_receiver.receive(),
+        )
+        .await
+        {
+            Either3::First(()) => {
+                info!("Realtime mode timed out, resuming previous mode");
+                return None;
+            }
+            Either3::Second(next) => frame = next,
+            Either3::Third(cmd) => {
+                info!("Realtime mode ended by command");
+                return Some(cmd);
+            }
+        }
+    }
+}
+
+/// Status-Modus: Blinkcode auf der ersten LED (siehe esp_core::status_led)
+///
+/// Läuft, solange die Lampe aus ist: bis zum nächsten Kommando, das dann
+/// zurückgegeben wird, oder bis zum Ende eines Realtime-Streams.
+async fn status_mode<L: SmartLedWriter, C: Clock>(
+    led: &mut L,
+    clock: &C,
+    command_receiver: &LedCommandReceiver,
+    realtime: &'static RealtimeSignal,
+) -> Option<LedCommand> {
+    let mut pixels = [RGB8::default(); LED_COUNT];
+    loop {
+        task_heartbeat(TaskId::Led);
+        pixels[0] = status_frame(clock.now_ms());
+        if let Err(_e) = led.write_frame(&pixels) {
+            error!("Failed to write to LED");
+        }
+        match select3(
+            Timer::after(Duration::from_millis(STATUS_LED_FRAME_MS)),
+            command_receiver.receive(),
+            realtime.wait(),
+        )
+        .await
+        {
+            Either3::First(()) => {}
+            Either3::Second(cmd) => return Some(cmd),
+            Either3::Third(frame) => {
+                return realtime_mode(led, frame, command_receiver, realtime).await;
+            }
+        }
+    }
+}
+
+/// Testmuster-Modus: Lauflicht über alle Pixel, dann R/G/B (siehe esp_core::test_pattern)
+///
+/// Läuft bis zum nächsten Kommando, das dann zurückgegeben wird.
+async fn test_pattern_mode<L: SmartLedWriter, C: Clock>(
+    led: &mut L,
+    clock: &C,
+    command_receiver: &LedCommandReceiver,
+) -> LedCommand {
+    info!("Test pattern started");
+    set_active_effect(EffectId::TestPattern);
+    let pattern = TestPattern::new(clock.now_ms(), TEST_PATTERN_BRIGHTNESS);
+    let mut pixels = [RGB8::default(); LED_COUNT];
+    loop {
+        task_heartbeat(TaskId::Led);
+        pattern.fill(clock.now_ms(), &mut pixels);
+        if let Err(_e) = led.write_frame(&pixels) {
+            error!("Failed to write to LED");
+        }
+        let frame = Timer::after(Duration::from_millis(TRANSITION_FRAME_MS));
+        if let Either::Second(cmd) = select(frame, command_receiver.receive()).await {
+            info!("Test pattern ended by command");
+            return cmd;
+        }
+    }
+}
+
+/// Diagnose-Modus: Blinkcode des letzten Verbindungsproblems auf allen Pixeln
+/// (siehe esp_core::diagnose)
+///
+/// Endet nach der Blinkfolge (Rückkehr zum vorherigen Modus) oder bei einem
+/// neuen Kommando, das dann zurückgegeben wird.
+async fn diagnose_mode<L: SmartLedWriter, C: Clock>(
+    led: &mut L,
+    clock: &C,
+    command_receiver: &LedCommandReceiver,
+) -> Option<LedCommand> {
+    let code = diagnose_code();
+    info!(
+        "Diagnose: {=str} (code {})",
+        code.map_or("ok", |code| code.name()),
+        code.map_or(0, |code| code.number())
+    );
+    let blink = DiagnoseBlink::new(code, clock.now_ms());
+    let mut pixels = [RGB8::default(); LED_COUNT];
+    loop {
+        task_heartbeat(TaskId::Led);
+        let Some(color) = blink.frame(clock.now_ms(), DIAGNOSE_BRIGHTNESS) else {
+            info!("Diagnose finished, resuming previous mode");
+            return None;
+        };
+        pixels.fill(color);
+        if let Err(_e) = led.write_frame(&pixels) {
+            error!("Failed to write to LED");
+        }
+        let frame = Timer::after(Duration::from_millis(STATUS_LED_FRAME_MS));
+        if let Either::Second(cmd) = select(frame, command_receiver.receive()).await {
+            info!("Diagnose ended by command");
+            return Some(cmd);
+        }
+    }
+}
+
+/// Lauftext-Modus: Nachricht läuft über die Matrix (siehe esp_core::text)
+///
+/// Läuft bis zum nächsten Kommando, das dann zurückgegeben wird.
+async fn scroll_text_mode<L: SmartLedWriter, C: Clock>(
+    led: &mut L,
+    clock: &C,
+    command_receiver: &LedCommandReceiver,
+    text: TextMessage,
+    color: RGB8,
+) -> LedCommand {
+    info!("Scrolling text started");
+    set_active_effect(EffectId::ScrollText);
+    let scroll = ScrollingText::new(clock.now_ms(), color, SCROLL_TEXT_STEP_MS);
+    let mut pixels = [RGB8::default(); LED_COUNT];
+    loop {
+        task_heartbeat(TaskId::Led);
+        scroll.render(clock.now_ms(), text.as_str(), MATRIX_LAYOUT, &mut pixels);
+        if let Err(_e) = led.write_frame(&pixels) {
+            error!("Failed to write to LED");
+        }
+        let frame = Timer::after(Duration::from_millis(u64::from(SCROLL_TEXT_STEP_MS)));
+        if let Either::Second(cmd) = select(frame, command_receiver.receive()).await {
+            info!("Scrolling text ended by command");
+            return cmd;
+        }
+    }
+}
+
+/// Uhr-Modus: aktuelle Uhrzeit auf Matrix oder Strip (siehe esp_core::clock_face)
+///
+/// Bis zur ersten SNTP-Synchronisation bleiben die LEDs aus.
+/// Läuft bis zum nächsten Kommando, das dann zurückgegeben wird.
+async fn clock_mode<L: SmartLedWriter>(
+    led: &mut L,
+    command_receiver: &LedCommandReceiver,
+    face: ClockFace,
+) -> LedCommand {
+    info!("Clock display started");
+    set_active_effect(EffectId::Clock);
+    if !wall_clock::is_synchronized() {
+        warn!("Clock display: time not synchronized yet");
+    }
+    let mut pixels = [RGB8::default(); LED_COUNT];
+    loop {
+        task_heartbeat(TaskId::Led);
+        match wall_clock::local_now() {
+            Some(time) => face.render(&time, MATRIX_LAYOUT, &mut pixels),
+            None => pixels.fill(RGB8::default()),
+        }
+        if let Err(_e) = led.write_frame(&pixels) {
+            error!("Failed to write to LED");
+        }
+        let frame = Timer::after(Duration::from_millis(TRANSITION_FRAME_MS));
+        if let Either::Second(cmd) = select(frame, command_receiver.receive()).await {
+            info!("Clock display ended by command");
+            return cmd;
+        }
+    }
+}
+
+/// Effekt-Modus: Farbverlauf, Meteor oder Plasma (siehe esp_core::crossfade)
+///
+/// Blendet vom vorherigen Effekt `from` über EFFECT_CROSSFADE_MS ein und
+/// übernimmt `SetEffectParam` (Farben wie beim Start mit `brightness`
+/// skaliert). Statische Effekte werden nach der Überblendung nur im
+/// Blink-Intervall neu geschrieben. Läuft bis zum nächsten Kommando und gibt
+/// es mit dem Effekt in seinem aktuellen Stand zurück, dem Ausgangspunkt
+/// der nächsten Überblendung.
+async fn effect_mode<L: SmartLedWriter, C: Clock>(
+    led: &mut L,
+    clock: &C,
+    command_receiver: &LedCommandReceiver,
+    effect: PixelEffect,
+    from: Option<PixelEffect>,
+    brightness: u8,
+) -> (LedCommand, PixelEffect) {
+    let id = effect.id();
+    info!("Effect {=str} started", id.name());
+    set_active_effect(id);
+    let duration_ms = if from.is_some() {
+        EFFECT_CROSSFADE_MS
+    } else {
+        0
+    };
+    let mut fade = Crossfade::new(from.unwrap_or(effect), effect, clock.now_ms(), duration_ms);
+    let mut pixels = [RGB8::default(); LED_COUNT];
+    let mut incoming = [RGB8::default(); LED_COUNT];
+    loop {
+        task_heartbeat(TaskId::Led);
+        let now_ms = clock.now_ms();
+        fade.render(now_ms, MATRIX_LAYOUT, &mut pixels, &mut incoming);
+        if let Err(_e) = led.write_frame(&pixels) {
+            error!("Failed to write to LED");
+        }
+        let frame_ms = if fade.to.is_animated() || !fade.is_finished(now_ms) {
+            TRANSITION_FRAME_MS
+        } else {
+            BLINK_INTERVAL_SECS * 1000
+        };
+        let frame = Timer::after(Duration::from_millis(frame_ms));
+        if let Either::Second(cmd) = select(frame, command_receiver.receive()).await {
+            let cmd = scale_param_colors(cmd, brightness);
+            let Some(cmd) = apply_effect_param(&mut fade.to, cmd, clock.now_ms()) else {
+                continue;
+            };
+            info!("Effect {=str} ended by command", id.name());
+            return (cmd, fade.to);
+        }
+    }
+}
+
+/// Skaliert live gesetzte Farben wie beim Start des Effekts mit `brightness`
+fn scale_param_colors(command: LedCommand, brightness: u8) -> LedCommand {
+    let LedCommand::SetEffectParam { value } = command else {
+        return command;
+    };
+    let value = match value {
+        ParamValue::Color(color) => ParamValue::Color(scale_brightness(color, brightness)),
+        ParamValue::Palette(palette) => ParamValue::Palette(palette.scaled(brightness)),
+        other => other,
+    };
+    LedCommand::SetEffectParam { value }
+}
+
+/// Übernimmt `SetEffectParam` in den laufenden Effekt (siehe esp_core::effect_param)
+///
+/// Gibt andere Kommandos zurück, die den Effekt beenden.
+fn apply_effect_param<E: LedEffect>(
+    effect: &mut E,
+    command: LedCommand,
+    now_ms: u64,
+) -> Option<LedCommand> {
+    let LedCommand::SetEffectParam { value } = command else {
+        return Some(command);
+    };
+    let name = value.name().name();
+    match effect.apply_param(value, now_ms) {
+        Ok(()) => info!("Effect param {=str} set", name),
+        Err(e) => warn!("Effect param {=str} rejected: {=str}", name, e.name()),
     }
+    None
 }
 
 /// LED Blink Task - Embassy Task für parallele Ausführung
@@ -93,24 +536,45 @@ pub async fn led_blink_logic<L: SmartLedWriter>(
 /// die testbare `led_blink_logic()` Funktion auf.
 ///
 /// # Parameter
-/// - `gpio8`: GPIO8 Peripheral für LED-Datenleitung
+/// - `led_pin`: Pin der LED-Datenleitung (aus der Gerätekonfiguration, siehe `board_pins!`)
 /// - `rmt_peripheral`: RMT Peripheral für präzises Timing
 /// - `color_publisher`: PubSub Publisher für LED-Farb-Broadcasts
 /// - `command_receiver`: Channel Receiver für WebSocket-Kommandos
+/// - `presets`: Presets für `LedCommand::ApplyPreset`
+/// - `calibration`: Weißabgleich, wird vor jedem Durchlauf übernommen
+/// - `device_config`: Gerätekonfiguration (Kanal-Reihenfolge und Strip-Zuordnung für den LED-Writer)
+/// - `realtime`: Signal für UDP-Realtime-Frames
+/// - `sync`: Signal für Sync-Frames an die Follower
 #[embassy_executor::task]
 pub async fn led_blink_task(
-    gpio8: esp_hal::peripherals::GPIO8<'static>,
+    led_pin: LedPin,
     rmt_peripheral: esp_hal::peripherals::RMT<'static>,
     color_publisher: LedColorPublisher,
     command_receiver: LedCommandReceiver,
+    presets: &'static PresetStore,
+    calibration: &'static CalibrationStore,
+    device_config: &'static DeviceConfigStore,
+    realtime: &'static RealtimeSignal,
+    sync: &'static SyncSignal,
 ) {
-    // Buffer für SmartLED Daten erstellen (1 LED)
+    // Buffer für SmartLED Daten erstellen (LED_COUNT LEDs)
     // Macro allokiert Speicher im richtigen Format für RMT
-    let mut rmt_buffer = smart_led_buffer!(1);
+    let mut rmt_buffer = smart_led_buffer!(LED_COUNT);
 
     // Hardware initialisieren: RmtLedWriter kapselt RMT + SmartLED
-    let led = RmtLedWriter::new(gpio8, rmt_peripheral, RMT_CLOCK_MHZ, &mut rmt_buffer);
+    let led = RmtLedWriter::new(led_pin, rmt_peripheral, RMT_CLOCK_MHZ, &mut rmt_buffer);
 
     // Business Logic aufrufen (jetzt testbar!)
-    led_blink_logic(led, color_publisher, command_receiver).await;
+    led_blink_logic(
+        led,
+        EmbassyClock,
+        color_publisher,
+        command_receiver,
+        presets,
+        calibration,
+        device_config,
+        realtime,
+        sync,
+    )
+    .await;
 }
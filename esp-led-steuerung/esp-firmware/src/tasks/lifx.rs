@@ -0,0 +1,145 @@
+// LIFX Task - Emuliert eine LIFX-Farblampe im LAN (UDP 56700)
+//
+// Vorhandene LIFX-Apps und -Bibliotheken (lifxlan, aiolifx/Home Assistant,
+// Photons) finden die Lampe per Broadcast (GetService) und steuern sie mit
+// SetColor/SetPower. Nur mit Cargo-Feature `lifx` enthalten:
+//   cargo build --release --features lifx
+//
+// Als Geräte-Adresse dient die WLAN-MAC. Codec und Zustandsmodell (HSBK ↔ RGB):
+// siehe esp_core::lifx.
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either, select};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpEndpoint, Stack};
+use embassy_time::{Duration, Timer};
+use esp_core::lifx::MAX_RESPONSE_LEN;
+use esp_core::{
+    LifxHeader, LifxLight, LifxResponse, NetCounter, decode_lifx_packet, encode_lifx_response,
+    lifx_response,
+};
+
+use crate::config::{LIFX_LABEL, LIFX_PACKET_BUFFER_SIZE, LIFX_UDP_PORT};
+use crate::net_stats::record_net;
+use crate::{LedColorSubscriber, LedCommandSender};
+
+/// LIFX Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Lauscht auf UDP-Port LIFX_UDP_PORT
+/// - Leitet Set-Anfragen als LED-Kommandos weiter, beantwortet Get-Anfragen
+/// - Gleicht den Lampenzustand mit Farb-Broadcasts anderer Quellen ab
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn lifx_task(
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    info!("LIFX: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0u8; LIFX_PACKET_BUFFER_SIZE * 4];
+    let mut tx_buffer = [0u8; MAX_RESPONSE_LEN * 4];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    if socket.bind(LIFX_UDP_PORT).is_err() {
+        warn!("LIFX: Failed to bind UDP port {}", LIFX_UDP_PORT);
+        return;
+    }
+    info!("LIFX: Listening on UDP port {}", LIFX_UDP_PORT);
+
+    let mac = esp_hal::efuse::Efuse::mac_address();
+    let mut light = LifxLight::default();
+    let mut packet = [0u8; LIFX_PACKET_BUFFER_SIZE];
+
+    loop {
+        match select(
+            socket.recv_from(&mut packet),
+            color_subscriber.next_message_pure(),
+        )
+        .await
+        {
+            Either::First(Ok((len, meta))) => {
+                record_net(NetCounter::UdpPacketsIn);
+                let (header, request) = match decode_lifx_packet(&packet[..len]) {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        warn!("LIFX: Invalid packet: {}", Debug2Format(&e));
+                        continue;
+                    }
+                };
+                // Pakete an andere Lampen ignorieren
+                if !header.is_for(mac) {
+                    continue;
+                }
+
+                if let Some(command) = light.apply(&request) {
+                    info!("LIFX: {} -> {}", Debug2Format(&request), command);
+                    command_sender.send(command).await;
+                }
+                if header.ack_required {
+                    reply(
+                        &socket,
+                        meta.endpoint,
+                        &header,
+                        mac,
+                        &LifxResponse::Acknowledgement,
+                    )
+                    .await;
+                }
+                if let Some(response) = lifx_response(&header, &request, &light, LIFX_LABEL) {
+                    reply(&socket, meta.endpoint, &header, mac, &response).await;
+                }
+            }
+            // Zu große Pakete werden verworfen
+            Either::First(Err(_)) => {}
+            // Änderungen aus anderen Quellen übernehmen (Web UI, MQTT, Auto-Modus)
+            Either::Second(msg) => light.sync_from_color(msg.color),
+        }
+    }
+}
+
+/// Kodiert und sendet eine Antwort an den Absender der Anfrage
+async fn reply(
+    socket: &UdpSocket<'_>,
+    endpoint: IpEndpoint,
+    request: &LifxHeader,
+    mac: [u8; 6],
+    response: &LifxResponse<'_>,
+) {
+    let mut out = [0u8; MAX_RESPONSE_LEN];
+    let Ok(len) = encode_lifx_response(&mut out, request, mac, response) else {
+        return;
+    };
+    if socket.send_to(&out[..len], endpoint).await.is_err() {
+        warn!("LIFX: Failed to send response");
+    } else {
+        record_net(NetCounter::UdpPacketsOut);
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
@@ -0,0 +1,140 @@
+// Log-Bridge Task - Log-Ringpuffer als Syslog über UDP
+//
+// Schickt jede Zeile des Log-Ringpuffers (log_ring.rs) als Syslog-Nachricht
+// (RFC 5424, esp_core::syslog) an LOG_UDP_HOST. Im Feld ohne Debug-Probe
+// reicht damit ein Syslog-Empfänger im LAN, z.B.:
+//   nc -kulp 514
+// Nur mit Cargo-Feature `log-udp` enthalten:
+//   cargo build --release --features log-udp
+//
+// defmt-Ausgaben selbst bleiben binär und gehen weiter nur an die Probe; es
+// gelangen dieselben Zeilen wie in GET /api/logs nach außen (Ereignisse,
+// Fehler, einzelne Meldungen). Zeilen ab dem Boot werden nachgeliefert,
+// sobald das Netzwerk steht, zwischenzeitlich überschriebene fehlen
+// (Lücke im `sequenceId`).
+
+use defmt::{Debug2Format, info, warn};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpAddress, IpEndpoint, Stack, dns::DnsQueryType};
+use embassy_time::{Duration, Timer, with_timeout};
+use esp_core::{BrokerAddress, NetCounter, write_syslog_frame};
+use heapless::String;
+
+use crate::config::{
+    DNS_TIMEOUT_SECS, LOG_UDP_DEFAULT_PORT, LOG_UDP_FRAME_SIZE, LOG_UDP_HOST, LOG_UDP_POLL_MS,
+    LOG_UDP_RETRY_SECS, MDNS_HOSTNAME,
+};
+use crate::log_ring::{log_next_seq, log_snapshot};
+use crate::net_stats::record_net;
+use crate::wall_clock;
+
+/// Log-Bridge Task
+///
+/// - Wartet auf Netzwerk-Verbindung und löst LOG_UDP_HOST auf
+/// - Prüft alle LOG_UDP_POLL_MS auf neue Log-Zeilen und sendet jede als
+///   eigenes Datagramm (HOSTNAME = MDNS_HOSTNAME)
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+#[embassy_executor::task]
+pub async fn log_udp_task(stack: &'static Stack<'static>) {
+    info!("Log-UDP: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let Some(host) = BrokerAddress::parse(LOG_UDP_HOST) else {
+        warn!("Log-UDP: Invalid address '{}'", LOG_UDP_HOST);
+        return;
+    };
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 1];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0u8; 64];
+    let mut tx_buffer = [0u8; LOG_UDP_FRAME_SIZE * 4];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    // Lokaler Port beliebig, Syslog antwortet nicht
+    if socket.bind(0).is_err() {
+        warn!("Log-UDP: Failed to bind UDP socket");
+        return;
+    }
+
+    let endpoint = loop {
+        match resolve(stack, host).await {
+            Some(endpoint) => break endpoint,
+            None => {
+                warn!(
+                    "Log-UDP: Cannot resolve '{}', retrying in {}s",
+                    host.host, LOG_UDP_RETRY_SECS
+                );
+                Timer::after(Duration::from_secs(LOG_UDP_RETRY_SECS)).await;
+            }
+        }
+    };
+    info!("Log-UDP: Sending to {}", Debug2Format(&endpoint));
+
+    // Ab der ersten Zeile seit Boot
+    let mut next = 0;
+    loop {
+        Timer::after(Duration::from_millis(LOG_UDP_POLL_MS)).await;
+        if log_next_seq() == next {
+            continue;
+        }
+
+        let ring = log_snapshot();
+        let boot_unix_ms = wall_clock::boot_unix_ms();
+        for line in ring.since(next) {
+            let mut frame: String<LOG_UDP_FRAME_SIZE> = String::new();
+            // Header passt immer, die Zeile ist auf LOG_LINE_LEN begrenzt
+            write_syslog_frame(&mut frame, MDNS_HOSTNAME, boot_unix_ms, line).ok();
+            // Sendefehler nur per defmt melden: eine Zeile im Ringpuffer
+            // würde selbst wieder gesendet
+            if socket.send_to(frame.as_bytes(), endpoint).await.is_err() {
+                warn!("Log-UDP: Failed to send line {}", line.seq);
+            } else {
+                record_net(NetCounter::UdpPacketsOut);
+            }
+            next = line.seq.wrapping_add(1);
+        }
+    }
+}
+
+/// Löst den Empfänger auf (IPv4, auch als Literal)
+async fn resolve(
+    stack: &'static Stack<'static>,
+    host: BrokerAddress<'static>,
+) -> Option<IpEndpoint> {
+    let port = host.port.unwrap_or(LOG_UDP_DEFAULT_PORT);
+    let result = with_timeout(
+        Duration::from_secs(DNS_TIMEOUT_SECS),
+        stack.dns_query(host.host, DnsQueryType::A),
+    )
+    .await;
+    let Ok(Ok(addrs)) = result else {
+        return None;
+    };
+    for addr in addrs {
+        if let IpAddress::Ipv4(ipv4) = addr {
+            return Some(IpEndpoint::new(IpAddress::Ipv4(ipv4), port));
+        }
+    }
+    None
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
@@ -0,0 +1,364 @@
+// Matter Task - Extended Color Light über Matter-over-WiFi (rs-matter)
+//
+// Macht die LED in Apple Home, Google Home und Home Assistant (Matter-Integration)
+// direkt sichtbar, ohne Bridge. Nur mit Cargo-Feature `matter` enthalten:
+//   cargo build --release --features matter
+//
+// Endpoint 1 (Extended Color Light) mit den Clustern:
+// - On/Off          (On, Off, Toggle)
+// - Level Control   (MoveToLevel, MoveToLevelWithOnOff)
+// - Color Control   (MoveToHue, MoveToSaturation, MoveToHueAndSaturation,
+//                    MoveToColorTemperature)
+//
+// Der Matter-Stack läuft über den bestehenden embassy-net Stack (WiFi ist
+// bereits verbunden, kein BLE-Commissioning). Umrechnung Attribute ↔ Farbe:
+// siehe esp_core::matter.
+
+use core::cell::Cell;
+use core::pin::pin;
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either3, select3};
+use embassy_net::Stack;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Timer};
+use esp_core::matter::{LIGHT_ENDPOINT_ID, MAX_LEVEL, MAX_MIREDS, MIN_MIREDS};
+use esp_core::{MatterAttribute, MatterLight};
+use rs_matter_embassy::epoch::epoch;
+use rs_matter_embassy::eth::{EmbassyEthMatterStack, EmbassyEthernet, PreexistingEthDriver};
+use rs_matter_embassy::matter::dm::clusters::decl::{color_control, level_control, on_off};
+use rs_matter_embassy::matter::dm::clusters::desc::{self, ClusterHandler as _};
+use rs_matter_embassy::matter::dm::devices::DEV_TYPE_EXTENDED_COLOR_LIGHT;
+use rs_matter_embassy::matter::dm::devices::test::{TEST_DEV_ATT, TEST_DEV_COMM, TEST_DEV_DET};
+use rs_matter_embassy::matter::dm::{
+    Async, Dataver, EmptyHandler, Endpoint, EpClMatcher, InvokeContext, Node, ReadContext,
+};
+use rs_matter_embassy::matter::error::Error;
+use rs_matter_embassy::matter::{clusters, devices};
+use rs_matter_embassy::rand::esp::esp_rand;
+use rs_matter_embassy::stack::persist::DummyKvBlobStore;
+use static_cell::StaticCell;
+
+use crate::{LedColorSubscriber, LedCommandSender};
+
+/// Geteilter Lichtzustand (Cluster-Handler lesen, Bridge schreibt)
+type SharedLight = Mutex<NoopRawMutex, Cell<MatterLight>>;
+
+/// Attribut-Änderungen vom Controller (Cluster-Handler → Bridge)
+type AttributeChannel = Channel<NoopRawMutex, MatterAttribute, 4>;
+
+/// Node-Beschreibung: Root-Endpoint + Licht-Endpoint
+const NODE: Node<'static> = Node {
+    id: 0,
+    endpoints: &[
+        EmbassyEthMatterStack::<()>::root_endpoint(),
+        Endpoint {
+            id: LIGHT_ENDPOINT_ID,
+            device_types: devices!(DEV_TYPE_EXTENDED_COLOR_LIGHT),
+            clusters: clusters!(
+                desc::DescHandler::CLUSTER,
+                LightHandler::ON_OFF_CLUSTER,
+                LightHandler::LEVEL_CONTROL_CLUSTER,
+                LightHandler::COLOR_CONTROL_CLUSTER
+            ),
+        },
+    ],
+};
+
+/// Matter Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Startet den Matter-Stack (Commissioning-Code erscheint im Log)
+/// - Setzt Attribut-Änderungen vom Controller in LED-Kommandos um
+/// - Meldet Farbänderungen aus anderen Quellen an die Controller zurück
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn matter_task(
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    info!("Matter: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    // Matter-Stack ist zu groß für den Task-Stack → statisch allokieren
+    static MATTER_STACK: StaticCell<EmbassyEthMatterStack<()>> = StaticCell::new();
+    let matter_stack = &*MATTER_STACK.init(EmbassyEthMatterStack::new(
+        &TEST_DEV_DET,
+        TEST_DEV_COMM,
+        &TEST_DEV_ATT,
+        epoch,
+        esp_rand,
+    ));
+
+    static LIGHT: StaticCell<SharedLight> = StaticCell::new();
+    let light = &*LIGHT.init(Mutex::new(Cell::new(MatterLight::default())));
+    static ATTRIBUTES: StaticCell<AttributeChannel> = StaticCell::new();
+    let attributes = &*ATTRIBUTES.init(AttributeChannel::new());
+
+    let handler = LightHandler {
+        dataver: Dataver::new_rand(matter_stack.matter().rand()),
+        light,
+        attributes,
+    };
+    let handlers = EmptyHandler
+        .chain(
+            EpClMatcher::new(Some(LIGHT_ENDPOINT_ID), Some(desc::DescHandler::CLUSTER.id)),
+            Async(desc::DescHandler::new(Dataver::new_rand(matter_stack.matter().rand())).adapt()),
+        )
+        .chain(
+            EpClMatcher::new(
+                Some(LIGHT_ENDPOINT_ID),
+                Some(LightHandler::ON_OFF_CLUSTER.id),
+            ),
+            Async(on_off::HandlerAdaptor(&handler)),
+        )
+        .chain(
+            EpClMatcher::new(
+                Some(LIGHT_ENDPOINT_ID),
+                Some(LightHandler::LEVEL_CONTROL_CLUSTER.id),
+            ),
+            Async(level_control::HandlerAdaptor(&handler)),
+        )
+        .chain(
+            EpClMatcher::new(
+                Some(LIGHT_ENDPOINT_ID),
+                Some(LightHandler::COLOR_CONTROL_CLUSTER.id),
+            ),
+            Async(color_control::HandlerAdaptor(&handler)),
+        );
+
+    // Fabrics werden (noch) nicht im Flash gespeichert → nach Neustart neu koppeln
+    let store = matter_stack.create_shared_store(DummyKvBlobStore);
+    let mut matter = pin!(matter_stack.run(
+        EmbassyEthernet::new(PreexistingEthDriver::new(stack), matter_stack),
+        &store,
+        (NODE, handlers),
+        (),
+    ));
+    info!("Matter: Stack running, commissioning code is printed above");
+
+    loop {
+        match select3(
+            &mut matter,
+            attributes.receive(),
+            color_subscriber.next_message_pure(),
+        )
+        .await
+        {
+            Either3::First(result) => {
+                warn!("Matter: Stack stopped: {}", Debug2Format(&result));
+                return;
+            }
+            // Controller hat ein Attribut geändert → LED-Kommando
+            Either3::Second(attribute) => {
+                let command = light.lock(|cell| {
+                    let mut state = cell.get();
+                    let command = state.apply(attribute);
+                    cell.set(state);
+                    command
+                });
+                info!("Matter: {} -> {}", Debug2Format(&attribute), command);
+                command_sender.send(command).await;
+            }
+            // Farbänderung aus anderer Quelle → Controller benachrichtigen
+            Either3::Third(msg) => {
+                let changed = light.lock(|cell| {
+                    let mut state = cell.get();
+                    let changed = state.sync_from_color(msg.color);
+                    cell.set(state);
+                    changed
+                });
+                if changed {
+                    handler.dataver.changed();
+                    matter_stack.notify_changed();
+                }
+            }
+        }
+    }
+}
+
+/// Cluster-Handler für On/Off, Level Control und Color Control
+///
+/// Liest den geteilten Zustand und leitet Kommandos als [`MatterAttribute`]
+/// an die Bridge weiter (Handler sind synchron, daher `try_send`).
+struct LightHandler {
+    dataver: Dataver,
+    light: &'static SharedLight,
+    attributes: &'static AttributeChannel,
+}
+
+impl LightHandler {
+    const ON_OFF_CLUSTER: rs_matter_embassy::matter::dm::Cluster<'static> =
+        on_off::FULL_CLUSTER.with_features(0);
+    const LEVEL_CONTROL_CLUSTER: rs_matter_embassy::matter::dm::Cluster<'static> =
+        level_control::FULL_CLUSTER.with_features(level_control::Feature::ON_OFF.bits());
+    const COLOR_CONTROL_CLUSTER: rs_matter_embassy::matter::dm::Cluster<'static> =
+        color_control::FULL_CLUSTER.with_features(
+            color_control::Feature::HUE_AND_SATURATION.bits()
+                | color_control::Feature::COLOR_TEMPERATURE.bits(),
+        );
+
+    fn state(&self) -> MatterLight {
+        self.light.lock(Cell::get)
+    }
+
+    fn send(&self, attribute: MatterAttribute) -> Result<(), Error> {
+        if self.attributes.try_send(attribute).is_err() {
+            warn!("Matter: Attribute queue full, dropping change");
+        }
+        self.dataver.changed();
+        Ok(())
+    }
+}
+
+impl on_off::ClusterHandler for LightHandler {
+    const CLUSTER: rs_matter_embassy::matter::dm::Cluster<'static> = Self::ON_OFF_CLUSTER;
+
+    fn dataver(&self) -> u32 {
+        self.dataver.get()
+    }
+
+    fn dataver_changed(&self) {
+        self.dataver.changed();
+    }
+
+    fn on_off(&self, _ctx: impl ReadContext) -> Result<bool, Error> {
+        Ok(self.state().on)
+    }
+
+    fn handle_off(&self, _ctx: impl InvokeContext) -> Result<(), Error> {
+        self.send(MatterAttribute::OnOff(false))
+    }
+
+    fn handle_on(&self, _ctx: impl InvokeContext) -> Result<(), Error> {
+        self.send(MatterAttribute::OnOff(true))
+    }
+
+    fn handle_toggle(&self, _ctx: impl InvokeContext) -> Result<(), Error> {
+        self.send(MatterAttribute::OnOff(!self.state().on))
+    }
+}
+
+impl level_control::ClusterHandler for LightHandler {
+    const CLUSTER: rs_matter_embassy::matter::dm::Cluster<'static> = Self::LEVEL_CONTROL_CLUSTER;
+
+    fn dataver(&self) -> u32 {
+        self.dataver.get()
+    }
+
+    fn dataver_changed(&self) {
+        self.dataver.changed();
+    }
+
+    fn current_level(&self, _ctx: impl ReadContext) -> Result<Option<u8>, Error> {
+        Ok(Some(self.state().level))
+    }
+
+    fn handle_move_to_level(
+        &self,
+        _ctx: impl InvokeContext,
+        request: level_control::MoveToLevelRequest<'_>,
+    ) -> Result<(), Error> {
+        self.send(MatterAttribute::Level(request.level()?))
+    }
+
+    fn handle_move_to_level_with_on_off(
+        &self,
+        _ctx: impl InvokeContext,
+        request: level_control::MoveToLevelWithOnOffRequest<'_>,
+    ) -> Result<(), Error> {
+        self.send(MatterAttribute::Level(request.level()?))
+    }
+}
+
+impl color_control::ClusterHandler for LightHandler {
+    const CLUSTER: rs_matter_embassy::matter::dm::Cluster<'static> = Self::COLOR_CONTROL_CLUSTER;
+
+    fn dataver(&self) -> u32 {
+        self.dataver.get()
+    }
+
+    fn dataver_changed(&self) {
+        self.dataver.changed();
+    }
+
+    fn current_hue(&self, _ctx: impl ReadContext) -> Result<u8, Error> {
+        Ok(self.state().hue)
+    }
+
+    fn current_saturation(&self, _ctx: impl ReadContext) -> Result<u8, Error> {
+        Ok(self.state().saturation)
+    }
+
+    fn color_temp_physical_min_mireds(&self, _ctx: impl ReadContext) -> Result<u16, Error> {
+        Ok(MIN_MIREDS)
+    }
+
+    fn color_temp_physical_max_mireds(&self, _ctx: impl ReadContext) -> Result<u16, Error> {
+        Ok(MAX_MIREDS)
+    }
+
+    fn handle_move_to_hue(
+        &self,
+        _ctx: impl InvokeContext,
+        request: color_control::MoveToHueRequest<'_>,
+    ) -> Result<(), Error> {
+        self.send(MatterAttribute::HueSaturation {
+            hue: request.hue()?.min(MAX_LEVEL),
+            saturation: self.state().saturation,
+        })
+    }
+
+    fn handle_move_to_saturation(
+        &self,
+        _ctx: impl InvokeContext,
+        request: color_control::MoveToSaturationRequest<'_>,
+    ) -> Result<(), Error> {
+        self.send(MatterAttribute::HueSaturation {
+            hue: self.state().hue,
+            saturation: request.saturation()?.min(MAX_LEVEL),
+        })
+    }
+
+    fn handle_move_to_hue_and_saturation(
+        &self,
+        _ctx: impl InvokeContext,
+        request: color_control::MoveToHueAndSaturationRequest<'_>,
+    ) -> Result<(), Error> {
+        self.send(MatterAttribute::HueSaturation {
+            hue: request.hue()?.min(MAX_LEVEL),
+            saturation: request.saturation()?.min(MAX_LEVEL),
+        })
+    }
+
+    fn handle_move_to_color_temperature(
+        &self,
+        _ctx: impl InvokeContext,
+        request: color_control::MoveToColorTemperatureRequest<'_>,
+    ) -> Result<(), Error> {
+        self.send(MatterAttribute::ColorTemperature {
+            mireds: request.color_temperature_mireds()?,
+        })
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
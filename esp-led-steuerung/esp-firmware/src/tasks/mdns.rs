@@ -12,33 +12,39 @@
 // - Adapter: edge-nal-embassy 0.7.0 (embassy-net Integration)
 
 use defmt::{Debug2Format, error, info, warn};
+use embassy_futures::select::{Either, select};
 use embassy_net::Stack;
-use embassy_time::{Duration, Timer};
+use embassy_time::{Duration, Ticker, Timer};
 
+use core::cell::Cell;
 use core::net::{Ipv4Addr, SocketAddr};
-use core::sync::atomic::{AtomicU32, Ordering};
 
+use critical_section::Mutex;
 use edge_mdns::{HostAnswersMdnsHandler, buf::VecBufAccess, domain::base::Ttl, host::Host, io};
 use edge_nal::{MulticastV4, UdpBind, UdpSplit};
 use edge_nal_embassy::{Udp, UdpBuffers};
 use embassy_sync::blocking_mutex::raw::NoopRawMutex;
 use embassy_sync::signal::Signal;
+use esp_core::{ErrorKind, ErrorSource, FirmwareError, TaskId};
 
 use crate::config::{
-    MDNS_HOSTNAME, MDNS_MULTICAST_ADDR, MDNS_PACKET_BUFFER_SIZE, MDNS_PORT,
+    MDNS_HEARTBEAT_SECS, MDNS_HOSTNAME, MDNS_MULTICAST_ADDR, MDNS_PACKET_BUFFER_SIZE, MDNS_PORT,
     MDNS_RECONNECT_DELAY_SECS, MDNS_TTL_SECS, MDNS_UDP_BUFFER_SIZE,
 };
+use crate::task_stats::task_heartbeat;
+use crate::{SystemEventChannel, report_error};
 
-/// Atomischer Counter für Random Number Generator
+/// Counter für den Random Number Generator
 ///
 /// Wird für mDNS Transaction IDs verwendet. Ein einfacher Counter
 /// ist für mDNS ausreichend, da keine kryptographische Sicherheit
-/// benötigt wird.
-static RNG_COUNTER: AtomicU32 = AtomicU32::new(0);
+/// benötigt wird. critical_section::Mutex statt Atomic: riscv32imc
+/// (ESP32-C3) hat kein `fetch_add`.
+static RNG_COUNTER: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
 
 /// Random Number Generator für mDNS
 ///
-/// Generiert Pseudo-Zufallszahlen basierend auf einem atomischen Counter.
+/// Generiert Pseudo-Zufallszahlen basierend auf einem fortlaufenden Counter.
 /// Wird von edge-mdns für Transaction IDs und Query IDs verwendet.
 ///
 /// # Parameter
@@ -48,7 +54,10 @@ static RNG_COUNTER: AtomicU32 = AtomicU32::new(0);
 /// Nutzt einen wrapping counter statt echter Zufallszahlen.
 /// Für mDNS-Protokoll ausreichend, da nur Eindeutigkeit benötigt wird.
 fn mdns_rng(buf: &mut [u8]) {
-    let mut counter = RNG_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let mut counter = critical_section::with(|cs| {
+        let counter = RNG_COUNTER.borrow(cs);
+        counter.replace(counter.get().wrapping_add(1))
+    });
     for chunk in buf.chunks_mut(4) {
         let bytes = counter.to_le_bytes();
         let len = chunk.len().min(4);
@@ -59,7 +68,7 @@ fn mdns_rng(buf: &mut [u8]) {
 
 /// mDNS Responder Task
 ///
-/// Dieser Task advertised den ESP32-C6 via mDNS unter dem Hostnamen
+/// Dieser Task advertised den ESP32 via mDNS unter dem Hostnamen
 /// definiert in `MDNS_HOSTNAME` (konfigurierbar in `src/config.rs`).
 ///
 /// # Funktionsweise
@@ -116,21 +125,30 @@ fn mdns_rng(buf: &mut [u8]) {
 ///
 /// # Parameter
 /// - `stack`: embassy-net Stack für Netzwerk-Operationen (shared mit allen Tasks)
+/// - `events`: System-Ereignisse (Fehler vor jedem Reconnect)
 ///
 /// # Resourcen-Nutzung
 /// - **RAM:** ~4.2 KB (UDP Buffers + mDNS State)
 /// - **Flash:** ~19 KB (edge-mdns Library)
 /// - **Sockets:** 1 UDP Socket (von 13 verfügbaren)
 #[embassy_executor::task]
-pub async fn mdns_responder_task(stack: &'static Stack<'static>) {
+pub async fn mdns_responder_task(
+    stack: &'static Stack<'static>,
+    events: &'static SystemEventChannel,
+) {
     info!("mDNS: Task started, waiting for network...");
     wait_for_network(stack).await;
     info!("mDNS: Network ready");
 
     loop {
+        // edge-mdns läuft blockierend: Lebenszeichen nur pro (Neu-)Start
+        task_heartbeat(TaskId::Mdns);
         match run_mdns_responder(stack).await {
             Ok(_) => warn!("mDNS: Responder stopped normally"),
-            Err(e) => error!("mDNS: Error: {}", Debug2Format(&e)),
+            Err(e) => {
+                error!("mDNS: Error: {}", Debug2Format(&e));
+                report_error(events, e.into());
+            }
         }
         info!("mDNS: Reconnecting in {}s...", MDNS_RECONNECT_DELAY_SECS);
         Timer::after(Duration::from_secs(MDNS_RECONNECT_DELAY_SECS)).await;
@@ -268,9 +286,20 @@ async fn run_mdns_responder(stack: &'static Stack<'static>) -> Result<(), MdnsEr
     // Blocking: Läuft bis Fehler auftritt
     // HostAnswersMdnsHandler implementiert einfache A-Record Responses
     // (nur Hostname → IP, kein Service Discovery)
-    mdns.run(HostAnswersMdnsHandler::new(&host))
-        .await
-        .map_err(|_| MdnsError::ResponderFailed)?;
+    // Daneben eigener Takt als Lebenszeichen für die Stall-Erkennung: edge-mdns
+    // bietet keinen Haken pro Anfrage. Erkannt wird ein nicht mehr gepollter
+    // Task oder ein Hänger beim Aufbau, nicht ein Hänger innerhalb von edge-mdns
+    let responder = mdns.run(HostAnswersMdnsHandler::new(&host));
+    let heartbeat = async {
+        let mut ticker = Ticker::every(Duration::from_secs(MDNS_HEARTBEAT_SECS));
+        loop {
+            ticker.next().await;
+            task_heartbeat(TaskId::Mdns);
+        }
+    };
+    // Der Takt endet nie, nur der Responder kann zurückkehren
+    let Either::First(result) = select(responder, heartbeat).await;
+    result.map_err(|_| MdnsError::ResponderFailed)?;
 
     Ok(())
 }
@@ -304,6 +333,16 @@ enum MdnsError {
     ResponderFailed,
 }
 
+impl From<MdnsError> for FirmwareError {
+    fn from(error: MdnsError) -> Self {
+        let kind = match error {
+            MdnsError::SocketBindFailed | MdnsError::MulticastJoinFailed => ErrorKind::BindFailed,
+            MdnsError::ResponderFailed => ErrorKind::ReceiveFailed,
+        };
+        FirmwareError::new(ErrorSource::Mdns, kind)
+    }
+}
+
 impl defmt::Format for MdnsError {
     fn format(&self, fmt: defmt::Formatter) {
         match self {
@@ -1,17 +1,88 @@
 // Task-Modul: Enthält alle Embassy Tasks
 //
 // Jeder Task läuft asynchron und unabhängig.
-// Tasks kommunizieren über Embassy Channels (LED → MQTT, HTTP ↔ LED).
+// Optionale Subsysteme (BLE, mDNS, MQTT, MQTT-SN, Matter, ESP-NOW, LIFX, SSDP, Bewegungsmelder, Eingänge, UART-Konsole, Log über UDP) sind per Cargo-Feature abschaltbar.
+// Tasks kommunizieren über Embassy Channels (LED → MQTT/MQTT-SN/BLE, MQTT-SN → LED, HTTP ↔ LED, Scheduler → LED, UDP → LED, LED → Sync → LED, CoAP ↔ LED, Konsole (TCP/UART) ↔ LED, OSC → LED, Matter ↔ LED, LIFX ↔ LED, ESP-NOW ↔ LED, Taster → LED, Bewegungsmelder → LED, Eingänge → LED/MQTT, Ereignisse → Status-LED).
 
+#[cfg(feature = "ble")]
+pub mod ble;
+pub mod button;
+pub mod coap;
+pub mod console;
+#[cfg(feature = "espnow")]
+pub mod espnow;
+pub mod heap;
 pub mod http;
+#[cfg(feature = "inputs")]
+pub mod inputs;
 pub mod led_blink;
+#[cfg(feature = "lifx")]
+pub mod lifx;
+#[cfg(feature = "log-udp")]
+pub mod log_udp;
+#[cfg(feature = "matter")]
+pub mod matter;
+#[cfg(feature = "mdns")]
 pub mod mdns;
+#[cfg(feature = "motion")]
+pub mod motion;
+#[cfg(feature = "mqtt")]
 pub mod mqtt;
+#[cfg(feature = "mqtt-sn")]
+pub mod mqtt_sn;
+pub mod osc;
+pub mod realtime;
+pub mod scheduler;
+pub mod sntp;
+#[cfg(feature = "ssdp")]
+pub mod ssdp;
+pub mod status_led;
+pub mod supervisor;
+pub mod sync;
+#[cfg(feature = "uart-console")]
+pub mod uart_console;
+#[cfg(feature = "usb-console")]
+pub mod usb_console;
 pub mod wifi;
 
 // Re-export Tasks für einfachen Import
+#[cfg(feature = "ble")]
+pub use ble::ble_advertise_task;
+pub use button::button_task;
+pub use coap::coap_server_task;
+pub use console::console_task;
+#[cfg(feature = "espnow")]
+pub use espnow::espnow_task;
+pub use heap::heap_monitor_task;
 pub use http::http_server_task;
+#[cfg(feature = "inputs")]
+pub use inputs::inputs_task;
 pub use led_blink::led_blink_task;
+#[cfg(feature = "lifx")]
+pub use lifx::lifx_task;
+#[cfg(feature = "log-udp")]
+pub use log_udp::log_udp_task;
+#[cfg(feature = "matter")]
+pub use matter::matter_task;
+#[cfg(feature = "mdns")]
 pub use mdns::mdns_responder_task;
+#[cfg(feature = "motion")]
+pub use motion::motion_task;
+#[cfg(feature = "mqtt")]
 pub use mqtt::mqtt_task;
+#[cfg(feature = "mqtt-sn")]
+pub use mqtt_sn::mqtt_sn_task;
+pub use osc::osc_task;
+pub use realtime::realtime_udp_task;
+pub use scheduler::scheduler_task;
+pub use sntp::sntp_task;
+#[cfg(feature = "ssdp")]
+pub use ssdp::ssdp_task;
+pub use status_led::status_led_task;
+pub use supervisor::stall_supervisor_task;
+pub use sync::sync_task;
+#[cfg(feature = "uart-console")]
+pub use uart_console::uart_console_task;
+#[cfg(feature = "usb-console")]
+pub use usb_console::usb_console_task;
 pub use wifi::{connection_task, dhcp_task, net_task};
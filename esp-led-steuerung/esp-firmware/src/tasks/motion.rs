@@ -0,0 +1,54 @@
+// Bewegungsmelder Task - schaltet das Licht bei Bewegung ein (PIR an GPIO4)
+//
+// PIR-Module (HC-SR501, AM312) liefern bei Bewegung aktiv high. Der Task
+// wartet per GPIO-Interrupt auf die steigende Flanke; hält der Melder den
+// Pegel länger (anhaltende Bewegung), wird die Haltezeit periodisch
+// verlängert. Logik in esp_core::motion.
+
+use defmt::info;
+use embassy_futures::select::{Either, select};
+use embassy_time::{Duration, Instant, Timer};
+use esp_core::{MotionAction, MotionTrigger};
+use esp_hal::gpio::{Input, InputConfig, Pull};
+
+use crate::LedCommandSender;
+use crate::config::{MOTION_HOLD_MINUTES, MOTION_PRESET_ID, MOTION_RECHECK_SECS};
+
+/// Bewegungsmelder Task - läuft parallel zu anderen Tasks
+///
+/// # Parameter
+/// - `gpio4`: GPIO4 Peripheral (Ausgang des PIR-Moduls)
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn motion_task(
+    gpio4: esp_hal::peripherals::GPIO4<'static>,
+    command_sender: LedCommandSender,
+) {
+    // Pull-down: ohne angeschlossenes Modul keine Fehlauslösung
+    let mut sensor = Input::new(gpio4, InputConfig::default().with_pull(Pull::Down));
+    let mut trigger = MotionTrigger::new(MOTION_PRESET_ID, MOTION_HOLD_MINUTES);
+    info!(
+        "Motion: Task started (preset {}, {} min)",
+        MOTION_PRESET_ID, MOTION_HOLD_MINUTES
+    );
+
+    loop {
+        let recheck = Timer::after(Duration::from_secs(MOTION_RECHECK_SECS));
+        // Ohne Flanke zählt nur ein weiterhin aktiver Melder
+        if let Either::Second(()) = select(sensor.wait_for_rising_edge(), recheck).await
+            && sensor.is_low()
+        {
+            continue;
+        }
+        let Some(action) = trigger.motion(Instant::now().as_millis()) else {
+            continue;
+        };
+        match action {
+            MotionAction::SwitchOn => info!("Motion: Detected, switching on"),
+            MotionAction::Extend => info!("Motion: Still active, extending hold time"),
+        }
+        for command in trigger.commands(action) {
+            command_sender.send(command).await;
+        }
+    }
+}
@@ -1,7 +1,29 @@
-// MQTT Task - Published LED-Farben an MQTT Broker
+// MQTT Task - Published LED-Farben an MQTT Broker und empfängt Text-Kommandos
+
 use defmt::{Debug2Format, error, info, warn};
+use embassy_futures::select::{Either, Either3, Either4, select, select3, select4};
 use embassy_net::{IpAddress, Stack, dns::DnsQueryType, tcp::TcpSocket};
-use embassy_time::{Duration, Timer, with_timeout};
+use embassy_time::{Duration, Instant, Ticker, Timer, with_timeout};
+use esp_core::broker::fallback_brokers;
+use esp_core::ha_discovery::{write_discovery_config, write_discovery_topic};
+use esp_core::mqtt::{
+    FACTORY_RESET_PAYLOAD, RESTART_PAYLOAD, color_payload, mode_payload, split_command_id,
+    system_action_confirmed, write_birth_payload, write_command_ack, write_health_payload,
+    write_heap_payload, write_input_topic, write_state_payload, write_tasmota_result,
+    write_tasmota_topic,
+};
+use esp_core::tasmota::{
+    TASMOTA_BUSY_RESULT, TASMOTA_COMMAND_PREFIX, TASMOTA_STAT_PREFIX, power_state,
+    tasmota_command_name,
+};
+use esp_core::{
+    BirthInfo, BrokerAddress, BrokerRotation, CommandAck, ErrorKind, ErrorSource, FirmwareError,
+    HaDevice, HaSensor, HealthInfo, LedColorMessage, NetCounter, OfflineChange, OfflineQueue,
+    PowerAction, PublishThrottle, RebootSource, SpanKind, TaskId, TasmotaCommand, classify_color,
+    parse_tasmota_command, parse_text_command,
+};
+use heapless::{String, Vec};
+use rgb::RGB8;
 
 use rust_mqtt::client::client::MqttClient;
 use rust_mqtt::client::client_config::{ClientConfig, MqttVersion};
@@ -9,37 +31,178 @@ use rust_mqtt::packet::v5::publish_packet::QualityOfService;
 use rust_mqtt::utils::rng_generator::CountingRng;
 use rust_mqtt::utils::types::EncodedString;
 
-use crate::LedColorSubscriber;
 use crate::config::*;
+use crate::device_health::{chip_temperature, rssi};
+use crate::dns_cache::{cached_broker_address, invalidate_broker_address, store_broker_address};
+use crate::heap_stats::heap_stats;
+use crate::net_stats::record_net;
+use crate::reboot::{request_factory_reset, request_reboot};
+use crate::task_stats::task_heartbeat;
+use crate::tcp_tuning::apply_socket_tuning;
+use crate::trace::span;
+use crate::web::protocol::VersionInfo;
+use crate::{
+    LedColorSubscriber, LedCommand, LedCommandSender, SystemEvent, SystemEventChannel,
+    SystemEventSubscriber, publish_event, report_error,
+};
 
 /// MQTT Task - läuft parallel zu anderen Tasks
 ///
 /// Dieser Task übernimmt das MQTT-Publishing:
 /// - Wartet auf Netzwerk-Verbindung
 /// - Verbindet sich mit MQTT Broker
+/// - Published Birth-Message mit Firmware-Version (retained)
+/// - Published Home-Assistant-Discovery für die Diagnose-Sensoren (retained)
 /// - Empfängt LED-Farb-Updates via Channel
 /// - Published Farbnamen **sofort bei Änderung** (event-basiert)
-/// - Automatisches Reconnect bei Fehlern
+/// - Empfängt Text-Kommandos auf MQTT_TOPIC_COMMAND und leitet sie an den LED-Task
+/// - Optional: zusätzlich Text-Kommandos auf dem Gruppen-Topic MQTT_TOPIC_GROUP
+/// - Quittiert jedes Text-Kommando auf MQTT_TOPIC_COMMAND_RESULT (ok bzw. Fehlergrund)
+/// - Published Heap-Telemetrie alle HEAP_TELEMETRY_INTERVAL_SECS auf MQTT_TOPIC_HEAP
+///   und Diagnose-Werte (RSSI, Laufzeit, Heap, Temperatur) auf MQTT_TOPIC_HEALTH
+/// - Optional: Tasmota-kompatible Topics (`cmnd/<topic>/POWER`, `Color`, `stat/...`)
+/// - Neustart auf MQTT_TOPIC_RESTART, Werksreset auf MQTT_TOPIC_FACTORY_RESET,
+///   trennt vor dem Neustart sauber
+/// - Automatisches Reconnect bei Fehlern, Zustandsänderungen während des
+///   Ausfalls werden gepuffert und nach dem Reconnect nachgeholt
 ///
 /// # Parameter
 /// - `stack`: embassy-net Stack für Netzwerk-Zugriff
 /// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+/// - `events`: System-Ereignisse (MqttConnected/MqttDisconnected, Broker-Wechsel, Fehler)
+/// - `event_subscriber`: System-Ereignisse zum Publishen (Zustand der digitalen Eingänge, Neustart)
 #[embassy_executor::task]
-pub async fn mqtt_task(stack: &'static Stack<'static>, mut color_subscriber: LedColorSubscriber) {
+pub async fn mqtt_task(
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+    events: &'static SystemEventChannel,
+    mut event_subscriber: SystemEventSubscriber,
+) {
     info!("MQTT: Task started, waiting for network...");
     wait_for_network(stack).await;
     info!("MQTT: Network ready");
 
+    // LED-Zustand für Tasmota-Kommandos, bleibt über Reconnects erhalten
+    let mut tasmota = TasmotaState::new();
+
+    // Aktiver Broker (primär + Fallbacks), bleibt über Reconnects erhalten
+    let mut brokers = BrokerRotation::new(broker_count(), MQTT_BROKER_SWITCH_FAILURES);
+
+    // Zustandsänderungen ohne Verbindung, bleiben bis zum nächsten Connect erhalten
+    let mut offline: OfflineQueue<MQTT_OFFLINE_QUEUE_LEN> = OfflineQueue::new();
+
+    // Im Auto-Modus gedrosselte, noch nicht gesendete Farbe der Verbindung
+    let mut held: Option<LedColorMessage> = None;
+
     loop {
-        match mqtt_connect_and_publish(stack, &mut color_subscriber).await {
+        // Lebenszeichen pro Verbindungsversuch (DNS/TCP mit Timeout, danach Telemetrie-Takt)
+        task_heartbeat(TaskId::Mqtt);
+        let mut switched = None;
+        match mqtt_connect_and_publish(
+            stack,
+            broker_address(brokers.index()),
+            &mut brokers,
+            &mut color_subscriber,
+            command_sender,
+            &mut tasmota,
+            &mut offline,
+            &mut held,
+            events,
+            &mut event_subscriber,
+        )
+        .await
+        {
             Ok(_) => warn!("MQTT: Connection closed normally"),
-            Err(e) => error!("MQTT: Error: {}", Debug2Format(&e)),
+            Err(e) => {
+                error!("MQTT: Error: {}", Debug2Format(&e));
+                report_error(events, e.into());
+                switched = brokers.failed();
+            }
+        }
+        // Zurückgehaltene Farbe beim nächsten Connect nachholen
+        if let Some(msg) = held.take() {
+            offline.push(OfflineChange::Color(msg));
+        }
+        publish_event(events, SystemEvent::MqttDisconnected);
+        record_net(NetCounter::MqttReconnects);
+        if let Some(index) = switched {
+            warn!(
+                "MQTT: Switching to broker {}/{} '{}'",
+                index + 1,
+                brokers.count(),
+                broker_address(index).host
+            );
+            // Gecachte Adresse gehört zum bisherigen Broker
+            invalidate_broker_address();
+            publish_event(
+                events,
+                SystemEvent::MqttBrokerSwitched { index: index as u8 },
+            );
         }
         info!("MQTT: Reconnecting in {}s...", MQTT_RECONNECT_DELAY_SECS);
-        Timer::after(Duration::from_secs(MQTT_RECONNECT_DELAY_SECS)).await;
+        buffer_offline(
+            Duration::from_secs(MQTT_RECONNECT_DELAY_SECS),
+            &mut color_subscriber,
+            &mut event_subscriber,
+            &mut tasmota,
+            &mut offline,
+        )
+        .await;
+    }
+}
+
+/// Puffert Zustandsänderungen, solange keine Verbindung besteht
+///
+/// Ersetzt die Wartezeit vor dem Reconnect: Farb-Updates und Eingangswechsel
+/// landen in der `OfflineQueue` statt in den Subscriber-Queues überzulaufen.
+async fn buffer_offline(
+    delay: Duration,
+    color_subscriber: &mut LedColorSubscriber,
+    event_subscriber: &mut SystemEventSubscriber,
+    tasmota: &mut TasmotaState,
+    offline: &mut OfflineQueue<MQTT_OFFLINE_QUEUE_LEN>,
+) {
+    let deadline = Instant::now() + delay;
+    loop {
+        match select3(
+            Timer::at(deadline),
+            color_subscriber.next_message_pure(),
+            event_subscriber.next_message_pure(),
+        )
+        .await
+        {
+            Either3::First(()) => return,
+            Either3::Second(msg) => {
+                tasmota.update(msg.color);
+                offline.push(OfflineChange::Color(msg));
+            }
+            Either3::Third(SystemEvent::InputChanged { input, active }) => {
+                offline.push(OfflineChange::Input { input, active });
+            }
+            // Neustart ohne Verbindung: nichts zu trennen
+            Either3::Third(_) => {}
+        }
     }
 }
 
+/// Anzahl der Broker (primärer Broker plus gültige Fallbacks)
+fn broker_count() -> usize {
+    1 + fallback_brokers(MQTT_BROKER_FALLBACKS.unwrap_or("")).count()
+}
+
+/// Broker zum Index der Broker-Liste (0 = `MQTT_BROKER`)
+fn broker_address(index: usize) -> BrokerAddress<'static> {
+    index
+        .checked_sub(1)
+        .and_then(|fallback| fallback_brokers(MQTT_BROKER_FALLBACKS.unwrap_or("")).nth(fallback))
+        .unwrap_or(BrokerAddress {
+            host: MQTT_BROKER,
+            port: None,
+        })
+}
+
 /// Wartet bis Netzwerk-Verbindung verfügbar ist
 ///
 /// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
@@ -54,38 +217,94 @@ async fn wait_for_network(stack: &'static Stack<'static>) {
     }
 }
 
+/// Löst Hostname zu IPv4-Adresse auf
+///
+/// Nutzt embassy-net DNS-Stack mit konfigurierbarem Timeout.
+async fn resolve_hostname(
+    stack: &'static Stack<'static>,
+    hostname: &str,
+) -> Result<embassy_net::Ipv4Address, MqttError> {
+    let result = with_timeout(
+        Duration::from_secs(DNS_TIMEOUT_SECS),
+        stack.dns_query(hostname, DnsQueryType::A),
+    )
+    .await;
+    match result {
+        Ok(Ok(addrs)) => {
+            for addr in addrs {
+                if let IpAddress::Ipv4(ipv4) = addr {
+                    return Ok(ipv4);
+                }
+            }
+            Err(MqttError::DnsResolutionFailed)
+        }
+        Ok(Err(_)) => Err(MqttError::DnsResolutionFailed),
+        Err(_) => Err(MqttError::DnsTimeout),
+    }
+}
+
 /// Verbindet mit MQTT Broker und published Farb-Updates
 ///
 /// Diese Funktion übernimmt den kompletten MQTT-Lifecycle:
-/// 1. DNS-Auflösung des Broker-Hostnames
+/// 1. DNS-Auflösung des Broker-Hostnames (nur ohne gültigen Cache-Eintrag)
 /// 2. TCP-Verbindung aufbauen
 /// 3. MQTT CONNECT senden
-/// 4. Farb-Updates empfangen und periodisch publishen
+/// 4. Birth-Message mit Firmware-Version publishen
+/// 5. Home-Assistant-Discovery für die Diagnose-Sensoren publishen
+/// 6. Kommando-Topic abonnieren (plus Gruppen-Topic und `cmnd/<topic>/+` im Tasmota-Modus)
+/// 7. Während des Ausfalls gepufferte Zustandsänderungen nachholen
+/// 8. Farb-Updates publishen und Kommandos empfangen
 ///
 /// Bei jedem Fehler wird die Funktion beendet und der Haupt-Loop
 /// startet automatisch einen Reconnect-Versuch.
 async fn mqtt_connect_and_publish(
     stack: &'static Stack<'static>,
+    broker: BrokerAddress<'static>,
+    brokers: &mut BrokerRotation,
     color_subscriber: &mut LedColorSubscriber,
+    command_sender: LedCommandSender,
+    tasmota: &mut TasmotaState,
+    offline: &mut OfflineQueue<MQTT_OFFLINE_QUEUE_LEN>,
+    held: &mut Option<LedColorMessage>,
+    events: &'static SystemEventChannel,
+    event_subscriber: &mut SystemEventSubscriber,
 ) -> Result<(), MqttError> {
-    // DNS Lookup
-    info!("MQTT: Resolving '{}'...", MQTT_BROKER);
-    let broker_ip = resolve_hostname(stack, MQTT_BROKER).await?;
-    info!("MQTT: Resolved to {}", Debug2Format(&broker_ip));
+    // DNS Lookup (gecachte Adresse bis TTL-Ablauf oder Verbindungsfehler)
+    let broker_ip = match cached_broker_address() {
+        Some(octets) => {
+            let broker_ip = embassy_net::Ipv4Address::from(octets);
+            info!("MQTT: Using cached address {}", Debug2Format(&broker_ip));
+            broker_ip
+        }
+        None => {
+            info!("MQTT: Resolving '{}'...", broker.host);
+            let broker_ip = resolve_hostname(stack, broker.host).await?;
+            info!("MQTT: Resolved to {}", Debug2Format(&broker_ip));
+            store_broker_address(broker_ip.octets());
+            broker_ip
+        }
+    };
 
     // TCP Connect
     let mut rx_buffer = [0u8; 4096];
     let mut tx_buffer = [0u8; 4096];
     let mut socket = TcpSocket::new(*stack, &mut rx_buffer, &mut tx_buffer);
-    socket.set_timeout(Some(Duration::from_secs(10)));
+    apply_socket_tuning(&mut socket);
 
     socket
-        .connect((broker_ip, MQTT_PORT))
+        .connect((broker_ip, broker.port.unwrap_or(MQTT_PORT)))
         .await
-        .map_err(|_| MqttError::ConnectionFailed)?;
+        .map_err(|_| {
+            // Adresse evtl. veraltet: beim nächsten Versuch neu auflösen
+            invalidate_broker_address();
+            MqttError::ConnectionFailed
+        })?;
     info!("MQTT: TCP connected");
 
     // MQTT Client Configuration
+    // Stabile Client-ID, aber Clean Start ist in rust-mqtt 0.3 fest gesetzt
+    // (Connect-Flags 0x02): persistente Sitzungen sind bewusst nicht umgesetzt,
+    // Abos werden unten nach jedem Connect erneuert
     let rng = CountingRng(20000);
     let mut config = ClientConfig::<5, _>::new(MqttVersion::MQTTv5, rng);
     config.client_id = EncodedString {
@@ -114,73 +333,581 @@ async fn mqtt_connect_and_publish(
         .connect_to_broker()
         .await
         .map_err(|_| MqttError::ProtocolError)?;
-    info!("MQTT: Connected to broker");
+    record_net(NetCounter::MqttAcks);
+    info!("MQTT: Connected to broker '{}'", broker.host);
+    brokers.connected();
+    publish_event(events, SystemEvent::MqttConnected);
 
-    // Publish Loop - Event-basiert
-    // Wartet blockierend auf neue Farb-Updates und published diese sofort
-    loop {
-        // Warte auf neue Farbe (blockiert bis Broadcast kommt)
-        let msg = color_subscriber.next_message_pure().await;
-
-        let mode_str = if msg.is_auto_mode { "Auto" } else { "Manuell" };
-        info!(
-            "MQTT: Color changed to '{}' ({}), publishing...",
-            msg.name, mode_str
-        );
+    // Birth-Message: Firmware-Version retained publishen
+    // Damit ist am Broker sichtbar, welche Geräte noch alte Firmware nutzen
+    let info = VersionInfo::current();
+    let birth = BirthInfo {
+        client_id: MQTT_CLIENT_ID,
+        version: info.version,
+        git_hash: info.git_hash,
+        build_timestamp: info.build_timestamp,
+    };
+    let mut birth_payload: String<MQTT_BIRTH_BUFFER_SIZE> = String::new();
+    write_birth_payload(&mut birth_payload, &birth).map_err(|_| MqttError::PublishFailed)?;
+    client
+        .send_message(
+            MQTT_TOPIC_INFO,
+            birth_payload.as_bytes(),
+            QualityOfService::QoS0,
+            true,
+        )
+        .await
+        .map_err(|_| MqttError::PublishFailed)?;
+    record_net(NetCounter::MqttPublishes);
+    info!(
+        "MQTT: Published birth message (v{}, {})",
+        info.version, info.git_hash
+    );
 
-        // Publishe Farbe auf erstes Topic
+    // Home-Assistant-Discovery: Sensoren erscheinen ohne manuelle Konfiguration
+    let device = HaDevice {
+        node_id: MQTT_CLIENT_ID,
+        name: HA_DEVICE_NAME,
+        model: HA_DEVICE_MODEL,
+        sw_version: info.version,
+        state_topic: MQTT_TOPIC_HEALTH,
+    };
+    for sensor in HaSensor::ALL {
+        let mut topic: String<MQTT_HA_TOPIC_LEN> = String::new();
+        write_discovery_topic(&mut topic, HA_DISCOVERY_PREFIX, MQTT_CLIENT_ID, sensor)
+            .map_err(|_| MqttError::TopicTooLong)?;
+        let mut payload: String<MQTT_HA_CONFIG_BUFFER_SIZE> = String::new();
+        write_discovery_config(&mut payload, &device, sensor)
+            .map_err(|_| MqttError::PublishFailed)?;
         client
-            .send_message(
-                MQTT_TOPIC_COLOR,
-                msg.name.as_bytes(),
-                QualityOfService::QoS0,
-                false,
-            )
+            .send_message(&topic, payload.as_bytes(), QualityOfService::QoS0, true)
             .await
             .map_err(|_| MqttError::PublishFailed)?;
+        record_net(NetCounter::MqttPublishes);
+    }
+    info!(
+        "MQTT: Published Home Assistant discovery ({} sensors)",
+        HaSensor::ALL.len()
+    );
+
+    // Kommando-Topic abonnieren
+    client
+        .subscribe_to_topic(MQTT_TOPIC_COMMAND)
+        .await
+        .map_err(|_| MqttError::SubscribeFailed)?;
+    record_net(NetCounter::MqttAcks);
+    info!("MQTT: Subscribed to '{}'", MQTT_TOPIC_COMMAND);
+
+    // Neustart- und Werksreset-Topic
+    client
+        .subscribe_to_topic(MQTT_TOPIC_RESTART)
+        .await
+        .map_err(|_| MqttError::SubscribeFailed)?;
+    record_net(NetCounter::MqttAcks);
+    info!("MQTT: Subscribed to '{}'", MQTT_TOPIC_RESTART);
+    client
+        .subscribe_to_topic(MQTT_TOPIC_FACTORY_RESET)
+        .await
+        .map_err(|_| MqttError::SubscribeFailed)?;
+    record_net(NetCounter::MqttAcks);
+    info!("MQTT: Subscribed to '{}'", MQTT_TOPIC_FACTORY_RESET);
 
-        // Publishe Modus auf zweites Topic
+    // Gruppen-Topic: gleiche Kommandos für mehrere Geräte
+    if let Some(group_topic) = MQTT_TOPIC_GROUP {
         client
-            .send_message(
-                MQTT_TOPIC_MODE,
-                mode_str.as_bytes(),
-                QualityOfService::QoS0,
-                false,
-            )
+            .subscribe_to_topic(group_topic)
             .await
-            .map_err(|_| MqttError::PublishFailed)?;
+            .map_err(|_| MqttError::SubscribeFailed)?;
+        record_net(NetCounter::MqttAcks);
+        info!("MQTT: Subscribed to group '{}'", group_topic);
+    }
 
-        info!("MQTT: Published color='{}' mode='{}'", msg.name, mode_str);
+    // Tasmota-Modus: alle Kommandos des Geräte-Topics abonnieren
+    if let Some(device_topic) = MQTT_TASMOTA_TOPIC {
+        let topic = tasmota_topic(TASMOTA_COMMAND_PREFIX, device_topic, "+")?;
+        client
+            .subscribe_to_topic(&topic)
+            .await
+            .map_err(|_| MqttError::SubscribeFailed)?;
+        record_net(NetCounter::MqttAcks);
+        info!("MQTT: Tasmota mode, subscribed to '{}'", topic.as_str());
     }
-}
 
-/// Löst Hostname zu IPv4-Adresse auf
-///
-/// Nutzt embassy-net DNS-Stack mit konfigurierbarem Timeout.
-async fn resolve_hostname(
-    stack: &'static Stack<'static>,
-    hostname: &str,
-) -> Result<embassy_net::Ipv4Address, MqttError> {
-    let result = with_timeout(
-        Duration::from_secs(DNS_TIMEOUT_SECS),
-        stack.dns_query(hostname, DnsQueryType::A),
-    )
-    .await;
+    // Farbmeldungen im Auto-Modus höchstens alle MQTT_AUTO_PUBLISH_SECS,
+    // dazwischen wird nur die neueste zurückgehalten (`held`, bei Abbruch in die Offline-Queue)
+    let mut throttle = PublishThrottle::new(u64::from(MQTT_AUTO_PUBLISH_SECS) * 1000);
 
-    match result {
-        Ok(Ok(addrs)) => {
-            for addr in addrs {
-                if let IpAddress::Ipv4(ipv4) = addr {
-                    return Ok(ipv4);
+    // Während des Ausfalls verpasste Änderungen in Originalreihenfolge nachholen.
+    // Ein Eintrag wird erst nach erfolgreichem Publish entfernt. Die Queue fasst
+    // Auto-Farben schon zusammen, nachgeholte Farben starten das Drosselintervall
+    let dropped = offline.take_dropped();
+    if !offline.is_empty() || dropped > 0 {
+        info!(
+            "MQTT: Flushing {} offline changes ({} dropped)",
+            offline.len(),
+            dropped
+        );
+    }
+    while let Some(change) = offline.front().copied() {
+        match change {
+            OfflineChange::Color(msg) => {
+                publish_color(&mut client, &msg).await?;
+                throttle.published(Instant::now().as_millis());
+            }
+            OfflineChange::Input { input, active } => {
+                publish_input(&mut client, input, active).await?
+            }
+        }
+        offline.pop();
+    }
+
+    // Heap-Telemetrie im festen Takt, unabhängig von Farb-Updates
+    let mut heap_ticker = Ticker::every(Duration::from_secs(HEAP_TELEMETRY_INTERVAL_SECS));
+
+    // Event Loop
+    // Wartet gleichzeitig auf eingehende Kommandos, neue Farb-Updates, den Telemetrie-Takt
+    // und System-Ereignisse
+    loop {
+        task_heartbeat(TaskId::Mqtt);
+        let msg = match select4(
+            client.receive_message(),
+            select(
+                color_subscriber.next_message_pure(),
+                publish_deadline(throttle.deadline_ms()),
+            ),
+            heap_ticker.next(),
+            event_subscriber.next_message_pure(),
+        )
+        .await
+        {
+            // Kommando vom Broker empfangen
+            Either4::First(received) => {
+                let (topic, payload) = received.map_err(|_| MqttError::ReceiveFailed)?;
+                record_net(NetCounter::MqttReceived);
+                let _span = span(SpanKind::MqttCommand);
+                let text = core::str::from_utf8(payload).unwrap_or("");
+
+                // Neustart bzw. Werksreset nur mit Bestätigungswort. Eine retained
+                // Anforderung wird vorher gelöscht, sonst käme sie nach jedem Boot wieder
+                if topic == MQTT_TOPIC_RESTART {
+                    if !system_action_confirmed(payload, RESTART_PAYLOAD) {
+                        warn!(
+                            "MQTT: Restart ignored, payload must be '{}'",
+                            RESTART_PAYLOAD
+                        );
+                        continue;
+                    }
+                    clear_retained(&mut client, MQTT_TOPIC_RESTART).await?;
+                    info!("MQTT: Restart requested");
+                    request_reboot(RebootSource::Mqtt);
+                    continue;
+                }
+                if topic == MQTT_TOPIC_FACTORY_RESET {
+                    if !system_action_confirmed(payload, FACTORY_RESET_PAYLOAD) {
+                        warn!(
+                            "MQTT: Factory reset ignored, payload must be '{}'",
+                            FACTORY_RESET_PAYLOAD
+                        );
+                        continue;
+                    }
+                    clear_retained(&mut client, MQTT_TOPIC_FACTORY_RESET).await?;
+                    warn!("MQTT: Factory reset requested");
+                    request_factory_reset(RebootSource::Mqtt);
+                    continue;
+                }
+
+                // Tasmota-Kommando (cmnd/<topic>/<Kommando>)
+                let tasmota_name = MQTT_TASMOTA_TOPIC
+                    .and_then(|device_topic| tasmota_command_name(topic, device_topic));
+                if let Some(name) = tasmota_name {
+                    let command = match parse_tasmota_command(name, text) {
+                        Ok(command) => command,
+                        Err(e) => {
+                            warn!(
+                                "MQTT: Invalid Tasmota command '{}': {}",
+                                name,
+                                Debug2Format(&e)
+                            );
+                            continue;
+                        }
+                    };
+                    info!("MQTT: Received Tasmota command {} '{}'", name, text);
+                    match tasmota.led_command(command) {
+                        // Zustand wird nach der Änderung vom LED-Task gemeldet,
+                        // volle Warteschlange: Kommando verwerfen statt den Empfang zu blockieren
+                        Some(led_command) => {
+                            if !command_sender.try_send(led_command).is_accepted() {
+                                warn!("MQTT: LED queue full, Tasmota command {} dropped", name);
+                                if let Some((topic, payload)) = tasmota_busy_message()? {
+                                    client
+                                        .send_message(
+                                            &topic,
+                                            payload.as_bytes(),
+                                            QualityOfService::QoS0,
+                                            false,
+                                        )
+                                        .await
+                                        .map_err(|_| MqttError::PublishFailed)?;
+                                    record_net(NetCounter::MqttPublishes);
+                                }
+                            }
+                        }
+                        // Abfrage oder keine Änderung: Zustand sofort melden
+                        None => {
+                            for (topic, payload) in tasmota_stat_messages(tasmota.current)? {
+                                client
+                                    .send_message(
+                                        &topic,
+                                        payload.as_bytes(),
+                                        QualityOfService::QoS0,
+                                        false,
+                                    )
+                                    .await
+                                    .map_err(|_| MqttError::PublishFailed)?;
+                                record_net(NetCounter::MqttPublishes);
+                            }
+                        }
+                    }
+                    continue;
+                }
+
+                // Eigenes Topic und Gruppen-Topic akzeptieren dieselben Text-Kommandos,
+                // optional mit Anfrage-ID für die Quittung ("@42 rot")
+                let (request_id, text) = split_command_id(text);
+                let ack = match parse_text_command(text) {
+                    Ok(command) => {
+                        info!("MQTT: Received command '{}' on '{}'", text, topic);
+                        if command_sender.try_send(command).is_accepted() {
+                            CommandAck::Applied
+                        } else {
+                            CommandAck::Busy
+                        }
+                    }
+                    Err(e) => {
+                        warn!("MQTT: Invalid command '{}': {}", text, Debug2Format(&e));
+                        CommandAck::Invalid(e)
+                    }
+                };
+
+                // Quittung, damit Automationen wissen, ob das Kommando angekommen ist
+                let mut ack_payload: String<MQTT_COMMAND_ACK_BUFFER_SIZE> = String::new();
+                write_command_ack(&mut ack_payload, request_id, text, ack)
+                    .map_err(|_| MqttError::PublishFailed)?;
+                client
+                    .send_message(
+                        MQTT_TOPIC_COMMAND_RESULT,
+                        ack_payload.as_bytes(),
+                        QualityOfService::QoS0,
+                        false,
+                    )
+                    .await
+                    .map_err(|_| MqttError::PublishFailed)?;
+                record_net(NetCounter::MqttPublishes);
+                continue;
+            }
+            // Neue Farbe vom LED-Task
+            Either4::Second(Either::First(msg)) => {
+                tasmota.update(msg.color);
+                if throttle.offer(msg.is_auto_mode, Instant::now().as_millis()) {
+                    *held = None;
+                    msg
+                } else {
+                    *held = Some(msg);
+                    continue;
                 }
             }
-            Err(MqttError::DnsResolutionFailed)
+            // Zurückgehaltene Farbe ist fällig
+            Either4::Second(Either::Second(())) => match held.take() {
+                Some(msg) if throttle.take_due(Instant::now().as_millis()) => msg,
+                other => {
+                    *held = other;
+                    continue;
+                }
+            },
+            // Heap-Telemetrie
+            Either4::Third(()) => {
+                let heap = heap_stats();
+                let mut heap_payload: String<MQTT_HEAP_BUFFER_SIZE> = String::new();
+                write_heap_payload(&mut heap_payload, &heap)
+                    .map_err(|_| MqttError::PublishFailed)?;
+                client
+                    .send_message(
+                        MQTT_TOPIC_HEAP,
+                        heap_payload.as_bytes(),
+                        QualityOfService::QoS0,
+                        false,
+                    )
+                    .await
+                    .map_err(|_| MqttError::PublishFailed)?;
+                record_net(NetCounter::MqttPublishes);
+
+                // Diagnose-Werte für die Home-Assistant-Sensoren
+                let health = HealthInfo {
+                    rssi: rssi(),
+                    uptime: Instant::now().as_secs(),
+                    heap_free: heap.free,
+                    temperature: chip_temperature(),
+                    broker: broker.host,
+                };
+                let mut health_payload: String<MQTT_HEALTH_BUFFER_SIZE> = String::new();
+                write_health_payload(&mut health_payload, &health)
+                    .map_err(|_| MqttError::PublishFailed)?;
+                client
+                    .send_message(
+                        MQTT_TOPIC_HEALTH,
+                        health_payload.as_bytes(),
+                        QualityOfService::QoS0,
+                        false,
+                    )
+                    .await
+                    .map_err(|_| MqttError::PublishFailed)?;
+                record_net(NetCounter::MqttPublishes);
+                continue;
+            }
+            // Zustand eines digitalen Eingangs (retained, z.B. ".../input/door" = "open")
+            Either4::Fourth(SystemEvent::InputChanged { input, active }) => {
+                publish_input(&mut client, input, active).await?;
+                continue;
+            }
+            // Vor dem Neustart sauber vom Broker trennen, statt auf den Keep-Alive-Timeout zu warten
+            Either4::Fourth(SystemEvent::RebootRequested { .. }) => {
+                info!("MQTT: Disconnecting before reboot");
+                let _ = client.disconnect().await;
+                return Ok(());
+            }
+            // Übrige Ereignisse betreffen MQTT nicht
+            Either4::Fourth(_) => continue,
+        };
+        publish_color(&mut client, &msg).await?;
+    }
+}
+
+/// Aktiver MQTT-Client einer Verbindung
+type Client<'a, 's> = MqttClient<'a, TcpSocket<'s>, 5, CountingRng>;
+
+/// Published Farbe, Modus, Zustand und ggf. Tasmota stat-Topics
+async fn publish_color(
+    client: &mut Client<'_, '_>,
+    msg: &LedColorMessage,
+) -> Result<(), MqttError> {
+    let _span = span(SpanKind::MqttPublish);
+
+    // String-Topics in der konfigurierten Sprache (UI_LANGUAGE)
+    let color_label = color_payload(UI_LANGUAGE, msg);
+    let color_str = color_label.as_str();
+    let mode_str = mode_payload(UI_LANGUAGE, msg);
+    info!(
+        "MQTT: Color changed to '{}' ({}), publishing...",
+        color_str, mode_str
+    );
+
+    // Publishe Farbe auf erstes Topic
+    client
+        .send_message(
+            MQTT_TOPIC_COLOR,
+            color_str.as_bytes(),
+            QualityOfService::QoS0,
+            false,
+        )
+        .await
+        .map_err(|_| MqttError::PublishFailed)?;
+    record_net(NetCounter::MqttPublishes);
+
+    // Publishe Modus auf zweites Topic
+    client
+        .send_message(
+            MQTT_TOPIC_MODE,
+            mode_str.as_bytes(),
+            QualityOfService::QoS0,
+            false,
+        )
+        .await
+        .map_err(|_| MqttError::PublishFailed)?;
+    record_net(NetCounter::MqttPublishes);
+
+    // Zustand als JSON mit Sequenznummer (Lücken = verpasste Updates)
+    let mut state_payload: String<MQTT_STATE_BUFFER_SIZE> = String::new();
+    write_state_payload(&mut state_payload, msg).map_err(|_| MqttError::PublishFailed)?;
+    client
+        .send_message(
+            MQTT_TOPIC_STATE,
+            state_payload.as_bytes(),
+            QualityOfService::QoS0,
+            false,
+        )
+        .await
+        .map_err(|_| MqttError::PublishFailed)?;
+    record_net(NetCounter::MqttPublishes);
+
+    info!(
+        "MQTT: Published color='{}' mode='{}' seq={}",
+        color_str, mode_str, msg.sequence
+    );
+
+    // Tasmota stat-Topics
+    for (topic, payload) in tasmota_stat_messages(msg.color)? {
+        client
+            .send_message(&topic, payload.as_bytes(), QualityOfService::QoS0, false)
+            .await
+            .map_err(|_| MqttError::PublishFailed)?;
+        record_net(NetCounter::MqttPublishes);
+    }
+    Ok(())
+}
+
+/// Published den Zustand eines digitalen Eingangs (retained, z.B. ".../input/door" = "open")
+async fn publish_input(
+    client: &mut Client<'_, '_>,
+    input: u8,
+    active: bool,
+) -> Result<(), MqttError> {
+    let Some(config) = DIGITAL_INPUTS.get(usize::from(input)) else {
+        return Ok(());
+    };
+    let mut topic: String<MQTT_INPUT_TOPIC_LEN> = String::new();
+    write_input_topic(&mut topic, MQTT_TOPIC_INPUTS, config.name)
+        .map_err(|_| MqttError::TopicTooLong)?;
+    client
+        .send_message(
+            &topic,
+            config.state_name(active).as_bytes(),
+            QualityOfService::QoS0,
+            true,
+        )
+        .await
+        .map_err(|_| MqttError::PublishFailed)?;
+    record_net(NetCounter::MqttPublishes);
+    Ok(())
+}
+
+/// Löscht eine retained Nachricht auf `topic` (leerer retained Publish)
+///
+/// rust-mqtt 0.3 meldet das Retain-Flag empfangener Nachrichten nicht, eine
+/// retained Systemaktion ist also nicht von einer neuen zu unterscheiden.
+async fn clear_retained(client: &mut Client<'_, '_>, topic: &str) -> Result<(), MqttError> {
+    client
+        .send_message(topic, b"", QualityOfService::QoS0, true)
+        .await
+        .map_err(|_| MqttError::PublishFailed)?;
+    record_net(NetCounter::MqttPublishes);
+    Ok(())
+}
+
+/// Wartet bis zur Frist der zurückgehaltenen Farbmeldung (ohne Frist nie)
+async fn publish_deadline(deadline_ms: Option<u64>) {
+    match deadline_ms {
+        Some(deadline_ms) => Timer::at(Instant::from_millis(deadline_ms)).await,
+        None => core::future::pending().await,
+    }
+}
+
+// ============================================================================
+// Tasmota-Kompatibilität
+// ============================================================================
+
+/// LED-Zustand für POWER ON/TOGGLE und Abfragen
+struct TasmotaState {
+    /// Aktuelle LED-Farbe
+    current: RGB8,
+    /// Letzte Farbe ungleich Schwarz (wird bei POWER ON wiederhergestellt)
+    last_on: RGB8,
+}
+
+impl TasmotaState {
+    fn new() -> Self {
+        let red = RGB8::new(LED_BRIGHTNESS, 0, 0);
+        Self {
+            current: red,
+            last_on: red,
+        }
+    }
+
+    /// Übernimmt eine neue Farbe vom LED-Task
+    fn update(&mut self, color: RGB8) {
+        self.current = color;
+        if color != RGB8::default() {
+            self.last_on = color;
+        }
+    }
+
+    /// Übersetzt ein Tasmota-Kommando in ein LED-Kommando
+    ///
+    /// `None` bei Abfragen oder wenn sich nichts ändert (z.B. ON bei bereits
+    /// eingeschalteter LED).
+    fn led_command(&self, command: TasmotaCommand) -> Option<LedCommand> {
+        let is_on = self.current != RGB8::default();
+        let set_color = |color: RGB8| LedCommand::SetColor {
+            target_color: color,
+            color_id: classify_color(color),
+        };
+        match command {
+            TasmotaCommand::Power(PowerAction::On) if !is_on => Some(set_color(self.last_on)),
+            TasmotaCommand::Power(PowerAction::Off) if is_on => Some(LedCommand::Off),
+            TasmotaCommand::Power(PowerAction::Toggle) if is_on => Some(LedCommand::Off),
+            TasmotaCommand::Power(PowerAction::Toggle) => Some(set_color(self.last_on)),
+            TasmotaCommand::Color(Some(color)) if color == RGB8::default() => Some(LedCommand::Off),
+            TasmotaCommand::Color(Some(color)) => Some(set_color(color)),
+            _ => None,
         }
-        Ok(Err(_)) => Err(MqttError::DnsResolutionFailed),
-        Err(_) => Err(MqttError::DnsTimeout),
     }
 }
 
+/// Setzt ein Tasmota-Topic zusammen (`<prefix><device_topic>/<suffix>`)
+fn tasmota_topic(
+    prefix: &str,
+    device_topic: &str,
+    suffix: &str,
+) -> Result<String<MQTT_TASMOTA_TOPIC_LEN>, MqttError> {
+    let mut topic = String::new();
+    write_tasmota_topic(&mut topic, prefix, device_topic, suffix)
+        .map_err(|_| MqttError::TopicTooLong)?;
+    Ok(topic)
+}
+
+/// Tasmota-Statusmeldung: (Topic, Payload)
+type TasmotaStat = (String<MQTT_TASMOTA_TOPIC_LEN>, String<48>);
+
+/// Erzeugt `stat/<topic>/POWER` und `stat/<topic>/RESULT` (leer außerhalb des Tasmota-Modus)
+fn tasmota_stat_messages(color: RGB8) -> Result<Vec<TasmotaStat, 2>, MqttError> {
+    let mut messages = Vec::new();
+    let Some(device_topic) = MQTT_TASMOTA_TOPIC else {
+        return Ok(messages);
+    };
+    let mut payload = String::new();
+    payload
+        .push_str(power_state(color))
+        .map_err(|_| MqttError::PublishFailed)?;
+    let power_message = (
+        tasmota_topic(TASMOTA_STAT_PREFIX, device_topic, "POWER")?,
+        payload,
+    );
+
+    let mut payload = String::new();
+    write_tasmota_result(&mut payload, color).map_err(|_| MqttError::PublishFailed)?;
+    let result_message = (
+        tasmota_topic(TASMOTA_STAT_PREFIX, device_topic, "RESULT")?,
+        payload,
+    );
+
+    messages.extend([power_message, result_message]);
+    Ok(messages)
+}
+
+/// Erzeugt `stat/<topic>/RESULT` mit `{"Command":"Busy"}` (`None` außerhalb des Tasmota-Modus)
+fn tasmota_busy_message() -> Result<Option<TasmotaStat>, MqttError> {
+    let Some(device_topic) = MQTT_TASMOTA_TOPIC else {
+        return Ok(None);
+    };
+    let mut payload = String::new();
+    payload
+        .push_str(TASMOTA_BUSY_RESULT)
+        .map_err(|_| MqttError::PublishFailed)?;
+    Ok(Some((
+        tasmota_topic(TASMOTA_STAT_PREFIX, device_topic, "RESULT")?,
+        payload,
+    )))
+}
+
 /// MQTT Fehler-Typen
 ///
 /// Alle möglichen Fehler die während MQTT-Operationen auftreten können.
@@ -191,6 +918,25 @@ enum MqttError {
     ConnectionFailed,
     ProtocolError,
     PublishFailed,
+    SubscribeFailed,
+    ReceiveFailed,
+    TopicTooLong,
+}
+
+impl From<MqttError> for FirmwareError {
+    fn from(error: MqttError) -> Self {
+        let kind = match error {
+            MqttError::DnsResolutionFailed => ErrorKind::DnsFailed,
+            MqttError::DnsTimeout => ErrorKind::Timeout,
+            MqttError::ConnectionFailed => ErrorKind::ConnectionFailed,
+            MqttError::ProtocolError => ErrorKind::ProtocolViolation,
+            MqttError::PublishFailed => ErrorKind::SendFailed,
+            MqttError::SubscribeFailed => ErrorKind::ProtocolViolation,
+            MqttError::ReceiveFailed => ErrorKind::ReceiveFailed,
+            MqttError::TopicTooLong => ErrorKind::CapacityExceeded,
+        };
+        FirmwareError::new(ErrorSource::Mqtt, kind)
+    }
 }
 
 impl defmt::Format for MqttError {
@@ -201,6 +947,9 @@ impl defmt::Format for MqttError {
             MqttError::ConnectionFailed => defmt::write!(fmt, "Connection failed"),
             MqttError::ProtocolError => defmt::write!(fmt, "Protocol error"),
             MqttError::PublishFailed => defmt::write!(fmt, "Publish failed"),
+            MqttError::SubscribeFailed => defmt::write!(fmt, "Subscribe failed"),
+            MqttError::ReceiveFailed => defmt::write!(fmt, "Receive failed"),
+            MqttError::TopicTooLong => defmt::write!(fmt, "Topic too long"),
         }
     }
 }
@@ -0,0 +1,428 @@
+// MQTT-SN Task - MQTT über UDP und ein MQTT-SN-Gateway
+//
+// Für Netze, in denen eine TCP-Verbindung zum Broker zu schwer ist oder der
+// Broker nur über ein MQTT-SN-Gateway erreichbar ist (z.B. Eclipse Paho
+// MQTT-SN Gateway). Nur mit Cargo-Feature `mqtt-sn` enthalten:
+//   cargo build --release --features mqtt-sn
+//
+// Unabhängig vom MQTT-Task (beide Features können gleichzeitig aktiv sein),
+// gleiche Client-ID, Topics und Payloads. Nachrichten-Format: siehe esp_core::mqtt_sn.
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either3, select3};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpAddress, IpEndpoint, Stack, dns::DnsQueryType};
+use embassy_time::{Duration, Instant, Ticker, Timer, with_deadline, with_timeout};
+use esp_core::mqtt::{
+    color_payload, mode_payload, split_command_id, write_command_ack, write_state_payload,
+};
+use esp_core::mqtt_sn::{MsgIdCounter, ReturnCode};
+use esp_core::{
+    BrokerAddress, CommandAck, MqttSnPacket, NetCounter, decode_mqtt_sn, parse_text_command,
+};
+use heapless::String;
+
+use crate::config::*;
+use crate::net_stats::record_net;
+use crate::{LedColorSubscriber, LedCommandSender};
+
+/// MQTT-SN Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung und löst MQTT_SN_GATEWAY auf
+/// - CONNECT mit MQTT_CLIENT_ID, danach REGISTER der Publish-Topics und
+///   SUBSCRIBE auf MQTT_TOPIC_COMMAND
+/// - Published Farbe, Modus und Zustand bei jedem Farb-Broadcast (QoS 0)
+/// - Leitet Text-Kommandos an den LED-Task und quittiert sie auf MQTT_TOPIC_COMMAND_RESULT
+/// - PINGREQ alle MQTT_SN_KEEPALIVE_SECS, ohne PINGRESP folgt ein Reconnect
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn mqtt_sn_task(
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    info!("MQTT-SN: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let Some(gateway) = BrokerAddress::parse(MQTT_SN_GATEWAY) else {
+        warn!("MQTT-SN: Invalid gateway address '{}'", MQTT_SN_GATEWAY);
+        return;
+    };
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0u8; MQTT_SN_PACKET_BUFFER_SIZE * 4];
+    let mut tx_buffer = [0u8; MQTT_SN_PACKET_BUFFER_SIZE * 4];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    // Lokaler Port beliebig, das Gateway antwortet an den Absender
+    if socket.bind(0).is_err() {
+        warn!("MQTT-SN: Failed to bind UDP socket");
+        return;
+    }
+
+    loop {
+        match resolve_gateway(stack, gateway).await {
+            Ok(endpoint) => {
+                info!("MQTT-SN: Connecting to gateway {}", Debug2Format(&endpoint));
+                let mut session = Session {
+                    socket: &socket,
+                    gateway: endpoint,
+                    msg_ids: MsgIdCounter::default(),
+                };
+                match session.run(&mut color_subscriber, command_sender).await {
+                    Ok(()) => info!("MQTT-SN: Gateway closed the session"),
+                    Err(e) => warn!("MQTT-SN: Session ended: {}", e),
+                }
+            }
+            Err(e) => warn!("MQTT-SN: {}", e),
+        }
+        Timer::after(Duration::from_secs(MQTT_SN_RECONNECT_DELAY_SECS)).await;
+    }
+}
+
+/// Topic-IDs einer Sitzung (vom Gateway vergeben)
+struct TopicIds {
+    color: u16,
+    mode: u16,
+    state: u16,
+    command_result: u16,
+    command: u16,
+}
+
+/// Eine MQTT-SN-Sitzung mit dem Gateway
+struct Session<'a> {
+    socket: &'a UdpSocket<'a>,
+    gateway: IpEndpoint,
+    msg_ids: MsgIdCounter,
+}
+
+impl Session<'_> {
+    /// Verbindet, registriert die Topics und verarbeitet danach Nachrichten
+    ///
+    /// `Ok(())` nach DISCONNECT durch das Gateway, sonst der Fehler.
+    async fn run(
+        &mut self,
+        color_subscriber: &mut LedColorSubscriber,
+        command_sender: LedCommandSender,
+    ) -> Result<(), SessionError> {
+        let connect = MqttSnPacket::Connect {
+            clean_session: true,
+            duration_secs: MQTT_SN_KEEPALIVE_SECS,
+            client_id: MQTT_CLIENT_ID,
+        };
+        let return_code = self
+            .request(&connect, |packet| match *packet {
+                MqttSnPacket::Connack { return_code } => Some(return_code),
+                _ => None,
+            })
+            .await?;
+        if return_code != ReturnCode::Accepted {
+            return Err(SessionError::Rejected(return_code));
+        }
+
+        let topics = TopicIds {
+            color: self.register(MQTT_TOPIC_COLOR).await?,
+            mode: self.register(MQTT_TOPIC_MODE).await?,
+            state: self.register(MQTT_TOPIC_STATE).await?,
+            command_result: self.register(MQTT_TOPIC_COMMAND_RESULT).await?,
+            command: self.subscribe(MQTT_TOPIC_COMMAND).await?,
+        };
+        info!("MQTT-SN: Connected, subscribed to '{}'", MQTT_TOPIC_COMMAND);
+
+        let mut packet = [0u8; MQTT_SN_PACKET_BUFFER_SIZE];
+        let mut ping = Ticker::every(Duration::from_secs(MQTT_SN_KEEPALIVE_SECS as u64));
+        let mut awaiting_ping = false;
+        loop {
+            match select3(
+                self.socket.recv_from(&mut packet),
+                color_subscriber.next_message_pure(),
+                ping.next(),
+            )
+            .await
+            {
+                Either3::First(Ok((len, meta))) => {
+                    record_net(NetCounter::UdpPacketsIn);
+                    // Nur Nachrichten des Gateways
+                    if meta.endpoint != self.gateway {
+                        continue;
+                    }
+                    match decode_mqtt_sn(&packet[..len]) {
+                        Ok(MqttSnPacket::Publish {
+                            qos,
+                            topic_id,
+                            msg_id,
+                            payload,
+                            ..
+                        }) => {
+                            if qos == 1 {
+                                self.send(&MqttSnPacket::Puback {
+                                    topic_id,
+                                    msg_id,
+                                    return_code: ReturnCode::Accepted,
+                                })
+                                .await?;
+                            }
+                            if topic_id == topics.command {
+                                self.handle_command(&topics, payload, command_sender)
+                                    .await?;
+                            }
+                        }
+                        // Topic-IDs für Wildcard-Abonnements, werden nicht genutzt
+                        Ok(MqttSnPacket::Register {
+                            topic_id, msg_id, ..
+                        }) => {
+                            self.send(&MqttSnPacket::Regack {
+                                topic_id,
+                                msg_id,
+                                return_code: ReturnCode::Accepted,
+                            })
+                            .await?;
+                        }
+                        Ok(MqttSnPacket::PingReq) => self.send(&MqttSnPacket::PingResp).await?,
+                        Ok(MqttSnPacket::PingResp) => awaiting_ping = false,
+                        Ok(MqttSnPacket::Disconnect) => return Ok(()),
+                        Ok(_) => {}
+                        Err(e) => warn!("MQTT-SN: Invalid packet: {}", Debug2Format(&e)),
+                    }
+                }
+                // Zu große Pakete werden verworfen
+                Either3::First(Err(_)) => {}
+                Either3::Second(msg) => {
+                    let color_label = color_payload(UI_LANGUAGE, &msg);
+                    let color_str = color_label.as_str();
+                    let mode_str = mode_payload(UI_LANGUAGE, &msg);
+                    self.publish(topics.color, color_str.as_bytes()).await?;
+                    self.publish(topics.mode, mode_str.as_bytes()).await?;
+                    let mut state_payload: String<MQTT_STATE_BUFFER_SIZE> = String::new();
+                    if write_state_payload(&mut state_payload, &msg).is_ok() {
+                        self.publish(topics.state, state_payload.as_bytes()).await?;
+                    }
+                    info!(
+                        "MQTT-SN: Published color='{}' mode='{}'",
+                        color_str, mode_str
+                    );
+                }
+                Either3::Third(()) => {
+                    // Letzter PINGREQ unbeantwortet: Gateway nicht mehr erreichbar
+                    if awaiting_ping {
+                        return Err(SessionError::Timeout);
+                    }
+                    self.send(&MqttSnPacket::PingReq).await?;
+                    awaiting_ping = true;
+                }
+            }
+        }
+    }
+
+    /// Text-Kommando an den LED-Task, Quittung wie beim MQTT-Task
+    async fn handle_command(
+        &self,
+        topics: &TopicIds,
+        payload: &[u8],
+        command_sender: LedCommandSender,
+    ) -> Result<(), SessionError> {
+        let text = core::str::from_utf8(payload).unwrap_or("");
+        let (request_id, text) = split_command_id(text);
+        let ack = match parse_text_command(text) {
+            Ok(command) => {
+                info!("MQTT-SN: Received command '{}'", text);
+                if command_sender.try_send(command).is_accepted() {
+                    CommandAck::Applied
+                } else {
+                    CommandAck::Busy
+                }
+            }
+            Err(e) => {
+                warn!("MQTT-SN: Invalid command '{}': {}", text, Debug2Format(&e));
+                CommandAck::Invalid(e)
+            }
+        };
+        let mut ack_payload: String<MQTT_COMMAND_ACK_BUFFER_SIZE> = String::new();
+        if write_command_ack(&mut ack_payload, request_id, text, ack).is_ok() {
+            self.publish(topics.command_result, ack_payload.as_bytes())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Registriert einen Topic-Namen und liefert die Topic-ID
+    async fn register(&mut self, topic_name: &str) -> Result<u16, SessionError> {
+        let msg_id = self.msg_ids.next_id();
+        let register = MqttSnPacket::Register {
+            topic_id: 0,
+            msg_id,
+            topic_name,
+        };
+        let (topic_id, return_code) = self
+            .request(&register, |packet| match *packet {
+                MqttSnPacket::Regack {
+                    topic_id,
+                    msg_id: id,
+                    return_code,
+                } if id == msg_id => Some((topic_id, return_code)),
+                _ => None,
+            })
+            .await?;
+        if return_code != ReturnCode::Accepted {
+            return Err(SessionError::Rejected(return_code));
+        }
+        Ok(topic_id)
+    }
+
+    /// Abonniert einen Topic-Namen (QoS 0) und liefert die Topic-ID
+    async fn subscribe(&mut self, topic_name: &str) -> Result<u16, SessionError> {
+        let msg_id = self.msg_ids.next_id();
+        let subscribe = MqttSnPacket::Subscribe {
+            qos: 0,
+            msg_id,
+            topic_name,
+        };
+        let (topic_id, return_code) = self
+            .request(&subscribe, |packet| match *packet {
+                MqttSnPacket::Suback {
+                    topic_id,
+                    msg_id: id,
+                    return_code,
+                    ..
+                } if id == msg_id => Some((topic_id, return_code)),
+                _ => None,
+            })
+            .await?;
+        if return_code != ReturnCode::Accepted {
+            return Err(SessionError::Rejected(return_code));
+        }
+        Ok(topic_id)
+    }
+
+    /// Published ohne Bestätigung (QoS 0)
+    async fn publish(&self, topic_id: u16, payload: &[u8]) -> Result<(), SessionError> {
+        self.send(&MqttSnPacket::Publish {
+            qos: 0,
+            retain: false,
+            topic_id,
+            msg_id: 0,
+            payload,
+        })
+        .await
+    }
+
+    /// Sendet eine Anfrage und wartet auf die passende Antwort
+    ///
+    /// Wiederholt die Anfrage nach MQTT_SN_RETRY_MS, höchstens MQTT_SN_RETRIES Mal.
+    /// Andere Nachrichten des Gateways werden währenddessen verworfen.
+    async fn request<T>(
+        &self,
+        request: &MqttSnPacket<'_>,
+        mut matches: impl FnMut(&MqttSnPacket) -> Option<T>,
+    ) -> Result<T, SessionError> {
+        let mut packet = [0u8; MQTT_SN_PACKET_BUFFER_SIZE];
+        for _ in 0..MQTT_SN_RETRIES {
+            self.send(request).await?;
+            let deadline = Instant::now() + Duration::from_millis(MQTT_SN_RETRY_MS);
+            while let Ok(received) =
+                with_deadline(deadline, self.socket.recv_from(&mut packet)).await
+            {
+                let Ok((len, meta)) = received else {
+                    continue;
+                };
+                record_net(NetCounter::UdpPacketsIn);
+                if meta.endpoint != self.gateway {
+                    continue;
+                }
+                if let Some(response) = decode_mqtt_sn(&packet[..len])
+                    .ok()
+                    .and_then(|response| matches(&response))
+                {
+                    return Ok(response);
+                }
+            }
+        }
+        Err(SessionError::Timeout)
+    }
+
+    /// Kodiert und sendet eine Nachricht an das Gateway
+    async fn send(&self, packet: &MqttSnPacket<'_>) -> Result<(), SessionError> {
+        let mut out = [0u8; MQTT_SN_PACKET_BUFFER_SIZE];
+        let len = packet.encode(&mut out).map_err(|_| SessionError::Encode)?;
+        self.socket
+            .send_to(&out[..len], self.gateway)
+            .await
+            .map_err(|_| SessionError::SendFailed)?;
+        record_net(NetCounter::UdpPacketsOut);
+        Ok(())
+    }
+}
+
+/// Löst die Gateway-Adresse auf (IPv4, auch als Literal)
+async fn resolve_gateway(
+    stack: &'static Stack<'static>,
+    gateway: BrokerAddress<'static>,
+) -> Result<IpEndpoint, SessionError> {
+    let port = gateway.port.unwrap_or(MQTT_SN_DEFAULT_PORT);
+    let result = with_timeout(
+        Duration::from_secs(DNS_TIMEOUT_SECS),
+        stack.dns_query(gateway.host, DnsQueryType::A),
+    )
+    .await;
+    match result {
+        Ok(Ok(addrs)) => {
+            for addr in addrs {
+                if let IpAddress::Ipv4(ipv4) = addr {
+                    return Ok(IpEndpoint::new(IpAddress::Ipv4(ipv4), port));
+                }
+            }
+            Err(SessionError::DnsFailed)
+        }
+        Ok(Err(_)) | Err(_) => Err(SessionError::DnsFailed),
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
+
+/// Fehler einer MQTT-SN-Sitzung (führt zum Reconnect)
+enum SessionError {
+    DnsFailed,
+    SendFailed,
+    /// Nachricht passt nicht in MQTT_SN_PACKET_BUFFER_SIZE
+    Encode,
+    /// Keine Antwort des Gateways
+    Timeout,
+    /// Gateway hat CONNECT, REGISTER oder SUBSCRIBE abgelehnt
+    Rejected(ReturnCode),
+}
+
+impl defmt::Format for SessionError {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            SessionError::DnsFailed => defmt::write!(fmt, "DNS failed"),
+            SessionError::SendFailed => defmt::write!(fmt, "Send failed"),
+            SessionError::Encode => defmt::write!(fmt, "Message too large"),
+            SessionError::Timeout => defmt::write!(fmt, "Gateway timeout"),
+            SessionError::Rejected(code) => {
+                defmt::write!(fmt, "Rejected by gateway ({})", Debug2Format(code))
+            }
+        }
+    }
+}
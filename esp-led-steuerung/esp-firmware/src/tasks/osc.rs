@@ -0,0 +1,86 @@
+// OSC Task - Empfängt Open Sound Control Nachrichten per UDP
+//
+// Ermöglicht die Steuerung aus Musik-/VJ-Software (TouchOSC, Ableton,
+// Resolume). Adressen wie `/led/color` und `/led/brightness` werden auf
+// LED-Kommandos abgebildet, siehe esp_core::osc.
+
+use defmt::{Debug2Format, info, warn};
+use embassy_net::Stack;
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_time::{Duration, Timer};
+use esp_core::{NetCounter, TaskId, decode_osc_message, osc_led_command};
+
+use crate::LedCommandSender;
+use crate::config::{OSC_PACKET_BUFFER_SIZE, OSC_UDP_PORT};
+use crate::net_stats::record_net;
+use crate::task_stats::task_heartbeat;
+
+/// OSC Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Lauscht auf UDP-Port OSC_UDP_PORT
+/// - Dekodiert OSC-Nachrichten und leitet erkannte Kommandos an den LED-Task
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn osc_task(stack: &'static Stack<'static>, command_sender: LedCommandSender) {
+    info!("OSC: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 1];
+    let mut rx_buffer = [0u8; OSC_PACKET_BUFFER_SIZE * 4];
+    let mut tx_buffer = [0u8; 16];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    if socket.bind(OSC_UDP_PORT).is_err() {
+        warn!("OSC: Failed to bind UDP port {}", OSC_UDP_PORT);
+        return;
+    }
+    info!("OSC: Listening on UDP port {}", OSC_UDP_PORT);
+
+    let mut packet = [0u8; OSC_PACKET_BUFFER_SIZE];
+    loop {
+        task_heartbeat(TaskId::Osc);
+        let Ok((len, _)) = socket.recv_from(&mut packet).await else {
+            // Zu große Pakete werden verworfen
+            continue;
+        };
+        record_net(NetCounter::UdpPacketsIn);
+
+        let message = match decode_osc_message(&packet[..len]) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("OSC: Invalid packet: {}", Debug2Format(&e));
+                continue;
+            }
+        };
+
+        // Unbekannte Adressen und losgelassene Buttons still ignorieren
+        if let Some(command) = osc_led_command(&message) {
+            info!("OSC: {} -> {}", message.address, command);
+            command_sender.send(command).await;
+        }
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
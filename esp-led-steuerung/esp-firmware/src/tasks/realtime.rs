@@ -0,0 +1,106 @@
+// Realtime Task - Empfängt Farbdaten per UDP (Ambilight / Screen-Sync)
+//
+// Zustandsloser UDP-Listener auf REALTIME_UDP_PORT. Jedes gültige Paket
+// wird als Frame an den LED-Task signalisiert, der es sofort ausgibt.
+// Kommen keine Pakete mehr, kehrt der LED-Task nach dem Timeout in den
+// vorherigen Modus zurück. Paket-Format: siehe esp_core::realtime.
+
+use defmt::{info, warn};
+use embassy_net::Stack;
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_time::{Duration, Timer};
+use esp_core::{NetCounter, SpanKind, TaskId, decode_realtime_packet};
+use rgb::RGB8;
+
+use crate::RealtimeSignal;
+use crate::config::{
+    LED_COUNT, REALTIME_PACKET_BUFFER_SIZE, REALTIME_TIMEOUT_MS, REALTIME_UDP_PORT,
+};
+use crate::net_stats::record_net;
+use crate::task_stats::task_heartbeat;
+use crate::trace::span;
+
+/// Ein Realtime-Frame für den LED-Task
+#[derive(Clone)]
+pub struct RealtimeFrame {
+    /// Eine Farbe pro LED
+    pub pixels: [RGB8; LED_COUNT],
+    /// Zeit bis zur Rückkehr in den vorherigen Modus
+    pub timeout: Duration,
+}
+
+/// Realtime Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Lauscht auf UDP-Port REALTIME_UDP_PORT
+/// - Dekodiert `[r,g,b]`- und DRGB-Pakete
+/// - Übergibt Frames per Signal an den LED-Task (nur der neueste Frame zählt)
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `realtime_signal`: Signal an den LED-Task
+#[embassy_executor::task]
+pub async fn realtime_udp_task(
+    stack: &'static Stack<'static>,
+    realtime_signal: &'static RealtimeSignal,
+) {
+    info!("Realtime: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 1];
+    let mut rx_buffer = [0u8; REALTIME_PACKET_BUFFER_SIZE * 4];
+    let mut tx_buffer = [0u8; 16];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    if socket.bind(REALTIME_UDP_PORT).is_err() {
+        warn!("Realtime: Failed to bind UDP port {}", REALTIME_UDP_PORT);
+        return;
+    }
+    info!("Realtime: Listening on UDP port {}", REALTIME_UDP_PORT);
+
+    let mut packet = [0u8; REALTIME_PACKET_BUFFER_SIZE];
+    loop {
+        task_heartbeat(TaskId::Realtime);
+        let Ok((len, _)) = socket.recv_from(&mut packet).await else {
+            // Zu große Pakete werden abgeschnitten bzw. verworfen
+            continue;
+        };
+        record_net(NetCounter::UdpPacketsIn);
+        let _span = span(SpanKind::Realtime);
+
+        let Some(decoded) = decode_realtime_packet(&packet[..len]) else {
+            warn!("Realtime: Invalid packet ({} bytes)", len);
+            continue;
+        };
+
+        let mut frame = RealtimeFrame {
+            pixels: [RGB8::default(); LED_COUNT],
+            timeout: decoded
+                .timeout_secs()
+                .map(|secs| Duration::from_secs(secs as u64))
+                .unwrap_or(Duration::from_millis(REALTIME_TIMEOUT_MS)),
+        };
+        decoded.render(&mut frame.pixels);
+        realtime_signal.signal(frame);
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
@@ -0,0 +1,324 @@
+// Scheduler Task - Führt Zeitplan-Einträge zur eingestellten Uhrzeit aus
+//
+// Zeitplan, Presets und Kalibrierung werden beim Start aus dem Flash geladen
+// (die Gerätekonfiguration schon in main, siehe crate::device_config) und
+// nach jeder Änderung (HTTP/WebSocket) wieder gespeichert. Fällige Einträge werden einmal pro Minute als LedCommand an
+// den LED-Task gesendet.
+//
+// Außerdem führt der Task angeforderte Neustarts und Werksresets aus (siehe
+// crate::reboot), da nur er den Flash beschreibt.
+
+use defmt::{error, info, warn};
+use embassy_futures::select::{Either3, Either4, select3, select4};
+use embassy_time::{Duration, Timer};
+use esp_core::{
+    Calibration, ErrorKind, ErrorSource, FirmwareError, RecordKind, ScheduleAction, SystemEvent,
+    TaskId,
+};
+
+use crate::calibration::CalibrationStore;
+use crate::config::{
+    REBOOT_GRACE_MS, SCHEDULE_CAPACITY, SCHEDULER_POLL_SECS, STORAGE_RECORD_BUFFER_SIZE,
+};
+use crate::device_config::DeviceConfigStore;
+use crate::preset::{FirmwarePresets, PresetStore};
+use crate::reboot::{RebootRequest, arm_safe_mode, wait_reboot_request};
+use crate::schedule::{FirmwareSchedule, ScheduleStore};
+use crate::task_stats::task_heartbeat;
+use crate::web_ui;
+use crate::{
+    LedCommandSender, SharedStorage, SystemEventChannel, publish_event, report_error, wall_clock,
+};
+
+/// Scheduler Task - läuft parallel zu anderen Tasks
+///
+/// - Lädt Zeitplan, Presets und Kalibrierung aus dem Flash
+/// - Prüft alle SCHEDULER_POLL_SECS Sekunden ob Einträge fällig sind
+/// - Sendet fällige Aktionen an den LED-Task (maximal einmal pro Minute)
+/// - Speichert jede dieser Einstellungen nach einer Änderung im Flash
+/// - Führt angeforderte Neustarts aus (ausstehende Änderungen vorher sichern)
+///   und Werksresets (alle Records löschen)
+///
+/// Solange die Uhrzeit nicht per SNTP synchronisiert ist, wird nichts ausgeführt.
+///
+/// # Parameter
+/// - `schedule`: Geteilter Zeitplan (auch von HTTP/WebSocket bearbeitet)
+/// - `presets`: Geteilte Presets (von HTTP bearbeitet)
+/// - `calibration`: Geteilter Weißabgleich (von HTTP bearbeitet)
+/// - `device_config`: Geteilte Gerätekonfiguration (von HTTP bearbeitet)
+/// - `storage`: Flash-Storage für die Persistenz
+/// - `command_sender`: Channel Sender für LED-Kommandos
+/// - `events`: System-Ereignisse (Fehler beim Speichern, Neustart)
+#[embassy_executor::task]
+pub async fn scheduler_task(
+    schedule: &'static ScheduleStore,
+    presets: &'static PresetStore,
+    calibration: &'static CalibrationStore,
+    device_config: &'static DeviceConfigStore,
+    storage: &'static SharedStorage,
+    command_sender: LedCommandSender,
+    events: &'static SystemEventChannel,
+) {
+    load_schedule(schedule, storage).await;
+    load_presets(presets, storage).await;
+    load_calibration(calibration, storage).await;
+
+    // Zuletzt ausgewertete Minute (verhindert doppelte Ausführung)
+    let mut last_minute: Option<u64> = None;
+
+    loop {
+        task_heartbeat(TaskId::Scheduler);
+        match select4(
+            Timer::after(Duration::from_secs(SCHEDULER_POLL_SECS)),
+            schedule.wait_changed(),
+            presets.wait_changed(),
+            select3(
+                calibration.wait_changed(),
+                device_config.wait_changed(),
+                wait_reboot_request(),
+            ),
+        )
+        .await
+        {
+            Either4::First(_) => {
+                let (Some(unix_secs), Some(now)) =
+                    (wall_clock::unix_time_secs(), wall_clock::local_now())
+                else {
+                    continue; // Noch keine Uhrzeit
+                };
+
+                let minute = unix_secs / 60;
+                if last_minute == Some(minute) {
+                    continue;
+                }
+                last_minute = Some(minute);
+
+                // Aktionen sammeln, damit der Mutex nicht über await gehalten wird
+                let due: heapless::Vec<ScheduleAction, SCHEDULE_CAPACITY> =
+                    schedule.with(|s| s.due_actions(&now).collect());
+                for action in due {
+                    info!(
+                        "Scheduler: Running entry at {:02}:{:02}",
+                        now.hour, now.minute
+                    );
+                    command_sender.send(action.into()).await;
+                }
+            }
+            Either4::Second(_) => save_schedule(schedule, storage, events).await,
+            Either4::Third(_) => save_presets(presets, storage, events).await,
+            Either4::Fourth(Either3::First(_)) => {
+                save_calibration(calibration, storage, events).await
+            }
+            Either4::Fourth(Either3::Second(_)) => {
+                save_device_config(device_config, storage, events).await
+            }
+            Either4::Fourth(Either3::Third(request)) => {
+                reboot(
+                    request,
+                    schedule,
+                    presets,
+                    calibration,
+                    device_config,
+                    storage,
+                    events,
+                )
+                .await
+            }
+        }
+    }
+}
+
+/// Sauberer Neustart: Ereignis melden, ausstehende Änderungen sichern, Reset
+///
+/// Nur Stores mit noch nicht gespeicherter Änderung werden geschrieben,
+/// jeder Speichervorgang löscht einen Flash-Sektor. Beim Werksreset werden
+/// stattdessen alle Records gelöscht, nach dem Neustart gelten die
+/// Standardwerte aus config.rs.
+async fn reboot(
+    request: RebootRequest,
+    schedule: &ScheduleStore,
+    presets: &PresetStore,
+    calibration: &CalibrationStore,
+    device_config: &DeviceConfigStore,
+    storage: &SharedStorage,
+    events: &SystemEventChannel,
+) -> ! {
+    let RebootRequest {
+        source,
+        factory_reset,
+        safe_mode,
+    } = request;
+    warn!(
+        "Scheduler: Reboot requested via {} (factory reset: {}, safe mode: {})",
+        source.name(),
+        factory_reset,
+        safe_mode
+    );
+    // MQTT trennt daraufhin die Verbindung zum Broker
+    publish_event(
+        events,
+        SystemEvent::RebootRequested {
+            source,
+            factory_reset,
+        },
+    );
+
+    if factory_reset {
+        erase_all(storage, events).await;
+    } else {
+        if schedule.take_changed() {
+            save_schedule(schedule, storage, events).await;
+        }
+        if presets.take_changed() {
+            save_presets(presets, storage, events).await;
+        }
+        if calibration.take_changed() {
+            save_calibration(calibration, storage, events).await;
+        }
+        if device_config.take_changed() {
+            save_device_config(device_config, storage, events).await;
+        }
+    }
+
+    Timer::after(Duration::from_millis(REBOOT_GRACE_MS)).await;
+    if safe_mode {
+        arm_safe_mode();
+    }
+    esp_hal::system::software_reset()
+}
+
+/// Löscht alle Records (Werksreset)
+async fn erase_all(storage: &SharedStorage, events: &SystemEventChannel) {
+    let mut storage = storage.lock().await;
+    for kind in RecordKind::ALL {
+        if let Err(e) = storage.erase(kind) {
+            error!("Scheduler: Failed to erase record: {}", e);
+            report_error(events, e.into());
+        }
+    }
+    // Hochgeladene Web-UI verwerfen, GET / liefert wieder die einkompilierte Seite
+    if let Err(e) = web_ui::erase(&mut storage) {
+        error!("Scheduler: Failed to erase web UI: {}", e);
+        report_error(events, e.into());
+    }
+    info!("Scheduler: All settings erased");
+}
+
+/// Lädt den Zeitplan aus dem Flash (leer wenn kein gültiger Record vorhanden)
+async fn load_schedule(schedule: &ScheduleStore, storage: &SharedStorage) {
+    let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+    let mut storage = storage.lock().await;
+    match storage.load(RecordKind::Schedule, &mut buffer) {
+        Some(payload) => {
+            let loaded = FirmwareSchedule::decode(payload);
+            info!("Scheduler: Loaded {} entries from flash", loaded.len());
+            schedule.replace(loaded);
+        }
+        None => info!("Scheduler: No stored schedule, starting empty"),
+    }
+}
+
+/// Speichert den aktuellen Zeitplan im Flash
+async fn save_schedule(
+    schedule: &ScheduleStore,
+    storage: &SharedStorage,
+    events: &SystemEventChannel,
+) {
+    let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+    let Some(len) = schedule.with(|s| s.encode(&mut buffer)) else {
+        warn!("Scheduler: Schedule too large for storage buffer");
+        report_error(
+            events,
+            FirmwareError::new(ErrorSource::Schedule, ErrorKind::CapacityExceeded),
+        );
+        return;
+    };
+    if let Err(e) = storage
+        .lock()
+        .await
+        .save(RecordKind::Schedule, &buffer[..len])
+    {
+        error!("Scheduler: Failed to save schedule: {}", e);
+        report_error(events, e.into());
+    }
+}
+
+/// Lädt die Presets aus dem Flash (Standard-Presets wenn kein gültiger Record vorhanden)
+async fn load_presets(presets: &PresetStore, storage: &SharedStorage) {
+    let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+    let mut storage = storage.lock().await;
+    match storage.load(RecordKind::Presets, &mut buffer) {
+        Some(payload) => {
+            let loaded = FirmwarePresets::decode(payload);
+            info!("Scheduler: Loaded {} presets from flash", loaded.len());
+            presets.replace(loaded);
+        }
+        None => info!("Scheduler: No stored presets, using defaults"),
+    }
+}
+
+/// Speichert die aktuellen Presets im Flash
+async fn save_presets(presets: &PresetStore, storage: &SharedStorage, events: &SystemEventChannel) {
+    let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+    let Some(len) = presets.with(|p| p.encode(&mut buffer)) else {
+        warn!("Scheduler: Presets too large for storage buffer");
+        report_error(
+            events,
+            FirmwareError::new(ErrorSource::Preset, ErrorKind::CapacityExceeded),
+        );
+        return;
+    };
+    if let Err(e) = storage
+        .lock()
+        .await
+        .save(RecordKind::Presets, &buffer[..len])
+    {
+        error!("Scheduler: Failed to save presets: {}", e);
+        report_error(events, e.into());
+    }
+}
+
+/// Lädt die Kalibrierung aus dem Flash (Standardwert wenn kein gültiger Record vorhanden)
+async fn load_calibration(calibration: &CalibrationStore, storage: &SharedStorage) {
+    let mut buffer = [0u8; STORAGE_RECORD_BUFFER_SIZE];
+    let mut storage = storage.lock().await;
+    match storage
+        .load(RecordKind::Calibration, &mut buffer)
+        .and_then(Calibration::from_bytes)
+    {
+        Some(loaded) => {
+            info!(
+                "Scheduler: Loaded calibration ({}, {}, {}) from flash",
+                loaded.r, loaded.g, loaded.b
+            );
+            calibration.replace(loaded);
+        }
+        None => info!("Scheduler: No stored calibration, using default"),
+    }
+}
+
+/// Speichert die aktuelle Kalibrierung im Flash
+async fn save_calibration(
+    calibration: &CalibrationStore,
+    storage: &SharedStorage,
+    events: &SystemEventChannel,
+) {
+    let bytes = calibration.get().to_bytes();
+    if let Err(e) = storage.lock().await.save(RecordKind::Calibration, &bytes) {
+        error!("Scheduler: Failed to save calibration: {}", e);
+        report_error(events, e.into());
+    }
+}
+
+/// Speichert die aktuelle Gerätekonfiguration im Flash
+async fn save_device_config(
+    device_config: &DeviceConfigStore,
+    storage: &SharedStorage,
+    events: &SystemEventChannel,
+) {
+    let bytes = device_config.get().to_bytes();
+    if let Err(e) = storage.lock().await.save(RecordKind::Config, &bytes) {
+        error!("Scheduler: Failed to save device config: {}", e);
+        report_error(events, e.into());
+    }
+}
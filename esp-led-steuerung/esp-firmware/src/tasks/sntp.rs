@@ -0,0 +1,183 @@
+// SNTP Task - Synchronisiert die Wall-Clock mit einem NTP-Server
+//
+// Sendet periodisch eine SNTP-Anfrage (RFC 4330) per UDP und setzt
+// die Wall-Clock (crate::wall_clock) auf die empfangene Serverzeit. Die
+// RTC wird dabei mitgestellt, damit die Uhrzeit Neustarts überdauert.
+// Paket-Aufbau und -Auswertung liegen in esp_core::sntp (Host-testbar).
+
+use defmt::{Debug2Format, info, warn};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpAddress, IpEndpoint, Stack, dns::DnsQueryType};
+use embassy_time::{Duration, Instant, Timer, with_timeout};
+use esp_core::sntp::{SNTP_PACKET_LEN, SNTP_PORT, parse_sntp_response, sntp_request};
+use esp_core::{ErrorKind, ErrorSource, FirmwareError, NetCounter, TaskId, TimeSource};
+
+use crate::config::{
+    DNS_TIMEOUT_SECS, SNTP_LOCAL_PORT, SNTP_RETRY_DELAY_SECS, SNTP_SERVER, SNTP_SYNC_INTERVAL_SECS,
+    SNTP_TIMEOUT_SECS,
+};
+use crate::net_stats::record_net;
+use crate::task_stats::task_heartbeat;
+use crate::{SystemEventChannel, report_error, wall_clock};
+
+/// SNTP Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Löst SNTP_SERVER per DNS auf
+/// - Fragt die Uhrzeit ab und korrigiert um die halbe Round-Trip-Zeit
+/// - Wiederholt nach SNTP_SYNC_INTERVAL_SECS (bzw. SNTP_RETRY_DELAY_SECS bei Fehler)
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `events`: System-Ereignisse (fehlgeschlagene Synchronisation)
+#[embassy_executor::task]
+pub async fn sntp_task(stack: &'static Stack<'static>, events: &'static SystemEventChannel) {
+    info!("SNTP: Task started, waiting for network...");
+    wait_for_network(stack).await;
+    info!("SNTP: Network ready");
+
+    loop {
+        task_heartbeat(TaskId::Sntp);
+        let delay_secs = match synchronize(stack).await {
+            Ok(unix_ms) => {
+                // Abweichung der aus der RTC übernommenen Uhrzeit (Drift seit dem Neustart)
+                if let (Some(TimeSource::Rtc), Some(rtc_ms)) =
+                    (wall_clock::source(), wall_clock::unix_time_ms())
+                {
+                    info!(
+                        "SNTP: RTC time was off by {} ms",
+                        unix_ms as i64 - rtc_ms as i64
+                    );
+                }
+                wall_clock::set_unix_time_ms(unix_ms);
+                info!("SNTP: Time synchronized (unix {} s)", unix_ms / 1000);
+                SNTP_SYNC_INTERVAL_SECS
+            }
+            Err(e) => {
+                warn!("SNTP: Sync failed: {}", e);
+                report_error(events, e.into());
+                SNTP_RETRY_DELAY_SECS
+            }
+        };
+        Timer::after(Duration::from_secs(delay_secs)).await;
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
+
+/// Führt eine SNTP-Abfrage durch
+///
+/// Gibt die aktuelle Unix-Zeit in Millisekunden zurück (bereits um die
+/// halbe Round-Trip-Zeit korrigiert).
+async fn synchronize(stack: &'static Stack<'static>) -> Result<u64, SntpError> {
+    let server_ip = resolve_server(stack).await?;
+    info!("SNTP: Querying {}", Debug2Format(&server_ip));
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 1];
+    let mut tx_meta = [PacketMetadata::EMPTY; 1];
+    let mut rx_buffer = [0u8; SNTP_PACKET_LEN];
+    let mut tx_buffer = [0u8; SNTP_PACKET_LEN];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    socket
+        .bind(SNTP_LOCAL_PORT)
+        .map_err(|_| SntpError::BindFailed)?;
+
+    let request = sntp_request();
+    let endpoint = IpEndpoint::new(IpAddress::Ipv4(server_ip), SNTP_PORT);
+    let sent_at = Instant::now();
+    socket
+        .send_to(&request, endpoint)
+        .await
+        .map_err(|_| SntpError::SendFailed)?;
+    record_net(NetCounter::UdpPacketsOut);
+
+    let mut response = [0u8; SNTP_PACKET_LEN];
+    let (len, _) = with_timeout(
+        Duration::from_secs(SNTP_TIMEOUT_SECS),
+        socket.recv_from(&mut response),
+    )
+    .await
+    .map_err(|_| SntpError::Timeout)?
+    .map_err(|_| SntpError::ReceiveFailed)?;
+    record_net(NetCounter::UdpPacketsIn);
+    let round_trip_ms = sent_at.elapsed().as_millis();
+
+    let server_ms = parse_sntp_response(&response[..len]).ok_or(SntpError::InvalidResponse)?;
+    Ok(server_ms + round_trip_ms / 2)
+}
+
+/// Löst SNTP_SERVER zu einer IPv4-Adresse auf
+async fn resolve_server(
+    stack: &'static Stack<'static>,
+) -> Result<embassy_net::Ipv4Address, SntpError> {
+    let addrs = with_timeout(
+        Duration::from_secs(DNS_TIMEOUT_SECS),
+        stack.dns_query(SNTP_SERVER, DnsQueryType::A),
+    )
+    .await
+    .map_err(|_| SntpError::DnsFailed)?
+    .map_err(|_| SntpError::DnsFailed)?;
+
+    for addr in addrs {
+        if let IpAddress::Ipv4(ipv4) = addr {
+            return Ok(ipv4);
+        }
+    }
+    Err(SntpError::DnsFailed)
+}
+
+/// SNTP Fehler-Typen
+#[derive(Debug)]
+enum SntpError {
+    DnsFailed,
+    BindFailed,
+    SendFailed,
+    ReceiveFailed,
+    Timeout,
+    InvalidResponse,
+}
+
+impl From<SntpError> for FirmwareError {
+    fn from(error: SntpError) -> Self {
+        let kind = match error {
+            SntpError::DnsFailed => ErrorKind::DnsFailed,
+            SntpError::BindFailed => ErrorKind::BindFailed,
+            SntpError::SendFailed => ErrorKind::SendFailed,
+            SntpError::ReceiveFailed => ErrorKind::ReceiveFailed,
+            SntpError::Timeout => ErrorKind::Timeout,
+            SntpError::InvalidResponse => ErrorKind::ProtocolViolation,
+        };
+        FirmwareError::new(ErrorSource::Sntp, kind)
+    }
+}
+
+impl defmt::Format for SntpError {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            SntpError::DnsFailed => defmt::write!(fmt, "DNS failed"),
+            SntpError::BindFailed => defmt::write!(fmt, "Bind failed"),
+            SntpError::SendFailed => defmt::write!(fmt, "Send failed"),
+            SntpError::ReceiveFailed => defmt::write!(fmt, "Receive failed"),
+            SntpError::Timeout => defmt::write!(fmt, "Timeout"),
+            SntpError::InvalidResponse => defmt::write!(fmt, "Invalid response"),
+        }
+    }
+}
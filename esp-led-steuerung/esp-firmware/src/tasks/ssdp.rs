@@ -0,0 +1,141 @@
+// SSDP Task - Macht die Lampe für UPnP Control Points sichtbar
+//
+// Beantwortet M-SEARCH-Anfragen auf 239.255.255.250:1900 und kündigt sich
+// regelmäßig per NOTIFY an. Windows listet die Lampe damit in der
+// Netzwerkumgebung, ein Doppelklick öffnet die Web UI (presentationURL aus
+// /description.xml). Nur mit Cargo-Feature `ssdp` enthalten:
+//   cargo build --release --features ssdp
+//
+// Nachrichten-Format und Gerätebeschreibung: siehe esp_core::ssdp.
+
+use core::net::Ipv4Addr;
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either, select};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpEndpoint, Stack};
+use embassy_time::{Duration, Instant, Timer};
+use esp_core::ssdp::{NotificationType, SSDP_MULTICAST_ADDR};
+use esp_core::{DeviceUuid, NetCounter, SsdpDevice, parse_m_search};
+use heapless::String;
+
+use crate::config::{
+    SSDP_NOTIFY_INTERVAL_SECS, SSDP_PACKET_BUFFER_SIZE, SSDP_RESPONSE_BUFFER_SIZE, SSDP_UDP_PORT,
+};
+use crate::net_stats::record_net;
+use crate::version::FIRMWARE_VERSION;
+
+/// SSDP Task - läuft parallel zu anderen Tasks
+///
+/// - Wartet auf Netzwerk-Verbindung
+/// - Tritt der SSDP-Multicast-Gruppe bei und lauscht auf UDP-Port SSDP_UDP_PORT
+/// - Beantwortet passende M-SEARCH-Anfragen per Unicast
+/// - Sendet alle SSDP_NOTIFY_INTERVAL_SECS eine `ssdp:alive`-Ankündigung
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+#[embassy_executor::task]
+pub async fn ssdp_task(stack: &'static Stack<'static>) {
+    info!("SSDP: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 3];
+    let mut rx_buffer = [0u8; SSDP_PACKET_BUFFER_SIZE * 2];
+    let mut tx_buffer = [0u8; SSDP_RESPONSE_BUFFER_SIZE * 3];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    if socket.bind(SSDP_UDP_PORT).is_err() {
+        warn!("SSDP: Failed to bind UDP port {}", SSDP_UDP_PORT);
+        return;
+    }
+    let group = Ipv4Addr::from(SSDP_MULTICAST_ADDR);
+    if stack.join_multicast_group(group).is_err() {
+        warn!(
+            "SSDP: Failed to join multicast group {}",
+            Debug2Format(&group)
+        );
+        return;
+    }
+    info!(
+        "SSDP: Listening on {}:{}",
+        Debug2Format(&group),
+        SSDP_UDP_PORT
+    );
+
+    let mac = esp_hal::efuse::Efuse::mac_address();
+    let mut device = SsdpDevice {
+        uuid: DeviceUuid::from_mac(mac),
+        ip: [0; 4],
+        version: FIRMWARE_VERSION,
+    };
+    let mut packet = [0u8; SSDP_PACKET_BUFFER_SIZE];
+    let mut next_notify = Instant::now();
+
+    loop {
+        // Adresse kann sich nach DHCP-Erneuerung ändern
+        if let Some(config) = stack.config_v4() {
+            device.ip = config.address.address().octets();
+        }
+
+        match select(socket.recv_from(&mut packet), Timer::at(next_notify)).await {
+            Either::First(Ok((len, meta))) => {
+                record_net(NetCounter::UdpPacketsIn);
+                // NOTIFY anderer Geräte und fremde Suchziele ignorieren
+                let Some(search) = parse_m_search(&packet[..len], &device.uuid) else {
+                    continue;
+                };
+                // Antworten mehrerer Lampen innerhalb von MX verteilen (UPnP verlangt
+                // eine zufällige Verzögerung, die MAC reicht zur Streuung)
+                let delay_ms = u64::from(mac[5]) * u64::from(search.mx) * 1000 / 256;
+                Timer::after(Duration::from_millis(delay_ms)).await;
+                for &target in search.target.responses() {
+                    let mut response: String<SSDP_RESPONSE_BUFFER_SIZE> = String::new();
+                    if device.write_search_response(&mut response, target).is_ok() {
+                        send(&socket, meta.endpoint, &response).await;
+                    }
+                }
+            }
+            // Zu große Pakete werden verworfen
+            Either::First(Err(_)) => {}
+            Either::Second(()) => {
+                let endpoint = IpEndpoint::from((group, SSDP_UDP_PORT));
+                for target in NotificationType::ALL {
+                    let mut notify: String<SSDP_RESPONSE_BUFFER_SIZE> = String::new();
+                    if device.write_notify(&mut notify, target, true).is_ok() {
+                        send(&socket, endpoint, &notify).await;
+                    }
+                }
+                next_notify = Instant::now() + Duration::from_secs(SSDP_NOTIFY_INTERVAL_SECS);
+            }
+        }
+    }
+}
+
+/// Sendet eine SSDP-Nachricht
+async fn send(socket: &UdpSocket<'_>, endpoint: IpEndpoint, message: &str) {
+    if socket.send_to(message.as_bytes(), endpoint).await.is_err() {
+        warn!("SSDP: Failed to send message");
+    } else {
+        record_net(NetCounter::UdpPacketsOut);
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
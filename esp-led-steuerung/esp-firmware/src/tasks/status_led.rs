@@ -0,0 +1,71 @@
+// Status-LED Task - Blinkcodes für die Verbindung
+//
+// Übernimmt die System-Ereignisse in den gemeinsamen StatusIndicator
+// (Logik in esp_core::status_led). Der LED-Task zeigt das Muster auf der
+// ersten LED, solange die Lampe aus ist (Leerlauf-Frames).
+// Außerdem führt er die Verbindungsdiagnose für das Kommando `diagnose`
+// (esp_core::diagnose), deshalb läuft er auch ohne STATUS_LED.
+
+use core::cell::Cell;
+
+use defmt::info;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::Instant;
+use esp_core::{ConnectivityDiagnosis, DiagnoseCode, StatusIndicator, SystemEvent};
+use rgb::RGB8;
+
+use crate::SystemEventSubscriber;
+use crate::config::STATUS_LED_BRIGHTNESS;
+
+/// Status aus den System-Ereignissen (Online erst mit MQTT, falls aktiviert)
+static STATUS: Mutex<CriticalSectionRawMutex, Cell<StatusIndicator>> =
+    Mutex::new(Cell::new(StatusIndicator::new(cfg!(feature = "mqtt"))));
+
+/// Letztes Verbindungsproblem (wie der Status: MQTT zählt, falls aktiviert)
+static DIAGNOSIS: Mutex<CriticalSectionRawMutex, Cell<ConnectivityDiagnosis>> = Mutex::new(
+    Cell::new(ConnectivityDiagnosis::new(cfg!(feature = "mqtt"))),
+);
+
+/// Code für das Kommando `diagnose`, `None` = kein Problem bekannt
+pub fn diagnose_code() -> Option<DiagnoseCode> {
+    DIAGNOSIS.lock(|cell| cell.get().code())
+}
+
+/// Farbe der Status-LED zum Zeitpunkt `now_ms`
+pub fn status_frame(now_ms: u64) -> RGB8 {
+    STATUS.lock(|cell| cell.get().frame(now_ms, STATUS_LED_BRIGHTNESS))
+}
+
+fn handle_event(event: &SystemEvent) {
+    DIAGNOSIS.lock(|cell| {
+        let mut diagnosis = cell.get();
+        diagnosis.handle(event);
+        cell.set(diagnosis);
+    });
+    let now_ms = Instant::now().as_millis();
+    STATUS.lock(|cell| {
+        let mut status = cell.get();
+        let before = status.code(now_ms);
+        status.handle(event, now_ms);
+        cell.set(status);
+        let after = status.code(now_ms);
+        if before != after {
+            info!("Status LED: {} -> {}", before.name(), after.name());
+        }
+    });
+}
+
+/// Status-LED Task - läuft parallel zu anderen Tasks
+///
+/// # Parameter
+/// - `event_subscriber`: System-Ereignisse (WLAN, MQTT, Fehler, Stall-Erkennung)
+#[embassy_executor::task]
+pub async fn status_led_task(mut event_subscriber: SystemEventSubscriber) {
+    info!("Status LED: Task started");
+    loop {
+        // Verpasste Ereignisse überspringen: der nächste Zustandswechsel korrigiert die Anzeige
+        let event = event_subscriber.next_message_pure().await;
+        handle_event(&event);
+    }
+}
@@ -0,0 +1,58 @@
+// Supervisor Task - Software-Stall-Erkennung
+//
+// Der Hardware-Watchdog sieht nur, ob der Executor läuft. Hängt ein einzelner
+// Task in einem `await` (Deadlock, verlorenes Signal), laufen die übrigen
+// weiter. Der Supervisor prüft deshalb die Lebenszeichen aus crate::task_stats
+// gegen STALL_DEADLINES (Logik in esp_core::stall, Host-testbar).
+
+use defmt::{error, info};
+use embassy_time::{Duration, Instant, Ticker, Timer};
+use esp_core::{StallChange, StallDetector, SystemEvent};
+
+use crate::config::{STALL_CHECK_INTERVAL_SECS, STALL_DEADLINES, STALL_REBOOT};
+use crate::task_stats::system_stats;
+use crate::{SystemEventChannel, publish_event};
+
+/// Supervisor Task - läuft parallel zu anderen Tasks
+///
+/// - Prüft alle STALL_CHECK_INTERVAL_SECS die Fristen der überwachten Tasks
+/// - Meldet `SystemEvent::TaskStalled` / `TaskRecovered` bei Zustandswechseln
+/// - Startet mit STALL_REBOOT neu, sobald ein Task hängt
+///
+/// # Parameter
+/// - `events`: System-Ereignisse (Stall gemeldet / behoben)
+#[embassy_executor::task]
+pub async fn stall_supervisor_task(events: &'static SystemEventChannel) {
+    info!(
+        "Supervisor: Monitoring {} tasks (reboot on stall: {})",
+        STALL_DEADLINES.len(),
+        STALL_REBOOT
+    );
+
+    let mut detector = StallDetector::new(STALL_DEADLINES);
+    let mut ticker = Ticker::every(Duration::from_secs(STALL_CHECK_INTERVAL_SECS));
+    loop {
+        ticker.next().await;
+
+        let mut stalled = false;
+        let now_ms = Instant::now().as_millis();
+        detector.check(&system_stats(), now_ms, |change| match change {
+            StallChange::Stalled { task, idle_ms } => {
+                error!("Supervisor: Task '{}' stalled ({} ms idle)", task, idle_ms);
+                publish_event(events, SystemEvent::TaskStalled { task, idle_ms });
+                stalled = true;
+            }
+            StallChange::Recovered { task } => {
+                info!("Supervisor: Task '{}' recovered", task);
+                publish_event(events, SystemEvent::TaskRecovered { task });
+            }
+        });
+
+        if stalled && STALL_REBOOT {
+            error!("Supervisor: Rebooting due to stalled task");
+            // Kurz warten, damit Event-Subscriber und Log-Ausgabe noch laufen
+            Timer::after(Duration::from_millis(100)).await;
+            esp_hal::system::software_reset();
+        }
+    }
+}
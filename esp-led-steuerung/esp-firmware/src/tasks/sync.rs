@@ -0,0 +1,179 @@
+// Sync Task - Hält mehrere Lampen per UDP-Multicast im Gleichschritt
+//
+// Leader: sendet die vom LED-Task vorausberechneten Frames an die
+// Multicast-Gruppe (höchstens alle SYNC_INTERVAL_MS, ohne Änderung als
+// Keepalive alle SYNC_KEEPALIVE_MS).
+// Follower: empfängt die Frames, rechnet den Anzeigezeitpunkt auf die eigene
+// Uhr um und übergibt sie zum richtigen Zeitpunkt als Realtime-Frame an den
+// LED-Task. Bleibt der Leader aus, kehrt der LED-Task nach dem Timeout in den
+// eigenen Modus zurück. Paket-Format und Zeitausgleich: siehe esp_core::sync.
+
+use core::net::Ipv4Addr;
+
+use defmt::{Debug2Format, info, warn};
+use embassy_futures::select::{Either, select};
+use embassy_net::Stack;
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_time::{Duration, Instant, Timer};
+use esp_core::sync::{SYNC_MULTICAST_ADDR, SYNC_PACKET_LEN, SYNC_TIMEOUT_MS};
+use esp_core::{NetCounter, SyncFollower, SyncFrame, SyncPacket, SyncRole, TaskId};
+
+use crate::calibration::CalibrationStore;
+use crate::config::{LED_COUNT, SYNC_INTERVAL_MS, SYNC_KEEPALIVE_MS, SYNC_ROLE, SYNC_UDP_PORT};
+use crate::net_stats::record_net;
+use crate::task_stats::task_heartbeat;
+use crate::tasks::realtime::RealtimeFrame;
+use crate::{RealtimeSignal, SyncSignal};
+
+/// Sync Task - läuft parallel zu anderen Tasks
+///
+/// Ohne `SYNC_ROLE` in .env beendet sich der Task sofort.
+///
+/// # Parameter
+/// - `stack`: embassy-net Stack für Netzwerk-Zugriff
+/// - `sync`: Frames des LED-Tasks (nur Leader)
+/// - `realtime`: Signal an den LED-Task (nur Follower)
+/// - `calibration`: eigener Weißabgleich für empfangene Frames (nur Follower)
+#[embassy_executor::task]
+pub async fn sync_task(
+    stack: &'static Stack<'static>,
+    sync: &'static SyncSignal,
+    realtime: &'static RealtimeSignal,
+    calibration: &'static CalibrationStore,
+) {
+    if SYNC_ROLE == SyncRole::Off {
+        return;
+    }
+    info!("Sync: Task started, waiting for network...");
+    wait_for_network(stack).await;
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_meta = [PacketMetadata::EMPTY; 2];
+    let mut rx_buffer = [0u8; SYNC_PACKET_LEN * 4];
+    let mut tx_buffer = [0u8; SYNC_PACKET_LEN * 2];
+    let mut socket = UdpSocket::new(
+        *stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    if socket.bind(SYNC_UDP_PORT).is_err() {
+        warn!("Sync: Failed to bind UDP port {}", SYNC_UDP_PORT);
+        return;
+    }
+
+    let group = Ipv4Addr::from(SYNC_MULTICAST_ADDR);
+    match SYNC_ROLE {
+        SyncRole::Leader => {
+            info!(
+                "Sync: Leader, sending to {}:{}",
+                Debug2Format(&group),
+                SYNC_UDP_PORT
+            );
+            leader_loop(&socket, group, sync).await;
+        }
+        SyncRole::Follower => {
+            if stack.join_multicast_group(group).is_err() {
+                warn!(
+                    "Sync: Failed to join multicast group {}",
+                    Debug2Format(&group)
+                );
+                return;
+            }
+            info!(
+                "Sync: Follower, listening on {}:{}",
+                Debug2Format(&group),
+                SYNC_UDP_PORT
+            );
+            follower_loop(&socket, realtime, calibration).await;
+        }
+        SyncRole::Off => {}
+    }
+}
+
+/// Leader: sendet den neuesten Frame des LED-Tasks
+async fn leader_loop(socket: &UdpSocket<'_>, group: Ipv4Addr, sync: &'static SyncSignal) -> ! {
+    let mut sequence: u32 = 0;
+    let mut last: Option<SyncFrame> = None;
+    loop {
+        task_heartbeat(TaskId::Sync);
+        let keepalive = Timer::after(Duration::from_millis(SYNC_KEEPALIVE_MS));
+        if let Either::First(frame) = select(sync.wait(), keepalive).await {
+            last = Some(frame);
+        }
+        let Some(frame) = last else {
+            continue;
+        };
+
+        // Vorlauf relativ zum tatsächlichen Sendezeitpunkt (Keepalive: 0, sofort zeigen)
+        let now_ms = Instant::now().as_millis();
+        let packet = SyncPacket {
+            sequence,
+            sent_ms: now_ms,
+            lead_ms: frame
+                .show_at_ms
+                .saturating_sub(now_ms)
+                .try_into()
+                .unwrap_or(u16::MAX),
+            color: frame.color,
+        };
+        sequence = sequence.wrapping_add(1);
+        if socket
+            .send_to(&packet.encode(), (group, SYNC_UDP_PORT))
+            .await
+            .is_err()
+        {
+            warn!("Sync: Failed to send packet");
+        } else {
+            record_net(NetCounter::UdpPacketsOut);
+        }
+        Timer::after(Duration::from_millis(SYNC_INTERVAL_MS)).await;
+    }
+}
+
+/// Follower: gibt empfangene Frames zum umgerechneten Zeitpunkt aus
+async fn follower_loop(
+    socket: &UdpSocket<'_>,
+    realtime: &'static RealtimeSignal,
+    calibration: &'static CalibrationStore,
+) -> ! {
+    let mut follower = SyncFollower::new();
+    let mut packet = [0u8; SYNC_PACKET_LEN];
+    loop {
+        task_heartbeat(TaskId::Sync);
+        let Ok((len, _)) = socket.recv_from(&mut packet).await else {
+            continue;
+        };
+        record_net(NetCounter::UdpPacketsIn);
+        let Some(decoded) = SyncPacket::decode(&packet[..len]) else {
+            warn!("Sync: Invalid packet ({} bytes)", len);
+            continue;
+        };
+        let Some(frame) = follower.receive(&decoded, Instant::now().as_millis()) else {
+            // Doppelt oder überholt
+            continue;
+        };
+
+        Timer::at(Instant::from_millis(frame.show_at_ms)).await;
+        let color = calibration.get().apply(frame.color);
+        realtime.signal(RealtimeFrame {
+            pixels: [color; LED_COUNT],
+            timeout: Duration::from_millis(SYNC_TIMEOUT_MS),
+        });
+    }
+}
+
+/// Wartet bis Netzwerk-Verbindung verfügbar ist
+///
+/// Prüft kontinuierlich Link-Status und DHCP-Konfiguration.
+async fn wait_for_network(stack: &'static Stack<'static>) {
+    loop {
+        if stack.is_link_up() {
+            if let Some(_) = stack.config_v4() {
+                break;
+            }
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
@@ -0,0 +1,65 @@
+// UART-Konsole - Befehlssatz der TCP-Konsole über UART1
+//
+// Für die Steuerung durch einen anderen Mikrocontroller oder einen per Kabel
+// angeschlossenen Host: TX an GPIO18, RX an GPIO19 (ESP32-C6, andere Chips
+// siehe `uart_console_pins!` in hal/chip.rs), UART_CONSOLE_BAUDRATE 8N1.
+// Zeilen enden mit `\n` (`\r` wird ignoriert), Antworten mit `\r\n`.
+// `quit` startet eine neue Sitzung (Banner), die Schnittstelle bleibt offen.
+
+use defmt::{Debug2Format, error, info, warn};
+use embassy_net::Stack;
+use esp_hal::gpio::AnyPin;
+use esp_hal::uart::{Config, Uart};
+
+use crate::config::UART_CONSOLE_BAUDRATE;
+use crate::tasks::console::ConsoleSession;
+use crate::{LedColorMessage, LedColorSubscriber, LedCommandSender};
+
+/// UART-Konsole Task - läuft parallel zu anderen Tasks
+///
+/// # Parameter
+/// - `uart`: UART1 Peripheral
+/// - `tx`: TX-Pin (zum RX der Gegenstelle, siehe `uart_console_pins!`)
+/// - `rx`: RX-Pin (zum TX der Gegenstelle)
+/// - `stack`: embassy-net Stack (IP-Adresse für `status`)
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn uart_console_task(
+    uart: esp_hal::peripherals::UART1<'static>,
+    tx: AnyPin<'static>,
+    rx: AnyPin<'static>,
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    let config = Config::default().with_baudrate(UART_CONSOLE_BAUDRATE);
+    let mut uart = match Uart::new(uart, config) {
+        Ok(uart) => uart.with_tx(tx).with_rx(rx).into_async(),
+        Err(e) => {
+            error!("UART console: Init failed: {}", Debug2Format(&e));
+            return;
+        }
+    };
+    info!(
+        "UART console: Listening on UART1 ({} baud)",
+        UART_CONSOLE_BAUDRATE
+    );
+
+    let mut state: Option<LedColorMessage> = None;
+    loop {
+        let mut session = ConsoleSession {
+            io: &mut uart,
+            stack,
+            color_subscriber: &mut color_subscriber,
+            command_sender,
+            state: &mut state,
+            log_follow: None,
+        };
+        match session.run().await {
+            Ok(()) => info!("UART console: Session ended"),
+            // Z.B. Überlauf des Empfangspuffers: Sitzung neu starten
+            Err(e) => warn!("UART console: Error: {}", Debug2Format(&e)),
+        }
+    }
+}
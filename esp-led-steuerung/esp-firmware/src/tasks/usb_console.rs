@@ -0,0 +1,53 @@
+// USB-Konsole - Befehlssatz der TCP-Konsole über USB-Serial-JTAG
+//
+// Der eingebaute USB-Port des Chips (DevKitC-6: "ESP32C6 Port") meldet sich am PC als
+// serielle Schnittstelle: `picocom /dev/ttyACM1` öffnet direkt eine Shell,
+// ohne espflash/defmt-Dekodierung. Zusätzlich erscheinen die Zeilen des
+// Log-Ringpuffers (ab Boot) zwischen den Eingaben.
+//
+// defmt läuft in diesem Aufbau immer über UART0 (CH343 Port), siehe
+// esp-println in Cargo.toml. Ohne geöffnetes Terminal blockieren Ausgaben,
+// bis ein Host liest - nur dieser Task wartet dann.
+
+use defmt::info;
+use embassy_net::Stack;
+use esp_hal::usb_serial_jtag::UsbSerialJtag;
+
+use crate::tasks::console::ConsoleSession;
+use crate::{LedColorMessage, LedColorSubscriber, LedCommandSender};
+
+/// USB-Konsole Task - läuft parallel zu anderen Tasks
+///
+/// # Parameter
+/// - `usb`: USB_SERIAL_JTAG Peripheral
+/// - `stack`: embassy-net Stack (IP-Adresse für `status`)
+/// - `color_subscriber`: PubSub Subscriber für LED-Farb-Broadcasts
+/// - `command_sender`: Channel Sender für LED-Kommandos
+#[embassy_executor::task]
+pub async fn usb_console_task(
+    usb: esp_hal::peripherals::USB_SERIAL_JTAG<'static>,
+    stack: &'static Stack<'static>,
+    mut color_subscriber: LedColorSubscriber,
+    command_sender: LedCommandSender,
+) {
+    let mut usb = UsbSerialJtag::new(usb).into_async();
+    info!("USB console: Listening on USB-Serial-JTAG");
+
+    let mut state: Option<LedColorMessage> = None;
+    // Log-Zeilen ab Boot, über Sitzungen hinweg fortgesetzt
+    let mut log_follow = 0;
+    loop {
+        let mut session = ConsoleSession {
+            io: &mut usb,
+            stack,
+            color_subscriber: &mut color_subscriber,
+            command_sender,
+            state: &mut state,
+            log_follow: Some(log_follow),
+        };
+        // USB-Serial-JTAG kennt keine Fehler, `quit` startet eine neue Sitzung
+        session.run().await.ok();
+        log_follow = session.log_follow.unwrap_or(log_follow);
+        info!("USB console: Session ended");
+    }
+}
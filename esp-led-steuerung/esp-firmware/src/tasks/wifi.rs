@@ -1,10 +1,15 @@
 // WiFi Task - Verbindet mit WLAN und managed Connection
 use defmt::{Debug2Format, error, info, warn};
+use embassy_futures::select::{Either, select};
 use embassy_net::{Runner, Stack};
 use embassy_time::{Duration, Timer};
 use esp_radio::wifi::{ClientConfig, ModeConfig, ScanConfig, WifiController, WifiDevice};
 
-use crate::config::{WIFI_PASSWORD, WIFI_SSID};
+use crate::config::{RSSI_SAMPLE_INTERVAL_SECS, WIFI_PASSWORD, WIFI_SSID};
+use crate::device_health::{set_bssid, set_rssi};
+use crate::task_stats::task_heartbeat;
+use crate::{SystemEvent, SystemEventChannel, publish_event, report_error};
+use esp_core::{ErrorKind, ErrorSource, FirmwareError, TaskId};
 
 /// WiFi Connection Task
 ///
@@ -12,11 +17,17 @@ use crate::config::{WIFI_PASSWORD, WIFI_SSID};
 /// - Verbindet mit Access Point
 /// - Holt IP-Adresse via DHCP
 /// - Überwacht Verbindung und reconnected bei Bedarf
+/// - Misst die Signalstärke alle RSSI_SAMPLE_INTERVAL_SECS (Diagnose-Telemetrie)
+/// - Meldet Verbindungswechsel als System-Ereignis
 #[embassy_executor::task]
-pub async fn connection_task(mut controller: WifiController<'static>) {
+pub async fn connection_task(
+    mut controller: WifiController<'static>,
+    events: &'static SystemEventChannel,
+) {
     info!("WiFi: Starting connection task");
 
     loop {
+        task_heartbeat(TaskId::Wifi);
         if matches!(controller.is_started(), Ok(false)) {
             info!("WiFi: Configuring and starting...");
 
@@ -43,6 +54,8 @@ pub async fn connection_task(mut controller: WifiController<'static>) {
         }
 
         // Scan for networks (optional, für Debugging)
+        // Der erste Treffer (stärkstes Signal) liefert die BSSID für /api/network
+        let mut target_bssid = None;
         match controller
             .scan_with_config_async(ScanConfig::default())
             .await
@@ -51,6 +64,7 @@ pub async fn connection_task(mut controller: WifiController<'static>) {
                 info!("WiFi: Found {} access points", ap_infos.len());
                 for ap_info in &ap_infos {
                     if ap_info.ssid.as_str() == WIFI_SSID {
+                        target_bssid.get_or_insert(ap_info.bssid);
                         info!(
                             "WiFi: Target AP found - SSID: {}, Signal: {} dBm",
                             WIFI_SSID, ap_info.signal_strength
@@ -68,20 +82,40 @@ pub async fn connection_task(mut controller: WifiController<'static>) {
         match controller.connect_async().await {
             Ok(_) => {
                 info!("WiFi: Connected successfully!");
+                set_bssid(target_bssid);
+                publish_event(events, SystemEvent::WifiConnected);
             }
             Err(e) => {
                 error!("WiFi: Connection failed: {}", Debug2Format(&e));
+                report_error(
+                    events,
+                    FirmwareError::new(ErrorSource::Wifi, ErrorKind::ConnectionFailed),
+                );
                 Timer::after(Duration::from_secs(5)).await;
                 continue;
             }
         }
 
-        // Wait for disconnect
+        // Bis zum Disconnect die Signalstärke messen
         info!("WiFi: Waiting for disconnect event...");
-        controller
-            .wait_for_event(esp_radio::wifi::WifiEvent::StaDisconnected)
-            .await;
+        loop {
+            if let Ok(rssi) = controller.rssi() {
+                set_rssi(Some(rssi));
+            }
+            match select(
+                controller.wait_for_event(esp_radio::wifi::WifiEvent::StaDisconnected),
+                Timer::after(Duration::from_secs(RSSI_SAMPLE_INTERVAL_SECS)),
+            )
+            .await
+            {
+                Either::First(()) => break,
+                Either::Second(()) => task_heartbeat(TaskId::Wifi),
+            }
+        }
+        set_rssi(None);
+        set_bssid(None);
         warn!("WiFi: Disconnected from AP, will retry...");
+        publish_event(events, SystemEvent::WifiDisconnected);
 
         Timer::after(Duration::from_secs(2)).await;
     }
@@ -102,7 +136,7 @@ pub async fn net_task(mut runner: Runner<'static, WifiDevice<'static>>) -> ! {
 /// Wartet bis eine IP-Adresse vom DHCP-Server erhalten wurde
 /// und loggt dann die Netzwerk-Konfiguration
 #[embassy_executor::task]
-pub async fn dhcp_task(stack: &'static Stack<'static>) {
+pub async fn dhcp_task(stack: &'static Stack<'static>, events: &'static SystemEventChannel) {
     loop {
         if stack.is_link_up() {
             break;
@@ -118,6 +152,12 @@ pub async fn dhcp_task(stack: &'static Stack<'static>) {
             info!("  IP:      {}", Debug2Format(&config.address.address()));
             info!("  Gateway: {}", Debug2Format(&config.gateway));
             info!("  DNS:     {}", Debug2Format(&config.dns_servers));
+            publish_event(
+                events,
+                SystemEvent::IpAcquired {
+                    address: config.address.address().octets(),
+                },
+            );
             break;
         }
         Timer::after(Duration::from_millis(500)).await;
@@ -0,0 +1,23 @@
+// TCP-Keepalive und Timeout für MQTT- und HTTP/WebSocket-Sockets
+//
+// Fällt der Access Point kurz weg, bleibt eine Verbindung halboffen: der
+// ESP32 hält sie für lebendig, bis das MQTT-Keep-Alive des Brokers abläuft
+// bzw. der Browser aufgibt. Mit Keepalive-Proben nach `TCP_KEEPALIVE_SECS`
+// Stille und Abbruch nach `TCP_TIMEOUT_SECS` ohne Antwort wird das nach
+// wenigen Sekunden erkannt und der Task verbindet neu bzw. nimmt den
+// nächsten Client an (Werte aus device.toml, Abschnitt `[tcp]`).
+
+use embassy_net::tcp::TcpSocket;
+use embassy_time::Duration;
+
+use crate::config::{TCP_KEEPALIVE_SECS, TCP_TIMEOUT_SECS};
+
+/// Setzt Keepalive und Timeout laut Konfiguration (0 = jeweils aus)
+pub fn apply_socket_tuning(socket: &mut TcpSocket<'_>) {
+    socket.set_keep_alive(secs(TCP_KEEPALIVE_SECS));
+    socket.set_timeout(secs(TCP_TIMEOUT_SECS));
+}
+
+fn secs(value: u64) -> Option<Duration> {
+    (value > 0).then(|| Duration::from_secs(value))
+}
@@ -0,0 +1,73 @@
+// Ablauf-Tracing (Konsole `trace`)
+//
+// Ausgewählte Abschnitte der Tasks (LED-Frame, MQTT-Publish, WebSocket, ...)
+// messen ihre Ein- und Austrittszeit mit `let _span = span(SpanKind::..)`;
+// beim Verlassen des Abschnitts landet er im Ringpuffer (esp_core::trace).
+// Standardmäßig aus: dann kostet ein Abschnitt nur das Lesen eines Flags.
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::Instant;
+use esp_core::{Span, SpanKind, TraceRing};
+
+use crate::config::TRACE_SPANS;
+
+/// Ringpuffer mit der Firmware-Konfiguration
+pub type FirmwareTraceRing = TraceRing<TRACE_SPANS>;
+
+/// Tracing aktiv (Konsole `trace on`/`trace off`)
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Beendete Abschnitte (CriticalSection, da `static`)
+static RING: Mutex<CriticalSectionRawMutex, RefCell<FirmwareTraceRing>> =
+    Mutex::new(RefCell::new(TraceRing::new()));
+
+/// Schaltet das Tracing ein (verwirft alte Messungen) oder aus
+pub fn set_tracing(enabled: bool) {
+    if enabled {
+        RING.lock(|ring| ring.borrow_mut().clear());
+    }
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Prüft ob das Tracing aktiv ist
+pub fn tracing_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Kopie aller gespeicherten Abschnitte
+pub fn trace_snapshot() -> FirmwareTraceRing {
+    RING.lock(|ring| ring.borrow().clone())
+}
+
+/// Beginnt einen Abschnitt, der beim Drop des Rückgabewerts endet
+pub fn span(kind: SpanKind) -> SpanGuard {
+    SpanGuard {
+        kind,
+        start_us: tracing_enabled().then(|| Instant::now().as_micros()),
+    }
+}
+
+/// Laufender Abschnitt (siehe [`span`])
+pub struct SpanGuard {
+    kind: SpanKind,
+    /// `None` = Tracing war beim Eintritt aus
+    start_us: Option<u64>,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let Some(start_us) = self.start_us else {
+            return;
+        };
+        let span = Span {
+            kind: self.kind,
+            start_us,
+            end_us: Instant::now().as_micros(),
+        };
+        RING.lock(|ring| ring.borrow_mut().push(span));
+    }
+}
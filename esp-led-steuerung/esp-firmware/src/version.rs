@@ -0,0 +1,30 @@
+// Firmware-Version und Build-Informationen
+//
+// Werden zur Compile-Zeit eingebettet (siehe build.rs) und über
+// /api/version, die WebSocket-Hello-Nachricht und die MQTT-Birth-Message
+// veröffentlicht. So lässt sich erkennen, welche Geräte noch alte Firmware nutzen.
+
+/// Firmware-Version aus Cargo.toml (SemVer)
+pub const FIRMWARE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Kurzer Git Commit-Hash des Builds ("unknown" ohne git)
+pub const GIT_HASH: &str = env!("GIT_HASH");
+
+/// Build-Zeitpunkt als Unix-Timestamp in Sekunden
+pub const BUILD_TIMESTAMP: u64 = parse_u64(env!("BUILD_TIMESTAMP"));
+
+/// Parst einen Dezimal-String zur Compile-Zeit
+///
+/// Ungültige Zeichen führen zu einem Compile-Fehler (const panic).
+const fn parse_u64(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    let mut value: u64 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let digit = bytes[i];
+        assert!(digit.is_ascii_digit(), "BUILD_TIMESTAMP ist keine Zahl");
+        value = value * 10 + (digit - b'0') as u64;
+        i += 1;
+    }
+    value
+}
@@ -0,0 +1,86 @@
+// Wall-Clock: Echtzeit-Uhr auf Basis von SNTP
+//
+// Speichert den Unix-Zeitpunkt des Boots (in ms). Die aktuelle Uhrzeit ergibt
+// sich aus diesem Offset plus der monotonen Embassy-Zeit seit dem Boot.
+//
+// Jede SNTP-Synchronisation stellt zusätzlich die RTC (LP-Timer) nach. Die
+// läuft über Soft-Resets, Abstürze und Deep Sleep weiter, sodass `init_rtc()`
+// die Uhrzeit beim Boot sofort wiederherstellt und Zeitpläne nicht bis zur
+// nächsten SNTP-Antwort pausieren. Nach einem Stromausfall zählt die RTC ab 0,
+// dann ist bis zur ersten Synchronisation keine Uhrzeit verfügbar.
+
+use core::cell::{Cell, RefCell};
+
+use critical_section::Mutex;
+use embassy_time::Instant;
+use esp_core::time::restored_unix_ms;
+use esp_core::{LocalTime, TimeSource, local_time};
+use esp_hal::rtc_cntl::Rtc;
+
+use crate::config::TIMEZONE;
+
+/// Unix-Zeit (ms) zum Zeitpunkt des Boots und ihre Herkunft, `None` ohne Uhrzeit
+///
+/// critical_section::Mutex statt Atomic: riscv32imac hat keine 64-Bit-Atomics.
+static BOOT_UNIX_MS: Mutex<Cell<Option<(u64, TimeSource)>>> = Mutex::new(Cell::new(None));
+
+/// RTC, die bei jeder Synchronisation nachgestellt wird (gesetzt von `init_rtc`)
+static RTC: Mutex<RefCell<Option<Rtc<'static>>>> = Mutex::new(RefCell::new(None));
+
+/// Übernimmt die RTC und stellt die Uhrzeit aus ihr wieder her
+///
+/// Einmal beim Boot aufrufen. Gibt die wiederhergestellte Unix-Zeit (ms)
+/// zurück, `None` wenn die RTC nie gestellt wurde (z.B. nach Stromausfall).
+pub fn init_rtc(rtc: Rtc<'static>) -> Option<u64> {
+    let restored = restored_unix_ms(rtc.current_time_us() / 1000);
+    if let Some(unix_ms) = restored {
+        set_boot_unix_ms(unix_ms, TimeSource::Rtc);
+    }
+    critical_section::with(|cs| RTC.borrow_ref_mut(cs).replace(rtc));
+    restored
+}
+
+/// Setzt die aktuelle Unix-Zeit (z.B. nach SNTP-Antwort) und stellt die RTC nach
+pub fn set_unix_time_ms(unix_ms: u64) {
+    set_boot_unix_ms(unix_ms, TimeSource::Sntp);
+    critical_section::with(|cs| {
+        if let Some(rtc) = RTC.borrow_ref(cs).as_ref() {
+            rtc.set_current_time_us(unix_ms * 1000);
+        }
+    });
+}
+
+fn set_boot_unix_ms(unix_ms: u64, source: TimeSource) {
+    let boot_unix_ms = unix_ms.saturating_sub(Instant::now().as_millis());
+    critical_section::with(|cs| BOOT_UNIX_MS.borrow(cs).set(Some((boot_unix_ms, source))));
+}
+
+/// Unix-Zeit des Boots in Millisekunden (UTC), z.B. für Zeitstempel im Log
+pub fn boot_unix_ms() -> Option<u64> {
+    critical_section::with(|cs| BOOT_UNIX_MS.borrow(cs).get()).map(|(boot_unix_ms, _)| boot_unix_ms)
+}
+
+/// Aktuelle Unix-Zeit in Millisekunden (UTC)
+pub fn unix_time_ms() -> Option<u64> {
+    Some(boot_unix_ms()? + Instant::now().as_millis())
+}
+
+/// Aktuelle Unix-Zeit in Sekunden (UTC)
+pub fn unix_time_secs() -> Option<u64> {
+    unix_time_ms().map(|ms| ms / 1000)
+}
+
+/// Aktuelle lokale Uhrzeit (Zeitzone aus config.rs)
+pub fn local_now() -> Option<LocalTime> {
+    unix_time_secs().map(|secs| local_time(secs, TIMEZONE))
+}
+
+/// Herkunft der aktuellen Uhrzeit, `None` ohne Uhrzeit
+pub fn source() -> Option<TimeSource> {
+    critical_section::with(|cs| BOOT_UNIX_MS.borrow(cs).get()).map(|(_, source)| source)
+}
+
+/// Prüft ob eine Uhrzeit verfügbar ist (per SNTP oder aus der RTC)
+pub fn is_synchronized() -> bool {
+    source().is_some()
+}
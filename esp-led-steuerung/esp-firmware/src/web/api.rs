@@ -0,0 +1,605 @@
+// JSON-API: REST-Endpoints unter /api
+//
+// Handler-Logik für die JSON-API. Das Routing erfolgt in tasks/http.rs,
+// die Protokoll-Typen liegen in web/protocol.rs. Request-Bodies liest
+// `ApiJson`, damit auch ungültiges JSON eine strukturierte Fehler-Antwort
+// ergibt. Geräte-Aktionen unter /api/system und Änderungen an /api/ui
+// verlangen `ApiAuth`.
+// Die Web-of-Things-Endpunkte (/.well-known/wot, /things/led/...) liegen
+// ebenfalls hier, ihr JSON schreibt esp_core::wot.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use defmt::info;
+use esp_core::wot::{
+    WotAction, WotProperty, action_command, property_command, write_properties,
+    write_property_value, write_thing_description,
+};
+use esp_core::{
+    DeviceConfig, EFFECTS, ErrorKind, ErrorSource, FirmwareError, LedColorMessage, LedCommand,
+    Preset, PresetError, RebootSource, ScheduleEntry, ScheduleError, UiEncoding, UiUploadError,
+    bearer_matches,
+};
+use picoserve::extract::{FromRequest, FromRequestParts};
+use picoserve::io::embedded_io_async;
+use picoserve::request::{RequestBody, RequestParts};
+use picoserve::response::{IntoResponse, Json, Response, StatusCode};
+
+use crate::boards::ALLOWED_LED_PINS;
+use crate::calibration::CalibrationStore;
+use crate::config::{HA_DEVICE_NAME, MDNS_HOSTNAME, REBOOT_GRACE_MS, WS_AUTH_TOKEN};
+use crate::crash::last_crash;
+use crate::device_config::DeviceConfigStore;
+use crate::effects::active_effect;
+use crate::preset::PresetStore;
+use crate::reboot::{request_factory_reset, request_reboot, request_safe_mode};
+use crate::schedule::ScheduleStore;
+use crate::tasks::status_led::diagnose_code;
+use crate::web::protocol::{
+    ApiError, CrashInfo, DiagnoseAccepted, EffectList, PresetCreated, PresetDto, PresetList,
+    RebootAccepted, RgbColor, ScheduleCreated, ScheduleEntryDto, ScheduleList, UiInfo,
+};
+use crate::web_ui::{self, WebUiError};
+use crate::{LedCommandSender, last_color_message};
+
+/// JSON-Request-Body der API
+///
+/// Wie `picoserve::extract::Json`, aber nicht lesbares oder nicht passendes
+/// JSON ergibt `400 {"error":"Invalid JSON body","category":"input",...}`
+/// statt einer Text-Antwort.
+pub struct ApiJson<T>(pub T);
+
+impl<'r, State, T: serde::Deserialize<'r>> FromRequest<'r, State> for ApiJson<T> {
+    type Rejection = ApiResponse;
+
+    async fn from_request<R: embedded_io_async::Read>(
+        state: &'r State,
+        request_parts: RequestParts<'r>,
+        request_body: RequestBody<'r, R>,
+    ) -> Result<Self, Self::Rejection> {
+        picoserve::extract::Json::<T>::from_request(state, request_parts, request_body)
+            .await
+            .map(|picoserve::extract::Json(value)| ApiJson(value))
+            .map_err(|_| ApiResponse::Error(ApiError::invalid_input("Invalid JSON body")))
+    }
+}
+
+/// Roher Request-Body (WoT-Properties und -Actions, JSON-Form wird dort geprüft)
+///
+/// Der Body wird kopiert: ein geliehener Extractor lässt sich in den
+/// Router-Closures nicht inferieren. Zu große Bodies ergeben
+/// `400 {"error":"Invalid request body",...}`.
+pub struct ApiBody(pub Vec<u8>);
+
+impl<'r, State> FromRequest<'r, State> for ApiBody {
+    type Rejection = ApiResponse;
+
+    async fn from_request<R: embedded_io_async::Read>(
+        _state: &'r State,
+        _request_parts: RequestParts<'r>,
+        request_body: RequestBody<'r, R>,
+    ) -> Result<Self, Self::Rejection> {
+        request_body
+            .read_all()
+            .await
+            .map(|body| ApiBody(body.to_vec()))
+            .map_err(|_| ApiResponse::Error(ApiError::invalid_input("Invalid request body")))
+    }
+}
+
+/// Upload einer neuen Oberfläche (POST /api/ui)
+///
+/// Schreibt den Body schon beim Extrahieren in den Flash (siehe web_ui.rs),
+/// statt ihn im HTTP-Buffer zu sammeln. `Content-Encoding: gzip` wird
+/// mitgespeichert und beim Ausliefern wieder gesetzt. Zu große Bodies ergeben
+/// `507`, ein laufender Upload `503`.
+pub struct UiBundleUpload;
+
+impl<'r, State> FromRequest<'r, State> for UiBundleUpload {
+    type Rejection = ApiResponse;
+
+    async fn from_request<R: embedded_io_async::Read>(
+        _state: &'r State,
+        request_parts: RequestParts<'r>,
+        request_body: RequestBody<'r, R>,
+    ) -> Result<Self, Self::Rejection> {
+        let encoding = request_parts
+            .headers()
+            .get("Content-Encoding")
+            .map(|header| header.as_raw());
+        let Some(encoding) = UiEncoding::from_header(encoding) else {
+            return Err(ApiResponse::Error(
+                WebUiError::Upload(UiUploadError::UnsupportedEncoding).into(),
+            ));
+        };
+        let len = request_body.content_length();
+        let mut reader = request_body.reader();
+        web_ui::install(encoding, len, &mut reader)
+            .await
+            .map(|_| UiBundleUpload)
+            .map_err(|error| ApiResponse::Error(error.into()))
+    }
+}
+
+/// Zugriffsschutz für Geräte-Aktionen (Neustart, Werksreset, abgesicherter Modus)
+///
+/// Mit gesetztem `WS_AUTH_TOKEN` muss der Request `Authorization: Bearer <token>`
+/// mitsenden, sonst `401 {"error":"Unauthorized",...}`. Ohne Token ist die API offen.
+pub struct ApiAuth;
+
+impl<'r, State> FromRequestParts<'r, State> for ApiAuth {
+    type Rejection = ApiResponse;
+
+    async fn from_request_parts(
+        _state: &'r State,
+        request_parts: &RequestParts<'r>,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(token) = WS_AUTH_TOKEN else {
+            return Ok(ApiAuth);
+        };
+        let authorized = request_parts
+            .headers()
+            .get("Authorization")
+            .is_some_and(|header| bearer_matches(token, header.as_raw()));
+        if authorized {
+            Ok(ApiAuth)
+        } else {
+            Err(ApiResponse::Error(ApiError::unauthorized()))
+        }
+    }
+}
+
+/// Response-Enum für JSON-API-Endpoints
+/// Ermöglicht unterschiedliche Body-Typen und Status-Codes aus einem Handler
+pub enum ApiResponse {
+    /// 200 OK mit Zeitplan-Liste
+    Schedule(ScheduleList),
+    /// 201 Created mit vergebener ID
+    Created(ScheduleCreated),
+    /// 200 OK mit dem letzten Absturz
+    Crash(CrashInfo),
+    /// 200 OK mit Preset-Liste
+    Presets(PresetList),
+    /// 201 Created mit vergebener Preset-ID
+    PresetCreated(PresetCreated),
+    /// 200 OK mit dem Weißabgleich
+    Calibration(RgbColor),
+    /// 200 OK mit der Gerätekonfiguration
+    Config(DeviceConfig),
+    /// 200 OK mit dem Effekt-Katalog
+    Effects(EffectList),
+    /// 200 OK mit der ausgelieferten Oberfläche
+    Ui(UiInfo),
+    /// 202 Accepted, Neustart (ggf. mit Werksreset) folgt
+    Reboot(RebootAccepted),
+    /// 202 Accepted, die Lampe blinkt den Diagnose-Code
+    Diagnose(DiagnoseAccepted),
+    /// 200 OK mit der WoT Thing Description (`application/td+json`)
+    ThingDescription(String),
+    /// 200 OK mit fertig geschriebenem JSON (WoT-Properties)
+    RawJson(String),
+    /// 204 No Content (WoT: Property geschrieben bzw. Action ausgeführt)
+    NoContent,
+    /// Fehler (HTTP-Status-Code aus `ApiError::status`)
+    Error(ApiError),
+}
+
+impl IntoResponse for ApiResponse {
+    async fn write_to<
+        R: embedded_io_async::Read,
+        W: picoserve::response::ResponseWriter<Error = R::Error>,
+    >(
+        self,
+        connection: picoserve::response::Connection<'_, R>,
+        response_writer: W,
+    ) -> Result<picoserve::ResponseSent, W::Error> {
+        match self {
+            ApiResponse::Schedule(list) => {
+                (StatusCode::OK, Json(list))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Created(created) => {
+                (StatusCode::new(201), Json(created))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Crash(crash) => {
+                (StatusCode::OK, Json(crash))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Presets(list) => {
+                (StatusCode::OK, Json(list))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::PresetCreated(created) => {
+                (StatusCode::new(201), Json(created))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Calibration(calibration) => {
+                (StatusCode::OK, Json(calibration))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Config(config) => {
+                (StatusCode::OK, Json(config))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Effects(list) => {
+                (StatusCode::OK, Json(list))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Ui(info) => {
+                (StatusCode::OK, Json(info))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Reboot(accepted) => {
+                (StatusCode::new(202), Json(accepted))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Diagnose(accepted) => {
+                (StatusCode::new(202), Json(accepted))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::ThingDescription(td) => {
+                Response::new(StatusCode::OK, td)
+                    .with_header("Content-Type", "application/td+json")
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::RawJson(json) => {
+                Response::new(StatusCode::OK, json)
+                    .with_header("Content-Type", "application/json")
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::NoContent => {
+                Response::new(StatusCode::new(204), "")
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            ApiResponse::Error(error) => {
+                (StatusCode::new(error.status), Json(error))
+                    .write_to(connection, response_writer)
+                    .await
+            }
+        }
+    }
+}
+
+/// Alle Zeitplan-Einträge als JSON-DTOs
+pub fn schedule_entries(schedule: &ScheduleStore) -> ScheduleList {
+    let entries = schedule.with(|s| s.entries().map(ScheduleEntryDto::from).collect());
+    ScheduleList { entries }
+}
+
+/// Legt einen Zeitplan-Eintrag an (gemeinsam für HTTP und WebSocket)
+pub fn add_schedule_entry(schedule: &ScheduleStore, dto: ScheduleEntryDto) -> Result<u8, ApiError> {
+    let entry = ScheduleEntry::try_from(dto).map_err(ApiError::invalid_input)?;
+    let id = schedule.add(entry)?;
+    info!(
+        "API: Schedule entry {} added ({:02}:{:02})",
+        id, entry.hour, entry.minute
+    );
+    Ok(id)
+}
+
+/// Fehlermeldung für ScheduleError
+pub fn schedule_error_message(error: ScheduleError) -> &'static str {
+    match error {
+        ScheduleError::Full => "Schedule full",
+        ScheduleError::InvalidEntry => "Invalid schedule entry",
+        ScheduleError::NotFound => "Schedule entry not found",
+    }
+}
+
+impl From<ScheduleError> for ApiError {
+    fn from(error: ScheduleError) -> Self {
+        Self::new(error.into(), schedule_error_message(error))
+    }
+}
+
+/// GET /api/schedule
+pub fn get_schedule(schedule: &ScheduleStore) -> ApiResponse {
+    ApiResponse::Schedule(schedule_entries(schedule))
+}
+
+/// POST /api/schedule
+pub fn post_schedule(schedule: &ScheduleStore, dto: ScheduleEntryDto) -> ApiResponse {
+    match add_schedule_entry(schedule, dto) {
+        Ok(id) => ApiResponse::Created(ScheduleCreated { id }),
+        Err(error) => ApiResponse::Error(error),
+    }
+}
+
+/// PUT /api/schedule/<id>
+pub fn put_schedule(schedule: &ScheduleStore, id: u8, dto: ScheduleEntryDto) -> ApiResponse {
+    let result = ScheduleEntry::try_from(dto)
+        .map_err(ApiError::invalid_input)
+        .and_then(|entry| schedule.update(id, entry).map_err(ApiError::from));
+    match result {
+        Ok(()) => {
+            info!("API: Schedule entry {} updated", id);
+            ApiResponse::Schedule(schedule_entries(schedule))
+        }
+        Err(error) => ApiResponse::Error(error),
+    }
+}
+
+/// DELETE /api/schedule/<id>
+pub fn delete_schedule(schedule: &ScheduleStore, id: u8) -> ApiResponse {
+    match schedule.remove(id) {
+        Ok(_) => {
+            info!("API: Schedule entry {} removed", id);
+            ApiResponse::Schedule(schedule_entries(schedule))
+        }
+        Err(error) => ApiResponse::Error(error.into()),
+    }
+}
+
+/// Alle Presets als JSON-DTOs
+pub fn preset_list(presets: &PresetStore) -> PresetList {
+    let presets = presets.with(|p| p.iter().map(PresetDto::from).collect());
+    PresetList { presets }
+}
+
+/// Fehlermeldung für PresetError
+pub fn preset_error_message(error: PresetError) -> &'static str {
+    match error {
+        PresetError::Full => "Presets full",
+        PresetError::InvalidPreset => "Invalid preset",
+        PresetError::NotFound => "Preset not found",
+    }
+}
+
+impl From<PresetError> for ApiError {
+    fn from(error: PresetError) -> Self {
+        Self::new(error.into(), preset_error_message(error))
+    }
+}
+
+/// GET /api/presets
+pub fn get_presets(presets: &PresetStore) -> ApiResponse {
+    ApiResponse::Presets(preset_list(presets))
+}
+
+/// POST /api/presets
+pub fn post_preset(presets: &PresetStore, dto: PresetDto) -> ApiResponse {
+    let result = Preset::try_from(dto)
+        .map_err(ApiError::invalid_input)
+        .and_then(|preset| presets.add(preset).map_err(ApiError::from));
+    match result {
+        Ok(id) => {
+            info!("API: Preset {} added", id);
+            ApiResponse::PresetCreated(PresetCreated { id })
+        }
+        Err(error) => ApiResponse::Error(error),
+    }
+}
+
+/// PUT /api/presets/<id>
+pub fn put_preset(presets: &PresetStore, id: u8, dto: PresetDto) -> ApiResponse {
+    let result = Preset::try_from(dto)
+        .map_err(ApiError::invalid_input)
+        .and_then(|preset| presets.update(id, preset).map_err(ApiError::from));
+    match result {
+        Ok(()) => {
+            info!("API: Preset {} updated", id);
+            ApiResponse::Presets(preset_list(presets))
+        }
+        Err(error) => ApiResponse::Error(error),
+    }
+}
+
+/// DELETE /api/presets/<id>
+pub fn delete_preset(presets: &PresetStore, id: u8) -> ApiResponse {
+    match presets.remove(id) {
+        Ok(_) => {
+            info!("API: Preset {} removed", id);
+            ApiResponse::Presets(preset_list(presets))
+        }
+        Err(error) => ApiResponse::Error(error.into()),
+    }
+}
+
+/// GET /api/calibration
+pub fn get_calibration(calibration: &CalibrationStore) -> ApiResponse {
+    ApiResponse::Calibration(calibration.get().into())
+}
+
+/// PUT /api/calibration
+pub fn put_calibration(calibration: &CalibrationStore, rgb: RgbColor) -> ApiResponse {
+    calibration.set(rgb.into());
+    info!("API: Calibration set to ({}, {}, {})", rgb.r, rgb.g, rgb.b);
+    ApiResponse::Calibration(rgb)
+}
+
+/// GET /api/config
+pub fn get_config(device_config: &DeviceConfigStore) -> ApiResponse {
+    ApiResponse::Config(device_config.get())
+}
+
+/// PUT /api/config (fehlende Felder erhalten Standardwerte)
+///
+/// `led_pin` muss aus `ALLOWED_LED_PINS` des Board-Profils stammen und wirkt
+/// erst nach einem Neustart.
+pub fn put_config(device_config: &DeviceConfigStore, config: DeviceConfig) -> ApiResponse {
+    if !config.has_allowed_led_pin(ALLOWED_LED_PINS) {
+        return ApiResponse::Error(ApiError::invalid_input("LED pin not allowed on this board"));
+    }
+    if config.led_pin != device_config.get().led_pin {
+        info!("API: LED pin changed, takes effect after reboot");
+    }
+    device_config.set(config);
+    info!("API: Device config updated");
+    ApiResponse::Config(config)
+}
+
+/// GET /api/effects
+pub fn get_effects() -> ApiResponse {
+    ApiResponse::Effects(EffectList {
+        active: active_effect(),
+        effects: &EFFECTS,
+    })
+}
+
+/// GET /api/crash (404 wenn der vorherige Lauf nicht abgestürzt ist)
+pub fn get_crash() -> ApiResponse {
+    match last_crash() {
+        Some(record) => ApiResponse::Crash(CrashInfo(record)),
+        None => ApiResponse::Error(ApiError::not_found("No crash recorded")),
+    }
+}
+
+impl From<WebUiError> for ApiError {
+    fn from(error: WebUiError) -> Self {
+        match error {
+            WebUiError::Upload(UiUploadError::TooLarge) => Self::new(
+                FirmwareError::new(ErrorSource::Http, ErrorKind::CapacityExceeded),
+                UiUploadError::TooLarge.message(),
+            ),
+            WebUiError::Upload(error) => Self::invalid_input(error.message()),
+            WebUiError::Storage(error) => Self::new(error.into(), "Flash access failed"),
+            WebUiError::Busy => Self::new(
+                FirmwareError::new(ErrorSource::Storage, ErrorKind::Busy),
+                "UI update in progress, try again",
+            ),
+        }
+    }
+}
+
+/// GET /api/ui (auch Antwort auf POST, der Upload läuft im Extractor `UiBundleUpload`)
+pub fn get_ui() -> ApiResponse {
+    ApiResponse::Ui(UiInfo::current())
+}
+
+/// DELETE /api/ui
+///
+/// Verwirft die hochgeladene Oberfläche, GET / liefert wieder die einkompilierte Seite.
+pub async fn delete_ui() -> ApiResponse {
+    match web_ui::remove().await {
+        Ok(()) => ApiResponse::Ui(UiInfo::current()),
+        Err(error) => ApiResponse::Error(error.into()),
+    }
+}
+
+/// POST /api/system/reboot
+///
+/// Die Antwort geht noch raus: der Scheduler sichert erst die Einstellungen
+/// und wartet REBOOT_GRACE_MS vor dem Reset.
+pub fn post_reboot() -> ApiResponse {
+    info!("API: Reboot requested");
+    request_reboot(RebootSource::Http);
+    ApiResponse::Reboot(RebootAccepted {
+        delay_ms: REBOOT_GRACE_MS,
+    })
+}
+
+/// POST /api/system/factory-reset
+///
+/// Löscht alle gespeicherten Einstellungen und startet neu (Antwort wie beim Neustart).
+pub fn post_factory_reset() -> ApiResponse {
+    info!("API: Factory reset requested");
+    request_factory_reset(RebootSource::Http);
+    ApiResponse::Reboot(RebootAccepted {
+        delay_ms: REBOOT_GRACE_MS,
+    })
+}
+
+/// POST /api/system/safe-mode
+///
+/// Startet einmalig ohne gespeicherte Gerätekonfiguration und ohne optionale
+/// Protokolle neu (Antwort wie beim Neustart). Der nächste Neustart ist wieder normal.
+pub fn post_safe_mode() -> ApiResponse {
+    info!("API: Safe mode requested");
+    request_safe_mode(RebootSource::Http);
+    ApiResponse::Reboot(RebootAccepted {
+        delay_ms: REBOOT_GRACE_MS,
+    })
+}
+
+/// POST /api/system/diagnose
+///
+/// Lässt die Lampe den Code des letzten Verbindungsproblems blinken
+/// (esp_core::diagnose), volle Warteschlange ergibt 503.
+pub fn post_diagnose(command_sender: LedCommandSender) -> ApiResponse {
+    if !command_sender.try_send(LedCommand::Diagnose).is_accepted() {
+        return ApiResponse::Error(ApiError::busy());
+    }
+    let code = diagnose_code();
+    info!(
+        "API: Diagnose requested ({=str})",
+        code.map_or("ok", |code| code.name())
+    );
+    ApiResponse::Diagnose(DiagnoseAccepted::new(code))
+}
+
+/// Aktueller Zustand für WoT (vor dem ersten Broadcast: Auto-Rotation, noch dunkel)
+fn wot_state() -> LedColorMessage {
+    last_color_message().unwrap_or(LedColorMessage::from_color(Default::default(), true))
+}
+
+/// Reiht ein WoT-Kommando ein, volle Warteschlange ergibt 503
+fn send_wot_command(command_sender: LedCommandSender, command: LedCommand) -> ApiResponse {
+    if !command_sender.try_send(command).is_accepted() {
+        return ApiResponse::Error(ApiError::busy());
+    }
+    info!("API: WoT command {}", command);
+    ApiResponse::NoContent
+}
+
+/// GET /.well-known/wot
+pub fn get_thing_description() -> ApiResponse {
+    let mut td = String::new();
+    // Schreiben in einen String schlägt nicht fehl
+    write_thing_description(&mut td, MDNS_HOSTNAME, HA_DEVICE_NAME).ok();
+    ApiResponse::ThingDescription(td)
+}
+
+/// GET /things/led/properties
+pub fn get_wot_properties() -> ApiResponse {
+    let mut json = String::new();
+    write_properties(&mut json, &wot_state()).ok();
+    ApiResponse::RawJson(json)
+}
+
+/// GET /things/led/properties/<name>
+pub fn get_wot_property(property: WotProperty) -> ApiResponse {
+    let mut json = String::new();
+    write_property_value(&mut json, property, &wot_state()).ok();
+    ApiResponse::RawJson(json)
+}
+
+/// PUT /things/led/properties/<name>
+pub fn put_wot_property(
+    command_sender: LedCommandSender,
+    property: WotProperty,
+    body: &[u8],
+) -> ApiResponse {
+    match property_command(property, body, &wot_state()) {
+        Ok(Some(command)) => send_wot_command(command_sender, command),
+        Ok(None) => ApiResponse::NoContent,
+        Err(e) => ApiResponse::Error(ApiError::invalid_input(e.message())),
+    }
+}
+
+/// POST /things/led/actions/<name>
+pub fn post_wot_action(
+    command_sender: LedCommandSender,
+    action: WotAction,
+    body: &[u8],
+) -> ApiResponse {
+    match action_command(action, body) {
+        Ok(command) => send_wot_command(command_sender, command),
+        Err(e) => ApiResponse::Error(ApiError::invalid_input(e.message())),
+    }
+}
@@ -0,0 +1,145 @@
+// Live-Log über HTTP: GET /api/logs/stream
+//
+// Hält die Verbindung offen und schreibt jede neue Zeile des
+// Log-Ringpuffers (log_ring.rs) als Chunk (`Transfer-Encoding: chunked`,
+// text/plain), z.B. `curl -N http://led.local/api/logs/stream`. Der Stream
+// beginnt mit der nächsten neuen Zeile, die bisherigen liefert GET /api/logs.
+//
+// Ein Stream belegt einen der HTTP-Tasks, solange der Client verbunden ist,
+// daher höchstens LOG_STREAM_MAX_CLIENTS gleichzeitig (sonst 503). Zeilen,
+// die vor dem Senden überschrieben wurden, meldet eine Zeile
+// `... <n> lines dropped`.
+
+use core::cell::Cell;
+use core::fmt::Write as _;
+
+use defmt::info;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::{Duration, Instant, Timer};
+use esp_core::{ErrorKind, ErrorSource, FirmwareError};
+use heapless::String;
+use picoserve::io::embedded_io_async;
+use picoserve::response::IntoResponse;
+use picoserve::response::chunked::{ChunkWriter, ChunkedResponse, Chunks, ChunksWritten};
+
+use crate::config::{
+    LOG_LINE_LEN, LOG_STREAM_HEARTBEAT_SECS, LOG_STREAM_MAX_CLIENTS, LOG_STREAM_POLL_MS,
+};
+use crate::log_ring::{log_line_since, log_next_seq};
+use crate::web::api::ApiResponse;
+use crate::web::protocol::ApiError;
+
+/// Anzahl offener Streams
+static CLIENTS: Mutex<CriticalSectionRawMutex, Cell<u8>> = Mutex::new(Cell::new(0));
+
+/// Belegter Stream-Platz, wird beim Drop freigegeben (auch bei Verbindungsabbruch)
+pub struct LogStream {
+    _private: (),
+}
+
+impl LogStream {
+    /// Belegt einen Platz, `None` wenn LOG_STREAM_MAX_CLIENTS erreicht ist
+    pub fn open() -> Option<Self> {
+        CLIENTS.lock(|clients| {
+            let count = clients.get();
+            if count >= LOG_STREAM_MAX_CLIENTS {
+                return None;
+            }
+            clients.set(count + 1);
+            Some(LogStream { _private: () })
+        })
+    }
+}
+
+impl Drop for LogStream {
+    fn drop(&mut self) {
+        CLIENTS.lock(|clients| clients.set(clients.get().saturating_sub(1)));
+        info!("HTTP: Log stream closed");
+    }
+}
+
+impl Chunks for LogStream {
+    fn content_type(&self) -> &'static str {
+        "text/plain; charset=utf-8"
+    }
+
+    async fn write_chunks<W: embedded_io_async::Write>(
+        self,
+        mut chunk_writer: ChunkWriter<W>,
+    ) -> Result<ChunksWritten, W::Error> {
+        info!("HTTP: Log stream opened");
+        let heartbeat = Duration::from_secs(LOG_STREAM_HEARTBEAT_SECS);
+        let mut next = log_next_seq();
+        let mut last_write = Instant::now();
+        loop {
+            let Some(line) = log_line_since(next) else {
+                // Alles Vorhandene raus, dann auf neue Zeilen warten. Die
+                // Leerzeile erkennt getrennte Clients auch ohne neue Zeilen
+                if last_write.elapsed() >= heartbeat {
+                    chunk_writer.write_chunk(b"\n").await?;
+                    last_write = Instant::now();
+                }
+                chunk_writer.flush().await?;
+                Timer::after(Duration::from_millis(LOG_STREAM_POLL_MS)).await;
+                continue;
+            };
+
+            let mut out: String<{ LOG_LINE_LEN + 64 }> = String::new();
+            let dropped = line.seq.wrapping_sub(next);
+            if dropped > 0 {
+                writeln!(out, "... {} lines dropped", dropped).ok();
+            }
+            // Passt immer, die Zeile ist auf LOG_LINE_LEN begrenzt
+            writeln!(out, "{}", line).ok();
+            chunk_writer.write_chunk(out.as_bytes()).await?;
+            next = line.seq.wrapping_add(1);
+            last_write = Instant::now();
+        }
+    }
+}
+
+/// Antwort auf GET /api/logs/stream
+pub enum LogStreamResponse {
+    /// 200, Zeilen folgen als Chunks
+    Stream(LogStream),
+    /// 503, schon LOG_STREAM_MAX_CLIENTS Streams offen
+    Busy,
+}
+
+/// GET /api/logs/stream
+pub fn get_log_stream() -> LogStreamResponse {
+    match LogStream::open() {
+        Some(stream) => LogStreamResponse::Stream(stream),
+        None => LogStreamResponse::Busy,
+    }
+}
+
+impl IntoResponse for LogStreamResponse {
+    async fn write_to<
+        R: embedded_io_async::Read,
+        W: picoserve::response::ResponseWriter<Error = R::Error>,
+    >(
+        self,
+        connection: picoserve::response::Connection<'_, R>,
+        response_writer: W,
+    ) -> Result<picoserve::ResponseSent, W::Error> {
+        match self {
+            LogStreamResponse::Stream(stream) => {
+                ChunkedResponse::new(stream)
+                    .into_response()
+                    .write_to(connection, response_writer)
+                    .await
+            }
+            LogStreamResponse::Busy => {
+                let error = ApiError::new(
+                    FirmwareError::new(ErrorSource::Http, ErrorKind::Busy),
+                    "Too many log streams",
+                );
+                ApiResponse::Error(error)
+                    .write_to(connection, response_writer)
+                    .await
+            }
+        }
+    }
+}
@@ -1,7 +1,11 @@
 // Web-Modul für HTTP Server und WebSocket
 // Organisiert alle Web-bezogenen Komponenten
 
+pub mod api;
+pub mod log_stream;
 pub mod protocol;
+#[cfg(feature = "ws-deflate")]
+pub mod ws_deflate;
 
 // HTML-Datei zur Compile-Zeit einbinden
 // Die Datei wird direkt ins Binary eingebettet
@@ -1,80 +1,655 @@
 // WebSocket-Protokoll-Definitionen
 // Definiert die JSON-Nachrichten für Client ↔ Server Kommunikation
 
+use esp_core::preset::PRESET_NAME_LEN;
+use esp_core::{
+    CrashRecord, DiagnoseBlink, DiagnoseCode, EffectId, EffectInfo, ErrorKind, ErrorSource,
+    FirmwareError, HeapStats, Preset, PresetEffect, PresetName, ScheduleAction, ScheduleEntry,
+    SystemStats, TaskId, TaskStats, UiEncoding,
+};
+use rgb::RGB8;
 use serde::{Deserialize, Serialize};
 
-/// Farbname-Enum für die drei unterstützten LED-Farben
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, defmt::Format)]
-pub enum ColorName {
-    #[serde(rename = "Rot")]
-    Red,
-    #[serde(rename = "Grün")]
-    Green,
-    #[serde(rename = "Blau")]
-    Blue,
+use crate::config::{PRESET_CAPACITY, SCHEDULE_CAPACITY, SUNRISE_DEFAULT_DURATION_MIN};
+use crate::log_ring::{FirmwareLogRing, log_snapshot};
+use crate::web::INDEX_HTML;
+use crate::web_ui::{WEB_UI_CAPACITY, installed};
+
+/// Farb-Identität (JSON: "Rot", "Grün", "Blau", "Aus"), kommt aus esp-core
+pub use esp_core::ColorId;
+
+/// Farbe als JSON `{"r":..,"g":..,"b":..}` (0-255)
+///
+/// Direkt `RGB8` (serde-Feature von esp-core aktiviert `rgb/serde`).
+/// Weißabgleich (HTTP /api/calibration) nutzt dasselbe Format, Umrechnung
+/// per `From` in esp-core, Beispiel: {"r":255,"g":235,"b":210}
+pub type RgbColor = RGB8;
+
+/// Client → Server Nachrichten: Parser und Zuordnung liegen host-testbar in esp_core::ws,
+/// der Zeitplan-Eintrag (`entry`) ist hier ein [`ScheduleEntryDto`]
+pub use esp_core::ws::{MessageType, OperationMode, WsRequest};
+
+/// Server → Client Nachrichten, Definition in esp_core::ws (host-testbar)
+pub type WsServerMessage =
+    esp_core::ws::WsServerMessage<heapless::Vec<ScheduleEntryDto, SCHEDULE_CAPACITY>, HeapInfo>;
+
+/// Firmware-Versions-Informationen
+/// Antwort von GET /api/version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct VersionInfo {
+    pub version: &'static str,
+    pub git_hash: &'static str,
+    pub build_timestamp: u64,
+}
+
+impl VersionInfo {
+    /// Versions-Informationen der laufenden Firmware (zur Compile-Zeit eingebettet)
+    pub const fn current() -> Self {
+        Self {
+            version: crate::version::FIRMWARE_VERSION,
+            git_hash: crate::version::GIT_HASH,
+            build_timestamp: crate::version::BUILD_TIMESTAMP,
+        }
+    }
+}
+
+/// Heap-Auslastung in Bytes
+/// Antwort von GET /api/heap, WebSocket-Telemetrie und MQTT_TOPIC_HEAP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct HeapInfo {
+    pub used: u32,
+    pub free: u32,
+    /// Höchster gemessener Verbrauch seit dem Start
+    pub high_water: u32,
+    pub size: u32,
+    pub usage_percent: u8,
+}
+
+impl HeapInfo {
+    /// Stand der letzten Messung des Heap-Monitors
+    pub fn current() -> Self {
+        crate::heap_stats::heap_stats().into()
+    }
+}
+
+impl From<HeapStats> for HeapInfo {
+    fn from(stats: HeapStats) -> Self {
+        Self {
+            used: stats.used,
+            free: stats.free,
+            high_water: stats.high_water,
+            size: stats.size(),
+            usage_percent: stats.usage_percent(),
+        }
+    }
+}
+
+/// Laufzeit-Statistik eines Tasks
+/// Beispiel: {"name":"mqtt","iterations":812,"errors":1,"last_activity_ms":73120,"idle_ms":880,"parked":false}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TaskInfo {
+    pub name: &'static str,
+    pub iterations: u32,
+    pub errors: u32,
+    /// Letzte Aktivität (ms seit Boot), `null` = noch nie aktiv
+    pub last_activity_ms: Option<u64>,
+    /// Millisekunden seit der letzten Aktivität
+    pub idle_ms: Option<u64>,
+    /// Wartet ohne Arbeit (z.B. keine WebSocket-Clients), keine Stall-Prüfung
+    pub parked: bool,
+}
+
+impl TaskInfo {
+    fn new(task: TaskId, stats: TaskStats, now_ms: u64) -> Self {
+        Self {
+            name: task.name(),
+            iterations: stats.iterations,
+            errors: stats.errors,
+            last_activity_ms: stats.last_activity_ms,
+            idle_ms: stats.idle_ms(now_ms),
+            parked: stats.parked,
+        }
+    }
+}
+
+/// Geräte-Übersicht: Laufzeit, Firmware, Heap, Reset-Grund und Task-Statistik
+/// Antwort von GET /api/system:
+/// `{"uptime_ms":73999,"version":{...},"heap":{...},"reset_reason":"power_on","safe_mode":false,"tasks":[...]}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SystemInfo {
+    pub uptime_ms: u64,
+    pub version: VersionInfo,
+    pub heap: HeapInfo,
+    /// Grund des letzten Resets (siehe `reboot::reset_reason`)
+    pub reset_reason: &'static str,
+    /// Boot im abgesicherten Modus (POST /api/system/safe-mode)
+    pub safe_mode: bool,
+    pub tasks: heapless::Vec<TaskInfo, { TaskId::COUNT }>,
+}
+
+impl SystemInfo {
+    /// Zählerstand aller Tasks zum Zeitpunkt `now_ms`
+    pub fn new(stats: &SystemStats, now_ms: u64) -> Self {
+        let tasks = stats
+            .iter()
+            .map(|(task, task_stats)| TaskInfo::new(task, task_stats, now_ms))
+            .collect();
+        Self {
+            uptime_ms: now_ms,
+            version: VersionInfo::current(),
+            heap: HeapInfo::current(),
+            reset_reason: crate::reboot::reset_reason(),
+            safe_mode: crate::reboot::is_safe_mode(),
+            tasks,
+        }
+    }
+
+    /// Aktueller Stand (Laufzeit seit Boot)
+    pub fn current() -> Self {
+        let now_ms = embassy_time::Instant::now().as_millis();
+        Self::new(&crate::task_stats::system_stats(), now_ms)
+    }
 }
 
-/// RGB-Struct für JSON-Serialisierung
-/// Repräsentiert eine Farbe mit r, g, b Werten (0-255)
+/// IPv4-Adresse als Text ("192.168.178.42")
+pub type IpText = heapless::String<15>;
+
+/// MAC-Adresse bzw. BSSID als Text ("aa:bb:cc:dd:ee:ff")
+pub type MacText = heapless::String<17>;
+
+/// Netzwerk-Verbindung
+/// Antwort von GET /api/network:
+/// `{"connected":true,"ip":"192.168.178.42","netmask":"255.255.255.0","gateway":"192.168.178.1",
+/// "dns":["192.168.178.1"],"mac":"…","ssid":"…","bssid":"…","rssi":-61}`
+///
+/// Ohne IP-Adresse (DHCP läuft noch) sind `ip`, `netmask` und `gateway` `null`,
+/// ohne WLAN-Verbindung auch `bssid` und `rssi`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NetworkInfo {
+    pub connected: bool,
+    pub ip: Option<IpText>,
+    pub netmask: Option<IpText>,
+    pub gateway: Option<IpText>,
+    pub dns: heapless::Vec<IpText, 3>,
+    pub mac: MacText,
+    pub ssid: &'static str,
+    pub bssid: Option<MacText>,
+    /// Signalstärke in dBm (letzte Messung des WiFi-Tasks)
+    pub rssi: Option<i32>,
+}
+
+impl NetworkInfo {
+    /// Aktueller Stand von Netzwerk-Stack und WiFi-Task
+    pub fn current(stack: embassy_net::Stack<'_>) -> Self {
+        let mut info = Self {
+            connected: stack.is_link_up(),
+            ip: None,
+            netmask: None,
+            gateway: None,
+            dns: heapless::Vec::new(),
+            mac: mac_text(esp_hal::efuse::Efuse::mac_address()),
+            ssid: crate::config::WIFI_SSID,
+            bssid: crate::device_health::bssid().map(mac_text),
+            rssi: crate::device_health::rssi(),
+        };
+        if let Some(config) = stack.config_v4() {
+            info.ip = Some(ip_text(config.address.address()));
+            info.netmask = Some(ip_text(config.address.netmask()));
+            info.gateway = config.gateway.map(ip_text);
+            info.dns = config.dns_servers.into_iter().map(ip_text).collect();
+        }
+        info
+    }
+}
+
+/// Formatiert eine IPv4-Adresse (höchstens 15 Zeichen)
+fn ip_text(address: core::net::Ipv4Addr) -> IpText {
+    let mut text = heapless::String::new();
+    // "255.255.255.255" passt immer
+    let _ = core::fmt::write(&mut text, format_args!("{}", address));
+    text
+}
+
+/// Formatiert eine MAC-Adresse als `aa:bb:cc:dd:ee:ff`
+fn mac_text(mac: [u8; 6]) -> MacText {
+    let mut text = heapless::String::new();
+    for (index, byte) in mac.iter().enumerate() {
+        let separator = if index == 0 { "" } else { ":" };
+        // 17 Zeichen passen immer
+        let _ = core::fmt::write(&mut text, format_args!("{}{:02x}", separator, byte));
+    }
+    text
+}
+
+/// Aktions-Typ eines Zeitplan-Eintrags (JSON)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub struct RgbColor {
-    pub r: u8,
-    pub g: u8,
-    pub b: u8,
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleActionKind {
+    Color,   // Feste Farbe (rgb erforderlich)
+    Auto,    // Auto-Rotation
+    Off,     // LED aus
+    Sunrise, // Lichtwecker (duration_min optional)
 }
 
-/// Client → Server Nachrichten
-/// Kommandos vom Browser an den ESP32
+/// Zeitplan-Eintrag für JSON (HTTP /api/schedule und WebSocket)
 ///
-/// Hinweis: Verwendet einfache untagged enum Struktur für serde-json-core Kompatibilität
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
-pub struct WsClientMessage {
-    #[serde(rename = "type")]
-    pub msg_type: MessageType,
+/// Beispiel: {"id":1,"days":127,"hour":7,"minute":0,"action":"color","rgb":{"r":10,"g":0,"b":0},"enabled":true}
+/// Lichtwecker: {"days":31,"hour":6,"minute":30,"action":"sunrise","duration_min":20}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleEntryDto {
+    /// Wird beim Anlegen vom Server vergeben
+    #[serde(default)]
+    pub id: u8,
+    /// Wochentage als Bitmaske (Bit 0 = Montag … Bit 6 = Sonntag)
+    pub days: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub action: ScheduleActionKind,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub color: Option<ColorName>,
+    pub rgb: Option<RgbColor>,
+    /// Dauer des Sonnenaufgangs in Minuten (nur bei "sunrise")
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub mode: Option<OperationMode>,
+    pub duration_min: Option<u8>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl From<&ScheduleEntry> for ScheduleEntryDto {
+    fn from(entry: &ScheduleEntry) -> Self {
+        let (action, rgb, duration_min) = match entry.action {
+            ScheduleAction::SetColor(color) => (
+                ScheduleActionKind::Color,
+                Some(RgbColor {
+                    r: color.r,
+                    g: color.g,
+                    b: color.b,
+                }),
+                None,
+            ),
+            ScheduleAction::Auto => (ScheduleActionKind::Auto, None, None),
+            ScheduleAction::Off => (ScheduleActionKind::Off, None, None),
+            ScheduleAction::Sunrise { duration_min } => {
+                (ScheduleActionKind::Sunrise, None, Some(duration_min))
+            }
+        };
+        Self {
+            id: entry.id,
+            days: entry.days,
+            hour: entry.hour,
+            minute: entry.minute,
+            action,
+            rgb,
+            duration_min,
+            enabled: entry.enabled,
+        }
+    }
+}
+
+impl TryFrom<ScheduleEntryDto> for ScheduleEntry {
+    type Error = &'static str;
+
+    /// Konvertiert den JSON-Eintrag, Fehler wenn bei "color" die Farbe fehlt
+    fn try_from(dto: ScheduleEntryDto) -> Result<Self, Self::Error> {
+        let action = match (dto.action, dto.rgb) {
+            (ScheduleActionKind::Color, Some(rgb)) => ScheduleAction::SetColor(RGB8 {
+                r: rgb.r,
+                g: rgb.g,
+                b: rgb.b,
+            }),
+            (ScheduleActionKind::Color, None) => return Err("Missing rgb for color action"),
+            (ScheduleActionKind::Auto, _) => ScheduleAction::Auto,
+            (ScheduleActionKind::Off, _) => ScheduleAction::Off,
+            (ScheduleActionKind::Sunrise, _) => ScheduleAction::Sunrise {
+                duration_min: dto.duration_min.unwrap_or(SUNRISE_DEFAULT_DURATION_MIN),
+            },
+        };
+        Ok(Self {
+            id: dto.id,
+            days: dto.days,
+            hour: dto.hour,
+            minute: dto.minute,
+            action,
+            enabled: dto.enabled,
+        })
+    }
+}
+
+/// Liste aller Zeitplan-Einträge (Antwort von GET /api/schedule)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScheduleList {
+    pub entries: heapless::Vec<ScheduleEntryDto, SCHEDULE_CAPACITY>,
+}
+
+/// Antwort nach dem Anlegen eines Zeitplan-Eintrags (POST /api/schedule)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ScheduleCreated {
+    pub id: u8,
+}
+
+/// Antwort auf POST /api/system/reboot (202 Accepted, Neustart folgt nach `delay_ms`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RebootAccepted {
+    pub delay_ms: u64,
+}
+
+/// Antwort auf POST /api/system/diagnose (202 Accepted, die Lampe blinkt `duration_ms` lang)
+/// Beispiel: {"code":3,"reason":"dns_failed","duration_ms":11700}, ohne Problem {"code":0,"reason":"ok",...}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct DiagnoseAccepted {
+    /// Anzahl der roten Blitze (0 = grün, kein Problem bekannt)
+    pub code: u8,
+    pub reason: &'static str,
+    pub duration_ms: u64,
+}
+
+impl DiagnoseAccepted {
+    pub fn new(code: Option<DiagnoseCode>) -> Self {
+        Self {
+            code: code.map_or(0, |code| code.number()),
+            reason: code.map_or("ok", |code| code.name()),
+            duration_ms: DiagnoseBlink::new(code, 0).duration_ms(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MessageType {
-    SetColor,
-    SetMode,
+/// Ausgelieferte Oberfläche (GET /api/ui, Antwort von POST und DELETE /api/ui)
+/// Beispiel: {"source":"flash","size":18234,"encoding":"gzip","crc":439041101,"capacity":131056},
+/// ohne Upload {"source":"builtin","size":29725,"encoding":"identity","crc":null,...}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct UiInfo {
+    /// `flash` (hochgeladen) oder `builtin` (einkompiliert)
+    pub source: &'static str,
+    pub size: u32,
+    pub encoding: &'static str,
+    /// CRC32 der hochgeladenen Daten
+    pub crc: Option<u32>,
+    /// Maximale Upload-Größe in Bytes
+    pub capacity: u32,
+}
+
+impl UiInfo {
+    pub fn current() -> Self {
+        let capacity = WEB_UI_CAPACITY as u32;
+        match installed() {
+            Some(header) => Self {
+                source: "flash",
+                size: header.len,
+                encoding: header.encoding.name(),
+                crc: Some(header.crc),
+                capacity,
+            },
+            None => Self {
+                source: "builtin",
+                size: INDEX_HTML.len() as u32,
+                encoding: UiEncoding::Identity.name(),
+                crc: None,
+                capacity,
+            },
+        }
+    }
 }
 
-/// Server → Client Nachrichten
-/// Status-Updates und Fehler vom ESP32 an den Browser
+/// Effekt-Katalog mit aktivem Effekt (GET /api/effects)
+/// Beispiel: {"active":"auto","effects":[{"name":"solid","usage":"<color> [<transition_ms>]","params":[...]},...]}
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
-#[serde(tag = "type")]
-pub enum WsServerMessage {
-    #[serde(rename = "status")]
-    Status {
-        color: ColorName,
-        rgb: RgbColor,
-        timestamp_ms: u64,
-        mode: OperationMode,
-    },
-    #[serde(rename = "error")]
-    Error { message: &'static str },
-}
-
-/// Betriebs-Modus der LED
+pub struct EffectList {
+    pub active: EffectId,
+    pub effects: &'static [EffectInfo],
+}
+
+/// Effekt eines Presets (JSON)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
-pub enum OperationMode {
-    Auto,   // Automatische Farb-Rotation
-    Manual, // Manuelle Steuerung vom Browser
-}
-
-impl ColorName {
-    /// Konvertiert ColorName zu einem deutschen String
-    pub fn as_str(self) -> &'static str {
-        match self {
-            ColorName::Red => "Rot",
-            ColorName::Green => "Grün",
-            ColorName::Blue => "Blau",
+pub enum PresetEffectKind {
+    Solid,   // Feste Farbe (rgb)
+    Auto,    // Auto-Rotation
+    Sunrise, // Lichtwecker (speed = Dauer in Minuten)
+}
+
+/// Preset für JSON (HTTP /api/presets)
+///
+/// Beispiel: {"id":1,"name":"Lesen","effect":"solid","rgb":{"r":40,"g":30,"b":18},"brightness":255,"speed":0}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetDto {
+    /// Wird beim Anlegen vom Server vergeben
+    #[serde(default)]
+    pub id: u8,
+    pub name: heapless::String<PRESET_NAME_LEN>,
+    pub effect: PresetEffectKind,
+    /// Farbe (nur bei "solid" erforderlich)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rgb: Option<RgbColor>,
+    #[serde(default = "default_preset_brightness")]
+    pub brightness: u8,
+    /// Tempo des Effekts (Sonnenaufgang: Minuten, Standard SUNRISE_DEFAULT_DURATION_MIN)
+    #[serde(default)]
+    pub speed: u8,
+}
+
+fn default_preset_brightness() -> u8 {
+    u8::MAX
+}
+
+impl From<&Preset> for PresetDto {
+    fn from(preset: &Preset) -> Self {
+        let (effect, rgb) = match preset.effect {
+            PresetEffect::Solid => (
+                PresetEffectKind::Solid,
+                Some(RgbColor {
+                    r: preset.color.r,
+                    g: preset.color.g,
+                    b: preset.color.b,
+                }),
+            ),
+            PresetEffect::Auto => (PresetEffectKind::Auto, None),
+            PresetEffect::Sunrise => (PresetEffectKind::Sunrise, None),
+        };
+        let mut name = heapless::String::new();
+        // Passt immer: beide Seiten begrenzt auf PRESET_NAME_LEN
+        let _ = name.push_str(preset.name.as_str());
+        Self {
+            id: preset.id,
+            name,
+            effect,
+            rgb,
+            brightness: preset.brightness,
+            speed: preset.speed,
+        }
+    }
+}
+
+impl TryFrom<PresetDto> for Preset {
+    type Error = &'static str;
+
+    /// Konvertiert das JSON-Preset, Fehler bei leerem Namen oder fehlender Farbe
+    fn try_from(dto: PresetDto) -> Result<Self, Self::Error> {
+        let name = PresetName::new(&dto.name).ok_or("Missing preset name")?;
+        let (effect, color, speed) = match (dto.effect, dto.rgb) {
+            (PresetEffectKind::Solid, Some(rgb)) => (
+                PresetEffect::Solid,
+                RGB8::new(rgb.r, rgb.g, rgb.b),
+                dto.speed,
+            ),
+            (PresetEffectKind::Solid, None) => return Err("Missing rgb for solid preset"),
+            (PresetEffectKind::Auto, _) => (PresetEffect::Auto, RGB8::default(), dto.speed),
+            (PresetEffectKind::Sunrise, _) => {
+                let speed = match dto.speed {
+                    0 => SUNRISE_DEFAULT_DURATION_MIN,
+                    speed => speed,
+                };
+                (PresetEffect::Sunrise, RGB8::default(), speed)
+            }
+        };
+        Ok(Preset::new(name, color, effect, dto.brightness, speed))
+    }
+}
+
+/// Liste aller Presets (Antwort von GET /api/presets, PUT und DELETE)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PresetList {
+    pub presets: heapless::Vec<PresetDto, PRESET_CAPACITY>,
+}
+
+/// Antwort nach dem Anlegen eines Presets (POST /api/presets)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct PresetCreated {
+    pub id: u8,
+}
+
+/// Fehler-Antwort der JSON-API
+/// Beispiel: {"error":"Schedule full","category":"resource","kind":"capacity_exceeded"}
+///
+/// Kategorie, Art und HTTP-Status stammen aus der gemeinsamen Taxonomie (esp_core::FirmwareError).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ApiError {
+    pub error: &'static str,
+    pub category: &'static str,
+    pub kind: &'static str,
+    #[serde(skip)]
+    pub status: u16,
+}
+
+impl ApiError {
+    /// Fehler-Antwort mit konkreter Meldung für den Client
+    pub fn new(error: FirmwareError, message: &'static str) -> Self {
+        Self {
+            error: message,
+            category: error.category().name(),
+            kind: error.kind.name(),
+            status: error.http_status(),
+        }
+    }
+
+    /// Ungültige Eingabe (400)
+    pub fn invalid_input(message: &'static str) -> Self {
+        Self::new(
+            FirmwareError::new(ErrorSource::Http, ErrorKind::InvalidInput),
+            message,
+        )
+    }
+
+    /// Fehlendes oder falsches Token (401)
+    pub fn unauthorized() -> Self {
+        Self::new(
+            FirmwareError::new(ErrorSource::Http, ErrorKind::Unauthorized),
+            "Unauthorized",
+        )
+    }
+
+    /// Warteschlange des LED-Tasks voll (503)
+    pub fn busy() -> Self {
+        Self::new(
+            FirmwareError::new(ErrorSource::Led, ErrorKind::Busy),
+            "LED busy, try again",
+        )
+    }
+
+    /// Ressource nicht vorhanden (404)
+    pub fn not_found(message: &'static str) -> Self {
+        Self::new(
+            FirmwareError::new(ErrorSource::Http, ErrorKind::NotFound),
+            message,
+        )
+    }
+}
+
+/// Absturz des vorherigen Laufs
+/// Antwort von GET /api/crash:
+/// `{"uptime_ms","build","message","registers":{"ra","sp","fp"},"frames":["0x42001234",...]}`
+///
+/// Adressen als Hex-Strings, direkt nutzbar mit `addr2line -e <elf>`.
+pub struct CrashInfo(pub CrashRecord);
+
+impl Serialize for CrashInfo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let record = &self.0;
+        let registers = CrashRegistersDto {
+            ra: hex_address(record.registers.ra),
+            sp: hex_address(record.registers.sp),
+            fp: hex_address(record.registers.fp),
+        };
+        let mut state = serializer.serialize_struct("CrashInfo", 5)?;
+        state.serialize_field("uptime_ms", &record.uptime_ms)?;
+        state.serialize_field("build", record.build())?;
+        state.serialize_field("message", record.message())?;
+        state.serialize_field("registers", &registers)?;
+        state.serialize_field("frames", &HexFrames(record.frames()))?;
+        state.end()
+    }
+}
+
+/// Register als Hex-Strings (JSON)
+#[derive(Serialize)]
+struct CrashRegistersDto {
+    ra: heapless::String<10>,
+    sp: heapless::String<10>,
+    fp: heapless::String<10>,
+}
+
+/// Rücksprungadressen als JSON-Array von Hex-Strings
+struct HexFrames<'a>(&'a [u32]);
+
+impl Serialize for HexFrames<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|&address| hex_address(address)))
+    }
+}
+
+/// Formatiert eine Adresse als `0x` + 8 Hex-Ziffern
+fn hex_address(address: u32) -> heapless::String<10> {
+    let mut text = heapless::String::new();
+    // 10 Zeichen passen immer
+    let _ = core::fmt::write(&mut text, format_args!("0x{:08x}", address));
+    text
+}
+
+/// Log-Ringpuffer
+/// Antwort von GET /api/logs: `{"dropped":n,"lines":[{"seq","timestamp_ms","level","text"}]}`
+pub struct LogList(pub FirmwareLogRing);
+
+impl LogList {
+    /// Aktueller Inhalt des Ringpuffers
+    pub fn current() -> Self {
+        Self(log_snapshot())
+    }
+}
+
+impl Serialize for LogList {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        /// Zeilen als JSON-Array (ohne Zwischenpuffer)
+        struct Lines<'a>(&'a FirmwareLogRing);
+
+        impl Serialize for Lines<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter().map(|line| LogLineDto {
+                    seq: line.seq,
+                    timestamp_ms: line.timestamp_ms,
+                    level: line.level.as_str(),
+                    text: line.text(),
+                }))
+            }
         }
+
+        let mut state = serializer.serialize_struct("LogList", 2)?;
+        state.serialize_field("dropped", &self.0.dropped())?;
+        state.serialize_field("lines", &Lines(&self.0))?;
+        state.end()
     }
 }
+
+/// Eine Log-Zeile (JSON)
+#[derive(Serialize)]
+struct LogLineDto<'a> {
+    seq: u32,
+    timestamp_ms: u64,
+    level: &'static str,
+    text: &'a str,
+}
@@ -0,0 +1,427 @@
+// WebSocket-Kompression permessage-deflate (Feature `ws-deflate`)
+//
+// picoserve kennt keine WebSocket-Erweiterungen, daher sitzt die Kompression
+// zwischen TCP-Socket und HTTP-Server: `DeflateSocket` liest den ersten
+// Request einer Verbindung mit (Angebot in `Sec-WebSocket-Extensions`),
+// ergänzt die Zusage in der `101 Switching Protocols`-Antwort und schreibt
+// danach die Frames um:
+// - Senden: Text-/Binär-Nachrichten ab WS_DEFLATE_MIN_SIZE komprimiert (RSV1)
+// - Empfangen: komprimierte Nachrichten des Browsers entpackt, picoserve
+//   sieht gewöhnliche Frames
+// Verbindungen ohne Angebot (normale HTTP-Requests, Clients ohne
+// Kompression) laufen unverändert durch. Logik und Tests: esp_core::ws_deflate.
+
+use core::cell::Cell;
+use core::fmt::Write as _;
+
+use defmt::warn;
+use embassy_net::tcp::{Error, TcpReader, TcpSocket, TcpWriter};
+use esp_core::ws_deflate::{
+    DeflateParams, Deflater, EXTENSIONS_HEADER, FrameHeader, MAX_FRAME_HEADER_LEN, OPCODE_BINARY,
+    OPCODE_TEXT, RequestScanner, ResponseScanner, apply_mask, inflate_message, parse_frame_header,
+    write_frame_header,
+};
+use heapless::String;
+use picoserve::io::embedded_io_async::{ErrorType, Read, Write};
+
+use crate::config::{
+    WEBSOCKET_BUFFER_SIZE, WS_DEFLATE_FRAME_SIZE, WS_DEFLATE_HEADER_LINE, WS_DEFLATE_MIN_SIZE,
+    WS_DEFLATE_WINDOW,
+};
+
+/// Empfangene Frames: komprimiert höchstens so groß wie picoserves Nachrichtenpuffer
+const RX_FRAME_SIZE: usize = WEBSOCKET_BUFFER_SIZE + MAX_FRAME_HEADER_LEN;
+const TX_FRAME_SIZE: usize = WS_DEFLATE_FRAME_SIZE + MAX_FRAME_HEADER_LEN;
+
+/// Zustand einer Verbindung, von Lese- und Schreibhälfte geteilt
+#[derive(Clone, Copy)]
+enum Phase {
+    /// Erster Request wird mitgelesen
+    Request,
+    /// Angebot gefunden, Antwort-Kopf wird mitgelesen
+    Response(DeflateParams),
+    /// Upgrade mit Kompression, Frames werden umgeschrieben
+    Frames,
+    /// Keine Kompression auf dieser Verbindung
+    Off,
+}
+
+/// Puffer der Lesehälfte
+struct RxState {
+    scanner: RequestScanner<WS_DEFLATE_HEADER_LINE>,
+    frame: [u8; RX_FRAME_SIZE],
+    out: [u8; RX_FRAME_SIZE],
+    /// Noch an picoserve auszuliefernder Bereich von `out`
+    pending: (usize, usize),
+    /// Restliche Nutzdaten eines unkomprimierten Frames (direkt durchgereicht)
+    passthrough: u64,
+}
+
+/// Puffer der Schreibhälfte
+struct TxState {
+    scanner: ResponseScanner,
+    deflater: Option<Deflater<WS_DEFLATE_WINDOW>>,
+    frame: [u8; TX_FRAME_SIZE],
+    /// Gesammelte Bytes des aktuellen Frames
+    filled: usize,
+    out: [u8; TX_FRAME_SIZE],
+    /// Restliche Bytes eines zu großen Frames (direkt durchgereicht)
+    passthrough: u64,
+}
+
+/// Puffer für eine Verbindung (einmal pro HTTP-Task, ~3.5 KB)
+pub struct DeflateBuffers {
+    phase: Cell<Phase>,
+    rx: RxState,
+    tx: TxState,
+}
+
+impl DeflateBuffers {
+    pub const fn new() -> Self {
+        Self {
+            phase: Cell::new(Phase::Request),
+            rx: RxState {
+                scanner: RequestScanner::new(),
+                frame: [0; RX_FRAME_SIZE],
+                out: [0; RX_FRAME_SIZE],
+                pending: (0, 0),
+                passthrough: 0,
+            },
+            tx: TxState {
+                scanner: ResponseScanner::new(),
+                deflater: None,
+                frame: [0; TX_FRAME_SIZE],
+                filled: 0,
+                out: [0; TX_FRAME_SIZE],
+                passthrough: 0,
+            },
+        }
+    }
+
+    /// Setzt den Zustand für eine neue Verbindung zurück
+    fn reset(&mut self) {
+        self.phase.set(Phase::Request);
+        self.rx.scanner = RequestScanner::new();
+        self.rx.pending = (0, 0);
+        self.rx.passthrough = 0;
+        self.tx.scanner = ResponseScanner::new();
+        self.tx.deflater = None;
+        self.tx.filled = 0;
+        self.tx.passthrough = 0;
+    }
+}
+
+/// TCP-Socket mit permessage-deflate für `picoserve::Server::serve`
+pub struct DeflateSocket<'s, 'b> {
+    socket: TcpSocket<'s>,
+    buffers: &'b mut DeflateBuffers,
+}
+
+impl<'s, 'b> DeflateSocket<'s, 'b> {
+    pub fn new(socket: TcpSocket<'s>, buffers: &'b mut DeflateBuffers) -> Self {
+        buffers.reset();
+        Self { socket, buffers }
+    }
+}
+
+impl picoserve::io::Socket for DeflateSocket<'_, '_> {
+    type Error = Error;
+    type ReadHalf<'a>
+        = DeflateReader<'a>
+    where
+        Self: 'a;
+    type WriteHalf<'a>
+        = DeflateWriter<'a>
+    where
+        Self: 'a;
+
+    fn split(&mut self) -> (Self::ReadHalf<'_>, Self::WriteHalf<'_>) {
+        let (reader, writer) = self.socket.split();
+        let DeflateBuffers { phase, rx, tx } = &mut *self.buffers;
+        let phase = &*phase;
+        (
+            DeflateReader {
+                inner: reader,
+                phase,
+                state: rx,
+            },
+            DeflateWriter {
+                inner: writer,
+                phase,
+                state: tx,
+            },
+        )
+    }
+
+    async fn shutdown<T: picoserve::Timer>(
+        self,
+        timeouts: &picoserve::Timeouts<T::Duration>,
+        timer: &mut T,
+    ) -> Result<(), picoserve::Error<Self::Error>> {
+        picoserve::io::Socket::shutdown(self.socket, timeouts, timer).await
+    }
+}
+
+/// Lesehälfte: entpackt komprimierte Frames des Browsers
+pub struct DeflateReader<'a> {
+    inner: TcpReader<'a>,
+    phase: &'a Cell<Phase>,
+    state: &'a mut RxState,
+}
+
+impl ErrorType for DeflateReader<'_> {
+    type Error = Error;
+}
+
+impl Read for DeflateReader<'_> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        match self.phase.get() {
+            Phase::Request => {
+                let len = self.inner.read(buf).await?;
+                if self.state.scanner.feed(&buf[..len]) {
+                    self.phase.set(match self.state.scanner.offer() {
+                        Some(params) => Phase::Response(params),
+                        None => Phase::Off,
+                    });
+                }
+                Ok(len)
+            }
+            Phase::Frames => self.read_frames(buf).await,
+            Phase::Response(_) | Phase::Off => self.inner.read(buf).await,
+        }
+    }
+}
+
+impl DeflateReader<'_> {
+    async fn read_frames(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let (start, end) = self.state.pending;
+        if start < end {
+            return self.read_frames_pending(buf);
+        }
+        let state = &mut *self.state;
+        if state.passthrough > 0 {
+            let limit = buf
+                .len()
+                .min(usize::try_from(state.passthrough).unwrap_or(usize::MAX));
+            let len = self.inner.read(&mut buf[..limit]).await?;
+            state.passthrough -= len as u64;
+            return Ok(len);
+        }
+
+        // Neuer Frame: erst zwei Bytes, dann Rest des Kopfs je nach Länge und Maske
+        if !read_exact(&mut self.inner, &mut state.frame[..2]).await? {
+            return Ok(0);
+        }
+        let extended = match state.frame[1] & 0x7f {
+            126 => 2,
+            127 => 8,
+            _ => 0,
+        };
+        let mask = if state.frame[1] & 0x80 != 0 { 4 } else { 0 };
+        let header_len = 2 + extended + mask;
+        if !read_exact(&mut self.inner, &mut state.frame[2..header_len]).await? {
+            return Ok(0);
+        }
+        let Some(header) = parse_frame_header(&state.frame[..header_len]) else {
+            return Ok(0);
+        };
+
+        if !header.rsv1 {
+            state.out[..header_len].copy_from_slice(&state.frame[..header_len]);
+            state.pending = (0, header_len);
+            state.passthrough = header.payload_len;
+            return self.read_frames_pending(buf);
+        }
+
+        // Komprimiert: nur vollständige Datennachrichten, die in den Puffer passen
+        let payload_len = usize::try_from(header.payload_len).unwrap_or(usize::MAX);
+        if !header.fin || !header.is_data() || payload_len > RX_FRAME_SIZE - header_len {
+            warn!("HTTP: Compressed WebSocket frame not supported, closing");
+            return Ok(0);
+        }
+        let payload = &mut state.frame[header_len..header_len + payload_len];
+        if !read_exact(&mut self.inner, payload).await? {
+            return Ok(0);
+        }
+        if let Some(mask) = header.mask {
+            apply_mask(payload, mask);
+        }
+        let Ok(len) = inflate_message(payload, &mut state.out[MAX_FRAME_HEADER_LEN..]) else {
+            warn!("HTTP: Invalid compressed WebSocket message, closing");
+            return Ok(0);
+        };
+
+        // Kopf ohne RSV1 direkt vor die entpackten Daten (Maske 0 = unverändert)
+        let mut head = [0u8; MAX_FRAME_HEADER_LEN];
+        let head_len = write_frame_header(
+            &mut head,
+            &FrameHeader {
+                rsv1: false,
+                mask: Some([0; 4]),
+                payload_len: len as u64,
+                ..header
+            },
+        );
+        let start = MAX_FRAME_HEADER_LEN - head_len;
+        state.out[start..MAX_FRAME_HEADER_LEN].copy_from_slice(&head[..head_len]);
+        state.pending = (start, MAX_FRAME_HEADER_LEN + len);
+        self.read_frames_pending(buf)
+    }
+
+    /// Liefert aus dem bereitgelegten Bereich
+    fn read_frames_pending(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let (start, end) = self.state.pending;
+        let len = buf.len().min(end - start);
+        buf[..len].copy_from_slice(&self.state.out[start..start + len]);
+        self.state.pending = (start + len, end);
+        Ok(len)
+    }
+}
+
+/// Liest genau `buf.len()` Bytes, `false` bei Verbindungsende
+async fn read_exact(reader: &mut TcpReader<'_>, buf: &mut [u8]) -> Result<bool, Error> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        let len = reader.read(&mut buf[filled..]).await?;
+        if len == 0 {
+            return Ok(false);
+        }
+        filled += len;
+    }
+    Ok(true)
+}
+
+/// Schreibhälfte: ergänzt die Zusage und komprimiert ausgehende Nachrichten
+pub struct DeflateWriter<'a> {
+    inner: TcpWriter<'a>,
+    phase: &'a Cell<Phase>,
+    state: &'a mut TxState,
+}
+
+impl ErrorType for DeflateWriter<'_> {
+    type Error = Error;
+}
+
+impl Write for DeflateWriter<'_> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        match self.phase.get() {
+            Phase::Response(params) => {
+                self.write_response_head(buf, params).await?;
+                Ok(buf.len())
+            }
+            Phase::Frames => self.write_frames(buf).await,
+            Phase::Request | Phase::Off => self.inner.write(buf).await,
+        }
+    }
+
+    async fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush().await
+    }
+}
+
+impl DeflateWriter<'_> {
+    /// Antwort-Kopf durchreichen, bei `101` die Zusage vor der Leerzeile einfügen
+    async fn write_response_head(
+        &mut self,
+        buf: &[u8],
+        params: DeflateParams,
+    ) -> Result<(), Error> {
+        match self.state.scanner.feed(buf) {
+            Some(index) => {
+                let mut line: String<160> = String::new();
+                // Passt immer: längste Zusage ~100 Zeichen
+                write!(line, "{EXTENSIONS_HEADER}: ").ok();
+                params.write_response(&mut line).ok();
+                line.push_str("\r\n").ok();
+                self.inner.write_all(&buf[..index]).await?;
+                self.inner.write_all(line.as_bytes()).await?;
+                self.inner.write_all(&buf[index..]).await?;
+            }
+            None => self.inner.write_all(buf).await?,
+        }
+        if self.state.scanner.is_finished() {
+            if self.state.scanner.is_upgrade() {
+                self.state.deflater = Some(Deflater::new(params));
+                self.phase.set(Phase::Frames);
+            } else {
+                self.phase.set(Phase::Off);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sammelt Frames und sendet sie (ggf. komprimiert) sobald vollständig
+    async fn write_frames(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let state = &mut *self.state;
+        if state.passthrough > 0 {
+            let limit = buf
+                .len()
+                .min(usize::try_from(state.passthrough).unwrap_or(usize::MAX));
+            let len = self.inner.write(&buf[..limit]).await?;
+            state.passthrough -= len as u64;
+            return Ok(len);
+        }
+
+        let len = buf.len().min(TX_FRAME_SIZE - state.filled);
+        state.frame[state.filled..state.filled + len].copy_from_slice(&buf[..len]);
+        state.filled += len;
+
+        while let Some(header) = parse_frame_header(&state.frame[..state.filled]) {
+            let total = header.header_len as u64 + header.payload_len;
+            if total > TX_FRAME_SIZE as u64 {
+                // Zu groß für den Puffer: unverändert durchreichen
+                self.inner.write_all(&state.frame[..state.filled]).await?;
+                state.passthrough = total - state.filled as u64;
+                state.filled = 0;
+                break;
+            }
+            let total = total as usize;
+            if state.filled < total {
+                break;
+            }
+            send_frame(&mut self.inner, state, &header, total).await?;
+            state.frame.copy_within(total..state.filled, 0);
+            state.filled -= total;
+        }
+        Ok(len)
+    }
+}
+
+/// Sendet einen vollständigen Frame aus `state.frame[..total]`
+async fn send_frame(
+    writer: &mut TcpWriter<'_>,
+    state: &mut TxState,
+    header: &FrameHeader,
+    total: usize,
+) -> Result<(), Error> {
+    let payload = &state.frame[header.header_len..total];
+    // Steuerframes und Fragmente bleiben unkomprimiert
+    let compressible = header.fin
+        && !header.rsv1
+        && matches!(header.opcode, OPCODE_TEXT | OPCODE_BINARY)
+        && payload.len() >= WS_DEFLATE_MIN_SIZE;
+    let compressed = match state.deflater.as_mut() {
+        Some(deflater) if compressible => {
+            deflater.compress(payload, &mut state.out[MAX_FRAME_HEADER_LEN..])
+        }
+        _ => None,
+    };
+    let Some(len) = compressed else {
+        return writer.write_all(&state.frame[..total]).await;
+    };
+
+    let mut head = [0u8; MAX_FRAME_HEADER_LEN];
+    let head_len = write_frame_header(
+        &mut head,
+        &FrameHeader {
+            rsv1: true,
+            payload_len: len as u64,
+            ..*header
+        },
+    );
+    let start = MAX_FRAME_HEADER_LEN - head_len;
+    state.out[start..MAX_FRAME_HEADER_LEN].copy_from_slice(&head[..head_len]);
+    writer
+        .write_all(&state.out[start..MAX_FRAME_HEADER_LEN + len])
+        .await
+}
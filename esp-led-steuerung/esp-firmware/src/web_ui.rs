@@ -0,0 +1,259 @@
+// Web-UI im Flash: per POST /api/ui hochgeladene Oberfläche
+//
+// Die Daten liegen in der "webui" Partition (siehe partitions.csv,
+// WEB_UI_FLASH_OFFSET), Header-Format und Upload-Prüfung in
+// esp_core::ui_bundle. `load()` liest beim Boot den Header und prüft die CRC;
+// solange eine gültige Oberfläche vorhanden ist, liefert GET / sie statt der
+// einkompilierten index.html aus.
+//
+// Upload: ersten Chunk prüfen, Sektoren löschen, Daten in Chunks schreiben,
+// Header zuletzt. Der geteilte Storage wird nur je Sektor bzw. Chunk
+// gesperrt, damit der Scheduler während eines Uploads weiter speichern kann.
+// Bricht die Verbindung ab, bleibt der Header gelöscht und GET / liefert
+// wieder die einkompilierte Seite.
+
+use core::cell::Cell;
+
+use defmt::{info, warn};
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use esp_core::ui_bundle::{UI_BUNDLE_HEADER_LEN, sectors_needed};
+use esp_core::{Crc32, UiBundleHeader, UiEncoding, UiUpload, UiUploadError};
+use picoserve::io::embedded_io_async::{Read, Write};
+
+use crate::SharedStorage;
+use crate::config::{STORAGE_SECTOR_SIZE, WEB_UI_CHUNK_SIZE, WEB_UI_FLASH_SIZE};
+use crate::storage::{Storage, StorageError, WRITE_ALIGN};
+
+/// Platz für Daten in der Partition (ohne Header)
+pub const WEB_UI_CAPACITY: usize = WEB_UI_FLASH_SIZE as usize - UI_BUNDLE_HEADER_LEN;
+
+/// Geteilter Storage für den HTTP-Task (gesetzt von `attach()`)
+static STORAGE: Mutex<CriticalSectionRawMutex, Cell<Option<&'static SharedStorage>>> =
+    Mutex::new(Cell::new(None));
+
+/// Header der gültigen Oberfläche im Flash, `None` = einkompilierte Seite
+static INSTALLED: Mutex<CriticalSectionRawMutex, Cell<Option<UiBundleHeader>>> =
+    Mutex::new(Cell::new(None));
+
+/// Upload oder Löschen läuft (verhindert verschränkte Schreibvorgänge)
+static BUSY: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(false));
+
+/// Fehler beim Hochladen oder Zurücksetzen der Oberfläche
+#[derive(Debug, Clone, Copy)]
+pub enum WebUiError {
+    /// Body ungültig (Größe, Kodierung, abgebrochen)
+    Upload(UiUploadError),
+    /// Flash-Zugriff fehlgeschlagen
+    Storage(StorageError),
+    /// Anderer Upload läuft oder der Storage ist noch nicht verfügbar
+    Busy,
+}
+
+impl From<UiUploadError> for WebUiError {
+    fn from(error: UiUploadError) -> Self {
+        WebUiError::Upload(error)
+    }
+}
+
+impl From<StorageError> for WebUiError {
+    fn from(error: StorageError) -> Self {
+        WebUiError::Storage(error)
+    }
+}
+
+/// Liest den Header beim Boot und prüft die CRC der Daten
+///
+/// Einmal beim Boot aufrufen, bevor der Storage geteilt wird.
+pub fn load(storage: &mut Storage) {
+    let mut buffer = [0u8; WEB_UI_CHUNK_SIZE];
+    if storage
+        .read_web_ui(0, &mut buffer[..UI_BUNDLE_HEADER_LEN])
+        .is_err()
+    {
+        return;
+    }
+    let Some(header) = UiBundleHeader::decode(&buffer, WEB_UI_CAPACITY) else {
+        return;
+    };
+
+    let mut crc = Crc32::new();
+    let len = header.len as usize;
+    let mut offset = 0;
+    while offset < len {
+        let chunk = (len - offset).min(WEB_UI_CHUNK_SIZE);
+        let aligned = chunk.next_multiple_of(WRITE_ALIGN);
+        let address = (UI_BUNDLE_HEADER_LEN + offset) as u32;
+        if storage
+            .read_web_ui(address, &mut buffer[..aligned])
+            .is_err()
+        {
+            return;
+        }
+        crc.update(&buffer[..chunk]);
+        offset += chunk;
+    }
+    if !header.verify(crc.finish()) {
+        warn!("Web UI: Stored UI corrupt, serving built-in page");
+        return;
+    }
+    info!(
+        "Web UI: Serving uploaded UI ({} bytes, {})",
+        header.len,
+        header.encoding.name()
+    );
+    INSTALLED.lock(|installed| installed.set(Some(header)));
+}
+
+/// Stellt dem HTTP-Task den geteilten Storage bereit
+pub fn attach(storage: &'static SharedStorage) {
+    STORAGE.lock(|cell| cell.set(Some(storage)));
+}
+
+/// Header der hochgeladenen Oberfläche, `None` = einkompilierte Seite
+pub fn installed() -> Option<UiBundleHeader> {
+    INSTALLED.lock(Cell::get)
+}
+
+/// Sperre für Upload und Löschen, wird beim Drop freigegeben
+///
+/// Auch wenn der Handler-Future bei einem Verbindungsabbruch verworfen wird.
+struct BusyGuard;
+
+impl BusyGuard {
+    fn acquire() -> Result<Self, WebUiError> {
+        if BUSY.lock(|busy| busy.replace(true)) {
+            return Err(WebUiError::Busy);
+        }
+        Ok(BusyGuard)
+    }
+}
+
+impl Drop for BusyGuard {
+    fn drop(&mut self) {
+        BUSY.lock(|busy| busy.set(false));
+    }
+}
+
+/// Schreibt eine neue Oberfläche aus dem Request-Body in den Flash
+///
+/// `len` ist die `Content-Length`. Ab dem Löschen der Partition liefert
+/// GET / die einkompilierte Seite, bis der Header geschrieben ist.
+pub async fn install<R: Read>(
+    encoding: UiEncoding,
+    len: usize,
+    reader: &mut R,
+) -> Result<UiBundleHeader, WebUiError> {
+    let mut upload = UiUpload::new(encoding, len, WEB_UI_CAPACITY)?;
+    let _guard = BusyGuard::acquire()?;
+    let storage = STORAGE.lock(Cell::get).ok_or(WebUiError::Busy)?;
+
+    let mut buffer = [0u8; WEB_UI_CHUNK_SIZE];
+    while upload.remaining() > 0 {
+        // Chunks bis auf den letzten voll lesen, damit jeder Write aligned beginnt
+        let chunk = upload.remaining().min(WEB_UI_CHUNK_SIZE);
+        read_full(reader, &mut buffer[..chunk]).await?;
+        let offset = (UI_BUNDLE_HEADER_LEN + upload.received()) as u32;
+        upload.push(&buffer[..chunk])?;
+
+        // Erst nach dem ersten geprüften Chunk löschen: ein falscher Upload
+        // (z.B. kein gzip) lässt die bisherige Oberfläche stehen
+        if offset as usize == UI_BUNDLE_HEADER_LEN {
+            INSTALLED.lock(|installed| installed.set(None));
+            for sector in 0..sectors_needed(len, STORAGE_SECTOR_SIZE as usize) {
+                storage.lock().await.erase_web_ui(sector as u32)?;
+            }
+        }
+
+        let aligned = chunk.next_multiple_of(WRITE_ALIGN);
+        buffer[chunk..aligned].fill(0xFF);
+        storage
+            .lock()
+            .await
+            .write_web_ui(offset, &buffer[..aligned])?;
+    }
+
+    let header = upload.finish()?;
+    storage.lock().await.write_web_ui(0, &header.encode())?;
+    INSTALLED.lock(|installed| installed.set(Some(header)));
+    info!(
+        "Web UI: Uploaded UI installed ({} bytes, {})",
+        header.len,
+        header.encoding.name()
+    );
+    Ok(header)
+}
+
+/// Liest genau `buffer.len()` Bytes aus dem Body
+async fn read_full<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), WebUiError> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]).await {
+            Ok(0) | Err(_) => return Err(UiUploadError::LengthMismatch.into()),
+            Ok(n) => filled += n,
+        }
+    }
+    Ok(())
+}
+
+/// Verwirft die hochgeladene Oberfläche (GET / liefert wieder die einkompilierte Seite)
+pub async fn remove() -> Result<(), WebUiError> {
+    let _guard = BusyGuard::acquire()?;
+    let storage = STORAGE.lock(Cell::get).ok_or(WebUiError::Busy)?;
+    INSTALLED.lock(|installed| installed.set(None));
+    // Gelöschter Header genügt, die Daten werden beim nächsten Upload überschrieben
+    storage.lock().await.erase_web_ui(0)?;
+    info!("Web UI: Reverted to built-in UI");
+    Ok(())
+}
+
+/// Löscht den Header ohne Sperren (Werksreset, Storage bereits gesperrt)
+pub fn erase(storage: &mut Storage) -> Result<(), StorageError> {
+    INSTALLED.lock(|installed| installed.set(None));
+    storage.erase_web_ui(0)
+}
+
+/// Body von GET / aus dem Flash (Chunk für Chunk, Content-Length aus dem Header)
+pub struct FlashUi(pub UiBundleHeader);
+
+impl picoserve::response::Content for FlashUi {
+    fn content_type(&self) -> &'static str {
+        "text/html; charset=utf-8"
+    }
+
+    fn content_length(&self) -> usize {
+        self.0.len as usize
+    }
+
+    async fn write_content<W: Write>(self, mut writer: W) -> Result<(), W::Error> {
+        let Some(storage) = STORAGE.lock(Cell::get) else {
+            return Ok(());
+        };
+        let mut buffer = [0u8; WEB_UI_CHUNK_SIZE];
+        let len = self.0.len as usize;
+        let mut offset = 0;
+        while offset < len {
+            // Neuer Upload oder Löschen während der Auslieferung: abbrechen,
+            // der Browser erkennt die zu kurze Antwort an der Content-Length
+            if installed() != Some(self.0) {
+                warn!("Web UI: UI replaced while serving, response truncated");
+                return Ok(());
+            }
+            let chunk = (len - offset).min(WEB_UI_CHUNK_SIZE);
+            let aligned = chunk.next_multiple_of(WRITE_ALIGN);
+            let address = (UI_BUNDLE_HEADER_LEN + offset) as u32;
+            if storage
+                .lock()
+                .await
+                .read_web_ui(address, &mut buffer[..aligned])
+                .is_err()
+            {
+                warn!("Web UI: Flash read failed, response truncated");
+                return Ok(());
+            }
+            writer.write_all(&buffer[..chunk]).await?;
+            offset += chunk;
+        }
+        Ok(())
+    }
+}
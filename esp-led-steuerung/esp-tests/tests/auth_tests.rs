@@ -0,0 +1,47 @@
+//! Integration Tests für die Token-Prüfung (WebSocket `auth`, HTTP `Authorization`)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{bearer_matches, token_matches};
+
+#[test]
+fn test_token_matches_exact() {
+    assert!(token_matches("geheim", "geheim"));
+}
+
+#[test]
+fn test_token_rejects_wrong_token() {
+    assert!(!token_matches("geheim", "geheiM"));
+    assert!(!token_matches("geheim", "xeheim"));
+}
+
+#[test]
+fn test_token_rejects_prefix_and_extension() {
+    assert!(!token_matches("geheim", "geh"));
+    assert!(!token_matches("geheim", "geheim2"));
+    assert!(!token_matches("geheim", ""));
+}
+
+#[test]
+fn test_token_rejects_trailing_zero_bytes() {
+    // Fehlende Bytes werden intern mit 0 verglichen, die Länge muss trotzdem stimmen
+    assert!(!token_matches("ab\0", "ab"));
+    assert!(!token_matches("ab", "ab\0"));
+}
+
+#[test]
+fn test_bearer_header_matches() {
+    assert!(bearer_matches("geheim", b"Bearer geheim"));
+    assert!(bearer_matches("geheim", b"bearer geheim"));
+    assert!(bearer_matches("geheim", b"BEARER  geheim "));
+}
+
+#[test]
+fn test_bearer_header_rejects_other_schemes_and_tokens() {
+    assert!(!bearer_matches("geheim", b"Bearer Geheim"));
+    assert!(!bearer_matches("geheim", b"Basic geheim"));
+    assert!(!bearer_matches("geheim", b"geheim"));
+    assert!(!bearer_matches("geheim", b"Bearer"));
+    assert!(!bearer_matches("geheim", b""));
+    assert!(!bearer_matches("geheim", b"Bearer \xffgeheim"));
+}
@@ -0,0 +1,54 @@
+//! Integration Tests für die wahrnehmungsgerechte Helligkeitskurve (CIE 1931)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::perceived_brightness;
+
+/// Referenzwerte: `round(Y · 255)` mit L* = level / 255 · 100
+const REFERENCE: [(u8, u8); 10] = [
+    (0, 0),
+    (10, 1),
+    (20, 2),
+    (21, 2),
+    (32, 4),
+    (64, 11),
+    (128, 47),
+    (192, 124),
+    (230, 196),
+    (255, 255),
+];
+
+#[test]
+fn test_matches_reference_values() {
+    for (level, expected) in REFERENCE {
+        assert_eq!(perceived_brightness(level), expected, "level {level}");
+    }
+}
+
+#[test]
+fn test_matches_float_formula_for_all_levels() {
+    for level in 1..=u8::MAX {
+        let lightness = level as f64 / 255.0 * 100.0;
+        let luminance = if lightness <= 8.0 {
+            lightness / 903.3
+        } else {
+            ((lightness + 16.0) / 116.0).powi(3)
+        };
+        let expected = ((luminance * 255.0).round() as u8).max(1);
+        assert_eq!(perceived_brightness(level), expected, "level {level}");
+    }
+}
+
+#[test]
+fn test_is_monotonic() {
+    for level in 1..=u8::MAX {
+        assert!(perceived_brightness(level) >= perceived_brightness(level - 1));
+    }
+}
+
+#[test]
+fn test_small_levels_stay_on() {
+    assert_eq!(perceived_brightness(0), 0);
+    assert_eq!(perceived_brightness(1), 1);
+    assert_eq!(perceived_brightness(5), 1);
+}
@@ -0,0 +1,89 @@
+//! Integration Tests für die MQTT-Broker-Liste (Parsen, Umschaltung)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::broker::fallback_brokers;
+use esp_core::{BrokerAddress, BrokerRotation};
+
+#[test]
+fn test_parse_broker_address() {
+    assert_eq!(
+        BrokerAddress::parse("mqtt2.home"),
+        Some(BrokerAddress {
+            host: "mqtt2.home",
+            port: None
+        })
+    );
+    assert_eq!(
+        BrokerAddress::parse(" 192.168.1.20:1884 "),
+        Some(BrokerAddress {
+            host: "192.168.1.20",
+            port: Some(1884)
+        })
+    );
+    assert_eq!(BrokerAddress::parse(""), None);
+    assert_eq!(BrokerAddress::parse(":1883"), None);
+    assert_eq!(BrokerAddress::parse("mqtt.home:"), None);
+    assert_eq!(BrokerAddress::parse("mqtt.home:0"), None);
+    assert_eq!(BrokerAddress::parse("mqtt.home:70000"), None);
+}
+
+#[test]
+fn test_fallback_list_skips_invalid_entries() {
+    let list = "mqtt2.home, ,bad:port,192.168.1.20:1884,";
+    let hosts: Vec<_> = fallback_brokers(list).map(|broker| broker.host).collect();
+    assert_eq!(hosts, ["mqtt2.home", "192.168.1.20"]);
+    assert_eq!(fallback_brokers("").count(), 0);
+}
+
+#[test]
+fn test_rotation_switches_after_repeated_failures() {
+    let mut rotation = BrokerRotation::new(3, 3);
+    assert_eq!(rotation.index(), 0);
+
+    assert_eq!(rotation.failed(), None);
+    assert_eq!(rotation.failed(), None);
+    assert_eq!(rotation.failed(), Some(1));
+    assert_eq!(rotation.index(), 1);
+    assert_eq!(rotation.failures(), 0);
+
+    for _ in 0..2 {
+        assert_eq!(rotation.failed(), None);
+    }
+    assert_eq!(rotation.failed(), Some(2));
+
+    // Nach dem letzten Fallback wieder der primäre Broker
+    for _ in 0..2 {
+        assert_eq!(rotation.failed(), None);
+    }
+    assert_eq!(rotation.failed(), Some(0));
+}
+
+#[test]
+fn test_connect_resets_failures() {
+    let mut rotation = BrokerRotation::new(2, 3);
+    rotation.failed();
+    rotation.failed();
+    rotation.connected();
+    assert_eq!(rotation.failures(), 0);
+
+    // Verbindungsabbrüche nach erfolgreichem Connect wechseln nicht
+    for _ in 0..5 {
+        assert_eq!(rotation.failed(), None);
+        rotation.connected();
+    }
+    assert_eq!(rotation.index(), 0);
+}
+
+#[test]
+fn test_single_broker_never_switches() {
+    let mut rotation = BrokerRotation::new(1, 1);
+    for _ in 0..10 {
+        assert_eq!(rotation.failed(), None);
+    }
+    assert_eq!(rotation.index(), 0);
+
+    // Ungültige Parameter werden auf 1 angehoben
+    let rotation = BrokerRotation::new(0, 0);
+    assert_eq!(rotation.count(), 1);
+}
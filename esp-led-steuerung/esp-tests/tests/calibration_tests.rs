@@ -0,0 +1,95 @@
+//! Integration Tests für den Weißabgleich (Kalibrierung und Testmuster)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::calibration::{CALIBRATION_COLORS, CALIBRATION_LEN, CALIBRATION_STEP_MS};
+use esp_core::{
+    Calibration, CalibrationPattern, Effect, LedCommand, LedState, LedStateConfig, RecordKind,
+    decode_record, encode_record, parse_text_command,
+};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 128,
+    sleep_fade_ms: 60_000,
+    brightness_fade_ms: 0,
+};
+
+#[test]
+fn test_neutral_calibration_keeps_color() {
+    let color = RGB8::new(200, 100, 7);
+    assert_eq!(Calibration::default(), Calibration::NEUTRAL);
+    assert_eq!(Calibration::NEUTRAL.apply(color), color);
+}
+
+#[test]
+fn test_calibration_scales_each_channel() {
+    let calibration = Calibration::new(255, 128, 0);
+    assert_eq!(
+        calibration.apply(RGB8::new(200, 200, 200)),
+        RGB8::new(200, 100, 0)
+    );
+    assert_eq!(calibration.apply(RGB8::default()), RGB8::default());
+}
+
+#[test]
+fn test_calibration_bytes_roundtrip() {
+    let calibration = Calibration::new(255, 235, 210);
+    let bytes = calibration.to_bytes();
+    assert_eq!(bytes.len(), CALIBRATION_LEN);
+    assert_eq!(Calibration::from_bytes(&bytes), Some(calibration));
+    assert_eq!(Calibration::from_bytes(&bytes[..2]), None);
+
+    let mut record = [0u8; 32];
+    let total = encode_record(RecordKind::Calibration, &bytes, &mut record).unwrap();
+    let loaded = decode_record(&record[..total], RecordKind::Calibration).unwrap();
+    assert_eq!(Calibration::from_bytes(loaded), Some(calibration));
+    assert!(decode_record(&record[..total], RecordKind::Presets).is_none());
+}
+
+#[test]
+fn test_pattern_cycles_through_test_colors() {
+    let pattern = CalibrationPattern::new(1000, 255);
+    assert_eq!(pattern.color_at(1000), CALIBRATION_COLORS[0]);
+    assert_eq!(
+        pattern.color_at(1000 + u64::from(CALIBRATION_STEP_MS)),
+        CALIBRATION_COLORS[1]
+    );
+
+    // Nach allen Farben wieder von vorne
+    let cycle = u64::from(CALIBRATION_STEP_MS) * CALIBRATION_COLORS.len() as u64;
+    assert_eq!(pattern.step_at(1000 + cycle), 0);
+
+    // Helligkeit skaliert die Testfarben
+    let dimmed = CalibrationPattern::new(0, 128);
+    assert_eq!(dimmed.color_at(0), RGB8::new(128, 128, 128));
+}
+
+#[test]
+fn test_calibrate_command_runs_until_next_command() {
+    assert!(matches!(
+        parse_text_command("Kalibrieren"),
+        Ok(LedCommand::Calibrate)
+    ));
+    assert!(matches!(
+        parse_text_command("calibrate"),
+        Ok(LedCommand::Calibrate)
+    ));
+
+    let mut state = LedState::new(CONFIG);
+    assert!(state.apply(LedCommand::Calibrate, 0).color_changed);
+    assert!(matches!(state.effect, Some(Effect::Calibration(_))));
+    assert!(!state.auto_rotate);
+    assert_eq!(state.color, RGB8::new(128, 128, 128));
+
+    // Broadcast nur beim Wechsel der Testfarbe, Effekt endet nicht von selbst
+    assert!(!state.tick(100).color_changed);
+    let step = state.tick(u64::from(CALIBRATION_STEP_MS));
+    assert!(step.color_changed);
+    assert!(!step.effect_finished);
+    assert_eq!(state.color, RGB8::new(128, 0, 0));
+
+    state.apply(LedCommand::Off, u64::from(CALIBRATION_STEP_MS));
+    assert!(state.effect.is_none());
+}
@@ -0,0 +1,149 @@
+//! Integration Tests für die Uhrzeit-Anzeige (Matrix und Binäruhr)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::text::FONT_HEIGHT;
+use esp_core::time::Weekday;
+use esp_core::{
+    ClockFace, ClockFormat, CommandParseError, LedCommand, LocalTime, MatrixLayout,
+    parse_text_command,
+};
+use rgb::RGB8;
+
+const OFF: RGB8 = RGB8::new(0, 0, 0);
+const HOUR: RGB8 = RGB8::new(1, 0, 0);
+const MINUTE: RGB8 = RGB8::new(0, 1, 0);
+const SECOND: RGB8 = RGB8::new(0, 0, 1);
+
+const FACE: ClockFace = ClockFace {
+    hour_color: HOUR,
+    minute_color: MINUTE,
+    second_color: SECOND,
+    format: ClockFormat::H24,
+};
+
+fn time(hour: u8, minute: u8, second: u8) -> LocalTime {
+    LocalTime {
+        weekday: Weekday::Monday,
+        hour,
+        minute,
+        second,
+    }
+}
+
+fn count(pixels: &[RGB8], color: RGB8) -> usize {
+    pixels.iter().filter(|&&p| p == color).count()
+}
+
+#[test]
+fn test_display_hour_formats() {
+    assert_eq!(ClockFormat::H24.display_hour(0), 0);
+    assert_eq!(ClockFormat::H24.display_hour(23), 23);
+    assert_eq!(ClockFormat::H12.display_hour(0), 12);
+    assert_eq!(ClockFormat::H12.display_hour(12), 12);
+    assert_eq!(ClockFormat::H12.display_hour(13), 1);
+}
+
+#[test]
+fn test_time_text() {
+    let mut buf = [0; 5];
+    assert_eq!(FACE.time_text(&time(7, 5, 0), &mut buf), ("07:05", 2));
+    let h12 = FACE.with_format(ClockFormat::H12);
+    assert_eq!(h12.time_text(&time(19, 30, 0), &mut buf), ("7:30", 1));
+    assert_eq!(h12.time_text(&time(23, 59, 0), &mut buf), ("11:59", 2));
+}
+
+#[test]
+fn test_binary_clock_on_strip() {
+    // 13:37:42 → 01101 | 100101 | 101010
+    let mut pixels = [OFF; 17];
+    FACE.render(
+        &time(13, 37, 42),
+        MatrixLayout::new(17, 1, false),
+        &mut pixels,
+    );
+    let expected = [
+        OFF, HOUR, HOUR, OFF, HOUR, // Stunden
+        MINUTE, OFF, OFF, MINUTE, OFF, MINUTE, // Minuten
+        SECOND, OFF, SECOND, OFF, SECOND, OFF, // Sekunden
+    ];
+    assert_eq!(pixels, expected);
+}
+
+#[test]
+fn test_binary_clock_12h_and_short_strip() {
+    // 12 h: 4 Stunden-Bits, 13 Uhr → 0001
+    let face = FACE.with_format(ClockFormat::H12);
+    let mut pixels = [HOUR; 5];
+    face.render_binary(&time(13, 32, 0), &mut pixels);
+    assert_eq!(pixels, [OFF, OFF, OFF, HOUR, MINUTE]);
+
+    // Kürzerer Strip zeigt nur die ersten Bits
+    let mut pixels = [OFF; 2];
+    FACE.render_binary(&time(23, 0, 0), &mut pixels);
+    assert_eq!(pixels, [HOUR, OFF]);
+}
+
+#[test]
+fn test_single_line_with_blinking_colon() {
+    let layout = MatrixLayout::new(32, 8, true);
+    let mut even = [OFF; 32 * 8];
+    let mut odd = [OFF; 32 * 8];
+    FACE.render(&time(12, 34, 0), layout, &mut even);
+    FACE.render(&time(12, 34, 1), layout, &mut odd);
+
+    assert!(count(&even, HOUR) > 0 && count(&even, MINUTE) > 0);
+    // Doppelpunkt (2x4 Pixel) nur in geraden Sekunden
+    assert_eq!(count(&even, SECOND), 8);
+    assert_eq!(count(&odd, SECOND), 0);
+    assert_eq!(count(&even, HOUR), count(&odd, HOUR));
+
+    // Stunden links, Minuten rechts
+    let first_minute = even.iter().position(|&p| p == MINUTE).unwrap();
+    assert!(layout.index(16, 0).unwrap() <= first_minute);
+}
+
+#[test]
+fn test_two_lines_on_square_matrix() {
+    let layout = MatrixLayout::new(16, 16, false);
+    let mut pixels = [OFF; 16 * 16];
+    FACE.render(&time(8, 15, 0), layout, &mut pixels);
+    let rows_with = |color: RGB8| {
+        (0..16)
+            .filter(|&y| (0..16).any(|x| pixels[layout.index(x, y).unwrap()] == color))
+            .count()
+    };
+    // Stunden oben, Minuten darunter, kein Doppelpunkt
+    assert!(rows_with(HOUR) <= FONT_HEIGHT && rows_with(MINUTE) <= FONT_HEIGHT);
+    let last_hour_row = (0..16)
+        .filter(|&y| (0..16).any(|x| pixels[layout.index(x, y).unwrap()] == HOUR))
+        .max()
+        .unwrap();
+    let first_minute = pixels.iter().position(|&p| p == MINUTE).unwrap();
+    assert!(first_minute / 16 > last_hour_row);
+    assert_eq!(count(&pixels, SECOND), 0);
+}
+
+#[test]
+fn test_parse_clock_command() {
+    assert!(matches!(
+        parse_text_command("Uhr"),
+        Ok(LedCommand::ShowClock { format: None })
+    ));
+    assert!(matches!(
+        parse_text_command("clock 12"),
+        Ok(LedCommand::ShowClock {
+            format: Some(ClockFormat::H12)
+        })
+    ));
+    assert!(matches!(
+        parse_text_command("uhr 24"),
+        Ok(LedCommand::ShowClock {
+            format: Some(ClockFormat::H24)
+        })
+    ));
+    assert!(matches!(
+        parse_text_command("uhr 13"),
+        Err(CommandParseError::InvalidArgument)
+    ));
+}
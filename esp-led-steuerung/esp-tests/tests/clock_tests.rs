@@ -0,0 +1,157 @@
+//! Integration Tests für zeitabhängige Logik mit MockClock
+//!
+//! Diese Tests laufen auf dem Host (x86_64), die Zeit wird manuell vorgestellt
+
+use esp_core::mock::MockClock;
+use esp_core::{
+    Clock, ColorId, Debouncer, LedCommand, LedState, LedStateConfig, Press, PressDetector, Sunrise,
+};
+use rgb::RGB8;
+
+// ============================================================================
+// Tests: MockClock
+// ============================================================================
+
+#[test]
+fn test_mock_clock_advance() {
+    let clock = MockClock::new(1000);
+    assert_eq!(clock.now_ms(), 1000);
+    clock.advance(250);
+    assert_eq!(clock.now_ms(), 1250);
+    // Auch über Referenz nutzbar (z.B. geteilte Uhr für mehrere Komponenten)
+    fn read(clock: impl Clock) -> u64 {
+        clock.now_ms()
+    }
+    assert_eq!(read(&clock), 1250);
+}
+
+// ============================================================================
+// Tests: Debouncer
+// ============================================================================
+
+#[test]
+fn test_debouncer_ignores_bounces() {
+    let clock = MockClock::default();
+    let mut input = Debouncer::new(false, 50);
+
+    // Prellen: kurze Impulse werden ignoriert
+    for _ in 0..5 {
+        assert_eq!(input.update(true, &clock), None);
+        clock.advance(10);
+        assert_eq!(input.update(false, &clock), None);
+        clock.advance(10);
+    }
+    assert!(!input.state());
+
+    // Stabiler Pegel nach Beruhigungszeit
+    assert_eq!(input.update(true, &clock), None);
+    clock.advance(49);
+    assert_eq!(input.update(true, &clock), None);
+    clock.advance(1);
+    assert_eq!(input.update(true, &clock), Some(true));
+    assert!(input.state());
+
+    // Keine erneute Meldung ohne Änderung
+    clock.advance(100);
+    assert_eq!(input.update(true, &clock), None);
+}
+
+#[test]
+fn test_debouncer_release() {
+    let clock = MockClock::new(5000);
+    let mut input = Debouncer::new(true, 20);
+    assert_eq!(input.update(false, &clock), None);
+    clock.advance(20);
+    assert_eq!(input.update(false, &clock), Some(false));
+}
+
+// ============================================================================
+// Tests: PressDetector
+// ============================================================================
+
+#[test]
+fn test_short_press_reported_on_release() {
+    let clock = MockClock::default();
+    let mut press = PressDetector::new(10_000);
+    assert_eq!(press.update(Some(true), &clock), None);
+    clock.advance(300);
+    assert_eq!(press.update(None, &clock), None);
+    assert_eq!(press.update(Some(false), &clock), Some(Press::Short));
+    // Ohne Druck keine weiteren Meldungen
+    clock.advance(20_000);
+    assert_eq!(press.update(None, &clock), None);
+}
+
+#[test]
+fn test_long_press_reported_while_held() {
+    let clock = MockClock::new(1000);
+    let mut press = PressDetector::new(10_000);
+    press.update(Some(true), &clock);
+    clock.advance(9_999);
+    assert_eq!(press.update(None, &clock), None);
+    clock.advance(1);
+    assert_eq!(press.update(None, &clock), Some(Press::Long));
+
+    // Nur einmal melden, beim Loslassen kein kurzer Druck
+    clock.advance(5_000);
+    assert_eq!(press.update(None, &clock), None);
+    assert_eq!(press.update(Some(false), &clock), None);
+
+    // Nächster Druck wird wieder normal ausgewertet
+    press.update(Some(true), &clock);
+    assert_eq!(press.update(Some(false), &clock), Some(Press::Short));
+}
+
+#[test]
+fn test_release_without_press_is_ignored() {
+    let clock = MockClock::default();
+    let mut press = PressDetector::new(1_000);
+    // Taster beim Start gedrückt gehalten: Startpegel ist kein Druck
+    assert_eq!(press.update(Some(false), &clock), None);
+}
+
+// ============================================================================
+// Tests: Verläufe mit MockClock
+// ============================================================================
+
+#[test]
+fn test_sunrise_with_clock() {
+    let clock = MockClock::new(10_000);
+    let sunrise = Sunrise::new(clock.now_ms(), 60_000, 128);
+
+    let start = sunrise.color_at(clock.now_ms());
+    clock.advance(30_000);
+    assert!(!sunrise.is_finished(clock.now_ms()));
+    assert_ne!(sunrise.color_at(clock.now_ms()), start);
+    clock.advance(30_000);
+    assert!(sunrise.is_finished(clock.now_ms()));
+    assert_eq!(sunrise.color_at(clock.now_ms()), sunrise.final_color());
+}
+
+#[test]
+fn test_led_state_sleep_timer_with_clock() {
+    let clock = MockClock::default();
+    let mut state = LedState::new(LedStateConfig {
+        base_brightness: 10,
+        sunrise_max_brightness: 128,
+        sleep_fade_ms: 60_000,
+        brightness_fade_ms: 0,
+    });
+    state.apply(LedCommand::Off, clock.now_ms());
+    state.apply(
+        LedCommand::SetColor {
+            target_color: RGB8::new(0, 200, 0),
+            color_id: ColorId::Green,
+        },
+        clock.now_ms(),
+    );
+    state.apply(LedCommand::SleepTimer { minutes: 1 }, clock.now_ms());
+
+    // Gesamte Dauer ist Ausblendphase (Fade auf Dauer begrenzt)
+    clock.advance(30_000);
+    assert_eq!(state.output(clock.now_ms()), RGB8::new(0, 100, 0));
+
+    clock.advance(30_000);
+    assert!(state.tick(clock.now_ms()).sleep_expired);
+    assert_eq!(state.output(clock.now_ms()), RGB8::default());
+}
@@ -0,0 +1,131 @@
+//! Integration Tests für den CoAP-Codec und die Observer-Liste
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::coap::{code, content_format};
+use esp_core::{CoapError, CoapResponse, CoapType, Observers, decode_coap_message};
+
+/// CON GET /led mit Token 0xAB, Observe=0 (Registrierung)
+const OBSERVE_LED: [u8; 10] = [
+    0x41, 0x01, 0x12, 0x34, // Header: Ver 1, CON, TKL 1, GET, MID 0x1234
+    0xAB, // Token
+    0x60, // Observe (Delta 6, Länge 0 = Wert 0)
+    0x53, b'l', b'e', b'd', // Uri-Path "led" (Delta 5)
+];
+
+#[test]
+fn test_decode_observe_get() {
+    let request = decode_coap_message(&OBSERVE_LED).unwrap();
+    assert_eq!(request.msg_type, CoapType::Confirmable);
+    assert_eq!(request.code, code::GET);
+    assert_eq!(request.message_id, 0x1234);
+    assert_eq!(request.token, &[0xAB]);
+    assert_eq!(request.observe, Some(0));
+    assert!(request.path_is(&["led"]));
+    assert!(!request.path_is(&["led", "x"]));
+    assert!(request.payload.is_empty());
+}
+
+#[test]
+fn test_decode_put_with_payload_and_multi_segment_path() {
+    let packet = [
+        0x50, 0x03, 0x00, 0x07, // NON PUT, TKL 0, MID 7
+        0xBB, b'.', b'w', b'e', b'l', b'l', b'-', b'k', b'n', b'o', b'w', b'n', // Uri-Path
+        0x04, b'c', b'o', b'r', b'e', // Uri-Path (Delta 0)
+        0x11, 0x00, // Content-Format text/plain (Delta 1)
+        0xFF, b'a', b'u', b't', b'o',
+    ];
+    let request = decode_coap_message(&packet).unwrap();
+    assert_eq!(request.msg_type, CoapType::NonConfirmable);
+    assert_eq!(request.code, code::PUT);
+    assert!(request.path_is(&[".well-known", "core"]));
+    assert_eq!(request.content_format, Some(content_format::TEXT_PLAIN));
+    assert_eq!(request.payload, b"auto");
+}
+
+#[test]
+fn test_decode_rejects_invalid_messages() {
+    assert_eq!(
+        decode_coap_message(&[0x40, 0x01]),
+        Err(CoapError::Truncated)
+    );
+    // Version 2
+    assert_eq!(
+        decode_coap_message(&[0x80, 0x01, 0, 0]),
+        Err(CoapError::UnsupportedVersion)
+    );
+    // Token-Länge 9
+    assert_eq!(
+        decode_coap_message(&[0x49, 0x01, 0, 0]),
+        Err(CoapError::MalformedOption)
+    );
+    // Payload-Marker ohne Payload
+    assert_eq!(
+        decode_coap_message(&[0x40, 0x01, 0, 0, 0xFF]),
+        Err(CoapError::Truncated)
+    );
+    // Unbekannte kritische Option 9
+    assert_eq!(
+        decode_coap_message(&[0x40, 0x01, 0, 0, 0x90]),
+        Err(CoapError::UnknownCriticalOption)
+    );
+}
+
+#[test]
+fn test_encode_notification_roundtrip() {
+    let response = CoapResponse {
+        msg_type: CoapType::NonConfirmable,
+        code: code::CONTENT,
+        message_id: 0x0102,
+        token: &[0xAB, 0xCD],
+        observe: Some(0x0123),
+        content_format: Some(content_format::JSON),
+        payload: b"{}",
+    };
+    let mut buf = [0u8; 32];
+    let len = response.encode(&mut buf).unwrap();
+    assert_eq!(
+        &buf[..len],
+        &[
+            0x52, 0x45, 0x01, 0x02, 0xAB, 0xCD, // Header + Token
+            0x62, 0x01, 0x23, // Observe = 0x0123
+            0x61, 50, // Content-Format JSON (Delta 6)
+            0xFF, b'{', b'}',
+        ]
+    );
+
+    let decoded = decode_coap_message(&buf[..len]).unwrap();
+    assert_eq!(decoded.observe, Some(0x0123));
+    assert_eq!(decoded.content_format, Some(content_format::JSON));
+    assert_eq!(decoded.token, &[0xAB, 0xCD]);
+    assert_eq!(decoded.payload, b"{}");
+
+    assert_eq!(
+        response.encode(&mut [0u8; 8]),
+        Err(CoapError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn test_observers_register_replace_and_remove() {
+    let mut observers: Observers<u16, 2> = Observers::new();
+    assert!(observers.is_empty());
+
+    assert!(observers.register(1, &[0x01]));
+    assert!(observers.register(2, &[0x02]));
+    // Erneute Registrierung ersetzt das Token
+    assert!(observers.register(1, &[0x11, 0x12]));
+    assert_eq!(observers.len(), 2);
+    // Liste voll
+    assert!(!observers.register(3, &[0x03]));
+
+    let first = observers.iter().find(|o| o.endpoint == 1).unwrap();
+    assert_eq!(first.token(), &[0x11, 0x12]);
+
+    assert!(observers.remove(1));
+    assert!(!observers.remove(1));
+    assert!(observers.register(3, &[0x03]));
+
+    assert_eq!(observers.next_sequence(), 1);
+    assert_eq!(observers.next_sequence(), 2);
+}
@@ -0,0 +1,122 @@
+//! Integration Tests für die Konsolen-Kommandos
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{
+    ColorId, CommandParseError, ConsoleCommand, LedCommand, LogLevel, TraceAction,
+    parse_console_command,
+};
+
+#[test]
+fn test_parse_system_commands() {
+    assert!(matches!(
+        parse_console_command("status"),
+        Ok(ConsoleCommand::Status)
+    ));
+    assert!(matches!(
+        parse_console_command("  HEAP \r"),
+        Ok(ConsoleCommand::Heap)
+    ));
+    assert!(matches!(
+        parse_console_command("dns"),
+        Ok(ConsoleCommand::Dns)
+    ));
+    assert!(matches!(
+        parse_console_command("net"),
+        Ok(ConsoleCommand::Net)
+    ));
+    assert!(matches!(
+        parse_console_command("reboot"),
+        Ok(ConsoleCommand::Reboot)
+    ));
+    assert!(matches!(
+        parse_console_command("?"),
+        Ok(ConsoleCommand::Help)
+    ));
+    assert!(matches!(
+        parse_console_command(""),
+        Err(CommandParseError::Empty)
+    ));
+    assert!(matches!(
+        parse_console_command("format c:"),
+        Err(CommandParseError::Unknown)
+    ));
+}
+
+#[test]
+fn test_parse_loglevel() {
+    assert!(matches!(
+        parse_console_command("loglevel"),
+        Ok(ConsoleCommand::LogLevel(None))
+    ));
+    assert!(matches!(
+        parse_console_command("loglevel Debug"),
+        Ok(ConsoleCommand::LogLevel(Some(LogLevel::Debug)))
+    ));
+    assert!(matches!(
+        parse_console_command("loglevel verbose"),
+        Err(CommandParseError::InvalidArgument)
+    ));
+
+    for level in LogLevel::ALL {
+        assert_eq!(LogLevel::from_u8(level as u8), Some(level));
+        assert_eq!(LogLevel::from_name(level.as_str()), Some(level));
+    }
+    assert_eq!(LogLevel::from_u8(5), None);
+    assert!(LogLevel::Error < LogLevel::Trace);
+}
+
+#[test]
+fn test_parse_trace() {
+    assert!(matches!(
+        parse_console_command("trace"),
+        Ok(ConsoleCommand::Trace(TraceAction::Show))
+    ));
+    assert!(matches!(
+        parse_console_command("trace on"),
+        Ok(ConsoleCommand::Trace(TraceAction::On))
+    ));
+    assert!(matches!(
+        parse_console_command("TRACE Off"),
+        Ok(ConsoleCommand::Trace(TraceAction::Off))
+    ));
+    assert!(matches!(
+        parse_console_command("trace verbose"),
+        Err(CommandParseError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn test_parse_set_commands() {
+    assert!(matches!(
+        parse_console_command("set color rot"),
+        Ok(ConsoleCommand::Led(LedCommand::SetColor {
+            color_id: ColorId::Red,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        parse_console_command("set mode auto"),
+        Ok(ConsoleCommand::Led(LedCommand::EnableAuto))
+    ));
+    assert!(matches!(
+        parse_console_command("set sleep 30"),
+        Ok(ConsoleCommand::Led(LedCommand::SleepTimer { minutes: 30 }))
+    ));
+    // Text-Kommandos auch ohne `set`
+    assert!(matches!(
+        parse_console_command("blau"),
+        Ok(ConsoleCommand::Led(LedCommand::SetColor {
+            color_id: ColorId::Blue,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        parse_console_command("set color"),
+        Err(CommandParseError::InvalidArgument)
+    ));
+    assert!(matches!(
+        parse_console_command("set color lila"),
+        Err(CommandParseError::Unknown)
+    ));
+}
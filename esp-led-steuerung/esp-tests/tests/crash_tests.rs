@@ -0,0 +1,87 @@
+//! Integration Tests für das Absturz-Protokoll (esp_core::crash)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::crash::{CRASH_MAX_FRAMES, CRASH_MESSAGE_LEN, CRASH_RECORD_MAX_LEN};
+use esp_core::{CrashRecord, CrashRegisters, RecordKind, decode_record, encode_record};
+
+fn sample_record() -> CrashRecord {
+    let mut record = CrashRecord::new(
+        73_999,
+        CrashRegisters {
+            ra: 0x4200_1234,
+            sp: 0x4087_F000,
+            fp: 0x4087_F010,
+        },
+    );
+    record.set_build("a1b2c3d");
+    record.write_message(format_args!(
+        "src/state.rs:{}: {}",
+        42, "index out of bounds"
+    ));
+    record.push_frame(0x4200_1234);
+    record.push_frame(0x4200_5678);
+    record
+}
+
+#[test]
+fn test_crash_record_roundtrip() {
+    let record = sample_record();
+    let mut buffer = [0u8; CRASH_RECORD_MAX_LEN];
+    let len = record.encode(&mut buffer).unwrap();
+
+    let decoded = CrashRecord::decode(&buffer[..len]).unwrap();
+    assert_eq!(decoded, record);
+    assert_eq!(decoded.build(), "a1b2c3d");
+    assert_eq!(decoded.message(), "src/state.rs:42: index out of bounds");
+    assert_eq!(decoded.frames(), [0x4200_1234, 0x4200_5678]);
+}
+
+#[test]
+fn test_crash_record_in_storage_record() {
+    let record = sample_record();
+    let mut payload = [0u8; CRASH_RECORD_MAX_LEN];
+    let len = record.encode(&mut payload).unwrap();
+
+    // Muss in STORAGE_RECORD_BUFFER_SIZE (256) der Firmware passen
+    let mut buffer = [0xFFu8; 256];
+    let total = encode_record(RecordKind::Crash, &payload[..len], &mut buffer).unwrap();
+    let stored = decode_record(&buffer[..total], RecordKind::Crash).unwrap();
+    assert_eq!(CrashRecord::decode(stored), Some(record));
+    assert_eq!(decode_record(&buffer[..total], RecordKind::Schedule), None);
+}
+
+#[test]
+fn test_crash_record_max_size_fits_storage() {
+    let mut record = CrashRecord::new(u64::MAX, CrashRegisters::default());
+    record.set_build("0123456789abcdef-dirty");
+    record.write_message(format_args!("{}", "x".repeat(500)));
+    while record.push_frame(0x4200_0000) {}
+
+    assert_eq!(record.frames().len(), CRASH_MAX_FRAMES);
+    assert_eq!(record.message().len(), CRASH_MESSAGE_LEN);
+    let mut buffer = [0u8; CRASH_RECORD_MAX_LEN];
+    assert_eq!(record.encode(&mut buffer), Some(CRASH_RECORD_MAX_LEN));
+    const { assert!(CRASH_RECORD_MAX_LEN + esp_core::record::RECORD_HEADER_LEN <= 256) };
+}
+
+#[test]
+fn test_crash_message_truncated_at_char_boundary() {
+    let mut record = CrashRecord::new(0, CrashRegisters::default());
+    record.write_message(format_args!("{}ü", "a".repeat(CRASH_MESSAGE_LEN - 1)));
+    assert_eq!(record.message().len(), CRASH_MESSAGE_LEN - 1);
+}
+
+#[test]
+fn test_crash_record_decode_rejects_garbage() {
+    assert_eq!(CrashRecord::decode(&[]), None);
+    // Zu viele Frames angegeben
+    let mut buffer = [0u8; 32];
+    buffer[20] = (CRASH_MAX_FRAMES + 1) as u8;
+    assert_eq!(CrashRecord::decode(&buffer), None);
+    // Abgeschnittener Text
+    let record = sample_record();
+    let mut buffer = [0u8; CRASH_RECORD_MAX_LEN];
+    let len = record.encode(&mut buffer).unwrap();
+    assert_eq!(CrashRecord::decode(&buffer[..len - 1]), None);
+}
@@ -0,0 +1,128 @@
+//! Integration Tests für das Überblenden beim Effektwechsel
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{
+    ColorSequence, Crossfade, EffectId, Gradient, LedEffect, MatrixLayout, Meteor, ParamError,
+    ParamValue, PixelEffect, Plasma,
+};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 255);
+const LAYOUT: MatrixLayout = MatrixLayout::new(4, 1, false);
+
+fn solid(color: RGB8) -> PixelEffect {
+    PixelEffect::Gradient(Gradient::new(&[color, color]).unwrap())
+}
+
+// ============================================================================
+// Fortschritt
+// ============================================================================
+
+#[test]
+fn test_progress() {
+    let fade = Crossfade::new(solid(RED), solid(BLUE), 1000, 500);
+    assert_eq!(fade.progress(0), 0);
+    assert_eq!(fade.progress(1000), 0);
+    assert_eq!(fade.progress(1250), 500);
+    assert_eq!(fade.progress(1500), 1000);
+    assert_eq!(fade.progress(9000), 1000);
+    assert!(!fade.is_finished(1499));
+    assert!(fade.is_finished(1500));
+}
+
+#[test]
+fn test_zero_duration_is_hard_cut() {
+    let fade = Crossfade::new(solid(RED), solid(BLUE), 1000, 0);
+    assert_eq!(fade.progress(1000), 1000);
+    assert!(fade.is_finished(0));
+
+    let mut pixels = [RGB8::default(); 4];
+    let mut incoming = [RGB8::default(); 4];
+    fade.render(1000, LAYOUT, &mut pixels, &mut incoming);
+    assert_eq!(pixels, [BLUE; 4]);
+}
+
+// ============================================================================
+// Mischbild
+// ============================================================================
+
+#[test]
+fn test_render_blends_pixels() {
+    let fade = Crossfade::new(solid(RED), solid(BLUE), 0, 1000);
+    let mut pixels = [RGB8::default(); 4];
+    let mut incoming = [RGB8::default(); 4];
+
+    fade.render(0, LAYOUT, &mut pixels, &mut incoming);
+    assert_eq!(pixels, [RED; 4]);
+    fade.render(500, LAYOUT, &mut pixels, &mut incoming);
+    assert_eq!(pixels, [RGB8::new(128, 0, 127); 4]);
+    fade.render(1000, LAYOUT, &mut pixels, &mut incoming);
+    assert_eq!(pixels, [BLUE; 4]);
+}
+
+#[test]
+fn test_render_short_incoming_buffer() {
+    let fade = Crossfade::new(solid(RED), solid(BLUE), 0, 1000);
+    let mut pixels = [RGB8::default(); 4];
+    let mut incoming = [RGB8::default(); 2];
+    fade.render(999, LAYOUT, &mut pixels, &mut incoming);
+    // Ohne Platz für das neue Bild bleibt der alte Effekt stehen
+    assert_eq!(pixels[2..], [RED; 2]);
+    assert_ne!(pixels[0], RED);
+}
+
+#[test]
+fn test_render_animated_effects() {
+    let meteor = PixelEffect::Meteor(Meteor::new(0, RED, 10, 2));
+    let plasma = Plasma::new(0, 64, ColorSequence::new(&[RED, BLUE]).unwrap());
+    let fade = Crossfade::new(meteor, PixelEffect::Plasma(plasma), 0, 1000);
+    let mut pixels = [RGB8::default(); 4];
+    let mut incoming = [RGB8::default(); 4];
+    fade.render(1000, LAYOUT, &mut pixels, &mut incoming);
+
+    let mut expected = [RGB8::default(); 4];
+    plasma.render(1000, LAYOUT, &mut expected);
+    assert_eq!(pixels, expected);
+}
+
+// ============================================================================
+// PixelEffect
+// ============================================================================
+
+#[test]
+fn test_pixel_effect_dispatch() {
+    let gradient = solid(RED);
+    assert_eq!(gradient.id(), EffectId::Gradient);
+    assert!(gradient.params().is_empty());
+    assert!(!gradient.is_animated());
+
+    let mut meteor = PixelEffect::Meteor(Meteor::new(0, RED, 10, 2));
+    assert_eq!(meteor.id(), EffectId::Meteor);
+    assert_eq!(meteor.params(), Meteor::PARAMS);
+    assert!(meteor.is_animated());
+    assert_eq!(meteor.apply_param(ParamValue::Speed(40), 0), Ok(()));
+    assert!(matches!(
+        meteor,
+        PixelEffect::Meteor(Meteor { speed: 40, .. })
+    ));
+
+    let mut gradient = gradient;
+    assert_eq!(
+        gradient.apply_param(ParamValue::Speed(40), 0),
+        Err(ParamError::Unsupported)
+    );
+}
+
+#[test]
+fn test_scaled_colors() {
+    let gradient = Gradient::new(&[RED, BLUE]).unwrap().scaled(128);
+    assert_eq!(
+        gradient.colors(),
+        [RGB8::new(128, 0, 0), RGB8::new(0, 0, 128)]
+    );
+    let sequence = ColorSequence::new(&[RED, BLUE]).unwrap();
+    assert_eq!(sequence.scaled(255), sequence);
+    assert_eq!(sequence.scaled(0).colors(), [RGB8::default(); 2]);
+}
@@ -0,0 +1,181 @@
+//! Integration Tests für die Gerätekonfiguration (Kanal-Reihenfolge, Strip-Zuordnung, LED-Pin)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::device_config::DEVICE_CONFIG_LEN;
+use esp_core::{ColorOrder, DeviceConfig, RecordKind, StripMapping, decode_record, encode_record};
+use rgb::RGB8;
+
+const COLOR: RGB8 = RGB8::new(1, 2, 3);
+
+#[test]
+fn test_default_is_ws2812_order() {
+    assert_eq!(DeviceConfig::default().color_order, ColorOrder::Grb);
+    assert_eq!(ColorOrder::Grb.wire_bytes(COLOR), [2, 1, 3]);
+}
+
+#[test]
+fn test_wire_bytes_for_all_orders() {
+    let cases = [
+        (ColorOrder::Rgb, [1, 2, 3]),
+        (ColorOrder::Grb, [2, 1, 3]),
+        (ColorOrder::Brg, [3, 1, 2]),
+        (ColorOrder::Rbg, [1, 3, 2]),
+        (ColorOrder::Gbr, [2, 3, 1]),
+        (ColorOrder::Bgr, [3, 2, 1]),
+    ];
+    for (order, expected) in cases {
+        assert_eq!(order.wire_bytes(COLOR), expected, "{:?}", order);
+        assert_eq!(ColorOrder::from_u8(order as u8), Some(order));
+    }
+    assert_eq!(ColorOrder::from_u8(6), None);
+}
+
+#[test]
+fn test_config_bytes_roundtrip() {
+    let config = DeviceConfig {
+        color_order: ColorOrder::Brg,
+        reverse: true,
+        mirror: false,
+        led_pin: None,
+    };
+    let bytes = config.to_bytes();
+    assert_eq!(bytes, [2, 0b01, 0xFF]);
+    assert_eq!(bytes.len(), DEVICE_CONFIG_LEN);
+    assert_eq!(DeviceConfig::from_bytes(&bytes), Some(config));
+
+    let mut record = [0u8; 32];
+    let total = encode_record(RecordKind::Config, &bytes, &mut record).unwrap();
+    let loaded = decode_record(&record[..total], RecordKind::Config).unwrap();
+    assert_eq!(DeviceConfig::from_bytes(loaded), Some(config));
+}
+
+#[test]
+fn test_config_from_bytes_tolerates_length_changes() {
+    // Älterer Record ohne Felder → Standardwerte, neuere Felder werden ignoriert
+    assert_eq!(DeviceConfig::from_bytes(&[]), Some(DeviceConfig::default()));
+    assert_eq!(
+        DeviceConfig::from_bytes(&[0]),
+        Some(DeviceConfig {
+            color_order: ColorOrder::Rgb,
+            ..DeviceConfig::default()
+        })
+    );
+    assert_eq!(
+        DeviceConfig::from_bytes(&[0, 0b10, 0xFF]),
+        Some(DeviceConfig {
+            color_order: ColorOrder::Rgb,
+            reverse: false,
+            mirror: true,
+            led_pin: None,
+        })
+    );
+    // Zusätzliche Bytes einer neueren Version werden ignoriert
+    assert_eq!(
+        DeviceConfig::from_bytes(&[0, 0, 4, 0xAA]),
+        Some(DeviceConfig {
+            color_order: ColorOrder::Rgb,
+            led_pin: Some(4),
+            ..DeviceConfig::default()
+        })
+    );
+    // Ungültige Reihenfolge
+    assert_eq!(DeviceConfig::from_bytes(&[9]), None);
+}
+
+#[test]
+fn test_led_pin_roundtrip() {
+    let config = DeviceConfig {
+        led_pin: Some(2),
+        ..DeviceConfig::default()
+    };
+    let bytes = config.to_bytes();
+    assert_eq!(bytes[2], 2);
+    assert_eq!(DeviceConfig::from_bytes(&bytes), Some(config));
+    assert_eq!(DeviceConfig::default().led_pin, None);
+}
+
+#[test]
+fn test_led_pin_allowed() {
+    const ALLOWED: &[u8] = &[8, 0, 2];
+    let default = DeviceConfig::default();
+    assert!(default.has_allowed_led_pin(ALLOWED));
+    assert!(default.has_allowed_led_pin(&[]));
+
+    let allowed = DeviceConfig {
+        led_pin: Some(2),
+        ..default
+    };
+    assert!(allowed.has_allowed_led_pin(ALLOWED));
+
+    // GPIO9 ist der Taster
+    let forbidden = DeviceConfig {
+        led_pin: Some(9),
+        ..default
+    };
+    assert!(!forbidden.has_allowed_led_pin(ALLOWED));
+}
+
+#[test]
+fn test_led_pin_or_falls_back_to_board_default() {
+    const ALLOWED: &[u8] = &[8, 0, 2];
+    assert_eq!(DeviceConfig::default().led_pin_or(ALLOWED, 8), 8);
+    let config = |pin| DeviceConfig {
+        led_pin: Some(pin),
+        ..DeviceConfig::default()
+    };
+    assert_eq!(config(2).led_pin_or(ALLOWED, 8), 2);
+    // Nicht (mehr) erlaubter Pin, z.B. nach Wechsel des Board-Profils
+    assert_eq!(config(9).led_pin_or(ALLOWED, 8), 8);
+}
+
+fn logical_indices(mapping: StripMapping, len: usize) -> Vec<usize> {
+    (0..len).map(|i| mapping.logical_index(i, len)).collect()
+}
+
+#[test]
+fn test_default_mapping_is_identity() {
+    assert_eq!(DeviceConfig::default().mapping(), StripMapping::default());
+    assert_eq!(logical_indices(StripMapping::default(), 4), [0, 1, 2, 3]);
+}
+
+#[test]
+fn test_mapping_reverse() {
+    let mapping = StripMapping {
+        reverse: true,
+        mirror: false,
+    };
+    assert_eq!(logical_indices(mapping, 4), [3, 2, 1, 0]);
+    assert_eq!(logical_indices(mapping, 1), [0]);
+}
+
+#[test]
+fn test_mapping_mirror_even_and_odd() {
+    let mapping = StripMapping {
+        reverse: false,
+        mirror: true,
+    };
+    assert_eq!(logical_indices(mapping, 6), [0, 1, 2, 2, 1, 0]);
+    // Mittlerer Pixel bei ungerader Länge nur einmal
+    assert_eq!(logical_indices(mapping, 5), [0, 1, 2, 1, 0]);
+}
+
+#[test]
+fn test_mapping_mirror_and_reverse() {
+    // Von der Mitte nach außen
+    let mapping = StripMapping {
+        reverse: true,
+        mirror: true,
+    };
+    assert_eq!(logical_indices(mapping, 6), [2, 1, 0, 0, 1, 2]);
+    assert_eq!(logical_indices(mapping, 5), [2, 1, 0, 1, 2]);
+}
+
+#[test]
+fn test_mapping_empty_strip() {
+    let mapping = StripMapping {
+        reverse: true,
+        mirror: true,
+    };
+    assert_eq!(mapping.logical_index(0, 0), 0);
+}
@@ -0,0 +1,214 @@
+//! Integration Tests für die Diagnose-Blinkcodes
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::diagnose::DIAGNOSE_REPEATS;
+use esp_core::{
+    ConnectivityDiagnosis, ConsoleCommand, DiagnoseBlink, DiagnoseCode, ErrorKind, ErrorSource,
+    FirmwareError, LedCommand, LedState, LedStateConfig, SystemEvent, parse_console_command,
+    parse_text_command,
+};
+use rgb::RGB8;
+
+const BRIGHTNESS: u8 = 96;
+const OFF: RGB8 = RGB8::new(0, 0, 0);
+const RED: RGB8 = RGB8::new(BRIGHTNESS, 0, 0);
+
+fn error(source: ErrorSource, kind: ErrorKind) -> SystemEvent {
+    SystemEvent::Error(FirmwareError::new(source, kind))
+}
+
+/// WLAN verbunden und IP-Adresse erhalten
+fn online_without_mqtt(diagnosis: &mut ConnectivityDiagnosis) {
+    diagnosis.handle(&SystemEvent::WifiConnected);
+    diagnosis.handle(&SystemEvent::IpAcquired {
+        address: [192, 168, 1, 42],
+    });
+}
+
+/// Anzahl der roten Blitze über die ganze Anzeige
+fn count_flashes(blink: &DiagnoseBlink) -> u64 {
+    let mut flashes = 0;
+    let mut previous = OFF;
+    let mut now_ms = blink.start_ms;
+    while let Some(color) = blink.frame(now_ms, BRIGHTNESS) {
+        if color == RED && previous == OFF {
+            flashes += 1;
+        }
+        previous = color;
+        now_ms += 50;
+    }
+    flashes
+}
+
+// ============================================================================
+// Kommando
+// ============================================================================
+
+#[test]
+fn test_parse_diagnose_command() {
+    assert!(matches!(
+        parse_text_command("diagnose"),
+        Ok(LedCommand::Diagnose)
+    ));
+    assert!(matches!(
+        parse_text_command("DIAGNOSE"),
+        Ok(LedCommand::Diagnose)
+    ));
+    assert!(matches!(
+        parse_console_command("diagnose"),
+        Ok(ConsoleCommand::Led(LedCommand::Diagnose))
+    ));
+}
+
+#[test]
+fn test_diagnose_keeps_led_state() {
+    let config = LedStateConfig {
+        base_brightness: 10,
+        sunrise_max_brightness: 128,
+        sleep_fade_ms: 60_000,
+        brightness_fade_ms: 0,
+    };
+    let mut state = LedState::new(config);
+    let before = state;
+    let transition = state.apply(LedCommand::Diagnose, 0);
+    assert!(!transition.color_changed);
+    assert_eq!(state, before);
+}
+
+// ============================================================================
+// Ursache
+// ============================================================================
+
+#[test]
+fn test_current_state_goes_first() {
+    let mut diagnosis = ConnectivityDiagnosis::new(true);
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::NoWifi));
+    diagnosis.handle(&SystemEvent::WifiConnected);
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::NoIp));
+    diagnosis.handle(&error(ErrorSource::Mqtt, ErrorKind::DnsFailed));
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::NoIp));
+    diagnosis.handle(&SystemEvent::IpAcquired {
+        address: [192, 168, 1, 42],
+    });
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::DnsFailed));
+}
+
+#[test]
+fn test_most_recent_failure_wins() {
+    let mut diagnosis = ConnectivityDiagnosis::new(true);
+    online_without_mqtt(&mut diagnosis);
+    assert_eq!(diagnosis.code(), None);
+
+    diagnosis.handle(&error(ErrorSource::Mqtt, ErrorKind::DnsFailed));
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::DnsFailed));
+    diagnosis.handle(&error(ErrorSource::Mqtt, ErrorKind::ConnectionFailed));
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::BrokerRefused));
+    diagnosis.handle(&error(ErrorSource::Mqtt, ErrorKind::ProtocolViolation));
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::BrokerRefused));
+
+    // Erfolgreiche Verbindung löscht den Fehler
+    diagnosis.handle(&SystemEvent::MqttConnected);
+    assert_eq!(diagnosis.code(), None);
+}
+
+#[test]
+fn test_session_errors_are_not_refusals() {
+    let mut diagnosis = ConnectivityDiagnosis::new(true);
+    online_without_mqtt(&mut diagnosis);
+    diagnosis.handle(&SystemEvent::MqttConnected);
+    diagnosis.handle(&error(ErrorSource::Mqtt, ErrorKind::ProtocolViolation));
+    diagnosis.handle(&SystemEvent::MqttDisconnected);
+    assert_eq!(diagnosis.code(), None);
+
+    // Andere Fehler betreffen die Verbindung nicht
+    diagnosis.handle(&error(ErrorSource::Led, ErrorKind::HardwareFailure));
+    diagnosis.handle(&error(ErrorSource::Http, ErrorKind::InvalidInput));
+    assert_eq!(diagnosis.code(), None);
+}
+
+#[test]
+fn test_wifi_loss_is_remembered() {
+    let mut diagnosis = ConnectivityDiagnosis::new(true);
+    online_without_mqtt(&mut diagnosis);
+    diagnosis.handle(&SystemEvent::MqttConnected);
+    diagnosis.handle(&SystemEvent::WifiDisconnected);
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::NoWifi));
+
+    // Wieder im WLAN, aber MQTT noch nicht verbunden
+    online_without_mqtt(&mut diagnosis);
+    assert_eq!(diagnosis.code(), Some(DiagnoseCode::NoWifi));
+    diagnosis.handle(&SystemEvent::MqttConnected);
+    assert_eq!(diagnosis.code(), None);
+}
+
+#[test]
+fn test_without_mqtt_ip_is_success() {
+    let mut diagnosis = ConnectivityDiagnosis::new(false);
+    diagnosis.handle(&error(ErrorSource::Wifi, ErrorKind::ConnectionFailed));
+    online_without_mqtt(&mut diagnosis);
+    assert_eq!(diagnosis.code(), None);
+}
+
+#[test]
+fn test_code_numbers() {
+    let codes = [
+        DiagnoseCode::NoWifi,
+        DiagnoseCode::NoIp,
+        DiagnoseCode::DnsFailed,
+        DiagnoseCode::BrokerRefused,
+    ];
+    for (index, code) in codes.into_iter().enumerate() {
+        assert_eq!(usize::from(code.number()), index + 1);
+    }
+    assert_eq!(DiagnoseCode::BrokerRefused.name(), "broker_refused");
+}
+
+// ============================================================================
+// Blinkfolge
+// ============================================================================
+
+#[test]
+fn test_blink_count_matches_code() {
+    for code in [
+        DiagnoseCode::NoWifi,
+        DiagnoseCode::NoIp,
+        DiagnoseCode::DnsFailed,
+        DiagnoseCode::BrokerRefused,
+    ] {
+        let blink = DiagnoseBlink::new(Some(code), 5000);
+        assert_eq!(
+            count_flashes(&blink),
+            u64::from(code.number()) * DIAGNOSE_REPEATS
+        );
+    }
+}
+
+#[test]
+fn test_blink_starts_with_pause() {
+    let blink = DiagnoseBlink::new(Some(DiagnoseCode::DnsFailed), 1000);
+    assert_eq!(blink.frame(1000, BRIGHTNESS), Some(OFF));
+    assert_eq!(blink.frame(2499, BRIGHTNESS), Some(OFF));
+    assert_eq!(blink.frame(2500, BRIGHTNESS), Some(RED));
+    assert_eq!(blink.frame(2900, BRIGHTNESS), Some(OFF));
+    assert_eq!(blink.frame(3300, BRIGHTNESS), Some(RED));
+}
+
+#[test]
+fn test_blink_ends() {
+    let blink = DiagnoseBlink::new(Some(DiagnoseCode::NoWifi), 1000);
+    let end = 1000 + blink.duration_ms();
+    assert!(blink.frame(end - 1, BRIGHTNESS).is_some());
+    assert_eq!(blink.frame(end, BRIGHTNESS), None);
+}
+
+#[test]
+fn test_no_problem_shows_green() {
+    let blink = DiagnoseBlink::new(None, 0);
+    assert_eq!(count_flashes(&blink), 0);
+    assert_eq!(
+        blink.frame(0, BRIGHTNESS),
+        Some(RGB8::new(0, BRIGHTNESS, 0))
+    );
+    assert_eq!(blink.frame(blink.duration_ms(), BRIGHTNESS), None);
+}
@@ -0,0 +1,66 @@
+//! Integration Tests für den DNS-Cache der Broker-Adresse
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{DnsCache, DnsCacheState};
+
+const TTL_MS: u64 = 60_000;
+const BROKER: [u8; 4] = [192, 168, 1, 10];
+
+#[test]
+fn test_empty_cache_requires_resolution() {
+    let mut cache = DnsCache::new(TTL_MS);
+    assert_eq!(cache.state(0), DnsCacheState::Empty);
+    assert_eq!(cache.lookup(0), None);
+    assert_eq!(cache.hits, 0);
+}
+
+#[test]
+fn test_stored_address_is_reused_until_ttl() {
+    let mut cache = DnsCache::new(TTL_MS);
+    cache.store(BROKER, 1000);
+
+    assert_eq!(cache.lookup(1000), Some(BROKER));
+    assert_eq!(cache.lookup(1000 + TTL_MS - 1), Some(BROKER));
+    assert_eq!(
+        cache.state(31_000),
+        DnsCacheState::Valid {
+            address: BROKER,
+            remaining_ms: 30_000
+        }
+    );
+    assert_eq!(cache.hits, 2);
+    assert_eq!(cache.resolutions, 1);
+}
+
+#[test]
+fn test_expired_entry_is_not_used() {
+    let mut cache = DnsCache::new(TTL_MS);
+    cache.store(BROKER, 1000);
+
+    assert_eq!(cache.lookup(1000 + TTL_MS), None);
+    assert_eq!(
+        cache.state(1000 + TTL_MS),
+        DnsCacheState::Expired { address: BROKER }
+    );
+
+    // Neu aufgelöst: TTL beginnt von vorn
+    cache.store([192, 168, 1, 11], 1000 + TTL_MS);
+    assert_eq!(cache.lookup(1000 + TTL_MS), Some([192, 168, 1, 11]));
+    assert_eq!(cache.resolutions, 2);
+}
+
+#[test]
+fn test_invalidate_forces_resolution() {
+    let mut cache = DnsCache::new(TTL_MS);
+    cache.store(BROKER, 0);
+    cache.invalidate();
+
+    assert_eq!(cache.state(0), DnsCacheState::Empty);
+    assert_eq!(cache.lookup(0), None);
+    assert_eq!(cache.invalidations, 1);
+
+    // Leerer Cache zählt nicht erneut
+    cache.invalidate();
+    assert_eq!(cache.invalidations, 1);
+}
@@ -0,0 +1,218 @@
+//! Integration Tests für die Live-Parameter des laufenden Effekts
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::effects::effect_info;
+use esp_core::ws::{WsRequest, parse_ws_message};
+use esp_core::{
+    ColorSequence, CommandParseError, CommandPriority, CommandQueue, EffectId, LedCommand,
+    LedEffect, Meteor, ParamError, ParamName, ParamValue, Plasma, PushOutcome, parse_text_command,
+};
+use rgb::RGB8;
+
+const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+fn palette() -> ColorSequence {
+    ColorSequence::parse("rot blau").unwrap()
+}
+
+// ============================================================================
+// Namen und Werte
+// ============================================================================
+
+#[test]
+fn test_param_name_parse() {
+    assert_eq!(ParamName::parse("speed"), Some(ParamName::Speed));
+    assert_eq!(ParamName::parse("INTENSITY"), Some(ParamName::Intensity));
+    assert_eq!(ParamName::parse("palette"), Some(ParamName::Palette));
+    assert_eq!(ParamName::parse("tempo"), None);
+    assert_eq!(ParamName::Tail.name(), "tail");
+}
+
+#[test]
+fn test_param_value_parse() {
+    assert_eq!(
+        ParamValue::parse(ParamName::Tail, "12"),
+        Ok(ParamValue::Tail(12))
+    );
+    assert_eq!(
+        ParamValue::parse(ParamName::Color, "#FF8000"),
+        Ok(ParamValue::Color(RGB8::new(255, 128, 0)))
+    );
+    assert_eq!(
+        ParamValue::parse(ParamName::Palette, "rot blau"),
+        Ok(ParamValue::Palette(palette()))
+    );
+    assert_eq!(
+        ParamValue::parse(ParamName::Speed, "schnell"),
+        Err(ParamError::InvalidValue)
+    );
+    assert_eq!(
+        ParamValue::parse(ParamName::Speed, "300"),
+        Err(ParamError::OutOfRange)
+    );
+    assert_eq!(
+        ParamValue::integer(ParamName::Palette, 3),
+        Err(ParamError::InvalidValue)
+    );
+}
+
+// ============================================================================
+// LedEffect
+// ============================================================================
+
+#[test]
+fn test_meteor_params() {
+    let mut meteor = Meteor::new(0, WHITE, 10, 3);
+    assert_eq!(meteor.apply_param(ParamValue::Tail(5), 0), Ok(()));
+    assert_eq!(meteor.tail, 5);
+    let red = RGB8::new(255, 0, 0);
+    assert_eq!(meteor.apply_param(ParamValue::Color(red), 0), Ok(()));
+    assert_eq!(meteor.color, red);
+
+    assert_eq!(
+        meteor.apply_param(ParamValue::Speed(0), 0),
+        Err(ParamError::OutOfRange)
+    );
+    assert_eq!(
+        meteor.apply_param(ParamValue::Palette(palette()), 0),
+        Err(ParamError::Unsupported)
+    );
+    assert_eq!(meteor.speed, 10);
+}
+
+#[test]
+fn test_meteor_speed_change_keeps_position() {
+    let mut meteor = Meteor::new(0, WHITE, 10, 3);
+    assert_eq!(meteor.head_at(1000, 40), 10);
+    assert_eq!(meteor.apply_param(ParamValue::Speed(20), 1000), Ok(()));
+    // Kein Sprung, danach doppelt so schnell
+    assert_eq!(meteor.head_at(1000, 40), 10);
+    assert_eq!(meteor.head_at(1500, 40), 20);
+}
+
+#[test]
+fn test_plasma_params() {
+    let mut plasma = Plasma::new(0, 64, palette());
+    assert_eq!(plasma.apply_param(ParamValue::Intensity(16), 0), Ok(()));
+    assert_eq!(plasma.scale, 16);
+    let sequence = ColorSequence::parse("blau #FF8000").unwrap();
+    assert_eq!(plasma.apply_param(ParamValue::Palette(sequence), 0), Ok(()));
+    assert_eq!(plasma.palette, sequence);
+
+    // Tempo 0 erlaubt (stehendes Bild), Intensität 0 nicht
+    assert_eq!(plasma.apply_param(ParamValue::Speed(0), 0), Ok(()));
+    assert_eq!(
+        plasma.apply_param(ParamValue::Intensity(0), 0),
+        Err(ParamError::OutOfRange)
+    );
+    assert_eq!(
+        plasma.apply_param(ParamValue::Tail(3), 0),
+        Err(ParamError::Unsupported)
+    );
+}
+
+#[test]
+fn test_plasma_speed_change_is_continuous() {
+    let mut plasma = Plasma::new(0, 64, palette());
+    let before = plasma.value_at(3, 1, 2000);
+    assert_eq!(plasma.apply_param(ParamValue::Speed(200), 2000), Ok(()));
+    assert_eq!(plasma.value_at(3, 1, 2000), before);
+    assert_ne!(plasma.value_at(3, 1, 2100), before);
+}
+
+#[test]
+fn test_catalog_lists_live_params() {
+    assert_eq!(effect_info(EffectId::Meteor).live_params, Meteor::PARAMS);
+    assert_eq!(effect_info(EffectId::Plasma).live_params, Plasma::PARAMS);
+    assert!(effect_info(EffectId::Solid).live_params.is_empty());
+    let names: Vec<_> = Plasma::PARAMS.iter().map(|param| param.name).collect();
+    assert_eq!(names, ["speed", "intensity", "palette"]);
+    for param in Meteor::PARAMS.iter().chain(Plasma::PARAMS) {
+        assert!(ParamName::parse(param.name).is_some(), "{}", param.name);
+    }
+}
+
+// ============================================================================
+// Kommandos
+// ============================================================================
+
+#[test]
+fn test_text_command() {
+    assert!(matches!(
+        parse_text_command("param speed 40"),
+        Ok(LedCommand::SetEffectParam {
+            value: ParamValue::Speed(40)
+        })
+    ));
+    match parse_text_command("set_effect_param palette  rot blau ") {
+        Ok(LedCommand::SetEffectParam {
+            value: ParamValue::Palette(sequence),
+        }) => assert_eq!(sequence, palette()),
+        _ => panic!("Expected SetEffectParam"),
+    }
+    for invalid in ["param", "param speed", "param tempo 3", "param color lila"] {
+        assert_eq!(
+            parse_text_command(invalid).err(),
+            Some(CommandParseError::InvalidArgument),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_ws_message() {
+    assert!(matches!(
+        parse_ws_message::<()>(
+            br#"{"type":"set_effect_param","name":"intensity","value":20}"#,
+            10
+        ),
+        WsRequest::Command(LedCommand::SetEffectParam {
+            value: ParamValue::Intensity(20)
+        })
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(
+            br#"{"type":"set_effect_param","name":"color","colors":"blau"}"#,
+            10
+        ),
+        WsRequest::Command(LedCommand::SetEffectParam {
+            value: ParamValue::Color(_)
+        })
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"set_effect_param","value":20}"#, 10),
+        WsRequest::Error("Invalid param")
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"set_effect_param","name":"speed"}"#, 10),
+        WsRequest::Error("Invalid value")
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(
+            br#"{"type":"set_effect_param","name":"tail","value":1000}"#,
+            10
+        ),
+        WsRequest::Error("Invalid value")
+    ));
+}
+
+#[test]
+fn test_queue_replaces_same_param_only() {
+    let speed = |value| LedCommand::SetEffectParam {
+        value: ParamValue::Speed(value),
+    };
+    assert_eq!(speed(1).priority(), CommandPriority::Low);
+
+    let mut queue = CommandQueue::<4>::new();
+    queue.push(speed(10));
+    assert!(matches!(
+        queue.push(LedCommand::SetEffectParam {
+            value: ParamValue::Intensity(5)
+        }),
+        PushOutcome::Queued
+    ));
+    assert!(matches!(queue.push(speed(20)), PushOutcome::Replaced));
+    assert_eq!(queue.len(), 2);
+}
@@ -0,0 +1,131 @@
+//! Integration Tests für den Effekt-Katalog (GET /api/effects)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::effects::effect_info;
+use esp_core::{
+    ColorId, EFFECTS, EffectId, LedCommand, LedState, LedStateConfig, ParamKind,
+    parse_text_command,
+};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 200,
+    sleep_fade_ms: 10_000,
+    brightness_fade_ms: 0,
+};
+
+#[test]
+fn test_catalog_has_one_entry_per_effect_in_order() {
+    assert_eq!(EFFECTS.len(), EffectId::ALL.len());
+    for (index, id) in EffectId::ALL.into_iter().enumerate() {
+        assert_eq!(EFFECTS[index].id, id);
+        assert_eq!(effect_info(id).id, id);
+        assert_eq!(EffectId::from_u8(id as u8), Some(id));
+    }
+    assert_eq!(EffectId::from_u8(EffectId::ALL.len() as u8), None);
+}
+
+#[test]
+fn test_effect_names_are_unique_snake_case() {
+    for (index, info) in EFFECTS.iter().enumerate() {
+        let name = info.id.name();
+        assert!(
+            name.chars().all(|c| c.is_ascii_lowercase() || c == '_'),
+            "{}",
+            name
+        );
+        assert!(
+            EFFECTS[index + 1..].iter().all(|other| other.id.name() != name),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_param_schemas_are_consistent() {
+    for info in &EFFECTS {
+        for param in info.params {
+            match param.kind {
+                ParamKind::Choice => assert!(!param.values.is_empty(), "{}", param.name),
+                ParamKind::Integer | ParamKind::Text => {
+                    assert!(param.values.is_empty(), "{}", param.name)
+                }
+            }
+            if let (Some(min), Some(max)) = (param.min, param.max) {
+                assert!(min <= max, "{}", param.name);
+            }
+            if let Some(default) = param.default {
+                assert!(!param.required, "{}", param.name);
+                assert!(param.min.is_none_or(|min| default >= min), "{}", param.name);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_usage_examples_parse_as_text_commands() {
+    // Jeder Effekt mit Text-Kommando lässt sich mit Beispielwerten starten
+    let examples = [
+        (EffectId::Solid, "rot"),
+        (EffectId::Auto, "auto"),
+        (EffectId::Random, "random"),
+        (EffectId::Fade, "blau 1500"),
+        (EffectId::Sunrise, "sunrise"),
+        (EffectId::Calibration, "calibrate"),
+        (EffectId::TestPattern, "testpattern"),
+        (EffectId::ScrollText, "text Hallo"),
+        (EffectId::Clock, "clock 12"),
+        (EffectId::Gradient, "gradient rot blau"),
+        (EffectId::Meteor, "meteor 40 10 blau"),
+        (EffectId::Plasma, "plasma 100"),
+    ];
+    for (id, example) in examples {
+        assert!(effect_info(id).usage.is_some(), "{:?}", id);
+        assert!(parse_text_command(example).is_ok(), "{}", example);
+    }
+    for color in effect_info(EffectId::Solid).params[0].values {
+        assert!(parse_text_command(color).is_ok(), "{}", color);
+    }
+    assert_eq!(effect_info(EffectId::Realtime).usage, None);
+}
+
+#[test]
+fn test_active_effect_follows_state() {
+    let mut state = LedState::new(CONFIG);
+    assert_eq!(state.active_effect(), EffectId::Auto);
+
+    state.apply(LedCommand::EnableRandom, 0);
+    assert_eq!(state.active_effect(), EffectId::Random);
+
+    let red = RGB8::new(10, 0, 0);
+    state.apply(
+        LedCommand::SetColor {
+            target_color: red,
+            color_id: ColorId::Red,
+        },
+        0,
+    );
+    assert_eq!(state.active_effect(), EffectId::Solid);
+
+    state.apply(
+        LedCommand::FadeTo {
+            target_color: RGB8::new(0, 0, 10),
+            color_id: ColorId::Blue,
+            transition_ms: 1000,
+        },
+        0,
+    );
+    assert_eq!(state.active_effect(), EffectId::Fade);
+
+    state.apply(LedCommand::Sunrise { duration_secs: 60 }, 0);
+    assert_eq!(state.active_effect(), EffectId::Sunrise);
+
+    state.apply(LedCommand::Calibrate, 0);
+    assert_eq!(state.active_effect(), EffectId::Calibration);
+
+    state.apply(LedCommand::Off, 0);
+    assert_eq!(state.active_effect(), EffectId::Solid);
+}
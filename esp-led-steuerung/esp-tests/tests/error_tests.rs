@@ -0,0 +1,138 @@
+//! Integration Tests für die gemeinsame Fehler-Taxonomie
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{
+    CoapError, ErrorCategory, ErrorCounters, ErrorKind, ErrorSource, FirmwareError, LedError,
+    OscError, ScheduleError, Severity,
+};
+
+// ============================================================================
+// Tests: Kategorie und Schweregrad
+// ============================================================================
+
+#[test]
+fn test_network_errors_are_warnings() {
+    for kind in [
+        ErrorKind::DnsFailed,
+        ErrorKind::ConnectionFailed,
+        ErrorKind::Timeout,
+        ErrorKind::ReceiveFailed,
+    ] {
+        let error = FirmwareError::new(ErrorSource::Mqtt, kind);
+        assert_eq!(error.category(), ErrorCategory::Network);
+        assert_eq!(error.severity(), Severity::Warning);
+    }
+}
+
+#[test]
+fn test_input_errors_are_info() {
+    let error = FirmwareError::new(ErrorSource::Http, ErrorKind::InvalidInput);
+    assert_eq!(error.category(), ErrorCategory::Input);
+    assert_eq!(error.severity(), Severity::Info);
+    assert_eq!(error.http_status(), 400);
+
+    let unauthorized = FirmwareError::new(ErrorSource::Http, ErrorKind::Unauthorized);
+    assert_eq!(unauthorized.category(), ErrorCategory::Input);
+    assert_eq!(unauthorized.severity(), Severity::Info);
+    assert_eq!(unauthorized.http_status(), 401);
+}
+
+#[test]
+fn test_hardware_and_resource_errors_are_severe() {
+    let led: FirmwareError = LedError::WriteFailed.into();
+    assert_eq!(led.source, ErrorSource::Led);
+    assert_eq!(led.category(), ErrorCategory::Hardware);
+    assert_eq!(led.severity(), Severity::Error);
+    assert_eq!(led.http_status(), 500);
+
+    let full = FirmwareError::new(ErrorSource::Schedule, ErrorKind::CapacityExceeded);
+    assert_eq!(full.category(), ErrorCategory::Resource);
+    assert!(full.severity() > Severity::Warning);
+}
+
+#[test]
+fn test_severity_ordering() {
+    assert!(Severity::Info < Severity::Warning);
+    assert!(Severity::Warning < Severity::Error);
+}
+
+// ============================================================================
+// Tests: Konvertierungen
+// ============================================================================
+
+#[test]
+fn test_schedule_error_conversion() {
+    let cases = [
+        (ScheduleError::Full, ErrorKind::CapacityExceeded, 507),
+        (ScheduleError::InvalidEntry, ErrorKind::InvalidInput, 400),
+        (ScheduleError::NotFound, ErrorKind::NotFound, 404),
+    ];
+    for (error, kind, status) in cases {
+        let error: FirmwareError = error.into();
+        assert_eq!(error.source, ErrorSource::Schedule);
+        assert_eq!(error.kind, kind);
+        assert_eq!(error.http_status(), status);
+    }
+}
+
+#[test]
+fn test_protocol_error_conversion() {
+    let coap: FirmwareError = CoapError::MalformedOption.into();
+    assert_eq!(coap.source, ErrorSource::Coap);
+    assert_eq!(coap.category(), ErrorCategory::Protocol);
+
+    let buffer: FirmwareError = CoapError::BufferTooSmall.into();
+    assert_eq!(buffer.category(), ErrorCategory::Resource);
+
+    let osc: FirmwareError = OscError::InvalidAddress.into();
+    assert_eq!(osc.source, ErrorSource::Osc);
+    assert_eq!(osc.kind, ErrorKind::ProtocolViolation);
+}
+
+// ============================================================================
+// Tests: Telemetrie-Zähler
+// ============================================================================
+
+#[test]
+fn test_counters_per_category() {
+    let mut counters = ErrorCounters::new();
+    counters.record(&FirmwareError::new(
+        ErrorSource::Wifi,
+        ErrorKind::ConnectionFailed,
+    ));
+    counters.record(&FirmwareError::new(ErrorSource::Mqtt, ErrorKind::Timeout));
+    counters.record(&LedError::WriteFailed.into());
+
+    assert_eq!(counters.get(ErrorCategory::Network), 2);
+    assert_eq!(counters.get(ErrorCategory::Hardware), 1);
+    assert_eq!(counters.get(ErrorCategory::Input), 0);
+    assert_eq!(counters.total(), 3);
+}
+
+#[test]
+fn test_category_index_matches_all() {
+    let mut counters = ErrorCounters::new();
+    counters.record(&FirmwareError::new(ErrorSource::Http, ErrorKind::NotFound));
+    for category in ErrorCategory::ALL {
+        let expected = u32::from(category == ErrorCategory::Input);
+        assert_eq!(counters.get(category), expected, "{}", category.name());
+    }
+}
+
+#[test]
+fn test_names() {
+    let error = FirmwareError::new(ErrorSource::Storage, ErrorKind::HardwareFailure);
+    assert_eq!(error.source.name(), "storage");
+    assert_eq!(error.kind.name(), "hardware_failure");
+    assert_eq!(error.category().name(), "hardware");
+}
+
+#[test]
+fn test_busy_is_transient_resource_error() {
+    let busy = FirmwareError::new(ErrorSource::Led, ErrorKind::Busy);
+    assert_eq!(busy.category(), ErrorCategory::Resource);
+    assert_eq!(busy.severity(), Severity::Warning);
+    assert_eq!(busy.http_status(), 503);
+    assert_eq!(busy.kind.name(), "busy");
+}
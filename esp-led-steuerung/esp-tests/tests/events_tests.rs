@@ -0,0 +1,91 @@
+//! Integration Tests für System-Ereignisse
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{
+    ClientKind, ErrorKind, ErrorSource, FirmwareError, RebootSource, SystemEvent, TaskId,
+};
+
+#[test]
+fn test_event_names_are_unique() {
+    let events = [
+        SystemEvent::WifiConnected,
+        SystemEvent::WifiDisconnected,
+        SystemEvent::IpAcquired {
+            address: [192, 168, 1, 42],
+        },
+        SystemEvent::MqttConnected,
+        SystemEvent::MqttDisconnected,
+        SystemEvent::MqttBrokerSwitched { index: 1 },
+        SystemEvent::InputChanged {
+            input: 0,
+            active: true,
+        },
+        SystemEvent::OtaStarted,
+        SystemEvent::ClientConnected {
+            kind: ClientKind::WebSocket,
+        },
+        SystemEvent::Error(FirmwareError::new(
+            ErrorSource::Mqtt,
+            ErrorKind::ConnectionFailed,
+        )),
+        SystemEvent::TaskStalled {
+            task: TaskId::Mqtt,
+            idle_ms: 90_000,
+        },
+        SystemEvent::TaskRecovered { task: TaskId::Mqtt },
+        SystemEvent::RebootRequested {
+            source: RebootSource::Http,
+            factory_reset: false,
+        },
+    ];
+
+    for (i, a) in events.iter().enumerate() {
+        for b in &events[i + 1..] {
+            assert_ne!(a.name(), b.name());
+        }
+    }
+}
+
+#[test]
+fn test_event_name_ignores_payload() {
+    let websocket = SystemEvent::ClientConnected {
+        kind: ClientKind::WebSocket,
+    };
+    let console = SystemEvent::ClientConnected {
+        kind: ClientKind::Console,
+    };
+    assert_eq!(websocket.name(), "client_connected");
+    assert_eq!(websocket.name(), console.name());
+    assert_ne!(websocket, console);
+}
+
+#[test]
+fn test_is_error() {
+    let error = SystemEvent::Error(FirmwareError::new(
+        ErrorSource::Wifi,
+        ErrorKind::ConnectionFailed,
+    ));
+    assert!(error.is_error());
+    assert_eq!(error.name(), "error");
+    assert!(!SystemEvent::WifiConnected.is_error());
+    assert!(!SystemEvent::OtaStarted.is_error());
+}
+
+#[test]
+fn test_reboot_requested() {
+    let event = SystemEvent::RebootRequested {
+        source: RebootSource::Mqtt,
+        factory_reset: false,
+    };
+    let reset = SystemEvent::RebootRequested {
+        source: RebootSource::Button,
+        factory_reset: true,
+    };
+    assert_eq!(event.name(), "reboot_requested");
+    assert_eq!(reset.name(), event.name());
+    assert!(!event.is_error());
+    assert_eq!(RebootSource::Mqtt.name(), "mqtt");
+    assert_eq!(RebootSource::Button.name(), "button");
+    assert_ne!(RebootSource::Http.name(), RebootSource::Console.name());
+}
@@ -0,0 +1,143 @@
+//! Integration Tests für den Farbverlauf über den Strip
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::gradient::GRADIENT_MAX_COLORS;
+use esp_core::ws::{WsRequest, parse_ws_message};
+use esp_core::{
+    CommandParseError, CommandPriority, EffectId, Gradient, LedCommand, LedState, LedStateConfig,
+    parse_text_command,
+};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 128,
+    sleep_fade_ms: 60_000,
+    brightness_fade_ms: 0,
+};
+
+const RED: RGB8 = RGB8::new(200, 0, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 200);
+const WHITE: RGB8 = RGB8::new(200, 200, 200);
+
+// ============================================================================
+// Gradient
+// ============================================================================
+
+#[test]
+fn test_two_colors_span_the_strip() {
+    let gradient = Gradient::new(&[RED, BLUE]).unwrap();
+    let mut pixels = [RGB8::default(); 5];
+    gradient.fill(&mut pixels);
+    assert_eq!(
+        pixels,
+        [
+            RED,
+            RGB8::new(150, 0, 50),
+            RGB8::new(100, 0, 100),
+            RGB8::new(50, 0, 150),
+            BLUE,
+        ]
+    );
+}
+
+#[test]
+fn test_colors_evenly_spaced() {
+    // Mittlere Farbe genau in der Mitte, dazwischen interpoliert
+    let gradient = Gradient::new(&[RED, WHITE, BLUE]).unwrap();
+    let mut pixels = [RGB8::default(); 9];
+    gradient.fill(&mut pixels);
+    assert_eq!(pixels[0], RED);
+    assert_eq!(pixels[2], RGB8::new(200, 100, 100));
+    assert_eq!(pixels[4], WHITE);
+    assert_eq!(pixels[6], RGB8::new(100, 100, 200));
+    assert_eq!(pixels[8], BLUE);
+}
+
+#[test]
+fn test_short_strips() {
+    let gradient = Gradient::new(&[RED, WHITE, BLUE]).unwrap();
+    let mut single = [RGB8::default(); 1];
+    gradient.fill(&mut single);
+    assert_eq!(single, [RED]);
+
+    // Weniger Pixel als Farben: Enden bleiben erste und letzte Farbe
+    let mut pair = [RGB8::default(); 2];
+    gradient.fill(&mut pair);
+    assert_eq!(pair, [RED, BLUE]);
+
+    gradient.fill(&mut []);
+    assert_eq!(gradient.color_at(10, 5), BLUE);
+}
+
+#[test]
+fn test_color_count_limits() {
+    assert!(Gradient::new(&[]).is_none());
+    assert!(Gradient::new(&[RED]).is_none());
+    assert!(Gradient::new(&[RED; GRADIENT_MAX_COLORS]).is_some());
+    assert!(Gradient::new(&[RED; GRADIENT_MAX_COLORS + 1]).is_none());
+}
+
+#[test]
+fn test_parse() {
+    let gradient = Gradient::parse("#C80000 0,0,200").unwrap();
+    assert_eq!(gradient.colors(), [RED, BLUE]);
+    assert!(Gradient::parse("rot blau").is_some());
+    assert!(Gradient::parse("").is_none());
+    assert!(Gradient::parse("#C80000").is_none());
+    assert!(Gradient::parse("#C80000 lila").is_none());
+}
+
+// ============================================================================
+// Kommandos
+// ============================================================================
+
+#[test]
+fn test_text_command() {
+    match parse_text_command("verlauf #C80000 #0000C8") {
+        Ok(LedCommand::ShowGradient { gradient }) => assert_eq!(gradient.colors(), [RED, BLUE]),
+        _ => panic!("Expected ShowGradient"),
+    }
+    assert!(matches!(
+        parse_text_command("GRADIENT rot grün blau"),
+        Ok(LedCommand::ShowGradient { .. })
+    ));
+    assert_eq!(
+        parse_text_command("gradient rot").err(),
+        Some(CommandParseError::InvalidArgument)
+    );
+}
+
+#[test]
+fn test_ws_message() {
+    let json = r##"{"type":"show_gradient","colors":"#C80000 #0000C8"}"##;
+    match parse_ws_message::<()>(json.as_bytes(), 10) {
+        WsRequest::Command(LedCommand::ShowGradient { gradient }) => {
+            assert_eq!(gradient.colors(), [RED, BLUE])
+        }
+        _ => panic!("Expected ShowGradient"),
+    }
+    for json in [
+        r#"{"type":"show_gradient"}"#,
+        r#"{"type":"show_gradient","colors":"rot"}"#,
+    ] {
+        assert!(matches!(
+            parse_ws_message::<()>(json.as_bytes(), 10),
+            WsRequest::Error("Invalid gradient")
+        ));
+    }
+}
+
+#[test]
+fn test_gradient_leaves_state_unchanged() {
+    // Wie Testmuster und Uhr: der LED-Task gibt den Verlauf selbst aus
+    let mut state = LedState::new(CONFIG);
+    let before = state.output(0);
+    let gradient = Gradient::new(&[RED, BLUE]).unwrap();
+    let command = LedCommand::ShowGradient { gradient };
+    assert_eq!(command.priority(), CommandPriority::Normal);
+    state.apply(command, 0);
+    assert_eq!(state.output(0), before);
+    assert_eq!(state.active_effect(), EffectId::Auto);
+}
@@ -0,0 +1,181 @@
+//! Integration Tests für den ESP-NOW-Gruppen-Sync (Paket-Format, Mesh-Weiterleitung)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::group_sync::{
+    GROUP_MAX_ORIGINS, GROUP_ORIGIN_TIMEOUT_MS, GROUP_PACKET_LEN, GROUP_TTL,
+};
+use esp_core::{ColorId, GroupPacket, GroupState, GroupSync, LedColorMessage, LedCommand};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+
+fn packet(origin: u32, sequence: u32, state: GroupState) -> GroupPacket {
+    GroupPacket {
+        origin,
+        sequence,
+        ttl: GROUP_TTL,
+        state,
+    }
+}
+
+#[test]
+fn test_packet_roundtrip() {
+    for state in [
+        GroupState::Off,
+        GroupState::Auto,
+        GroupState::Color(RGB8::new(1, 2, 3)),
+    ] {
+        let packet = GroupPacket {
+            origin: 0xA1B2_C3D4,
+            sequence: 7,
+            ttl: 2,
+            state,
+        };
+        let bytes = packet.encode();
+        assert_eq!(bytes.len(), GROUP_PACKET_LEN);
+        assert_eq!(GroupPacket::decode(&bytes), Some(packet));
+    }
+}
+
+#[test]
+fn test_decode_rejects_invalid_packets() {
+    let bytes = packet(1, 0, GroupState::Auto).encode();
+    assert_eq!(GroupPacket::decode(&bytes[..GROUP_PACKET_LEN - 1]), None);
+
+    let mut wrong_magic = bytes;
+    wrong_magic[0] = b'X';
+    assert_eq!(GroupPacket::decode(&wrong_magic), None);
+
+    let mut unknown_state = bytes;
+    unknown_state[12] = 9;
+    assert_eq!(GroupPacket::decode(&unknown_state), None);
+}
+
+#[test]
+fn test_state_from_message() {
+    let auto = LedColorMessage::from_color(RED, true);
+    assert_eq!(GroupState::from_message(&auto), GroupState::Auto);
+    let off = LedColorMessage::from_color(RGB8::default(), false);
+    assert_eq!(GroupState::from_message(&off), GroupState::Off);
+    let red = LedColorMessage::from_color(RED, false);
+    assert_eq!(GroupState::from_message(&red), GroupState::Color(RED));
+
+    assert!(matches!(
+        GroupState::Color(RED).command(),
+        LedCommand::SetColor {
+            target_color: RED,
+            color_id: ColorId::Red,
+        }
+    ));
+    assert!(matches!(GroupState::Auto.command(), LedCommand::EnableAuto));
+}
+
+#[test]
+fn test_local_update_sends_only_changes() {
+    let mut group = GroupSync::new(1);
+    let first = group.local_update(GroupState::Auto).unwrap();
+    assert_eq!(first.origin, 1);
+    assert_eq!(first.ttl, GROUP_TTL);
+    // Weiterer Farbwechsel der Auto-Rotation: kein Paket
+    assert_eq!(group.local_update(GroupState::Auto), None);
+    let second = group.local_update(GroupState::Off).unwrap();
+    assert_eq!(second.sequence, first.sequence + 1);
+}
+
+#[test]
+fn test_received_state_is_applied_and_forwarded() {
+    let mut group = GroupSync::new(1);
+    let received = group
+        .receive(&packet(2, 0, GroupState::Color(RED)), 1000)
+        .unwrap();
+    assert!(matches!(
+        received.command,
+        Some(LedCommand::SetColor { .. })
+    ));
+    let forward = received.forward.unwrap();
+    assert_eq!(forward.ttl, GROUP_TTL - 1);
+    assert_eq!(forward.origin, 2);
+    // Übernommener Zustand wird nicht als eigene Änderung zurückgesendet
+    assert_eq!(group.local_update(GroupState::Color(RED)), None);
+    assert_eq!(group.state(), Some(GroupState::Color(RED)));
+}
+
+#[test]
+fn test_duplicates_and_own_packets_are_dropped() {
+    let mut group = GroupSync::new(1);
+    let sent = group.local_update(GroupState::Auto).unwrap();
+    // Eigenes Paket kommt über einen anderen Knoten zurück
+    assert!(group.receive(&sent, 1000).is_none());
+
+    assert!(
+        group
+            .receive(&packet(2, 5, GroupState::Off), 1000)
+            .is_some()
+    );
+    // Gleiches Paket über einen zweiten Weg
+    assert!(
+        group
+            .receive(&packet(2, 5, GroupState::Off), 1010)
+            .is_none()
+    );
+    assert!(
+        group
+            .receive(&packet(2, 4, GroupState::Auto), 1020)
+            .is_none()
+    );
+    assert!(
+        group
+            .receive(&packet(2, 6, GroupState::Auto), 1030)
+            .is_some()
+    );
+}
+
+#[test]
+fn test_ttl_zero_is_not_forwarded_and_same_state_not_reapplied() {
+    let mut group = GroupSync::new(1);
+    let mut last_hop = packet(2, 0, GroupState::Off);
+    last_hop.ttl = 0;
+    let received = group.receive(&last_hop, 0).unwrap();
+    assert_eq!(received.forward, None);
+    assert!(matches!(received.command, Some(LedCommand::Off)));
+
+    // Anderer Absender meldet denselben Zustand: weiterleiten, nicht erneut anwenden
+    let received = group.receive(&packet(3, 0, GroupState::Off), 10).unwrap();
+    assert!(received.command.is_none());
+    assert!(received.forward.is_some());
+}
+
+#[test]
+fn test_restarted_origin_is_accepted_after_timeout() {
+    let mut group = GroupSync::new(1);
+    group.receive(&packet(2, 100, GroupState::Auto), 1000);
+    assert!(
+        group
+            .receive(&packet(2, 0, GroupState::Off), 2000)
+            .is_none()
+    );
+    let later = 1000 + GROUP_ORIGIN_TIMEOUT_MS + 1;
+    assert!(
+        group
+            .receive(&packet(2, 0, GroupState::Off), later)
+            .is_some()
+    );
+}
+
+#[test]
+fn test_origin_table_evicts_longest_silent() {
+    let mut group = GroupSync::new(0);
+    for origin in 1..=GROUP_MAX_ORIGINS as u32 {
+        group.receive(&packet(origin, 0, GroupState::Auto), u64::from(origin));
+    }
+    // Neuer Absender verdrängt Absender 1 (am längsten still)
+    assert!(
+        group
+            .receive(&packet(100, 0, GroupState::Off), 50)
+            .is_some()
+    );
+    assert!(group.receive(&packet(1, 0, GroupState::Auto), 60).is_some());
+    // Absender 3 ist noch bekannt (Absender 2 wurde für 1 verdrängt)
+    assert!(group.receive(&packet(3, 0, GroupState::Auto), 70).is_none());
+}
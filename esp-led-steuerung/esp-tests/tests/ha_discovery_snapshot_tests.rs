@@ -0,0 +1,86 @@
+//! Snapshot-Tests für die Home-Assistant-Discovery (Golden Files)
+//!
+//! Home Assistant ignoriert fehlerhafte Konfigurationen kommentarlos, deshalb
+//! werden alle Discovery-Nachrichten (Topic + Payload) pro Konfiguration gegen
+//! `tests/snapshots/ha_discovery_<name>.txt` verglichen.
+//!
+//! Nach einer gewollten Formatänderung die Snapshots neu schreiben und den Diff prüfen:
+//!
+//! ```bash
+//! UPDATE_SNAPSHOTS=1 cargo test --test ha_discovery_snapshot_tests
+//! ```
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use std::path::PathBuf;
+
+use esp_core::ha_discovery::{write_discovery_config, write_discovery_topic};
+use esp_core::{HaDevice, HaSensor};
+
+/// Alle Discovery-Nachrichten eines Geräts: je Sensor Topic, Payload, Leerzeile
+fn render(prefix: &str, device: &HaDevice<'_>) -> String {
+    let mut out = String::new();
+    for sensor in HaSensor::ALL {
+        write_discovery_topic(&mut out, prefix, device.node_id, sensor).unwrap();
+        out.push('\n');
+        write_discovery_config(&mut out, device, sensor).unwrap();
+        out.push_str("\n\n");
+    }
+    out
+}
+
+/// Vergleicht mit dem Golden File, schreibt es mit `UPDATE_SNAPSHOTS=1` neu
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("ha_discovery_{name}.txt"));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing snapshot {}", path.display()));
+    // Zeilenweise vergleichen, damit der Fehler die betroffene Nachricht zeigt
+    for (line, (expected, actual)) in expected.lines().zip(actual.lines()).enumerate() {
+        assert_eq!(actual, expected, "{name}: line {}", line + 1);
+    }
+    assert_eq!(actual, expected, "{name}: length differs");
+}
+
+#[test]
+fn test_snapshot_default_config() {
+    // Standardwerte aus config.rs (HA_DISCOVERY_PREFIX, HA_DEVICE_NAME, MQTT_CLIENT_ID, ...)
+    let device = HaDevice {
+        node_id: "esp32c6-led-publisher",
+        name: "ESP32-C6 LED-Steuerung",
+        model: "ESP32-C6",
+        sw_version: "1.0.0",
+        state_topic: "devices/esp32c6/health",
+    };
+    assert_snapshot("default", &render("homeassistant", &device));
+}
+
+#[test]
+fn test_snapshot_custom_prefix() {
+    let device = HaDevice {
+        node_id: "wohnzimmer_1",
+        name: "Wohnzimmer",
+        model: "nanoESP32-C6",
+        sw_version: "1.2.0-rc1",
+        state_topic: "rooms/wohnzimmer/health",
+    };
+    assert_snapshot("custom_prefix", &render("ha/discovery", &device));
+}
+
+#[test]
+fn test_snapshot_escaped_strings() {
+    // Anführungszeichen und Backslash maskiert, Steuerzeichen entfernt
+    let device = HaDevice {
+        node_id: "esp32c6-led",
+        name: "LED \"Flur\" \\ 2\n",
+        model: "ESP32-C6",
+        sw_version: "1.0.0",
+        state_topic: "devices/esp32c6/health",
+    };
+    assert_snapshot("escaped", &render("homeassistant", &device));
+}
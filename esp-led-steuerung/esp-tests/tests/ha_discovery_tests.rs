@@ -0,0 +1,79 @@
+//! Integration Tests für die Home-Assistant-Discovery (Topics und Konfiguration)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::ha_discovery::{write_discovery_config, write_discovery_topic};
+use esp_core::{HaDevice, HaSensor};
+
+const DEVICE: HaDevice<'static> = HaDevice {
+    node_id: "esp32c6-led",
+    name: "LED-Steuerung",
+    model: "ESP32-C6",
+    sw_version: "1.0.0",
+    state_topic: "devices/esp32c6/health",
+};
+
+fn config(device: &HaDevice<'_>, sensor: HaSensor) -> String {
+    let mut out = String::new();
+    write_discovery_config(&mut out, device, sensor).unwrap();
+    out
+}
+
+#[test]
+fn test_discovery_topic() {
+    let mut topic = String::new();
+    write_discovery_topic(
+        &mut topic,
+        "homeassistant",
+        "esp32c6-led",
+        HaSensor::FreeHeap,
+    )
+    .unwrap();
+    assert_eq!(topic, "homeassistant/sensor/esp32c6-led/heap_free/config");
+}
+
+#[test]
+fn test_rssi_config() {
+    assert_eq!(
+        config(&DEVICE, HaSensor::Rssi),
+        concat!(
+            r#"{"name":"WLAN-Signal","unique_id":"esp32c6-led_rssi","#,
+            r#""state_topic":"devices/esp32c6/health","#,
+            r#""value_template":"{{ value_json.rssi }}","#,
+            r#""device_class":"signal_strength","unit_of_measurement":"dBm","#,
+            r#""state_class":"measurement","entity_category":"diagnostic","#,
+            r#""device":{"identifiers":["esp32c6-led"],"name":"LED-Steuerung","#,
+            r#""model":"ESP32-C6","sw_version":"1.0.0"}}"#,
+        )
+    );
+}
+
+#[test]
+fn test_all_sensors_share_device() {
+    for sensor in HaSensor::ALL {
+        let json = config(&DEVICE, sensor);
+        assert!(json.contains(&format!(r#""unique_id":"esp32c6-led_{}""#, sensor.key())));
+        assert!(json.contains(&format!(r#""unit_of_measurement":"{}""#, sensor.unit())));
+        assert!(json.contains(&format!("{{{{ value_json.{} }}}}", sensor.key())));
+        assert!(json.ends_with(r#""sw_version":"1.0.0"}}"#));
+    }
+}
+
+#[test]
+fn test_sensor_metadata() {
+    let keys: Vec<_> = HaSensor::ALL.iter().map(|s| s.key()).collect();
+    assert_eq!(keys, ["rssi", "uptime", "heap_free", "temperature"]);
+    assert_eq!(HaSensor::Temperature.unit(), "°C");
+    assert_eq!(HaSensor::Uptime.state_class(), "total_increasing");
+    assert_eq!(HaSensor::FreeHeap.device_class(), Some("data_size"));
+}
+
+#[test]
+fn test_strings_are_escaped() {
+    let device = HaDevice {
+        name: "LED \"Wohnzimmer\" \\ 1\n",
+        ..DEVICE
+    };
+    let json = config(&device, HaSensor::Uptime);
+    assert!(json.contains(r#""name":"LED \"Wohnzimmer\" \\ 1","#));
+}
@@ -0,0 +1,48 @@
+//! Integration Tests für ETag und If-None-Match
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::http_cache::ETAG_MAX_LEN;
+use esp_core::{ETag, if_none_match};
+
+#[test]
+fn test_etag_format() {
+    assert_eq!(
+        ETag::new("a1b2c3d", 0x6650_1234).as_str(),
+        "\"a1b2c3d-66501234\""
+    );
+    assert_eq!(ETag::new("ui", 0).as_str(), "\"ui-0\"");
+    assert_eq!(
+        ETag::new("ui", u64::MAX).as_str(),
+        "\"ui-ffffffffffffffff\""
+    );
+    assert_eq!(ETag::new("a1b2c3d", 1).to_string(), "\"a1b2c3d-1\"");
+}
+
+#[test]
+fn test_etag_sanitizes_tag() {
+    // Anführungszeichen und Leerzeichen würden den Header zerbrechen
+    assert_eq!(ETag::new("a\"b c-d", 1).as_str(), "\"abcd-1\"");
+    let long = ETag::new(&"x".repeat(100), u64::MAX);
+    assert_eq!(long.as_str().len(), ETAG_MAX_LEN);
+    assert!(long.as_str().starts_with('"') && long.as_str().ends_with('"'));
+}
+
+#[test]
+fn test_if_none_match_exact() {
+    let etag = ETag::new("a1b2c3d", 42);
+    assert!(if_none_match(b"\"a1b2c3d-2a\"", &etag));
+    assert!(!if_none_match(b"\"a1b2c3d-2b\"", &etag));
+    // Ohne Anführungszeichen ist es kein gültiger ETag
+    assert!(!if_none_match(b"a1b2c3d-2a", &etag));
+    assert!(!if_none_match(b"", &etag));
+}
+
+#[test]
+fn test_if_none_match_list_weak_and_wildcard() {
+    let etag = ETag::new("ui", 0xDEAD_BEEF);
+    assert!(if_none_match(b"\"old-1\", \"ui-deadbeef\"", &etag));
+    assert!(if_none_match(b"W/\"ui-deadbeef\"", &etag));
+    assert!(if_none_match(b" * ", &etag));
+    assert!(!if_none_match(b"\"old-1\", W/\"old-2\"", &etag));
+}
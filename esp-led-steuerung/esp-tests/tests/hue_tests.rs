@@ -0,0 +1,98 @@
+//! Integration Tests für HSV-Umrechnung und Farbton-Rotation
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::color::HUE_CIRCLE;
+use esp_core::{Hsv, rotate_hue};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+const YELLOW: RGB8 = RGB8::new(255, 255, 0);
+const GREEN: RGB8 = RGB8::new(0, 255, 0);
+const CYAN: RGB8 = RGB8::new(0, 255, 255);
+const BLUE: RGB8 = RGB8::new(0, 0, 255);
+const MAGENTA: RGB8 = RGB8::new(255, 0, 255);
+
+/// Farbkreis in 60°-Schritten
+const LANDMARKS: [RGB8; 6] = [RED, YELLOW, GREEN, CYAN, BLUE, MAGENTA];
+
+fn assert_close(actual: RGB8, expected: RGB8) {
+    let diff = |a: u8, b: u8| a.abs_diff(b);
+    assert!(
+        diff(actual.r, expected.r) <= 1
+            && diff(actual.g, expected.g) <= 1
+            && diff(actual.b, expected.b) <= 1,
+        "{actual:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn test_hsv_landmark_hues() {
+    for (index, color) in LANDMARKS.into_iter().enumerate() {
+        let hsv = Hsv::from_rgb(color);
+        assert_eq!(hsv.hue, index as u16 * HUE_CIRCLE / 6);
+        assert_eq!((hsv.saturation, hsv.value), (255, 255));
+        assert_eq!(hsv.to_rgb(), color);
+    }
+}
+
+#[test]
+fn test_hsv_gray_has_no_saturation() {
+    let hsv = Hsv::from_rgb(RGB8::new(80, 80, 80));
+    assert_eq!((hsv.hue, hsv.saturation, hsv.value), (0, 0, 80));
+    assert_eq!(hsv.to_rgb(), RGB8::new(80, 80, 80));
+}
+
+#[test]
+fn test_rotate_hits_landmarks_in_60_degree_steps() {
+    for (index, color) in LANDMARKS.into_iter().enumerate() {
+        let next = LANDMARKS[(index + 1) % LANDMARKS.len()];
+        assert_eq!(rotate_hue(color, 60), next);
+        assert_eq!(rotate_hue(next, -60), color);
+    }
+}
+
+#[test]
+fn test_rotate_by_120_degrees_matches_primary_cycle() {
+    assert_eq!(rotate_hue(RED, 120), GREEN);
+    assert_eq!(rotate_hue(GREEN, 120), BLUE);
+    assert_eq!(rotate_hue(BLUE, 120), RED);
+}
+
+#[test]
+fn test_full_turns_are_identity() {
+    let orange = RGB8::new(255, 128, 0);
+    assert_eq!(rotate_hue(orange, 360), orange);
+    assert_eq!(rotate_hue(orange, -720), orange);
+    assert_eq!(rotate_hue(orange, 0), orange);
+}
+
+#[test]
+fn test_rotate_keeps_saturation_and_value() {
+    let color = RGB8::new(200, 100, 50);
+    let before = Hsv::from_rgb(color);
+    let after = Hsv::from_rgb(rotate_hue(color, 75));
+    assert!(before.saturation.abs_diff(after.saturation) <= 1);
+    assert_eq!(before.value, after.value);
+}
+
+#[test]
+fn test_rotate_half_turn_gives_complement() {
+    assert_eq!(rotate_hue(RED, 180), CYAN);
+    assert_close(
+        rotate_hue(RGB8::new(40, 20, 10), 180),
+        RGB8::new(10, 30, 40),
+    );
+}
+
+#[test]
+fn test_rotate_back_and_forth_is_stable() {
+    let color = RGB8::new(180, 60, 120);
+    assert_close(rotate_hue(rotate_hue(color, 37), -37), color);
+}
+
+#[test]
+fn test_gray_is_unchanged() {
+    let gray = RGB8::new(90, 90, 90);
+    assert_eq!(rotate_hue(gray, 90), gray);
+}
@@ -0,0 +1,73 @@
+//! Integration Tests für die Lokalisierung der Anzeigenamen
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{ColorId, Language, LedCommand, parse_color_name, parse_text_command};
+use rgb::RGB8;
+
+const ALL_COLORS: [ColorId; 5] = [
+    ColorId::Red,
+    ColorId::Green,
+    ColorId::Blue,
+    ColorId::Off,
+    ColorId::Custom,
+];
+
+#[test]
+fn test_german_names_match_canonical_names() {
+    for id in ALL_COLORS {
+        assert_eq!(Language::German.color_name(id), id.name());
+    }
+    assert_eq!(Language::German.mode_name(true), "Auto");
+    assert_eq!(Language::German.mode_name(false), "Manuell");
+}
+
+#[test]
+fn test_english_names() {
+    assert_eq!(Language::English.color_name(ColorId::Green), "Green");
+    assert_eq!(Language::English.color_name(ColorId::Off), "Off");
+    assert_eq!(Language::English.color_name(ColorId::Custom), "Custom");
+    assert_eq!(Language::English.mode_name(false), "Manual");
+}
+
+#[test]
+fn test_language_codes_roundtrip() {
+    for language in Language::ALL {
+        assert_eq!(Language::from_code(language.code()), Some(language));
+    }
+    assert_eq!(Language::from_code("fr"), None);
+    assert_eq!(Language::default(), Language::German);
+}
+
+#[test]
+fn test_parse_color_name_accepts_all_languages() {
+    for language in Language::ALL {
+        for id in [ColorId::Red, ColorId::Green, ColorId::Blue, ColorId::Off] {
+            assert_eq!(parse_color_name(language.color_name(id)), Some(id));
+        }
+    }
+    assert_eq!(parse_color_name("GREEN"), Some(ColorId::Green));
+    assert_eq!(parse_color_name("gruen"), Some(ColorId::Green));
+    // Benutzerdefiniert ist nicht wählbar
+    assert_eq!(parse_color_name("Custom"), None);
+    assert_eq!(parse_color_name("Gelb"), None);
+}
+
+#[test]
+fn test_english_text_commands() {
+    assert!(matches!(
+        parse_text_command("green"),
+        Ok(LedCommand::SetColor {
+            target_color: RGB8 { r: 0, g: 10, b: 0 },
+            color_id: ColorId::Green,
+        })
+    ));
+    assert!(matches!(
+        LedCommand::try_from("Blue"),
+        Ok(LedCommand::SetColor {
+            color_id: ColorId::Blue,
+            ..
+        })
+    ));
+    assert!(matches!(parse_text_command("off"), Ok(LedCommand::Off)));
+}
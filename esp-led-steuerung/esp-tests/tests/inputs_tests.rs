@@ -0,0 +1,93 @@
+//! Integration Tests für die digitalen Eingänge (Pegel, Entprellung, Kommandos)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::mock::MockClock;
+use esp_core::{DigitalInput, DigitalInputConfig, LedCommand};
+
+const DOOR: DigitalInputConfig = DigitalInputConfig {
+    name: "door",
+    active_low: false,
+    active_state: "open",
+    inactive_state: "closed",
+    on_active: Some(LedCommand::ApplyPreset { id: 2 }),
+    on_inactive: Some(LedCommand::Off),
+};
+
+#[test]
+fn test_active_level() {
+    assert!(DOOR.is_active(true));
+    assert!(!DOOR.is_active(false));
+
+    let button = DigitalInputConfig {
+        active_low: true,
+        ..DOOR
+    };
+    assert!(button.is_active(false));
+    assert!(!button.is_active(true));
+}
+
+#[test]
+fn test_state_names_and_commands() {
+    assert_eq!(DOOR.state_name(true), "open");
+    assert_eq!(DOOR.state_name(false), "closed");
+    assert!(matches!(
+        DOOR.command(true),
+        Some(LedCommand::ApplyPreset { id: 2 })
+    ));
+    assert!(matches!(DOOR.command(false), Some(LedCommand::Off)));
+
+    let report_only = DigitalInputConfig {
+        on_active: None,
+        on_inactive: None,
+        ..DOOR
+    };
+    assert!(report_only.command(true).is_none());
+}
+
+#[test]
+fn test_initial_level_emits_no_event() {
+    let clock = MockClock::default();
+    let mut door = DigitalInput::new(DOOR, true, 50);
+    assert!(door.is_active());
+    clock.advance(100);
+    assert_eq!(door.update(true, &clock), None);
+}
+
+#[test]
+fn test_debounced_open_and_close() {
+    let clock = MockClock::default();
+    let mut door = DigitalInput::new(DOOR, false, 50);
+
+    // Prellen beim Öffnen
+    assert_eq!(door.update(true, &clock), None);
+    clock.advance(10);
+    assert_eq!(door.update(false, &clock), None);
+    clock.advance(10);
+    assert_eq!(door.update(true, &clock), None);
+    clock.advance(49);
+    assert_eq!(door.update(true, &clock), None);
+    clock.advance(1);
+    assert_eq!(door.update(true, &clock), Some(true));
+    assert!(door.is_active());
+
+    clock.advance(1000);
+    assert_eq!(door.update(false, &clock), None);
+    clock.advance(50);
+    assert_eq!(door.update(false, &clock), Some(false));
+}
+
+#[test]
+fn test_active_low_input_reports_logical_state() {
+    let clock = MockClock::default();
+    let config = DigitalInputConfig {
+        active_low: true,
+        ..DOOR
+    };
+    let mut input = DigitalInput::new(config, true, 20);
+    assert!(!input.is_active());
+
+    input.update(false, &clock);
+    clock.advance(20);
+    assert_eq!(input.update(false, &clock), Some(true));
+}
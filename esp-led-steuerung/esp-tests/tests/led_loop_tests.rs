@@ -0,0 +1,323 @@
+//! Integration Tests für die LED-Hauptschleife (esp_core::LedLoop)
+//!
+//! Alle Abhängigkeiten sind Mocks: LED Writer und Uhr aus esp-core
+//! (Feature `test-util`), Vec-basierte Farb-Senke und Kommando-Quelle. Diese Tests laufen auf dem Host (x86_64).
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+use esp_core::mock::{MockClock, MockLedWriter};
+use esp_core::{
+    Calibration, Clock, ColorId, ColorSink, CommandSource, LedColorMessage, LedCommand, LedLoop,
+    LedStateConfig, SeededRng,
+};
+use rgb::RGB8;
+
+// ============================================================================
+// Mocks
+// ============================================================================
+
+/// Farb-Senke, die alle Broadcasts sammelt
+#[derive(Default)]
+pub struct VecColorSink {
+    pub messages: Vec<LedColorMessage>,
+}
+
+impl ColorSink for VecColorSink {
+    fn publish(&mut self, msg: LedColorMessage) {
+        self.messages.push(msg);
+    }
+}
+
+/// Kommando-Quelle aus einer vorbereiteten Liste
+#[derive(Default)]
+pub struct VecCommandSource {
+    pub commands: VecDeque<LedCommand>,
+}
+
+impl CommandSource for VecCommandSource {
+    fn try_next(&mut self) -> Option<LedCommand> {
+        self.commands.pop_front()
+    }
+}
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 200,
+    sleep_fade_ms: 10_000,
+    brightness_fade_ms: 0,
+};
+
+fn new_loop(
+    clock: &MockClock,
+    commands: impl IntoIterator<Item = LedCommand>,
+) -> LedLoop<MockLedWriter, &MockClock, VecColorSink, VecCommandSource> {
+    LedLoop::new(
+        CONFIG,
+        MockLedWriter::default(),
+        clock,
+        VecColorSink::default(),
+        VecCommandSource {
+            commands: commands.into_iter().collect(),
+        },
+    )
+}
+
+fn set_color(color: RGB8) -> LedCommand {
+    LedCommand::SetColor {
+        target_color: color,
+        color_id: ColorId::Custom,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_auto_rotation_writes_and_publishes_each_step() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(&clock, []);
+
+    let first = led_loop.step();
+    let second = led_loop.step();
+
+    assert!(first.command.is_none());
+    assert_eq!(led_loop.led.write_count(), 2);
+    assert_eq!(led_loop.led.last_color(), Some(RGB8::new(0, 0, 10)));
+    assert_eq!(led_loop.sink.messages.len(), 2);
+    assert_eq!(led_loop.sink.messages[0].color_id, ColorId::Green);
+    assert_eq!(led_loop.sink.messages[1].color_id, ColorId::Blue);
+    assert!(led_loop.sink.messages.iter().all(|msg| msg.is_auto_mode));
+    assert!(second.published.is_some());
+    assert!(!second.animating);
+}
+
+#[test]
+fn test_set_color_is_applied_and_published() {
+    let clock = MockClock::default();
+    let color = RGB8::new(1, 2, 3);
+    let mut led_loop = new_loop(&clock, [set_color(color)]);
+
+    let cycle = led_loop.step();
+
+    assert!(matches!(cycle.command, Some(LedCommand::SetColor { .. })));
+    assert_eq!(led_loop.led.last_color(), Some(color));
+    assert_eq!(led_loop.sink.messages.len(), 1);
+    assert_eq!(led_loop.sink.messages[0].color, color);
+    assert!(!led_loop.sink.messages[0].is_auto_mode);
+
+    // Manueller Modus ohne Änderung: kein weiterer Broadcast
+    let cycle = led_loop.step();
+    assert!(cycle.published.is_none());
+    assert_eq!(led_loop.sink.messages.len(), 1);
+}
+
+#[test]
+fn test_one_command_per_step() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(&clock, [LedCommand::Off, set_color(RGB8::new(5, 5, 5))]);
+
+    assert!(matches!(led_loop.step().command, Some(LedCommand::Off)));
+    assert_eq!(led_loop.led.last_color(), Some(RGB8::default()));
+    assert_eq!(led_loop.source.commands.len(), 1);
+
+    assert!(matches!(
+        led_loop.step().command,
+        Some(LedCommand::SetColor { .. })
+    ));
+    assert!(led_loop.step().command.is_none());
+}
+
+#[test]
+fn test_pending_command_takes_precedence() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(&clock, [set_color(RGB8::new(5, 5, 5))]);
+    led_loop.pending = Some(LedCommand::Off);
+
+    assert!(matches!(led_loop.step().command, Some(LedCommand::Off)));
+    assert!(led_loop.pending.is_none());
+    assert!(matches!(
+        led_loop.step().command,
+        Some(LedCommand::SetColor { .. })
+    ));
+}
+
+#[test]
+fn test_write_failure_is_reported() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(&clock, []);
+    led_loop.led.fail_next_write();
+
+    let cycle = led_loop.step();
+    assert!(cycle.write_failed);
+    assert_eq!(led_loop.led.write_count(), 0);
+    // Broadcast erfolgt trotzdem (Zustand hat sich geändert)
+    assert!(cycle.published.is_some());
+
+    assert!(!led_loop.step().write_failed);
+}
+
+/// Uhr, die bei jeder Mikrosekunden-Abfrage um `step_us` weiterläuft
+struct SteppingClock {
+    now_us: Cell<u64>,
+    step_us: u64,
+}
+
+impl Clock for SteppingClock {
+    fn now_ms(&self) -> u64 {
+        self.now_us.get() / 1000
+    }
+
+    fn now_us(&self) -> u64 {
+        let now = self.now_us.get();
+        self.now_us.set(now + self.step_us);
+        now
+    }
+}
+
+#[test]
+fn test_frame_timing_measures_render_and_write() {
+    let clock = SteppingClock {
+        now_us: Cell::new(0),
+        step_us: 40,
+    };
+    let mut led_loop = LedLoop::new(
+        CONFIG,
+        MockLedWriter::default(),
+        &clock,
+        VecColorSink::default(),
+        VecCommandSource::default(),
+    );
+
+    let cycle = led_loop.step();
+    assert_eq!(cycle.timing.render_us, 40);
+    assert_eq!(cycle.timing.write_us, 40);
+    assert_eq!(cycle.timing.total_us(), 80);
+}
+
+#[test]
+fn test_frame_timing_zero_with_frozen_clock() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(&clock, []);
+    assert_eq!(led_loop.step().timing.total_us(), 0);
+}
+
+#[test]
+fn test_brightness_scales_output_without_broadcast() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(
+        &clock,
+        [
+            set_color(RGB8::new(200, 100, 0)),
+            LedCommand::SetBrightness { brightness: 128 },
+        ],
+    );
+
+    led_loop.step();
+    let cycle = led_loop.step();
+
+    assert!(cycle.published.is_none());
+    // Wahrnehmungsgerechte Kurve: 128 → 47/255
+    assert_eq!(led_loop.led.last_color(), Some(RGB8::new(37, 18, 0)));
+}
+
+#[test]
+fn test_sleep_timer_expires_through_loop() {
+    let clock = MockClock::default();
+    let mut led_loop = new_loop(
+        &clock,
+        [
+            set_color(RGB8::new(100, 100, 100)),
+            LedCommand::SleepTimer { minutes: 1 },
+        ],
+    );
+
+    led_loop.step();
+    let cycle = led_loop.step();
+    assert!(!cycle.animating);
+
+    // In der Ausblendphase (letzte 10 s): höhere Bildrate
+    clock.advance(55_000);
+    let cycle = led_loop.step();
+    assert!(cycle.animating);
+    assert!(led_loop.led.last_color().unwrap().r < 100);
+
+    clock.advance(5_000);
+    let cycle = led_loop.step();
+    assert!(cycle.transition.sleep_expired);
+    assert_eq!(led_loop.led.last_color(), Some(RGB8::default()));
+    assert_eq!(
+        led_loop.sink.messages.last().unwrap().color_id,
+        ColorId::Off
+    );
+    assert!(!cycle.animating);
+}
+
+#[test]
+fn test_broadcasts_carry_sequence_and_timestamp() {
+    let clock = MockClock::new(1000);
+    let mut led_loop = new_loop(&clock, []);
+
+    led_loop.step();
+    clock.advance(250);
+    led_loop.step();
+
+    let messages = &led_loop.sink.messages;
+    assert_eq!(messages[0].sequence, 0);
+    assert_eq!(messages[0].timestamp_ms, 1000);
+    assert_eq!(messages[1].sequence, 1);
+    assert_eq!(messages[1].timestamp_ms, 1250);
+    assert!(messages[1].is_newer_than(messages[0].sequence));
+    assert_eq!(messages[1].missed_since(messages[0].sequence), 0);
+}
+
+#[test]
+fn test_sequence_gap_and_wraparound() {
+    let msg = LedColorMessage::from_color(RGB8::new(10, 0, 0), true);
+
+    // Zwei Nachrichten verpasst (Queue übergelaufen)
+    let later = msg.stamped(5, 0);
+    assert_eq!(later.missed_since(2), 2);
+    assert!(later.is_newer_than(2));
+    // Veraltete bzw. doppelte Nachricht
+    assert!(!later.is_newer_than(6));
+    assert!(!later.is_newer_than(5));
+
+    // Überlauf: 0 folgt auf u32::MAX
+    let wrapped = msg.stamped(0, 0);
+    assert!(wrapped.is_newer_than(u32::MAX));
+    assert_eq!(wrapped.missed_since(u32::MAX), 0);
+}
+
+#[test]
+fn test_calibration_applies_to_output_only() {
+    let clock = MockClock::default();
+    let color = RGB8::new(200, 200, 200);
+    let mut led_loop = new_loop(&clock, [set_color(color)]);
+    led_loop.calibration = Calibration::new(255, 128, 0);
+
+    led_loop.step();
+
+    // LED bekommt die korrigierte Farbe, Broadcast meldet die gewünschte
+    assert_eq!(led_loop.led.last_color(), Some(RGB8::new(200, 100, 0)));
+    assert_eq!(led_loop.sink.messages[0].color, color);
+}
+
+#[test]
+fn test_random_mode_uses_loop_rng() {
+    let clock = MockClock::default();
+    let run = |seed| {
+        let mut led_loop =
+            new_loop(&clock, [LedCommand::EnableRandom]).with_rng(SeededRng::new(seed));
+        (0..4)
+            .map(|_| led_loop.step().published.map(|msg| msg.color))
+            .collect::<Vec<_>>()
+    };
+
+    let colors = run(5);
+    assert!(colors.iter().all(Option::is_some));
+    // Gleicher Seed → gleiche Folge
+    assert_eq!(colors, run(5));
+    assert_ne!(colors, run(6));
+}
@@ -0,0 +1,302 @@
+//! Deterministische Simulation der LED-Hauptschleife (wie `led_blink_task`)
+//!
+//! Ein Skript legt fest, wann welches Kommando in der Warteschlange landet.
+//! Die Simulation stellt die Uhr wie der Task vor (`LedCycle::next_delay_ms`),
+//! Kommandos werden also erst beim nächsten Durchlauf gelesen. Geprüft werden
+//! die exakte Folge der LED-Schreibvorgänge (mit Zeitstempel) und aller Broadcasts.
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use std::collections::VecDeque;
+
+use esp_core::mock::{MockClock, MockLedWriter};
+use esp_core::{
+    Clock, ColorSink, CommandQueue, CommandSource, LedColorMessage, LedCommand, LedLoop,
+    LedStateConfig, classify_color,
+};
+use rgb::RGB8;
+
+/// Wie `BLINK_INTERVAL_SECS` in der Firmware-Konfiguration
+const INTERVAL_MS: u64 = 1000;
+/// Wie `TRANSITION_FRAME_MS` in der Firmware-Konfiguration
+const FRAME_MS: u64 = 100;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 200,
+    sleep_fade_ms: 10_000,
+    brightness_fade_ms: 0,
+};
+
+const RED: RGB8 = RGB8::new(10, 0, 0);
+const GREEN: RGB8 = RGB8::new(0, 10, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 10);
+const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+// ============================================================================
+// Simulation
+// ============================================================================
+
+/// Farb-Senke, die alle Broadcasts sammelt
+#[derive(Default)]
+struct VecColorSink {
+    messages: Vec<LedColorMessage>,
+}
+
+impl ColorSink for VecColorSink {
+    fn publish(&mut self, msg: LedColorMessage) {
+        self.messages.push(msg);
+    }
+}
+
+/// Kommando-Quelle mit den Regeln der Firmware-Warteschlange
+#[derive(Default)]
+struct QueueSource {
+    queue: CommandQueue<4>,
+}
+
+impl CommandSource for QueueSource {
+    fn try_next(&mut self) -> Option<LedCommand> {
+        self.queue.pop()
+    }
+}
+
+struct Simulation {
+    clock: MockClock,
+    led_loop: LedLoop<MockLedWriter, MockClock, VecColorSink, QueueSource>,
+    /// (Zeitpunkt in ms, Kommando), aufsteigend sortiert
+    script: VecDeque<(u64, LedCommand)>,
+}
+
+impl Simulation {
+    fn new(script: impl IntoIterator<Item = (u64, LedCommand)>) -> Self {
+        let clock = MockClock::default();
+        let led_loop = LedLoop::new(
+            CONFIG,
+            MockLedWriter::with_clock(clock.clone()),
+            clock.clone(),
+            VecColorSink::default(),
+            QueueSource::default(),
+        );
+        Self {
+            clock,
+            led_loop,
+            script: script.into_iter().collect(),
+        }
+    }
+
+    /// Führt Durchläufe aus, solange die Uhr vor `end_ms` steht
+    fn run_until(&mut self, end_ms: u64) {
+        loop {
+            let now_ms = self.clock.now_ms();
+            // Bis jetzt eingetroffene Kommandos einreihen (wie die Sender-Tasks)
+            while self
+                .script
+                .front()
+                .is_some_and(|(at_ms, _)| *at_ms <= now_ms)
+            {
+                let (_, command) = self.script.pop_front().unwrap();
+                self.led_loop.source.queue.push(command);
+            }
+            if now_ms >= end_ms {
+                break;
+            }
+            let cycle = self.led_loop.step();
+            self.clock
+                .advance(cycle.next_delay_ms(INTERVAL_MS, FRAME_MS));
+        }
+    }
+
+    /// Alle Schreibvorgänge als (Zeitpunkt, Farbe)
+    fn writes(&self) -> Vec<(u64, RGB8)> {
+        self.led_loop
+            .led
+            .successful()
+            .map(|write| (write.timestamp_ms, write.pixels[0]))
+            .collect()
+    }
+
+    /// Alle Broadcasts als (Sequenz, Zeitpunkt, Farbe, Auto-Modus)
+    fn published(&self) -> Vec<(u32, u64, RGB8, bool)> {
+        self.led_loop
+            .sink
+            .messages
+            .iter()
+            .map(|msg| (msg.sequence, msg.timestamp_ms, msg.color, msg.is_auto_mode))
+            .collect()
+    }
+}
+
+fn set_color(color: RGB8) -> LedCommand {
+    LedCommand::SetColor {
+        target_color: color,
+        color_id: classify_color(color),
+    }
+}
+
+fn fade_to(color: RGB8, transition_ms: u32) -> LedCommand {
+    LedCommand::FadeTo {
+        target_color: color,
+        color_id: classify_color(color),
+        transition_ms,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_auto_rotation_timeline() {
+    let mut sim = Simulation::new([]);
+    sim.run_until(4000);
+
+    assert_eq!(
+        sim.writes(),
+        [(0, GREEN), (1000, BLUE), (2000, RED), (3000, GREEN)]
+    );
+    assert_eq!(
+        sim.published(),
+        [
+            (0, 0, GREEN, true),
+            (1, 1000, BLUE, true),
+            (2, 2000, RED, true),
+            (3, 3000, GREEN, true),
+        ]
+    );
+}
+
+#[test]
+fn test_command_is_applied_at_next_step() {
+    // Kommando mitten im Intervall: die Wartezeit wird nicht unterbrochen
+    let color = RGB8::new(5, 5, 5);
+    let mut sim = Simulation::new([(500, set_color(color))]);
+    sim.run_until(3000);
+
+    assert_eq!(sim.writes(), [(0, GREEN), (1000, color), (2000, color)]);
+    // Manueller Modus ohne Änderung: kein weiterer Broadcast
+    assert_eq!(
+        sim.published(),
+        [(0, 0, GREEN, true), (1, 1000, color, false)]
+    );
+}
+
+#[test]
+fn test_mode_switch_manual_and_back_to_auto() {
+    let mut sim = Simulation::new([(500, set_color(RED)), (1500, LedCommand::EnableAuto)]);
+    sim.run_until(4000);
+
+    // Auto-Rotation setzt nach der manuellen Farbe fort
+    assert_eq!(
+        sim.writes(),
+        [(0, GREEN), (1000, RED), (2000, GREEN), (3000, BLUE)]
+    );
+    assert_eq!(
+        sim.published(),
+        [
+            (0, 0, GREEN, true),
+            (1, 1000, RED, false),
+            (2, 2000, GREEN, true),
+            (3, 3000, BLUE, true),
+        ]
+    );
+}
+
+#[test]
+fn test_fade_runs_at_frame_rate_and_publishes_target_once() {
+    let target = RGB8::new(200, 0, 0);
+    let mut sim = Simulation::new([(500, fade_to(target, 400))]);
+    sim.run_until(3000);
+
+    // Verlauf ab der aktuellen Farbe (Grün) in 100-ms-Frames, danach wieder 1-s-Intervall
+    assert_eq!(
+        sim.writes(),
+        [
+            (0, GREEN),
+            (1000, GREEN),
+            (1100, RGB8::new(50, 8, 0)),
+            (1200, RGB8::new(100, 5, 0)),
+            (1300, RGB8::new(150, 3, 0)),
+            (1400, target),
+            (2400, target),
+        ]
+    );
+    // Zielfarbe wird schon beim Start gemeldet, das Ende des Verlaufs nicht erneut
+    assert_eq!(
+        sim.published(),
+        [(0, 0, GREEN, true), (1, 1000, target, false)]
+    );
+}
+
+#[test]
+fn test_command_interrupts_running_fade() {
+    let target = RGB8::new(200, 0, 0);
+    let mut sim = Simulation::new([(500, fade_to(target, 400)), (1150, set_color(BLUE))]);
+    sim.run_until(3000);
+
+    assert_eq!(
+        sim.writes(),
+        [
+            (0, GREEN),
+            (1000, GREEN),
+            (1100, RGB8::new(50, 8, 0)),
+            (1200, BLUE),
+            (2200, BLUE),
+        ]
+    );
+    assert_eq!(
+        sim.published(),
+        [
+            (0, 0, GREEN, true),
+            (1, 1000, target, false),
+            (2, 1200, BLUE, false),
+        ]
+    );
+}
+
+#[test]
+fn test_color_race_within_one_interval_keeps_newest() {
+    // Zwei Farbwechsel vor dem nächsten Durchlauf: nur der neueste zählt
+    let mut sim = Simulation::new([(200, set_color(RED)), (700, set_color(BLUE))]);
+    sim.run_until(3000);
+
+    assert_eq!(sim.writes(), [(0, GREEN), (1000, BLUE), (2000, BLUE)]);
+    assert_eq!(
+        sim.published(),
+        [(0, 0, GREEN, true), (1, 1000, BLUE, false)]
+    );
+}
+
+#[test]
+fn test_off_overtakes_waiting_mode_switch() {
+    // Off hat höhere Priorität als der früher eingetroffene Modus-Wechsel
+    let mut sim = Simulation::new([(300, LedCommand::EnableRandom), (600, LedCommand::Off)]);
+    sim.run_until(1500);
+
+    assert_eq!(sim.writes(), [(0, GREEN), (1000, OFF)]);
+    assert_eq!(
+        sim.published(),
+        [(0, 0, GREEN, true), (1, 1000, OFF, false)]
+    );
+    assert!(matches!(
+        sim.led_loop.source.queue.pop(),
+        Some(LedCommand::EnableRandom)
+    ));
+}
+
+#[test]
+fn test_simultaneous_commands_one_per_step() {
+    // Gleicher Zeitpunkt, verschiedene Arten: FIFO, ein Kommando pro Durchlauf
+    let mut sim = Simulation::new([(500, set_color(RED)), (500, LedCommand::EnableAuto)]);
+    sim.run_until(3000);
+
+    assert_eq!(sim.writes(), [(0, GREEN), (1000, RED), (2000, GREEN)]);
+    assert_eq!(
+        sim.published(),
+        [
+            (0, 0, GREEN, true),
+            (1, 1000, RED, false),
+            (2, 2000, GREEN, true),
+        ]
+    );
+}
@@ -1,40 +1,16 @@
 //! Integration Tests für LED Logic
 //!
-//! Diese Tests laufen auf dem Host (x86_64) und nutzen MockLedWriter
-
-use esp_core::{LedColorMessage, LedCommand, LedError, SmartLedWriter, rotate_color};
+//! Diese Tests laufen auf dem Host (x86_64) und nutzen den gemeinsamen
+//! MockLedWriter aus esp-core (Feature `test-util`)
+
+use esp_core::mock::{MockClock, MockLedWriter};
+use esp_core::{
+    BleLedState, COLOR_LABEL_LEN, Clock, ColorId, ColorLabel, CommandParseError, Language,
+    LedColorMessage, LedCommand, LedError, SmartLedWriter, classify_color, decode_ble_payload,
+    encode_ble_payload, parse_text_command, rotate_color,
+};
 use rgb::RGB8;
 
-// ============================================================================
-// Mock LED Writer
-// ============================================================================
-
-#[derive(Default)]
-pub struct MockLedWriter {
-    pub last_color: Option<RGB8>,
-    pub write_count: usize,
-    pub fail_next_write: bool,
-}
-
-impl MockLedWriter {
-    pub fn new() -> Self {
-        Self::default()
-    }
-}
-
-impl SmartLedWriter for MockLedWriter {
-    fn write(&mut self, color: RGB8) -> Result<(), LedError> {
-        if self.fail_next_write {
-            self.fail_next_write = false;
-            return Err(LedError::WriteFailed);
-        }
-
-        self.last_color = Some(color);
-        self.write_count += 1;
-        Ok(())
-    }
-}
-
 // ============================================================================
 // Tests: MockLedWriter
 // ============================================================================
@@ -44,13 +20,13 @@ fn test_mock_led_writer_write() {
     let mut mock = MockLedWriter::new();
     let color = RGB8 { r: 10, g: 0, b: 0 };
 
-    assert_eq!(mock.write_count, 0);
-    assert_eq!(mock.last_color, None);
+    assert_eq!(mock.write_count(), 0);
+    assert_eq!(mock.last_color(), None);
 
     mock.write(color).unwrap();
 
-    assert_eq!(mock.write_count, 1);
-    assert_eq!(mock.last_color, Some(color));
+    assert_eq!(mock.write_count(), 1);
+    assert_eq!(mock.last_color(), Some(color));
 }
 
 #[test]
@@ -61,25 +37,25 @@ fn test_mock_led_writer_multiple_writes() {
     mock.write(RGB8 { r: 0, g: 10, b: 0 }).unwrap();
     mock.write(RGB8 { r: 0, g: 0, b: 10 }).unwrap();
 
-    assert_eq!(mock.write_count, 3);
-    assert_eq!(mock.last_color, Some(RGB8 { r: 0, g: 0, b: 10 }));
+    assert_eq!(mock.write_count(), 3);
+    assert_eq!(mock.last_color(), Some(RGB8 { r: 0, g: 0, b: 10 }));
 }
 
 #[test]
 fn test_mock_led_writer_fail() {
     let mut mock = MockLedWriter::new();
-    mock.fail_next_write = true;
+    mock.fail_next_write();
 
     let result = mock.write(RGB8 { r: 10, g: 0, b: 0 });
     assert_eq!(result, Err(LedError::WriteFailed));
-    assert_eq!(mock.write_count, 0);
-    assert_eq!(mock.last_color, None);
+    assert_eq!(mock.write_count(), 0);
+    assert_eq!(mock.last_color(), None);
 }
 
 #[test]
 fn test_mock_led_writer_recovers_after_fail() {
     let mut mock = MockLedWriter::new();
-    mock.fail_next_write = true;
+    mock.fail_next_write();
 
     // First write fails
     let result1 = mock.write(RGB8 { r: 10, g: 0, b: 0 });
@@ -88,8 +64,63 @@ fn test_mock_led_writer_recovers_after_fail() {
     // Second write succeeds
     let result2 = mock.write(RGB8 { r: 0, g: 10, b: 0 });
     assert!(result2.is_ok());
-    assert_eq!(mock.write_count, 1);
-    assert_eq!(mock.last_color, Some(RGB8 { r: 0, g: 10, b: 0 }));
+    assert_eq!(mock.write_count(), 1);
+    assert_eq!(mock.last_color(), Some(RGB8 { r: 0, g: 10, b: 0 }));
+}
+
+#[test]
+fn test_mock_led_writer_records_history_with_timestamps() {
+    let clock = MockClock::new(1000);
+    let mut mock = MockLedWriter::with_clock(clock.clone());
+
+    mock.write(RGB8 { r: 10, g: 0, b: 0 }).unwrap();
+    clock.advance(50);
+    let frame = [RGB8 { r: 1, g: 2, b: 3 }, RGB8 { r: 4, g: 5, b: 6 }];
+    mock.write_frame(&frame).unwrap();
+
+    assert_eq!(mock.history.len(), 2);
+    assert_eq!(mock.history[0].timestamp_ms, 1000);
+    assert_eq!(mock.history[0].pixels, [RGB8 { r: 10, g: 0, b: 0 }]);
+    assert_eq!(mock.history[1].timestamp_ms, 1050);
+    assert_eq!(mock.last_frame(), Some(&frame[..]));
+    assert_eq!(clock.now_ms(), 1050);
+}
+
+#[test]
+fn test_mock_led_writer_scripted_failures() {
+    let mut mock = MockLedWriter::new();
+    mock.script_failures([false, true, false, true]);
+
+    let results: Vec<bool> = (0..5)
+        .map(|i| mock.write(RGB8 { r: i, g: 0, b: 0 }).is_ok())
+        .collect();
+
+    assert_eq!(results, [true, false, true, false, true]);
+    assert_eq!(mock.write_count(), 3);
+    assert_eq!(mock.failed_count(), 2);
+    // Fehlgeschlagene Versuche stehen mit in der Historie
+    assert_eq!(mock.history.len(), 5);
+    assert!(mock.history[1].failed);
+    assert_eq!(mock.last_color(), Some(RGB8 { r: 4, g: 0, b: 0 }));
+}
+
+#[test]
+fn test_mock_led_writer_fail_writes_then_recovers() {
+    let mut mock = MockLedWriter::new();
+    mock.fail_writes(3);
+
+    for _ in 0..3 {
+        assert_eq!(
+            mock.write(RGB8 { r: 10, g: 0, b: 0 }),
+            Err(LedError::WriteFailed)
+        );
+    }
+    assert!(mock.write(RGB8 { r: 0, g: 10, b: 0 }).is_ok());
+    assert_eq!(mock.write_count(), 1);
+
+    mock.clear_history();
+    assert!(mock.history.is_empty());
+    assert_eq!(mock.last_color(), None);
 }
 
 // ============================================================================
@@ -134,7 +165,7 @@ fn test_rotate_color_full_cycle() {
 fn test_led_color_message_red_auto() {
     let color = RGB8 { r: 10, g: 0, b: 0 };
     let msg = LedColorMessage::from_color(color, true);
-    assert_eq!(msg.name, "Rot");
+    assert_eq!(msg.color_id.name(), "Rot");
     assert_eq!(msg.color, color);
     assert!(msg.is_auto_mode);
 }
@@ -143,7 +174,7 @@ fn test_led_color_message_red_auto() {
 fn test_led_color_message_green_manual() {
     let color = RGB8 { r: 0, g: 10, b: 0 };
     let msg = LedColorMessage::from_color(color, false);
-    assert_eq!(msg.name, "Grün");
+    assert_eq!(msg.color_id.name(), "Grün");
     assert_eq!(msg.color, color);
     assert!(!msg.is_auto_mode);
 }
@@ -152,19 +183,88 @@ fn test_led_color_message_green_manual() {
 fn test_led_color_message_blue() {
     let color = RGB8 { r: 0, g: 0, b: 10 };
     let msg = LedColorMessage::from_color(color, true);
-    assert_eq!(msg.name, "Blau");
+    assert_eq!(msg.color_id.name(), "Blau");
     assert_eq!(msg.color, color);
 }
 
 #[test]
-fn test_led_color_message_unknown() {
+fn test_led_color_message_custom() {
     let color = RGB8 {
         r: 10,
         g: 10,
         b: 10,
     };
     let msg = LedColorMessage::from_color(color, false);
-    assert_eq!(msg.name, "Unbekannt");
+    assert_eq!(msg.color_id.name(), "Benutzerdefiniert");
+    assert_eq!(msg.display_name(Language::German).as_str(), "RGB(10,10,10)");
+}
+
+#[test]
+fn test_color_label() {
+    assert_eq!(
+        ColorLabel::rgb(RGB8::new(255, 255, 255)).as_str(),
+        "RGB(255,255,255)"
+    );
+    assert_eq!(
+        ColorLabel::rgb(RGB8::new(12, 34, 56)).as_str(),
+        "RGB(12,34,56)"
+    );
+    assert_eq!(ColorLabel::new("Grün").unwrap().as_str(), "Grün");
+    assert!(ColorLabel::new("").is_none());
+    assert!(ColorLabel::new(&"x".repeat(COLOR_LABEL_LEN)).is_some());
+    assert!(ColorLabel::new(&"x".repeat(COLOR_LABEL_LEN + 1)).is_none());
+}
+
+// ============================================================================
+// Tests: ColorId
+// ============================================================================
+
+#[test]
+fn test_classify_color() {
+    assert_eq!(classify_color(RGB8::new(200, 0, 0)), ColorId::Red);
+    assert_eq!(classify_color(RGB8::new(0, 1, 0)), ColorId::Green);
+    assert_eq!(classify_color(RGB8::new(0, 0, 10)), ColorId::Blue);
+    assert_eq!(classify_color(RGB8::default()), ColorId::Off);
+    assert_eq!(classify_color(RGB8::new(10, 5, 0)), ColorId::Custom);
+}
+
+#[test]
+fn test_classify_color_tolerance() {
+    // Nahe an einer Grundfarbe, unabhängig von der Helligkeit
+    assert_eq!(classify_color(RGB8::new(255, 30, 20)), ColorId::Red);
+    assert_eq!(classify_color(RGB8::new(20, 200, 20)), ColorId::Green);
+    assert_eq!(classify_color(RGB8::new(0, 2, 10)), ColorId::Blue);
+    // Genau an der Grenze (20 % des hellsten Kanals) und knapp darüber
+    assert_eq!(classify_color(RGB8::new(30, 200, 10)), ColorId::Green);
+    assert_eq!(classify_color(RGB8::new(31, 200, 10)), ColorId::Custom);
+    // Mischfarben und Weiß
+    assert_eq!(classify_color(RGB8::new(255, 128, 0)), ColorId::Custom);
+    assert_eq!(classify_color(RGB8::new(255, 255, 255)), ColorId::Custom);
+    assert_eq!(classify_color(RGB8::new(1, 1, 1)), ColorId::Custom);
+}
+
+#[test]
+fn test_color_id_name_roundtrip() {
+    for id in [ColorId::Red, ColorId::Green, ColorId::Blue, ColorId::Off] {
+        assert_eq!(ColorId::from_name(id.name()), Some(id));
+    }
+    assert_eq!(ColorId::Custom.name(), "Benutzerdefiniert");
+    assert_eq!(ColorId::from_name("Benutzerdefiniert"), None);
+    assert_eq!(ColorId::from_name("Gelb"), None);
+}
+
+#[test]
+fn test_color_id_command() {
+    assert!(matches!(
+        ColorId::Blue.command(42),
+        Some(LedCommand::SetColor {
+            target_color: RGB8 { r: 0, g: 0, b: 42 },
+            color_id: ColorId::Blue,
+        })
+    ));
+    assert!(ColorId::Off.command(42).is_none());
+    assert!(ColorId::Custom.command(42).is_none());
+    assert_eq!(ColorId::Off.color(42), Some(RGB8::default()));
 }
 
 // ============================================================================
@@ -177,8 +277,11 @@ fn test_led_command_try_from_rot() {
     let cmd = LedCommand::try_from("Rot");
     assert!(cmd.is_ok());
     match cmd.unwrap() {
-        LedCommand::SetColor { target_color, name } => {
-            assert_eq!(name, "Rot");
+        LedCommand::SetColor {
+            target_color,
+            color_id,
+        } => {
+            assert_eq!(color_id, ColorId::Red);
             assert_eq!(target_color.r, 10); // DEFAULT_BRIGHTNESS
             assert_eq!(target_color.g, 0);
             assert_eq!(target_color.b, 0);
@@ -202,3 +305,130 @@ fn test_led_command_enable_auto() {
         _ => panic!("Expected EnableAuto variant"),
     }
 }
+
+// ============================================================================
+// Tests: BLE Advertisement Payload
+// ============================================================================
+
+#[test]
+fn test_ble_payload_encode_auto() {
+    let msg = LedColorMessage::from_color(RGB8 { r: 10, g: 0, b: 0 }, true);
+    assert_eq!(encode_ble_payload(&msg), [1, 0x01, 10, 0, 0]);
+}
+
+#[test]
+fn test_ble_payload_roundtrip_manual() {
+    let msg = LedColorMessage::from_color(RGB8 { r: 0, g: 0, b: 10 }, false);
+    let state = decode_ble_payload(&encode_ble_payload(&msg));
+    assert_eq!(
+        state,
+        Some(BleLedState {
+            color: RGB8 { r: 0, g: 0, b: 10 },
+            is_auto_mode: false,
+        })
+    );
+}
+
+#[test]
+fn test_ble_payload_decode_invalid() {
+    assert_eq!(decode_ble_payload(&[]), None);
+    assert_eq!(decode_ble_payload(&[2, 0, 10, 0, 0]), None); // unbekannte Version
+    assert_eq!(decode_ble_payload(&[1, 0, 10, 0]), None); // zu kurz
+}
+
+// ============================================================================
+// Tests: Text-Kommandos
+// ============================================================================
+
+#[test]
+fn test_parse_text_command_colors_case_insensitive() {
+    for input in ["rot", "ROT", "Rot"] {
+        match parse_text_command(input) {
+            Ok(LedCommand::SetColor { color_id, .. }) => assert_eq!(color_id, ColorId::Red),
+            _ => panic!("Expected SetColor for {}", input),
+        }
+    }
+    match parse_text_command("gruen") {
+        Ok(LedCommand::SetColor { color_id, .. }) => assert_eq!(color_id, ColorId::Green),
+        _ => panic!("Expected SetColor variant"),
+    }
+}
+
+#[test]
+fn test_parse_text_command_modes() {
+    assert!(matches!(
+        parse_text_command("auto"),
+        Ok(LedCommand::EnableAuto)
+    ));
+    assert!(matches!(parse_text_command(" off "), Ok(LedCommand::Off)));
+    assert!(matches!(
+        parse_text_command("sunrise"),
+        Ok(LedCommand::Sunrise {
+            duration_secs: 1200
+        })
+    ));
+}
+
+#[test]
+fn test_parse_text_command_transition() {
+    match parse_text_command("blau 1500") {
+        Ok(LedCommand::FadeTo {
+            color_id,
+            transition_ms,
+            ..
+        }) => {
+            assert_eq!(color_id, ColorId::Blue);
+            assert_eq!(transition_ms, 1500);
+        }
+        _ => panic!("Expected FadeTo variant"),
+    }
+    assert!(matches!(
+        parse_text_command("aus 500"),
+        Ok(LedCommand::FadeTo {
+            color_id: ColorId::Off,
+            transition_ms: 500,
+            ..
+        })
+    ));
+    // 0 = sofort
+    assert!(matches!(
+        parse_text_command("rot 0"),
+        Ok(LedCommand::SetColor { .. })
+    ));
+    assert_eq!(
+        parse_text_command("rot schnell").err(),
+        Some(CommandParseError::InvalidArgument)
+    );
+}
+
+#[test]
+fn test_parse_text_command_sleep_timer() {
+    assert!(matches!(
+        parse_text_command("sleep 15"),
+        Ok(LedCommand::SleepTimer { minutes: 15 })
+    ));
+    assert!(matches!(
+        parse_text_command("sleep 0"),
+        Ok(LedCommand::SleepTimer { minutes: 0 })
+    ));
+    assert_eq!(
+        parse_text_command("sleep").err(),
+        Some(CommandParseError::InvalidArgument)
+    );
+    assert_eq!(
+        parse_text_command("sleep -5").err(),
+        Some(CommandParseError::InvalidArgument)
+    );
+}
+
+#[test]
+fn test_parse_text_command_errors() {
+    assert_eq!(
+        parse_text_command("   ").err(),
+        Some(CommandParseError::Empty)
+    );
+    assert_eq!(
+        parse_text_command("lila").err(),
+        Some(CommandParseError::Unknown)
+    );
+}
@@ -0,0 +1,293 @@
+//! Integration Tests für das LIFX-LAN-Protokoll
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::lifx::{HEADER_LEN, MAX_RESPONSE_LEN, PRODUCT_ID, VENDOR_ID, message_type};
+use esp_core::{
+    ColorId, Hsbk, LedCommand, LifxError, LifxLight, LifxRequest, LifxResponse, decode_lifx_packet,
+    encode_lifx_response, lifx_response,
+};
+use rgb::RGB8;
+
+const MAC: [u8; 6] = [0x40, 0x4C, 0xCA, 0x01, 0x02, 0x03];
+
+/// Baut ein Client-Paket (Header laut LIFX-Dokumentation)
+fn packet(message_type: u16, tagged: bool, flags: u8, payload: &[u8]) -> Vec<u8> {
+    let mut packet = vec![0u8; HEADER_LEN];
+    let size = (HEADER_LEN + payload.len()) as u16;
+    packet[0..2].copy_from_slice(&size.to_le_bytes());
+    let protocol = 1024u16 | 1 << 12 | if tagged { 1 << 13 } else { 0 };
+    packet[2..4].copy_from_slice(&protocol.to_le_bytes());
+    packet[4..8].copy_from_slice(&0xDEAD_BEEFu32.to_le_bytes());
+    if !tagged {
+        packet[8..14].copy_from_slice(&MAC);
+    }
+    packet[22] = flags;
+    packet[23] = 42;
+    packet[32..34].copy_from_slice(&message_type.to_le_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
+
+fn set_color_payload(color: Hsbk, duration_ms: u32) -> Vec<u8> {
+    let mut payload = vec![0u8];
+    for value in [color.hue, color.saturation, color.brightness, color.kelvin] {
+        payload.extend_from_slice(&value.to_le_bytes());
+    }
+    payload.extend_from_slice(&duration_ms.to_le_bytes());
+    payload
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+const RED: Hsbk = Hsbk {
+    hue: 0,
+    saturation: u16::MAX,
+    brightness: u16::MAX,
+    kelvin: 3500,
+};
+
+#[test]
+fn test_decode_discovery_broadcast() {
+    let (header, request) =
+        decode_lifx_packet(&packet(message_type::GET_SERVICE, true, 0, &[])).unwrap();
+    assert_eq!(request, LifxRequest::GetService);
+    assert!(header.tagged);
+    assert_eq!(header.source, 0xDEAD_BEEF);
+    assert_eq!(header.sequence, 42);
+    assert!(header.is_for(MAC));
+    assert!(header.is_for([0; 6]));
+}
+
+#[test]
+fn test_decode_targets_own_mac_only() {
+    let (header, _) = decode_lifx_packet(&packet(message_type::GET_COLOR, false, 0, &[])).unwrap();
+    assert!(header.is_for(MAC));
+    assert!(!header.is_for([1, 2, 3, 4, 5, 6]));
+}
+
+#[test]
+fn test_decode_set_color_and_flags() {
+    let payload = set_color_payload(RED, 1500);
+    let (header, request) =
+        decode_lifx_packet(&packet(message_type::SET_COLOR, false, 0x03, &payload)).unwrap();
+    assert!(header.ack_required);
+    assert!(header.res_required);
+    assert_eq!(
+        request,
+        LifxRequest::SetColor {
+            color: RED,
+            duration_ms: 1500
+        }
+    );
+}
+
+#[test]
+fn test_decode_power() {
+    let (_, request) =
+        decode_lifx_packet(&packet(message_type::SET_POWER, false, 0, &[0xFF, 0xFF])).unwrap();
+    assert_eq!(request, LifxRequest::SetPower { on: true });
+
+    let mut payload = vec![0, 0];
+    payload.extend_from_slice(&250u32.to_le_bytes());
+    let (_, request) =
+        decode_lifx_packet(&packet(message_type::SET_LIGHT_POWER, false, 0, &payload)).unwrap();
+    assert_eq!(
+        request,
+        LifxRequest::SetLightPower {
+            on: false,
+            duration_ms: 250
+        }
+    );
+}
+
+#[test]
+fn test_decode_errors() {
+    assert_eq!(decode_lifx_packet(&[0u8; 10]), Err(LifxError::Truncated));
+
+    // SetColor ohne vollständige Nutzlast
+    let short = packet(message_type::SET_COLOR, false, 0, &[0; 5]);
+    assert_eq!(decode_lifx_packet(&short), Err(LifxError::Truncated));
+
+    // Größe im Header länger als das Paket
+    let mut oversized = packet(message_type::GET_COLOR, false, 0, &[]);
+    oversized[0] = 80;
+    assert_eq!(decode_lifx_packet(&oversized), Err(LifxError::Truncated));
+
+    let mut wrong_protocol = packet(message_type::GET_COLOR, false, 0, &[]);
+    wrong_protocol[2] = 0x01;
+    assert_eq!(
+        decode_lifx_packet(&wrong_protocol),
+        Err(LifxError::InvalidProtocol)
+    );
+
+    let (_, request) = decode_lifx_packet(&packet(58, false, 0, &[0; 64])).unwrap();
+    assert_eq!(request, LifxRequest::Unsupported(58));
+}
+
+#[test]
+fn test_hsbk_conversion() {
+    assert_eq!(RED.to_rgb(), RGB8::new(255, 0, 0));
+    let blue = Hsbk { hue: 43690, ..RED };
+    assert_eq!(blue.to_rgb(), RGB8::new(0, 0, 255));
+
+    // Sättigung 0: Weiß laut Farbtemperatur, skaliert mit der Helligkeit
+    let white = Hsbk {
+        saturation: 0,
+        brightness: 32768,
+        kelvin: 6500,
+        ..RED
+    };
+    assert_eq!(white.to_rgb(), RGB8::new(128, 125, 127));
+
+    let green = Hsbk::from_rgb(RGB8::new(0, 255, 0), 2700);
+    assert_eq!(green.saturation, u16::MAX);
+    assert_eq!(green.brightness, u16::MAX);
+    assert_eq!(green.kelvin, 2700);
+    assert_eq!(green.to_rgb(), RGB8::new(0, 255, 0));
+}
+
+#[test]
+fn test_set_color_while_off_only_stores() {
+    let mut light = LifxLight::default();
+    let request = LifxRequest::SetColor {
+        color: RED,
+        duration_ms: 0,
+    };
+    assert_eq!(light.apply(&request), None);
+    assert_eq!(light.color, RED);
+
+    assert_eq!(
+        light.apply(&LifxRequest::SetPower { on: true }),
+        Some(LedCommand::SetColor {
+            target_color: RGB8::new(255, 0, 0),
+            color_id: ColorId::Red,
+        })
+    );
+    assert_eq!(
+        light.apply(&LifxRequest::SetLightPower {
+            on: false,
+            duration_ms: 1000
+        }),
+        Some(LedCommand::FadeTo {
+            target_color: RGB8::default(),
+            color_id: ColorId::Off,
+            transition_ms: 1000,
+        })
+    );
+    assert!(!light.on);
+    assert_eq!(light.apply(&LifxRequest::GetColor), None);
+}
+
+#[test]
+fn test_sync_keeps_own_color() {
+    let mut light = LifxLight {
+        on: true,
+        color: Hsbk {
+            saturation: 0,
+            ..RED
+        },
+    };
+    let own = light.color;
+    light.sync_from_color(own.to_rgb());
+    assert_eq!(light.color, own);
+
+    light.sync_from_color(RGB8::new(0, 0, 255));
+    assert!(light.on);
+    assert_eq!(light.color.to_rgb(), RGB8::new(0, 0, 255));
+
+    light.sync_from_color(RGB8::default());
+    assert!(!light.on);
+    assert_eq!(light.color.to_rgb(), RGB8::new(0, 0, 255));
+}
+
+#[test]
+fn test_responses_depend_on_flags() {
+    let light = LifxLight::default();
+    let (header, request) =
+        decode_lifx_packet(&packet(message_type::SET_POWER, false, 0x02, &[0, 0])).unwrap();
+    assert_eq!(lifx_response(&header, &request, &light, "led"), None);
+
+    let (header, request) =
+        decode_lifx_packet(&packet(message_type::SET_POWER, false, 0x01, &[0, 0])).unwrap();
+    assert_eq!(
+        lifx_response(&header, &request, &light, "led"),
+        Some(LifxResponse::StatePower { on: false })
+    );
+
+    let (header, request) =
+        decode_lifx_packet(&packet(message_type::GET_LABEL, false, 0, &[])).unwrap();
+    assert_eq!(
+        lifx_response(&header, &request, &light, "led"),
+        Some(LifxResponse::StateLabel { label: "led" })
+    );
+}
+
+#[test]
+fn test_encode_state_service() {
+    let (header, request) =
+        decode_lifx_packet(&packet(message_type::GET_SERVICE, true, 0, &[])).unwrap();
+    let response = lifx_response(&header, &request, &LifxLight::default(), "led").unwrap();
+    let mut out = [0u8; MAX_RESPONSE_LEN];
+    let len = encode_lifx_response(&mut out, &header, MAC, &response).unwrap();
+    assert_eq!(len, HEADER_LEN + 5);
+    assert_eq!(u16_at(&out, 0), len as u16);
+    // Protokoll 1024, addressable, nicht tagged
+    assert_eq!(u16_at(&out, 2), 0x1400);
+    assert_eq!(&out[4..8], &0xDEAD_BEEFu32.to_le_bytes());
+    assert_eq!(&out[8..14], &MAC);
+    assert_eq!(out[23], 42);
+    assert_eq!(u16_at(&out, 32), message_type::STATE_SERVICE);
+    assert_eq!(out[HEADER_LEN], 1);
+    assert_eq!(u16_at(&out, HEADER_LEN + 1), 56700);
+}
+
+#[test]
+fn test_encode_light_state() {
+    let light = LifxLight {
+        on: true,
+        color: RED,
+    };
+    let (header, _) = decode_lifx_packet(&packet(message_type::GET_COLOR, false, 0, &[])).unwrap();
+    let label = "Ein sehr langer Lampenname mit mehr als 32 Bytes";
+    let response = LifxResponse::LightState { light, label };
+    let mut out = [0xAAu8; MAX_RESPONSE_LEN];
+    let len = encode_lifx_response(&mut out, &header, MAC, &response).unwrap();
+    assert_eq!(len, MAX_RESPONSE_LEN);
+    assert_eq!(u16_at(&out, 32), message_type::LIGHT_STATE);
+
+    let payload = &out[HEADER_LEN..len];
+    assert_eq!(u16_at(payload, 0), 0);
+    assert_eq!(u16_at(payload, 2), u16::MAX);
+    assert_eq!(u16_at(payload, 4), u16::MAX);
+    assert_eq!(u16_at(payload, 6), 3500);
+    assert_eq!(u16_at(payload, 10), u16::MAX);
+    assert_eq!(&payload[12..44], &label.as_bytes()[..32]);
+    assert_eq!(&payload[44..52], &[0; 8]);
+}
+
+#[test]
+fn test_encode_version_and_ack() {
+    let (header, _) =
+        decode_lifx_packet(&packet(message_type::GET_VERSION, false, 0, &[])).unwrap();
+    let mut out = [0u8; MAX_RESPONSE_LEN];
+    let len = encode_lifx_response(&mut out, &header, MAC, &LifxResponse::StateVersion).unwrap();
+    assert_eq!(len, HEADER_LEN + 12);
+    assert_eq!(&out[HEADER_LEN..HEADER_LEN + 4], &VENDOR_ID.to_le_bytes());
+    assert_eq!(
+        &out[HEADER_LEN + 4..HEADER_LEN + 8],
+        &PRODUCT_ID.to_le_bytes()
+    );
+
+    let len = encode_lifx_response(&mut out, &header, MAC, &LifxResponse::Acknowledgement).unwrap();
+    assert_eq!(len, HEADER_LEN);
+    assert_eq!(u16_at(&out, 32), message_type::ACKNOWLEDGEMENT);
+
+    let mut small = [0u8; HEADER_LEN];
+    assert_eq!(
+        encode_lifx_response(&mut small, &header, MAC, &LifxResponse::StateVersion),
+        Err(LifxError::BufferTooSmall)
+    );
+}
@@ -0,0 +1,86 @@
+//! Integration Tests für den Log-Ringpuffer
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{LogLevel, LogRing};
+
+fn texts<const N: usize, const LEN: usize>(ring: &LogRing<N, LEN>) -> Vec<&str> {
+    ring.iter().map(|line| line.text()).collect()
+}
+
+#[test]
+fn test_push_and_iterate_in_order() {
+    let mut ring = LogRing::<4, 32>::new();
+    assert!(ring.is_empty());
+
+    ring.push(100, LogLevel::Info, format_args!("boot"));
+    ring.push(200, LogLevel::Warn, format_args!("heap {}%", 90));
+
+    assert_eq!(ring.len(), 2);
+    assert_eq!(texts(&ring), ["boot", "heap 90%"]);
+
+    let last = ring.iter().last().unwrap();
+    assert_eq!(last.seq, 1);
+    assert_eq!(last.timestamp_ms, 200);
+    assert_eq!(last.level, LogLevel::Warn);
+}
+
+#[test]
+fn test_overwrites_oldest_when_full() {
+    let mut ring = LogRing::<3, 16>::new();
+    for i in 0..5 {
+        ring.push(i, LogLevel::Info, format_args!("line {}", i));
+    }
+    assert_eq!(ring.len(), 3);
+    assert_eq!(texts(&ring), ["line 2", "line 3", "line 4"]);
+    assert_eq!(ring.dropped(), 2);
+}
+
+#[test]
+fn test_long_lines_truncated_at_char_boundary() {
+    let mut ring = LogRing::<2, 5>::new();
+    ring.push(0, LogLevel::Info, format_args!("abcdefgh"));
+    // "ü" belegt 2 Bytes und passt nur noch zur Hälfte: wird ganz weggelassen
+    ring.push(0, LogLevel::Info, format_args!("aaaaü"));
+    assert_eq!(texts(&ring), ["abcde", "aaaa"]);
+}
+
+#[test]
+fn test_since_returns_only_new_lines() {
+    let mut ring = LogRing::<4, 16>::new();
+    for i in 0..6 {
+        ring.push(i, LogLevel::Info, format_args!("{}", i));
+    }
+    let seqs: Vec<u32> = ring.since(4).map(|line| line.seq).collect();
+    assert_eq!(seqs, [4, 5]);
+
+    // Bereits überschriebene Nummern: alles Vorhandene
+    let seqs: Vec<u32> = ring.since(0).map(|line| line.seq).collect();
+    assert_eq!(seqs, [2, 3, 4, 5]);
+
+    // Nichts Neues
+    assert_eq!(ring.since(6).count(), 0);
+}
+
+#[test]
+fn test_next_seq_counts_pushed_lines() {
+    let mut ring = LogRing::<4, 16>::new();
+    assert_eq!(ring.next_seq(), 0);
+    for i in 0..6 {
+        ring.push(i, LogLevel::Info, format_args!("{}", i));
+    }
+    assert_eq!(ring.next_seq(), 6);
+    assert_eq!(ring.since(ring.next_seq()).count(), 0);
+}
+
+#[test]
+fn test_display_format() {
+    let mut ring = LogRing::<2, 32>::new();
+    ring.push(73_999, LogLevel::Warn, format_args!("heap {}%", 90));
+    ring.push(5, LogLevel::Error, format_args!("boot"));
+    let lines: Vec<String> = ring.iter().map(|line| line.to_string()).collect();
+    assert_eq!(
+        lines,
+        ["[    73.999] warn  heap 90%", "[     0.005] error boot"]
+    );
+}
@@ -0,0 +1,108 @@
+//! Integration Tests für das Matter-Lichtmodell
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::matter::{MAX_LEVEL, hsv_to_rgb, rgb_to_hsv};
+use esp_core::{ColorId, LedCommand, MatterAttribute, MatterLight};
+use rgb::RGB8;
+
+fn set_color(command: LedCommand) -> Option<RGB8> {
+    match command {
+        LedCommand::SetColor { target_color, .. } => Some(target_color),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_hsv_primary_colors() {
+    assert_eq!(hsv_to_rgb(0, MAX_LEVEL, 255), RGB8::new(255, 0, 0));
+    // Hue 85 ≈ 120,5° (Quantisierung der 0-254-Skala)
+    assert_eq!(hsv_to_rgb(85, MAX_LEVEL, 255), RGB8::new(0, 255, 2));
+    assert_eq!(hsv_to_rgb(0, 0, 128), RGB8::new(128, 128, 128));
+
+    assert_eq!(rgb_to_hsv(RGB8::new(255, 0, 0)), (0, MAX_LEVEL, 255));
+    assert_eq!(rgb_to_hsv(RGB8::new(0, 10, 0)), (85, MAX_LEVEL, 10));
+    assert_eq!(rgb_to_hsv(RGB8::new(0, 0, 255)), (169, MAX_LEVEL, 255));
+    assert_eq!(rgb_to_hsv(RGB8::new(40, 40, 40)), (0, 0, 40));
+}
+
+#[test]
+fn test_hsv_roundtrip() {
+    for color in [
+        RGB8::new(255, 128, 0),
+        RGB8::new(10, 0, 10),
+        RGB8::new(0, 200, 255),
+        RGB8::new(255, 180, 107),
+    ] {
+        let (h, s, v) = rgb_to_hsv(color);
+        let back = hsv_to_rgb(h, s, v);
+        let close = |a: u8, b: u8| a.abs_diff(b) <= 3;
+        assert!(
+            close(back.r, color.r) && close(back.g, color.g) && close(back.b, color.b),
+            "{color:?} -> {back:?}"
+        );
+    }
+}
+
+#[test]
+fn test_apply_attributes() {
+    let mut light = MatterLight::default();
+    assert!(matches!(light.command(), LedCommand::Off));
+
+    // Einschalten: Weiß bei voller Helligkeit
+    let color = set_color(light.apply(MatterAttribute::OnOff(true))).unwrap();
+    assert_eq!(color, RGB8::new(255, 255, 255));
+
+    // Rot, halbe Helligkeit
+    light.apply(MatterAttribute::HueSaturation {
+        hue: 0,
+        saturation: MAX_LEVEL,
+    });
+    let command = light.apply(MatterAttribute::Level(127));
+    assert!(matches!(
+        command,
+        LedCommand::SetColor {
+            target_color: RGB8 { r: 127, g: 0, b: 0 },
+            color_id: ColorId::Red
+        }
+    ));
+
+    // Ausschalten merkt sich die Farbe
+    assert!(matches!(
+        light.apply(MatterAttribute::OnOff(false)),
+        LedCommand::Off
+    ));
+    let color = set_color(light.apply(MatterAttribute::OnOff(true))).unwrap();
+    assert_eq!(color, RGB8::new(127, 0, 0));
+
+    // Level 0 ist reserviert und wird auf 1 angehoben
+    light.apply(MatterAttribute::Level(0));
+    assert_eq!(light.level, 1);
+}
+
+#[test]
+fn test_color_temperature() {
+    let mut light = MatterLight::default();
+    // 2700 K ≈ 370 Mireds: warmweiß, Rot dominiert
+    let color = set_color(light.apply(MatterAttribute::ColorTemperature { mireds: 370 })).unwrap();
+    assert_eq!(color.r, 255);
+    assert!(color.g > color.b);
+    assert!(light.on);
+}
+
+#[test]
+fn test_sync_from_color() {
+    let mut light = MatterLight::default();
+    assert!(light.sync_from_color(RGB8::new(0, 0, 10)));
+    assert!(light.on);
+    assert_eq!(light.hue, 169);
+    assert_eq!(light.level, 10);
+
+    // Unveränderte Farbe meldet keine Änderung
+    assert!(!light.sync_from_color(RGB8::new(0, 0, 10)));
+
+    // Aus behält Farbe und Level
+    assert!(light.sync_from_color(RGB8::default()));
+    assert!(!light.on);
+    assert_eq!(light.hue, 169);
+}
@@ -0,0 +1,146 @@
+//! Integration Tests für den Meteor-Effekt
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::meteor::{DEFAULT_METEOR_SPEED, DEFAULT_METEOR_TAIL};
+use esp_core::ws::{WsRequest, parse_ws_message};
+use esp_core::{CommandParseError, LedCommand, Meteor, parse_text_command};
+use rgb::RGB8;
+
+const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+// ============================================================================
+// Meteor
+// ============================================================================
+
+#[test]
+fn test_head_moves_with_speed() {
+    // 10 Pixel pro Sekunde, Start bei 1000 ms
+    let meteor = Meteor::new(1000, WHITE, 10, 3);
+    assert_eq!(meteor.head_at(0, 20), 0);
+    assert_eq!(meteor.head_at(1000, 20), 0);
+    assert_eq!(meteor.head_at(1099, 20), 0);
+    assert_eq!(meteor.head_at(1100, 20), 1);
+    assert_eq!(meteor.head_at(2500, 20), 15);
+}
+
+#[test]
+fn test_restarts_after_tail_left_strip() {
+    let meteor = Meteor::new(0, WHITE, 10, 3);
+    // 20 Pixel + 3 Pixel Schweif = 23 Schritte pro Durchlauf
+    assert_eq!(meteor.head_at(2200, 20), 22);
+    assert_eq!(meteor.head_at(2300, 20), 0);
+}
+
+#[test]
+fn test_tail_fades_linearly() {
+    let meteor = Meteor::new(0, WHITE, 10, 3);
+    assert_eq!(meteor.tail_brightness(0), 255);
+    assert_eq!(meteor.tail_brightness(1), 191);
+    assert_eq!(meteor.tail_brightness(2), 127);
+    assert_eq!(meteor.tail_brightness(3), 63);
+    assert_eq!(meteor.tail_brightness(4), 0);
+
+    // Ohne Schweif nur der Kopf
+    let head_only = Meteor::new(0, WHITE, 10, 0);
+    assert_eq!(head_only.tail_brightness(0), 255);
+    assert_eq!(head_only.tail_brightness(1), 0);
+}
+
+#[test]
+fn test_fill() {
+    let color = RGB8::new(200, 100, 0);
+    let meteor = Meteor::new(0, color, 10, 2);
+    let mut pixels = [WHITE; 6];
+    // Kopf auf Pixel 3
+    meteor.fill(300, &mut pixels);
+    assert_eq!(
+        pixels,
+        [
+            RGB8::default(),
+            RGB8::new(67, 33, 0),
+            RGB8::new(133, 67, 0),
+            color,
+            RGB8::default(),
+            RGB8::default(),
+        ]
+    );
+
+    // Kopf hinter dem Strip-Ende: nur noch der Schweif
+    meteor.fill(700, &mut pixels);
+    assert_eq!(pixels[5], RGB8::new(67, 33, 0));
+    assert_eq!(pixels[..5], [RGB8::default(); 5]);
+
+    meteor.fill(0, &mut []);
+}
+
+// ============================================================================
+// Kommandos
+// ============================================================================
+
+#[test]
+fn test_text_command_defaults() {
+    assert!(matches!(
+        parse_text_command("meteor"),
+        Ok(LedCommand::Meteor {
+            color: None,
+            speed: DEFAULT_METEOR_SPEED,
+            tail: DEFAULT_METEOR_TAIL,
+        })
+    ));
+}
+
+#[test]
+fn test_text_command_with_arguments() {
+    match parse_text_command("METEOR 60 12 #FF8000") {
+        Ok(LedCommand::Meteor { color, speed, tail }) => {
+            assert_eq!(color, Some(RGB8::new(255, 128, 0)));
+            assert_eq!((speed, tail), (60, 12));
+        }
+        _ => panic!("Expected Meteor"),
+    }
+    assert!(matches!(
+        parse_text_command("meteor 5 0"),
+        Ok(LedCommand::Meteor {
+            color: None,
+            speed: 5,
+            tail: 0,
+        })
+    ));
+    for invalid in ["meteor 0", "meteor 300", "meteor 10 x", "meteor 10 4 lila"] {
+        assert_eq!(
+            parse_text_command(invalid).err(),
+            Some(CommandParseError::InvalidArgument),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_ws_message() {
+    let json = r##"{"type":"meteor","speed":40,"tail":12,"colors":"#FF8000"}"##;
+    match parse_ws_message::<()>(json.as_bytes(), 10) {
+        WsRequest::Command(LedCommand::Meteor { color, speed, tail }) => {
+            assert_eq!(color, Some(RGB8::new(255, 128, 0)));
+            assert_eq!((speed, tail), (40, 12));
+        }
+        _ => panic!("Expected Meteor"),
+    }
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"meteor"}"#, 10),
+        WsRequest::Command(LedCommand::Meteor {
+            color: None,
+            speed: DEFAULT_METEOR_SPEED,
+            tail: DEFAULT_METEOR_TAIL,
+        })
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"meteor","speed":0}"#, 10),
+        WsRequest::Error("Invalid speed")
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"meteor","colors":"lila"}"#, 10),
+        WsRequest::Error("Invalid color")
+    ));
+}
@@ -0,0 +1,58 @@
+//! Integration Tests für den Bewegungsmelder (Einschalten, Haltezeit)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::motion::MOTION_EXTEND_INTERVAL_MS;
+use esp_core::{LedCommand, MotionAction, MotionTrigger};
+
+const HOLD_MS: u64 = 5 * 60_000;
+
+#[test]
+fn test_first_motion_switches_on() {
+    let mut trigger = MotionTrigger::new(3, 5);
+    assert!(!trigger.is_active(0));
+    assert_eq!(trigger.motion(1000), Some(MotionAction::SwitchOn));
+    assert!(trigger.is_active(1000 + HOLD_MS - 1));
+    assert!(!trigger.is_active(1000 + HOLD_MS));
+}
+
+#[test]
+fn test_switch_on_applies_preset_then_sleep_timer() {
+    let trigger = MotionTrigger::new(3, 5);
+    let commands: Vec<_> = trigger.commands(MotionAction::SwitchOn).collect();
+    assert_eq!(commands.len(), 2);
+    assert!(matches!(commands[0], LedCommand::ApplyPreset { id: 3 }));
+    assert!(matches!(commands[1], LedCommand::SleepTimer { minutes: 5 }));
+
+    let commands: Vec<_> = trigger.commands(MotionAction::Extend).collect();
+    assert_eq!(commands.len(), 1);
+    assert!(matches!(commands[0], LedCommand::SleepTimer { minutes: 5 }));
+}
+
+#[test]
+fn test_repeated_motion_is_rate_limited() {
+    let mut trigger = MotionTrigger::new(0, 5);
+    trigger.motion(0);
+    assert_eq!(trigger.motion(MOTION_EXTEND_INTERVAL_MS - 1), None);
+    assert_eq!(
+        trigger.motion(MOTION_EXTEND_INTERVAL_MS),
+        Some(MotionAction::Extend)
+    );
+    // Haltezeit läuft ab der Verlängerung
+    assert!(trigger.is_active(MOTION_EXTEND_INTERVAL_MS + HOLD_MS - 1));
+}
+
+#[test]
+fn test_motion_after_hold_switches_on_again() {
+    let mut trigger = MotionTrigger::new(0, 5);
+    trigger.motion(0);
+    assert_eq!(trigger.motion(HOLD_MS), Some(MotionAction::SwitchOn));
+}
+
+#[test]
+fn test_zero_hold_time_is_raised() {
+    let mut trigger = MotionTrigger::new(0, 0);
+    trigger.motion(0);
+    assert!(trigger.is_active(59_999));
+    assert!(!trigger.is_active(60_000));
+}
@@ -0,0 +1,271 @@
+//! Integration Tests für den MQTT-SN-Codec
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::mqtt_sn::{MsgIdCounter, ReturnCode, msg_type};
+use esp_core::{MqttSnError, MqttSnPacket, decode_mqtt_sn};
+
+fn encode(packet: &MqttSnPacket) -> Vec<u8> {
+    let mut out = [0u8; 600];
+    let len = packet.encode(&mut out).unwrap();
+    out[..len].to_vec()
+}
+
+#[test]
+fn test_encode_connect() {
+    let bytes = encode(&MqttSnPacket::Connect {
+        clean_session: true,
+        duration_secs: 60,
+        client_id: "led",
+    });
+    assert_eq!(
+        bytes,
+        [9, msg_type::CONNECT, 0x04, 0x01, 0, 60, b'l', b'e', b'd']
+    );
+}
+
+#[test]
+fn test_encode_register_and_subscribe() {
+    let bytes = encode(&MqttSnPacket::Register {
+        topic_id: 0,
+        msg_id: 0x0102,
+        topic_name: "a/b",
+    });
+    assert_eq!(bytes, [9, msg_type::REGISTER, 0, 0, 1, 2, b'a', b'/', b'b']);
+
+    let bytes = encode(&MqttSnPacket::Subscribe {
+        qos: 1,
+        msg_id: 7,
+        topic_name: "cmd",
+    });
+    assert_eq!(
+        bytes,
+        [8, msg_type::SUBSCRIBE, 0x20, 0, 7, b'c', b'm', b'd']
+    );
+}
+
+#[test]
+fn test_encode_publish_flags() {
+    let bytes = encode(&MqttSnPacket::Publish {
+        qos: 0,
+        retain: true,
+        topic_id: 0x0A0B,
+        msg_id: 0,
+        payload: b"rot",
+    });
+    let mut expected = vec![10, msg_type::PUBLISH, 0x10, 0x0A, 0x0B, 0, 0];
+    expected.extend_from_slice(b"rot");
+    assert_eq!(bytes, expected);
+}
+
+#[test]
+fn test_decode_gateway_responses() {
+    assert_eq!(
+        decode_mqtt_sn(&[3, msg_type::CONNACK, 0]),
+        Ok(MqttSnPacket::Connack {
+            return_code: ReturnCode::Accepted
+        })
+    );
+    assert_eq!(
+        decode_mqtt_sn(&[7, msg_type::REGACK, 0, 5, 0, 1, 0]),
+        Ok(MqttSnPacket::Regack {
+            topic_id: 5,
+            msg_id: 1,
+            return_code: ReturnCode::Accepted
+        })
+    );
+    assert_eq!(
+        decode_mqtt_sn(&[8, msg_type::SUBACK, 0x00, 0, 9, 0, 2, 0x02]),
+        Ok(MqttSnPacket::Suback {
+            qos: 0,
+            topic_id: 9,
+            msg_id: 2,
+            return_code: ReturnCode::InvalidTopicId
+        })
+    );
+    assert_eq!(
+        decode_mqtt_sn(&[2, msg_type::PINGRESP]),
+        Ok(MqttSnPacket::PingResp)
+    );
+    // DISCONNECT mit optionaler Dauer
+    assert_eq!(
+        decode_mqtt_sn(&[4, msg_type::DISCONNECT, 0, 30]),
+        Ok(MqttSnPacket::Disconnect)
+    );
+    // Unbekannter Antwortcode
+    assert_eq!(
+        decode_mqtt_sn(&[3, msg_type::CONNACK, 0x7F]),
+        Ok(MqttSnPacket::Connack {
+            return_code: ReturnCode::NotSupported
+        })
+    );
+}
+
+#[test]
+fn test_decode_publish_from_gateway() {
+    let mut packet = vec![10, msg_type::PUBLISH, 0x20, 0, 9, 0x12, 0x34];
+    packet.extend_from_slice(b"aux\xEE");
+    // Letztes Byte liegt hinter der angegebenen Länge
+    assert_eq!(
+        decode_mqtt_sn(&packet),
+        Ok(MqttSnPacket::Publish {
+            qos: 1,
+            retain: false,
+            topic_id: 9,
+            msg_id: 0x1234,
+            payload: b"aux",
+        })
+    );
+}
+
+#[test]
+fn test_roundtrip_all_packets() {
+    let payload = [0x55u8; 300];
+    let packets = [
+        MqttSnPacket::Connect {
+            clean_session: false,
+            duration_secs: 900,
+            client_id: "esp32c6-led",
+        },
+        MqttSnPacket::Connack {
+            return_code: ReturnCode::Congestion,
+        },
+        MqttSnPacket::Register {
+            topic_id: 3,
+            msg_id: 4,
+            topic_name: "devices/esp32c6/led-color",
+        },
+        MqttSnPacket::Regack {
+            topic_id: 3,
+            msg_id: 4,
+            return_code: ReturnCode::Accepted,
+        },
+        MqttSnPacket::Publish {
+            qos: 1,
+            retain: false,
+            topic_id: 3,
+            msg_id: 5,
+            payload: b"{\"on\":true}",
+        },
+        // Langes Längenfeld ab 256 Bytes
+        MqttSnPacket::Publish {
+            qos: 0,
+            retain: false,
+            topic_id: 3,
+            msg_id: 0,
+            payload: &payload,
+        },
+        MqttSnPacket::Puback {
+            topic_id: 3,
+            msg_id: 5,
+            return_code: ReturnCode::InvalidTopicId,
+        },
+        MqttSnPacket::Subscribe {
+            qos: 0,
+            msg_id: 6,
+            topic_name: "devices/+/cmnd",
+        },
+        MqttSnPacket::Suback {
+            qos: 0,
+            topic_id: 0,
+            msg_id: 6,
+            return_code: ReturnCode::Accepted,
+        },
+        MqttSnPacket::PingReq,
+        MqttSnPacket::PingResp,
+        MqttSnPacket::Disconnect,
+    ];
+    for packet in packets {
+        let bytes = encode(&packet);
+        assert_eq!(decode_mqtt_sn(&bytes), Ok(packet), "{:?}", packet);
+    }
+}
+
+#[test]
+fn test_long_length_field() {
+    let payload = [0xAAu8; 300];
+    let bytes = encode(&MqttSnPacket::Publish {
+        qos: 0,
+        retain: false,
+        topic_id: 1,
+        msg_id: 0,
+        payload: &payload,
+    });
+    assert_eq!(bytes.len(), 3 + 1 + 5 + 300);
+    assert_eq!(bytes[0], 0x01);
+    assert_eq!(u16::from_be_bytes([bytes[1], bytes[2]]), bytes.len() as u16);
+    assert_eq!(bytes[3], msg_type::PUBLISH);
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(decode_mqtt_sn(&[]), Err(MqttSnError::Truncated));
+    assert_eq!(decode_mqtt_sn(&[1]), Err(MqttSnError::Truncated));
+    assert_eq!(
+        decode_mqtt_sn(&[0, msg_type::PINGREQ]),
+        Err(MqttSnError::InvalidLength)
+    );
+    // Langes Längenfeld ohne die zwei Längen-Bytes
+    assert_eq!(
+        decode_mqtt_sn(&[1, msg_type::PINGREQ]),
+        Err(MqttSnError::Truncated)
+    );
+    // Länge größer als das Paket
+    assert_eq!(
+        decode_mqtt_sn(&[5, msg_type::CONNACK, 0]),
+        Err(MqttSnError::Truncated)
+    );
+    // REGACK ohne Antwortcode
+    assert_eq!(
+        decode_mqtt_sn(&[6, msg_type::REGACK, 0, 5, 0, 1]),
+        Err(MqttSnError::Truncated)
+    );
+    // ADVERTISE wird nicht unterstützt
+    assert_eq!(
+        decode_mqtt_sn(&[5, 0x00, 1, 0, 60]),
+        Err(MqttSnError::Unsupported(0x00))
+    );
+    assert_eq!(
+        decode_mqtt_sn(&[4, msg_type::REGISTER, 0, 1]),
+        Err(MqttSnError::Truncated)
+    );
+    assert_eq!(
+        decode_mqtt_sn(&[7, msg_type::REGISTER, 0, 1, 0, 1, 0xFF]),
+        Err(MqttSnError::InvalidString)
+    );
+
+    let mut small = [0u8; 4];
+    assert_eq!(
+        MqttSnPacket::Connect {
+            clean_session: true,
+            duration_secs: 60,
+            client_id: "led",
+        }
+        .encode(&mut small),
+        Err(MqttSnError::BufferTooSmall)
+    );
+    let mut no_room_for_long_length = [0u8; 260];
+    assert_eq!(
+        MqttSnPacket::Publish {
+            qos: 0,
+            retain: false,
+            topic_id: 1,
+            msg_id: 0,
+            payload: &[0; 252],
+        }
+        .encode(&mut no_room_for_long_length),
+        Err(MqttSnError::BufferTooSmall)
+    );
+}
+
+#[test]
+fn test_msg_id_counter_skips_zero() {
+    let mut ids = MsgIdCounter::default();
+    assert_eq!(ids.next_id(), 1);
+    assert_eq!(ids.next_id(), 2);
+    for _ in 0..(u16::MAX - 3) {
+        ids.next_id();
+    }
+    assert_eq!(ids.next_id(), u16::MAX);
+    assert_eq!(ids.next_id(), 1);
+}
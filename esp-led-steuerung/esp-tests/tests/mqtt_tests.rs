@@ -0,0 +1,270 @@
+//! Integration Tests für die MQTT-Payloads und Topics (exakte Bytes am Broker)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::mqtt::{
+    COMMAND_ACK_ECHO_LEN, COMMAND_ID_MAX_LEN, FACTORY_RESET_PAYLOAD, RESTART_PAYLOAD,
+    color_payload, mode_payload, split_command_id, system_action_confirmed, write_birth_payload,
+    write_command_ack, write_health_payload, write_heap_payload, write_input_topic,
+    write_state_payload, write_tasmota_result, write_tasmota_topic,
+};
+use esp_core::tasmota::{TASMOTA_COMMAND_PREFIX, TASMOTA_STAT_PREFIX};
+use esp_core::{
+    BirthInfo, ColorLabel, CommandAck, CommandParseError, HealthInfo, HeapStats, Language,
+    LedColorMessage, parse_text_command,
+};
+use rgb::RGB8;
+
+/// Schreibt mit `write` in einen String (wie heapless::String in der Firmware)
+fn render(write: impl FnOnce(&mut String) -> core::fmt::Result) -> String {
+    let mut out = String::new();
+    write(&mut out).unwrap();
+    out
+}
+
+fn message(color: RGB8, is_auto_mode: bool) -> LedColorMessage {
+    LedColorMessage::from_color(color, is_auto_mode).stamped(7, 1200)
+}
+
+#[test]
+fn test_color_and_mode_payloads() {
+    let red = message(RGB8::new(10, 0, 0), true);
+    assert_eq!(color_payload(Language::German, &red).as_str(), "Rot");
+    assert_eq!(color_payload(Language::English, &red).as_str(), "Red");
+    assert_eq!(mode_payload(Language::German, &red), "Auto");
+
+    let off = message(RGB8::default(), false);
+    assert_eq!(color_payload(Language::German, &off).as_str(), "Aus");
+    assert_eq!(mode_payload(Language::German, &off), "Manuell");
+    assert_eq!(mode_payload(Language::English, &off), "Manual");
+}
+
+#[test]
+fn test_color_payload_dynamic_names() {
+    // Farbe ohne Namen → RGB-Werte statt "Benutzerdefiniert"
+    let orange = message(RGB8::new(255, 128, 0), true);
+    assert_eq!(
+        color_payload(Language::German, &orange).as_str(),
+        "RGB(255,128,0)"
+    );
+
+    // Eigener Name (Preset) hat Vorrang, Protokoll-Name bleibt
+    let name = ColorLabel::new("Abendrot");
+    let preset = message(RGB8::new(10, 0, 0), false).with_name(name);
+    assert_eq!(
+        color_payload(Language::English, &preset).as_str(),
+        "Abendrot"
+    );
+    assert!(render(|out| write_state_payload(out, &preset)).starts_with(r#"{"color":"Rot","#));
+}
+
+#[test]
+fn test_state_payload() {
+    let msg = message(RGB8::new(0, 10, 0), false);
+    assert_eq!(
+        render(|out| write_state_payload(out, &msg)),
+        r#"{"color":"Grün","rgb":{"r":0,"g":10,"b":0},"mode":"manual","seq":7,"timestamp_ms":1200}"#
+    );
+
+    // Mischfarbe im Auto-Modus, Protokoll-Name unabhängig von der Sprache
+    let msg = message(RGB8::new(255, 128, 0), true);
+    assert_eq!(
+        render(|out| write_state_payload(out, &msg)),
+        r#"{"color":"Benutzerdefiniert","rgb":{"r":255,"g":128,"b":0},"mode":"auto","seq":7,"timestamp_ms":1200}"#
+    );
+}
+
+#[test]
+fn test_birth_payload() {
+    let info = BirthInfo {
+        client_id: "esp32c6-led-publisher",
+        version: "1.0.0",
+        git_hash: "abc1234",
+        build_timestamp: 1_760_000_000,
+    };
+    assert_eq!(
+        render(|out| write_birth_payload(out, &info)),
+        concat!(
+            r#"{"status":"online","client_id":"esp32c6-led-publisher","#,
+            r#""version":"1.0.0","git_hash":"abc1234","build_timestamp":1760000000}"#,
+        )
+    );
+}
+
+#[test]
+fn test_heap_payload() {
+    let mut stats = HeapStats::new();
+    stats.record(52_008, 50_392);
+    stats.record(41_230, 61_170);
+    assert_eq!(
+        render(|out| write_heap_payload(out, &stats)),
+        r#"{"used":41230,"free":61170,"high_water":52008,"size":102400,"usage_percent":40}"#
+    );
+}
+
+#[test]
+fn test_health_payload() {
+    let health = HealthInfo {
+        rssi: Some(-61),
+        uptime: 3600,
+        heap_free: 61_170,
+        temperature: Some(41.5),
+        broker: "mqtt.home",
+    };
+    assert_eq!(
+        render(|out| write_health_payload(out, &health)),
+        r#"{"rssi":-61,"uptime":3600,"heap_free":61170,"temperature":41.5,"broker":"mqtt.home"}"#
+    );
+}
+
+#[test]
+fn test_health_payload_missing_values_are_null() {
+    let health = HealthInfo {
+        rssi: None,
+        uptime: 0,
+        heap_free: 0,
+        temperature: Some(f32::NAN),
+        broker: "192.168.1.20",
+    };
+    assert_eq!(
+        render(|out| write_health_payload(out, &health)),
+        r#"{"rssi":null,"uptime":0,"heap_free":0,"temperature":null,"broker":"192.168.1.20"}"#
+    );
+}
+
+#[test]
+fn test_health_payload_escapes_broker() {
+    let health = HealthInfo {
+        rssi: None,
+        uptime: 1,
+        heap_free: 2,
+        temperature: None,
+        broker: "a\"b\\c",
+    };
+    assert!(render(|out| write_health_payload(out, &health)).ends_with(r#""broker":"a\"b\\c"}"#));
+}
+
+#[test]
+fn test_input_topic() {
+    assert_eq!(
+        render(|out| write_input_topic(out, "devices/esp32c6/input", "door")),
+        "devices/esp32c6/input/door"
+    );
+}
+
+#[test]
+fn test_tasmota_topics() {
+    assert_eq!(
+        render(|out| write_tasmota_topic(out, TASMOTA_COMMAND_PREFIX, "led", "+")),
+        "cmnd/led/+"
+    );
+    assert_eq!(
+        render(|out| write_tasmota_topic(out, TASMOTA_STAT_PREFIX, "led", "RESULT")),
+        "stat/led/RESULT"
+    );
+}
+
+#[test]
+fn test_tasmota_result() {
+    assert_eq!(
+        render(|out| write_tasmota_result(out, RGB8::new(255, 16, 0))),
+        r#"{"POWER":"ON","Color":"FF1000"}"#
+    );
+    assert_eq!(
+        render(|out| write_tasmota_result(out, RGB8::default())),
+        r#"{"POWER":"OFF","Color":"000000"}"#
+    );
+}
+
+#[test]
+fn test_command_ack_ok() {
+    assert_eq!(
+        render(|out| write_command_ack(out, None, "rot 500", CommandAck::Applied)),
+        r#"{"command":"rot 500","status":"ok"}"#
+    );
+}
+
+#[test]
+fn test_command_ack_errors() {
+    let error = parse_text_command("lila").unwrap_err();
+    assert_eq!(
+        render(|out| write_command_ack(out, None, "lila", CommandAck::Invalid(error))),
+        r#"{"command":"lila","status":"error","error":"unknown_command"}"#
+    );
+    assert_eq!(
+        render(|out| write_command_ack(
+            out,
+            None,
+            "sleep x",
+            CommandAck::Invalid(CommandParseError::InvalidArgument)
+        )),
+        r#"{"command":"sleep x","status":"error","error":"invalid_argument"}"#
+    );
+    assert_eq!(
+        render(|out| write_command_ack(out, None, "auto", CommandAck::Busy)),
+        r#"{"command":"auto","status":"error","error":"busy"}"#
+    );
+}
+
+#[test]
+fn test_command_ack_echoes_request_id() {
+    assert_eq!(
+        render(|out| write_command_ack(out, Some("42"), "rot", CommandAck::Applied)),
+        r#"{"id":"42","command":"rot","status":"ok"}"#
+    );
+    assert_eq!(
+        render(|out| write_command_ack(out, Some("node-red.7"), "auto", CommandAck::Busy)),
+        r#"{"id":"node-red.7","command":"auto","status":"error","error":"busy"}"#
+    );
+}
+
+#[test]
+fn test_split_command_id() {
+    assert_eq!(split_command_id("@42 rot 500"), (Some("42"), "rot 500"));
+    assert_eq!(
+        split_command_id("  @a_b-c.1   auto"),
+        (Some("a_b-c.1"), "auto")
+    );
+    assert_eq!(split_command_id("@42"), (Some("42"), ""));
+    assert_eq!(split_command_id("rot"), (None, "rot"));
+
+    // Ungültige IDs: Text bleibt unverändert (und wird als Kommando abgelehnt)
+    assert_eq!(split_command_id("@ rot"), (None, "@ rot"));
+    assert_eq!(split_command_id("@a\"b rot"), (None, "@a\"b rot"));
+    let long = format!("@{} rot", "x".repeat(COMMAND_ID_MAX_LEN + 1));
+    assert_eq!(split_command_id(&long), (None, long.as_str()));
+    let max = format!("@{} rot", "x".repeat(COMMAND_ID_MAX_LEN));
+    assert_eq!(split_command_id(&max).1, "rot");
+}
+
+#[test]
+fn test_command_ack_escapes_and_truncates_command() {
+    assert_eq!(
+        render(|out| write_command_ack(out, None, "say \"hi\"\n", CommandAck::Applied)),
+        r#"{"command":"say \"hi\"","status":"ok"}"#
+    );
+
+    // Kürzen nur an Zeichengrenzen ("ü" = 2 Bytes)
+    let long = "ü".repeat(COMMAND_ACK_ECHO_LEN);
+    let ack = render(|out| write_command_ack(out, None, &long, CommandAck::Applied));
+    assert!(ack.contains(&"ü".repeat(COMMAND_ACK_ECHO_LEN / 2)));
+    assert!(!ack.contains(&"ü".repeat(COMMAND_ACK_ECHO_LEN / 2 + 1)));
+}
+
+#[test]
+fn test_system_action_confirmed() {
+    assert!(system_action_confirmed(b"restart", RESTART_PAYLOAD));
+    assert!(system_action_confirmed(b" RESTART\n", RESTART_PAYLOAD));
+    // Leerer Payload löscht die retained Nachricht und löst nichts aus
+    assert!(!system_action_confirmed(b"", RESTART_PAYLOAD));
+    assert!(!system_action_confirmed(b"1", RESTART_PAYLOAD));
+    assert!(!system_action_confirmed(b"restart now", RESTART_PAYLOAD));
+
+    assert!(system_action_confirmed(
+        b"factory-reset",
+        FACTORY_RESET_PAYLOAD
+    ));
+    // Neustart-Wort löscht keine Einstellungen
+    assert!(!system_action_confirmed(b"restart", FACTORY_RESET_PAYLOAD));
+    assert!(!system_action_confirmed(b"", FACTORY_RESET_PAYLOAD));
+}
@@ -0,0 +1,85 @@
+//! Integration Tests für die Netzwerk-Zähler
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{NetCounter, NetStats};
+
+fn prometheus(stats: &NetStats) -> String {
+    let mut out = String::new();
+    stats.write_prometheus(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn test_counters_are_independent() {
+    let mut stats = NetStats::new();
+    stats.record(NetCounter::MqttPublishes);
+    stats.record(NetCounter::MqttPublishes);
+    stats.record(NetCounter::UdpPacketsIn);
+    for counter in NetCounter::ALL {
+        let expected = match counter {
+            NetCounter::MqttPublishes => 2,
+            NetCounter::UdpPacketsIn => 1,
+            _ => 0,
+        };
+        assert_eq!(stats.count(counter), expected, "{}", counter.name());
+    }
+}
+
+#[test]
+fn test_add_saturates() {
+    let mut stats = NetStats::new();
+    stats.add(NetCounter::HttpRequests, 3);
+    assert_eq!(stats.count(NetCounter::HttpRequests), 3);
+    stats.add(NetCounter::HttpRequests, u32::MAX);
+    stats.record(NetCounter::HttpRequests);
+    assert_eq!(stats.count(NetCounter::HttpRequests), u32::MAX);
+}
+
+#[test]
+fn test_all_in_index_order() {
+    for (index, counter) in NetCounter::ALL.into_iter().enumerate() {
+        assert_eq!(counter as usize, index);
+    }
+    assert_eq!(NetStats::default(), NetStats::new());
+}
+
+#[test]
+fn test_prometheus_values() {
+    let mut stats = NetStats::new();
+    stats.add(NetCounter::MqttAcks, 6);
+    stats.add(NetCounter::WsMessagesIn, 4);
+    stats.add(NetCounter::WsMessagesOut, 9);
+    stats.record(NetCounter::MqttReconnects);
+    let out = prometheus(&stats);
+    assert!(out.contains("net_mqtt_acks_total 6\n"));
+    assert!(out.contains("net_mqtt_reconnects_total 1\n"));
+    assert!(out.contains("net_ws_messages_total{direction=\"in\"} 4\n"));
+    assert!(out.contains("net_ws_messages_total{direction=\"out\"} 9\n"));
+    assert!(out.contains("net_udp_packets_total{direction=\"out\"} 0\n"));
+}
+
+#[test]
+fn test_prometheus_type_once_per_metric() {
+    let out = prometheus(&NetStats::new());
+    for name in [
+        "net_mqtt_publishes_total",
+        "net_mqtt_received_total",
+        "net_mqtt_acks_total",
+        "net_mqtt_reconnects_total",
+        "net_ws_messages_total",
+        "net_http_requests_total",
+        "net_udp_packets_total",
+    ] {
+        assert_eq!(
+            out.matches(&format!("# TYPE {name} counter\n")).count(),
+            1,
+            "{name}"
+        );
+        assert_eq!(out.matches(&format!("# HELP {name} ")).count(), 1, "{name}");
+    }
+    // Ein Wert pro Zähler, jede Zeile endet mit Zeilenumbruch
+    let samples = out.lines().filter(|line| !line.starts_with('#')).count();
+    assert_eq!(samples, NetCounter::COUNT);
+    assert!(out.ends_with('\n'));
+}
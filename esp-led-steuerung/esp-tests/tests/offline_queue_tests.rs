@@ -0,0 +1,114 @@
+//! Integration Tests für den MQTT-Offline-Puffer
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{LedColorMessage, OfflineChange, OfflineQueue};
+use rgb::RGB8;
+
+/// Manuelle Farbänderung
+fn color(sequence: u32) -> OfflineChange {
+    OfflineChange::Color(
+        LedColorMessage::from_color(RGB8::new(10, 0, 0), false)
+            .stamped(sequence, u64::from(sequence) * 1000),
+    )
+}
+
+/// Farbe der Auto-Rotation
+fn auto_color(sequence: u32) -> OfflineChange {
+    OfflineChange::Color(
+        LedColorMessage::from_color(RGB8::new(0, 0, 10), true)
+            .stamped(sequence, u64::from(sequence) * 1000),
+    )
+}
+
+fn input(input: u8, active: bool) -> OfflineChange {
+    OfflineChange::Input { input, active }
+}
+
+/// Sequenznummer einer Farbänderung bzw. 1000 + Eingang
+fn key(change: &OfflineChange) -> u32 {
+    match change {
+        OfflineChange::Color(msg) => msg.sequence,
+        OfflineChange::Input { input, .. } => 1000 + u32::from(*input),
+    }
+}
+
+fn drain<const N: usize>(queue: &mut OfflineQueue<N>) -> Vec<u32> {
+    core::iter::from_fn(|| queue.pop())
+        .map(|change| key(&change))
+        .collect()
+}
+
+#[test]
+fn test_fifo_order() {
+    let mut queue: OfflineQueue<4> = OfflineQueue::new();
+    assert!(queue.is_empty());
+    queue.push(color(1));
+    queue.push(input(2, true));
+    queue.push(color(2));
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.front().map(key), Some(1));
+    assert_eq!(drain(&mut queue), vec![1, 1002, 2]);
+    assert!(queue.pop().is_none());
+    assert_eq!(queue.dropped(), 0);
+}
+
+#[test]
+fn test_full_queue_drops_superseded_entry() {
+    let mut queue: OfflineQueue<3> = OfflineQueue::new();
+    queue.push(input(0, true));
+    queue.push(color(1));
+    queue.push(color(2));
+    // Eingang 0 hat keinen neueren Eintrag, Farbe 1 wird von Farbe 2 ersetzt
+    queue.push(input(1, true));
+    assert_eq!(queue.dropped(), 1);
+    assert_eq!(drain(&mut queue), vec![1000, 2, 1001]);
+}
+
+#[test]
+fn test_full_queue_keeps_latest_per_topic() {
+    let mut queue: OfflineQueue<3> = OfflineQueue::new();
+    for sequence in 1..=10 {
+        queue.push(color(sequence));
+    }
+    queue.push(input(0, true));
+    assert_eq!(queue.take_dropped(), 8);
+    assert_eq!(queue.dropped(), 0);
+    assert_eq!(drain(&mut queue), vec![9, 10, 1000]);
+}
+
+#[test]
+fn test_full_queue_distinct_topics_drops_oldest() {
+    let mut queue: OfflineQueue<2> = OfflineQueue::new();
+    queue.push(input(0, true));
+    queue.push(input(1, true));
+    queue.push(input(2, true));
+    assert_eq!(queue.dropped(), 1);
+    assert_eq!(drain(&mut queue), vec![1001, 1002]);
+}
+
+#[test]
+fn test_same_topic() {
+    assert!(color(1).same_topic(&color(2)));
+    assert!(input(3, true).same_topic(&input(3, false)));
+    assert!(!input(3, true).same_topic(&input(4, true)));
+    assert!(!color(1).same_topic(&input(0, true)));
+}
+
+#[test]
+fn test_auto_colors_coalesced() {
+    let mut queue: OfflineQueue<8> = OfflineQueue::new();
+    for sequence in 1..=30 {
+        queue.push(auto_color(sequence));
+    }
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue.dropped(), 0);
+
+    // Manuelle Farbe und Eingänge trennen die Auto-Phasen
+    queue.push(color(31));
+    queue.push(auto_color(32));
+    queue.push(auto_color(33));
+    queue.push(input(0, true));
+    queue.push(auto_color(34));
+    assert_eq!(drain(&mut queue), vec![30, 31, 33, 1000, 34]);
+}
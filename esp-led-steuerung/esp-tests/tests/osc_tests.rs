@@ -0,0 +1,154 @@
+//! Integration Tests für das OSC-Protokoll
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{ColorId, LedCommand, OscArg, OscError, decode_osc_message, osc_led_command};
+use rgb::RGB8;
+
+/// Hängt einen null-terminierten, auf 4 Bytes aufgefüllten String an
+fn push_string(packet: &mut Vec<u8>, text: &str) {
+    packet.extend_from_slice(text.as_bytes());
+    packet.push(0);
+    while !packet.len().is_multiple_of(4) {
+        packet.push(0);
+    }
+}
+
+/// Baut eine OSC-Nachricht aus Adresse, Typ-Tags und Argument-Daten
+fn osc_packet(address: &str, tags: &str, data: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::new();
+    push_string(&mut packet, address);
+    push_string(&mut packet, tags);
+    packet.extend_from_slice(data);
+    packet
+}
+
+fn floats(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+}
+
+fn command_for(packet: &[u8]) -> Option<LedCommand> {
+    osc_led_command(&decode_osc_message(packet).unwrap())
+}
+
+#[test]
+fn test_decode_message_with_mixed_args() {
+    let mut data = 42i32.to_be_bytes().to_vec();
+    data.extend(floats(&[0.5]));
+    push_string(&mut data, "rot");
+    let packet = osc_packet("/led/test", ",ifsT", &data);
+
+    let message = decode_osc_message(&packet).unwrap();
+    assert_eq!(message.address, "/led/test");
+    let args: Vec<_> = message.args().collect();
+    assert_eq!(
+        args,
+        [
+            OscArg::Int(42),
+            OscArg::Float(0.5),
+            OscArg::Str("rot"),
+            OscArg::Bool(true)
+        ]
+    );
+}
+
+#[test]
+fn test_decode_rejects_invalid_packets() {
+    assert_eq!(decode_osc_message(b"/led"), Err(OscError::Truncated));
+    assert_eq!(
+        decode_osc_message(b"#bundle\0"),
+        Err(OscError::InvalidAddress)
+    );
+    // Typ-Tag ohne Daten
+    assert_eq!(
+        decode_osc_message(&osc_packet("/led/brightness", ",f", &[])),
+        Err(OscError::Truncated)
+    );
+    assert_eq!(
+        decode_osc_message(&osc_packet("/led/x", ",b", &[0; 4])),
+        Err(OscError::UnsupportedType(b'b'))
+    );
+}
+
+#[test]
+fn test_color_mapping() {
+    // Float-Fader (TouchOSC)
+    let packet = osc_packet("/led/color", ",fff", &floats(&[1.0, 0.5, 0.0]));
+    match command_for(&packet) {
+        Some(LedCommand::SetColor { target_color, .. }) => {
+            assert_eq!(target_color, RGB8::new(255, 128, 0))
+        }
+        _ => panic!("Expected SetColor"),
+    }
+
+    // Integer-Werte werden begrenzt
+    let data: Vec<u8> = [0i32, 300, -5]
+        .iter()
+        .flat_map(|v| v.to_be_bytes())
+        .collect();
+    let packet = osc_packet("/led/color", ",iii", &data);
+    match command_for(&packet) {
+        Some(LedCommand::SetColor {
+            target_color,
+            color_id,
+        }) => {
+            assert_eq!(target_color, RGB8::new(0, 255, 0));
+            assert_eq!(color_id, ColorId::Green);
+        }
+        _ => panic!("Expected SetColor"),
+    }
+
+    // RGBA-Farbe und Farbname
+    let packet = osc_packet("/led/color", ",r", &[0, 0, 9, 255]);
+    assert!(matches!(
+        command_for(&packet),
+        Some(LedCommand::SetColor {
+            color_id: ColorId::Blue,
+            ..
+        })
+    ));
+    let mut data = Vec::new();
+    push_string(&mut data, "rot");
+    let packet = osc_packet("/led/color", ",s", &data);
+    assert!(matches!(
+        command_for(&packet),
+        Some(LedCommand::SetColor {
+            color_id: ColorId::Red,
+            ..
+        })
+    ));
+
+    // Schwarz schaltet aus, zu wenige Argumente werden ignoriert
+    let packet = osc_packet("/led/color", ",fff", &floats(&[0.0, 0.0, 0.0]));
+    assert!(matches!(command_for(&packet), Some(LedCommand::Off)));
+    let packet = osc_packet("/led/color", ",ff", &floats(&[1.0, 1.0]));
+    assert!(command_for(&packet).is_none());
+}
+
+#[test]
+fn test_brightness_and_buttons() {
+    let packet = osc_packet("/led/brightness", ",f", &floats(&[0.25]));
+    assert!(matches!(
+        command_for(&packet),
+        Some(LedCommand::SetBrightness { brightness: 64 })
+    ));
+
+    // Button gedrückt (1.0) löst aus, losgelassen (0.0) nicht
+    let packet = osc_packet("/led/auto", ",f", &floats(&[1.0]));
+    assert!(matches!(command_for(&packet), Some(LedCommand::EnableAuto)));
+    let packet = osc_packet("/led/auto", ",f", &floats(&[0.0]));
+    assert!(command_for(&packet).is_none());
+    let packet = osc_packet("/led/off", ",", &[]);
+    assert!(matches!(command_for(&packet), Some(LedCommand::Off)));
+
+    let mut data = Vec::new();
+    push_string(&mut data, "sleep 30");
+    let packet = osc_packet("/led/command", ",s", &data);
+    assert!(matches!(
+        command_for(&packet),
+        Some(LedCommand::SleepTimer { minutes: 30 })
+    ));
+
+    let packet = osc_packet("/other", ",f", &floats(&[1.0]));
+    assert!(command_for(&packet).is_none());
+}
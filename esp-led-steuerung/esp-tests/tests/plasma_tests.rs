@@ -0,0 +1,150 @@
+//! Integration Tests für den Plasma-Effekt
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::plasma::{DEFAULT_PLASMA_SPEED, palette_color, sin8};
+use esp_core::ws::{WsRequest, parse_ws_message};
+use esp_core::{
+    ColorSequence, CommandParseError, LedCommand, MatrixLayout, Plasma, parse_text_command,
+};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+const GREEN: RGB8 = RGB8::new(0, 255, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 255);
+
+fn rgb_palette() -> ColorSequence {
+    ColorSequence::new(&[RED, GREEN, BLUE]).unwrap()
+}
+
+// ============================================================================
+// Sinus und Palette
+// ============================================================================
+
+#[test]
+fn test_sin8() {
+    assert_eq!(sin8(0), 128);
+    assert_eq!(sin8(32), 218);
+    assert_eq!(sin8(64), 255);
+    assert_eq!(sin8(96), 218);
+    assert_eq!(sin8(128), 128);
+    assert_eq!(sin8(160), 38);
+    assert_eq!(sin8(192), 1);
+    // Stetig über den Überlauf
+    assert_eq!(sin8(255), 125);
+    for angle in 0..=255u8 {
+        assert_eq!(
+            u16::from(sin8(angle)) + u16::from(sin8(angle.wrapping_add(128))),
+            256,
+            "{}",
+            angle
+        );
+    }
+}
+
+#[test]
+fn test_palette_color_is_cyclic() {
+    let palette = [RED, GREEN, BLUE];
+    assert_eq!(palette_color(&palette, 0), RED);
+    // Ein Drittel des Kreises: (fast) Grün
+    assert_eq!(palette_color(&palette, 86), RGB8::new(0, 254, 1));
+    // Zwischen Grün und Blau
+    assert_eq!(palette_color(&palette, 128), RGB8::new(0, 128, 127));
+    // Kurz vor dem Überlauf zurück Richtung Rot
+    let last = palette_color(&palette, 255);
+    assert!(last.r > 240 && last.g == 0 && last.b < 15, "{:?}", last);
+}
+
+#[test]
+fn test_palette_color_single_or_empty() {
+    assert_eq!(palette_color(&[BLUE], 200), BLUE);
+    assert_eq!(palette_color(&[], 200), RGB8::default());
+}
+
+// ============================================================================
+// Plasma
+// ============================================================================
+
+#[test]
+fn test_value_at() {
+    let plasma = Plasma::new(1000, DEFAULT_PLASMA_SPEED, rgb_palette());
+    // Alle Wellen auf der Nulllinie
+    assert_eq!(plasma.value_at(0, 0, 1000), 128);
+    // Waagerecht 64 (Maximum), diagonal 32
+    assert_eq!(plasma.value_at(2, 0, 1000), 200);
+    // Vor dem Start wie beim Start
+    assert_eq!(plasma.value_at(2, 0, 0), plasma.value_at(2, 0, 1000));
+}
+
+#[test]
+fn test_value_moves_with_speed() {
+    let plasma = Plasma::new(0, DEFAULT_PLASMA_SPEED, rgb_palette());
+    assert_ne!(plasma.value_at(1, 1, 0), plasma.value_at(1, 1, 500));
+
+    // Tempo 0: stehendes Bild
+    let frozen = Plasma::new(0, 0, rgb_palette());
+    assert_eq!(frozen.value_at(3, 2, 0), frozen.value_at(3, 2, 60_000));
+}
+
+#[test]
+fn test_render() {
+    let plasma = Plasma::new(0, DEFAULT_PLASMA_SPEED, rgb_palette());
+    let layout = MatrixLayout::new(4, 2, true);
+    let sentinel = RGB8::new(1, 2, 3);
+    let mut pixels = [sentinel; 10];
+    plasma.render(250, layout, &mut pixels);
+
+    for y in 0..2 {
+        for x in 0..4 {
+            let index = layout.index(x, y).unwrap();
+            let expected = palette_color(&[RED, GREEN, BLUE], plasma.value_at(x, y, 250));
+            assert_eq!(pixels[index], expected, "({}, {})", x, y);
+        }
+    }
+    // Pixel außerhalb der Matrix bleiben unverändert
+    assert_eq!(pixels[8..], [sentinel; 2]);
+
+    // Zu kurzer Puffer
+    let mut short = [sentinel; 3];
+    plasma.render(250, layout, &mut short);
+}
+
+// ============================================================================
+// Kommandos
+// ============================================================================
+
+#[test]
+fn test_text_command() {
+    assert!(matches!(
+        parse_text_command("plasma"),
+        Ok(LedCommand::Plasma {
+            speed: DEFAULT_PLASMA_SPEED
+        })
+    ));
+    assert!(matches!(
+        parse_text_command("PLASMA 0"),
+        Ok(LedCommand::Plasma { speed: 0 })
+    ));
+    for invalid in ["plasma 256", "plasma schnell"] {
+        assert_eq!(
+            parse_text_command(invalid).err(),
+            Some(CommandParseError::InvalidArgument),
+            "{}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_ws_message() {
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"plasma","speed":100}"#, 10),
+        WsRequest::Command(LedCommand::Plasma { speed: 100 })
+    ));
+    assert!(matches!(
+        parse_ws_message::<()>(br#"{"type":"plasma"}"#, 10),
+        WsRequest::Command(LedCommand::Plasma {
+            speed: DEFAULT_PLASMA_SPEED
+        })
+    ));
+}
@@ -0,0 +1,303 @@
+//! Integration Tests für Presets (esp_core::preset)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::mock::{MockClock, MockLedWriter};
+use esp_core::preset::{PRESET_LEN, PRESET_NAME_LEN};
+use esp_core::{
+    ColorLabel, ColorSink, CommandSource, ErrorKind, ErrorSource, FirmwareError, Language,
+    LedColorMessage, LedCommand, LedLoop, LedState, LedStateConfig, Preset, PresetEffect,
+    PresetError, PresetName, Presets, RecordKind, decode_record, encode_record, parse_text_command,
+};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 200,
+    sleep_fade_ms: 10_000,
+    brightness_fade_ms: 0,
+};
+
+fn preset(name: &str, effect: PresetEffect, brightness: u8, speed: u8) -> Preset {
+    Preset::new(
+        PresetName::new(name).unwrap(),
+        RGB8::new(40, 30, 18),
+        effect,
+        brightness,
+        speed,
+    )
+}
+
+#[derive(Default)]
+struct VecColorSink(Vec<LedColorMessage>);
+
+impl ColorSink for VecColorSink {
+    fn publish(&mut self, msg: LedColorMessage) {
+        self.0.push(msg);
+    }
+}
+
+#[derive(Default)]
+struct VecCommandSource(Vec<LedCommand>);
+
+impl CommandSource for VecCommandSource {
+    fn try_next(&mut self) -> Option<LedCommand> {
+        (!self.0.is_empty()).then(|| self.0.remove(0))
+    }
+}
+
+// ============================================================================
+// Tests: Presets
+// ============================================================================
+
+#[test]
+fn test_preset_name_limits() {
+    assert_eq!(PresetName::new("Lesen").unwrap().as_str(), "Lesen");
+    assert!(PresetName::new("").is_none());
+    assert!(PresetName::new(&"x".repeat(PRESET_NAME_LEN)).is_some());
+    assert!(PresetName::new(&"x".repeat(PRESET_NAME_LEN + 1)).is_none());
+}
+
+#[test]
+fn test_presets_add_assigns_ids_and_rejects_invalid() {
+    let mut presets = Presets::<2>::new();
+    assert_eq!(
+        presets.add(preset("Lesen", PresetEffect::Solid, 255, 0)),
+        Ok(1)
+    );
+    assert_eq!(
+        presets.add(preset("Wecker", PresetEffect::Sunrise, 255, 0)),
+        Err(PresetError::InvalidPreset)
+    );
+    assert_eq!(
+        presets.add(preset("Party", PresetEffect::Auto, 255, 0)),
+        Ok(2)
+    );
+    assert_eq!(
+        presets.add(preset("Nacht", PresetEffect::Solid, 64, 0)),
+        Err(PresetError::Full)
+    );
+    assert_eq!(presets.get(2).unwrap().name.as_str(), "Party");
+
+    assert_eq!(presets.remove(1).unwrap().name.as_str(), "Lesen");
+    assert_eq!(presets.remove(1), Err(PresetError::NotFound));
+    assert_eq!(presets.len(), 1);
+}
+
+#[test]
+fn test_presets_next_id_cycles() {
+    let mut presets = Presets::<4>::new();
+    assert_eq!(presets.next_id(None), None);
+    for name in ["A", "B", "C"] {
+        presets
+            .add(preset(name, PresetEffect::Solid, 255, 0))
+            .unwrap();
+    }
+    assert_eq!(presets.next_id(None), Some(1));
+    assert_eq!(presets.next_id(Some(1)), Some(2));
+    assert_eq!(presets.next_id(Some(3)), Some(1));
+    // Gelöschtes Preset: wieder von vorne
+    presets.remove(2).unwrap();
+    assert_eq!(presets.next_id(Some(2)), Some(1));
+}
+
+#[test]
+fn test_presets_update_keeps_id() {
+    let mut presets = Presets::<4>::new();
+    let id = presets
+        .add(preset("Lesen", PresetEffect::Solid, 255, 0))
+        .unwrap();
+    presets
+        .update(id, preset("Abend", PresetEffect::Solid, 96, 0))
+        .unwrap();
+    let updated = presets.get(id).unwrap();
+    assert_eq!((updated.name.as_str(), updated.brightness), ("Abend", 96));
+    assert_eq!(
+        presets.update(id, preset("Wecker", PresetEffect::Sunrise, 255, 0)),
+        Err(PresetError::InvalidPreset)
+    );
+    assert_eq!(
+        presets.update(9, preset("Nacht", PresetEffect::Solid, 64, 0)),
+        Err(PresetError::NotFound)
+    );
+}
+
+// ============================================================================
+// Tests: Persistenz
+// ============================================================================
+
+#[test]
+fn test_presets_encode_decode_roundtrip() {
+    let mut presets = Presets::<4>::new();
+    presets
+        .add(preset("Lesen", PresetEffect::Solid, 255, 0))
+        .unwrap();
+    presets
+        .add(preset("Wecker", PresetEffect::Sunrise, 200, 15))
+        .unwrap();
+    presets
+        .add(preset("Größe", PresetEffect::Auto, 128, 0))
+        .unwrap();
+
+    let mut buffer = [0u8; 128];
+    let len = presets.encode(&mut buffer).unwrap();
+    assert_eq!(len, 1 + 3 * PRESET_LEN);
+    assert!(presets.encode(&mut buffer[..len - 1]).is_none());
+
+    let decoded = Presets::<4>::decode(&buffer[..len]);
+    assert_eq!(
+        decoded.iter().collect::<Vec<_>>(),
+        presets.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_presets_decode_skips_invalid_and_truncates() {
+    let mut presets = Presets::<4>::new();
+    for name in ["A", "B", "C"] {
+        presets
+            .add(preset(name, PresetEffect::Solid, 255, 0))
+            .unwrap();
+    }
+    let mut buffer = [0u8; 128];
+    let len = presets.encode(&mut buffer).unwrap();
+    // Unbekannter Effekt im zweiten Preset
+    buffer[1 + PRESET_LEN + 1] = 9;
+
+    let decoded = Presets::<4>::decode(&buffer[..len]);
+    assert_eq!(decoded.len(), 2);
+    assert!(decoded.get(2).is_none());
+
+    // Kapazität kleiner als gespeicherte Anzahl
+    let small = Presets::<1>::decode(&buffer[..len]);
+    assert_eq!(small.len(), 1);
+    assert!(Presets::<4>::decode(&[]).is_empty());
+}
+
+#[test]
+fn test_presets_record_roundtrip() {
+    let mut presets = Presets::<4>::new();
+    presets
+        .add(preset("Nacht", PresetEffect::Solid, 64, 0))
+        .unwrap();
+    let mut payload = [0u8; 64];
+    let len = presets.encode(&mut payload).unwrap();
+
+    let mut record = [0u8; 128];
+    let total = encode_record(RecordKind::Presets, &payload[..len], &mut record).unwrap();
+    let loaded = decode_record(&record[..total], RecordKind::Presets).unwrap();
+    assert_eq!(Presets::<4>::decode(loaded).get(1), presets.get(1));
+    assert!(decode_record(&record[..total], RecordKind::Schedule).is_none());
+}
+
+#[test]
+fn test_preset_error_taxonomy() {
+    let error = FirmwareError::from(PresetError::Full);
+    assert_eq!(error.source, ErrorSource::Preset);
+    assert_eq!(error.kind, ErrorKind::CapacityExceeded);
+    assert_eq!(
+        FirmwareError::from(PresetError::NotFound).kind,
+        ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn test_preset_commands() {
+    let solid = preset("Lesen", PresetEffect::Solid, 255, 0);
+    assert!(matches!(
+        solid.command(),
+        LedCommand::SetColor { target_color, .. } if target_color == RGB8::new(40, 30, 18)
+    ));
+    assert!(matches!(
+        preset("Party", PresetEffect::Auto, 255, 0).command(),
+        LedCommand::EnableAuto
+    ));
+    assert!(matches!(
+        preset("Wecker", PresetEffect::Sunrise, 255, 15).command(),
+        LedCommand::Sunrise { duration_secs: 900 }
+    ));
+}
+
+#[test]
+fn test_apply_preset_sets_color_and_brightness() {
+    let mut state = LedState::new(CONFIG);
+    let transition = state.apply_preset(&preset("Nacht", PresetEffect::Solid, 64, 0), 0);
+    assert!(transition.color_changed);
+    assert!(!state.auto_rotate);
+    assert_eq!(state.color, RGB8::new(40, 30, 18));
+    assert_eq!(state.brightness, 64);
+}
+
+#[test]
+fn test_preset_name_in_message_until_next_command() {
+    let mut state = LedState::new(CONFIG);
+    state.apply_preset(&preset("Nacht", PresetEffect::Solid, 64, 0), 0);
+    let message = state.message();
+    assert_eq!(message.name, ColorLabel::new("Nacht"));
+    assert_eq!(message.display_name(Language::English).as_str(), "Nacht");
+
+    // Helligkeit behält den Namen, ein Farbkommando nicht
+    state.apply(LedCommand::SetBrightness { brightness: 10 }, 0);
+    assert!(state.message().name.is_some());
+    state.apply(LedCommand::Off, 0);
+    assert!(state.message().name.is_none());
+}
+
+#[test]
+fn test_parse_preset_command() {
+    assert!(matches!(
+        parse_text_command("preset 3"),
+        Ok(LedCommand::ApplyPreset { id: 3 })
+    ));
+    assert!(parse_text_command("preset").is_err());
+    assert!(parse_text_command("preset abc").is_err());
+}
+
+// ============================================================================
+// Tests: LedLoop
+// ============================================================================
+
+#[test]
+fn test_led_loop_resolves_apply_preset() {
+    let clock = MockClock::default();
+    let mut presets = Presets::<4>::new();
+    let id = presets
+        .add(preset("Nacht", PresetEffect::Solid, 128, 0))
+        .unwrap();
+    let mut led_loop = LedLoop::new(
+        CONFIG,
+        MockLedWriter::default(),
+        &clock,
+        VecColorSink::default(),
+        VecCommandSource(vec![LedCommand::ApplyPreset { id }]),
+    )
+    .with_presets(&presets);
+
+    let cycle = led_loop.step();
+    assert!(cycle.transition.color_changed);
+    assert_eq!(led_loop.state.brightness, 128);
+    assert_eq!(led_loop.led.last_color(), Some(RGB8::new(7, 6, 3)));
+    assert_eq!(led_loop.sink.0.len(), 1);
+}
+
+#[test]
+fn test_led_loop_ignores_unknown_preset() {
+    let clock = MockClock::default();
+    let mut led_loop = LedLoop::new(
+        CONFIG,
+        MockLedWriter::default(),
+        &clock,
+        VecColorSink::default(),
+        VecCommandSource(vec![
+            LedCommand::SleepTimer { minutes: 5 },
+            LedCommand::ApplyPreset { id: 7 },
+        ]),
+    );
+
+    led_loop.step();
+    led_loop.step();
+    // Ohne Presets bleibt alles unverändert, auch der Sleep-Timer läuft weiter
+    assert!(led_loop.state.auto_rotate);
+    assert!(led_loop.state.sleep_timer.is_some());
+    assert_eq!(led_loop.state.brightness, u8::MAX);
+}
@@ -0,0 +1,71 @@
+//! Integration Tests für die Drosselung der MQTT-Farbmeldungen
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::PublishThrottle;
+
+#[test]
+fn test_first_auto_message_immediate() {
+    let mut throttle = PublishThrottle::new(5000);
+    assert!(throttle.offer(true, 12_000));
+    assert_eq!(throttle.deadline_ms(), None);
+}
+
+#[test]
+fn test_auto_messages_deferred_until_interval() {
+    let mut throttle = PublishThrottle::new(5000);
+    assert!(throttle.offer(true, 0));
+    assert!(!throttle.offer(true, 1000));
+    assert!(!throttle.offer(true, 2000));
+    assert_eq!(throttle.deadline_ms(), Some(5000));
+
+    assert!(!throttle.take_due(4999));
+    assert!(throttle.take_due(5000));
+    assert_eq!(throttle.deadline_ms(), None);
+    // Nur einmal fällig
+    assert!(!throttle.take_due(6000));
+
+    // Nächstes Intervall beginnt mit dem nachgeholten Publish
+    assert!(!throttle.offer(true, 6000));
+    assert_eq!(throttle.deadline_ms(), Some(10_000));
+}
+
+#[test]
+fn test_auto_message_after_interval_immediate() {
+    let mut throttle = PublishThrottle::new(5000);
+    assert!(throttle.offer(true, 0));
+    assert!(throttle.offer(true, 5000));
+    assert!(throttle.offer(true, 20_000));
+}
+
+#[test]
+fn test_manual_message_immediate_and_replaces_pending() {
+    let mut throttle = PublishThrottle::new(5000);
+    assert!(throttle.offer(true, 0));
+    assert!(!throttle.offer(true, 1000));
+    // Wechsel in den manuellen Modus geht sofort raus
+    assert!(throttle.offer(false, 1500));
+    assert_eq!(throttle.deadline_ms(), None);
+    assert!(!throttle.take_due(10_000));
+    assert!(throttle.offer(false, 1600));
+}
+
+#[test]
+fn test_zero_interval_disables_throttling() {
+    let mut throttle = PublishThrottle::new(0);
+    for now in 0..10 {
+        assert!(throttle.offer(true, now));
+    }
+    assert_eq!(throttle.deadline_ms(), None);
+}
+
+#[test]
+fn test_published_starts_interval() {
+    let mut throttle = PublishThrottle::new(5000);
+    // Nachgeholte Offline-Meldung
+    throttle.published(10_000);
+    assert!(!throttle.offer(true, 11_000));
+    assert_eq!(throttle.deadline_ms(), Some(15_000));
+    throttle.published(12_000);
+    assert_eq!(throttle.deadline_ms(), None);
+}
@@ -0,0 +1,149 @@
+//! Integration Tests für die Prioritäts-Warteschlange der LED-Kommandos
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{ColorId, CommandPriority, CommandQueue, LedCommand, PushOutcome};
+use rgb::RGB8;
+
+fn red() -> LedCommand {
+    LedCommand::SetColor {
+        target_color: RGB8::new(10, 0, 0),
+        color_id: ColorId::Red,
+    }
+}
+
+fn brightness(value: u8) -> LedCommand {
+    LedCommand::SetBrightness { brightness: value }
+}
+
+#[test]
+fn test_priorities() {
+    assert_eq!(LedCommand::Off.priority(), CommandPriority::High);
+    assert_eq!(red().priority(), CommandPriority::Normal);
+    assert_eq!(LedCommand::EnableAuto.priority(), CommandPriority::Normal);
+    assert_eq!(brightness(1).priority(), CommandPriority::Low);
+}
+
+#[test]
+fn test_fifo_within_priority() {
+    let mut queue: CommandQueue<4> = CommandQueue::new();
+    assert!(queue.is_empty());
+    queue.push(LedCommand::EnableAuto);
+    queue.push(LedCommand::SleepTimer { minutes: 5 });
+    assert_eq!(queue.len(), 2);
+
+    assert!(matches!(queue.pop(), Some(LedCommand::EnableAuto)));
+    assert!(matches!(
+        queue.pop(),
+        Some(LedCommand::SleepTimer { minutes: 5 })
+    ));
+    assert!(queue.pop().is_none());
+}
+
+#[test]
+fn test_off_preempts_queued_commands() {
+    let mut queue: CommandQueue<4> = CommandQueue::new();
+    queue.push(brightness(50));
+    queue.push(red());
+    queue.push(LedCommand::Off);
+
+    assert!(matches!(queue.pop(), Some(LedCommand::Off)));
+    assert!(matches!(queue.pop(), Some(LedCommand::SetColor { .. })));
+    assert!(matches!(
+        queue.pop(),
+        Some(LedCommand::SetBrightness { brightness: 50 })
+    ));
+}
+
+#[test]
+fn test_newer_state_replaces_older() {
+    let mut queue: CommandQueue<4> = CommandQueue::new();
+    for value in 0..10 {
+        queue.push(brightness(value));
+    }
+    queue.push(red());
+    queue.push(LedCommand::EnableAuto);
+    // Neue Farbe ersetzt die alte und landet hinter EnableAuto
+    assert!(matches!(queue.push(red()), PushOutcome::Replaced));
+    assert_eq!(queue.len(), 3);
+
+    assert!(matches!(queue.pop(), Some(LedCommand::EnableAuto)));
+    assert!(matches!(queue.pop(), Some(LedCommand::SetColor { .. })));
+    assert!(matches!(
+        queue.pop(),
+        Some(LedCommand::SetBrightness { brightness: 9 })
+    ));
+}
+
+#[test]
+fn test_fade_to_behaves_like_set_color() {
+    let mut queue: CommandQueue<4> = CommandQueue::new();
+    assert_eq!(
+        red().with_transition(500).priority(),
+        CommandPriority::Normal
+    );
+    // Ausschalten mit Übergang bleibt hoch priorisiert
+    assert_eq!(
+        LedCommand::Off.with_transition(500).priority(),
+        CommandPriority::High
+    );
+
+    queue.push(red());
+    assert!(matches!(
+        queue.push(red().with_transition(500)),
+        PushOutcome::Replaced
+    ));
+    assert_eq!(queue.len(), 1);
+    assert!(matches!(queue.pop(), Some(LedCommand::FadeTo { .. })));
+}
+
+#[test]
+fn test_full_queue_policy() {
+    let mut queue: CommandQueue<2> = CommandQueue::new();
+    queue.push(brightness(10));
+    queue.push(LedCommand::EnableAuto);
+
+    // Niedrigste Priorität wird verdrängt
+    assert!(matches!(
+        queue.push(LedCommand::Sunrise { duration_secs: 60 }),
+        PushOutcome::Evicted(LedCommand::SetBrightness { brightness: 10 })
+    ));
+    // Bei gleicher Priorität der älteste Eintrag
+    assert!(matches!(
+        queue.push(LedCommand::SleepTimer { minutes: 1 }),
+        PushOutcome::Evicted(LedCommand::EnableAuto)
+    ));
+    // Niedrigere Priorität als alle Einträge → verworfen
+    assert!(matches!(queue.push(brightness(20)), PushOutcome::Rejected));
+    // Off verdrängt immer
+    assert!(matches!(
+        queue.push(LedCommand::Off),
+        PushOutcome::Evicted(LedCommand::Sunrise { .. })
+    ));
+
+    assert!(matches!(queue.pop(), Some(LedCommand::Off)));
+    assert!(matches!(
+        queue.pop(),
+        Some(LedCommand::SleepTimer { minutes: 1 })
+    ));
+    assert!(queue.pop().is_none());
+}
+
+#[test]
+fn test_queue_stats_count_dropped_commands() {
+    let mut queue: CommandQueue<2> = CommandQueue::new();
+    queue.push(brightness(10));
+    queue.push(brightness(11));
+    queue.push(LedCommand::EnableAuto);
+    queue.push(LedCommand::Sunrise { duration_secs: 60 });
+    let rejected = queue.push(brightness(12));
+    assert!(!rejected.is_accepted());
+    assert!(PushOutcome::Queued.is_accepted());
+
+    let stats = queue.stats();
+    assert_eq!(stats.queued, 2);
+    assert_eq!(stats.replaced, 1);
+    assert_eq!(stats.evicted, 1);
+    assert_eq!(stats.rejected, 1);
+    assert_eq!(stats.dropped(), 2);
+}
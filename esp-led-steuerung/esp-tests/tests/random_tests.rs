@@ -0,0 +1,177 @@
+//! Integration Tests für Zufallsmodus und Zufallsgenerator
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::color::HUE_CIRCLE;
+use esp_core::random::RANDOM_MIN_HUE_DISTANCE;
+use esp_core::{
+    Hsv, LedCommand, LedState, LedStateConfig, Rng, SeededRng, parse_text_command, random_color,
+};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 128,
+    sleep_fade_ms: 60_000,
+    brightness_fade_ms: 0,
+};
+
+/// Liefert eine feste Zahlenfolge (zyklisch)
+struct ScriptedRng {
+    values: Vec<u32>,
+    index: usize,
+}
+
+impl ScriptedRng {
+    fn new(values: &[u32]) -> Self {
+        Self {
+            values: values.to_vec(),
+            index: 0,
+        }
+    }
+}
+
+impl Rng for ScriptedRng {
+    fn next_u32(&mut self) -> u32 {
+        let value = self.values[self.index % self.values.len()];
+        self.index += 1;
+        value
+    }
+}
+
+fn hue_distance(a: RGB8, b: RGB8) -> u16 {
+    let diff = Hsv::from_rgb(a).hue.abs_diff(Hsv::from_rgb(b).hue);
+    diff.min(HUE_CIRCLE - diff)
+}
+
+// ============================================================================
+// SeededRng
+// ============================================================================
+
+#[test]
+fn test_seeded_rng_is_reproducible() {
+    let mut a = SeededRng::new(42);
+    let mut b = SeededRng::new(42);
+    let first: Vec<u32> = (0..8).map(|_| a.next_u32()).collect();
+    let second: Vec<u32> = (0..8).map(|_| b.next_u32()).collect();
+    assert_eq!(first, second);
+
+    let mut other = SeededRng::new(43);
+    assert_ne!(first[0], other.next_u32());
+}
+
+#[test]
+fn test_seeded_rng_zero_seed_does_not_stick() {
+    let mut rng = SeededRng::new(0);
+    assert!((0..4).all(|_| rng.next_u32() != 0));
+}
+
+// ============================================================================
+// random_color
+// ============================================================================
+
+#[test]
+fn test_random_color_from_black_uses_any_hue() {
+    // 510 = Grün
+    let mut rng = ScriptedRng::new(&[510]);
+    assert_eq!(
+        random_color(&mut rng, RGB8::default(), 255),
+        RGB8::new(0, 255, 0)
+    );
+}
+
+#[test]
+fn test_random_color_keeps_minimum_distance() {
+    // Kleinster und größter Zufallswert: genau der Mindestabstand in beide Richtungen
+    let red = RGB8::new(255, 0, 0);
+    let span = (HUE_CIRCLE - 2 * RANDOM_MIN_HUE_DISTANCE + 1) as u32;
+    let mut rng = ScriptedRng::new(&[0, span - 1]);
+    assert_eq!(random_color(&mut rng, red, 255), RGB8::new(255, 255, 0));
+    assert_eq!(random_color(&mut rng, red, 255), RGB8::new(255, 0, 255));
+}
+
+#[test]
+fn test_random_colors_are_distinct_and_saturated() {
+    let mut rng = SeededRng::new(7);
+    let mut previous = RGB8::new(10, 0, 0);
+    for _ in 0..200 {
+        let color = random_color(&mut rng, previous, 10);
+        assert!(hue_distance(previous, color) >= RANDOM_MIN_HUE_DISTANCE - 8);
+        let hsv = Hsv::from_rgb(color);
+        assert_eq!(hsv.value, 10);
+        assert!(color.r == 0 || color.g == 0 || color.b == 0);
+        previous = color;
+    }
+}
+
+// ============================================================================
+// Zufallsmodus im Zustandsautomaten
+// ============================================================================
+
+#[test]
+fn test_text_commands_enable_random() {
+    assert!(matches!(
+        parse_text_command("zufall"),
+        Ok(LedCommand::EnableRandom)
+    ));
+    assert!(matches!(
+        parse_text_command("Random"),
+        Ok(LedCommand::EnableRandom)
+    ));
+}
+
+#[test]
+fn test_random_mode_picks_new_color_each_tick() {
+    let mut state = LedState::new(CONFIG);
+    let mut rng = SeededRng::new(1);
+    state.apply(LedCommand::EnableRandom, 0);
+    assert!(state.auto_rotate && state.random);
+
+    let mut previous = state.color;
+    for _ in 0..5 {
+        // Ohne Zufallsquelle ändert der Tick im Zufallsmodus nichts
+        assert!(!state.tick(0).color_changed);
+        assert_eq!(state.color, previous);
+        assert!(state.tick_random(&mut rng).color_changed);
+        assert_ne!(state.color, previous);
+        previous = state.color;
+    }
+    assert!(state.message().is_auto_mode);
+}
+
+#[test]
+fn test_manual_command_ends_random_mode() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::EnableRandom, 0);
+    state.apply(LedCommand::Off, 0);
+    assert!(!state.random);
+    assert!(!state.tick_random(&mut SeededRng::new(1)).color_changed);
+    assert_eq!(state.color, RGB8::default());
+}
+
+#[test]
+fn test_enable_auto_returns_to_sequence() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::EnableRandom, 0);
+    state.apply(LedCommand::EnableAuto, 0);
+    assert!(!state.random);
+    assert!(!state.tick_random(&mut SeededRng::new(1)).color_changed);
+}
+
+#[test]
+fn test_brightness_keeps_random_mode() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::EnableRandom, 0);
+    state.apply(LedCommand::SetBrightness { brightness: 100 }, 0);
+    state.apply(LedCommand::SleepTimer { minutes: 10 }, 0);
+    assert!(state.random);
+}
+
+#[test]
+fn test_sleep_expiry_ends_random_mode() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::EnableRandom, 0);
+    state.apply(LedCommand::SleepTimer { minutes: 1 }, 0);
+    assert!(state.tick(60_000).sleep_expired);
+    assert!(!state.random);
+}
@@ -0,0 +1,77 @@
+//! Integration Tests für das UDP-Realtime-Protokoll
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::realtime::PROTOCOL_DRGB;
+use esp_core::{LedError, RealtimePacket, SmartLedWriter, decode_realtime_packet};
+use rgb::RGB8;
+
+/// Minimaler Writer, der nur die Einzel-Farbe implementiert (Default write_frame)
+#[derive(Default)]
+struct SingleLedWriter {
+    last_color: Option<RGB8>,
+}
+
+impl SmartLedWriter for SingleLedWriter {
+    fn write(&mut self, color: RGB8) -> Result<(), LedError> {
+        self.last_color = Some(color);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_decode_raw_rgb_fill() {
+    let packet = decode_realtime_packet(&[1, 2, 3]).unwrap();
+    assert_eq!(packet, RealtimePacket::Fill(RGB8::new(1, 2, 3)));
+    assert_eq!(packet.timeout_secs(), None);
+
+    let mut frame = [RGB8::default(); 4];
+    packet.render(&mut frame);
+    assert!(frame.iter().all(|p| *p == RGB8::new(1, 2, 3)));
+}
+
+#[test]
+fn test_decode_drgb_pixels() {
+    let packet = decode_realtime_packet(&[PROTOCOL_DRGB, 5, 10, 0, 0, 0, 20, 0]).unwrap();
+    assert_eq!(packet.timeout_secs(), Some(5));
+
+    // Frame mit 3 LEDs: die dritte bleibt unverändert
+    let mut frame = [RGB8::new(9, 9, 9); 3];
+    packet.render(&mut frame);
+    assert_eq!(
+        frame,
+        [RGB8::new(10, 0, 0), RGB8::new(0, 20, 0), RGB8::new(9, 9, 9)]
+    );
+}
+
+#[test]
+fn test_decode_drgb_zero_timeout_uses_default() {
+    let packet = decode_realtime_packet(&[PROTOCOL_DRGB, 0, 1, 2, 3]).unwrap();
+    assert_eq!(packet.timeout_secs(), None);
+}
+
+#[test]
+fn test_decode_rejects_invalid_packets() {
+    assert_eq!(decode_realtime_packet(&[]), None);
+    assert_eq!(decode_realtime_packet(&[1, 2]), None);
+    // Unbekanntes Protokoll
+    assert_eq!(decode_realtime_packet(&[7, 1, 1, 2, 3]), None);
+    // DRGB ohne Pixel bzw. mit unvollständigem Pixel
+    assert_eq!(decode_realtime_packet(&[PROTOCOL_DRGB, 1]), None);
+    assert_eq!(
+        decode_realtime_packet(&[PROTOCOL_DRGB, 1, 1, 2, 3, 4]),
+        None
+    );
+}
+
+#[test]
+fn test_default_write_frame_writes_first_pixel() {
+    let mut writer = SingleLedWriter::default();
+    writer.write_frame(&[]).unwrap();
+    assert_eq!(writer.last_color, None);
+
+    writer
+        .write_frame(&[RGB8::new(1, 0, 0), RGB8::new(0, 1, 0)])
+        .unwrap();
+    assert_eq!(writer.last_color, Some(RGB8::new(1, 0, 0)));
+}
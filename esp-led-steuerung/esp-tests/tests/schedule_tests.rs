@@ -0,0 +1,281 @@
+//! Integration Tests für Zeitplan, Zeitrechnung, SNTP und Persistenz-Records
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::record::crc32;
+use esp_core::schedule::ALL_DAYS;
+use esp_core::sntp::{SNTP_PACKET_LEN, parse_sntp_response, sntp_request};
+use esp_core::time::{
+    MIN_PLAUSIBLE_UNIX_SECS, civil_from_days, days_from_civil, eu_dst_active, restored_unix_ms,
+};
+use esp_core::{
+    ColorId, LedCommand, RecordKind, Schedule, ScheduleAction, ScheduleEntry, ScheduleError,
+    TimeZone, Weekday, decode_record, encode_record, local_time,
+};
+use rgb::RGB8;
+
+fn entry(days: u8, hour: u8, minute: u8, action: ScheduleAction) -> ScheduleEntry {
+    ScheduleEntry {
+        id: 0,
+        days,
+        hour,
+        minute,
+        action,
+        enabled: true,
+    }
+}
+
+// ============================================================================
+// Tests: Zeitrechnung
+// ============================================================================
+
+#[test]
+fn test_local_time_utc_epoch() {
+    // 1970-01-01 00:00:00 UTC war ein Donnerstag
+    let t = local_time(0, TimeZone::UTC);
+    assert_eq!(t.weekday, Weekday::Thursday);
+    assert_eq!((t.hour, t.minute, t.second), (0, 0, 0));
+}
+
+#[test]
+fn test_local_time_cet_winter_and_summer() {
+    // 2024-01-15 12:00:00 UTC (Montag, Winterzeit) → 13:00 MEZ
+    let winter = local_time(1_705_320_000, TimeZone::CET);
+    assert_eq!(winter.weekday, Weekday::Monday);
+    assert_eq!((winter.hour, winter.minute), (13, 0));
+
+    // 2024-07-15 12:00:00 UTC (Montag, Sommerzeit) → 14:00 MESZ
+    let summer = local_time(1_721_044_800, TimeZone::CET);
+    assert_eq!(summer.weekday, Weekday::Monday);
+    assert_eq!((summer.hour, summer.minute), (14, 0));
+}
+
+#[test]
+fn test_civil_date_roundtrip() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(19_782), (2024, 2, 29)); // Schalttag
+    assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+}
+
+#[test]
+fn test_eu_dst_boundaries_2024() {
+    // Sommerzeit 2024: 31.03. 01:00 UTC bis 27.10. 01:00 UTC
+    let start = 1_711_846_800;
+    let end = 1_729_990_800;
+    assert!(!eu_dst_active(start - 1));
+    assert!(eu_dst_active(start));
+    assert!(eu_dst_active(end - 1));
+    assert!(!eu_dst_active(end));
+}
+
+#[test]
+fn test_restored_rtc_time_must_be_plausible() {
+    // RTC nach Stromausfall: zählt ab 0, Uhrzeit unbekannt
+    assert_eq!(restored_unix_ms(0), None);
+    assert_eq!(restored_unix_ms(3_600_000), None);
+    assert_eq!(restored_unix_ms(MIN_PLAUSIBLE_UNIX_SECS * 1000 - 1), None);
+
+    // Vor dem Neustart per SNTP gestellt
+    let min_ms = MIN_PLAUSIBLE_UNIX_SECS * 1000;
+    assert_eq!(restored_unix_ms(min_ms), Some(min_ms));
+    assert_eq!(restored_unix_ms(1_721_044_800_123), Some(1_721_044_800_123));
+}
+
+// ============================================================================
+// Tests: SNTP
+// ============================================================================
+
+#[test]
+fn test_sntp_request_header() {
+    let request = sntp_request();
+    assert_eq!(request.len(), SNTP_PACKET_LEN);
+    assert_eq!(request[0] & 0x07, 3); // Mode: Client
+    assert_eq!((request[0] >> 3) & 0x07, 4); // Version 4
+}
+
+#[test]
+fn test_sntp_parse_response() {
+    let mut packet = [0u8; SNTP_PACKET_LEN];
+    packet[0] = 0b00_100_100; // Version 4, Mode 4 (Server)
+    packet[1] = 2; // Stratum
+    // 2024-01-01 00:00:00 UTC = NTP 3_913_056_000, + 0.5 s
+    packet[40..44].copy_from_slice(&3_913_056_000u32.to_be_bytes());
+    packet[44..48].copy_from_slice(&0x8000_0000u32.to_be_bytes());
+    assert_eq!(parse_sntp_response(&packet), Some(1_704_067_200_500));
+}
+
+#[test]
+fn test_sntp_rejects_invalid_packets() {
+    let mut packet = [0u8; SNTP_PACKET_LEN];
+    packet[0] = 0b00_100_100;
+    packet[1] = 0; // Kiss-of-Death
+    assert_eq!(parse_sntp_response(&packet), None);
+    assert_eq!(parse_sntp_response(&packet[..40]), None);
+}
+
+// ============================================================================
+// Tests: Schedule
+// ============================================================================
+
+#[test]
+fn test_schedule_add_assigns_ids() {
+    let mut schedule = Schedule::<4>::new();
+    let a = schedule.add(entry(ALL_DAYS, 7, 0, ScheduleAction::Auto));
+    let b = schedule.add(entry(ALL_DAYS, 23, 0, ScheduleAction::Off));
+    assert_eq!(a, Ok(1));
+    assert_eq!(b, Ok(2));
+    assert_eq!(schedule.len(), 2);
+}
+
+#[test]
+fn test_schedule_rejects_invalid_and_full() {
+    let mut schedule = Schedule::<1>::new();
+    assert_eq!(
+        schedule.add(entry(ALL_DAYS, 24, 0, ScheduleAction::Off)),
+        Err(ScheduleError::InvalidEntry)
+    );
+    assert_eq!(
+        schedule.add(entry(0, 7, 0, ScheduleAction::Off)),
+        Err(ScheduleError::InvalidEntry)
+    );
+    schedule
+        .add(entry(ALL_DAYS, 7, 0, ScheduleAction::Off))
+        .unwrap();
+    assert_eq!(
+        schedule.add(entry(ALL_DAYS, 8, 0, ScheduleAction::Off)),
+        Err(ScheduleError::Full)
+    );
+}
+
+#[test]
+fn test_schedule_remove() {
+    let mut schedule = Schedule::<4>::new();
+    let id = schedule
+        .add(entry(ALL_DAYS, 7, 0, ScheduleAction::Auto))
+        .unwrap();
+    assert!(schedule.remove(id).is_ok());
+    assert_eq!(schedule.remove(id), Err(ScheduleError::NotFound));
+    assert!(schedule.is_empty());
+}
+
+#[test]
+fn test_schedule_update_keeps_id() {
+    let mut schedule = Schedule::<4>::new();
+    let id = schedule
+        .add(entry(ALL_DAYS, 7, 0, ScheduleAction::Auto))
+        .unwrap();
+    schedule
+        .update(id, entry(0b0001_1111, 6, 45, ScheduleAction::Off))
+        .unwrap();
+    let updated = schedule.get(id).unwrap();
+    assert_eq!(updated.id, id);
+    assert_eq!(
+        (updated.days, updated.hour, updated.minute, updated.action),
+        (0b0001_1111, 6, 45, ScheduleAction::Off)
+    );
+    assert_eq!(schedule.len(), 1);
+
+    // Ungültige Uhrzeit ändert nichts
+    assert_eq!(
+        schedule.update(id, entry(ALL_DAYS, 24, 0, ScheduleAction::Auto)),
+        Err(ScheduleError::InvalidEntry)
+    );
+    assert_eq!(schedule.get(id).unwrap().hour, 6);
+    assert_eq!(
+        schedule.update(9, entry(ALL_DAYS, 7, 0, ScheduleAction::Auto)),
+        Err(ScheduleError::NotFound)
+    );
+}
+
+#[test]
+fn test_schedule_due_actions_respects_weekday() {
+    let mut schedule = Schedule::<4>::new();
+    let red = RGB8 { r: 10, g: 0, b: 0 };
+    // Nur Montag (Bit 0) um 18:30
+    schedule
+        .add(entry(0b0000_0001, 18, 30, ScheduleAction::SetColor(red)))
+        .unwrap();
+
+    // 2024-01-15 17:30 UTC = Montag 18:30 MEZ
+    let monday = local_time(1_705_339_800, TimeZone::CET);
+    let due: Vec<_> = schedule.due_actions(&monday).collect();
+    assert_eq!(due, vec![ScheduleAction::SetColor(red)]);
+
+    // Einen Tag später: Dienstag 18:30 → nicht fällig
+    let tuesday = local_time(1_705_339_800 + 86_400, TimeZone::CET);
+    assert_eq!(schedule.due_actions(&tuesday).count(), 0);
+}
+
+#[test]
+fn test_schedule_disabled_entry_not_due() {
+    let mut schedule = Schedule::<2>::new();
+    let mut disabled = entry(ALL_DAYS, 0, 0, ScheduleAction::Off);
+    disabled.enabled = false;
+    schedule.add(disabled).unwrap();
+    let midnight = local_time(0, TimeZone::UTC);
+    assert_eq!(schedule.due_actions(&midnight).count(), 0);
+}
+
+#[test]
+fn test_schedule_encode_decode_roundtrip() {
+    let mut schedule = Schedule::<4>::new();
+    schedule
+        .add(entry(
+            ALL_DAYS,
+            7,
+            15,
+            ScheduleAction::SetColor(RGB8 { r: 1, g: 2, b: 3 }),
+        ))
+        .unwrap();
+    schedule
+        .add(entry(0b0110_0000, 22, 45, ScheduleAction::Off))
+        .unwrap();
+
+    let mut buffer = [0u8; 64];
+    let len = schedule.encode(&mut buffer).unwrap();
+    let decoded = Schedule::<4>::decode(&buffer[..len]);
+    assert!(decoded.entries().eq(schedule.entries()));
+}
+
+#[test]
+fn test_schedule_action_to_command() {
+    assert!(matches!(
+        LedCommand::from(ScheduleAction::Off),
+        LedCommand::Off
+    ));
+    match LedCommand::from(ScheduleAction::SetColor(RGB8 { r: 0, g: 10, b: 0 })) {
+        LedCommand::SetColor { color_id, .. } => assert_eq!(color_id, ColorId::Green),
+        _ => panic!("Expected SetColor variant"),
+    }
+}
+
+// ============================================================================
+// Tests: Persistenz-Records
+// ============================================================================
+
+#[test]
+fn test_crc32_reference_value() {
+    // Standard-Prüfwert für CRC-32/ISO-HDLC
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn test_record_roundtrip() {
+    let mut buffer = [0u8; 32];
+    let len = encode_record(RecordKind::Schedule, &[1, 2, 3], &mut buffer).unwrap();
+    assert_eq!(
+        decode_record(&buffer[..len], RecordKind::Schedule),
+        Some(&[1u8, 2, 3][..])
+    );
+}
+
+#[test]
+fn test_record_rejects_erased_and_corrupt_flash() {
+    let erased = [0xFFu8; 32];
+    assert_eq!(decode_record(&erased, RecordKind::Schedule), None);
+
+    let mut buffer = [0u8; 32];
+    let len = encode_record(RecordKind::Schedule, &[1, 2, 3], &mut buffer).unwrap();
+    buffer[len - 1] ^= 0xFF; // Payload-Bit kippen
+    assert_eq!(decode_record(&buffer[..len], RecordKind::Schedule), None);
+}
@@ -0,0 +1,183 @@
+//! Integration Tests für die Farbfolge der Auto-Rotation
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::sequence::SEQUENCE_MAX_COLORS;
+use esp_core::{
+    ColorSequence, CommandParseError, LedCommand, LedState, LedStateConfig, parse_text_command,
+    rotate_color,
+};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 128,
+    sleep_fade_ms: 60_000,
+    brightness_fade_ms: 0,
+};
+
+const ORANGE: RGB8 = RGB8::new(255, 128, 0);
+const TEAL: RGB8 = RGB8::new(0, 128, 128);
+const VIOLET: RGB8 = RGB8::new(128, 0, 255);
+
+fn custom() -> ColorSequence {
+    ColorSequence::new(&[ORANGE, TEAL, VIOLET]).unwrap()
+}
+
+// ============================================================================
+// ColorSequence
+// ============================================================================
+
+#[test]
+fn test_classic_matches_rotate_color() {
+    let sequence = ColorSequence::classic(10);
+    let mut color = sequence.first();
+    for _ in 0..6 {
+        let next = sequence.next_after(color);
+        assert_eq!(next, rotate_color(color));
+        color = next;
+    }
+}
+
+#[test]
+fn test_custom_sequence_cycles() {
+    let sequence = custom();
+    assert_eq!(sequence.colors(), [ORANGE, TEAL, VIOLET]);
+    assert_eq!(sequence.next_after(ORANGE), TEAL);
+    assert_eq!(sequence.next_after(TEAL), VIOLET);
+    assert_eq!(sequence.next_after(VIOLET), ORANGE);
+}
+
+#[test]
+fn test_unknown_color_restarts_sequence() {
+    assert_eq!(custom().next_after(RGB8::new(1, 2, 3)), ORANGE);
+}
+
+#[test]
+fn test_single_color_sequence_stays() {
+    let sequence = ColorSequence::new(&[TEAL]).unwrap();
+    assert_eq!(sequence.next_after(TEAL), TEAL);
+}
+
+#[test]
+fn test_new_rejects_empty_and_too_long() {
+    assert!(ColorSequence::new(&[]).is_none());
+    assert!(ColorSequence::new(&[TEAL; SEQUENCE_MAX_COLORS]).is_some());
+    assert!(ColorSequence::new(&[TEAL; SEQUENCE_MAX_COLORS + 1]).is_none());
+}
+
+#[test]
+fn test_parse_mixed_formats() {
+    let sequence = ColorSequence::parse("#FF8000 008080 128,0,255 rot").unwrap();
+    assert_eq!(
+        sequence.colors(),
+        [ORANGE, TEAL, VIOLET, RGB8::new(10, 0, 0)]
+    );
+}
+
+#[test]
+fn test_parse_rejects_invalid() {
+    assert!(ColorSequence::parse("").is_none());
+    assert!(ColorSequence::parse("#FF8000 lila").is_none());
+    assert!(ColorSequence::parse("1 2 3 4 5 6 7 8 9").is_none());
+    assert!(ColorSequence::parse("#FF80").is_none());
+}
+
+// ============================================================================
+// Text-Kommando
+// ============================================================================
+
+#[test]
+fn test_text_command_sets_sequence() {
+    match parse_text_command("sequence #FF8000 #008080") {
+        Ok(LedCommand::SetSequence {
+            sequence: Some(sequence),
+        }) => assert_eq!(sequence.colors(), [ORANGE, TEAL]),
+        _ => panic!("Expected SetSequence"),
+    }
+}
+
+#[test]
+fn test_text_command_without_colors_resets() {
+    assert!(matches!(
+        parse_text_command("SEQUENCE"),
+        Ok(LedCommand::SetSequence { sequence: None })
+    ));
+    assert_eq!(
+        parse_text_command("sequence nope").err(),
+        Some(CommandParseError::InvalidArgument)
+    );
+}
+
+// ============================================================================
+// LedState
+// ============================================================================
+
+#[test]
+fn test_state_rotates_through_custom_sequence() {
+    let mut state = LedState::new(CONFIG);
+    let transition = state.apply(
+        LedCommand::SetSequence {
+            sequence: Some(custom()),
+        },
+        0,
+    );
+    assert!(transition.color_changed);
+    assert_eq!(state.color, ORANGE);
+
+    state.tick(0);
+    assert_eq!(state.color, TEAL);
+    state.tick(0);
+    assert_eq!(state.color, VIOLET);
+    state.tick(0);
+    assert_eq!(state.color, ORANGE);
+}
+
+#[test]
+fn test_sequence_in_manual_mode_waits_for_auto() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::Off, 0);
+    let transition = state.apply(
+        LedCommand::SetSequence {
+            sequence: Some(custom()),
+        },
+        0,
+    );
+    assert!(!transition.color_changed);
+    assert_eq!(state.color, RGB8::default());
+
+    // Nach "Aus" startet die Rotation am Anfang der Folge
+    state.apply(LedCommand::EnableAuto, 0);
+    assert_eq!(state.color, ORANGE);
+    state.tick(0);
+    assert_eq!(state.color, TEAL);
+}
+
+#[test]
+fn test_sequence_keeps_sleep_timer() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::SleepTimer { minutes: 30 }, 0);
+    state.apply(
+        LedCommand::SetSequence {
+            sequence: Some(custom()),
+        },
+        0,
+    );
+    assert!(state.sleep_timer.is_some());
+}
+
+#[test]
+fn test_reset_restores_classic_sequence() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(
+        LedCommand::SetSequence {
+            sequence: Some(custom()),
+        },
+        0,
+    );
+    state.apply(LedCommand::SetSequence { sequence: None }, 0);
+    assert_eq!(state.sequence, ColorSequence::classic(10));
+    assert_eq!(state.color, RGB8::new(10, 0, 0));
+    state.tick(0);
+    assert_eq!(state.color, RGB8::new(0, 10, 0));
+}
@@ -0,0 +1,182 @@
+//! Integration Tests für die serde-Abbildung der Core-Typen
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{
+    Calibration, ClockFormat, ColorId, ColorLabel, ColorSequence, Gradient, LedColorMessage,
+    LedCommand, ParamValue, TextMessage,
+};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 255);
+
+fn to_json<T: serde::Serialize>(value: &T) -> String {
+    let mut buffer = [0u8; 512];
+    let len = serde_json_core::to_slice(value, &mut buffer).unwrap();
+    String::from_utf8(buffer[..len].to_vec()).unwrap()
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(json: &str) -> Option<T> {
+    serde_json_core::from_str(json).ok().map(|(value, _)| value)
+}
+
+// ============================================================================
+// LedCommand
+// ============================================================================
+
+#[test]
+fn test_command_set_color() {
+    let command = LedCommand::SetColor {
+        target_color: RED,
+        color_id: ColorId::Red,
+    };
+    let json = to_json(&command);
+    assert_eq!(
+        json,
+        r#"{"set_color":{"target_color":{"r":255,"g":0,"b":0},"color_id":"Rot"}}"#
+    );
+    assert!(matches!(
+        from_json::<LedCommand>(&json),
+        Some(LedCommand::SetColor {
+            target_color: RED,
+            color_id: ColorId::Red,
+        })
+    ));
+}
+
+#[test]
+fn test_command_unit_variant() {
+    assert_eq!(to_json(&LedCommand::EnableAuto), r#""enable_auto""#);
+    assert!(matches!(
+        from_json::<LedCommand>(r#""test_pattern""#),
+        Some(LedCommand::TestPattern)
+    ));
+    assert!(from_json::<LedCommand>(r#""unbekannt""#).is_none());
+}
+
+#[test]
+fn test_command_round_trips() {
+    let commands = [
+        LedCommand::SetBrightness { brightness: 128 },
+        LedCommand::ScrollText {
+            text: TextMessage::new("Hallo").unwrap(),
+        },
+        LedCommand::ShowClock {
+            format: Some(ClockFormat::H12),
+        },
+        LedCommand::SetSequence {
+            sequence: ColorSequence::new(&[RED, BLUE]),
+        },
+        LedCommand::ShowGradient {
+            gradient: Gradient::new(&[RED, BLUE]).unwrap(),
+        },
+        LedCommand::SetEffectParam {
+            value: ParamValue::Speed(40),
+        },
+    ];
+    for command in commands {
+        let json = to_json(&command);
+        let parsed: LedCommand = from_json(&json).unwrap();
+        assert_eq!(to_json(&parsed), json);
+    }
+}
+
+#[test]
+fn test_command_nested_values() {
+    assert_eq!(
+        to_json(&LedCommand::ShowClock {
+            format: Some(ClockFormat::H24)
+        }),
+        r#"{"show_clock":{"format":"h24"}}"#
+    );
+    assert_eq!(
+        to_json(&LedCommand::SetEffectParam {
+            value: ParamValue::Speed(40)
+        }),
+        r#"{"set_effect_param":{"value":{"speed":40}}}"#
+    );
+}
+
+// ============================================================================
+// Fest allokierte Typen
+// ============================================================================
+
+#[test]
+fn test_text_as_string() {
+    let text = TextMessage::new("Hallo Welt").unwrap();
+    assert_eq!(to_json(&text), r#""Hallo Welt""#);
+    assert_eq!(from_json::<TextMessage>(r#""Hallo Welt""#), Some(text));
+    assert_eq!(from_json::<TextMessage>(r#""""#), None);
+    let too_long = format!("\"{}\"", "x".repeat(33));
+    assert_eq!(from_json::<TextMessage>(&too_long), None);
+}
+
+#[test]
+fn test_label_as_string() {
+    let label = ColorLabel::new("Abendrot").unwrap();
+    assert_eq!(to_json(&label), r#""Abendrot""#);
+    assert_eq!(
+        from_json::<ColorLabel>(r#""Abendrot""#).map(|label| label.as_str().to_owned()),
+        Some("Abendrot".to_owned())
+    );
+    let too_long = format!("\"{}\"", "x".repeat(21));
+    assert!(from_json::<ColorLabel>(&too_long).is_none());
+}
+
+#[test]
+fn test_colors_as_array() {
+    let sequence = ColorSequence::new(&[RED, BLUE]).unwrap();
+    let json = to_json(&sequence);
+    assert_eq!(json, r#"[{"r":255,"g":0,"b":0},{"r":0,"g":0,"b":255}]"#);
+    assert_eq!(from_json::<ColorSequence>(&json), Some(sequence));
+    assert_eq!(
+        from_json::<Gradient>(&json),
+        Some(Gradient::new(&[RED, BLUE]).unwrap())
+    );
+
+    // Verlauf braucht mindestens zwei Farben, beide höchstens acht
+    let single = r#"[{"r":255,"g":0,"b":0}]"#;
+    assert!(from_json::<ColorSequence>(single).is_some());
+    assert_eq!(from_json::<Gradient>(single), None);
+    let nine = format!("[{}]", [r#"{"r":1,"g":2,"b":3}"#; 9].join(","));
+    assert_eq!(from_json::<ColorSequence>(&nine), None);
+    assert_eq!(from_json::<ColorSequence>("[]"), None);
+}
+
+// ============================================================================
+// LedColorMessage / Kalibrierung
+// ============================================================================
+
+#[test]
+fn test_color_message() {
+    let message = LedColorMessage {
+        sequence: 7,
+        timestamp_ms: 1200,
+        ..LedColorMessage::from_color(RGB8::new(0, 200, 0), false)
+    };
+    let json = to_json(&message);
+    assert_eq!(
+        json,
+        r#"{"color":{"r":0,"g":200,"b":0},"color_id":"Grün","name":null,"is_auto_mode":false,"sequence":7,"timestamp_ms":1200}"#
+    );
+    let parsed: LedColorMessage = from_json(&json).unwrap();
+    assert_eq!(parsed.color, message.color);
+    assert_eq!(parsed.color_id, ColorId::Green);
+    assert_eq!(parsed.sequence, 7);
+
+    let named = message.with_name(ColorLabel::new("Wald"));
+    assert!(to_json(&named).contains(r#""name":"Wald""#));
+}
+
+#[test]
+fn test_calibration_as_rgb() {
+    let calibration = Calibration {
+        r: 255,
+        g: 200,
+        b: 180,
+    };
+    let color: RGB8 = calibration.into();
+    assert_eq!(color, RGB8::new(255, 200, 180));
+    assert_eq!(Calibration::from(color), calibration);
+}
@@ -0,0 +1,180 @@
+//! Integration Tests für SSDP-Discovery und UPnP-Gerätebeschreibung
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::ssdp::{DEVICE_TYPE, NotificationType, SearchTarget};
+use esp_core::{
+    DeviceDescription, DeviceUuid, SsdpDevice, SsdpSearch, parse_m_search, write_device_description,
+};
+
+const MAC: [u8; 6] = [0x40, 0x4C, 0xCA, 0x01, 0x02, 0xAB];
+
+fn m_search(st: &str, mx: &str) -> String {
+    format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\nMAN: \"ssdp:discover\"\r\nMX: {}\r\nST: {}\r\n\r\n",
+        mx, st
+    )
+}
+
+fn device(uuid: DeviceUuid) -> SsdpDevice<'static> {
+    SsdpDevice {
+        uuid,
+        ip: [192, 168, 1, 42],
+        version: "0.2.0",
+    }
+}
+
+#[test]
+fn test_uuid_from_mac() {
+    let uuid = DeviceUuid::from_mac(MAC);
+    assert_eq!(uuid.as_str(), "4c454453-7465-7565-7275-404cca0102ab");
+    assert_ne!(uuid, DeviceUuid::from_mac([0; 6]));
+}
+
+#[test]
+fn test_parse_search_targets() {
+    let uuid = DeviceUuid::from_mac(MAC);
+    assert_eq!(
+        parse_m_search(m_search("ssdp:all", "2").as_bytes(), &uuid),
+        Some(SsdpSearch {
+            target: SearchTarget::All,
+            mx: 2
+        })
+    );
+    assert_eq!(
+        parse_m_search(m_search("upnp:rootdevice", "1").as_bytes(), &uuid)
+            .map(|search| search.target),
+        Some(SearchTarget::Only(NotificationType::RootDevice))
+    );
+    assert_eq!(
+        parse_m_search(m_search(DEVICE_TYPE, "1").as_bytes(), &uuid).map(|search| search.target),
+        Some(SearchTarget::Only(NotificationType::DeviceType))
+    );
+    let by_uuid = m_search("uuid:4C454453-7465-7565-7275-404CCA0102AB", "1");
+    assert_eq!(
+        parse_m_search(by_uuid.as_bytes(), &uuid).map(|search| search.target),
+        Some(SearchTarget::Only(NotificationType::Uuid))
+    );
+}
+
+#[test]
+fn test_parse_ignores_other_packets() {
+    let uuid = DeviceUuid::from_mac(MAC);
+    // Suche nach anderen Geräten
+    let other = m_search("urn:schemas-upnp-org:device:MediaRenderer:1", "1");
+    assert_eq!(parse_m_search(other.as_bytes(), &uuid), None);
+    let other_uuid = m_search("uuid:4c454453-7465-7565-7275-000000000000", "1");
+    assert_eq!(parse_m_search(other_uuid.as_bytes(), &uuid), None);
+
+    // Ankündigung eines anderen Geräts
+    let notify = "NOTIFY * HTTP/1.1\r\nNT: upnp:rootdevice\r\nNTS: ssdp:alive\r\n\r\n";
+    assert_eq!(parse_m_search(notify.as_bytes(), &uuid), None);
+
+    // MAN fehlt
+    let no_man = "M-SEARCH * HTTP/1.1\r\nMX: 1\r\nST: ssdp:all\r\n\r\n";
+    assert_eq!(parse_m_search(no_man.as_bytes(), &uuid), None);
+
+    assert_eq!(parse_m_search(&[0xFF, 0xFE], &uuid), None);
+}
+
+#[test]
+fn test_parse_headers_case_insensitive_and_mx_clamped() {
+    let uuid = DeviceUuid::from_mac(MAC);
+    let search = "m-search * HTTP/1.1\nst:ssdp:all\nman: \"ssdp:discover\"\nmx: 120\n\n";
+    assert_eq!(
+        parse_m_search(search.as_bytes(), &uuid),
+        Some(SsdpSearch {
+            target: SearchTarget::All,
+            mx: 5
+        })
+    );
+    // Unicast-Suche ohne MX
+    let unicast = "M-SEARCH * HTTP/1.1\r\nMAN: \"ssdp:discover\"\r\nST: ssdp:all\r\n\r\n";
+    assert_eq!(
+        parse_m_search(unicast.as_bytes(), &uuid).map(|search| search.mx),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_search_all_answers_every_notification_type() {
+    assert_eq!(SearchTarget::All.responses(), &NotificationType::ALL);
+    assert_eq!(
+        SearchTarget::Only(NotificationType::Uuid).responses(),
+        &[NotificationType::Uuid]
+    );
+}
+
+#[test]
+fn test_search_response() {
+    let device = device(DeviceUuid::from_mac(MAC));
+    let mut out = String::new();
+    device
+        .write_search_response(&mut out, NotificationType::RootDevice)
+        .unwrap();
+    assert_eq!(
+        out,
+        "HTTP/1.1 200 OK\r\n\
+         CACHE-CONTROL: max-age=1800\r\n\
+         EXT:\r\n\
+         LOCATION: http://192.168.1.42/description.xml\r\n\
+         SERVER: esp-hal/1.0 UPnP/1.1 esp-led-steuerung/0.2.0\r\n\
+         ST: upnp:rootdevice\r\n\
+         USN: uuid:4c454453-7465-7565-7275-404cca0102ab::upnp:rootdevice\r\n\r\n"
+    );
+
+    let mut out = String::new();
+    device
+        .write_search_response(&mut out, NotificationType::Uuid)
+        .unwrap();
+    assert!(out.contains("\r\nST: uuid:4c454453-7465-7565-7275-404cca0102ab\r\n"));
+    assert!(out.ends_with("\r\nUSN: uuid:4c454453-7465-7565-7275-404cca0102ab\r\n\r\n"));
+}
+
+#[test]
+fn test_notify_alive_and_byebye() {
+    let device = device(DeviceUuid::from_mac(MAC));
+    let mut alive = String::new();
+    device
+        .write_notify(&mut alive, NotificationType::DeviceType, true)
+        .unwrap();
+    assert!(alive.starts_with("NOTIFY * HTTP/1.1\r\nHOST: 239.255.255.250:1900\r\n"));
+    assert!(alive.contains("\r\nLOCATION: http://192.168.1.42/description.xml\r\n"));
+    assert!(alive.contains("\r\nNT: urn:schemas-upnp-org:device:Basic:1\r\nNTS: ssdp:alive\r\n"));
+    assert!(alive.ends_with(
+        "USN: uuid:4c454453-7465-7565-7275-404cca0102ab::urn:schemas-upnp-org:device:Basic:1\r\n\r\n"
+    ));
+
+    let mut byebye = String::new();
+    device
+        .write_notify(&mut byebye, NotificationType::RootDevice, false)
+        .unwrap();
+    assert!(byebye.contains("\r\nNTS: ssdp:byebye\r\n"));
+    assert!(!byebye.contains("LOCATION"));
+}
+
+#[test]
+fn test_device_description() {
+    let uuid = DeviceUuid::from_mac(MAC);
+    let description = DeviceDescription {
+        friendly_name: "Lampe <Flur> & Küche",
+        manufacturer: "legacycode-labs",
+        model_name: "ESP32-C6",
+        model_number: "0.2.0",
+        ip: [192, 168, 1, 42],
+    };
+    let mut xml = String::new();
+    write_device_description(&mut xml, &uuid, &description).unwrap();
+    assert!(
+        xml.starts_with(
+            "<?xml version=\"1.0\"?>\n<root xmlns=\"urn:schemas-upnp-org:device-1-0\">"
+        )
+    );
+    assert!(xml.contains("<deviceType>urn:schemas-upnp-org:device:Basic:1</deviceType>"));
+    assert!(xml.contains("<friendlyName>Lampe &lt;Flur&gt; &amp; Küche</friendlyName>"));
+    assert!(xml.contains("<modelNumber>0.2.0</modelNumber>"));
+    assert!(xml.contains("<serialNumber>404cca0102ab</serialNumber>"));
+    assert!(xml.contains("<UDN>uuid:4c454453-7465-7565-7275-404cca0102ab</UDN>"));
+    assert!(xml.contains("<presentationURL>http://192.168.1.42/</presentationURL>"));
+    assert!(xml.ends_with("</device>\n</root>\n"));
+}
@@ -0,0 +1,228 @@
+//! Integration Tests für den LED-Zustandsautomaten
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{Effect, LedCommand, LedState, LedStateConfig};
+use rgb::RGB8;
+
+const CONFIG: LedStateConfig = LedStateConfig {
+    base_brightness: 10,
+    sunrise_max_brightness: 128,
+    sleep_fade_ms: 60_000,
+    brightness_fade_ms: 0,
+};
+
+const RED: RGB8 = RGB8::new(10, 0, 0);
+const GREEN: RGB8 = RGB8::new(0, 10, 0);
+const BLUE: RGB8 = RGB8::new(0, 0, 10);
+
+fn set_color(color: RGB8) -> LedCommand {
+    LedCommand::SetColor {
+        target_color: color,
+        color_id: esp_core::classify_color(color),
+    }
+}
+
+#[test]
+fn test_initial_state_rotates() {
+    let mut state = LedState::new(CONFIG);
+    assert!(state.auto_rotate);
+    assert_eq!(state.color, RED);
+
+    assert!(state.tick(0).color_changed);
+    assert_eq!(state.color, GREEN);
+    assert!(state.message().is_auto_mode);
+    assert!(!state.is_animating(0));
+}
+
+#[test]
+fn test_set_color_switches_to_manual() {
+    let mut state = LedState::new(CONFIG);
+    assert!(state.apply(set_color(BLUE), 0).color_changed);
+    assert!(!state.auto_rotate);
+
+    // Manueller Modus: Tick ändert nichts
+    assert!(!state.tick(1000).color_changed);
+    assert_eq!(state.output(1000), BLUE);
+    assert_eq!(state.message().color_id, esp_core::ColorId::Blue);
+}
+
+#[test]
+fn test_enable_auto_after_off_restarts_with_red() {
+    let mut state = LedState::new(CONFIG);
+    assert!(state.apply(LedCommand::Off, 0).color_changed);
+    assert_eq!(state.output(0), RGB8::default());
+
+    // Modus-Wechsel selbst publiziert nicht, erst der nächste Tick
+    assert!(!state.apply(LedCommand::EnableAuto, 0).color_changed);
+    assert_eq!(state.color, RED);
+    assert!(state.tick(0).color_changed);
+    assert_eq!(state.color, GREEN);
+}
+
+#[test]
+fn test_brightness_keeps_mode_and_timers() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::SleepTimer { minutes: 30 }, 0);
+    let transition = state.apply(LedCommand::SetBrightness { brightness: 128 }, 0);
+
+    assert!(!transition.color_changed);
+    assert!(state.auto_rotate);
+    assert!(state.sleep_timer.is_some());
+    // 128 entspricht wahrgenommen halber Helligkeit (CIE 1931: 47/255)
+    assert_eq!(state.output(0), RGB8::new(2, 0, 0));
+}
+
+#[test]
+fn test_sunrise_runs_and_finishes() {
+    let mut state = LedState::new(CONFIG);
+    assert!(
+        state
+            .apply(LedCommand::Sunrise { duration_secs: 60 }, 0)
+            .color_changed
+    );
+    assert!(matches!(state.effect, Some(Effect::Sunrise(_))));
+    assert!(!state.auto_rotate);
+    assert!(state.is_animating(0));
+
+    let start = state.color;
+    state.tick(30_000);
+    assert_ne!(state.color, start);
+
+    let transition = state.tick(60_000);
+    assert!(transition.effect_finished);
+    assert!(transition.color_changed);
+    assert!(state.effect.is_none());
+    assert!(!state.is_animating(60_000));
+}
+
+#[test]
+fn test_command_cancels_sunrise_and_sleep_timer() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(LedCommand::Sunrise { duration_secs: 60 }, 0);
+    state.apply(LedCommand::SleepTimer { minutes: 5 }, 0);
+    // Sleep-Timer bricht den Sonnenaufgang ab
+    assert!(state.effect.is_none());
+    assert!(state.sleep_timer.is_some());
+
+    state.apply(set_color(RED), 0);
+    assert!(state.sleep_timer.is_none());
+
+    // "sleep 0" bricht nur ab
+    state.apply(LedCommand::SleepTimer { minutes: 5 }, 0);
+    assert!(
+        !state
+            .apply(LedCommand::SleepTimer { minutes: 0 }, 0)
+            .color_changed
+    );
+    assert!(state.sleep_timer.is_none());
+}
+
+#[test]
+fn test_sleep_timer_fades_and_turns_off() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(set_color(RGB8::new(200, 0, 0)), 0);
+    state.apply(LedCommand::SleepTimer { minutes: 2 }, 0);
+
+    // Vor der Ausblendphase unverändert
+    assert_eq!(state.output(30_000), RGB8::new(200, 0, 0));
+    assert!(!state.is_animating(30_000));
+
+    // Mitte der Ausblendphase: halbe Helligkeit
+    assert_eq!(state.output(90_000), RGB8::new(100, 0, 0));
+    assert!(state.is_animating(90_000));
+    assert!(!state.tick(90_000).sleep_expired);
+
+    let transition = state.tick(120_000);
+    assert!(transition.sleep_expired);
+    assert!(transition.color_changed);
+    assert_eq!(state.color, RGB8::default());
+    assert!(!state.auto_rotate);
+    assert!(state.sleep_timer.is_none());
+}
+
+#[test]
+fn test_fade_to_reports_target_and_finishes() {
+    let mut state = LedState::new(CONFIG);
+    state.apply(set_color(RGB8::new(200, 0, 0)), 0);
+
+    let command = set_color(RGB8::new(0, 0, 200)).with_transition(1000);
+    assert!(state.apply(command, 0).color_changed);
+    assert!(!state.auto_rotate);
+    assert!(state.is_animating(0));
+    // Broadcast meldet sofort die Zielfarbe
+    assert_eq!(state.message().color, RGB8::new(0, 0, 200));
+
+    // Halbzeit: Mischfarbe, kein erneuter Broadcast
+    assert!(!state.tick(500).color_changed);
+    assert_eq!(state.color, RGB8::new(100, 0, 100));
+
+    let transition = state.tick(1000);
+    assert!(transition.effect_finished);
+    assert!(!transition.color_changed);
+    assert_eq!(state.color, RGB8::new(0, 0, 200));
+    assert!(!state.is_animating(1000));
+}
+
+#[test]
+fn test_fade_to_zero_duration_is_immediate() {
+    let mut state = LedState::new(CONFIG);
+    let command = LedCommand::FadeTo {
+        target_color: BLUE,
+        color_id: esp_core::ColorId::Blue,
+        transition_ms: 0,
+    };
+    assert!(state.apply(command, 0).color_changed);
+    assert_eq!(state.color, BLUE);
+    assert!(state.effect.is_none());
+
+    // Ausschalten mit Übergang blendet nach Schwarz
+    state.apply(LedCommand::Off.with_transition(200), 0);
+    assert!(matches!(state.effect, Some(Effect::Fade(_))));
+    state.tick(200);
+    assert_eq!(state.color, RGB8::default());
+}
+
+#[test]
+fn test_brightness_ramps_smoothly() {
+    let mut state = LedState::new(LedStateConfig {
+        brightness_fade_ms: 300,
+        ..CONFIG
+    });
+    state.apply(set_color(RGB8::new(255, 255, 255)), 0);
+    state.apply(LedCommand::SetBrightness { brightness: 55 }, 1000);
+
+    // Ziel sofort gemeldet, sichtbar gleitend
+    assert_eq!(state.brightness, 55);
+    assert!(state.is_animating(1000));
+    assert_eq!(state.brightness_at(1000), 255);
+    assert_eq!(state.brightness_at(1150), 155);
+    assert_eq!(state.output(1000), RGB8::new(255, 255, 255));
+    assert!(state.output(1150).r < 255);
+
+    // Neuer Wert mitten in der Rampe startet beim Zwischenwert
+    state.apply(LedCommand::SetBrightness { brightness: 255 }, 1150);
+    assert_eq!(state.brightness_at(1150), 155);
+    assert_eq!(state.brightness_at(1450), 255);
+
+    state.tick(1450);
+    assert!(state.brightness_ramp.is_none());
+    assert!(!state.is_animating(1450));
+    assert_eq!(state.output(1450), RGB8::new(255, 255, 255));
+}
+
+#[test]
+fn test_brightness_ramp_survives_color_commands() {
+    let mut state = LedState::new(LedStateConfig {
+        brightness_fade_ms: 300,
+        ..CONFIG
+    });
+    state.apply(LedCommand::SetBrightness { brightness: 0 }, 0);
+    state.apply(set_color(GREEN), 100);
+    assert!(state.brightness_ramp.is_some());
+    // Gleiche Helligkeit erneut setzen startet keine Rampe
+    state.tick(300);
+    state.apply(LedCommand::SetBrightness { brightness: 0 }, 400);
+    assert!(state.brightness_ramp.is_none());
+    assert_eq!(state.output(400), RGB8::default());
+}
@@ -0,0 +1,112 @@
+//! Integration Tests für die Status-Blinkcodes
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::status_led::STATUS_ERROR_HOLD_MS;
+use esp_core::{
+    ErrorKind, ErrorSource, FirmwareError, StatusCode, StatusIndicator, SystemEvent, TaskId,
+};
+use rgb::RGB8;
+
+const BRIGHTNESS: u8 = 16;
+const OFF: RGB8 = RGB8::new(0, 0, 0);
+
+fn error_event() -> SystemEvent {
+    SystemEvent::Error(FirmwareError::new(
+        ErrorSource::Mqtt,
+        ErrorKind::ConnectionFailed,
+    ))
+}
+
+#[test]
+fn test_connecting_blinks_blue_slowly() {
+    let status = StatusIndicator::new(true);
+    assert_eq!(status.code(0), StatusCode::Connecting);
+    assert_eq!(status.frame(0, BRIGHTNESS), RGB8::new(0, 0, BRIGHTNESS));
+    assert_eq!(status.frame(999, BRIGHTNESS), RGB8::new(0, 0, BRIGHTNESS));
+    assert_eq!(status.frame(1000, BRIGHTNESS), OFF);
+    assert_eq!(status.frame(2000, BRIGHTNESS), RGB8::new(0, 0, BRIGHTNESS));
+}
+
+#[test]
+fn test_online_after_mqtt_connected() {
+    let mut status = StatusIndicator::new(true);
+    status.handle(&SystemEvent::WifiConnected, 0);
+    status.handle(
+        &SystemEvent::IpAcquired {
+            address: [192, 168, 1, 42],
+        },
+        0,
+    );
+    assert_eq!(status.code(0), StatusCode::Connecting);
+
+    status.handle(&SystemEvent::MqttConnected, 0);
+    assert_eq!(status.code(0), StatusCode::Online);
+    assert_eq!(status.frame(1500, BRIGHTNESS), RGB8::new(0, BRIGHTNESS, 0));
+
+    status.handle(&SystemEvent::MqttDisconnected, 0);
+    assert_eq!(status.code(0), StatusCode::Connecting);
+}
+
+#[test]
+fn test_online_with_ip_without_mqtt() {
+    let mut status = StatusIndicator::new(false);
+    status.handle(
+        &SystemEvent::IpAcquired {
+            address: [192, 168, 1, 42],
+        },
+        0,
+    );
+    assert_eq!(status.code(0), StatusCode::Online);
+
+    status.handle(&SystemEvent::WifiDisconnected, 0);
+    assert_eq!(status.code(0), StatusCode::Connecting);
+}
+
+#[test]
+fn test_error_double_blinks_red() {
+    let mut status = StatusIndicator::new(true);
+    status.handle(&SystemEvent::MqttConnected, 0);
+    status.handle(&error_event(), 0);
+    assert_eq!(status.code(0), StatusCode::Error);
+
+    let red = RGB8::new(BRIGHTNESS, 0, 0);
+    let lit: Vec<bool> = [0, 149, 150, 299, 300, 449, 450, 600, 1499, 1500]
+        .iter()
+        .map(|&ms| status.frame(ms, BRIGHTNESS) == red)
+        .collect();
+    assert_eq!(
+        lit,
+        [
+            true, true, false, false, true, true, false, false, false, true
+        ]
+    );
+}
+
+#[test]
+fn test_error_expires_to_connection_state() {
+    let mut status = StatusIndicator::new(true);
+    status.handle(&SystemEvent::MqttConnected, 0);
+    status.handle(&error_event(), 1000);
+    assert_eq!(
+        status.code(1000 + STATUS_ERROR_HOLD_MS - 1),
+        StatusCode::Error
+    );
+    assert_eq!(status.code(1000 + STATUS_ERROR_HOLD_MS), StatusCode::Online);
+}
+
+#[test]
+fn test_task_recovered_clears_error() {
+    let mut status = StatusIndicator::new(true);
+    status.handle(
+        &SystemEvent::TaskStalled {
+            task: TaskId::Mqtt,
+            idle_ms: 90_000,
+        },
+        0,
+    );
+    assert_eq!(status.code(0), StatusCode::Error);
+
+    status.handle(&SystemEvent::TaskRecovered { task: TaskId::Mqtt }, 10);
+    assert_eq!(status.code(10), StatusCode::Connecting);
+}
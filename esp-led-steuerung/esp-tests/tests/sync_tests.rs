@@ -0,0 +1,121 @@
+//! Integration Tests für den Multicast-Sync (Paket-Format, Zeitausgleich)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::sync::{SYNC_PACKET_LEN, SYNC_TIMEOUT_MS};
+use esp_core::{SyncFollower, SyncFrame, SyncPacket, SyncRole};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+
+fn packet(sequence: u32, sent_ms: u64) -> SyncPacket {
+    SyncPacket {
+        sequence,
+        sent_ms,
+        lead_ms: 100,
+        color: RED,
+    }
+}
+
+#[test]
+fn test_packet_roundtrip() {
+    let packet = SyncPacket {
+        sequence: 0x0102_0304,
+        sent_ms: 123_456_789,
+        lead_ms: 250,
+        color: RGB8::new(1, 2, 3),
+    };
+    let bytes = packet.encode();
+    assert_eq!(bytes.len(), SYNC_PACKET_LEN);
+    assert_eq!(&bytes[..5], b"LSYN\x01");
+    assert_eq!(&bytes[5..9], &[1, 2, 3, 4]);
+    assert_eq!(SyncPacket::decode(&bytes), Some(packet));
+}
+
+#[test]
+fn test_decode_rejects_invalid_packets() {
+    let bytes = packet(1, 1000).encode();
+    assert_eq!(SyncPacket::decode(&bytes[..SYNC_PACKET_LEN - 1]), None);
+
+    let mut wrong_magic = bytes;
+    wrong_magic[0] = b'X';
+    assert_eq!(SyncPacket::decode(&wrong_magic), None);
+
+    let mut wrong_version = bytes;
+    wrong_version[4] = 2;
+    assert_eq!(SyncPacket::decode(&wrong_version), None);
+}
+
+#[test]
+fn test_role_from_name() {
+    assert_eq!(SyncRole::from_name("leader"), Some(SyncRole::Leader));
+    assert_eq!(SyncRole::from_name("follower"), Some(SyncRole::Follower));
+    assert_eq!(SyncRole::from_name("off"), Some(SyncRole::Off));
+    assert_eq!(SyncRole::from_name("master"), None);
+}
+
+#[test]
+fn test_follower_converts_to_local_time() {
+    // Follower-Uhr läuft 5000 ms vor, Laufzeit 10 ms
+    let mut follower = SyncFollower::new();
+    let frame = follower.receive(&packet(0, 1000), 6010).unwrap();
+    assert_eq!(follower.offset_ms(), Some(5010));
+    assert_eq!(
+        frame,
+        SyncFrame {
+            show_at_ms: 1000 + 100 + 5010,
+            color: RED,
+        }
+    );
+}
+
+#[test]
+fn test_delayed_packets_do_not_shift_offset() {
+    let mut follower = SyncFollower::new();
+    follower.receive(&packet(0, 1000), 6010);
+    // 40 ms Verzögerung im WLAN: Anzeige bleibt am Leader-Takt ausgerichtet
+    let frame = follower.receive(&packet(1, 1100), 6150).unwrap();
+    assert_eq!(follower.offset_ms(), Some(5011));
+    assert_eq!(frame.show_at_ms, 1100 + 100 + 5011);
+    // Schnelleres Paket verbessert die Schätzung sofort
+    follower.receive(&packet(2, 1200), 6205);
+    assert_eq!(follower.offset_ms(), Some(5005));
+}
+
+#[test]
+fn test_late_frame_is_shown_immediately() {
+    let mut follower = SyncFollower::new();
+    follower.receive(&packet(0, 1000), 1010);
+    // Stark verzögert: Anzeigezeitpunkt liegt bereits zurück
+    let frame = follower.receive(&packet(1, 1100), 1500).unwrap();
+    assert_eq!(frame.show_at_ms, 1500);
+}
+
+#[test]
+fn test_duplicate_and_reordered_packets_are_dropped() {
+    let mut follower = SyncFollower::new();
+    assert!(follower.receive(&packet(5, 1000), 1010).is_some());
+    assert!(follower.receive(&packet(5, 1000), 1020).is_none());
+    assert!(follower.receive(&packet(4, 900), 1030).is_none());
+    assert!(follower.receive(&packet(6, 1100), 1110).is_some());
+    // Überlauf der Sequenznummer
+    let mut follower = SyncFollower::new();
+    follower.receive(&packet(u32::MAX, 1000), 1010);
+    assert!(follower.receive(&packet(0, 1100), 1110).is_some());
+}
+
+#[test]
+fn test_restarted_leader_is_accepted_after_timeout() {
+    let mut follower = SyncFollower::new();
+    follower.receive(&packet(500, 90_000), 100_000);
+    // Leader startet neu: Sequenz und Uhr beginnen wieder bei 0
+    assert!(follower.receive(&packet(0, 50), 100_500).is_none());
+    let frame = follower
+        .receive(&packet(1, 4000), 100_000 + SYNC_TIMEOUT_MS + 1)
+        .unwrap();
+    assert_eq!(
+        follower.offset_ms(),
+        Some(100_000 + SYNC_TIMEOUT_MS as i64 + 1 - 4000)
+    );
+    assert_eq!(frame.color, RED);
+}
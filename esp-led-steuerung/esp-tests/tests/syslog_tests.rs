@@ -0,0 +1,77 @@
+//! Integration Tests für die Syslog-Rahmen der Log-Bridge
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{LogLevel, LogRing, syslog_severity, write_syslog_frame};
+
+/// Syslog-Nachricht der jüngsten Zeile im Ring
+fn last_frame<const N: usize, const LEN: usize>(
+    ring: &LogRing<N, LEN>,
+    boot_unix_ms: Option<u64>,
+) -> String {
+    let line = ring.iter().last().unwrap();
+    let mut out = String::new();
+    write_syslog_frame(&mut out, "led", boot_unix_ms, line).unwrap();
+    out
+}
+
+#[test]
+fn test_frame_without_clock() {
+    let mut ring = LogRing::<4, 64>::new();
+    ring.push(12, LogLevel::Info, format_args!("Boot: v{}", "0.1.0"));
+    assert_eq!(
+        last_frame(&ring, None),
+        "<134>1 - led esp-led - - [meta sequenceId=\"1\" sysUpTime=\"1\"] Boot: v0.1.0"
+    );
+}
+
+#[test]
+fn test_frame_with_clock() {
+    let mut ring = LogRing::<4, 64>::new();
+    ring.push(90_250, LogLevel::Error, format_args!("MQTT: DNS failed"));
+    // 2026-10-15T08:30:00Z = Boot + 90,25 s
+    let boot_unix_ms = 1_792_053_000_000 - 90_250;
+    assert_eq!(
+        last_frame(&ring, Some(boot_unix_ms)),
+        "<131>1 2026-10-15T08:30:00.000Z led esp-led - - \
+         [meta sequenceId=\"1\" sysUpTime=\"9025\"] MQTT: DNS failed"
+    );
+}
+
+#[test]
+fn test_timestamp_fields_padded() {
+    let mut ring = LogRing::<4, 64>::new();
+    ring.push(7, LogLevel::Debug, format_args!("x"));
+    // 2024-02-29T01:02:03.007Z
+    let frame = last_frame(&ring, Some(1_709_168_523_000));
+    assert!(frame.starts_with("<135>1 2024-02-29T01:02:03.007Z led "));
+}
+
+#[test]
+fn test_sequence_id_follows_line_numbers() {
+    let mut ring = LogRing::<2, 32>::new();
+    for i in 0..5 {
+        ring.push(0, LogLevel::Warn, format_args!("line {i}"));
+    }
+    // Überschriebene Zeilen hinterlassen eine Lücke, Nummerierung ab 1
+    let frames: Vec<String> = ring
+        .iter()
+        .map(|line| {
+            let mut out = String::new();
+            write_syslog_frame(&mut out, "led", None, line).unwrap();
+            out
+        })
+        .collect();
+    assert!(frames[0].contains("sequenceId=\"4\""));
+    assert!(frames[0].ends_with("] line 3"));
+    assert!(frames[1].contains("sequenceId=\"5\""));
+}
+
+#[test]
+fn test_severity_mapping() {
+    assert_eq!(syslog_severity(LogLevel::Error), 3);
+    assert_eq!(syslog_severity(LogLevel::Warn), 4);
+    assert_eq!(syslog_severity(LogLevel::Info), 6);
+    assert_eq!(syslog_severity(LogLevel::Debug), 7);
+    assert_eq!(syslog_severity(LogLevel::Trace), 7);
+}
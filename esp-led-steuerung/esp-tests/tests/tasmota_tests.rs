@@ -0,0 +1,68 @@
+//! Integration Tests für die Tasmota-kompatiblen MQTT-Kommandos
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::tasmota::{format_hex_color, power_state, tasmota_command_name};
+use esp_core::{CommandParseError, PowerAction, TasmotaCommand, parse_tasmota_command};
+use rgb::RGB8;
+
+#[test]
+fn test_parse_power_commands() {
+    let power = |payload| parse_tasmota_command("POWER", payload);
+    assert_eq!(power("ON"), Ok(TasmotaCommand::Power(PowerAction::On)));
+    assert_eq!(power("off"), Ok(TasmotaCommand::Power(PowerAction::Off)));
+    assert_eq!(
+        power("Toggle"),
+        Ok(TasmotaCommand::Power(PowerAction::Toggle))
+    );
+    assert_eq!(power("1"), Ok(TasmotaCommand::Power(PowerAction::On)));
+    assert_eq!(power(""), Ok(TasmotaCommand::Power(PowerAction::Query)));
+    assert_eq!(power("dim"), Err(CommandParseError::InvalidArgument));
+
+    // Index 1 und Kleinschreibung wie bei Tasmota
+    assert_eq!(
+        parse_tasmota_command("power1", "on"),
+        Ok(TasmotaCommand::Power(PowerAction::On))
+    );
+}
+
+#[test]
+fn test_parse_color_commands() {
+    let expected = Ok(TasmotaCommand::Color(Some(RGB8::new(255, 16, 0))));
+    assert_eq!(parse_tasmota_command("Color", "FF1000"), expected);
+    assert_eq!(parse_tasmota_command("COLOR1", "#ff1000"), expected);
+    assert_eq!(parse_tasmota_command("Color", "255, 16, 0"), expected);
+    assert_eq!(
+        parse_tasmota_command("Color", ""),
+        Ok(TasmotaCommand::Color(None))
+    );
+
+    for invalid in ["FF10", "GG0000", "1,2", "1,2,3,4", "256,0,0"] {
+        assert_eq!(
+            parse_tasmota_command("Color", invalid),
+            Err(CommandParseError::InvalidArgument),
+            "{invalid}"
+        );
+    }
+    assert_eq!(
+        parse_tasmota_command("Dimmer", "50"),
+        Err(CommandParseError::Unknown)
+    );
+}
+
+#[test]
+fn test_tasmota_command_name() {
+    assert_eq!(tasmota_command_name("cmnd/led/POWER", "led"), Some("POWER"));
+    assert_eq!(tasmota_command_name("cmnd/other/POWER", "led"), None);
+    assert_eq!(tasmota_command_name("cmnd/led2/POWER", "led"), None);
+    assert_eq!(tasmota_command_name("cmnd/led/", "led"), None);
+    assert_eq!(tasmota_command_name("stat/led/POWER", "led"), None);
+}
+
+#[test]
+fn test_format_hex_color_and_power_state() {
+    assert_eq!(&format_hex_color(RGB8::new(255, 16, 0)), b"FF1000");
+    assert_eq!(&format_hex_color(RGB8::new(0, 0, 10)), b"00000A");
+    assert_eq!(power_state(RGB8::default()), "OFF");
+    assert_eq!(power_state(RGB8::new(0, 0, 1)), "ON");
+}
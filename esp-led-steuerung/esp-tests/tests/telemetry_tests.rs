@@ -0,0 +1,283 @@
+//! Integration Tests für Telemetrie-Werte (Heap-Auslastung, Task-Statistik, Stall-Erkennung,
+//! Render-Metriken)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{
+    ErrorSource, FrameTiming, HeapStats, RenderStats, StallChange, StallDetector, SystemStats,
+    TaskDeadline, TaskId, TaskStats,
+};
+
+// ============================================================================
+// Tests: HeapStats
+// ============================================================================
+
+#[test]
+fn test_heap_stats_empty() {
+    let stats = HeapStats::new();
+    assert_eq!(stats.size(), 0);
+    assert_eq!(stats.usage_percent(), 0);
+    assert_eq!(stats.high_water_percent(), 0);
+}
+
+#[test]
+fn test_heap_stats_record() {
+    let mut stats = HeapStats::new();
+    stats.record(25_000, 75_000);
+    assert_eq!(stats.used, 25_000);
+    assert_eq!(stats.free, 75_000);
+    assert_eq!(stats.size(), 100_000);
+    assert_eq!(stats.usage_percent(), 25);
+}
+
+#[test]
+fn test_heap_high_water_keeps_peak() {
+    let mut stats = HeapStats::new();
+    stats.record(80_000, 20_000);
+    stats.record(30_000, 70_000);
+    assert_eq!(stats.used, 30_000);
+    assert_eq!(stats.high_water, 80_000);
+    assert_eq!(stats.usage_percent(), 30);
+    assert_eq!(stats.high_water_percent(), 80);
+}
+
+#[test]
+fn test_heap_percent_rounds_down() {
+    let mut stats = HeapStats::new();
+    stats.record(99_999, 1);
+    assert_eq!(stats.usage_percent(), 99);
+    stats.record(100_000, 0);
+    assert_eq!(stats.usage_percent(), 100);
+}
+
+// ============================================================================
+// Tests: Task-Statistik
+// ============================================================================
+
+#[test]
+fn test_task_stats_start_inactive() {
+    let stats = SystemStats::new();
+    for (task, task_stats) in stats.iter() {
+        assert_eq!(task_stats, TaskStats::new(), "{}", task.name());
+        assert_eq!(task_stats.idle_ms(1_000), None);
+    }
+}
+
+#[test]
+fn test_task_stats_record_iteration() {
+    let mut stats = SystemStats::new();
+    stats.record_iteration(TaskId::Mqtt, 1_000);
+    stats.record_iteration(TaskId::Mqtt, 2_500);
+
+    let mqtt = stats.get(TaskId::Mqtt);
+    assert_eq!(mqtt.iterations, 2);
+    assert_eq!(mqtt.errors, 0);
+    assert_eq!(mqtt.last_activity_ms, Some(2_500));
+    assert_eq!(mqtt.idle_ms(4_000), Some(1_500));
+
+    // Andere Tasks bleiben unberührt
+    assert_eq!(stats.get(TaskId::Led), TaskStats::new());
+}
+
+#[test]
+fn test_task_stats_record_error_counts_as_activity() {
+    let mut stats = SystemStats::new();
+    stats.record_error(TaskId::Sntp, 7_000);
+
+    let sntp = stats.get(TaskId::Sntp);
+    assert_eq!(sntp.errors, 1);
+    assert_eq!(sntp.iterations, 0);
+    assert_eq!(sntp.last_activity_ms, Some(7_000));
+}
+
+#[test]
+fn test_task_index_matches_all() {
+    let mut stats = SystemStats::new();
+    stats.record_iteration(TaskId::Console, 1);
+    for (task, task_stats) in stats.iter() {
+        let expected = u32::from(task == TaskId::Console);
+        assert_eq!(task_stats.iterations, expected, "{}", task.name());
+    }
+    assert_eq!(TaskId::ALL.len(), TaskId::COUNT);
+}
+
+#[test]
+fn test_task_for_error_source() {
+    assert_eq!(TaskId::for_source(ErrorSource::Mqtt), Some(TaskId::Mqtt));
+    assert_eq!(
+        TaskId::for_source(ErrorSource::Storage),
+        Some(TaskId::Scheduler)
+    );
+    assert_eq!(
+        TaskId::for_source(ErrorSource::Schedule),
+        Some(TaskId::Scheduler)
+    );
+    assert_eq!(TaskId::for_source(ErrorSource::Network), None);
+}
+
+#[test]
+fn test_task_parked_until_next_iteration() {
+    let mut stats = SystemStats::new();
+    stats.record_parked(TaskId::Http, 1_000);
+    assert!(stats.get(TaskId::Http).parked);
+    assert_eq!(stats.get(TaskId::Http).iterations, 0);
+
+    stats.record_iteration(TaskId::Http, 2_000);
+    assert!(!stats.get(TaskId::Http).parked);
+}
+
+// ============================================================================
+// Tests: Stall-Erkennung
+// ============================================================================
+
+fn detector() -> StallDetector<2> {
+    StallDetector::new([
+        TaskDeadline {
+            task: TaskId::Led,
+            timeout_ms: 5_000,
+        },
+        TaskDeadline {
+            task: TaskId::Mqtt,
+            timeout_ms: 60_000,
+        },
+    ])
+}
+
+fn check(detector: &mut StallDetector<2>, stats: &SystemStats, now_ms: u64) -> Vec<StallChange> {
+    let mut changes = Vec::new();
+    detector.check(stats, now_ms, |change| changes.push(change));
+    changes
+}
+
+#[test]
+fn test_stall_ignores_tasks_never_active() {
+    let mut detector = detector();
+    let stats = SystemStats::new();
+    assert!(check(&mut detector, &stats, 1_000_000).is_empty());
+}
+
+#[test]
+fn test_stall_detected_once() {
+    let mut detector = detector();
+    let mut stats = SystemStats::new();
+    stats.record_iteration(TaskId::Led, 1_000);
+    stats.record_iteration(TaskId::Mqtt, 1_000);
+
+    assert!(check(&mut detector, &stats, 6_000).is_empty());
+
+    let changes = check(&mut detector, &stats, 6_001);
+    assert_eq!(
+        changes,
+        [StallChange::Stalled {
+            task: TaskId::Led,
+            idle_ms: 5_001
+        }]
+    );
+    assert!(detector.is_stalled(TaskId::Led));
+    assert!(!detector.is_stalled(TaskId::Mqtt));
+
+    // Weiterhin hängend: keine erneute Meldung
+    assert!(check(&mut detector, &stats, 10_000).is_empty());
+}
+
+#[test]
+fn test_stall_recovers_on_heartbeat() {
+    let mut detector = detector();
+    let mut stats = SystemStats::new();
+    stats.record_iteration(TaskId::Led, 0);
+    check(&mut detector, &stats, 10_000);
+
+    stats.record_iteration(TaskId::Led, 11_000);
+    let changes = check(&mut detector, &stats, 11_500);
+    assert_eq!(changes, [StallChange::Recovered { task: TaskId::Led }]);
+    assert!(!detector.is_stalled(TaskId::Led));
+}
+
+#[test]
+fn test_stall_skips_parked_task() {
+    let mut detector = detector();
+    let mut stats = SystemStats::new();
+    stats.record_parked(TaskId::Mqtt, 0);
+    assert!(check(&mut detector, &stats, 3_600_000).is_empty());
+}
+
+#[test]
+fn test_stall_unmonitored_task_ignored() {
+    let mut detector = detector();
+    let mut stats = SystemStats::new();
+    stats.record_iteration(TaskId::Osc, 0);
+    assert!(check(&mut detector, &stats, 3_600_000).is_empty());
+    assert!(!detector.is_stalled(TaskId::Osc));
+}
+
+// ============================================================================
+// Tests: RenderStats
+// ============================================================================
+
+fn frame(render_us: u32, write_us: u32) -> FrameTiming {
+    FrameTiming {
+        render_us,
+        write_us,
+    }
+}
+
+#[test]
+fn test_render_stats_empty() {
+    let stats = RenderStats::<8>::new(1000);
+    let summary = stats.summary();
+    assert!(stats.is_empty());
+    assert_eq!(summary.frames, 0);
+    assert_eq!(summary.render_p50_us, 0);
+    assert_eq!(summary.write_p95_us, 0);
+}
+
+#[test]
+fn test_render_stats_percentiles() {
+    let mut stats = RenderStats::<100>::new(1000);
+    // Aufsteigend 1..=100, in gemischter Reihenfolge
+    for value in (1..=100).rev() {
+        stats.record(frame(value, value * 10));
+    }
+    assert_eq!(stats.render_percentile(50), 50);
+    assert_eq!(stats.render_percentile(95), 95);
+    assert_eq!(stats.write_percentile(50), 500);
+    assert_eq!(stats.write_percentile(100), 1000);
+}
+
+#[test]
+fn test_render_stats_window_drops_oldest() {
+    let mut stats = RenderStats::<4>::new(1000);
+    for _ in 0..4 {
+        stats.record(frame(900, 0));
+    }
+    for _ in 0..4 {
+        stats.record(frame(10, 0));
+    }
+    let summary = stats.summary();
+    assert_eq!(stats.len(), 4);
+    assert_eq!(summary.frames, 8);
+    assert_eq!(summary.render_p95_us, 10);
+}
+
+#[test]
+fn test_render_stats_counts_over_budget() {
+    let mut stats = RenderStats::<4>::new(1000);
+    stats.record(frame(400, 600));
+    stats.record(frame(400, 601));
+    stats.record(frame(2000, 0));
+    assert_eq!(stats.summary().over_budget, 2);
+}
+
+#[test]
+fn test_render_summary_prometheus() {
+    let mut stats = RenderStats::<4>::new(100_000);
+    stats.record(frame(12, 250));
+    let mut out = String::new();
+    stats.summary().write_prometheus(&mut out).unwrap();
+
+    assert!(out.contains("led_frames_total 1\n"));
+    assert!(out.contains("led_frame_budget_us 100000\n"));
+    assert!(out.contains("led_frame_render_us{quantile=\"0.5\"} 12\n"));
+    assert!(out.contains("led_frame_write_us{quantile=\"0.95\"} 250\n"));
+    assert!(out.contains("# TYPE led_frame_write_us summary\n"));
+}
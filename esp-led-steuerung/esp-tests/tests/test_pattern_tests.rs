@@ -0,0 +1,86 @@
+//! Integration Tests für das Testmuster (Lauflicht und R/G/B)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::test_pattern::TEST_PATTERN_STEP_MS;
+use esp_core::{
+    Effect, LedCommand, LedState, LedStateConfig, TestPattern, TestPatternPhase, parse_text_command,
+};
+use rgb::RGB8;
+
+const STEP: u64 = TEST_PATTERN_STEP_MS as u64;
+
+const OFF: RGB8 = RGB8::new(0, 0, 0);
+const WHITE: RGB8 = RGB8::new(255, 255, 255);
+
+#[test]
+fn test_walk_then_fill_phases() {
+    let pattern = TestPattern::new(1000, 255);
+    assert_eq!(pattern.phase_at(1000, 3), TestPatternPhase::Walk(0));
+    assert_eq!(
+        pattern.phase_at(1000 + 2 * STEP, 3),
+        TestPatternPhase::Walk(2)
+    );
+    assert_eq!(
+        pattern.phase_at(1000 + 3 * STEP, 3),
+        TestPatternPhase::Fill(RGB8::new(255, 0, 0))
+    );
+    assert_eq!(
+        pattern.phase_at(1000 + 5 * STEP, 3),
+        TestPatternPhase::Fill(RGB8::new(0, 0, 255))
+    );
+    // Danach wieder von vorne
+    assert_eq!(
+        pattern.phase_at(1000 + 6 * STEP, 3),
+        TestPatternPhase::Walk(0)
+    );
+}
+
+#[test]
+fn test_fill_lights_single_pixel_while_walking() {
+    let pattern = TestPattern::new(0, 255);
+    let mut pixels = [RGB8::default(); 4];
+
+    pattern.fill(STEP, &mut pixels);
+    assert_eq!(pixels, [OFF, WHITE, OFF, OFF]);
+
+    pattern.fill(5 * STEP, &mut pixels);
+    assert_eq!(pixels, [RGB8::new(0, 255, 0); 4]);
+}
+
+#[test]
+fn test_fill_scales_brightness() {
+    let pattern = TestPattern::new(0, 128);
+    let mut pixels = [RGB8::default(); 1];
+    pattern.fill(0, &mut pixels);
+    assert_eq!(pixels, [RGB8::new(128, 128, 128)]);
+
+    // Leerer Strip ist kein Fehler
+    pattern.fill(0, &mut []);
+}
+
+#[test]
+fn test_test_pattern_command_keeps_state() {
+    assert!(matches!(
+        parse_text_command("Testmuster"),
+        Ok(LedCommand::TestPattern)
+    ));
+    assert!(matches!(
+        parse_text_command("testpattern"),
+        Ok(LedCommand::TestPattern)
+    ));
+
+    let mut state = LedState::new(LedStateConfig {
+        base_brightness: 10,
+        sunrise_max_brightness: 128,
+        sleep_fade_ms: 60_000,
+        brightness_fade_ms: 0,
+    });
+    state.apply(LedCommand::Sunrise { duration_secs: 60 }, 0);
+    let before = state;
+
+    // Der LED-Task gibt das Muster selbst aus, der Sonnenaufgang läuft danach weiter
+    assert!(!state.apply(LedCommand::TestPattern, 0).color_changed);
+    assert_eq!(state, before);
+    assert!(matches!(state.effect, Some(Effect::Sunrise(_))));
+}
@@ -0,0 +1,166 @@
+//! Integration Tests für Matrix-Zuordnung, Schrift und Lauftext
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::text::{FONT_HEIGHT, GLYPH_ADVANCE, TEXT_LEN, glyph, text_column, text_width};
+use esp_core::{
+    CommandParseError, CommandQueue, LedCommand, LedState, LedStateConfig, MatrixLayout,
+    PushOutcome, ScrollingText, TextMessage, parse_text_command,
+};
+use rgb::RGB8;
+
+const OFF: RGB8 = RGB8::new(0, 0, 0);
+const ON: RGB8 = RGB8::new(10, 20, 30);
+
+#[test]
+fn test_matrix_index_row_major() {
+    let layout = MatrixLayout::new(4, 2, false);
+    assert_eq!(layout.len(), 8);
+    assert_eq!(layout.index(0, 0), Some(0));
+    assert_eq!(layout.index(3, 0), Some(3));
+    assert_eq!(layout.index(0, 1), Some(4));
+    assert_eq!(layout.index(4, 0), None);
+    assert_eq!(layout.index(0, 2), None);
+}
+
+#[test]
+fn test_matrix_index_serpentine() {
+    // Zweite Zeile läuft von rechts nach links
+    let layout = MatrixLayout::new(4, 3, true);
+    assert_eq!(layout.index(0, 0), Some(0));
+    assert_eq!(layout.index(0, 1), Some(7));
+    assert_eq!(layout.index(3, 1), Some(4));
+    assert_eq!(layout.index(0, 2), Some(8));
+}
+
+#[test]
+fn test_glyph_lookup() {
+    assert_eq!(glyph(' '), [0; 5]);
+    // Kleinbuchstaben und Umlaute werden groß dargestellt
+    assert_eq!(glyph('a'), glyph('A'));
+    assert_eq!(glyph('ö'), glyph('Ö'));
+    assert_ne!(glyph('Ö'), glyph('O'));
+    // Unbekanntes Zeichen → '?'
+    assert_eq!(glyph('€'), glyph('?'));
+    // Kein Zeichen ragt über die Schrifthöhe hinaus
+    for c in ('0'..='9').chain('A'..='Z') {
+        assert!(glyph(c).iter().all(|bits| bits >> FONT_HEIGHT == 0), "{c}");
+    }
+}
+
+#[test]
+fn test_text_columns_include_spacing() {
+    assert_eq!(text_width("HI"), 2 * GLYPH_ADVANCE);
+    assert_eq!(text_width("Ü"), GLYPH_ADVANCE);
+    assert_eq!(text_column("HI", 0), glyph('H')[0]);
+    assert_eq!(text_column("HI", 5), 0);
+    assert_eq!(text_column("HI", 6), glyph('I')[0]);
+    assert_eq!(text_column("HI", 100), 0);
+}
+
+#[test]
+fn test_scroll_offset_wraps_after_full_pass() {
+    let scroll = ScrollingText::new(1000, ON, 100);
+    assert_eq!(scroll.offset_at(1000, 12, 8), 0);
+    assert_eq!(scroll.offset_at(1250, 12, 8), 2);
+    // Text rechts hinein, links hinaus: 12 + 8 Spalten pro Durchlauf
+    assert_eq!(scroll.offset_at(1000 + 20 * 100, 12, 8), 0);
+    // Vor dem Start und bei Schrittweite 0 kein Fehler
+    assert_eq!(scroll.offset_at(0, 12, 8), 0);
+    assert_eq!(ScrollingText::new(0, ON, 0).offset_at(5, 12, 8), 5);
+}
+
+#[test]
+fn test_render_scrolls_in_from_the_right() {
+    let layout = MatrixLayout::new(5, FONT_HEIGHT, false);
+    let scroll = ScrollingText::new(0, ON, 100);
+    let mut pixels = [ON; 5 * FONT_HEIGHT];
+
+    // Zu Beginn ist die Matrix leer
+    scroll.render(0, "I", layout, &mut pixels);
+    assert!(pixels.iter().all(|&p| p == OFF));
+
+    // Nach 3 Spalten: Spalten 0-2 des 'I' stehen rechts (x = 2..5)
+    scroll.render(300, "I", layout, &mut pixels);
+    let lit = |x: usize, y: usize| pixels[layout.index(x, y).unwrap()] == ON;
+    for y in 0..FONT_HEIGHT {
+        assert!(!lit(0, y) && !lit(1, y));
+        assert_eq!(lit(4, y), glyph('I')[2] & (1 << y) != 0, "y={y}");
+    }
+    // Mittlere Spalte des 'I' ist durchgehend
+    assert!((0..FONT_HEIGHT).all(|y| lit(4, y)));
+}
+
+#[test]
+fn test_render_respects_serpentine_wiring() {
+    let layout = MatrixLayout::new(5, FONT_HEIGHT, true);
+    let scroll = ScrollingText::new(0, ON, 100);
+    let mut pixels = [OFF; 5 * FONT_HEIGHT];
+    scroll.render(300, "I", layout, &mut pixels);
+    // Zeile 1 läuft rückwärts: Spalte 4 ist Pixel 5
+    assert_eq!(pixels[5], ON);
+    assert_eq!(pixels[9], OFF);
+}
+
+#[test]
+fn test_render_single_row_shows_middle_of_font() {
+    // Strip als Matrix mit einer Zeile: zeigt die mittlere Schriftzeile
+    let layout = MatrixLayout::new(6, 1, false);
+    let scroll = ScrollingText::new(0, ON, 100);
+    let mut pixels = [OFF; 6];
+    scroll.render(6 * 100, "-", layout, &mut pixels);
+    assert_eq!(pixels, [ON, ON, ON, ON, ON, OFF]);
+
+    // Zu kleiner Puffer ist kein Fehler
+    scroll.render(600, "-", layout, &mut pixels[..2]);
+}
+
+#[test]
+fn test_text_message_limits() {
+    assert_eq!(TextMessage::new("Hallo").unwrap().as_str(), "Hallo");
+    assert!(TextMessage::new("").is_none());
+    assert!(TextMessage::new(&"x".repeat(TEXT_LEN)).is_some());
+    assert!(TextMessage::new(&"x".repeat(TEXT_LEN + 1)).is_none());
+}
+
+#[test]
+fn test_parse_text_command_keeps_spaces() {
+    match parse_text_command("Text  Hallo Welt! ") {
+        Ok(LedCommand::ScrollText { text }) => assert_eq!(text.as_str(), "Hallo Welt!"),
+        _ => panic!("ScrollText erwartet"),
+    }
+    assert!(matches!(
+        parse_text_command("text"),
+        Err(CommandParseError::InvalidArgument)
+    ));
+    assert!(matches!(
+        parse_text_command(&format!("text {}", "x".repeat(TEXT_LEN + 1))),
+        Err(CommandParseError::InvalidArgument)
+    ));
+}
+
+#[test]
+fn test_scroll_text_keeps_state_and_supersedes_queued_text() {
+    let text = |s: &str| LedCommand::ScrollText {
+        text: TextMessage::new(s).unwrap(),
+    };
+
+    let mut state = LedState::new(LedStateConfig {
+        base_brightness: 10,
+        sunrise_max_brightness: 128,
+        sleep_fade_ms: 60_000,
+        brightness_fade_ms: 0,
+    });
+    let before = state;
+    assert!(!state.apply(text("Hallo"), 0).color_changed);
+    assert_eq!(state, before);
+
+    // Nur die neueste wartende Nachricht zählt
+    let mut queue = CommandQueue::<4>::new();
+    queue.push(text("alt"));
+    assert!(matches!(queue.push(text("neu")), PushOutcome::Replaced));
+    match queue.pop() {
+        Some(LedCommand::ScrollText { text }) => assert_eq!(text.as_str(), "neu"),
+        _ => panic!("ScrollText erwartet"),
+    }
+}
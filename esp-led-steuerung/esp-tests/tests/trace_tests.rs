@@ -0,0 +1,80 @@
+//! Integration Tests für das Ablauf-Tracing
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::{Span, SpanKind, SpanStats, TraceRing};
+
+fn span(kind: SpanKind, start_us: u64, end_us: u64) -> Span {
+    Span {
+        kind,
+        start_us,
+        end_us,
+    }
+}
+
+#[test]
+fn test_iter_oldest_first() {
+    let mut ring: TraceRing<4> = TraceRing::new();
+    assert!(ring.is_empty());
+    ring.push(span(SpanKind::LedFrame, 0, 10));
+    ring.push(span(SpanKind::MqttPublish, 5, 40));
+    ring.push(span(SpanKind::LedFrame, 20, 30));
+    assert_eq!(ring.len(), 3);
+    let starts: Vec<u64> = ring.iter().map(|span| span.start_us).collect();
+    assert_eq!(starts, [0, 5, 20]);
+    assert_eq!(ring.iter().next_back().unwrap().start_us, 20);
+}
+
+#[test]
+fn test_overwrites_oldest() {
+    let mut ring: TraceRing<3> = TraceRing::new();
+    for start in 0..5 {
+        ring.push(span(SpanKind::Realtime, start * 100, start * 100 + 1));
+    }
+    assert_eq!(ring.len(), 3);
+    let starts: Vec<u64> = ring.iter().map(|span| span.start_us).collect();
+    assert_eq!(starts, [200, 300, 400]);
+}
+
+#[test]
+fn test_summary_includes_overwritten() {
+    let mut ring: TraceRing<2> = TraceRing::new();
+    ring.push(span(SpanKind::WsSend, 0, 300));
+    ring.push(span(SpanKind::WsSend, 1_000, 1_100));
+    ring.push(span(SpanKind::WsSend, 2_000, 2_200));
+    ring.push(span(SpanKind::Console, 3_000, 3_050));
+
+    let summary = ring.summary();
+    let ws = summary[SpanKind::WsSend as usize];
+    assert_eq!((ws.count, ws.total_us, ws.max_us), (3, 600, 300));
+    assert_eq!(ws.avg_us(), 200);
+    assert_eq!(summary[SpanKind::Console as usize].count, 1);
+    assert_eq!(summary[SpanKind::LedFrame as usize], SpanStats::default());
+}
+
+#[test]
+fn test_clear() {
+    let mut ring: TraceRing<4> = TraceRing::new();
+    ring.push(span(SpanKind::MqttCommand, 0, 50));
+    ring.clear();
+    assert!(ring.is_empty());
+    assert_eq!(ring.iter().count(), 0);
+    assert_eq!(ring.summary()[SpanKind::MqttCommand as usize].count, 0);
+}
+
+#[test]
+fn test_duration_and_avg_without_spans() {
+    // Austritt vor Eintritt (Zeitquelle gesprungen) ergibt 0 statt Überlauf
+    assert_eq!(span(SpanKind::WsCommand, 50, 10).duration_us(), 0);
+    assert_eq!(SpanStats::default().avg_us(), 0);
+}
+
+#[test]
+fn test_all_in_index_order() {
+    for (index, kind) in SpanKind::ALL.into_iter().enumerate() {
+        assert_eq!(kind as usize, index);
+    }
+    let names: Vec<&str> = SpanKind::ALL.iter().map(SpanKind::name).collect();
+    assert_eq!(names.len(), SpanKind::COUNT);
+    assert!(names.contains(&"led_frame"));
+}
@@ -0,0 +1,177 @@
+//! Integration Tests für Farbtemperatur, Transition-Engine und Sonnenaufgang
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::color::{MAX_KELVIN, MIN_KELVIN};
+use esp_core::schedule::ALL_DAYS;
+use esp_core::{
+    BrightnessRamp, LedCommand, Schedule, ScheduleAction, ScheduleEntry, SleepTimer, Sunrise,
+    Transition, color_temperature, lerp_color, scale_brightness,
+};
+use rgb::RGB8;
+
+// ============================================================================
+// Tests: Farb-Hilfsfunktionen
+// ============================================================================
+
+#[test]
+fn test_color_temperature_clamps_range() {
+    assert_eq!(color_temperature(0), color_temperature(MIN_KELVIN));
+    assert_eq!(color_temperature(20_000), color_temperature(MAX_KELVIN));
+}
+
+#[test]
+fn test_color_temperature_interpolates_between_steps() {
+    // 2750 K liegt genau zwischen 2500 K (255,161,72) und 3000 K (255,180,107)
+    assert_eq!(color_temperature(2750), RGB8::new(255, 170, 89));
+}
+
+#[test]
+fn test_scale_brightness() {
+    let white = RGB8::new(255, 255, 255);
+    assert_eq!(scale_brightness(white, 0), RGB8::new(0, 0, 0));
+    assert_eq!(scale_brightness(white, 255), white);
+    assert_eq!(
+        scale_brightness(RGB8::new(200, 100, 0), 128),
+        RGB8::new(100, 50, 0)
+    );
+}
+
+#[test]
+fn test_lerp_color_endpoints_and_midpoint() {
+    let from = RGB8::new(0, 100, 200);
+    let to = RGB8::new(200, 100, 0);
+    assert_eq!(lerp_color(from, to, 0), from);
+    assert_eq!(lerp_color(from, to, 500), RGB8::new(100, 100, 100));
+    assert_eq!(lerp_color(from, to, 5000), to);
+}
+
+// ============================================================================
+// Tests: Transition
+// ============================================================================
+
+#[test]
+fn test_transition_progress() {
+    let t = Transition::new(RGB8::new(0, 0, 0), RGB8::new(100, 0, 0), 1000, 2000);
+    assert_eq!(t.color_at(0), RGB8::new(0, 0, 0)); // vor dem Start
+    assert_eq!(t.color_at(2000), RGB8::new(50, 0, 0));
+    assert!(!t.is_finished(2999));
+    assert!(t.is_finished(3000));
+    assert_eq!(t.color_at(10_000), RGB8::new(100, 0, 0));
+}
+
+#[test]
+fn test_transition_zero_duration_is_immediate() {
+    let t = Transition::new(RGB8::new(0, 0, 0), RGB8::new(9, 9, 9), 500, 0);
+    assert!(t.is_finished(0));
+    assert_eq!(t.color_at(0), RGB8::new(9, 9, 9));
+}
+
+#[test]
+fn test_brightness_ramp() {
+    let ramp = BrightnessRamp::new(200, 100, 1000, 300);
+    assert_eq!(ramp.level_at(0), 200);
+    assert_eq!(ramp.level_at(1150), 150);
+    assert!(!ramp.is_finished(1299));
+    assert_eq!(ramp.level_at(1300), 100);
+    assert!(ramp.is_finished(1300));
+
+    let up = BrightnessRamp::new(0, 255, 0, 0);
+    assert!(up.is_finished(0));
+    assert_eq!(up.level_at(0), 255);
+}
+
+// ============================================================================
+// Tests: Sonnenaufgang
+// ============================================================================
+
+#[test]
+fn test_sunrise_starts_dark_red_and_ends_warm_white() {
+    let sunrise = Sunrise::new(0, 60_000, 255);
+    let start = sunrise.color_at(0);
+    assert!(start.r > 0 && start.g == 0 && start.b == 0);
+    assert!(start.r < 10);
+
+    let end = sunrise.color_at(60_000);
+    assert_eq!(end, color_temperature(3000));
+    assert_eq!(end, sunrise.final_color());
+    assert!(sunrise.is_finished(60_000));
+}
+
+#[test]
+fn test_sunrise_brightness_increases_monotonically() {
+    let sunrise = Sunrise::new(0, 100_000, 200);
+    let mut last = 0u16;
+    for step in 0..=100 {
+        let c = sunrise.color_at(step * 1000);
+        let sum = c.r as u16 + c.g as u16 + c.b as u16;
+        assert!(sum >= last, "brightness dropped at step {}", step);
+        last = sum;
+    }
+}
+
+#[test]
+fn test_sunrise_schedule_action() {
+    let mut schedule = Schedule::<2>::new();
+    let entry = ScheduleEntry {
+        id: 0,
+        days: ALL_DAYS,
+        hour: 6,
+        minute: 30,
+        action: ScheduleAction::Sunrise { duration_min: 20 },
+        enabled: true,
+    };
+    schedule.add(entry).unwrap();
+
+    // Roundtrip über die Flash-Serialisierung
+    let mut buffer = [0u8; 32];
+    let len = schedule.encode(&mut buffer).unwrap();
+    let decoded = Schedule::<2>::decode(&buffer[..len]);
+    assert!(decoded.entries().eq(schedule.entries()));
+
+    match LedCommand::from(ScheduleAction::Sunrise { duration_min: 20 }) {
+        LedCommand::Sunrise { duration_secs } => assert_eq!(duration_secs, 1200),
+        _ => panic!("Expected Sunrise variant"),
+    }
+
+    // Dauer 0 ist ungültig
+    let mut invalid = entry;
+    invalid.action = ScheduleAction::Sunrise { duration_min: 0 };
+    assert!(!invalid.is_valid());
+}
+
+// ============================================================================
+// Tests: Sleep-Timer
+// ============================================================================
+
+#[test]
+fn test_sleep_timer_keeps_color_until_fade() {
+    let timer = SleepTimer::new(0, 600_000, 60_000);
+    let color = RGB8::new(100, 50, 0);
+    assert_eq!(timer.apply(color, 0), color);
+    assert_eq!(timer.apply(color, 539_999), color);
+    assert!(!timer.is_fading(539_999));
+    assert_eq!(timer.remaining_ms(100_000), 500_000);
+}
+
+#[test]
+fn test_sleep_timer_fades_to_black() {
+    let timer = SleepTimer::new(0, 600_000, 60_000);
+    let color = RGB8::new(100, 50, 0);
+    assert!(timer.is_fading(570_000));
+    assert_eq!(timer.apply(color, 570_000), RGB8::new(50, 25, 0));
+    assert!(timer.is_finished(600_000));
+    assert_eq!(timer.apply(color, 600_000), RGB8::new(0, 0, 0));
+}
+
+#[test]
+fn test_sleep_timer_short_duration_limits_fade() {
+    // 30 s Timer mit 60 s Ausblendzeit → blendet sofort über 30 s aus
+    let timer = SleepTimer::new(1000, 30_000, 60_000);
+    assert_eq!(timer.fade_ms, 30_000);
+    assert!(timer.is_fading(1000));
+    assert_eq!(
+        timer.apply(RGB8::new(100, 0, 0), 16_000),
+        RGB8::new(50, 0, 0)
+    );
+}
@@ -0,0 +1,128 @@
+//! Integration Tests für die Web-UI im Flash
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::record::crc32;
+use esp_core::ui_bundle::{UI_BUNDLE_HEADER_LEN, sectors_needed};
+use esp_core::{Crc32, UiBundleHeader, UiEncoding, UiUpload, UiUploadError};
+
+const CAPACITY: usize = 0x2_0000 - UI_BUNDLE_HEADER_LEN;
+
+#[test]
+fn test_crc32_incremental_matches_oneshot() {
+    let data = b"<!DOCTYPE html><html><body>LED</body></html>";
+    let mut crc = Crc32::new();
+    for chunk in data.chunks(7) {
+        crc.update(chunk);
+    }
+    assert_eq!(crc.finish(), crc32(data));
+    // Bekannter Prüfwert (IEEE 802.3)
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+}
+
+#[test]
+fn test_encoding_from_header() {
+    assert_eq!(UiEncoding::from_header(None), Some(UiEncoding::Identity));
+    assert_eq!(
+        UiEncoding::from_header(Some(b"identity")),
+        Some(UiEncoding::Identity)
+    );
+    assert_eq!(
+        UiEncoding::from_header(Some(b" GZIP ")),
+        Some(UiEncoding::Gzip)
+    );
+    assert_eq!(UiEncoding::from_header(Some(b"br")), None);
+    assert_eq!(UiEncoding::Gzip.content_encoding(), Some("gzip"));
+    assert_eq!(UiEncoding::Identity.content_encoding(), None);
+}
+
+#[test]
+fn test_header_roundtrip() {
+    let header = UiBundleHeader {
+        encoding: UiEncoding::Gzip,
+        len: 12_345,
+        crc: 0xDEAD_BEEF,
+    };
+    let bytes = header.encode();
+    assert_eq!(&bytes[0..4], b"LEDU");
+    assert_eq!(UiBundleHeader::decode(&bytes, CAPACITY), Some(header));
+    assert!(header.verify(0xDEAD_BEEF));
+    assert!(!header.verify(0));
+}
+
+#[test]
+fn test_header_rejects_erased_and_oversized() {
+    // Gelöschter Flash bzw. abgebrochener Upload
+    assert_eq!(
+        UiBundleHeader::decode(&[0xFF; UI_BUNDLE_HEADER_LEN], CAPACITY),
+        None
+    );
+    // Zu kurz
+    assert_eq!(UiBundleHeader::decode(b"LEDU", CAPACITY), None);
+
+    let header = UiBundleHeader {
+        encoding: UiEncoding::Identity,
+        len: 5_000,
+        crc: 1,
+    };
+    assert_eq!(UiBundleHeader::decode(&header.encode(), 4_096), None);
+
+    let mut bytes = header.encode();
+    bytes[4] = 2; // unbekannte Version
+    assert_eq!(UiBundleHeader::decode(&bytes, CAPACITY), None);
+}
+
+#[test]
+fn test_upload_in_chunks() {
+    let data = b"<!DOCTYPE html><title>LED</title>";
+    let mut upload = UiUpload::new(UiEncoding::Identity, data.len(), CAPACITY).unwrap();
+    for chunk in data.chunks(8) {
+        upload.push(chunk).unwrap();
+    }
+    assert_eq!(upload.received(), data.len());
+    assert_eq!(upload.remaining(), 0);
+    let header = upload.finish().unwrap();
+    assert_eq!(header.len as usize, data.len());
+    assert_eq!(header.crc, crc32(data));
+    assert_eq!(header.encoding, UiEncoding::Identity);
+}
+
+#[test]
+fn test_upload_rejects_bad_sizes() {
+    assert_eq!(
+        UiUpload::new(UiEncoding::Identity, 0, CAPACITY).unwrap_err(),
+        UiUploadError::Empty
+    );
+    assert_eq!(
+        UiUpload::new(UiEncoding::Identity, CAPACITY + 1, CAPACITY).unwrap_err(),
+        UiUploadError::TooLarge
+    );
+
+    let mut upload = UiUpload::new(UiEncoding::Identity, 4, CAPACITY).unwrap();
+    upload.push(b"<ht").unwrap();
+    // Body endet zu früh
+    assert_eq!(upload.finish().unwrap_err(), UiUploadError::LengthMismatch);
+    // Mehr als angekündigt
+    assert_eq!(
+        upload.push(b"ml").unwrap_err(),
+        UiUploadError::LengthMismatch
+    );
+}
+
+#[test]
+fn test_upload_checks_gzip_magic() {
+    let mut upload = UiUpload::new(UiEncoding::Gzip, 6, CAPACITY).unwrap();
+    assert_eq!(upload.push(b"<html>").unwrap_err(), UiUploadError::NotGzip);
+
+    let mut upload = UiUpload::new(UiEncoding::Gzip, 6, CAPACITY).unwrap();
+    upload.push(&[0x1F, 0x8B, 0x08, 0x00, 0x00, 0x00]).unwrap();
+    assert_eq!(upload.finish().unwrap().encoding, UiEncoding::Gzip);
+}
+
+#[test]
+fn test_sectors_needed() {
+    assert_eq!(sectors_needed(1, 4096), 1);
+    assert_eq!(sectors_needed(4096 - UI_BUNDLE_HEADER_LEN, 4096), 1);
+    assert_eq!(sectors_needed(4096 - UI_BUNDLE_HEADER_LEN + 1, 4096), 2);
+    assert_eq!(sectors_needed(CAPACITY, 4096), 32);
+}
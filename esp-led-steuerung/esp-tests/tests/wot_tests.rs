@@ -0,0 +1,186 @@
+//! Integration Tests für die Web-of-Things-API (Thing Description, Properties, Actions)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::wot::{
+    WotAction, WotError, WotProperty, action_command, property_command, write_properties,
+    write_property_value, write_thing_description,
+};
+use esp_core::{ColorId, LedColorMessage, LedCommand, classify_color};
+use rgb::RGB8;
+
+const RED: RGB8 = RGB8::new(255, 0, 0);
+
+fn state(color: RGB8, auto: bool) -> LedColorMessage {
+    LedColorMessage::from_color(color, auto)
+}
+
+fn set_color(color: RGB8) -> LedCommand {
+    LedCommand::SetColor {
+        target_color: color,
+        color_id: classify_color(color),
+    }
+}
+
+#[test]
+fn test_names_roundtrip() {
+    for property in WotProperty::ALL {
+        assert_eq!(WotProperty::from_name(property.name()), Some(property));
+    }
+    for action in WotAction::ALL {
+        assert_eq!(WotAction::from_name(action.name()), Some(action));
+    }
+    assert_eq!(WotProperty::from_name("brightness"), None);
+    assert_eq!(WotAction::from_name("on"), None);
+    assert_eq!("color".parse::<WotProperty>(), Ok(WotProperty::Color));
+    assert_eq!("fade".parse::<WotAction>(), Ok(WotAction::Fade));
+}
+
+#[test]
+fn test_thing_description_lists_all_endpoints() {
+    let mut td = String::new();
+    write_thing_description(&mut td, "led", "Lampe \"Flur\"").unwrap();
+    assert!(td.starts_with("{\"@context\":[\"https://www.w3.org/2022/wot/td/v1.1\""));
+    assert!(td.ends_with('}'));
+    assert!(td.contains("\"id\":\"urn:dev:ops:led\""));
+    assert!(td.contains("\"title\":\"Lampe \\\"Flur\\\"\""));
+    assert!(td.contains("\"security\":\"nosec_sc\""));
+    for property in WotProperty::ALL {
+        let href = format!("\"href\":\"/things/led/properties/{}\"", property.name());
+        assert!(td.contains(&href), "{}", href);
+    }
+    for action in WotAction::ALL {
+        let href = format!("\"href\":\"/things/led/actions/{}\"", action.name());
+        assert!(td.contains(&href), "{}", href);
+    }
+    assert_eq!(td.matches('{').count(), td.matches('}').count());
+    assert_eq!(td.matches('[').count(), td.matches(']').count());
+}
+
+#[test]
+fn test_property_values() {
+    let current = state(RGB8::new(255, 0, 16), false);
+    let mut out = String::new();
+    write_properties(&mut out, &current).unwrap();
+    assert_eq!(out, r##"{"on":true,"color":"#ff0010","auto":false}"##);
+
+    let mut out = String::new();
+    write_property_value(&mut out, WotProperty::On, &state(RGB8::default(), false)).unwrap();
+    assert_eq!(out, "false");
+
+    let mut out = String::new();
+    write_property_value(&mut out, WotProperty::Auto, &state(RED, true)).unwrap();
+    assert_eq!(out, "true");
+}
+
+#[test]
+fn test_write_on_accepts_plain_and_object_form() {
+    let off = state(RGB8::default(), false);
+    let on = state(RED, false);
+    assert_eq!(
+        property_command(WotProperty::On, b"true", &off),
+        Ok(Some(LedCommand::EnableAuto))
+    );
+    assert_eq!(
+        property_command(WotProperty::On, br#"{"on":false}"#, &on),
+        Ok(Some(LedCommand::Off))
+    );
+    // Bereits im gewünschten Zustand
+    assert_eq!(property_command(WotProperty::On, b"true", &on), Ok(None));
+    assert_eq!(
+        property_command(WotProperty::On, b"\"on\"", &on),
+        Err(WotError::InvalidValue)
+    );
+}
+
+#[test]
+fn test_write_color() {
+    let current = state(RED, true);
+    assert_eq!(
+        property_command(WotProperty::Color, br##""#0000ff""##, &current),
+        Ok(Some(set_color(RGB8::new(0, 0, 255))))
+    );
+    assert_eq!(
+        property_command(WotProperty::Color, br##"{"color":"#102030"}"##, &current),
+        Ok(Some(set_color(RGB8::new(16, 32, 48))))
+    );
+    assert_eq!(
+        property_command(WotProperty::Color, br##""#000000""##, &current),
+        Ok(Some(LedCommand::Off))
+    );
+    assert_eq!(
+        property_command(WotProperty::Color, br#""0000ff""#, &current),
+        Err(WotError::InvalidColor)
+    );
+    assert_eq!(
+        property_command(WotProperty::Color, br##""#1,2,3""##, &current),
+        Err(WotError::InvalidColor)
+    );
+}
+
+#[test]
+fn test_write_auto() {
+    assert_eq!(
+        property_command(WotProperty::Auto, b"true", &state(RED, false)),
+        Ok(Some(LedCommand::EnableAuto))
+    );
+    // Auto aus: aktuelle Farbe festhalten
+    assert_eq!(
+        property_command(WotProperty::Auto, br#"{"auto":false}"#, &state(RED, true)),
+        Ok(Some(set_color(RED)))
+    );
+    assert_eq!(
+        property_command(WotProperty::Auto, b"false", &state(RED, false)),
+        Ok(None)
+    );
+}
+
+#[test]
+fn test_fade_action() {
+    let expected = LedCommand::FadeTo {
+        target_color: RGB8::new(0, 0, 255),
+        color_id: ColorId::Blue,
+        transition_ms: 1500,
+    };
+    assert_eq!(
+        action_command(
+            WotAction::Fade,
+            br##"{"color":"#0000ff","transition_ms":1500}"##
+        ),
+        Ok(expected)
+    );
+    assert_eq!(
+        action_command(
+            WotAction::Fade,
+            br##"{"fade":{"input":{"color":"#0000ff","transition_ms":1500}}}"##
+        ),
+        Ok(expected)
+    );
+    assert!(matches!(
+        action_command(WotAction::Fade, br##"{"color":"#0000ff"}"##),
+        Ok(LedCommand::FadeTo {
+            transition_ms: 0,
+            ..
+        })
+    ));
+    assert_eq!(
+        action_command(WotAction::Fade, br#"{"transition_ms":1500}"#),
+        Err(WotError::InvalidValue)
+    );
+}
+
+#[test]
+fn test_command_action() {
+    assert_eq!(
+        action_command(WotAction::Command, br#""auto""#),
+        Ok(LedCommand::EnableAuto)
+    );
+    assert_eq!(
+        action_command(WotAction::Command, br#"{"command":{"input":"sunrise 10"}}"#),
+        Ok(LedCommand::Sunrise { duration_secs: 600 })
+    );
+    assert_eq!(
+        action_command(WotAction::Command, br#""tanzen""#),
+        Err(WotError::InvalidCommand)
+    );
+}
@@ -0,0 +1,278 @@
+//! Integration Tests für die WebSocket-Kompression (permessage-deflate)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::ws_deflate::{
+    DeflateParams, Deflater, FrameHeader, InflateError, OPCODE_TEXT, RequestScanner,
+    ResponseScanner, apply_mask, inflate_message, negotiate_permessage_deflate, parse_frame_header,
+    write_frame_header,
+};
+
+const STATUS: &[u8] = br#"{"type":"status","color":"red","label":"Rot","rgb":{"r":255,"g":0,"b":0},"timestamp_ms":123456,"mode":"auto","seq":42}"#;
+
+/// `{"type":"set_color","color":"Blau","transition_ms":1500}` von zlib (feste Codes)
+const FIXED_BLOCK: [u8; 52] = [
+    0xaa, 0x56, 0x2a, 0xa9, 0x2c, 0x48, 0x55, 0xb2, 0x52, 0x2a, 0x4e, 0x2d, 0x89, 0x4f, 0xce, 0xcf,
+    0xc9, 0x2f, 0x52, 0xd2, 0x51, 0x82, 0xd0, 0x56, 0x4a, 0x4e, 0x39, 0x89, 0xa5, 0x40, 0x6e, 0x49,
+    0x51, 0x62, 0x5e, 0x71, 0x66, 0x49, 0x66, 0x7e, 0x5e, 0x7c, 0x6e, 0xb1, 0x92, 0x95, 0xa1, 0xa9,
+    0x81, 0x41, 0x2d, 0x00,
+];
+
+/// `STATUS` dreimal hintereinander von zlib (dynamische Codes)
+const DYNAMIC_BLOCK: [u8; 107] = [
+    0xe4, 0x8d, 0x41, 0x0e, 0x83, 0x30, 0x0c, 0x04, 0xff, 0xb2, 0x67, 0x1f, 0x68, 0x4a, 0x7a, 0xf0,
+    0x33, 0xfa, 0x81, 0x2a, 0x80, 0x85, 0x90, 0x12, 0x99, 0x26, 0xe6, 0x80, 0x22, 0xfe, 0x5e, 0xf7,
+    0x1d, 0x1c, 0x67, 0x56, 0xbb, 0xdb, 0x61, 0xe7, 0x2e, 0x60, 0x34, 0x4b, 0x76, 0x34, 0x10, 0x66,
+    0xcd, 0x5a, 0x5d, 0x54, 0x59, 0x9c, 0x72, 0x9a, 0x24, 0x3b, 0xbd, 0xd5, 0x9c, 0xea, 0x3a, 0x81,
+    0x3b, 0x3c, 0x0e, 0x31, 0x12, 0x56, 0xf0, 0x40, 0x70, 0x35, 0x5c, 0x04, 0xdb, 0x8a, 0xf8, 0x48,
+    0xd9, 0x3f, 0xa5, 0x81, 0x1f, 0xe1, 0x39, 0xc6, 0x17, 0xa1, 0xe8, 0xf2, 0x5f, 0x4f, 0x87, 0xa9,
+    0xf7, 0x9b, 0x7c, 0xc1, 0x63, 0xb8, 0xfa, 0x8d, 0x5e, 0x7f, 0x00,
+];
+
+fn response(params: DeflateParams) -> String {
+    let mut out = String::new();
+    params.write_response(&mut out).unwrap();
+    out
+}
+
+fn inflate(input: &[u8]) -> Vec<u8> {
+    let mut out = [0u8; 1024];
+    let len = inflate_message(input, &mut out).unwrap();
+    out[..len].to_vec()
+}
+
+fn default_params() -> DeflateParams {
+    negotiate_permessage_deflate("permessage-deflate").unwrap()
+}
+
+#[test]
+fn test_negotiate_browser_offer() {
+    // Chrome und Firefox
+    let params =
+        negotiate_permessage_deflate("permessage-deflate; client_max_window_bits").unwrap();
+    assert!(!params.server_no_context_takeover);
+    assert_eq!(params.server_max_window_bits, 15);
+    assert_eq!(
+        response(params),
+        "permessage-deflate; client_no_context_takeover"
+    );
+}
+
+#[test]
+fn test_negotiate_server_parameters() {
+    let params = negotiate_permessage_deflate(
+        "permessage-deflate; server_no_context_takeover; server_max_window_bits=\"10\"",
+    )
+    .unwrap();
+    assert_eq!(
+        response(params),
+        "permessage-deflate; server_no_context_takeover; server_max_window_bits=10; client_no_context_takeover"
+    );
+}
+
+#[test]
+fn test_negotiate_rejects_unsupported() {
+    assert_eq!(negotiate_permessage_deflate(""), None);
+    assert_eq!(negotiate_permessage_deflate("x-webkit-deflate-frame"), None);
+    assert_eq!(
+        negotiate_permessage_deflate("permessage-deflate; server_max_window_bits=7"),
+        None
+    );
+    assert_eq!(
+        negotiate_permessage_deflate("permessage-deflate; unknown_param"),
+        None
+    );
+    // Nächstes Angebot, wenn das erste nicht passt
+    assert_eq!(
+        negotiate_permessage_deflate(
+            "permessage-deflate; unknown_param, permessage-deflate; client_max_window_bits=12"
+        ),
+        Some(default_params())
+    );
+}
+
+#[test]
+fn test_request_scanner_finds_offer() {
+    let request = b"GET /ws HTTP/1.1\r\nHost: led.local\r\nUpgrade: websocket\r\n\
+sec-websocket-extensions: permessage-deflate; client_max_window_bits\r\n\r\n";
+    // Byteweise wie aus einem langsamen Socket
+    let mut scanner: RequestScanner<128> = RequestScanner::new();
+    let finished: Vec<bool> = request.iter().map(|&byte| scanner.feed(&[byte])).collect();
+    assert_eq!(finished.iter().filter(|&&done| done).count(), 1);
+    assert!(finished[request.len() - 1]);
+    assert_eq!(scanner.offer(), Some(default_params()));
+}
+
+#[test]
+fn test_request_scanner_without_offer() {
+    let mut scanner: RequestScanner<32> = RequestScanner::new();
+    let long_line = format!("User-Agent: {}\r\n", "x".repeat(100));
+    assert!(!scanner.feed(b"GET / HTTP/1.1\r\n"));
+    assert!(!scanner.feed(long_line.as_bytes()));
+    assert!(scanner.feed(b"Sec-WebSocket-Extensions: x-webkit-deflate-frame\r\n\r\n"));
+    assert_eq!(scanner.offer(), None);
+}
+
+#[test]
+fn test_response_scanner() {
+    let head = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n";
+    let (first, second) = head.split_at(40);
+    let mut scanner = ResponseScanner::new();
+    assert_eq!(scanner.feed(first), None);
+    assert!(!scanner.is_finished());
+    assert_eq!(scanner.feed(second), Some(second.len() - 2));
+    assert!(scanner.is_upgrade());
+    // Danach folgende Frames werden nicht mehr untersucht
+    assert_eq!(scanner.feed(b"\r\n\r\n"), None);
+
+    let mut scanner = ResponseScanner::new();
+    assert_eq!(
+        scanner.feed(b"HTTP/1.1 503 Service Unavailable\r\nRetry-After: 5\r\n\r\nbody"),
+        None
+    );
+    assert!(scanner.is_finished());
+    assert!(!scanner.is_upgrade());
+}
+
+#[test]
+fn test_compress_rfc_example() {
+    let mut deflater: Deflater<256> = Deflater::new(default_params());
+    let mut out = [0u8; 32];
+    // Nicht kleiner als das Original: unkomprimiert senden, ohne Wörterbuch-Eintrag
+    assert_eq!(deflater.compress(b"Hello", &mut out), None);
+    let len = deflater.compress_any(b"Hello", &mut out).unwrap();
+    assert_eq!(&out[..len], &[0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00]);
+    // Wiederholung verweist ins Wörterbuch
+    let len = deflater.compress(b"Hello", &mut out).unwrap();
+    assert!(len < 5);
+    let stream = [
+        &[
+            0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00, 0x00, 0x00, 0xff, 0xff,
+        ][..],
+        &out[..len],
+    ]
+    .concat();
+    assert_eq!(inflate(&stream), b"HelloHello");
+}
+
+#[test]
+fn test_inflate_all_block_types() {
+    // Stored-Block aus RFC 7692 Abschnitt 7.2.3.3
+    assert_eq!(
+        inflate(&[
+            0x00, 0x05, 0x00, 0xfa, 0xff, 0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x00
+        ]),
+        b"Hello"
+    );
+    assert_eq!(
+        inflate(&FIXED_BLOCK),
+        br#"{"type":"set_color","color":"Blau","transition_ms":1500}"#
+    );
+    assert_eq!(inflate(&DYNAMIC_BLOCK), STATUS.repeat(3));
+}
+
+#[test]
+fn test_inflate_errors() {
+    let mut out = [0u8; 1024];
+    assert_eq!(
+        inflate_message(&DYNAMIC_BLOCK[..20], &mut out),
+        Err(InflateError::Truncated)
+    );
+    assert_eq!(
+        inflate_message(&FIXED_BLOCK, &mut out[..16]),
+        Err(InflateError::OutputFull)
+    );
+    // BTYPE = 11 ist reserviert
+    assert_eq!(
+        inflate_message(&[0x07], &mut out),
+        Err(InflateError::InvalidBlock)
+    );
+}
+
+#[test]
+fn test_context_takeover_shrinks_repeated_status() {
+    let mut deflater: Deflater<512> = Deflater::new(default_params());
+    let mut first = [0u8; 256];
+    let first_len = deflater.compress(STATUS, &mut first).unwrap();
+    let mut second = [0u8; 256];
+    let second_len = deflater.compress(STATUS, &mut second).unwrap();
+    assert!(second_len * 4 < first_len, "{second_len} vs {first_len}");
+
+    // Der Browser entpackt den Strom über beide Nachrichten hinweg
+    let stream = [
+        &first[..first_len],
+        &[0x00, 0x00, 0xff, 0xff],
+        &second[..second_len],
+    ]
+    .concat();
+    assert_eq!(inflate(&stream), STATUS.repeat(2));
+}
+
+#[test]
+fn test_no_context_takeover_compresses_independently() {
+    let params =
+        negotiate_permessage_deflate("permessage-deflate; server_no_context_takeover").unwrap();
+    let mut deflater: Deflater<512> = Deflater::new(params);
+    let mut out = [0u8; 256];
+    let first_len = deflater.compress(STATUS, &mut out).unwrap();
+    let second_len = deflater.compress(STATUS, &mut out).unwrap();
+    assert_eq!(first_len, second_len);
+    assert_eq!(inflate(&out[..second_len]), STATUS);
+}
+
+#[test]
+fn test_roundtrip_small_window() {
+    let params =
+        negotiate_permessage_deflate("permessage-deflate; server_max_window_bits=8").unwrap();
+    let mut deflater: Deflater<64> = Deflater::new(params);
+    let message = STATUS.repeat(4);
+    let mut out = [0u8; 512];
+    let len = deflater.compress(&message, &mut out).unwrap();
+    assert_eq!(inflate(&out[..len]), message);
+
+    // Ausgabe zu klein: unkomprimiert senden, Wörterbuch bleibt unverändert
+    assert_eq!(deflater.compress(&message, &mut out[..8]), None);
+}
+
+#[test]
+fn test_frame_header_roundtrip() {
+    for payload_len in [0, 125, 126, 65_535, 65_536] {
+        for mask in [None, Some([1, 2, 3, 4])] {
+            let header = FrameHeader {
+                fin: true,
+                rsv1: true,
+                opcode: OPCODE_TEXT,
+                mask,
+                payload_len,
+                header_len: 0,
+            };
+            let mut out = [0u8; 14];
+            let len = write_frame_header(&mut out, &header);
+            let parsed = parse_frame_header(&out[..len]).unwrap();
+            assert_eq!(
+                parsed,
+                FrameHeader {
+                    header_len: len,
+                    ..header
+                }
+            );
+            assert!(parsed.is_data());
+            // Unvollständiger Kopf
+            assert_eq!(parse_frame_header(&out[..len - 1]), None);
+        }
+    }
+}
+
+#[test]
+fn test_control_frames_and_mask() {
+    // Ping mit leerer Nutzlast
+    let ping = parse_frame_header(&[0x89, 0x00]).unwrap();
+    assert!(!ping.is_data());
+    assert!(!ping.rsv1);
+
+    let mut payload = *b"Hello";
+    apply_mask(&mut payload, [0x37, 0xfa, 0x21, 0x3d]);
+    assert_eq!(payload, [0x7f, 0x9f, 0x4d, 0x51, 0x58]);
+    apply_mask(&mut payload, [0x37, 0xfa, 0x21, 0x3d]);
+    assert_eq!(&payload, b"Hello");
+}
@@ -0,0 +1,380 @@
+//! Integration Tests für das WebSocket-Protokoll (JSON → LedCommand / WsServerMessage)
+//!
+//! Diese Tests laufen auf dem Host (x86_64)
+
+use esp_core::ws::{WsRequest, WsServerMessage, is_valid_auth, parse_ws_message};
+use esp_core::{ClockFormat, ColorId, Language, LedColorMessage, LedCommand};
+use rgb::RGB8;
+use serde::{Deserialize, Serialize};
+
+const BRIGHTNESS: u8 = 10;
+
+/// Zeitplan-Eintrag wie ScheduleEntryDto, nur die Felder für die Tests
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+struct Entry {
+    hour: u8,
+    minute: u8,
+}
+
+/// Heap-Telemetrie wie HeapInfo, nur die Felder für die Tests
+#[derive(Serialize)]
+struct Heap {
+    used: u32,
+    free: u32,
+}
+
+/// Antwort wie WsServerMessage der Firmware, Zeitplan als Slice
+type Reply<'a> = WsServerMessage<&'a [Entry], Heap>;
+
+fn parse(json: &str) -> WsRequest<Entry> {
+    parse_ws_message(json.as_bytes(), BRIGHTNESS)
+}
+
+fn command(json: &str) -> LedCommand {
+    match parse(json) {
+        WsRequest::Command(command) => command,
+        _ => panic!("no command for {json}"),
+    }
+}
+
+fn error(json: &str) -> &'static str {
+    match parse(json) {
+        WsRequest::Error(message) => message,
+        _ => panic!("no error for {json}"),
+    }
+}
+
+/// Serialisiert eine Antwort wie `send_*` in der Firmware
+fn reply_json(reply: &Reply) -> String {
+    let mut buffer = [0u8; 256];
+    let n = serde_json_core::to_slice(reply, &mut buffer).expect("reply fits");
+    String::from_utf8(buffer[..n].to_vec()).unwrap()
+}
+
+/// Fehlerantwort auf eine Client-Nachricht
+fn error_reply(json: &str) -> String {
+    reply_json(&WsServerMessage::Error {
+        message: error(json),
+    })
+}
+
+fn is_ignored(json: &str) -> bool {
+    matches!(parse(json), WsRequest::Ignored)
+}
+
+#[test]
+fn test_set_color() {
+    assert!(matches!(
+        command(r#"{"type":"set_color","color":"Blau"}"#),
+        LedCommand::SetColor {
+            target_color: RGB8 {
+                r: 0,
+                g: 0,
+                b: BRIGHTNESS
+            },
+            color_id: ColorId::Blue,
+        }
+    ));
+    // Englische Namen werden ebenfalls akzeptiert
+    assert!(matches!(
+        command(r#"{"type":"set_color","color":"Red"}"#),
+        LedCommand::SetColor {
+            color_id: ColorId::Red,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_set_color_with_transition() {
+    assert!(matches!(
+        command(r#"{"type":"set_color","color":"Grün","transition_ms":1500}"#),
+        LedCommand::FadeTo {
+            color_id: ColorId::Green,
+            transition_ms: 1500,
+            ..
+        }
+    ));
+    // 0 = sofort
+    assert!(matches!(
+        command(r#"{"type":"set_color","color":"Grün","transition_ms":0}"#),
+        LedCommand::SetColor { .. }
+    ));
+}
+
+#[test]
+fn test_set_color_without_selectable_color_is_ignored() {
+    assert!(is_ignored(r#"{"type":"set_color"}"#));
+    assert!(is_ignored(r#"{"type":"set_color","color":"Aus"}"#));
+    assert!(is_ignored(
+        r#"{"type":"set_color","color":"Benutzerdefiniert"}"#
+    ));
+    assert!(is_ignored(r#"{"type":"set_color","color":"Unbekannt"}"#));
+}
+
+#[test]
+fn test_set_mode() {
+    assert!(matches!(
+        command(r#"{"type":"set_mode","mode":"auto"}"#),
+        LedCommand::EnableAuto
+    ));
+    assert!(matches!(
+        command(r#"{"type":"set_mode","mode":"random"}"#),
+        LedCommand::EnableRandom
+    ));
+    assert!(is_ignored(r#"{"type":"set_mode","mode":"manual"}"#));
+    assert!(is_ignored(r#"{"type":"set_mode"}"#));
+}
+
+#[test]
+fn test_sleep_timer() {
+    assert!(matches!(
+        command(r#"{"type":"sleep_timer","minutes":30}"#),
+        LedCommand::SleepTimer { minutes: 30 }
+    ));
+    assert!(matches!(
+        command(r#"{"type":"sleep_timer","minutes":0}"#),
+        LedCommand::SleepTimer { minutes: 0 }
+    ));
+    assert!(is_ignored(r#"{"type":"sleep_timer"}"#));
+}
+
+#[test]
+fn test_apply_preset() {
+    assert!(matches!(
+        command(r#"{"type":"apply_preset","id":2}"#),
+        LedCommand::ApplyPreset { id: 2 }
+    ));
+    assert_eq!(error(r#"{"type":"apply_preset"}"#), "Missing id");
+}
+
+#[test]
+fn test_commands_without_arguments() {
+    assert!(matches!(
+        command(r#"{"type":"calibrate"}"#),
+        LedCommand::Calibrate
+    ));
+    assert!(matches!(
+        command(r#"{"type":"test_pattern"}"#),
+        LedCommand::TestPattern
+    ));
+}
+
+#[test]
+fn test_scroll_text() {
+    match command(r#"{"type":"scroll_text","text":"Hallo Welt"}"#) {
+        LedCommand::ScrollText { text } => assert_eq!(text.as_str(), "Hallo Welt"),
+        _ => panic!("expected ScrollText"),
+    }
+
+    assert_eq!(error(r#"{"type":"scroll_text"}"#), "Invalid text");
+    assert_eq!(error(r#"{"type":"scroll_text","text":""}"#), "Invalid text");
+    // Länger als TEXT_LEN (32 Bytes)
+    let too_long = format!(r#"{{"type":"scroll_text","text":"{}"}}"#, "A".repeat(33));
+    assert_eq!(error(&too_long), "Invalid text");
+}
+
+#[test]
+fn test_show_clock() {
+    assert!(matches!(
+        command(r#"{"type":"show_clock"}"#),
+        LedCommand::ShowClock { format: None }
+    ));
+    assert!(matches!(
+        command(r#"{"type":"show_clock","hours":12}"#),
+        LedCommand::ShowClock {
+            format: Some(ClockFormat::H12)
+        }
+    ));
+    assert!(matches!(
+        command(r#"{"type":"show_clock","hours":24}"#),
+        LedCommand::ShowClock {
+            format: Some(ClockFormat::H24)
+        }
+    ));
+    assert_eq!(
+        error(r#"{"type":"show_clock","hours":13}"#),
+        "Invalid hours"
+    );
+}
+
+#[test]
+fn test_set_sequence() {
+    match command(r##"{"type":"set_sequence","colors":"#FF8000 rot"}"##) {
+        LedCommand::SetSequence {
+            sequence: Some(sequence),
+        } => assert_eq!(
+            sequence.colors(),
+            &[RGB8::new(255, 128, 0), RGB8::new(10, 0, 0)][..]
+        ),
+        _ => panic!("expected SetSequence"),
+    }
+
+    // Ohne Farben: klassische Folge
+    for json in [
+        r#"{"type":"set_sequence"}"#,
+        r#"{"type":"set_sequence","colors":"  "}"#,
+    ] {
+        assert!(matches!(
+            command(json),
+            LedCommand::SetSequence { sequence: None }
+        ));
+    }
+
+    assert_eq!(
+        error(r##"{"type":"set_sequence","colors":"#FF80 rot"}"##),
+        "Invalid color sequence"
+    );
+}
+
+#[test]
+fn test_schedule_messages() {
+    assert!(matches!(
+        parse(r#"{"type":"schedule_get"}"#),
+        WsRequest::ScheduleGet
+    ));
+
+    match parse(r#"{"type":"schedule_add","entry":{"hour":7,"minute":30}}"#) {
+        WsRequest::ScheduleAdd(entry) => assert_eq!(
+            entry,
+            Entry {
+                hour: 7,
+                minute: 30
+            }
+        ),
+        _ => panic!("expected ScheduleAdd"),
+    }
+    assert_eq!(error(r#"{"type":"schedule_add"}"#), "Missing entry");
+
+    assert!(matches!(
+        parse(r#"{"type":"schedule_remove","id":3}"#),
+        WsRequest::ScheduleRemove(3)
+    ));
+    assert_eq!(error(r#"{"type":"schedule_remove"}"#), "Missing id");
+}
+
+#[test]
+fn test_auth_after_login_is_ignored() {
+    assert!(is_ignored(r#"{"type":"auth","token":"geheim"}"#));
+}
+
+#[test]
+fn test_auth_message() {
+    assert!(is_valid_auth(
+        br#"{"type":"auth","token":"geheim"}"#,
+        "geheim"
+    ));
+    assert!(!is_valid_auth(
+        br#"{"type":"auth","token":"falsch"}"#,
+        "geheim"
+    ));
+    assert!(!is_valid_auth(br#"{"type":"auth"}"#, "geheim"));
+    // Andere Nachricht statt auth
+    assert!(!is_valid_auth(
+        br#"{"type":"set_color","token":"geheim"}"#,
+        "geheim"
+    ));
+    assert!(!is_valid_auth(b"geheim", "geheim"));
+}
+
+#[test]
+fn test_malformed_input() {
+    for json in [
+        "",
+        "not json",
+        "{",
+        r#"{"color":"Blau"}"#,
+        r#"{"type":"unknown"}"#,
+        r#"{"type":"set_color","color":"Lila"}"#,
+        r#"{"type":"apply_preset","id":256}"#,
+        r#"{"type":"sleep_timer","minutes":-1}"#,
+        r#"{"type":"schedule_add","entry":{"hour":7}}"#,
+        r#"{"type":42}"#,
+    ] {
+        assert_eq!(error(json), "JSON parse error", "input: {json}");
+    }
+}
+
+#[test]
+fn test_error_replies() {
+    assert_eq!(
+        error_reply("not json"),
+        r#"{"type":"error","message":"JSON parse error"}"#
+    );
+    assert_eq!(
+        error_reply(r#"{"type":"schedule_add"}"#),
+        r#"{"type":"error","message":"Missing entry"}"#
+    );
+    assert_eq!(
+        error_reply(r#"{"type":"schedule_remove"}"#),
+        r#"{"type":"error","message":"Missing id"}"#
+    );
+}
+
+#[test]
+fn test_status_reply() {
+    let manual = LedColorMessage::from_color(RGB8::new(0, 0, BRIGHTNESS), false).stamped(7, 1200);
+    assert_eq!(
+        reply_json(&WsServerMessage::status(&manual, Language::German)),
+        r#"{"type":"status","color":"Blau","label":"Blau","rgb":{"r":0,"g":0,"b":10},"timestamp_ms":1200,"mode":"manual","seq":7}"#
+    );
+
+    let auto = LedColorMessage::from_color(RGB8::new(BRIGHTNESS, 0, 0), true).stamped(8, 2200);
+    assert_eq!(
+        reply_json(&WsServerMessage::status(&auto, Language::English)),
+        r#"{"type":"status","color":"Rot","label":"Red","rgb":{"r":10,"g":0,"b":0},"timestamp_ms":2200,"mode":"auto","seq":8}"#
+    );
+}
+
+#[test]
+fn test_schedule_replies() {
+    // schedule_get → aktueller Zeitplan
+    assert!(matches!(
+        parse(r#"{"type":"schedule_get"}"#),
+        WsRequest::ScheduleGet
+    ));
+    assert_eq!(
+        reply_json(&WsServerMessage::Schedule { entries: &[] }),
+        r#"{"type":"schedule","entries":[]}"#
+    );
+
+    // schedule_add → Zeitplan mit dem neuen Eintrag
+    let WsRequest::ScheduleAdd(entry) =
+        parse(r#"{"type":"schedule_add","entry":{"hour":7,"minute":30}}"#)
+    else {
+        panic!("no schedule_add");
+    };
+    let entries = [entry];
+    assert_eq!(
+        reply_json(&WsServerMessage::Schedule { entries: &entries }),
+        r#"{"type":"schedule","entries":[{"hour":7,"minute":30}]}"#
+    );
+}
+
+#[test]
+fn test_busy_hello_heap_replies() {
+    assert_eq!(
+        reply_json(&WsServerMessage::Busy {
+            message: "LED busy, try again",
+            category: "resource",
+            kind: "busy",
+            dropped: 3,
+        }),
+        r#"{"type":"busy","message":"LED busy, try again","category":"resource","kind":"busy","dropped":3}"#
+    );
+    assert_eq!(
+        reply_json(&WsServerMessage::Hello {
+            version: "1.0.0",
+            git_hash: "abc1234",
+            build_timestamp: 1_760_000_000,
+        }),
+        r#"{"type":"hello","version":"1.0.0","git_hash":"abc1234","build_timestamp":1760000000}"#
+    );
+    assert_eq!(
+        reply_json(&WsServerMessage::Heap(Heap {
+            used: 41230,
+            free: 61170,
+        })),
+        r#"{"type":"heap","used":41230,"free":61170}"#
+    );
+}
@@ -0,0 +1,10 @@
+//! BLE: Manufacturer-Data-Payload
+
+#![no_main]
+
+use esp_core::decode_ble_payload;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_ble_payload(data);
+});
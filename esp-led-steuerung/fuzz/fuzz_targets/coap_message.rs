@@ -0,0 +1,10 @@
+//! CoAP: komplettes UDP-Datagramm
+
+#![no_main]
+
+use esp_core::decode_coap_message;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_coap_message(data);
+});
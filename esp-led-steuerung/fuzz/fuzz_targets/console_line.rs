@@ -0,0 +1,12 @@
+//! Debug-Konsole: eine Eingabezeile
+
+#![no_main]
+
+use esp_core::parse_console_command;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(line) = core::str::from_utf8(data) {
+        let _ = parse_console_command(line);
+    }
+});
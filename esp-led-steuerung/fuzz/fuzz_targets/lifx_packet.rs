@@ -0,0 +1,18 @@
+//! LIFX LAN: UDP-Paket dekodieren, Antwort bilden und kodieren
+
+#![no_main]
+
+use esp_core::lifx::MAX_RESPONSE_LEN;
+use esp_core::{LifxLight, decode_lifx_packet, encode_lifx_response, lifx_response};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok((header, request)) = decode_lifx_packet(data) else {
+        return;
+    };
+    let light = LifxLight::default();
+    if let Some(response) = lifx_response(&header, &request, &light, "ESP32-C6 LED") {
+        let mut out = [0u8; MAX_RESPONSE_LEN];
+        let _ = encode_lifx_response(&mut out, &header, [0x02, 0, 0, 0, 0, 1], &response);
+    }
+});
@@ -0,0 +1,31 @@
+//! MQTT-Kommandos: Topic + Payload wie im MQTT-Task
+//!
+//! Eingabe-Format: `<topic>\n<payload>` (ohne `\n` ist alles Payload)
+
+#![no_main]
+
+use esp_core::{parse_tasmota_command, parse_text_command};
+use esp_core::tasmota::tasmota_command_name;
+use libfuzzer_sys::fuzz_target;
+
+/// Geräte-Topic für den Tasmota-Modus
+const DEVICE_TOPIC: &str = "led";
+
+fuzz_target!(|data: &[u8]| {
+    let (topic, payload) = match data.iter().position(|&b| b == b'\n') {
+        Some(split) => (&data[..split], &data[split + 1..]),
+        None => (&[][..], data),
+    };
+    let topic = core::str::from_utf8(topic).unwrap_or("");
+    // Wie im MQTT-Task: ungültiges UTF-8 wird zu ""
+    let text = core::str::from_utf8(payload).unwrap_or("");
+
+    match tasmota_command_name(topic, DEVICE_TOPIC) {
+        Some(name) => {
+            let _ = parse_tasmota_command(name, text);
+        }
+        None => {
+            let _ = parse_text_command(text);
+        }
+    }
+});
@@ -0,0 +1,10 @@
+//! MQTT-SN: komplettes UDP-Datagramm
+
+#![no_main]
+
+use esp_core::decode_mqtt_sn;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_mqtt_sn(data);
+});
@@ -0,0 +1,12 @@
+//! OSC: UDP-Datagramm dekodieren und auf ein LED-Kommando abbilden
+
+#![no_main]
+
+use esp_core::{decode_osc_message, osc_led_command};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = decode_osc_message(data) {
+        let _ = osc_led_command(&message);
+    }
+});
@@ -0,0 +1,10 @@
+//! UDP-Realtime (WLED-kompatibel): Paket dekodieren
+
+#![no_main]
+
+use esp_core::decode_realtime_packet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_realtime_packet(data);
+});
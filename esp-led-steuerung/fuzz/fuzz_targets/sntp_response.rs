@@ -0,0 +1,10 @@
+//! SNTP: Antwort-Paket des Zeitservers
+
+#![no_main]
+
+use esp_core::sntp::parse_sntp_response;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_sntp_response(data);
+});
@@ -0,0 +1,11 @@
+//! SSDP: M-SEARCH-Anfrage aus einem Multicast-Datagramm
+
+#![no_main]
+
+use esp_core::{DeviceUuid, parse_m_search};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let uuid = DeviceUuid::from_mac([0x02, 0, 0, 0, 0, 1]);
+    let _ = parse_m_search(data, &uuid);
+});
@@ -0,0 +1,18 @@
+//! Flash-Record + Zeitplan: beschädigte Flash-Inhalte beim Start
+
+#![no_main]
+
+use esp_core::{RecordKind, Schedule, decode_record};
+use libfuzzer_sys::fuzz_target;
+
+/// Kapazität wie in der Firmware (SCHEDULE_CAPACITY)
+const SCHEDULE_CAPACITY: usize = 8;
+
+fuzz_target!(|data: &[u8]| {
+    // Mit Header (Magic, Länge, CRC32) ...
+    if let Some(payload) = decode_record(data, RecordKind::Schedule) {
+        let _ = Schedule::<SCHEDULE_CAPACITY>::decode(payload);
+    }
+    // ... und ohne Header (mit Header erreicht der Fuzzer den Decoder kaum, der CRC passt selten)
+    let _ = Schedule::<SCHEDULE_CAPACITY>::decode(data);
+});
@@ -0,0 +1,20 @@
+//! WebSocket: Frame-Header und permessage-deflate-Nutzdaten
+
+#![no_main]
+
+use esp_core::ws_deflate::{
+    MAX_FRAME_HEADER_LEN, inflate_message, negotiate_permessage_deflate, parse_frame_header,
+};
+use libfuzzer_sys::fuzz_target;
+
+/// Wie der Empfangspuffer der Firmware (WEBSOCKET_BUFFER_SIZE + Header)
+const RX_FRAME_SIZE: usize = 512 + MAX_FRAME_HEADER_LEN;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_frame_header(data);
+    let mut out = [0u8; RX_FRAME_SIZE];
+    let _ = inflate_message(data, &mut out);
+    if let Ok(header) = core::str::from_utf8(data) {
+        let _ = negotiate_permessage_deflate(header);
+    }
+});
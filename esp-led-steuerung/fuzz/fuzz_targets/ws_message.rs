@@ -0,0 +1,38 @@
+//! WebSocket: JSON-Nachricht eines Clients (Auth + Anfrage)
+
+#![no_main]
+
+use esp_core::ws::{is_valid_auth, parse_ws_message};
+use libfuzzer_sys::fuzz_target;
+use serde::Deserialize;
+
+/// Zeitplan-Eintrag mit den Feldern von `ScheduleEntryDto` der Firmware
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Entry<'a> {
+    #[serde(default)]
+    id: u8,
+    days: u8,
+    hour: u8,
+    minute: u8,
+    action: &'a str,
+    #[serde(default)]
+    rgb: Option<Rgb>,
+    #[serde(default)]
+    duration_min: Option<u8>,
+    #[serde(default)]
+    enabled: Option<bool>,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Rgb {
+    r: u8,
+    g: u8,
+    b: u8,
+}
+
+fuzz_target!(|data: &[u8]| {
+    let _ = is_valid_auth(data, "geheim");
+    let _ = parse_ws_message::<Entry>(data, 128);
+});